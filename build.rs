@@ -63,6 +63,14 @@ fn main() {
         "cargo:rustc-env=SEARCH_PROVIDER_OBJECT_PATH={}",
         search_provider_object_path()
     );
+    println!(
+        "cargo:rustc-env=SYNC_DAEMON_BUS_NAME={}",
+        sync_daemon_bus_name()
+    );
+    println!(
+        "cargo:rustc-env=SYNC_DAEMON_OBJECT_PATH={}",
+        sync_daemon_object_path()
+    );
 
     export_dependency_versions();
     write_window_ui();
@@ -1321,6 +1329,11 @@ Icon={app_id}
 Terminal=false
 Categories=System;Security;
 StartupNotify=true
+Actions=synchronize;
+
+[Desktop Action synchronize]
+Name=Synchronize
+Exec={project} --sync
 "
     );
     fs::write(dir.join(format!("{project}.desktop")), contents)
@@ -1388,3 +1401,11 @@ fn search_provider_bus_name() -> String {
 fn search_provider_object_path() -> String {
     format!("/{}", search_provider_bus_name().replace('.', "/"))
 }
+
+fn sync_daemon_bus_name() -> String {
+    format!("{}.SyncDaemon", app_id().replace('-', "_"))
+}
+
+fn sync_daemon_object_path() -> String {
+    format!("/{}", sync_daemon_bus_name().replace('.', "/"))
+}
@@ -0,0 +1,227 @@
+use super::store_backend::store_backend;
+use super::{
+    delete_password_entry, move_password_folder, read_password_entry, save_password_entry,
+    PasswordEntryError, PasswordEntryWriteError,
+};
+use crate::password::entry_files::{
+    label_from_password_entry_relative_path, normalize_password_entry_label,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+const ATTACHMENT_HEADER: &str = "keycord-attachment-v1";
+const ATTACHMENT_NAME_KEY: &str = "name: ";
+const ATTACHMENT_FOLDER_SUFFIX: &str = ".attachments";
+const ATTACHMENT_LINE_WIDTH: usize = 76;
+pub const MAX_ATTACHMENT_BYTES: usize = 1024 * 1024;
+
+pub fn attachment_folder_label(entry_label: &str) -> String {
+    let entry_label = normalize_password_entry_label(entry_label);
+    match entry_label.rsplit_once('/') {
+        Some((dir, name)) => format!("{dir}/.{name}{ATTACHMENT_FOLDER_SUFFIX}"),
+        None => format!(".{entry_label}{ATTACHMENT_FOLDER_SUFFIX}"),
+    }
+}
+
+fn attachment_label(entry_label: &str, file_name: &str) -> Result<String, PasswordEntryWriteError> {
+    let file_name = file_name.trim();
+    if file_name.is_empty()
+        || file_name.starts_with('.')
+        || file_name.contains(['/', '\\'])
+        || file_name.chars().any(char::is_control)
+    {
+        return Err(PasswordEntryWriteError::other(
+            "That attachment name is not allowed.",
+        ));
+    }
+
+    Ok(format!(
+        "{}/{file_name}",
+        attachment_folder_label(entry_label)
+    ))
+}
+
+fn encode_attachment(file_name: &str, bytes: &[u8]) -> Result<String, PasswordEntryWriteError> {
+    if bytes.len() > MAX_ATTACHMENT_BYTES {
+        return Err(PasswordEntryWriteError::other(
+            "Attachments must be 1 MiB or smaller.",
+        ));
+    }
+
+    let encoded = Zeroizing::new(BASE64.encode(bytes));
+    let mut contents = format!("{ATTACHMENT_HEADER}\n{ATTACHMENT_NAME_KEY}{file_name}\n");
+    for chunk in encoded.as_bytes().chunks(ATTACHMENT_LINE_WIDTH) {
+        contents.push('\n');
+        contents.push_str(&String::from_utf8_lossy(chunk));
+    }
+    Ok(contents)
+}
+
+fn decode_attachment(contents: &str) -> Result<Vec<u8>, PasswordEntryError> {
+    let mut lines = contents.lines();
+    if lines.next().map(str::trim) != Some(ATTACHMENT_HEADER) {
+        return Err(PasswordEntryError::other("This item is not an attachment."));
+    }
+
+    let encoded = Zeroizing::new(
+        lines
+            .filter(|line| !line.starts_with(ATTACHMENT_NAME_KEY))
+            .map(str::trim)
+            .collect::<String>(),
+    );
+    BASE64
+        .decode(encoded.as_bytes())
+        .map_err(|err| PasswordEntryError::other(format!("Invalid attachment data: {err}")))
+}
+
+fn attachment_folder_path(store_root: &str, entry_label: &str) -> PathBuf {
+    Path::new(store_root).join(attachment_folder_label(entry_label))
+}
+
+pub fn list_password_attachments(store_root: &str, entry_label: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(attachment_folder_path(store_root, entry_label)) else {
+        return Vec::new();
+    };
+
+    let mut names = entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_file()))
+        .filter_map(|entry| label_from_password_entry_relative_path(Path::new(&entry.file_name())))
+        .filter(|name| !name.is_empty() && !name.starts_with('.'))
+        .collect::<Vec<_>>();
+    names.sort();
+    names.dedup();
+    names
+}
+
+pub fn save_password_attachment(
+    store_root: &str,
+    entry_label: &str,
+    file_name: &str,
+    bytes: &[u8],
+) -> Result<(), PasswordEntryWriteError> {
+    let label = attachment_label(entry_label, file_name)?;
+    let contents = Zeroizing::new(encode_attachment(file_name.trim(), bytes)?);
+    save_password_entry(store_root, &label, &contents, true)
+}
+
+pub fn read_password_attachment(
+    store_root: &str,
+    entry_label: &str,
+    file_name: &str,
+) -> Result<Vec<u8>, PasswordEntryError> {
+    let label = attachment_label(entry_label, file_name)
+        .map_err(|err| PasswordEntryError::other(err.to_string()))?;
+    let contents = Zeroizing::new(read_password_entry(store_root, &label)?);
+    decode_attachment(&contents)
+}
+
+pub fn delete_password_attachment(
+    store_root: &str,
+    entry_label: &str,
+    file_name: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    let label = attachment_label(entry_label, file_name)?;
+    delete_password_entry(store_root, &label)
+}
+
+// Attachments go with their item, so a later item with the old name doesn't
+// pick them up. The caller already holds the store lock.
+pub(super) fn move_password_attachments(
+    store_root: &str,
+    old_label: &str,
+    new_label: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    if !attachment_folder_path(store_root, old_label).is_dir() {
+        return Ok(());
+    }
+    move_password_folder(
+        store_root,
+        &attachment_folder_label(old_label),
+        &attachment_folder_label(new_label),
+    )
+}
+
+pub(super) fn delete_password_attachments(
+    store_root: &str,
+    entry_label: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    for name in list_password_attachments(store_root, entry_label) {
+        let label = attachment_label(entry_label, &name)?;
+        store_backend().delete_password_entry(store_root, &label)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        attachment_folder_label, attachment_label, decode_attachment, encode_attachment,
+        list_password_attachments, MAX_ATTACHMENT_BYTES,
+    };
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn attachments_live_in_a_hidden_folder_next_to_the_entry() {
+        assert_eq!(
+            attachment_folder_label("work/github"),
+            "work/.github.attachments"
+        );
+        assert_eq!(attachment_folder_label("github"), ".github.attachments");
+        assert_eq!(
+            attachment_label("work/github", "codes.pdf").expect("valid attachment label"),
+            "work/.github.attachments/codes.pdf"
+        );
+    }
+
+    #[test]
+    fn attachment_names_must_be_plain_file_names() {
+        assert!(attachment_label("github", "").is_err());
+        assert!(attachment_label("github", ".hidden").is_err());
+        assert!(attachment_label("github", "../escape").is_err());
+        assert!(attachment_label("github", r"nested\name").is_err());
+    }
+
+    #[test]
+    fn attachment_contents_round_trip_binary_bytes() {
+        let bytes = (0..=255).cycle().take(500).collect::<Vec<u8>>();
+        let contents = encode_attachment("key.bin", &bytes).expect("encode attachment");
+
+        assert!(contents.starts_with("keycord-attachment-v1\nname: key.bin\n"));
+        assert!(contents.lines().all(|line| line.len() <= 76));
+        assert_eq!(
+            decode_attachment(&contents).expect("decode attachment"),
+            bytes
+        );
+    }
+
+    #[test]
+    fn oversized_attachments_and_plain_entries_are_rejected() {
+        assert!(encode_attachment("big.bin", &vec![0; MAX_ATTACHMENT_BYTES + 1]).is_err());
+        assert!(decode_attachment("secret\nusername: alice").is_err());
+    }
+
+    #[test]
+    fn attachment_listing_reads_entry_files_from_the_attachment_folder() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("keycord-attachments-{nanos}"));
+        let folder = store.join("work").join(".github.attachments");
+        fs::create_dir_all(&folder).expect("create attachment folder");
+        fs::write(folder.join("codes.pdf.gpg"), b"x").expect("write attachment");
+        fs::write(folder.join("key.bin.keycord"), b"x").expect("write attachment");
+        fs::write(folder.join("notes.txt"), b"x").expect("write stray file");
+
+        assert_eq!(
+            list_password_attachments(&store.to_string_lossy(), "work/github"),
+            vec!["codes.pdf".to_string(), "key.bin".to_string()]
+        );
+
+        let _ = fs::remove_dir_all(store);
+    }
+}
@@ -1,7 +1,14 @@
 use crate::logging::{run_command_output, run_command_with_input, CommandLogOptions};
 use crate::preferences::Preferences;
 use crate::support::runtime::require_host_command_features;
+use std::collections::HashMap;
 use std::process::{Command, Output};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+
+// Long enough to type a passphrase into pinentry, short enough that a
+// prompt nobody can see doesn't block the store forever.
+const PASS_CLI_TIMEOUT: Duration = Duration::from_secs(120);
 
 fn command_error(output: &Output, prefix: &str) -> String {
     let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -12,8 +19,13 @@ fn command_error(output: &Output, prefix: &str) -> String {
     }
 }
 
-fn store_command(store_root: &str) -> Command {
-    Preferences::new().command_with_envs(&[("PASSWORD_STORE_DIR", store_root)])
+fn store_call_lock(store_root: &str) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    locks.entry(store_root.to_string()).or_default().clone()
 }
 
 #[cfg(target_os = "linux")]
@@ -21,30 +33,78 @@ fn host_program_command(program: &str, args: &[&str]) -> Command {
     Preferences::new().host_program_command(program, args)
 }
 
-pub(super) fn run_store_command_output(
-    store_root: &str,
-    action: &str,
-    log_options: CommandLogOptions,
-    configure: impl FnOnce(&mut Command),
-) -> Result<Output, String> {
-    require_host_command_features()?;
-    let mut cmd = store_command(store_root);
-    configure(&mut cmd);
-    run_command_output(&mut cmd, action, log_options)
-        .map_err(|err| format!("Failed to run the host backend command: {err}"))
+// The configured `pass` command for one store. Calls that change the store
+// run one at a time, and a call that hangs is stopped after its timeout.
+// Reads are queued per key by the gpg queue instead, so they can run side by
+// side once the key is unlocked. Arguments go straight to the process, never
+// through a shell.
+pub(super) struct PassCli<'a> {
+    store_root: &'a str,
+    timeout: Duration,
+    serialized: bool,
 }
 
-pub(super) fn run_store_command_with_input(
-    store_root: &str,
-    action: &str,
-    input: &str,
-    log_options: CommandLogOptions,
-    configure: impl FnOnce(&mut Command),
-) -> Result<Output, String> {
-    require_host_command_features()?;
-    let mut cmd = store_command(store_root);
-    configure(&mut cmd);
-    run_command_with_input(&mut cmd, action, input, log_options)
+impl<'a> PassCli<'a> {
+    pub(super) const fn new(store_root: &'a str) -> Self {
+        Self {
+            store_root,
+            timeout: PASS_CLI_TIMEOUT,
+            serialized: true,
+        }
+    }
+
+    pub(super) const fn for_read(mut self) -> Self {
+        self.serialized = false;
+        self
+    }
+
+    pub(super) const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn command(&self, configure: impl FnOnce(&mut Command)) -> Result<Command, String> {
+        require_host_command_features()?;
+        let mut cmd =
+            Preferences::new().command_with_envs(&[("PASSWORD_STORE_DIR", self.store_root)]);
+        configure(&mut cmd);
+        Ok(cmd)
+    }
+
+    const fn log_options(&self, log_options: CommandLogOptions) -> CommandLogOptions {
+        CommandLogOptions {
+            timeout: Some(self.timeout),
+            ..log_options
+        }
+    }
+
+    pub(super) fn output(
+        &self,
+        action: &str,
+        log_options: CommandLogOptions,
+        configure: impl FnOnce(&mut Command),
+    ) -> Result<Output, String> {
+        let mut cmd = self.command(configure)?;
+        let lock = store_call_lock(self.store_root);
+        let _guard = self
+            .serialized
+            .then(|| lock.lock().unwrap_or_else(PoisonError::into_inner));
+        run_command_output(&mut cmd, action, self.log_options(log_options))
+            .map_err(|err| format!("Failed to run the host backend command: {err}"))
+    }
+
+    pub(super) fn output_with_input(
+        &self,
+        action: &str,
+        input: &str,
+        log_options: CommandLogOptions,
+        configure: impl FnOnce(&mut Command),
+    ) -> Result<Output, String> {
+        let mut cmd = self.command(configure)?;
+        let lock = store_call_lock(self.store_root);
+        let _guard = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        run_command_with_input(&mut cmd, action, input, self.log_options(log_options))
+    }
 }
 
 #[cfg(target_os = "linux")]
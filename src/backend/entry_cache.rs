@@ -0,0 +1,278 @@
+use crate::logging::log_error;
+use crate::support::background::spawn_worker;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+const ENTRY_CACHE_CAPACITY: usize = 8;
+const SEARCH_ENTRY_CACHE_CAPACITY: usize = 256;
+const ENTRY_CACHE_TTL: Duration = Duration::from_secs(60);
+const ENTRY_CACHE_PURGE_INTERVAL: Duration = Duration::from_secs(5);
+
+struct CachedEntry {
+    store: String,
+    label: String,
+    contents: Zeroizing<String>,
+    cached_at: Instant,
+    write_generation: u64,
+}
+
+impl CachedEntry {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.cached_at) >= ENTRY_CACHE_TTL
+    }
+
+    fn is_fresh(&self, now: Instant, write_generation: u64) -> bool {
+        self.write_generation == write_generation && !self.is_expired(now)
+    }
+}
+
+// Most recently used last. The contents are wiped when an entry is evicted,
+// expires, or the cache is cleared. A worker drops expired entries while
+// anything is cached, so they don't wait for the next lookup.
+static ENTRY_CACHE: Mutex<Vec<CachedEntry>> = Mutex::new(Vec::new());
+// Searching in contents reads every item again for each query, so it keeps
+// its own, larger pool with the same expiry rules. It is also cleared when
+// content search is turned off or the search closes.
+static SEARCH_ENTRY_CACHE: Mutex<Vec<CachedEntry>> = Mutex::new(Vec::new());
+
+fn cached_entry_in(
+    entries: &mut Vec<CachedEntry>,
+    store: &str,
+    label: &str,
+    now: Instant,
+    write_generation: u64,
+) -> Option<Zeroizing<String>> {
+    entries.retain(|entry| entry.is_fresh(now, write_generation));
+    let index = entries
+        .iter()
+        .position(|entry| entry.store == store && entry.label == label)?;
+    let entry = entries.remove(index);
+    let contents = entry.contents.clone();
+    entries.push(entry);
+    Some(contents)
+}
+
+fn cache_entry_in(entries: &mut Vec<CachedEntry>, entry: CachedEntry, capacity: usize) {
+    entries.retain(|cached| cached.store != entry.store || cached.label != entry.label);
+    if entries.len() >= capacity {
+        entries.remove(0);
+    }
+    entries.push(entry);
+}
+
+fn cached_entry(
+    cache: &Mutex<Vec<CachedEntry>>,
+    store: &str,
+    label: &str,
+    write_generation: u64,
+) -> Option<Zeroizing<String>> {
+    let mut entries = cache
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    cached_entry_in(&mut entries, store, label, Instant::now(), write_generation)
+}
+
+static PURGE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+fn purge_expired_entries(now: Instant) -> bool {
+    let mut empty = true;
+    for cache in [&ENTRY_CACHE, &SEARCH_ENTRY_CACHE] {
+        let mut entries = cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entries.retain(|entry| !entry.is_expired(now));
+        empty &= entries.is_empty();
+    }
+    empty
+}
+
+// Runs until both caches are empty. An entry cached while the worker stops
+// either sees it still running or starts a new one.
+fn schedule_cache_purge() {
+    if PURGE_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let spawned = spawn_worker("entry-cache-purge", || loop {
+        thread::sleep(ENTRY_CACHE_PURGE_INTERVAL);
+        if !purge_expired_entries(Instant::now()) {
+            continue;
+        }
+        PURGE_SCHEDULED.store(false, Ordering::SeqCst);
+        if purge_expired_entries(Instant::now()) || PURGE_SCHEDULED.swap(true, Ordering::SeqCst) {
+            break;
+        }
+    });
+    if let Err(err) = spawned {
+        PURGE_SCHEDULED.store(false, Ordering::SeqCst);
+        log_error(format!("Failed to start the entry cache purge: {err}"));
+    }
+}
+
+fn cache_entry(
+    cache: &Mutex<Vec<CachedEntry>>,
+    capacity: usize,
+    store: &str,
+    label: &str,
+    contents: &str,
+    write_generation: u64,
+) {
+    let mut entries = cache
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    cache_entry_in(
+        &mut entries,
+        CachedEntry {
+            store: store.to_string(),
+            label: label.to_string(),
+            contents: Zeroizing::new(contents.to_string()),
+            cached_at: Instant::now(),
+            write_generation,
+        },
+        capacity,
+    );
+    drop(entries);
+    schedule_cache_purge();
+}
+
+// Only valid while no store write finished in between, so an item that was
+// saved, moved, or re-encrypted is always decrypted again.
+pub(super) fn cached_password_entry(
+    store: &str,
+    label: &str,
+    write_generation: u64,
+) -> Option<Zeroizing<String>> {
+    cached_entry(&ENTRY_CACHE, store, label, write_generation)
+}
+
+// The generation is the one seen before decrypting, so a write that lands
+// while gpg runs keeps the old contents out of the cache.
+pub(super) fn cache_password_entry(
+    store: &str,
+    label: &str,
+    contents: &str,
+    write_generation: u64,
+) {
+    cache_entry(
+        &ENTRY_CACHE,
+        ENTRY_CACHE_CAPACITY,
+        store,
+        label,
+        contents,
+        write_generation,
+    );
+}
+
+pub(super) fn cached_search_entry(
+    store: &str,
+    label: &str,
+    write_generation: u64,
+) -> Option<Zeroizing<String>> {
+    cached_password_entry(store, label, write_generation)
+        .or_else(|| cached_entry(&SEARCH_ENTRY_CACHE, store, label, write_generation))
+}
+
+pub(super) fn cache_search_entry(store: &str, label: &str, contents: &str, write_generation: u64) {
+    cache_entry(
+        &SEARCH_ENTRY_CACHE,
+        SEARCH_ENTRY_CACHE_CAPACITY,
+        store,
+        label,
+        contents,
+        write_generation,
+    );
+}
+
+pub(super) fn clear_search_entry_cache() {
+    SEARCH_ENTRY_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clear();
+}
+
+pub(super) fn forget_cached_store_entries(store: &str) {
+    for cache in [&ENTRY_CACHE, &SEARCH_ENTRY_CACHE] {
+        cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .retain(|entry| entry.store != store);
+    }
+}
+
+pub(super) fn clear_password_entry_cache() {
+    for cache in [&ENTRY_CACHE, &SEARCH_ENTRY_CACHE] {
+        cache
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cache_entry_in, cached_entry_in, CachedEntry, ENTRY_CACHE_CAPACITY, ENTRY_CACHE_TTL,
+    };
+    use std::time::Instant;
+    use zeroize::Zeroizing;
+
+    fn cached(label: &str, cached_at: Instant) -> CachedEntry {
+        CachedEntry {
+            store: "/stores/work".to_string(),
+            label: label.to_string(),
+            contents: Zeroizing::new(format!("{label}-secret")),
+            cached_at,
+            write_generation: 1,
+        }
+    }
+
+    #[test]
+    fn cached_entries_expire_on_writes_and_after_the_ttl() {
+        let now = Instant::now();
+        let mut entries = vec![cached("mail", now)];
+        assert_eq!(
+            cached_entry_in(&mut entries, "/stores/work", "mail", now, 1)
+                .as_deref()
+                .map(String::as_str),
+            Some("mail-secret")
+        );
+        assert_eq!(
+            cached_entry_in(
+                &mut entries,
+                "/stores/work",
+                "mail",
+                now + ENTRY_CACHE_TTL,
+                1
+            ),
+            None
+        );
+
+        let mut entries = vec![cached("mail", now)];
+        assert_eq!(
+            cached_entry_in(&mut entries, "/stores/work", "mail", now, 2),
+            None
+        );
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn the_least_recently_used_entry_is_evicted_first() {
+        let now = Instant::now();
+        let mut entries = Vec::new();
+        for index in 0..ENTRY_CACHE_CAPACITY {
+            cache_entry_in(
+                &mut entries,
+                cached(&format!("item{index}"), now),
+                ENTRY_CACHE_CAPACITY,
+            );
+        }
+        assert!(cached_entry_in(&mut entries, "/stores/work", "item0", now, 1).is_some());
+
+        cache_entry_in(&mut entries, cached("new", now), ENTRY_CACHE_CAPACITY);
+        assert_eq!(entries.len(), ENTRY_CACHE_CAPACITY);
+        assert!(cached_entry_in(&mut entries, "/stores/work", "item0", now, 1).is_some());
+        assert!(cached_entry_in(&mut entries, "/stores/work", "item1", now, 1).is_none());
+    }
+}
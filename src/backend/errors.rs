@@ -1,5 +1,18 @@
+use crate::support::process::COMMAND_TIMEOUT_MESSAGE;
 use thiserror::Error;
 
+const READ_ONLY_STORE_TOAST: &str = "This store is read-only.";
+const COMMAND_TIMEOUT_TOAST: &str = "pass took too long and was stopped.";
+pub(crate) const STORE_BUSY_TOAST: &str = "Another app is changing this store. Try again.";
+
+fn read_only_store_message(store_root: &str) -> String {
+    format!("The store '{store_root}' is read-only.")
+}
+
+pub(super) fn store_busy_message(store_root: &str) -> String {
+    format!("The store '{store_root}' is locked by another write.")
+}
+
 fn save_toast_message_for_fido2_store_message(message: &str) -> Option<&'static str> {
     if message.contains("Enter the FIDO2 security key PIN.") {
         Some("Enter the FIDO2 security key PIN.")
@@ -22,6 +35,12 @@ fn save_toast_message_for_fido2_store_message(message: &str) -> Option<&'static
     }
 }
 
+fn toast_message_for_command_timeout(message: &str) -> Option<&'static str> {
+    message
+        .contains(COMMAND_TIMEOUT_MESSAGE)
+        .then_some(COMMAND_TIMEOUT_TOAST)
+}
+
 fn import_toast_message_for_private_key_other(message: &str) -> Option<&'static str> {
     if message.contains("Connect only one FIDO2 security key before continuing.") {
         Some("Unplug the other security keys, then try again.")
@@ -61,10 +80,11 @@ impl PasswordEntryError {
         Self::Other(message.into())
     }
 
-    pub const fn toast_message(&self) -> Option<&'static str> {
+    pub fn toast_message(&self) -> Option<&'static str> {
         match self {
             Self::MissingPrivateKey(_) => Some("Add a private key in Preferences."),
             Self::IncompatiblePrivateKey(_) => Some("This key can't open your items."),
+            Self::Other(message) => toast_message_for_command_timeout(message),
             _ => None,
         }
     }
@@ -83,9 +103,29 @@ pub enum PasswordEntryWriteError {
     #[error("{0}")]
     IncompatiblePrivateKey(String),
     #[error("{0}")]
+    ReadOnlyStore(String),
+    #[error("{0}")]
+    StoreBusy(String),
+    #[error("{0}")]
     Other(String),
 }
 
+// A write that first reads the item fails the way the read did, so a locked
+// key still asks to be unlocked.
+impl From<PasswordEntryError> for PasswordEntryWriteError {
+    fn from(err: PasswordEntryError) -> Self {
+        match err {
+            PasswordEntryError::EntryNotFound(message) => Self::EntryNotFound(message),
+            PasswordEntryError::MissingPrivateKey(message) => Self::MissingPrivateKey(message),
+            PasswordEntryError::LockedPrivateKey(message) => Self::LockedPrivateKey(message),
+            PasswordEntryError::IncompatiblePrivateKey(message) => {
+                Self::IncompatiblePrivateKey(message)
+            }
+            PasswordEntryError::Other(message) => Self::Other(message),
+        }
+    }
+}
+
 impl PasswordEntryWriteError {
     pub fn already_exists(message: impl Into<String>) -> Self {
         Self::EntryAlreadyExists(message.into())
@@ -95,6 +135,14 @@ impl PasswordEntryWriteError {
         Self::EntryNotFound(message.into())
     }
 
+    pub fn read_only_store(store_root: &str) -> Self {
+        Self::ReadOnlyStore(read_only_store_message(store_root))
+    }
+
+    pub fn store_busy(store_root: &str) -> Self {
+        Self::StoreBusy(store_busy_message(store_root))
+    }
+
     pub fn other(message: impl Into<String>) -> Self {
         Self::Other(message.into())
     }
@@ -105,7 +153,10 @@ impl PasswordEntryWriteError {
             Self::MissingPrivateKey(_) => "Add a private key in Preferences.",
             Self::LockedPrivateKey(_) => "Unlock the key in Preferences.",
             Self::IncompatiblePrivateKey(_) => "This key can't open your items.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
             Self::Other(message) => save_toast_message_for_fido2_store_message(message)
+                .or_else(|| toast_message_for_command_timeout(message))
                 .unwrap_or("Couldn't save changes."),
             Self::EntryNotFound(_) => "Couldn't save changes.",
         }
@@ -115,6 +166,8 @@ impl PasswordEntryWriteError {
         match self {
             Self::EntryAlreadyExists(_) => "An item with that name already exists.",
             Self::EntryNotFound(_) => "That item no longer exists.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
             Self::MissingPrivateKey(_)
             | Self::LockedPrivateKey(_)
             | Self::IncompatiblePrivateKey(_)
@@ -125,6 +178,8 @@ impl PasswordEntryWriteError {
     pub const fn delete_toast_message(&self) -> &'static str {
         match self {
             Self::EntryNotFound(_) => "That item no longer exists.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
             Self::EntryAlreadyExists(_)
             | Self::MissingPrivateKey(_)
             | Self::LockedPrivateKey(_)
@@ -132,6 +187,32 @@ impl PasswordEntryWriteError {
             | Self::Other(_) => "Couldn't delete the item.",
         }
     }
+
+    pub const fn create_folder_toast_message(&self) -> &'static str {
+        match self {
+            Self::EntryAlreadyExists(_) => "That folder already exists.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
+            Self::EntryNotFound(_)
+            | Self::MissingPrivateKey(_)
+            | Self::LockedPrivateKey(_)
+            | Self::IncompatiblePrivateKey(_)
+            | Self::Other(_) => "Couldn't create the folder.",
+        }
+    }
+
+    pub const fn rename_folder_toast_message(&self) -> &'static str {
+        match self {
+            Self::EntryAlreadyExists(_) => "That folder already exists.",
+            Self::EntryNotFound(_) => "That folder no longer exists.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
+            Self::MissingPrivateKey(_)
+            | Self::LockedPrivateKey(_)
+            | Self::IncompatiblePrivateKey(_)
+            | Self::Other(_) => "Couldn't rename the folder.",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Error)]
@@ -145,6 +226,12 @@ pub enum StoreRecipientsError {
     #[error("{0}")]
     IncompatiblePrivateKey(String),
     #[error("{0}")]
+    ReadOnlyStore(String),
+    #[error("{0}")]
+    StoreBusy(String),
+    #[error("{0}")]
+    PartialCheckout(String),
+    #[error("{0}")]
     Other(String),
 }
 
@@ -153,6 +240,20 @@ impl StoreRecipientsError {
         Self::InvalidStorePath(message.into())
     }
 
+    pub fn read_only_store(store_root: &str) -> Self {
+        Self::ReadOnlyStore(read_only_store_message(store_root))
+    }
+
+    pub fn store_busy(store_root: &str) -> Self {
+        Self::StoreBusy(store_busy_message(store_root))
+    }
+
+    pub fn partial_checkout(store_root: &str) -> Self {
+        Self::PartialCheckout(format!(
+            "Only some folders of '{store_root}' are checked out."
+        ))
+    }
+
     pub fn other(message: impl Into<String>) -> Self {
         Self::Other(message.into())
     }
@@ -160,9 +261,12 @@ impl StoreRecipientsError {
     pub fn toast_message(&self, fallback: &'static str) -> &'static str {
         match self {
             Self::InvalidStorePath(_) => "The selected store path is not a folder.",
+            Self::PartialCheckout(_) => "Check out all folders before changing keys.",
             Self::MissingPrivateKey(_) => "Add a private key in Preferences.",
             Self::LockedPrivateKey(_) => "Unlock the key in Preferences.",
             Self::IncompatiblePrivateKey(_) => "This key can't open your items.",
+            Self::ReadOnlyStore(_) => READ_ONLY_STORE_TOAST,
+            Self::StoreBusy(_) => STORE_BUSY_TOAST,
             Self::Other(message) => {
                 save_toast_message_for_fido2_store_message(message).unwrap_or(fallback)
             }
@@ -457,6 +561,7 @@ impl PrivateKeyError {
 #[cfg(test)]
 mod tests {
     use super::{PasswordEntryWriteError, PrivateKeyError, StoreRecipientsError};
+    use crate::support::process::COMMAND_TIMEOUT_MESSAGE;
 
     #[test]
     fn write_errors_map_to_user_toasts() {
@@ -476,6 +581,26 @@ mod tests {
             .save_toast_message(),
             "Touch the FIDO2 security key and try again."
         );
+        assert_eq!(
+            PasswordEntryWriteError::Other(format!(
+                "Failed to run the host backend command: {COMMAND_TIMEOUT_MESSAGE} (120s)"
+            ))
+            .save_toast_message(),
+            "pass took too long and was stopped."
+        );
+        let read_only = PasswordEntryWriteError::read_only_store("/stores/team");
+        assert_eq!(read_only.save_toast_message(), "This store is read-only.");
+        assert_eq!(read_only.rename_toast_message(), "This store is read-only.");
+        assert_eq!(read_only.delete_toast_message(), "This store is read-only.");
+        let busy = PasswordEntryWriteError::store_busy("/stores/team");
+        assert_eq!(
+            busy.save_toast_message(),
+            "Another app is changing this store. Try again."
+        );
+        assert_eq!(
+            busy.delete_toast_message(),
+            "Another app is changing this store. Try again."
+        );
     }
 
     #[test]
@@ -0,0 +1,127 @@
+use super::entry_cache::forget_cached_store_entries;
+use crate::logging::log_debug;
+use adw::glib;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StoreEvent {
+    Added {
+        store: String,
+        label: String,
+    },
+    Changed {
+        store: String,
+        label: String,
+    },
+    Removed {
+        store: String,
+        label: String,
+    },
+    Renamed {
+        store: String,
+        from: String,
+        to: String,
+    },
+    Synced {
+        store: String,
+    },
+    // Files changed on disk, as seen by the folders the list watches. This
+    // is how `pass`, `git pull` in a terminal, or a file sync client show up.
+    ChangedOutside {
+        store: String,
+    },
+    Decrypted {
+        store: String,
+        label: String,
+    },
+}
+
+impl StoreEvent {
+    pub fn store(&self) -> &str {
+        match self {
+            Self::Added { store, .. }
+            | Self::Changed { store, .. }
+            | Self::Removed { store, .. }
+            | Self::Renamed { store, .. }
+            | Self::Synced { store }
+            | Self::ChangedOutside { store }
+            | Self::Decrypted { store, .. } => store,
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Self::Added { store, label } => format!("Added {label} to {store}"),
+            Self::Changed { store, label } => format!("Changed {label} in {store}"),
+            Self::Removed { store, label } => format!("Removed {label} from {store}"),
+            Self::Renamed { store, from, to } => format!("Renamed {from} to {to} in {store}"),
+            Self::Synced { store } => format!("Synced {store}"),
+            Self::ChangedOutside { store } => format!("Changed {store} outside Keycord"),
+            Self::Decrypted { store, label } => format!("Decrypted {label} from {store}"),
+        }
+    }
+}
+
+type StoreEventHandler = Rc<dyn Fn(&StoreEvent)>;
+
+thread_local! {
+    static STORE_EVENT_HANDLERS: RefCell<Vec<StoreEventHandler>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+// Handlers live on the main thread for as long as the process runs, so
+// features can keep their own state in step without scanning the stores.
+pub fn subscribe_store_events(handler: impl Fn(&StoreEvent) + 'static) {
+    STORE_EVENT_HANDLERS.with(|handlers| handlers.borrow_mut().push(Rc::new(handler)));
+}
+
+// The handlers are copied out first, so one of them can subscribe
+// without tripping over the borrow.
+fn deliver_store_event(event: &StoreEvent) {
+    let handlers =
+        STORE_EVENT_HANDLERS.with(|handlers| handlers.borrow().iter().cloned().collect::<Vec<_>>());
+    for handler in handlers {
+        handler(event);
+    }
+}
+
+pub fn log_store_events() {
+    subscribe_store_events(|event| log_debug(format!("Store event: {}", event.description())));
+}
+
+// Store work mostly runs on worker threads, so events are handed to the
+// main loop and reach the handlers there, in the order they were sent.
+pub fn publish_store_event(event: StoreEvent) {
+    if !matches!(event, StoreEvent::Decrypted { .. }) {
+        forget_cached_store_entries(event.store());
+    }
+    glib::MainContext::default().invoke(move || deliver_store_event(&event));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deliver_store_event, subscribe_store_events, StoreEvent};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn store_events_reach_every_subscriber_in_order() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        for name in ["first", "second"] {
+            let seen = seen.clone();
+            subscribe_store_events(move |event| {
+                seen.borrow_mut().push(format!("{name} {}", event.store()));
+            });
+        }
+
+        deliver_store_event(&StoreEvent::Synced {
+            store: "/stores/work".to_string(),
+        });
+
+        assert_eq!(
+            *seen.borrow(),
+            vec!["first /stores/work", "second /stores/work"]
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use crate::logging::log_error;
+use crate::support::secure_fs::ensure_private_dir;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, OnceLock, PoisonError};
+
+const GPG_QUEUE_LOCKS_DIR_NAME: &str = "keycord-gpg";
+
+struct GpgQueue {
+    busy: Mutex<HashSet<String>>,
+    turn_ended: Condvar,
+}
+
+fn gpg_queue() -> &'static GpgQueue {
+    static QUEUE: OnceLock<GpgQueue> = OnceLock::new();
+    QUEUE.get_or_init(|| GpgQueue {
+        busy: Mutex::new(HashSet::new()),
+        turn_ended: Condvar::new(),
+    })
+}
+
+// While held, no other gpg operation that may ask for the same key's
+// passphrase runs, in this process or in another Keycord process such as
+// the search provider. A parallel turn holds nothing.
+pub(super) struct GpgTurn {
+    key: Option<String>,
+    _file: Option<File>,
+}
+
+impl GpgTurn {
+    const fn parallel() -> Self {
+        Self {
+            key: None,
+            _file: None,
+        }
+    }
+}
+
+impl Drop for GpgTurn {
+    fn drop(&mut self) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let queue = gpg_queue();
+        queue
+            .busy
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key);
+        queue.turn_ended.notify_all();
+    }
+}
+
+fn gpg_queue_locks_dir() -> PathBuf {
+    dirs_next::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(GPG_QUEUE_LOCKS_DIR_NAME)
+}
+
+// Every process resolves the same key to the same file.
+fn gpg_queue_lock_path_in(dir: &Path, key: &str) -> PathBuf {
+    let digest = Sha256::digest(key.as_bytes());
+    let mut name = String::with_capacity(digest.len() * 2 + 5);
+    for byte in digest {
+        let _ = write!(&mut name, "{byte:02x}");
+    }
+    name.push_str(".lock");
+    dir.join(name)
+}
+
+// A lock file that can't be set up is logged and skipped, so reading still
+// works, just without the queue across processes.
+fn lock_gpg_queue_file(path: &Path) -> Option<File> {
+    let file = path
+        .parent()
+        .map_or(Ok(()), ensure_private_dir)
+        .and_then(|()| {
+            OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(path)
+        })
+        .and_then(|file| file.lock().map(|()| file));
+    match file {
+        Ok(file) => Some(file),
+        Err(err) => {
+            log_error(format!(
+                "Failed to lock the gpg queue '{}': {err}",
+                path.display()
+            ));
+            None
+        }
+    }
+}
+
+fn wait_for_gpg_key_turn_in(dir: &Path, key: &str) -> GpgTurn {
+    let queue = gpg_queue();
+    let mut busy = queue.busy.lock().unwrap_or_else(PoisonError::into_inner);
+    while busy.contains(key) {
+        busy = queue
+            .turn_ended
+            .wait(busy)
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+    busy.insert(key.to_string());
+    drop(busy);
+
+    GpgTurn {
+        key: Some(key.to_string()),
+        _file: lock_gpg_queue_file(&gpg_queue_lock_path_in(dir, key)),
+    }
+}
+
+fn wait_for_gpg_turn_in(dir: &Path, key: &str, key_is_unlocked: impl Fn() -> bool) -> GpgTurn {
+    if key_is_unlocked() {
+        return GpgTurn::parallel();
+    }
+    let turn = wait_for_gpg_key_turn_in(dir, key);
+    // The turn before this one may have unlocked the key. Then the rest of
+    // the queue goes through together instead of one by one.
+    if key_is_unlocked() {
+        return GpgTurn::parallel();
+    }
+    turn
+}
+
+// Operations that need the same key would otherwise start a pinentry prompt
+// each. Queued, the first one unlocks the key in gpg-agent and the rest go
+// through without asking again. Operations for other keys, and any
+// operation whose key is already unlocked, don't wait.
+pub(super) fn wait_for_gpg_turn(key: &str, key_is_unlocked: impl Fn() -> bool) -> GpgTurn {
+    wait_for_gpg_turn_in(&gpg_queue_locks_dir(), key, key_is_unlocked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wait_for_gpg_turn_in;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    fn queue_dir() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        std::env::temp_dir().join(format!("keycord-gpg-queue-{nanos}"))
+    }
+
+    #[test]
+    fn gpg_operations_for_the_same_locked_key_take_turns() {
+        let dir = queue_dir();
+        let busy = Arc::new(AtomicBool::new(false));
+
+        let workers = (0..4)
+            .map(|_| {
+                let dir = dir.clone();
+                let busy = busy.clone();
+                thread::spawn(move || {
+                    let _turn = wait_for_gpg_turn_in(&dir, "locked", || false);
+                    assert!(!busy.swap(true, Ordering::SeqCst));
+                    thread::sleep(Duration::from_millis(5));
+                    busy.store(false, Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().expect("worker should not panic");
+        }
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn gpg_operations_for_other_or_unlocked_keys_run_together() {
+        let dir = queue_dir();
+        let running = Arc::new(AtomicUsize::new(0));
+        let overlapped = Arc::new(AtomicBool::new(false));
+
+        let workers = [("first", false), ("second", false), ("unlocked", true)]
+            .into_iter()
+            .chain([("unlocked", true)])
+            .map(|(key, unlocked)| {
+                let dir = dir.clone();
+                let running = running.clone();
+                let overlapped = overlapped.clone();
+                thread::spawn(move || {
+                    let _turn = wait_for_gpg_turn_in(&dir, key, || unlocked);
+                    if running.fetch_add(1, Ordering::SeqCst) > 0 {
+                        overlapped.store(true, Ordering::SeqCst);
+                    }
+                    thread::sleep(Duration::from_millis(50));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().expect("worker should not panic");
+        }
+
+        assert!(overlapped.load(Ordering::SeqCst));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn gpg_operations_behind_an_unlocking_turn_skip_the_queue() {
+        let dir = queue_dir();
+        let unlocked = Arc::new(AtomicBool::new(false));
+        let first = wait_for_gpg_turn_in(&dir, "key", || unlocked.load(Ordering::SeqCst));
+
+        let waiter = {
+            let dir = dir.clone();
+            let unlocked = unlocked.clone();
+            thread::spawn(move || {
+                let turn = wait_for_gpg_turn_in(&dir, "key", || unlocked.load(Ordering::SeqCst));
+                turn.key.is_none()
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+        unlocked.store(true, Ordering::SeqCst);
+        drop(first);
+
+        assert!(waiter.join().expect("waiter should not panic"));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -1,3 +1,5 @@
+use super::entry_cache::cached_password_entry;
+use super::gpg_queue::{wait_for_gpg_turn, GpgTurn};
 use super::host_errors::{
     ensure_host_command_success, password_entry_error_from_host_failure,
     password_entry_error_from_host_launch, password_entry_write_error_from_host_failure,
@@ -11,20 +13,25 @@ use crate::backend::command::{
     ensure_success, run_host_program_output, run_host_program_with_input,
 };
 use crate::backend::{
-    command::{run_store_command_output, run_store_command_with_input},
-    PasswordEntryError, PasswordEntryWriteError, StoreRecipients, StoreRecipientsError,
-    StoreRecipientsPrivateKeyRequirement,
+    command::PassCli, store_write_generation, PasswordEntryError, PasswordEntryWriteError,
+    StoreRecipients, StoreRecipientsError, StoreRecipientsPrivateKeyRequirement,
 };
 #[cfg(all(target_os = "linux", feature = "audit"))]
 use crate::logging::log_error;
 use crate::logging::CommandLogOptions;
-use crate::support::git::{ensure_store_git_repository, has_git_repository};
+use crate::support::git::{
+    ensure_store_git_repository, has_git_repository, squash_store_git_commits, store_git_head_oid,
+};
 #[cfg(all(target_os = "linux", feature = "audit"))]
 use sequoia_openpgp::{cert::CertParser, parse::Parse, Cert};
-#[cfg(all(target_os = "linux", feature = "audit"))]
-use std::collections::HashSet;
+#[cfg(target_os = "linux")]
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::process::{Command, Output};
+#[cfg(target_os = "linux")]
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::Duration;
+use zeroize::{Zeroize, Zeroizing};
 
 #[cfg(target_os = "linux")]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -44,10 +51,16 @@ impl HostGpgPrivateKeySummary {
     }
 }
 
+// `pass init` re-encrypts every item below the folder, which takes a while
+// in a large store.
+const PASS_INIT_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
 fn read_entry_output(store_root: &str, label: &str, action: &str) -> Result<Output, String> {
-    run_store_command_output(store_root, action, CommandLogOptions::SENSITIVE, |cmd| {
-        configure_pass_show_command(cmd, label);
-    })
+    PassCli::new(store_root)
+        .for_read()
+        .output(action, CommandLogOptions::SENSITIVE, |cmd| {
+            configure_pass_show_command(cmd, label);
+        })
 }
 
 fn append_pass_entry_args<'a>(cmd: &mut Command, labels: impl IntoIterator<Item = &'a str>) {
@@ -83,6 +96,15 @@ fn configure_pass_init_command(cmd: &mut Command, recipients: &[String]) {
     append_pass_entry_args(cmd, recipients.iter().map(String::as_str));
 }
 
+fn configure_pass_folder_init_command(
+    cmd: &mut Command,
+    relative_dir: &str,
+    recipients: &[String],
+) {
+    cmd.arg("init").arg(format!("--path={relative_dir}"));
+    append_pass_entry_args(cmd, recipients.iter().map(String::as_str));
+}
+
 fn ensure_valid_entry_label(label: &str) -> Result<(), String> {
     validated_entry_label_path(label).map(|_| ())
 }
@@ -112,6 +134,43 @@ fn store_recipients_error_from_internal_message(
     }
 }
 
+// The recipients in the nearest `.gpg-id`, the file `pass` encrypts an item
+// to, stand in for the key that opens it.
+fn host_gpg_recipients(store_root: &str, relative_dir: &Path) -> Vec<String> {
+    let root = Path::new(store_root);
+    let mut dir = Some(relative_dir);
+    while let Some(current) = dir {
+        if let Ok(contents) = std::fs::read_to_string(root.join(current).join(".gpg-id")) {
+            let mut recipients = contents
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+                .collect::<Vec<_>>();
+            recipients.sort();
+            recipients.dedup();
+            return recipients;
+        }
+        dir = current.parent();
+    }
+    Vec::new()
+}
+
+fn wait_for_host_gpg_turn(store_root: &str, relative_dir: &Path) -> GpgTurn {
+    let recipients = host_gpg_recipients(store_root, relative_dir);
+    let key = if recipients.is_empty() {
+        store_root.to_string()
+    } else {
+        recipients.join("\n")
+    };
+    wait_for_gpg_turn(&key, || host_gpg_key_is_unlocked(&recipients))
+}
+
+fn entry_relative_dir(label: &str) -> &Path {
+    Path::new(label).parent().unwrap_or_else(|| Path::new(""))
+}
+
+#[cfg(test)]
 pub(super) fn read_password_entry(
     store_root: &str,
     label: &str,
@@ -125,11 +184,19 @@ pub(super) fn read_password_entry_with_progress(
 ) -> Result<String, PasswordEntryError> {
     validate_entry_label_for_read(label)?;
 
+    let _turn = wait_for_host_gpg_turn(store_root, entry_relative_dir(label));
+    // A read of the same item that waited behind this one's passphrase
+    // prompt takes its result instead of decrypting again.
+    if let Some(contents) = cached_password_entry(store_root, label, store_write_generation()) {
+        return Ok(contents.to_string());
+    }
     let output = read_entry_output(store_root, label, "Read password entry")
         .map_err(password_entry_error_from_host_launch)?;
-    let output = ensure_host_command_success(HostStoreAction::ReadEntry, output, "pass failed")
+    let mut output = ensure_host_command_success(HostStoreAction::ReadEntry, output, "pass failed")
         .map_err(password_entry_error_from_host_failure)?;
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    let contents = String::from_utf8_lossy(&output.stdout).to_string();
+    output.stdout.zeroize();
+    Ok(contents)
 }
 
 pub(super) fn read_password_line(
@@ -138,15 +205,18 @@ pub(super) fn read_password_line(
 ) -> Result<String, PasswordEntryError> {
     validate_entry_label_for_read(label)?;
 
+    let _turn = wait_for_host_gpg_turn(store_root, entry_relative_dir(label));
     let output = read_entry_output(store_root, label, "Read password entry for clipboard copy")
         .map_err(password_entry_error_from_host_launch)?;
-    let output = ensure_host_command_success(HostStoreAction::ReadLine, output, "pass failed")
+    let mut output = ensure_host_command_success(HostStoreAction::ReadLine, output, "pass failed")
         .map_err(password_entry_error_from_host_failure)?;
-    Ok(String::from_utf8_lossy(&output.stdout)
+    let password = String::from_utf8_lossy(&output.stdout)
         .lines()
         .next()
         .unwrap_or_default()
-        .to_string())
+        .to_string();
+    output.stdout.zeroize();
+    Ok(password)
 }
 
 pub(super) const fn password_entry_is_readable(_store_root: &str, _label: &str) -> bool {
@@ -174,22 +244,61 @@ pub(super) fn save_password_entry_with_progress(
 ) -> Result<(), PasswordEntryWriteError> {
     validate_entry_label_for_write(label)?;
 
-    let output = run_store_command_with_input(
-        store_root,
-        "Save password entry",
-        contents,
-        CommandLogOptions::SENSITIVE,
-        |cmd| {
-            configure_pass_insert_command(cmd, label, overwrite);
-        },
-    )
-    .map_err(password_entry_write_error_from_host_launch)?;
+    let output = PassCli::new(store_root)
+        .output_with_input(
+            "Save password entry",
+            contents,
+            CommandLogOptions::SENSITIVE,
+            |cmd| {
+                configure_pass_insert_command(cmd, label, overwrite);
+            },
+        )
+        .map_err(password_entry_write_error_from_host_launch)?;
 
     ensure_host_command_success(HostStoreAction::SaveEntry, output, "pass insert failed")
         .map(|_| ())
         .map_err(password_entry_write_error_from_host_failure)
 }
 
+// `pass insert` commits every entry on its own, so the commits are folded
+// into one afterwards and the store gets a single commit like it does with
+// the Integrated backend.
+pub(super) fn save_password_entries(
+    store_root: &str,
+    entries: &[(String, Zeroizing<String>)],
+    git_message: &str,
+) -> Vec<Result<(), PasswordEntryWriteError>> {
+    let base = if has_git_repository(store_root) {
+        store_git_head_oid(store_root).unwrap_or_else(|err| {
+            log_error(format!(
+                "Failed to read the Git HEAD of {store_root}: {err}"
+            ));
+            None
+        })
+    } else {
+        None
+    };
+    let results = entries
+        .iter()
+        .map(|(label, contents)| save_password_entry(store_root, label, contents, true))
+        .collect::<Vec<_>>();
+
+    if let Some(base) = base {
+        let paths = entries
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_ok())
+            .map(|((label, _), _)| format!("{label}.gpg"))
+            .collect::<Vec<_>>();
+        if let Err(err) = squash_store_git_commits(store_root, &base, git_message, &paths) {
+            log_error(format!(
+                "Failed to combine the commits for {store_root}: {err}"
+            ));
+        }
+    }
+    results
+}
+
 pub(super) fn rename_password_entry(
     store_root: &str,
     old_label: &str,
@@ -198,15 +307,36 @@ pub(super) fn rename_password_entry(
     validate_entry_label_for_write(old_label)?;
     validate_entry_label_for_write(new_label)?;
 
-    let output = run_store_command_output(
-        store_root,
-        "Rename password entry",
-        CommandLogOptions::DEFAULT,
-        |cmd| {
+    let output = PassCli::new(store_root)
+        .output("Rename password entry", CommandLogOptions::DEFAULT, |cmd| {
             configure_pass_move_command(cmd, old_label, new_label);
-        },
-    )
-    .map_err(password_entry_write_error_from_host_launch)?;
+        })
+        .map_err(password_entry_write_error_from_host_launch)?;
+
+    ensure_host_command_success(HostStoreAction::RenameEntry, output, "pass mv failed")
+        .map(|_| ())
+        .map_err(password_entry_write_error_from_host_failure)
+}
+
+// A trailing slash makes `pass mv` pick the folder even when an item with the
+// same name sits next to it.
+pub(super) fn rename_password_folder(
+    store_root: &str,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    validate_entry_label_for_write(old_folder)?;
+    validate_entry_label_for_write(new_folder)?;
+
+    let output = PassCli::new(store_root)
+        .output(
+            "Rename password folder",
+            CommandLogOptions::DEFAULT,
+            |cmd| {
+                configure_pass_move_command(cmd, &format!("{old_folder}/"), new_folder);
+            },
+        )
+        .map_err(password_entry_write_error_from_host_launch)?;
 
     ensure_host_command_success(HostStoreAction::RenameEntry, output, "pass mv failed")
         .map(|_| ())
@@ -219,15 +349,11 @@ pub(super) fn delete_password_entry(
 ) -> Result<(), PasswordEntryWriteError> {
     validate_entry_label_for_write(label)?;
 
-    let output = run_store_command_output(
-        store_root,
-        "Delete password entry",
-        CommandLogOptions::DEFAULT,
-        |cmd| {
+    let output = PassCli::new(store_root)
+        .output("Delete password entry", CommandLogOptions::DEFAULT, |cmd| {
             configure_pass_remove_command(cmd, label);
-        },
-    )
-    .map_err(password_entry_write_error_from_host_launch)?;
+        })
+        .map_err(password_entry_write_error_from_host_launch)?;
 
     ensure_host_command_success(HostStoreAction::DeleteEntry, output, "pass rm failed")
         .map(|_| ())
@@ -261,15 +387,17 @@ pub(super) fn save_store_recipients_with_progress(
 
     let should_initialize_git =
         !Path::new(store_root).join(".gpg-id").exists() && !has_git_repository(store_root);
-    let output = run_store_command_output(
-        store_root,
-        "Save password store recipients",
-        CommandLogOptions::DEFAULT,
-        |cmd| {
-            configure_pass_init_command(cmd, recipients.standard());
-        },
-    )
-    .map_err(store_recipients_error_from_host_launch)?;
+    let _turn = wait_for_host_gpg_turn(store_root, Path::new(""));
+    let output = PassCli::new(store_root)
+        .with_timeout(PASS_INIT_TIMEOUT)
+        .output(
+            "Save password store recipients",
+            CommandLogOptions::DEFAULT,
+            |cmd| {
+                configure_pass_init_command(cmd, recipients.standard());
+            },
+        )
+        .map_err(store_recipients_error_from_host_launch)?;
 
     ensure_host_command_success(HostStoreAction::SaveRecipients, output, "pass init failed")
         .map_err(store_recipients_error_from_host_failure)?;
@@ -295,9 +423,7 @@ pub(super) fn save_store_recipients_with_progress_for_relative_dir(
         );
     }
 
-    Err(StoreRecipientsError::other(
-        "Managing nested .gpg-id files requires the Integrated backend.",
-    ))
+    save_store_folder_recipients(store_root, &relative_dir, recipients)
 }
 
 pub(super) fn save_store_recipients_for_relative_dir(
@@ -311,9 +437,38 @@ pub(super) fn save_store_recipients_for_relative_dir(
         return save_store_recipients(store_root, recipients, private_key_requirement);
     }
 
-    Err(StoreRecipientsError::other(
-        "Managing nested .gpg-id files requires the Integrated backend.",
-    ))
+    save_store_folder_recipients(store_root, &relative_dir, recipients)
+}
+
+// `pass init --path` writes the folder's `.gpg-id`, re-encrypts the items
+// below it, and commits, the same as running it by hand.
+fn save_store_folder_recipients(
+    store_root: &str,
+    relative_dir: &Path,
+    recipients: &StoreRecipients,
+) -> Result<(), StoreRecipientsError> {
+    if !recipients.fido2().is_empty() {
+        return Err(StoreRecipientsError::other(
+            "FIDO2 recipients require the Integrated backend.",
+        ));
+    }
+
+    let _turn = wait_for_host_gpg_turn(store_root, relative_dir);
+    let relative_dir = relative_dir.to_string_lossy();
+    let output = PassCli::new(store_root)
+        .with_timeout(PASS_INIT_TIMEOUT)
+        .output(
+            "Save password folder recipients",
+            CommandLogOptions::DEFAULT,
+            |cmd| {
+                configure_pass_folder_init_command(cmd, &relative_dir, recipients.standard());
+            },
+        )
+        .map_err(store_recipients_error_from_host_launch)?;
+
+    ensure_host_command_success(HostStoreAction::SaveRecipients, output, "pass init failed")
+        .map(|_| ())
+        .map_err(store_recipients_error_from_host_failure)
 }
 
 pub(super) fn store_recipients_private_key_requiring_unlock(
@@ -337,9 +492,10 @@ pub(super) fn store_recipients_private_key_requiring_unlock_for_relative_dir(
 #[cfg(test)]
 mod validation_tests {
     use super::{
-        configure_pass_init_command, configure_pass_insert_command, configure_pass_move_command,
-        configure_pass_remove_command, configure_pass_show_command, delete_password_entry,
-        read_password_entry_with_progress, rename_password_entry, save_password_entry,
+        configure_pass_folder_init_command, configure_pass_init_command,
+        configure_pass_insert_command, configure_pass_move_command, configure_pass_remove_command,
+        configure_pass_show_command, delete_password_entry, read_password_entry_with_progress,
+        rename_password_entry, save_password_entry,
     };
     use crate::backend::{PasswordEntryError, PasswordEntryWriteError};
     use std::process::Command;
@@ -440,6 +596,19 @@ mod validation_tests {
             vec!["init", "--", "-recipient", "ABCD"]
         );
     }
+
+    #[test]
+    fn host_backend_folder_pass_init_uses_the_path_option() {
+        let mut init = Command::new("pass");
+        configure_pass_folder_init_command(&mut init, "team/ops", &[String::from("ABCD")]);
+
+        assert_eq!(
+            init.get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect::<Vec<_>>(),
+            vec!["init", "--path=team/ops", "--", "ABCD"]
+        );
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -461,6 +630,32 @@ pub fn list_host_gpg_private_keys() -> Result<Vec<HostGpgPrivateKeySummary>, Str
     )))
 }
 
+#[cfg(target_os = "linux")]
+pub fn host_gpg_available() -> bool {
+    run_host_program_output(
+        "gpg",
+        &["--version"],
+        "Check host GPG",
+        CommandLogOptions::DEFAULT,
+    )
+    .is_ok_and(|output| output.status.success())
+}
+
+// gpg exits with 2 when no secret key matches, which isn't worth an error log.
+#[cfg(target_os = "linux")]
+pub fn host_gpg_secret_key_available(recipient: &str) -> bool {
+    run_host_program_output(
+        "gpg",
+        &["--batch", "--list-secret-keys", "--", recipient],
+        "Check host GPG private key",
+        CommandLogOptions {
+            accepted_exit_codes: &[2],
+            ..CommandLogOptions::DEFAULT
+        },
+    )
+    .is_ok_and(|output| output.status.success())
+}
+
 #[cfg(all(target_os = "linux", feature = "audit"))]
 pub fn available_host_gpg_public_certs() -> Result<Vec<Cert>, String> {
     let output = run_host_program_output(
@@ -501,7 +696,9 @@ pub fn import_host_gpg_private_key_bytes(bytes: &[u8]) -> Result<(), String> {
         "Import host GPG private key",
         CommandLogOptions::SENSITIVE,
     )?;
-    ensure_success(output, "gpg --import failed").map(|_| ())
+    ensure_success(output, "gpg --import failed")?;
+    super::entry_cache::clear_password_entry_cache();
+    Ok(())
 }
 
 #[cfg(target_os = "linux")]
@@ -512,7 +709,121 @@ pub fn delete_host_gpg_private_key(fingerprint: &str) -> Result<(), String> {
         "Delete host GPG private key",
         CommandLogOptions::DEFAULT,
     )?;
-    ensure_success(output, "gpg --delete-secret-keys failed").map(|_| ())
+    ensure_success(output, "gpg --delete-secret-keys failed")?;
+    // Items the deleted key decrypted must not stay readable from the cache.
+    super::entry_cache::clear_password_entry_cache();
+    Ok(())
+}
+
+// Keygrips of the secret subkeys that can decrypt for these recipients. They
+// don't change while the keys stay the same, so each set is looked up once.
+#[cfg(target_os = "linux")]
+fn host_gpg_decryption_keygrips(recipients: &[String]) -> Vec<String> {
+    static KEYGRIPS: OnceLock<Mutex<HashMap<Vec<String>, Vec<String>>>> = OnceLock::new();
+    let keygrips = KEYGRIPS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(found) = keygrips
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(recipients)
+    {
+        return found.clone();
+    }
+
+    let mut args = vec![
+        "--batch",
+        "--with-colons",
+        "--with-keygrip",
+        "--list-secret-keys",
+        "--",
+    ];
+    args.extend(recipients.iter().map(String::as_str));
+    let found = run_host_program_output(
+        "gpg",
+        &args,
+        "Find host GPG keygrips",
+        CommandLogOptions {
+            accepted_exit_codes: &[2],
+            ..CommandLogOptions::DEFAULT
+        },
+    )
+    .ok()
+    .filter(|output| output.status.success())
+    .map(|output| parse_host_gpg_decryption_keygrips(&String::from_utf8_lossy(&output.stdout)))
+    .unwrap_or_default();
+    if !found.is_empty() {
+        keygrips
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(recipients.to_vec(), found.clone());
+    }
+    found
+}
+
+#[cfg(target_os = "linux")]
+fn parse_host_gpg_decryption_keygrips(output: &str) -> Vec<String> {
+    let mut keygrips = Vec::new();
+    let mut can_decrypt = false;
+    for line in output.lines() {
+        let fields = line.split(':').collect::<Vec<_>>();
+        match fields.first().copied() {
+            Some("sec" | "ssb") => {
+                can_decrypt = fields.get(11).is_some_and(|caps| caps.contains('e'));
+            }
+            Some("grp") if can_decrypt => {
+                if let Some(keygrip) = fields.get(9).filter(|keygrip| !keygrip.is_empty()) {
+                    keygrips.push((*keygrip).to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    keygrips
+}
+
+// `KEYINFO` lines carry the keygrip in the third field and a `1` in the
+// seventh when gpg-agent has its passphrase cached.
+#[cfg(target_os = "linux")]
+fn parse_gpg_agent_cached_keygrips(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            (fields.get(1) == Some(&"KEYINFO") && fields.get(6) == Some(&"1"))
+                .then(|| fields.get(2).map(|keygrip| (*keygrip).to_string()))
+                .flatten()
+        })
+        .collect()
+}
+
+// Anything that can't be checked counts as locked, so the read waits its
+// turn as before.
+#[cfg(target_os = "linux")]
+fn host_gpg_key_is_unlocked(recipients: &[String]) -> bool {
+    if recipients.is_empty() {
+        return false;
+    }
+    let keygrips = host_gpg_decryption_keygrips(recipients);
+    if keygrips.is_empty() {
+        return false;
+    }
+    let Ok(output) = run_host_program_output(
+        "gpg-connect-agent",
+        &["--no-autostart", "KEYINFO --list", "/bye"],
+        "Check host gpg-agent keys",
+        CommandLogOptions::DEFAULT,
+    ) else {
+        return false;
+    };
+    if !output.status.success() {
+        return false;
+    }
+    let cached = parse_gpg_agent_cached_keygrips(&String::from_utf8_lossy(&output.stdout));
+    keygrips.iter().any(|keygrip| cached.contains(keygrip))
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn host_gpg_key_is_unlocked(_recipients: &[String]) -> bool {
+    false
 }
 
 #[cfg(target_os = "linux")]
@@ -647,7 +958,9 @@ mod tests {
     #[cfg(feature = "audit")]
     use super::parse_host_gpg_public_certs;
     use super::{
-        list_host_gpg_private_keys, parse_host_gpg_private_keys, save_password_entry,
+        delete_password_entry, list_host_gpg_private_keys, parse_gpg_agent_cached_keygrips,
+        parse_host_gpg_decryption_keygrips, parse_host_gpg_private_keys, read_password_entry,
+        rename_password_entry, rename_password_folder, save_password_entries, save_password_entry,
         save_store_recipients,
     };
     use crate::backend::test_support::assert_entry_is_encrypted_for_each_recipient;
@@ -655,42 +968,12 @@ mod tests {
     use crate::backend::{
         StoreRecipients, StoreRecipientsError, StoreRecipientsPrivateKeyRequirement,
     };
+    use crate::password::entry_files::{collect_password_entry_paths, PasswordEntryWalkOptions};
     use crate::preferences::Preferences;
-    use crate::support::git::has_git_repository;
+    use crate::support::git::{has_git_repository, sync_store_repository};
     #[cfg(feature = "audit")]
-    use sequoia_openpgp::cert::CertBuilder;
-    use sequoia_openpgp::serialize::Serialize;
-    use std::io::Write;
-    use std::process::{Command, Stdio};
-
-    fn import_secret_key(bytes: &[u8]) -> Result<(), String> {
-        let mut child = Command::new("gpg")
-            .args(["--batch", "--yes", "--import"])
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|err| format!("Failed to start gpg secret-key import: {err}"))?;
-
-        {
-            let stdin = child
-                .stdin
-                .as_mut()
-                .ok_or_else(|| "gpg secret-key import did not provide stdin".to_string())?;
-            stdin
-                .write_all(bytes)
-                .map_err(|err| format!("Failed to write imported secret key bytes: {err}"))?;
-        }
-
-        let output = child
-            .wait_with_output()
-            .map_err(|err| format!("Failed to wait for gpg secret-key import: {err}"))?;
-        if output.status.success() {
-            Ok(())
-        } else {
-            Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
-        }
-    }
+    use sequoia_openpgp::{cert::CertBuilder, serialize::Serialize};
+    use zeroize::Zeroizing;
 
     #[test]
     fn host_backend_encrypts_entries_for_all_store_recipients() {
@@ -771,12 +1054,7 @@ mod tests {
 
         let key = SystemBackendTestEnv::generate_secret_key("Recipient <host-empty@example.com>")
             .expect("generate host recipient key");
-        let mut secret_key_bytes = Vec::new();
-        key.cert
-            .as_tsk()
-            .serialize(&mut secret_key_bytes)
-            .expect("serialize secret key");
-        import_secret_key(&secret_key_bytes).expect("import host secret key");
+        SystemBackendTestEnv::import_secret_key(&key).expect("import host secret key");
         SystemBackendTestEnv::import_public_key(&key.public_key_bytes)
             .expect("import host recipient key");
         SystemBackendTestEnv::trust_public_key(&key.fingerprint_hex)
@@ -816,12 +1094,7 @@ mod tests {
         let key =
             SystemBackendTestEnv::generate_secret_key("Recipient <host-git-clean@example.com>")
                 .expect("generate host recipient key");
-        let mut secret_key_bytes = Vec::new();
-        key.cert
-            .as_tsk()
-            .serialize(&mut secret_key_bytes)
-            .expect("serialize secret key");
-        import_secret_key(&secret_key_bytes).expect("import host secret key");
+        SystemBackendTestEnv::import_secret_key(&key).expect("import host secret key");
         SystemBackendTestEnv::import_public_key(&key.public_key_bytes)
             .expect("import host recipient key");
         SystemBackendTestEnv::trust_public_key(&key.fingerprint_hex)
@@ -844,6 +1117,61 @@ mod tests {
         );
     }
 
+    #[test]
+    #[expect(
+        clippy::significant_drop_tightening,
+        reason = "SystemBackendTestEnv must stay alive for the full test to keep the temp store and env vars in place."
+    )]
+    fn host_backend_batch_save_makes_one_commit() {
+        let env = SystemBackendTestEnv::new();
+        env.init_store_git_repository()
+            .expect("initialize store git repository");
+
+        let key = SystemBackendTestEnv::generate_secret_key("Recipient <host-batch@example.com>")
+            .expect("generate host recipient key");
+        SystemBackendTestEnv::import_secret_key(&key).expect("import host secret key");
+        SystemBackendTestEnv::import_public_key(&key.public_key_bytes)
+            .expect("import host recipient key");
+        SystemBackendTestEnv::trust_public_key(&key.fingerprint_hex)
+            .expect("trust host recipient key");
+
+        let store_root = env.store_root().to_string_lossy().to_string();
+        save_store_recipients(
+            &store_root,
+            &StoreRecipients::new(vec![key.fingerprint_hex.clone()], Vec::new()),
+            StoreRecipientsPrivateKeyRequirement::AnyManagedKey,
+        )
+        .expect("save store recipients");
+        let commit_count = || {
+            SystemBackendTestEnv::git(env.store_root(), &["rev-list", "--count", "HEAD"])
+                .expect("count commits")
+                .trim()
+                .to_string()
+        };
+        let before = commit_count();
+
+        let entries = ["team/a", "team/b", "team/c"]
+            .map(|label| (label.to_string(), Zeroizing::new("secret".to_string())));
+        let results = save_password_entries(&store_root, &entries, "Replace url in 3 passwords");
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(
+            commit_count().parse::<usize>().expect("commit count"),
+            before.parse::<usize>().expect("commit count") + 1
+        );
+        assert_eq!(
+            SystemBackendTestEnv::git(env.store_root(), &["log", "-1", "--format=%s"])
+                .expect("read last commit")
+                .trim(),
+            "Replace url in 3 passwords"
+        );
+        assert_eq!(
+            env.store_git_status_porcelain()
+                .expect("read store git status after batch save"),
+            ""
+        );
+    }
+
     #[test]
     fn host_gpg_parser_keeps_primary_fingerprint_and_user_ids() {
         let parsed = parse_host_gpg_private_keys(
@@ -892,6 +1220,32 @@ uid:u::::1::Duplicate Alice <alice@example.com>::::::::::0:\n",
         );
     }
 
+    #[test]
+    fn host_gpg_unlock_check_uses_cached_decryption_keygrips() {
+        let keygrips = parse_host_gpg_decryption_keygrips(
+            "\
+sec:u:255:22:PRIMARY:1700000000:::u:::scESC:::+:::ed25519:::0:\n\
+fpr:::::::::AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA:\n\
+grp:::::::::SIGNGRIP:\n\
+uid:u::::1700000000::HASH::Alice Example <alice@example.com>::::::::::0:\n\
+ssb:u:255:18:SUB:1700000000::::::e:::+:::cv25519::\n\
+fpr:::::::::BBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB:\n\
+grp:::::::::DECRYPTGRIP:\n",
+        );
+        assert_eq!(keygrips, vec!["DECRYPTGRIP".to_string()]);
+
+        let cached = parse_gpg_agent_cached_keygrips(
+            "\
+S KEYINFO SIGNGRIP D - - 1 P - - -\n\
+S KEYINFO DECRYPTGRIP D - - - P - - -\n\
+S KEYINFO OTHERGRIP D - - 1 P - - -\n\
+OK\n",
+        );
+        assert!(cached.contains("SIGNGRIP"));
+        assert!(cached.contains("OTHERGRIP"));
+        assert!(!cached.contains("DECRYPTGRIP"));
+    }
+
     #[test]
     #[expect(
         clippy::significant_drop_tightening,
@@ -903,12 +1257,7 @@ uid:u::::1::Duplicate Alice <alice@example.com>::::::::::0:\n",
 
         let key = SystemBackendTestEnv::generate_secret_key("Host User <host-user@example.com>")
             .expect("generate secret key");
-        let mut bytes = Vec::new();
-        key.cert
-            .as_tsk()
-            .serialize(&mut bytes)
-            .expect("serialize secret key");
-        import_secret_key(&bytes).expect("import host secret key");
+        SystemBackendTestEnv::import_secret_key(&key).expect("import host secret key");
 
         let keys = list_host_gpg_private_keys().expect("list host gpg private keys");
         assert!(keys.iter().any(|found| {
@@ -920,6 +1269,114 @@ uid:u::::1::Duplicate Alice <alice@example.com>::::::::::0:\n",
         }));
     }
 
+    fn listed_labels(store_root: &std::path::Path) -> Vec<String> {
+        let mut labels =
+            collect_password_entry_paths(store_root, PasswordEntryWalkOptions::default())
+                .expect("list store entries")
+                .iter()
+                .filter_map(|path| {
+                    let relative = path.strip_prefix(store_root).ok()?.to_string_lossy();
+                    Some(relative.trim_end_matches(".gpg").to_string())
+                })
+                .collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
+    #[test]
+    #[expect(
+        clippy::significant_drop_tightening,
+        reason = "SystemBackendTestEnv must stay alive for the full test to keep the temp store and env vars in place."
+    )]
+    fn host_backend_fixture_store_round_trips_entries_and_syncs_with_a_remote() {
+        let env = SystemBackendTestEnv::new();
+        env.init_store_git_repository()
+            .expect("initialize store git repository");
+        let key = SystemBackendTestEnv::generate_secret_key("Fixture <host-fixture@example.com>")
+            .expect("generate host recipient key");
+        SystemBackendTestEnv::import_secret_key(&key).expect("import host secret key");
+        SystemBackendTestEnv::import_public_key(&key.public_key_bytes)
+            .expect("import host recipient key");
+        SystemBackendTestEnv::trust_public_key(&key.fingerprint_hex)
+            .expect("trust host recipient key");
+
+        let store_root = env.store_root().to_string_lossy().to_string();
+        save_store_recipients(
+            &store_root,
+            &StoreRecipients::new(vec![key.fingerprint_hex.clone()], Vec::new()),
+            StoreRecipientsPrivateKeyRequirement::AnyManagedKey,
+        )
+        .expect("save store recipients");
+        save_password_entry(
+            &store_root,
+            "team/mail",
+            "mail-secret\nusername: alice",
+            true,
+        )
+        .expect("save mail entry");
+        save_password_entry(&store_root, "team/ops/db", "db-secret", true).expect("save db entry");
+        save_password_entry(&store_root, "scratch", "temporary", true).expect("save scratch");
+        let remote = env
+            .add_store_bare_remote("origin")
+            .expect("add bare origin remote");
+
+        assert_eq!(
+            listed_labels(env.store_root()),
+            vec!["scratch", "team/mail", "team/ops/db"]
+        );
+        assert_eq!(
+            read_password_entry(&store_root, "team/mail").expect("read mail entry"),
+            "mail-secret\nusername: alice"
+        );
+
+        rename_password_entry(&store_root, "team/mail", "team/email").expect("rename entry");
+        rename_password_folder(&store_root, "team/ops", "ops").expect("rename folder");
+        delete_password_entry(&store_root, "scratch").expect("delete entry");
+        assert_eq!(
+            listed_labels(env.store_root()),
+            vec!["ops/db", "team/email"]
+        );
+        assert_eq!(
+            env.store_git_status_porcelain()
+                .expect("read store git status after edits"),
+            ""
+        );
+
+        let other_device = env
+            .clone_store_remote(&remote, "other-device")
+            .expect("clone origin remote");
+        std::fs::create_dir_all(other_device.join("shared")).expect("create shared folder");
+        std::fs::copy(
+            other_device.join("team/mail.gpg"),
+            other_device.join("shared/mail.gpg"),
+        )
+        .expect("copy entry on the other device");
+        SystemBackendTestEnv::git(&other_device, &["add", "shared/mail.gpg"])
+            .expect("stage shared entry");
+        SystemBackendTestEnv::git(&other_device, &["commit", "-m", "Add shared/mail"])
+            .expect("commit shared entry");
+        SystemBackendTestEnv::git(&other_device, &["push", "origin", "HEAD"])
+            .expect("push shared entry");
+
+        sync_store_repository(&store_root).expect("sync with origin");
+
+        assert_eq!(
+            read_password_entry(&store_root, "shared/mail").expect("read synced entry"),
+            "mail-secret\nusername: alice"
+        );
+        assert_eq!(
+            SystemBackendTestEnv::git(&other_device, &["ls-remote", "origin", "HEAD"])
+                .expect("read origin head")
+                .split_whitespace()
+                .next(),
+            Some(
+                SystemBackendTestEnv::git(env.store_root(), &["rev-parse", "HEAD"])
+                    .expect("read store head")
+                    .as_str()
+            )
+        );
+    }
+
     #[cfg(feature = "audit")]
     #[test]
     fn host_gpg_public_key_parser_reads_multiple_certs() {
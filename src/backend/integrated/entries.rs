@@ -23,6 +23,7 @@ use crate::logging::log_error;
 use crate::support::secure_fs::write_atomic_file;
 use std::fs;
 use std::path::Path;
+use zeroize::Zeroizing;
 
 pub fn read_password_entry(store_root: &str, label: &str) -> Result<String, PasswordEntryError> {
     read_password_entry_with_progress(store_root, label, &mut |_| {})
@@ -125,7 +126,7 @@ pub fn read_password_entry_with_progress(
 }
 
 pub fn read_password_line(store_root: &str, label: &str) -> Result<String, PasswordEntryError> {
-    let secret = read_password_entry(store_root, label)?;
+    let secret = Zeroizing::new(read_password_entry(store_root, label)?);
     Ok(secret.lines().next().unwrap_or_default().to_string())
 }
 
@@ -137,6 +138,7 @@ pub fn password_entry_fido2_recipient_count(store_root: &str, label: &str) -> us
     fido2_recipient_count(store_root, label).unwrap_or(0)
 }
 
+#[cfg(test)]
 pub fn save_password_entry(
     store_root: &str,
     label: &str,
@@ -146,6 +148,12 @@ pub fn save_password_entry(
     save_password_entry_with_progress(store_root, label, contents, overwrite, &mut |_| {})
 }
 
+struct WrittenPasswordEntry {
+    git_message: String,
+    git_paths: Vec<String>,
+    fingerprint: String,
+}
+
 pub fn save_password_entry_with_progress(
     store_root: &str,
     label: &str,
@@ -153,6 +161,47 @@ pub fn save_password_entry_with_progress(
     overwrite: bool,
     report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
 ) -> Result<(), PasswordEntryWriteError> {
+    let written = write_password_entry(store_root, label, contents, overwrite, report_progress)?;
+    maybe_commit_git_paths(
+        store_root,
+        &written.git_message,
+        written.git_paths,
+        Some(&written.fingerprint),
+    );
+    Ok(())
+}
+
+// Every entry is written before the single commit, so one bad entry leaves
+// the others saved and the commit only covers what was written.
+pub fn save_password_entries(
+    store_root: &str,
+    entries: &[(String, Zeroizing<String>)],
+    git_message: &str,
+) -> Vec<Result<(), PasswordEntryWriteError>> {
+    let mut git_paths = Vec::new();
+    let mut fingerprint = None;
+    let results = entries
+        .iter()
+        .map(|(label, contents)| {
+            let written = write_password_entry(store_root, label, contents, true, &mut |_| {})?;
+            git_paths.extend(written.git_paths);
+            fingerprint.get_or_insert(written.fingerprint);
+            Ok(())
+        })
+        .collect();
+    if !git_paths.is_empty() {
+        maybe_commit_git_paths(store_root, git_message, git_paths, fingerprint.as_deref());
+    }
+    results
+}
+
+fn write_password_entry(
+    store_root: &str,
+    label: &str,
+    contents: &str,
+    overwrite: bool,
+    report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
+) -> Result<WrittenPasswordEntry, PasswordEntryWriteError> {
     let existing_entry_path = existing_entry_file_path(store_root, label)
         .map_err(password_entry_write_error_from_integrated_message)?;
     let entry_path = desired_entry_file_path(store_root, label)
@@ -206,17 +255,14 @@ pub fn save_password_entry_with_progress(
             Ok(())
         })
         .map_err(password_entry_write_error_from_integrated_message);
-    if result.is_ok() {
-        maybe_commit_git_paths(
-            store_root,
-            &git_message,
-            existing_git_path
-                .into_iter()
-                .chain(std::iter::once(new_git_path)),
-            Some(context.fingerprint()),
-        );
-    }
-    result
+    result.map(|()| WrittenPasswordEntry {
+        git_message,
+        git_paths: existing_git_path
+            .into_iter()
+            .chain(std::iter::once(new_git_path))
+            .collect(),
+        fingerprint: context.fingerprint().to_string(),
+    })
 }
 
 pub fn rename_password_entry(
@@ -262,6 +308,31 @@ pub fn rename_password_entry(
     result
 }
 
+pub fn rename_password_folder(
+    store_root: &str,
+    old_folder: &Path,
+    new_folder: &Path,
+) -> Result<(), PasswordEntryWriteError> {
+    let commit_fingerprint = commit_identity_fingerprint_for_folder(store_root, old_folder);
+    let old_path = Path::new(store_root).join(old_folder);
+    let new_path = Path::new(store_root).join(new_folder);
+    ensure_parent_dir(&new_path).map_err(password_entry_write_error_from_integrated_message)?;
+    fs::rename(&old_path, &new_path).map_err(|err| password_entry_write_error_from_io(&err))?;
+    let result = cleanup_empty_store_dirs(store_root, &old_path)
+        .map_err(password_entry_write_error_from_integrated_message);
+    if result.is_ok() {
+        let old_git_path = old_folder.to_string_lossy().to_string();
+        let new_git_path = new_folder.to_string_lossy().to_string();
+        maybe_commit_git_paths(
+            store_root,
+            &format!("Rename folder from {old_git_path} to {new_git_path}"),
+            [old_git_path, new_git_path],
+            commit_fingerprint.as_deref(),
+        );
+    }
+    result
+}
+
 pub fn delete_password_entry(store_root: &str, label: &str) -> Result<(), PasswordEntryWriteError> {
     let commit_fingerprint = commit_identity_fingerprint_for_label(store_root, label);
     let entry_path = existing_entry_file_path(store_root, label)
@@ -303,6 +374,13 @@ fn commit_identity_fingerprint_for_label(store_root: &str, label: &str) -> Optio
     }
 }
 
+// Only the folder part of the label picks the recipients, so a folder signs
+// with the identity an item directly inside it would use.
+fn commit_identity_fingerprint_for_folder(store_root: &str, folder: &Path) -> Option<String> {
+    let label = folder.join("item").to_string_lossy().replace('\\', "/");
+    commit_identity_fingerprint_for_label(store_root, &label)
+}
+
 fn ensure_required_private_keys_are_ready(
     fingerprints: &[String],
 ) -> Result<(), PasswordEntryError> {
@@ -11,7 +11,7 @@ use crate::logging::{
     log_error, log_info, run_command_output, run_command_with_input, CommandLogOptions,
 };
 use crate::preferences::Preferences;
-use crate::support::git::has_git_repository;
+use crate::support::git::{check_staged_large_store_files, has_git_repository, LargeStoreFiles};
 use crate::support::runtime::{require_host_command_features, supports_host_command_features};
 use sequoia_openpgp::policy::StandardPolicy;
 use sequoia_openpgp::serialize::stream::{Armorer, Message, Signer};
@@ -587,6 +587,27 @@ fn signature_for_commit(
     Ok(Some(signature))
 }
 
+fn log_large_store_files(store_root: &str, large_files: LargeStoreFiles) {
+    if large_files.files.is_empty() {
+        return;
+    }
+    let paths = large_files
+        .files
+        .iter()
+        .map(|file| format!("{} ({} MiB)", file.path, file.mib()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if large_files.left_out {
+        log_error(format!(
+            "Left large files out of the password store Git commit for {store_root}: {paths}."
+        ));
+    } else {
+        log_error(format!(
+            "Committing large files to the password store Git history for {store_root}: {paths}."
+        ));
+    }
+}
+
 fn commit_git_paths(
     store_root: &str,
     message: &str,
@@ -619,6 +640,7 @@ fn commit_git_paths(
         paths.join(", "),
     ));
     stage_git_paths(store_root, paths)?;
+    log_large_store_files(store_root, check_staged_large_store_files(store_root)?);
     if !staged_git_paths_have_changes(store_root, paths)? {
         log_info(format!(
             "Skip password store Git commit for {store_root}: the staged paths have no changes."
@@ -628,7 +650,11 @@ fn commit_git_paths(
 
     let tree_oid = write_git_tree(store_root)?;
     let parent_oid = head_oid(store_root)?;
-    let identity = commit_identity(explicit_fingerprint)?;
+    let mut identity = commit_identity(explicit_fingerprint)?;
+    if let Some((name, email)) = Preferences::new().git_identity() {
+        identity.identity.name = name;
+        identity.identity.email = email;
+    }
     log_commit_identity_resolution(store_root, &identity);
     let author_ident = git_ident(store_root, "author", &identity.identity)?;
     let committer_ident = git_ident(store_root, "committer", &identity.identity)?;
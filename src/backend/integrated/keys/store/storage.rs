@@ -45,6 +45,7 @@ const SMARTCARD_FEATURE_DISABLED_ERROR: &str =
 #[cfg(not(feature = "hardwarekey"))]
 const HARDWAREKEY_FEATURE_DISABLED_ERROR: &str =
     "Managed hardware-key setup is disabled in this build of Keycord.";
+use crate::backend::entry_cache::clear_password_entry_cache;
 use crate::backend::PrivateKeyError;
 #[cfg(feature = "fidokey")]
 use crate::fido2_recipient::parse_fido2_recipient_string;
@@ -770,6 +771,7 @@ pub fn import_ripasso_private_key_bytes(
         let (unlocked_cert, _) = prepare_managed_private_key_bytes(bytes, passphrase)?;
         cache_unlocked_ripasso_private_key(unlocked_cert);
     }
+    clear_password_entry_cache();
 
     Ok(key)
 }
@@ -913,7 +915,9 @@ pub fn import_ripasso_hardware_key_bytes(
     bytes: &[u8],
     hardware: ManagedRipassoHardwareKey,
 ) -> Result<ManagedRipassoPrivateKey, PrivateKeyError> {
-    store_ripasso_hardware_key_bytes(bytes, hardware)
+    let key = store_ripasso_hardware_key_bytes(bytes, hardware)?;
+    clear_password_entry_cache();
+    Ok(key)
 }
 
 #[cfg(not(feature = "smartcard"))]
@@ -1135,6 +1139,8 @@ pub fn remove_ripasso_private_key(fingerprint: &str) -> Result<(), String> {
         }
     }
     remove_cached_unlocked_ripasso_private_key(fingerprint)?;
+    // Items the removed key decrypted must not stay readable from the cache.
+    clear_password_entry_cache();
     Ok(())
 }
 
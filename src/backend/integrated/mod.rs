@@ -42,14 +42,17 @@ pub(crate) use self::keys::{
 pub(crate) use self::keys::{
     prepare_managed_private_key_storage_for_startup, ManagedKeyStorageStartup,
 };
-pub use self::recipients::preferred_ripasso_private_key_fingerprint_for_entry;
 #[cfg(test)]
 pub use self::recipients::required_private_key_fingerprints_for_entry;
+pub use self::recipients::{
+    preferred_ripasso_private_key_fingerprint_for_entry, recipients_with_private_key,
+};
 
 pub use self::entries::{
     delete_password_entry, password_entry_fido2_recipient_count, password_entry_is_readable,
     read_password_entry, read_password_entry_with_progress, read_password_line,
-    rename_password_entry, save_password_entry, save_password_entry_with_progress,
+    rename_password_entry, rename_password_folder, save_password_entries,
+    save_password_entry_with_progress,
 };
 pub(in crate::backend) use self::store::try_initialize_empty_store_recipients;
 pub use self::store::{
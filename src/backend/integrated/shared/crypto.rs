@@ -18,6 +18,7 @@ use super::recipients::{
 };
 use crate::backend::{PasswordEntryError, StoreRecipientsPrivateKeyRequirement};
 use crate::fido2_recipient::{is_fido2_recipient_string, parse_fido2_recipient_string};
+use crate::support::secrets::string_from_secret_utf8;
 use rand::random;
 use ripasso::crypto::{Crypto, Sequoia};
 use ripasso::pass::{Comment, KeyRingStatus, OwnerTrustLevel, Recipient};
@@ -272,7 +273,7 @@ impl IntegratedCryptoContext {
         existing_ciphertext: &[u8],
         report_progress: Option<&mut dyn FnMut(Fido2WriteProgress)>,
     ) -> Result<Vec<u8>, String> {
-        let dek = self.decrypt_existing_any_managed_dek(existing_ciphertext)?;
+        let dek = Zeroizing::new(self.decrypt_existing_any_managed_dek(existing_ciphertext)?);
         let pgp_wrapped_dek = if self.recipients.is_empty() {
             None
         } else {
@@ -335,10 +336,12 @@ impl IntegratedCryptoContext {
             return decrypt_any_managed_ciphertext_for_fingerprint(&self.fingerprint, ciphertext);
         }
 
-        let dek =
-            decrypt_fido2_any_managed_bundle_dek_for_bindings(&self.fido2_recipients, ciphertext)?;
+        let dek = Zeroizing::new(decrypt_fido2_any_managed_bundle_dek_for_bindings(
+            &self.fido2_recipients,
+            ciphertext,
+        )?);
         let plaintext = decrypt_payload_from_any_managed_bundle(ciphertext, &dek)?;
-        String::from_utf8(plaintext).map_err(|err| err.to_string())
+        string_from_secret_utf8(plaintext)
     }
 }
 
@@ -461,15 +464,18 @@ fn decrypt_any_managed_ciphertext_for_fingerprint_with_progress(
             &direct_fido2_fingerprint,
             ciphertext,
         ) {
-            Ok(plaintext) => return String::from_utf8(plaintext).map_err(|err| err.to_string()),
+            Ok(plaintext) => return string_from_secret_utf8(plaintext),
             Err(err) if err != PASSWORD_ENTRY_CANDIDATE_MISMATCH => return Err(err),
             Err(_) => {}
         }
 
         if let Some(wrapped_dek) = extract_pgp_wrapped_dek_from_any_managed_bundle(ciphertext)? {
-            let dek = decrypt_pgp_wrapped_dek_for_fingerprint(fingerprint, &wrapped_dek)?;
+            let dek = Zeroizing::new(decrypt_pgp_wrapped_dek_for_fingerprint(
+                fingerprint,
+                &wrapped_dek,
+            )?);
             let plaintext = decrypt_payload_from_any_managed_bundle(ciphertext, &dek)?;
-            return String::from_utf8(plaintext).map_err(|err| err.to_string());
+            return string_from_secret_utf8(plaintext);
         }
 
         return Err(PASSWORD_ENTRY_CANDIDATE_MISMATCH.to_string());
@@ -521,7 +527,7 @@ fn decrypt_password_entry_requiring_all_private_keys_with_progress(
         let decrypted = decrypt_required_private_key_layer(recipient, &current)?;
         let is_final_layer = index + 1 == required_private_key_recipients.len();
         if is_final_layer {
-            return String::from_utf8(decrypted).map_err(|err| err.to_string());
+            return string_from_secret_utf8(decrypted);
         }
 
         current = unwrap_required_private_key_layer(&decrypted)?;
@@ -554,14 +560,14 @@ fn decrypt_any_managed_fido2_ciphertext_requiring_all_keys(
     ciphertext: &[u8],
     mut report_progress: Option<&mut dyn FnMut(Fido2ReadProgress)>,
 ) -> Result<String, String> {
-    let mut required_dek: Option<Vec<u8>> = None;
+    let mut required_dek: Option<Zeroizing<Vec<u8>>> = None;
 
     for (index, binding) in bindings.iter().enumerate() {
         report_fido2_progress(&mut report_progress, index + 1, bindings.len());
-        let dek = decrypt_fido2_any_managed_bundle_dek_for_bindings(
+        let dek = Zeroizing::new(decrypt_fido2_any_managed_bundle_dek_for_bindings(
             std::slice::from_ref(binding),
             ciphertext,
-        )?;
+        )?);
         if let Some(expected_dek) = required_dek.as_ref() {
             if expected_dek != &dek {
                 return Err("Invalid FIDO2 any-managed password entry.".to_string());
@@ -574,7 +580,7 @@ fn decrypt_any_managed_fido2_ciphertext_requiring_all_keys(
     let dek = required_dek
         .ok_or_else(|| "No recipients were found for this password entry.".to_string())?;
     let plaintext = decrypt_payload_from_any_managed_bundle(ciphertext, &dek)?;
-    String::from_utf8(plaintext).map_err(|err| err.to_string())
+    string_from_secret_utf8(plaintext)
 }
 
 fn decrypt_required_private_key_layer(
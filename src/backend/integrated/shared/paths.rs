@@ -1,13 +1,13 @@
 use std::fs;
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
 
 use crate::backend::path_validation::{
     validated_entry_label_path, validated_relative_directory_path,
 };
 use crate::fido2_recipient::FIDO2_RECIPIENTS_FILE_NAME;
 use crate::password::entry_files::{
-    is_password_entry_file, label_from_password_entry_path, password_entry_extension,
+    collect_password_entry_paths, label_from_password_entry_path, password_entry_extension,
+    PasswordEntryWalkOptions,
 };
 use crate::support::runtime::supports_legacy_compat_features;
 use crate::support::secure_fs::write_atomic_file;
@@ -224,22 +224,13 @@ pub(super) fn cleanup_empty_store_dirs(store_root: &str, entry_path: &Path) -> R
 }
 
 pub(super) fn collect_password_entry_files(store_root: &Path) -> Result<Vec<PathBuf>, String> {
-    if !store_root.exists() {
-        return Ok(Vec::new());
-    }
-
-    let mut entries = Vec::new();
-    for entry in WalkDir::new(store_root) {
-        let entry = entry.map_err(|err| err.to_string())?;
-        if !entry.file_type().is_file() {
-            continue;
-        }
-        if is_password_entry_file(entry.path()) {
-            entries.push(entry.into_path());
-        }
-    }
-
-    Ok(entries)
+    collect_password_entry_paths(
+        store_root,
+        PasswordEntryWalkOptions {
+            include_hidden: true,
+        },
+    )
+    .map_err(|err| err.to_string())
 }
 
 #[cfg(test)]
@@ -594,6 +594,27 @@ pub(super) fn decryption_candidate_fingerprints_for_entry(
     Ok(prioritized_unique_fingerprints(candidates))
 }
 
+// The recipients, out of `recipients`, whose private key was imported into
+// the app or sits on a connected smartcard.
+pub fn recipients_with_private_key(recipients: &[String]) -> Result<HashSet<String>, String> {
+    let available = available_private_key_fingerprints()?;
+    let key_ring = load_available_standard_key_ring()?;
+    let mut found = HashSet::new();
+    for recipient in recipients {
+        let Some((_, cert)) = resolve_recipient_cert(recipient, &key_ring)? else {
+            continue;
+        };
+        let fingerprint = cert.fingerprint().to_hex();
+        if available
+            .iter()
+            .any(|available| available.eq_ignore_ascii_case(&fingerprint))
+        {
+            found.insert(recipient.clone());
+        }
+    }
+    Ok(found)
+}
+
 pub fn preferred_ripasso_private_key_fingerprint_for_entry(
     store_root: &str,
     label: &str,
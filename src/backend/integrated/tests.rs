@@ -1,7 +1,7 @@
 use super::crypto::IntegratedCryptoContext;
 use super::entries::{
     delete_password_entry, password_entry_is_readable, read_password_entry, rename_password_entry,
-    save_password_entry,
+    rename_password_folder, save_password_entry,
 };
 #[cfg(any(feature = "fidostore", feature = "fidokey"))]
 use super::entries::{
@@ -56,7 +56,7 @@ use super::store::{
     store_recipients_private_key_requiring_unlock,
 };
 use crate::backend::{
-    preferred_ripasso_private_key_fingerprint_for_entry,
+    preferred_ripasso_private_key_fingerprint_for_entry, read_password_entries_in_parallel,
     required_private_key_fingerprints_for_entry, test_support::SystemBackendTestEnv,
     PasswordEntryError, PasswordEntryWriteError, PrivateKeyError, StoreRecipientsError,
     StoreRecipientsPrivateKeyRequirement,
@@ -72,6 +72,7 @@ use crate::fido2_recipient::is_fido2_recipient_string;
 use crate::fido2_recipient::{
     build_fido2_recipient_string, derived_fido2_recipient_id, FIDO2_RECIPIENTS_FILE_NAME,
 };
+use crate::logging::log_info;
 use crate::preferences::Preferences;
 use crate::store::recipients::split_store_recipients;
 use crate::support::git::has_git_repository;
@@ -82,8 +83,9 @@ use sequoia_openpgp::{cert::CertBuilder, crypto::Password, parse::Parse, seriali
 #[cfg(any(feature = "fidostore", feature = "fidokey"))]
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 #[cfg(any(feature = "fidostore", feature = "fidokey"))]
 fn test_fido2_recipient(label: &str, credential_id: &[u8]) -> String {
@@ -3858,6 +3860,50 @@ fn unreadable_entry_rename_commits_without_a_signature() {
         .expect("inspect commit headers"));
 }
 
+#[test]
+fn folder_rename_moves_every_entry_in_one_commit() {
+    let env = SystemBackendTestEnv::new();
+    let bytes = protected_cert_bytes("Folder Key <folder-rename@example.com>");
+    let imported =
+        import_ripasso_private_key_bytes(&bytes, Some("hunter2")).expect("import private key");
+    Preferences::new()
+        .set_ripasso_own_fingerprint(Some(&imported.fingerprint))
+        .expect("select signing key");
+    env.init_store_git_repository()
+        .expect("initialize git repository");
+    let store_root = env.store_root().to_string_lossy().to_string();
+
+    save_store_recipients(
+        &store_root,
+        std::slice::from_ref(&imported.fingerprint),
+        StoreRecipientsPrivateKeyRequirement::AnyManagedKey,
+    )
+    .expect("save store recipients");
+    save_password_entry(&store_root, "team/mail", "mail-secret", true).expect("save mail");
+    save_password_entry(&store_root, "team/ops/db", "db-secret", true).expect("save db");
+
+    rename_password_folder(&store_root, Path::new("team"), Path::new("work/team"))
+        .expect("rename folder");
+
+    assert!(!env.store_root().join("team").exists());
+    assert_eq!(
+        read_password_entry(&store_root, "work/team/mail").expect("read moved mail"),
+        "mail-secret"
+    );
+    assert_eq!(
+        read_password_entry(&store_root, "work/team/ops/db").expect("read moved db"),
+        "db-secret"
+    );
+    let subjects = env
+        .store_git_commit_subjects()
+        .expect("read commit subjects");
+    assert_eq!(subjects[0], "Rename folder from team to work/team");
+    assert_eq!(subjects[1], "Add password for team/ops/db");
+    assert!(env
+        .store_head_commit_has_signature()
+        .expect("inspect commit headers"));
+}
+
 #[test]
 fn unreadable_entry_delete_commits_without_a_signature() {
     let env = SystemBackendTestEnv::new();
@@ -4071,3 +4117,54 @@ fn store_recipients_unlock_helper_detects_a_locked_entry_key() {
         Some(imported.fingerprint)
     );
 }
+
+// Decryption throughput, one item at a time and through the bulk reader that
+// search and the weak password scan use. Run with
+// `cargo test --release -- --ignored benchmark_`.
+#[test]
+#[ignore = "benchmark"]
+fn benchmark_decrypting_entries() {
+    let env = SystemBackendTestEnv::new();
+    let key = generate_ripasso_private_key("Benchmark User", "benchmark@example.com", "hunter2")
+        .expect("generate private key");
+    let store = env.root_dir().join("benchmark-store");
+    fs::create_dir_all(&store).expect("create store");
+    fs::write(store.join(".gpg-id"), format!("{}\n", key.fingerprint)).expect("write recipients");
+    let store_root = store.to_string_lossy().to_string();
+    let labels = (0..200)
+        .map(|index| format!("folder-{}/item-{index}", index % 10))
+        .collect::<Vec<_>>();
+    for label in &labels {
+        save_password_entry(&store_root, label, "secret\nusername: benchmark", false)
+            .expect("save benchmark entry");
+    }
+
+    let started = Instant::now();
+    for label in &labels {
+        read_password_entry(&store_root, label).expect("read benchmark entry");
+    }
+    let one_by_one = started.elapsed();
+
+    let requests = labels
+        .iter()
+        .map(|label| (store_root.clone(), label.clone()))
+        .collect::<Vec<_>>();
+    let started = Instant::now();
+    let results = read_password_entries_in_parallel(requests, |(store_root, label)| {
+        read_password_entry(&store_root, &label)
+    });
+    let parallel = started.elapsed();
+
+    assert!(results.iter().all(|result| matches!(result, Some(Ok(_)))));
+    log_info(format!(
+        "decrypting {} items took {} ms one by one and {} ms in parallel",
+        labels.len(),
+        one_by_one.as_millis(),
+        parallel.as_millis()
+    ));
+    assert!(
+        parallel < Duration::from_secs(10),
+        "decrypting {} items in parallel took {parallel:?}",
+        labels.len()
+    );
+}
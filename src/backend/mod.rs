@@ -1,21 +1,35 @@
+mod attachments;
 mod command;
+mod entry_cache;
 mod errors;
+mod events;
+mod gpg_queue;
 mod host;
 mod host_errors;
 mod integrated;
+mod parallel;
 mod path_validation;
+mod store_backend;
+mod store_lock;
 #[cfg(test)]
-mod test_support;
+pub(crate) mod test_support;
 
 #[cfg(feature = "audit")]
 use sequoia_openpgp::Cert;
 
+pub use self::attachments::{
+    delete_password_attachment, list_password_attachments, read_password_attachment,
+    save_password_attachment,
+};
 pub use self::errors::PasswordEntryError;
 pub use self::errors::PrivateKeyError;
+pub(crate) use self::errors::STORE_BUSY_TOAST;
 pub use self::errors::{PasswordEntryWriteError, StoreRecipientsError};
+pub use self::events::{log_store_events, publish_store_event, subscribe_store_events, StoreEvent};
 #[cfg(feature = "legacy-compat")]
 pub(crate) use self::integrated::ManagedKeyStorageRecovery as ManagedKeyRecovery;
 pub(crate) use self::integrated::ManagedKeyStorageStartup as StartupPreparation;
+pub use self::parallel::read_password_entries_in_parallel;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PasswordEntryProgress {
@@ -99,6 +113,51 @@ pub use integrated::{
     git_commit_private_key_requiring_unlock_for_store_recipients,
 };
 
+pub fn host_gpg_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        host::host_gpg_available()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+pub fn host_gpg_secret_key_available(recipient: &str) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        host::host_gpg_secret_key_available(recipient)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = recipient;
+        false
+    }
+}
+
+// Which of `recipients` this device can decrypt for with the backend in
+// use: keys imported into the app, or secret keys in the host gpg keyring.
+pub fn recipients_with_private_key(recipients: &[String]) -> HashSet<String> {
+    dispatch_backend(
+        || {
+            integrated::recipients_with_private_key(recipients).unwrap_or_else(|err| {
+                log_error(format!("Failed to look up the app's private keys: {err}"));
+                HashSet::new()
+            })
+        },
+        || {
+            recipients
+                .iter()
+                .filter(|recipient| host_gpg_secret_key_available(recipient))
+                .cloned()
+                .collect()
+        },
+    )
+}
+
 #[cfg(feature = "audit")]
 pub fn available_standard_public_certs() -> Result<Vec<Cert>, String> {
     integrated::available_standard_public_certs()
@@ -117,7 +176,45 @@ pub fn available_host_gpg_public_certs() -> Result<Vec<Cert>, String> {
     }
 }
 
+use self::attachments::{delete_password_attachments, move_password_attachments};
+use self::entry_cache::{
+    cache_password_entry, cache_search_entry, cached_password_entry, cached_search_entry,
+    clear_password_entry_cache, clear_search_entry_cache, forget_cached_store_entries,
+};
+use self::errors::store_busy_message;
+use self::path_validation::validated_entry_label_path;
+use self::store_backend::store_backend;
+use self::store_lock::{lock_store, StoreLock};
+use crate::logging::log_error;
 use crate::preferences::Preferences;
+use crate::support::git::{
+    has_git_repository, store_git_deleted_entry_file, store_git_sparse_folders,
+    StoreGitDeletedEntry,
+};
+use crate::support::secure_fs::write_private_file;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use zeroize::Zeroizing;
+
+static STORE_WRITE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// Bumped whenever a write finishes, so views read from a store can tell
+// whether anything changed since they were built.
+pub fn store_write_generation() -> u64 {
+    STORE_WRITE_GENERATION.load(Ordering::Relaxed)
+}
+
+struct StoreWrite {
+    _lock: Option<StoreLock>,
+}
+
+impl Drop for StoreWrite {
+    fn drop(&mut self) {
+        STORE_WRITE_GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
 
 fn dispatch_backend<T>(integrated: impl FnOnce() -> T, host: impl FnOnce() -> T) -> T {
     if Preferences::new().uses_integrated_backend() {
@@ -135,39 +232,140 @@ pub const fn supports_first_time_fido2_pin_setup() -> bool {
     ))
 }
 
-macro_rules! dispatch_backend_call {
-    ($(fn $name:ident($($arg:ident: $arg_ty:ty),* $(,)?) -> $ret:ty;)+) => {
-        $(
-            pub fn $name($($arg: $arg_ty),*) -> $ret {
-                dispatch_backend(
-                    || integrated::$name($($arg),*),
-                    || host::$name($($arg),*),
-                )
-            }
-        )+
-    };
+// Writes hold the store lock until the caller drops it, so a save and its Git
+// commit can't interleave with another Keycord window writing the same store.
+fn lock_writable_store<E>(
+    store_root: &str,
+    read_only: impl FnOnce(&str) -> E,
+    busy: impl FnOnce(&str) -> E,
+) -> Result<StoreWrite, E> {
+    if Preferences::new().store_is_read_only(store_root) {
+        return Err(read_only(store_root));
+    }
+    let lock = lock_store(store_root).map_err(|_| busy(store_root))?;
+    Ok(StoreWrite { _lock: lock })
+}
+
+// A sync pulls and merges into the store, so it holds the same lock as a
+// write, whether it runs here, in the sync daemon, or in another window.
+// Read-only stores still sync: only edits are blocked there.
+pub struct StoreSync {
+    _write: StoreWrite,
+}
+
+pub fn lock_store_for_sync(store_root: &str) -> Result<StoreSync, String> {
+    let lock = lock_store(store_root).map_err(|_| store_busy_message(store_root))?;
+    Ok(StoreSync {
+        _write: StoreWrite { _lock: lock },
+    })
+}
+
+// Re-encrypting only reaches the items on disk. With some folders left out of
+// a sparse checkout, the items in them would keep the old keys.
+fn lock_store_for_recipient_change(store_root: &str) -> Result<StoreWrite, StoreRecipientsError> {
+    let lock = lock_writable_store(
+        store_root,
+        StoreRecipientsError::read_only_store,
+        StoreRecipientsError::store_busy,
+    )?;
+    if !has_git_repository(store_root) {
+        return Ok(lock);
+    }
+    match store_git_sparse_folders(store_root) {
+        Ok(Some(_)) => Err(StoreRecipientsError::partial_checkout(store_root)),
+        Ok(None) => Ok(lock),
+        Err(err) => {
+            log_error(format!(
+                "Failed to read the sparse checkout of {store_root}: {err}"
+            ));
+            Ok(lock)
+        }
+    }
+}
+
+pub fn read_password_entry(store_root: &str, label: &str) -> Result<String, PasswordEntryError> {
+    read_password_entry_with_progress(store_root, label, &mut |_| {})
 }
 
-dispatch_backend_call! {
-    fn read_password_entry(store_root: &str, label: &str) -> Result<String, PasswordEntryError>;
-    fn read_password_line(store_root: &str, label: &str) -> Result<String, PasswordEntryError>;
-    fn save_password_entry(
-        store_root: &str,
-        label: &str,
-        contents: &str,
-        overwrite: bool,
-    ) -> Result<(), PasswordEntryWriteError>;
-    fn rename_password_entry(
-        store_root: &str,
-        old_label: &str,
-        new_label: &str,
-    ) -> Result<(), PasswordEntryWriteError>;
-    fn delete_password_entry(store_root: &str, label: &str) -> Result<(), PasswordEntryWriteError>;
-    fn save_store_recipients(
-        store_root: &str,
-        recipients: &StoreRecipients,
-        private_key_requirement: StoreRecipientsPrivateKeyRequirement,
-    ) -> Result<(), StoreRecipientsError>;
+pub fn read_password_line(store_root: &str, label: &str) -> Result<String, PasswordEntryError> {
+    store_backend().read_password_line(store_root, label)
+}
+
+pub fn save_password_entry(
+    store_root: &str,
+    label: &str,
+    contents: &str,
+    overwrite: bool,
+) -> Result<(), PasswordEntryWriteError> {
+    save_password_entry_with_progress(store_root, label, contents, overwrite, &mut |_| {})
+}
+
+pub fn rename_password_entry(
+    store_root: &str,
+    old_label: &str,
+    new_label: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    store_backend().rename_password_entry(store_root, old_label, new_label)?;
+    if let Err(err) = move_password_attachments(store_root, old_label, new_label) {
+        log_error(format!(
+            "Failed to move the attachments of '{old_label}' to '{new_label}': {err}"
+        ));
+    }
+    publish_store_event(StoreEvent::Renamed {
+        store: store_root.to_string(),
+        from: old_label.to_string(),
+        to: new_label.to_string(),
+    });
+    Ok(())
+}
+
+pub fn delete_password_entry(store_root: &str, label: &str) -> Result<(), PasswordEntryWriteError> {
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    store_backend().delete_password_entry(store_root, label)?;
+    if let Err(err) = delete_password_attachments(store_root, label) {
+        log_error(format!(
+            "Failed to delete the attachments of '{label}': {err}"
+        ));
+    }
+    publish_store_event(StoreEvent::Removed {
+        store: store_root.to_string(),
+        label: label.to_string(),
+    });
+    Ok(())
+}
+
+pub fn save_store_recipients(
+    store_root: &str,
+    recipients: &StoreRecipients,
+    private_key_requirement: StoreRecipientsPrivateKeyRequirement,
+) -> Result<(), StoreRecipientsError> {
+    let _lock = lock_store_for_recipient_change(store_root)?;
+    dispatch_backend(
+        || integrated::save_store_recipients(store_root, recipients, private_key_requirement),
+        || host::save_store_recipients(store_root, recipients, private_key_requirement),
+    )
+}
+
+// Drafts are encrypted like items but live in their own folder outside the
+// stores, so saving one takes no store lock and isn't counted as a store
+// write that would reload the list.
+pub fn save_password_draft(
+    draft_root: &str,
+    label: &str,
+    contents: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    store_backend().save_password_entry(draft_root, label, contents, true, &mut |_| {})?;
+    forget_cached_store_entries(draft_root);
+    Ok(())
 }
 
 pub fn list_connected_smartcard_keys() -> Result<Vec<ConnectedSmartcardKey>, String> {
@@ -181,17 +379,175 @@ pub fn save_password_entry_with_progress(
     overwrite: bool,
     report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
 ) -> Result<(), PasswordEntryWriteError> {
-    if Preferences::new().uses_integrated_backend() {
-        integrated::save_password_entry_with_progress(
-            store_root,
-            label,
-            contents,
-            overwrite,
-            report_progress,
-        )
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    store_backend().save_password_entry(store_root, label, contents, overwrite, report_progress)?;
+    let store = store_root.to_string();
+    let label = label.to_string();
+    publish_store_event(if overwrite {
+        StoreEvent::Changed { store, label }
     } else {
-        host::save_password_entry_with_progress(store_root, label, contents, overwrite)
+        StoreEvent::Added { store, label }
+    });
+    Ok(())
+}
+
+// Decrypts the item from disk, past the cache, and saves what `update` makes
+// of it while holding the store lock, so a value derived from the saved item,
+// like a HOTP counter, builds on the latest version instead of an older copy.
+pub fn update_password_entry<T>(
+    store_root: &str,
+    label: &str,
+    update: impl FnOnce(&str) -> Result<Option<(Zeroizing<String>, T)>, String>,
+) -> Result<Option<T>, PasswordEntryWriteError> {
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    let contents =
+        Zeroizing::new(store_backend().read_password_entry(store_root, label, &mut |_| {})?);
+    let Some((contents, value)) = update(&contents).map_err(PasswordEntryWriteError::other)? else {
+        return Ok(None);
+    };
+    store_backend().save_password_entry(store_root, label, &contents, true, &mut |_| {})?;
+    publish_store_event(StoreEvent::Changed {
+        store: store_root.to_string(),
+        label: label.to_string(),
+    });
+    Ok(Some(value))
+}
+
+pub fn save_password_entries(
+    store_root: &str,
+    entries: &[(String, Zeroizing<String>)],
+    git_message: &str,
+) -> Vec<Result<(), PasswordEntryWriteError>> {
+    let _lock = match lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    ) {
+        Ok(lock) => lock,
+        Err(err) => return entries.iter().map(|_| Err(err.clone())).collect(),
+    };
+    dispatch_backend(
+        || integrated::save_password_entries(store_root, entries, git_message),
+        || host::save_password_entries(store_root, entries, git_message),
+    )
+}
+
+// A restored item is decrypted and saved again like a new item, so it is
+// encrypted for the store's current recipients and read-only stores are
+// refused, instead of the old file coming back as it was. The old file is
+// only on disk while it's being read.
+pub fn restore_deleted_password_entry(
+    store_root: &str,
+    entry: &StoreGitDeletedEntry,
+) -> Result<(), PasswordEntryWriteError> {
+    let contents = {
+        let _lock = lock_writable_store(
+            store_root,
+            PasswordEntryWriteError::read_only_store,
+            PasswordEntryWriteError::store_busy,
+        )?;
+        let path = Path::new(store_root).join(&entry.path);
+        if path.exists() {
+            return Err(PasswordEntryWriteError::already_exists(
+                "An item with that name already exists.",
+            ));
+        }
+        let ciphertext = store_git_deleted_entry_file(store_root, entry)
+            .map_err(PasswordEntryWriteError::other)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|err| PasswordEntryWriteError::other(err.to_string()))?;
+        }
+        write_private_file(&path, ciphertext)
+            .map_err(|err| PasswordEntryWriteError::other(err.to_string()))?;
+        let contents = store_backend().read_password_entry(store_root, &entry.label, &mut |_| {});
+        if let Err(err) = fs::remove_file(&path) {
+            log_error(format!("Failed to remove '{}': {err}", path.display()));
+        }
+        Zeroizing::new(contents.map_err(|err| PasswordEntryWriteError::other(err.to_string()))?)
+    };
+
+    let message = format!("Restore {} from history.", entry.label);
+    save_password_entries(store_root, &[(entry.label.clone(), contents)], &message)
+        .pop()
+        .unwrap_or(Ok(()))
+}
+
+// `pass` has no command for folders, so both backends create them directly.
+// Git doesn't track empty folders, so a new one stays on this device until
+// it holds an item.
+pub fn create_password_folder(
+    store_root: &str,
+    folder: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    let relative = validated_entry_label_path(folder).map_err(PasswordEntryWriteError::other)?;
+    let path = Path::new(store_root).join(relative);
+    if path.exists() {
+        return Err(PasswordEntryWriteError::already_exists(format!(
+            "'{}' already exists.",
+            path.display()
+        )));
     }
+    fs::create_dir_all(&path).map_err(|err| PasswordEntryWriteError::other(err.to_string()))
+}
+
+// The folder moves as a whole, so attachments and `.gpg-id` files keep their
+// place next to the items and the store gets a single commit.
+pub fn rename_password_folder(
+    store_root: &str,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    let _lock = lock_writable_store(
+        store_root,
+        PasswordEntryWriteError::read_only_store,
+        PasswordEntryWriteError::store_busy,
+    )?;
+    move_password_folder(store_root, old_folder, new_folder)
+}
+
+fn move_password_folder(
+    store_root: &str,
+    old_folder: &str,
+    new_folder: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    let old_relative =
+        validated_entry_label_path(old_folder).map_err(PasswordEntryWriteError::other)?;
+    let new_relative =
+        validated_entry_label_path(new_folder).map_err(PasswordEntryWriteError::other)?;
+    if new_relative.starts_with(&old_relative) {
+        return Err(PasswordEntryWriteError::other(
+            "A folder can't be moved into itself.",
+        ));
+    }
+    let root = Path::new(store_root);
+    if !root.join(&old_relative).is_dir() {
+        return Err(PasswordEntryWriteError::entry_not_found(format!(
+            "Folder '{old_folder}' was not found."
+        )));
+    }
+    if root.join(&new_relative).exists() {
+        return Err(PasswordEntryWriteError::already_exists(format!(
+            "'{new_folder}' already exists."
+        )));
+    }
+    dispatch_backend(
+        || integrated::rename_password_folder(store_root, &old_relative, &new_relative),
+        || host::rename_password_folder(store_root, old_folder, new_folder),
+    )
 }
 
 pub fn save_store_recipients_with_progress(
@@ -200,6 +556,7 @@ pub fn save_store_recipients_with_progress(
     private_key_requirement: StoreRecipientsPrivateKeyRequirement,
     report_progress: &mut dyn FnMut(StoreRecipientsSaveProgress),
 ) -> Result<(), StoreRecipientsError> {
+    let _lock = lock_store_for_recipient_change(store_root)?;
     if Preferences::new().uses_integrated_backend() {
         integrated::save_store_recipients_with_progress(
             store_root,
@@ -218,6 +575,7 @@ pub fn save_store_recipients_for_relative_dir(
     recipients: &StoreRecipients,
     private_key_requirement: StoreRecipientsPrivateKeyRequirement,
 ) -> Result<(), StoreRecipientsError> {
+    let _lock = lock_store_for_recipient_change(store_root)?;
     dispatch_backend(
         || {
             integrated::save_store_recipients_for_relative_dir(
@@ -245,6 +603,7 @@ pub fn save_store_recipients_with_progress_for_relative_dir(
     private_key_requirement: StoreRecipientsPrivateKeyRequirement,
     report_progress: &mut dyn FnMut(StoreRecipientsSaveProgress),
 ) -> Result<(), StoreRecipientsError> {
+    let _lock = lock_store_for_recipient_change(store_root)?;
     if Preferences::new().uses_integrated_backend() {
         integrated::save_store_recipients_with_progress_for_relative_dir(
             store_root,
@@ -268,11 +627,40 @@ pub fn read_password_entry_with_progress(
     label: &str,
     report_progress: &mut dyn FnMut(PasswordEntryReadProgress),
 ) -> Result<String, PasswordEntryError> {
-    if Preferences::new().uses_integrated_backend() {
-        integrated::read_password_entry_with_progress(store_root, label, report_progress)
-    } else {
-        host::read_password_entry_with_progress(store_root, label)
+    let write_generation = store_write_generation();
+    if let Some(contents) = cached_password_entry(store_root, label, write_generation) {
+        return Ok(contents.to_string());
     }
+
+    let contents = store_backend().read_password_entry(store_root, label, report_progress)?;
+    cache_password_entry(store_root, label, &contents, write_generation);
+    publish_store_event(StoreEvent::Decrypted {
+        store: store_root.to_string(),
+        label: label.to_string(),
+    });
+    Ok(contents)
+}
+
+// Searching in contents reads every item for each query. Items it already
+// decrypted come from the cache, so typing a longer query doesn't run the
+// backend again. A search reads items in bulk, so it sends no events.
+pub fn read_password_entry_for_search(
+    store_root: &str,
+    label: &str,
+) -> Result<String, PasswordEntryError> {
+    let write_generation = store_write_generation();
+    if let Some(contents) = cached_search_entry(store_root, label, write_generation) {
+        return Ok(contents.to_string());
+    }
+
+    let contents = store_backend().read_password_entry(store_root, label, &mut |_| {})?;
+    cache_search_entry(store_root, label, &contents, write_generation);
+    Ok(contents)
+}
+
+// Decrypted search results stay only while a content search is open.
+pub fn forget_content_search_entries() {
+    clear_search_entry_cache();
 }
 
 pub fn password_entry_is_readable(store_root: &str, label: &str) -> bool {
@@ -319,6 +707,7 @@ pub fn store_recipients_private_key_requiring_unlock_for_relative_dir(
 }
 
 pub fn clear_runtime_secret_state() {
+    clear_password_entry_cache();
     integrated::clear_integrated_runtime_secret_state();
 }
 
@@ -0,0 +1,139 @@
+use crate::support::background::spawn_worker;
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+const MAX_PARALLEL_ENTRY_READ_WORKERS: usize = 4;
+
+fn parallel_entry_read_worker_count(entry_count: usize) -> usize {
+    if entry_count <= 1 {
+        return entry_count;
+    }
+
+    let available_parallelism = thread::available_parallelism()
+        .map(NonZeroUsize::get)
+        .unwrap_or(1);
+    entry_count
+        .min(available_parallelism)
+        .min(MAX_PARALLEL_ENTRY_READ_WORKERS)
+}
+
+// A read that panics gives `None` for its own request. The worker goes on
+// with the next one, so every request still gets a result in its place.
+fn read_one<Request, Output>(
+    read: &impl Fn(Request) -> Output,
+    request: Request,
+) -> Option<Output> {
+    catch_unwind(AssertUnwindSafe(|| read(request))).ok()
+}
+
+// Bulk tools like search indexing and the weak password scan decrypt every
+// item they look at. Workers pull the next request from a shared queue and
+// report back over a channel, so one slow item doesn't hold up the rest.
+// Results keep the order of the requests.
+pub fn read_password_entries_in_parallel<Request, Output, Read>(
+    requests: Vec<Request>,
+    read: Read,
+) -> Vec<Option<Output>>
+where
+    Request: Send + 'static,
+    Output: Send + 'static,
+    Read: Fn(Request) -> Output + Send + Sync + 'static,
+{
+    let worker_count = parallel_entry_read_worker_count(requests.len());
+    if worker_count <= 1 {
+        return requests
+            .into_iter()
+            .map(|request| read_one(&read, request))
+            .collect();
+    }
+
+    let (send, recv) = mpsc::channel();
+    let pending = Arc::new(Mutex::new(
+        requests.into_iter().enumerate().collect::<VecDeque<_>>(),
+    ));
+    let read = Arc::new(read);
+    let mut spawned_workers = 0usize;
+    for _ in 0..worker_count {
+        let send = send.clone();
+        let pending = pending.clone();
+        let read = read.clone();
+        if spawn_worker("parallel-entry-read", move || loop {
+            let next = {
+                let mut pending = pending.lock().expect("pending entry reads poisoned");
+                pending.pop_front()
+            };
+            let Some((index, request)) = next else {
+                return;
+            };
+            if send.send((index, read_one(&*read, request))).is_err() {
+                return;
+            }
+        })
+        .is_ok()
+        {
+            spawned_workers += 1;
+        }
+    }
+    drop(send);
+
+    let mut results = recv.into_iter().collect::<Vec<_>>();
+    if spawned_workers == 0 {
+        let remaining = pending
+            .lock()
+            .expect("pending entry reads poisoned")
+            .drain(..)
+            .collect::<Vec<_>>();
+        results.extend(
+            remaining
+                .into_iter()
+                .map(|(index, request)| (index, read_one(&*read, request))),
+        );
+    }
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, output)| output).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parallel_entry_read_worker_count, read_password_entries_in_parallel,
+        MAX_PARALLEL_ENTRY_READ_WORKERS,
+    };
+
+    #[test]
+    fn parallel_reads_keep_request_order() {
+        let requests = (0..64).collect::<Vec<_>>();
+        let results = read_password_entries_in_parallel(requests, |value| value * 2);
+
+        assert_eq!(
+            results,
+            (0..64).map(|value| Some(value * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn a_panicking_read_only_loses_its_own_result() {
+        let requests = (0..16).collect::<Vec<_>>();
+        let results = read_password_entries_in_parallel(requests, |value| {
+            assert_ne!(value, 5, "unreadable item");
+            value
+        });
+
+        assert_eq!(
+            results,
+            (0..16)
+                .map(|value| (value != 5).then_some(value))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn parallel_read_workers_are_bounded() {
+        assert_eq!(parallel_entry_read_worker_count(0), 0);
+        assert_eq!(parallel_entry_read_worker_count(1), 1);
+        assert!(parallel_entry_read_worker_count(1_000) <= MAX_PARALLEL_ENTRY_READ_WORKERS);
+    }
+}
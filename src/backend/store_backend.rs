@@ -0,0 +1,190 @@
+use super::{
+    dispatch_backend, host, integrated, PasswordEntryError, PasswordEntryReadProgress,
+    PasswordEntryWriteError, PasswordEntryWriteProgress,
+};
+use std::sync::{Arc, OnceLock, RwLock};
+
+// The item reads and writes the rest of the app needs from a store. Store
+// locking and read-only checks stay in front of it, so every backend gets
+// them for free.
+pub trait StoreBackend: Send + Sync {
+    fn read_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        report_progress: &mut dyn FnMut(PasswordEntryReadProgress),
+    ) -> Result<String, PasswordEntryError>;
+    fn read_password_line(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<String, PasswordEntryError>;
+    fn save_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        contents: &str,
+        overwrite: bool,
+        report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
+    ) -> Result<(), PasswordEntryWriteError>;
+    fn rename_password_entry(
+        &self,
+        store_root: &str,
+        old_label: &str,
+        new_label: &str,
+    ) -> Result<(), PasswordEntryWriteError>;
+    fn delete_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<(), PasswordEntryWriteError>;
+}
+
+// Picks the integrated or host backend from the preferences on every call,
+// so switching backends takes effect without a restart.
+struct SystemStoreBackend;
+
+impl StoreBackend for SystemStoreBackend {
+    fn read_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        report_progress: &mut dyn FnMut(PasswordEntryReadProgress),
+    ) -> Result<String, PasswordEntryError> {
+        dispatch_backend(
+            || integrated::read_password_entry_with_progress(store_root, label, report_progress),
+            || host::read_password_entry_with_progress(store_root, label),
+        )
+    }
+
+    fn read_password_line(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<String, PasswordEntryError> {
+        dispatch_backend(
+            || integrated::read_password_line(store_root, label),
+            || host::read_password_line(store_root, label),
+        )
+    }
+
+    fn save_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        contents: &str,
+        overwrite: bool,
+        report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
+    ) -> Result<(), PasswordEntryWriteError> {
+        dispatch_backend(
+            || {
+                integrated::save_password_entry_with_progress(
+                    store_root,
+                    label,
+                    contents,
+                    overwrite,
+                    report_progress,
+                )
+            },
+            || host::save_password_entry_with_progress(store_root, label, contents, overwrite),
+        )
+    }
+
+    fn rename_password_entry(
+        &self,
+        store_root: &str,
+        old_label: &str,
+        new_label: &str,
+    ) -> Result<(), PasswordEntryWriteError> {
+        dispatch_backend(
+            || integrated::rename_password_entry(store_root, old_label, new_label),
+            || host::rename_password_entry(store_root, old_label, new_label),
+        )
+    }
+
+    fn delete_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<(), PasswordEntryWriteError> {
+        dispatch_backend(
+            || integrated::delete_password_entry(store_root, label),
+            || host::delete_password_entry(store_root, label),
+        )
+    }
+}
+
+fn backend_cell() -> &'static RwLock<Arc<dyn StoreBackend>> {
+    static STORE_BACKEND: OnceLock<RwLock<Arc<dyn StoreBackend>>> = OnceLock::new();
+    STORE_BACKEND.get_or_init(|| RwLock::new(Arc::new(SystemStoreBackend)))
+}
+
+pub(super) fn store_backend() -> Arc<dyn StoreBackend> {
+    match backend_cell().read() {
+        Ok(backend) => backend.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+#[cfg(test)]
+pub(super) fn set_store_backend_for_tests(backend: Arc<dyn StoreBackend>) {
+    super::entry_cache::clear_password_entry_cache();
+    match backend_cell().write() {
+        Ok(mut current) => *current = backend,
+        Err(poisoned) => {
+            let mut current = poisoned.into_inner();
+            *current = backend;
+        }
+    }
+}
+
+#[cfg(test)]
+pub(super) fn reset_store_backend_for_tests() {
+    set_store_backend_for_tests(Arc::new(SystemStoreBackend));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::test_support::SystemBackendTestEnv;
+    use crate::backend::{
+        delete_password_entry, read_password_entry, read_password_line, rename_password_entry,
+        save_password_entry, PasswordEntryWriteError,
+    };
+    use crate::preferences::Preferences;
+
+    #[test]
+    #[expect(
+        clippy::significant_drop_tightening,
+        reason = "SystemBackendTestEnv must stay alive for the full test to keep the swapped backend in place."
+    )]
+    fn swapped_backends_serve_items_behind_the_read_only_check() {
+        let env = SystemBackendTestEnv::new();
+        let backend = env.use_memory_store_backend();
+        let store_root = env.store_root().to_string_lossy().to_string();
+
+        save_password_entry(&store_root, "team/mail", "hunter2\nusername: alice", false)
+            .expect("save entry");
+        assert!(matches!(
+            save_password_entry(&store_root, "team/mail", "other", false),
+            Err(PasswordEntryWriteError::EntryAlreadyExists(_))
+        ));
+        rename_password_entry(&store_root, "team/mail", "team/email").expect("rename entry");
+        assert_eq!(backend.labels(&store_root), vec!["team/email"]);
+        assert_eq!(
+            read_password_line(&store_root, "team/email").expect("read first line"),
+            "hunter2"
+        );
+
+        Preferences::new()
+            .set_store_read_only(&store_root, true)
+            .expect("mark store read-only");
+        assert!(matches!(
+            delete_password_entry(&store_root, "team/email"),
+            Err(PasswordEntryWriteError::ReadOnlyStore(_))
+        ));
+        assert_eq!(
+            read_password_entry(&store_root, "team/email").expect("read entry"),
+            "hunter2\nusername: alice"
+        );
+    }
+}
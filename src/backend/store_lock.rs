@@ -0,0 +1,129 @@
+use crate::logging::log_error;
+use crate::support::secure_fs::ensure_private_dir;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const STORE_LOCKS_DIR_NAME: &str = "keycord-store-locks";
+const STORE_LOCK_WAIT: Duration = Duration::from_secs(5);
+const STORE_LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// Held for the whole write, including its Git commit. The operating system
+// drops the lock with the file handle, so a crashed instance never leaves a
+// store locked.
+pub(super) struct StoreLock {
+    _file: File,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct StoreBusy;
+
+fn store_locks_dir() -> PathBuf {
+    dirs_next::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(STORE_LOCKS_DIR_NAME)
+}
+
+// Lock files live outside the store so they never show up in Git or in the
+// list. Every instance resolves the same store to the same file.
+fn store_lock_path_in(dir: &Path, store_root: &str) -> PathBuf {
+    let store = fs::canonicalize(store_root).unwrap_or_else(|_| PathBuf::from(store_root));
+    let digest = Sha256::digest(store.to_string_lossy().as_bytes());
+    let mut name = String::with_capacity(digest.len() * 2 + 5);
+    for byte in digest {
+        let _ = write!(&mut name, "{byte:02x}");
+    }
+    name.push_str(".lock");
+    dir.join(name)
+}
+
+fn open_lock_file(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent() {
+        ensure_private_dir(parent)?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)
+}
+
+// A lock that can't be set up is logged and skipped, so a missing runtime
+// folder never blocks saving.
+fn lock_store_at(path: &Path, wait: Duration) -> Result<Option<StoreLock>, StoreBusy> {
+    let file = match open_lock_file(path) {
+        Ok(file) => file,
+        Err(err) => {
+            log_error(format!(
+                "Failed to open store lock '{}': {err}",
+                path.display()
+            ));
+            return Ok(None);
+        }
+    };
+
+    let started = Instant::now();
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(Some(StoreLock { _file: file })),
+            Err(TryLockError::WouldBlock) if started.elapsed() < wait => {
+                thread::sleep(STORE_LOCK_POLL_INTERVAL);
+            }
+            Err(TryLockError::WouldBlock) => return Err(StoreBusy),
+            Err(TryLockError::Error(err)) => {
+                log_error(format!(
+                    "Failed to lock store lock '{}': {err}",
+                    path.display()
+                ));
+                return Ok(None);
+            }
+        }
+    }
+}
+
+pub(super) fn lock_store(store_root: &str) -> Result<Option<StoreLock>, StoreBusy> {
+    lock_store_at(
+        &store_lock_path_in(&store_locks_dir(), store_root),
+        STORE_LOCK_WAIT,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lock_store_at, store_lock_path_in, StoreBusy};
+    use std::time::Duration;
+
+    #[test]
+    fn store_lock_paths_are_stable_per_store() {
+        let dir = std::env::temp_dir();
+        let first = store_lock_path_in(&dir, "/missing/stores/team");
+        assert_eq!(first, store_lock_path_in(&dir, "/missing/stores/team"));
+        assert_ne!(first, store_lock_path_in(&dir, "/missing/stores/personal"));
+        assert_eq!(
+            first.extension().and_then(|extension| extension.to_str()),
+            Some("lock")
+        );
+    }
+
+    #[test]
+    fn second_store_lock_reports_busy_until_the_first_is_dropped() {
+        let dir = std::env::temp_dir().join(format!("keycord-store-lock-{}", std::process::id()));
+        let path = store_lock_path_in(&dir, "/missing/stores/team");
+
+        let first = lock_store_at(&path, Duration::ZERO).expect("first lock");
+        assert!(first.is_some());
+        assert!(matches!(
+            lock_store_at(&path, Duration::ZERO),
+            Err(StoreBusy)
+        ));
+
+        drop(first);
+        assert!(lock_store_at(&path, Duration::ZERO)
+            .expect("lock after release")
+            .is_some());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
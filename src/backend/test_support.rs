@@ -1,6 +1,15 @@
+use crate::backend::entry_cache::clear_password_entry_cache;
 use crate::backend::integrated::clear_cached_unlocked_ripasso_private_keys;
+use crate::backend::store_backend::{
+    reset_store_backend_for_tests, set_store_backend_for_tests, StoreBackend,
+};
+use crate::backend::{
+    PasswordEntryError, PasswordEntryReadProgress, PasswordEntryWriteError,
+    PasswordEntryWriteProgress,
+};
 use ripasso::crypto::{Crypto, Sequoia};
 use sequoia_openpgp::{cert::CertBuilder, serialize::Serialize, Cert};
+use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsString;
 use std::fs;
@@ -20,6 +29,7 @@ fn test_lock() -> &'static Mutex<()> {
 
 fn reset_backend_test_state() {
     clear_cached_unlocked_ripasso_private_keys();
+    clear_password_entry_cache();
 }
 
 fn command_error(action: &str, output: &Output) -> String {
@@ -103,6 +113,44 @@ fn git_status_porcelain(path: &Path) -> Result<String, String> {
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+fn run_git(path: &Path, args: &[&str]) -> Result<String, String> {
+    let output = ensure_success(
+        &format!("git {}", args.join(" ")),
+        Command::new("git")
+            .args(["-C"])
+            .arg(path)
+            .args(args)
+            .output()
+            .map_err(|err| format!("Failed to start git: {err}"))?,
+    )?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn import_secret_key(bytes: &[u8]) -> Result<(), String> {
+    let mut child = Command::new("gpg")
+        .args(["--batch", "--yes", "--import"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Failed to start gpg secret-key import: {err}"))?;
+
+    {
+        let stdin = child
+            .stdin
+            .as_mut()
+            .ok_or_else(|| "gpg secret-key import did not provide stdin".to_string())?;
+        stdin
+            .write_all(bytes)
+            .map_err(|err| format!("Failed to write imported secret key bytes: {err}"))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("Failed to wait for gpg secret-key import: {err}"))?;
+    ensure_success("gpg secret-key import", output).map(|_| ())
+}
+
 fn import_public_key(bytes: &[u8]) -> Result<(), String> {
     let mut child = Command::new("gpg")
         .args(["--batch", "--yes", "--import"])
@@ -207,6 +255,99 @@ pub struct GeneratedSecretKey {
     pub public_key_bytes: Vec<u8>,
 }
 
+// Items kept in memory, keyed by store and label, so the list, search and
+// save flows can run without GPG or Git.
+#[derive(Default)]
+pub struct MemoryStoreBackend {
+    entries: Mutex<BTreeMap<(String, String), String>>,
+}
+
+impl MemoryStoreBackend {
+    fn entries(&self) -> MutexGuard<'_, BTreeMap<(String, String), String>> {
+        match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    pub fn labels(&self, store_root: &str) -> Vec<String> {
+        self.entries()
+            .keys()
+            .filter(|(root, _)| root == store_root)
+            .map(|(_, label)| label.clone())
+            .collect()
+    }
+}
+
+impl StoreBackend for MemoryStoreBackend {
+    fn read_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        _report_progress: &mut dyn FnMut(PasswordEntryReadProgress),
+    ) -> Result<String, PasswordEntryError> {
+        self.entries()
+            .get(&(store_root.to_string(), label.to_string()))
+            .cloned()
+            .ok_or_else(|| PasswordEntryError::EntryNotFound(label.to_string()))
+    }
+
+    fn read_password_line(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<String, PasswordEntryError> {
+        let contents = self.read_password_entry(store_root, label, &mut |_| {})?;
+        Ok(contents.lines().next().unwrap_or_default().to_string())
+    }
+
+    fn save_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+        contents: &str,
+        overwrite: bool,
+        _report_progress: &mut dyn FnMut(PasswordEntryWriteProgress),
+    ) -> Result<(), PasswordEntryWriteError> {
+        let mut entries = self.entries();
+        let key = (store_root.to_string(), label.to_string());
+        if !overwrite && entries.contains_key(&key) {
+            return Err(PasswordEntryWriteError::already_exists(label));
+        }
+        entries.insert(key, contents.to_string());
+        Ok(())
+    }
+
+    fn rename_password_entry(
+        &self,
+        store_root: &str,
+        old_label: &str,
+        new_label: &str,
+    ) -> Result<(), PasswordEntryWriteError> {
+        let mut entries = self.entries();
+        let new_key = (store_root.to_string(), new_label.to_string());
+        if entries.contains_key(&new_key) {
+            return Err(PasswordEntryWriteError::already_exists(new_label));
+        }
+        let contents = entries
+            .remove(&(store_root.to_string(), old_label.to_string()))
+            .ok_or_else(|| PasswordEntryWriteError::entry_not_found(old_label))?;
+        entries.insert(new_key, contents);
+        Ok(())
+    }
+
+    fn delete_password_entry(
+        &self,
+        store_root: &str,
+        label: &str,
+    ) -> Result<(), PasswordEntryWriteError> {
+        self.entries()
+            .remove(&(store_root.to_string(), label.to_string()))
+            .map(|_| ())
+            .ok_or_else(|| PasswordEntryWriteError::entry_not_found(label))
+    }
+}
+
 pub struct SystemBackendTestEnv {
     _guard: MutexGuard<'static, ()>,
     original_home: Option<OsString>,
@@ -273,6 +414,13 @@ impl SystemBackendTestEnv {
         env::remove_var("GPG_AGENT_INFO");
     }
 
+    // Stays in place until the env is dropped, which also holds the test lock.
+    pub fn use_memory_store_backend(&self) -> Arc<MemoryStoreBackend> {
+        let backend = Arc::new(MemoryStoreBackend::default());
+        set_store_backend_for_tests(backend.clone());
+        backend
+    }
+
     pub fn init_store_git_repository(&self) -> Result<(), String> {
         init_git_repository(self.store_root())
     }
@@ -297,6 +445,40 @@ impl SystemBackendTestEnv {
         verify_git_head_signature(self.store_root())
     }
 
+    // A bare repository next to the store, added as a remote and seeded with
+    // the store's current branch, so sync has somewhere real to talk to.
+    pub fn add_store_bare_remote(&self, name: &str) -> Result<PathBuf, String> {
+        let remote = self.root.join(format!("{name}.git"));
+        fs::create_dir_all(&remote)
+            .map_err(|err| format!("Failed to create bare remote dir: {err}"))?;
+        run_git(&remote, &["init", "--bare"])?;
+        let branch = run_git(self.store_root(), &["symbolic-ref", "--short", "HEAD"])?;
+        run_git(
+            &remote,
+            &["symbolic-ref", "HEAD", &format!("refs/heads/{branch}")],
+        )?;
+        let url = remote.to_string_lossy().to_string();
+        run_git(self.store_root(), &["remote", "add", name, &url])?;
+        run_git(self.store_root(), &["push", name, "HEAD"])?;
+        Ok(remote)
+    }
+
+    // A second working copy of a remote, standing in for another device.
+    pub fn clone_store_remote(&self, remote: &Path, name: &str) -> Result<PathBuf, String> {
+        let clone = self.root.join(name);
+        run_git(
+            &self.root,
+            &["clone", &remote.to_string_lossy(), &clone.to_string_lossy()],
+        )?;
+        run_git(&clone, &["config", "user.name", "PasswordStore Tests"])?;
+        run_git(&clone, &["config", "user.email", "tests@example.com"])?;
+        Ok(clone)
+    }
+
+    pub fn git(path: &Path, args: &[&str]) -> Result<String, String> {
+        run_git(path, args)
+    }
+
     pub fn generate_secret_key(user_id: &str) -> Result<GeneratedSecretKey, String> {
         let (cert, _) = CertBuilder::general_purpose(Some(user_id))
             .generate()
@@ -319,6 +501,15 @@ impl SystemBackendTestEnv {
         })
     }
 
+    pub fn import_secret_key(key: &GeneratedSecretKey) -> Result<(), String> {
+        let mut bytes = Vec::new();
+        key.cert
+            .as_tsk()
+            .serialize(&mut bytes)
+            .map_err(|err| format!("Failed to serialize test secret key: {err}"))?;
+        import_secret_key(&bytes)
+    }
+
     pub fn import_public_key(bytes: &[u8]) -> Result<(), String> {
         import_public_key(bytes)
     }
@@ -331,6 +522,7 @@ impl SystemBackendTestEnv {
 impl Drop for SystemBackendTestEnv {
     fn drop(&mut self) {
         reset_backend_test_state();
+        reset_store_backend_for_tests();
         if let Some(home) = self.original_home.as_ref() {
             env::set_var("HOME", home);
         } else {
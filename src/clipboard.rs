@@ -1,21 +1,34 @@
-use crate::backend::{
-    preferred_ripasso_private_key_fingerprint_for_entry, read_password_line, PasswordEntryError,
-};
+use crate::backend::{preferred_ripasso_private_key_fingerprint_for_entry, PasswordEntryError};
 use crate::i18n::gettext;
-use crate::logging::{log_error, run_command_status, CommandLogOptions};
-use crate::password::model::PassEntry;
-use crate::preferences::Preferences;
+use crate::logging::log_error;
+use crate::password::alias::read_password_entry_following_alias;
+use crate::password::file::pass_file_clipboard_text;
+use crate::password::model::{OpenPassFile, PassEntry};
+use crate::password::otp::current_totp_code;
+use crate::preferences::{Preferences, UsernameFallbackMode};
 use crate::private_key::unlock::prompt_private_key_unlock_for_action;
-use crate::support::background::{spawn_result_task, spawn_worker};
+use crate::support::activity::record_activity;
+use crate::support::background::spawn_result_task;
 use crate::support::ui::flat_icon_button_with_tooltip;
-use adw::gtk::{gdk::Display, Button, Widget};
+use adw::gio::{self, Notification};
+use adw::gtk::{
+    gdk::{Clipboard, Display},
+    Button, Widget,
+};
 use adw::{glib, prelude::*, EntryRow, PasswordEntryRow, Toast, ToastOverlay};
+use std::cell::Cell;
 use std::rc::Rc;
 use std::time::Duration;
+use zeroize::Zeroizing;
 
 const COPY_BUTTON_ICON_NAME: &str = "edit-copy-symbolic";
 const COPIED_BUTTON_ICON_NAME: &str = "object-select-symbolic";
 const COPY_BUTTON_FEEDBACK_MS: u64 = 1200;
+const COPIED_NOTIFICATION_ID: &str = "password-copied";
+
+thread_local! {
+    static CLIPBOARD_GENERATION: Cell<u64> = const { Cell::new(0) };
+}
 
 fn show_clipboard_unavailable_toast(overlay: &ToastOverlay) {
     overlay.add_toast(Toast::new(&gettext("Clipboard unavailable.")));
@@ -38,6 +51,56 @@ pub fn show_copy_feedback(button: &Button) {
     });
 }
 
+fn copied_notification_body(clear_seconds: u32) -> Option<String> {
+    (clear_seconds > 0).then(|| {
+        gettext("The clipboard clears in {seconds} seconds.")
+            .replace("{seconds}", &clear_seconds.to_string())
+    })
+}
+
+fn send_copied_notification(item: &PassEntry, clear_seconds: u32) {
+    let Some(app) = gio::Application::default() else {
+        return;
+    };
+
+    let notification =
+        Notification::new(&gettext("Password for {item} copied").replace("{item}", &item.basename));
+    if let Some(body) = copied_notification_body(clear_seconds) {
+        notification.set_body(Some(&body));
+    }
+    app.send_notification(Some(COPIED_NOTIFICATION_ID), &notification);
+}
+
+fn schedule_clipboard_clear(clipboard: Clipboard) {
+    let generation = CLIPBOARD_GENERATION.with(|current| {
+        let next = current.get().wrapping_add(1);
+        current.set(next);
+        next
+    });
+    let seconds = Preferences::new().clipboard_clear_seconds();
+    if seconds == 0 {
+        return;
+    }
+
+    glib::timeout_add_seconds_local_once(seconds, move || {
+        if CLIPBOARD_GENERATION.with(Cell::get) == generation && clipboard.is_local() {
+            clipboard.set_text("");
+        }
+    });
+}
+
+pub fn clear_copied_secret() {
+    let Some(display) = Display::default() else {
+        return;
+    };
+
+    let clipboard = display.clipboard();
+    if clipboard.is_local() {
+        CLIPBOARD_GENERATION.with(|current| current.set(current.get().wrapping_add(1)));
+        clipboard.set_text("");
+    }
+}
+
 pub fn set_clipboard_text(text: &str, overlay: &ToastOverlay, button: Option<&Button>) -> bool {
     Display::default().map_or_else(
         || {
@@ -47,6 +110,7 @@ pub fn set_clipboard_text(text: &str, overlay: &ToastOverlay, button: Option<&Bu
         |display| {
             let clipboard = display.clipboard();
             clipboard.set_text(text);
+            schedule_clipboard_clear(clipboard);
             if let Some(button) = button {
                 show_copy_feedback(button);
             }
@@ -62,7 +126,7 @@ where
     let overlay = overlay.clone();
     let feedback_button = button.clone();
     button.connect_clicked(move |_| {
-        let text = text();
+        let text = Zeroizing::new(text());
         let _ = set_clipboard_text(&text, &overlay, Some(&feedback_button));
     });
 }
@@ -81,24 +145,32 @@ where
     }
 }
 
-fn copy_password_entry_to_clipboard_via_pass_command(item: PassEntry, button: Option<&Button>) {
-    if let Some(button) = button {
-        show_copy_feedback(button);
-    }
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CopiedPart {
+    Password,
+    Line(usize),
+    Username,
+    Otp,
+}
 
-    if let Err(err) = spawn_worker("clipboard-pass-copy", move || {
-        let settings = Preferences::new();
-        let mut cmd = settings.command();
-        cmd.env("PASSWORD_STORE_DIR", &item.store_path)
-            .arg("-c")
-            .arg(item.label());
-        let _ = run_command_status(
-            &mut cmd,
-            "Copy password to clipboard",
-            CommandLogOptions::SENSITIVE,
-        );
-    }) {
-        log_error(format!("Failed to spawn clipboard copy worker: {err}"));
+fn copied_part_text(
+    item: &PassEntry,
+    contents: &str,
+    part: CopiedPart,
+    username_fallback_mode: UsernameFallbackMode,
+) -> Option<String> {
+    match part {
+        CopiedPart::Password => pass_file_clipboard_text(contents, None),
+        CopiedPart::Line(line) => pass_file_clipboard_text(contents, Some(line)),
+        CopiedPart::Username => {
+            let mut pass_file = OpenPassFile::new_with_mode(item.clone(), username_fallback_mode);
+            pass_file.refresh_from_contents(contents);
+            pass_file
+                .username()
+                .filter(|username| !username.trim().is_empty())
+                .map(str::to_string)
+        }
+        CopiedPart::Otp => current_totp_code(contents),
     }
 }
 
@@ -107,6 +179,8 @@ fn handle_copy_password_error(
     overlay: &ToastOverlay,
     button: Option<&Button>,
     error: &PasswordEntryError,
+    notify: bool,
+    part: CopiedPart,
 ) -> bool {
     if !matches!(error, PasswordEntryError::LockedPrivateKey(_)) {
         return false;
@@ -122,10 +196,12 @@ fn handle_copy_password_error(
                 overlay,
                 fingerprint,
                 Rc::new(move || {
-                    copy_password_entry_to_clipboard_via_read(
+                    copy_password_entry_via_read(
                         retry_item.clone(),
                         retry_overlay.clone(),
                         retry_button.clone(),
+                        notify,
+                        part,
                     );
                 }),
                 Rc::new(move |success| {
@@ -145,30 +221,66 @@ fn handle_copy_password_error(
     }
 }
 
-pub fn copy_password_entry_to_clipboard_via_read(
+// Both backends read the item here instead of leaving the copy to `pass -c`,
+// so a `clip:` line picks the copied value the same way everywhere.
+fn copy_password_entry_via_read(
     item: PassEntry,
     overlay: ToastOverlay,
     button: Option<Button>,
+    notify: bool,
+    part: CopiedPart,
 ) {
     set_copy_button_loading(button.as_ref(), true);
+    let username_fallback_mode = Preferences::new().username_fallback_mode();
     let overlay_for_disconnect = overlay.clone();
     let button_for_disconnect = button.clone();
     let task_item = item.clone();
     spawn_result_task(
         move || {
             let label = task_item.label();
-            read_password_line(&task_item.store_path, &label)
+            let (_, contents) =
+                read_password_entry_following_alias(&task_item.store_path, &label, &mut |_| {})?;
+            Ok(
+                copied_part_text(&task_item, &contents, part, username_fallback_mode)
+                    .map(Zeroizing::new),
+            )
         },
-        move |result| match result {
-            Ok(password) => {
-                if set_clipboard_text(&password, &overlay, button.as_ref()) {
+        move |result: Result<Option<Zeroizing<String>>, PasswordEntryError>| match result {
+            Ok(Some(text)) if part == CopiedPart::Password && text.trim().is_empty() => {
+                set_copy_button_loading(button.as_ref(), false);
+                overlay.add_toast(Toast::new(&gettext("This item has no password.")));
+            }
+            Ok(Some(text)) => {
+                if set_clipboard_text(&text, &overlay, button.as_ref()) {
                     overlay.add_toast(Toast::new(&gettext("Copied.")));
+                    record_activity(match part {
+                        CopiedPart::Password => format!("Copied the password of {}", item.label()),
+                        CopiedPart::Line(line) => format!("Copied line {line} of {}", item.label()),
+                        CopiedPart::Username => format!("Copied the username of {}", item.label()),
+                        CopiedPart::Otp => format!("Copied the OTP code of {}", item.label()),
+                    });
+                    if notify {
+                        send_copied_notification(
+                            &item,
+                            Preferences::new().clipboard_clear_seconds(),
+                        );
+                    }
                 }
                 set_copy_button_loading(button.as_ref(), false);
             }
+            Ok(None) => {
+                set_copy_button_loading(button.as_ref(), false);
+                let message = match part {
+                    CopiedPart::Username => "This item has no username.",
+                    CopiedPart::Otp => "This item has no time-based OTP code.",
+                    CopiedPart::Password | CopiedPart::Line(_) => "That line doesn't exist.",
+                };
+                overlay.add_toast(Toast::new(&gettext(message)));
+            }
             Err(err) => {
                 log_error(format!("Failed to copy password entry: {err}"));
-                if handle_copy_password_error(&item, &overlay, button.as_ref(), &err) {
+                if handle_copy_password_error(&item, &overlay, button.as_ref(), &err, notify, part)
+                {
                     return;
                 }
                 set_copy_button_loading(button.as_ref(), false);
@@ -187,10 +299,74 @@ pub fn copy_password_entry_to_clipboard(
     overlay: ToastOverlay,
     button: Option<Button>,
 ) {
-    let settings = Preferences::new();
-    if settings.uses_integrated_backend() {
-        copy_password_entry_to_clipboard_via_read(item, overlay, button);
-    } else {
-        copy_password_entry_to_clipboard_via_pass_command(item, button.as_ref());
+    copy_password_entry_via_read(item, overlay, button, false, CopiedPart::Password);
+}
+
+// Lines count from 1, like `pass -c N`.
+pub fn copy_password_entry_line_to_clipboard(item: PassEntry, overlay: ToastOverlay, line: usize) {
+    copy_password_entry_via_read(item, overlay, None, false, CopiedPart::Line(line));
+}
+
+// The username field wins over the folder or file name, like on the item page.
+pub fn copy_password_entry_username_to_clipboard(item: PassEntry, overlay: ToastOverlay) {
+    copy_password_entry_via_read(item, overlay, None, false, CopiedPart::Username);
+}
+
+// Counter-based codes are only handed out from the item page, which saves
+// the raised counter.
+pub fn copy_password_entry_otp_to_clipboard(item: PassEntry, overlay: ToastOverlay) {
+    copy_password_entry_via_read(item, overlay, None, false, CopiedPart::Otp);
+}
+
+pub fn copy_password_entry_with_notification(item: PassEntry, overlay: ToastOverlay) {
+    copy_password_entry_via_read(item, overlay, None, true, CopiedPart::Password);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{copied_notification_body, copied_part_text, CopiedPart};
+    use crate::password::model::PassEntry;
+    use crate::preferences::UsernameFallbackMode;
+
+    #[test]
+    fn copied_notification_mentions_auto_clear_only_when_enabled() {
+        assert_eq!(copied_notification_body(0), None);
+        assert_eq!(
+            copied_notification_body(30),
+            Some("The clipboard clears in 30 seconds.".to_string())
+        );
+    }
+
+    #[test]
+    fn copied_usernames_prefer_the_field_over_the_path() {
+        let item = PassEntry::from_label("/tmp/store", "mail/alice/github");
+
+        assert_eq!(
+            copied_part_text(
+                &item,
+                "secret\nuser: bob",
+                CopiedPart::Username,
+                UsernameFallbackMode::Folder
+            ),
+            Some("bob".to_string())
+        );
+        assert_eq!(
+            copied_part_text(
+                &item,
+                "secret",
+                CopiedPart::Username,
+                UsernameFallbackMode::Folder
+            ),
+            Some("alice".to_string())
+        );
+        assert_eq!(
+            copied_part_text(
+                &item,
+                "secret\nuser:",
+                CopiedPart::Username,
+                UsernameFallbackMode::Folder
+            ),
+            None
+        );
     }
 }
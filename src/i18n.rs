@@ -34,6 +34,45 @@ pub fn gettext(message: &str) -> String {
     }
 }
 
+pub fn ngettext(singular: &str, plural: &str, count: usize) -> String {
+    #[cfg(target_os = "linux")]
+    {
+        linux::ngettext(singular, plural, count)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        untranslated_plural(singular, plural, count).to_string()
+    }
+}
+
+// Short words like "Empty" can need different translations depending on
+// what they describe, so the context travels with the message.
+pub fn pgettext(context: &str, message: &str) -> String {
+    if message.is_empty() {
+        return String::new();
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        linux::pgettext(context, message)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = context;
+        message.to_string()
+    }
+}
+
+const fn untranslated_plural<'a>(singular: &'a str, plural: &'a str, count: usize) -> &'a str {
+    if count == 1 {
+        singular
+    } else {
+        plural
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn available_locales() -> impl Iterator<Item = &'static str> {
     AVAILABLE_LOCALES
@@ -92,8 +131,8 @@ fn preferred_locale_dir() -> PathBuf {
 
 #[cfg(target_os = "linux")]
 mod linux {
-    use super::{domain, preferred_locale_dir};
-    use libc::{c_char, LC_ALL};
+    use super::{domain, preferred_locale_dir, untranslated_plural};
+    use libc::{c_char, c_ulong, LC_ALL};
     use std::ffi::{CStr, CString};
     use std::sync::Once;
 
@@ -111,6 +150,12 @@ mod linux {
         fn textdomain_raw(domainname: *const c_char) -> *mut c_char;
         #[link_name = "gettext"]
         fn gettext_raw(msgid: *const c_char) -> *mut c_char;
+        #[link_name = "ngettext"]
+        fn ngettext_raw(
+            msgid: *const c_char,
+            msgid_plural: *const c_char,
+            n: c_ulong,
+        ) -> *mut c_char;
         fn setlocale(category: libc::c_int, locale: *const c_char) -> *mut c_char;
     }
 
@@ -136,6 +181,33 @@ mod linux {
         init();
         translate(message, |message| unsafe { gettext_raw(message) })
     }
+    pub fn ngettext(singular: &str, plural: &str, count: usize) -> String {
+        init();
+        let (Ok(singular_c), Ok(plural_c)) = (CString::new(singular), CString::new(plural)) else {
+            return untranslated_plural(singular, plural, count).to_string();
+        };
+        let n = c_ulong::try_from(count).unwrap_or(c_ulong::MAX);
+
+        unsafe {
+            let translated = ngettext_raw(singular_c.as_ptr(), plural_c.as_ptr(), n);
+            if translated.is_null() {
+                return untranslated_plural(singular, plural, count).to_string();
+            }
+
+            CStr::from_ptr(translated).to_string_lossy().into_owned()
+        }
+    }
+
+    pub fn pgettext(context: &str, message: &str) -> String {
+        let key = format!("{context}\u{4}{message}");
+        let translated = gettext(&key);
+        if translated == key {
+            message.to_string()
+        } else {
+            translated
+        }
+    }
+
     fn translate(message: &str, translate: impl FnOnce(*const c_char) -> *mut c_char) -> String {
         let Ok(message) = CString::new(message) else {
             return message.to_string();
@@ -1,6 +1,8 @@
 mod run;
 mod streams;
 
+use std::time::Duration;
+
 pub use self::run::run_command_output;
 pub use self::run::run_command_status;
 pub use self::run::run_command_with_input;
@@ -11,6 +13,7 @@ pub struct CommandLogOptions {
     pub redact_stderr: bool,
     pub redact_stdin: bool,
     pub accepted_exit_codes: &'static [i32],
+    pub timeout: Option<Duration>,
 }
 
 impl CommandLogOptions {
@@ -19,6 +22,7 @@ impl CommandLogOptions {
         redact_stderr: false,
         redact_stdin: false,
         accepted_exit_codes: &[],
+        timeout: None,
     };
 
     pub const SENSITIVE: Self = Self {
@@ -26,5 +30,6 @@ impl CommandLogOptions {
         redact_stderr: true,
         redact_stdin: true,
         accepted_exit_codes: &[],
+        timeout: None,
     };
 }
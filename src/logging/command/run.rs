@@ -1,7 +1,9 @@
+use super::super::span::log_span;
 use super::super::store::{log_error, log_info};
 use super::streams::{join_stream_logger, spawn_stream_logger};
 use super::CommandLogOptions;
 use crate::support::background::spawn_worker_or_panic;
+use crate::support::process::{prepare_command_timeout, wait_for_child};
 use std::ffi::OsStr;
 use std::io;
 use std::io::Write;
@@ -102,7 +104,9 @@ fn run_command_output_inner(
 ) -> io::Result<Output> {
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    prepare_command_timeout(cmd, options.timeout);
     let command = describe_command(cmd);
+    let _span = log_span(format!("{context}\n$ {command}"));
 
     match cmd.spawn() {
         Ok(mut child) => {
@@ -127,7 +131,7 @@ fn run_command_output_inner(
                 )
             });
 
-            let status = match child.wait() {
+            let status = match wait_for_child(&mut child, options.timeout) {
                 Ok(status) => status,
                 Err(err) => {
                     log_error(format!("{context}\n$ {command}\nfailed to wait: {err}"));
@@ -202,7 +206,9 @@ pub fn run_command_with_input(
     cmd.stdin(Stdio::piped());
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    prepare_command_timeout(cmd, options.timeout);
     let command = describe_command(cmd);
+    let _span = log_span(format!("{context}\n$ {command}"));
 
     let mut child = match cmd.spawn() {
         Ok(child) => child,
@@ -247,7 +253,7 @@ pub fn run_command_with_input(
     };
     let input_writer = spawn_input_writer(stdin, input.to_string());
 
-    let status = match child.wait() {
+    let status = match wait_for_child(&mut child, options.timeout) {
         Ok(status) => status,
         Err(err) => {
             log_error(format!("{context}\n$ {command}\nfailed to wait: {err}"));
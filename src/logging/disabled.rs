@@ -1,6 +1,9 @@
 use crate::support::background::spawn_worker;
-use std::io::{self, Write};
+use crate::support::process::{prepare_command_timeout, wait_for_child};
+use std::io::{self, Read, Write};
 use std::process::{Command, ExitStatus, Output, Stdio};
+use std::thread;
+use std::time::Duration;
 
 pub fn log_info(message: impl Into<String>) {
     let _ = sanitize_diagnostic_message(&message.into());
@@ -10,6 +13,23 @@ pub fn log_error(message: impl Into<String>) {
     let _ = sanitize_diagnostic_message(&message.into());
 }
 
+pub fn log_debug(message: impl Into<String>) {
+    let _ = message.into();
+}
+
+pub fn set_verbose_logging(_verbose: bool) {}
+
+pub fn verbose_logging() -> bool {
+    false
+}
+
+pub struct LogSpan;
+
+pub fn log_span(name: impl Into<String>) -> LogSpan {
+    let _ = name.into();
+    LogSpan
+}
+
 pub fn log_snapshot() -> (usize, usize, String) {
     (0, 0, String::new())
 }
@@ -24,6 +44,7 @@ pub struct CommandLogOptions {
     pub redact_stderr: bool,
     pub redact_stdin: bool,
     pub accepted_exit_codes: &'static [i32],
+    pub timeout: Option<Duration>,
 }
 
 impl CommandLogOptions {
@@ -32,6 +53,7 @@ impl CommandLogOptions {
         redact_stderr: false,
         redact_stdin: false,
         accepted_exit_codes: &[],
+        timeout: None,
     };
 
     pub const SENSITIVE: Self = Self {
@@ -39,6 +61,7 @@ impl CommandLogOptions {
         redact_stderr: true,
         redact_stdin: true,
         accepted_exit_codes: &[],
+        timeout: None,
     };
 }
 
@@ -51,13 +74,59 @@ fn consume_command_log_options(options: CommandLogOptions) {
     );
 }
 
+fn spawn_stream_reader<R>(stream: Option<R>) -> io::Result<thread::JoinHandle<io::Result<Vec<u8>>>>
+where
+    R: Read + Send + 'static,
+{
+    spawn_worker("disabled-command-stream-reader", move || {
+        let mut bytes = Vec::new();
+        if let Some(mut stream) = stream {
+            stream.read_to_end(&mut bytes)?;
+        }
+        Ok(bytes)
+    })
+}
+
+fn join_stream_reader(handle: thread::JoinHandle<io::Result<Vec<u8>>>) -> io::Result<Vec<u8>> {
+    handle
+        .join()
+        .unwrap_or_else(|_| Err(io::Error::other("command output reader panicked")))
+}
+
+// `Child::wait_with_output` can't give up, so commands with a time limit
+// read their streams on workers and wait here instead.
+fn wait_with_output_and_timeout(
+    mut child: std::process::Child,
+    timeout: Option<Duration>,
+) -> io::Result<Output> {
+    if timeout.is_none() {
+        return child.wait_with_output();
+    }
+
+    let stdout = spawn_stream_reader(child.stdout.take())?;
+    let stderr = spawn_stream_reader(child.stderr.take())?;
+    let status = wait_for_child(&mut child, timeout)?;
+    Ok(Output {
+        status,
+        stdout: join_stream_reader(stdout)?,
+        stderr: join_stream_reader(stderr)?,
+    })
+}
+
 pub fn run_command_output(
     cmd: &mut Command,
     _context: &str,
     options: CommandLogOptions,
 ) -> io::Result<Output> {
     consume_command_log_options(options);
-    cmd.output()
+    if options.timeout.is_none() {
+        return cmd.output();
+    }
+
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    prepare_command_timeout(cmd, options.timeout);
+    wait_with_output_and_timeout(cmd.spawn()?, options.timeout)
 }
 
 pub fn run_command_status(
@@ -79,6 +148,7 @@ pub fn run_command_with_input(
     cmd.stdin(Stdio::piped());
     cmd.stdout(Stdio::piped());
     cmd.stderr(Stdio::piped());
+    prepare_command_timeout(cmd, options.timeout);
 
     let mut child = cmd
         .spawn()
@@ -94,8 +164,7 @@ pub fn run_command_with_input(
     })
     .map_err(|err| format!("Failed to spawn command input writer: {err}"))?;
 
-    let output = child
-        .wait_with_output()
+    let output = wait_with_output_and_timeout(child, options.timeout)
         .map_err(|err| format!("Failed to wait for command: {err}"))?;
     match writer.join() {
         Ok(Ok(())) => Ok(output),
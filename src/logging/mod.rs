@@ -1,18 +1,25 @@
 mod command;
+mod span;
 mod store;
 
 pub use command::run_command_status;
 pub use command::run_command_with_input;
 pub use command::{run_command_output, CommandLogOptions};
+pub use span::log_span;
+pub use store::log_debug;
 pub use store::log_error;
 pub use store::log_info;
 pub use store::log_snapshot;
 #[cfg(feature = "legacy-compat")]
 pub(crate) use store::sanitize_diagnostic_message;
+pub use store::{set_verbose_logging, verbose_logging};
 
 #[cfg(test)]
 mod tests {
-    use super::{log_error, log_snapshot, run_command_output, CommandLogOptions};
+    use super::{
+        log_debug, log_error, log_snapshot, log_span, run_command_output, set_verbose_logging,
+        CommandLogOptions,
+    };
     use crate::logging::store::log_info;
     use std::process::Command;
 
@@ -32,6 +39,20 @@ mod tests {
         assert!(text_after.contains("second log line"));
     }
 
+    #[test]
+    fn debug_entries_and_spans_need_verbose_logging() {
+        let marker = format!("verbose-log-test-{}", std::process::id());
+        log_debug(format!("{marker} quiet"));
+        set_verbose_logging(true);
+        drop(log_span(format!("{marker} span")));
+        set_verbose_logging(false);
+
+        let (_, _, text) = log_snapshot();
+        assert!(!text.contains(&format!("{marker} quiet")));
+        assert!(text.contains(&format!("[DEBUG] {marker} span: started")));
+        assert!(text.contains(&format!("[DEBUG] {marker} span: finished in ")));
+    }
+
     #[test]
     fn run_command_output_logs_streams() {
         let marker = format!("stream-log-test-{}", std::process::id());
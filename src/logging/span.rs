@@ -0,0 +1,30 @@
+use super::store::{log_debug, verbose_logging};
+use std::time::Instant;
+
+// Marks the start and end of a longer step, such as one git or gpg command,
+// so verbose logs show where time went. Does nothing unless verbose.
+pub struct LogSpan {
+    name: String,
+    started: Option<Instant>,
+}
+
+pub fn log_span(name: impl Into<String>) -> LogSpan {
+    let name = name.into();
+    let started = verbose_logging().then(|| {
+        log_debug(format!("{name}: started"));
+        Instant::now()
+    });
+    LogSpan { name, started }
+}
+
+impl Drop for LogSpan {
+    fn drop(&mut self) {
+        if let Some(started) = self.started {
+            log_debug(format!(
+                "{}: finished in {} ms",
+                self.name,
+                started.elapsed().as_millis()
+            ));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use regex::{Captures, Regex};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{OnceLock, RwLock};
 use url::Url;
 
@@ -9,6 +10,8 @@ struct LogState {
     error_revision: usize,
 }
 
+static VERBOSE_LOGGING: AtomicBool = AtomicBool::new(false);
+
 fn global_log_state() -> &'static RwLock<LogState> {
     static LOG_STATE: OnceLock<RwLock<LogState>> = OnceLock::new();
     LOG_STATE.get_or_init(|| RwLock::new(LogState::default()))
@@ -40,6 +43,10 @@ fn push_log_entry(level: &str, message: &str, is_error: bool) {
         return;
     }
 
+    if verbose_logging() {
+        eprintln!("[{level}] {message}");
+    }
+
     with_log_state_write(|state| {
         if !state.text.is_empty() {
             state.text.push_str("\n\n");
@@ -148,6 +155,22 @@ pub fn log_error(message: impl Into<String>) {
     push_log_entry("ERROR", &message, true);
 }
 
+// Debug entries are only kept after `--verbose` or the Tools switch turns
+// them on, and verbose mode also mirrors every entry to stderr.
+pub fn set_verbose_logging(verbose: bool) {
+    VERBOSE_LOGGING.store(verbose, Ordering::Relaxed);
+}
+
+pub fn verbose_logging() -> bool {
+    VERBOSE_LOGGING.load(Ordering::Relaxed)
+}
+
+pub fn log_debug(message: impl Into<String>) {
+    if verbose_logging() {
+        push_log_entry("DEBUG", &message.into(), false);
+    }
+}
+
 pub fn log_snapshot() -> (usize, usize, String) {
     with_log_state_read(|state| (state.revision, state.error_revision, state.text.clone()))
 }
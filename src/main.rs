@@ -22,23 +22,38 @@ mod private_key;
 mod search_provider;
 mod store;
 mod support;
+#[cfg(target_os = "linux")]
+mod sync_daemon;
 mod updater;
 mod window;
 
+use crate::backend::log_store_events;
 use crate::i18n::gettext;
-use crate::logging::{log_error, run_command_output, CommandLogOptions};
-use crate::password::model::OpenPassFile;
-use crate::preferences::Preferences;
+use crate::logging::{
+    log_error, log_info, run_command_output, set_verbose_logging, CommandLogOptions,
+};
+use crate::password::entry_files::{
+    normalize_password_entry_label, store_containing_password_entry,
+};
+use crate::password::model::{OpenPassFile, PassEntry};
+use crate::preferences::{add_session_store, Preferences};
+use crate::support::actions::activate_widget_action;
+use crate::support::crash::{install_panic_hook, watch_for_crash_reports};
 use crate::support::hardening::apply_process_hardening;
 use crate::support::object_data::{
     cloned_data, set_cloned_data, set_string_data, take_data, take_string_data,
 };
+use crate::support::portable::portable_root;
 use crate::support::runtime::handle_unsupported_host_command_invocation;
 #[cfg(feature = "legacy-compat")]
 use crate::support::startup::{
     fatal_startup_error, prompt_startup_recovery_dialog, show_startup_error_dialog,
     StartupRecoveryChoice,
 };
+use crate::support::startup_timing::{
+    begin_startup_timing, finish_startup_timing, log_startup_phase,
+};
+use crate::support::sync_history::track_store_syncs;
 #[cfg(all(target_os = "linux", feature = "setup"))]
 use crate::support::theme::install_color_scheme_tracking;
 use crate::window::navigation::APP_WINDOW_TITLE;
@@ -59,7 +74,6 @@ use std::ffi::OsString;
 use std::fs;
 #[cfg(target_os = "windows")]
 use std::hash::{Hash, Hasher};
-#[cfg(any(target_os = "windows", test))]
 use std::path::Path;
 #[cfg(target_os = "windows")]
 use std::path::PathBuf;
@@ -70,12 +84,17 @@ const APP_ID: &str = env!("APP_ID");
 const RESOURCE_ID: &str = env!("RESOURCE_ID");
 const ISSUE_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "/issues");
 const MAIN_WINDOW_ACTIVATING_KEY: &str = "main-window-activating";
+const MAIN_WINDOW_ACTIVATION_PENDING_KEY: &str = "main-window-activation-pending";
 const RIPASSO_VERSION: &str = env!("RIPASSO_VERSION");
 const SEQUOIA_OPENPGP_VERSION: &str = env!("SEQUOIA_OPENPGP_VERSION");
 const SHORTCUTS_UI: &str = include_str!("../data/shortcuts.ui");
 
 fn main() -> ExitCode {
-    let args = std::env::args_os().collect::<Vec<_>>();
+    let mut args = std::env::args_os().collect::<Vec<_>>();
+    if take_verbose_flag(&mut args) {
+        set_verbose_logging(true);
+    }
+    install_panic_hook();
     if handle_unsupported_host_command_invocation(&args) {
         return 126.into();
     }
@@ -83,12 +102,26 @@ fn main() -> ExitCode {
     if let Some(code) = updater::handle_special_command(&args) {
         return code;
     }
+    log_store_events();
+    track_store_syncs();
+    if let Some(root) = portable_root() {
+        log_info(format!("Running in portable mode from {}.", root.display()));
+    }
+    if let Some(store) = std::env::var_os("PASSWORD_STORE_DIR") {
+        add_session_store(&store.to_string_lossy());
+    }
 
     #[cfg(target_os = "linux")]
     if search_provider::is_search_provider_command(&args) {
         return search_provider::run();
     }
 
+    #[cfg(target_os = "linux")]
+    if sync_daemon::is_sync_daemon_command(&args) {
+        return sync_daemon::run(&args);
+    }
+
+    begin_startup_timing();
     i18n::init();
     if let Err(err) = apply_process_hardening() {
         log_error(format!("Failed to apply process hardening: {err}"));
@@ -135,6 +168,8 @@ fn main() -> ExitCode {
     install_color_scheme_tracking(&display);
     let theme = IconTheme::for_display(&display);
     theme.add_resource_path(RESOURCE_ID);
+    log_startup_phase("initializing the toolkit");
+    password::attachments::clear_stale_opened_attachments();
 
     match backend::prepare_startup() {
         Ok(backend::StartupPreparation::Ready) => {}
@@ -171,6 +206,8 @@ fn main() -> ExitCode {
         }
     }
 
+    log_startup_phase("preparing private-key storage");
+
     // Create the application
     let app = Application::builder()
         .application_id(APP_ID)
@@ -180,6 +217,7 @@ fn main() -> ExitCode {
     // keyboard shortcuts
     app.set_accels_for_action("app.about", &["F1"]);
     register_app_actions(&app);
+    watch_for_crash_reports(&app, ISSUE_URL);
 
     // When the desktop asks us to "open" something, just activate the app
     {
@@ -191,10 +229,44 @@ fn main() -> ExitCode {
     // Handle command-line arguments
     {
         app.connect_command_line(|app, cmd| {
-            let args = cmd.arguments();
-            if let Some(pass_file) = command_line_pass_file(&args) {
+            let mut args = cmd.arguments();
+            if take_verbose_flag(&mut args) {
+                set_verbose_logging(true);
+            }
+            let cwd = cmd.cwd();
+            let mut added_store = false;
+            // A second launch hands its command line to the running app, so
+            // the variable is read from the launching process here.
+            let env_store = cmd
+                .getenv("PASSWORD_STORE_DIR")
+                .map(|store| OsString::from(store.as_str()));
+            for store in env_store.into_iter().chain(take_store_flags(&mut args)) {
+                let store = Path::new(&store);
+                let store = match cwd.as_deref() {
+                    Some(cwd) if store.is_relative() => cwd.join(store),
+                    _ => store.to_path_buf(),
+                };
+                added_store |= add_session_store(&store.to_string_lossy());
+            }
+            if added_store {
+                if let Some(window) = existing_main_window(app) {
+                    activate_widget_action(&window, "win.reload-password-list");
+                }
+            }
+            let stores = Preferences::new().stores();
+            if let Some(pass_file) = command_line_pass_file(&args, cwd.as_deref())
+                .or_else(|| command_line_show_label(&args, &stores))
+            {
                 set_cloned_data(app, "open-pass-file", pass_file);
-            } else if let Some(query) = command_line_query(&args) {
+            } else if let Some(entry) = command_line_copy_entry(&args, cwd.as_deref())
+                .or_else(|| command_line_copy_label(&args, &stores))
+            {
+                set_cloned_data(app, "copy-pass-entry", entry);
+            } else if command_line_synchronize(&args) {
+                set_cloned_data(app, "synchronize", true);
+            } else if let Some(query) =
+                command_line_label_query(&args).or_else(|| command_line_query(&args))
+            {
                 set_string_data(app, "query", query);
             }
             app.activate(); // continue normal startup path
@@ -205,6 +277,7 @@ fn main() -> ExitCode {
 
     app.connect_shutdown(|_| {
         backend::clear_runtime_secret_state();
+        password::attachments::clear_opened_attachments();
     });
     {
         let app_for_shutdown = app.clone();
@@ -221,16 +294,32 @@ fn main() -> ExitCode {
 
         let query = take_string_data(app, "query");
         let pass_file = take_data(app, "open-pass-file");
+        let copy_entry = take_data::<_, PassEntry>(app, "copy-pass-entry");
+        let synchronize = take_data::<_, bool>(app, "synchronize").unwrap_or(false);
         if let Some(window) = existing_main_window(app) {
             window::dispatch_main_window_command(&window, query, pass_file);
             window.present();
+            if let Some(entry) = copy_entry {
+                window::copy_main_window_entry(&window, entry);
+            }
+            if synchronize {
+                activate_widget_action(&window, "win.synchronize");
+            }
             return;
         }
 
         match window::create_main_window(app, query, pass_file) {
             Ok(win) => {
+                log_startup_phase("building the main window");
                 win.present();
+                finish_startup_timing("showing the main window");
                 updater::after_window_presented(app, &win);
+                if let Some(entry) = copy_entry {
+                    window::copy_main_window_entry(&win, entry);
+                }
+                if synchronize {
+                    activate_widget_action(&win, "win.synchronize");
+                }
             }
             Err(err) => {
                 #[cfg(feature = "legacy-compat")]
@@ -252,9 +341,12 @@ struct MainWindowActivationGuard {
     app: Application,
 }
 
+// A launch that arrives while the window is still being built keeps its
+// command, and it is carried out once the window is there.
 impl MainWindowActivationGuard {
     fn acquire(app: &Application) -> Option<Self> {
         if cloned_data::<_, bool>(app, MAIN_WINDOW_ACTIVATING_KEY).unwrap_or(false) {
+            set_cloned_data(app, MAIN_WINDOW_ACTIVATION_PENDING_KEY, true);
             return None;
         }
 
@@ -266,6 +358,11 @@ impl MainWindowActivationGuard {
 impl Drop for MainWindowActivationGuard {
     fn drop(&mut self) {
         set_cloned_data(&self.app, MAIN_WINDOW_ACTIVATING_KEY, false);
+        let pending =
+            take_data::<_, bool>(&self.app, MAIN_WINDOW_ACTIVATION_PENDING_KEY).unwrap_or(false);
+        if pending && existing_main_window(&self.app).is_some() {
+            self.app.activate();
+        }
     }
 }
 
@@ -279,22 +376,105 @@ fn existing_main_window(app: &Application) -> Option<adw::ApplicationWindow> {
         })
 }
 
-fn command_line_pass_file(args: &[OsString]) -> Option<OpenPassFile> {
-    if args.get(1).is_none_or(|arg| arg != "--open-entry") {
+const ENTRY_COMMAND_FLAGS: [&str; 5] =
+    ["--open-entry", "--copy-entry", "--show", "--copy", "--sync"];
+
+// `--verbose` works with every other option, so it is taken out before the
+// rest of the arguments are read.
+fn take_verbose_flag(args: &mut Vec<OsString>) -> bool {
+    let count = args.len();
+    args.retain(|arg| arg != "--verbose");
+    args.len() != count
+}
+
+fn take_store_flags(args: &mut Vec<OsString>) -> Vec<OsString> {
+    let mut stores = Vec::new();
+    let mut index = 1;
+    while index < args.len() {
+        if args[index] == "--store" && index + 1 < args.len() {
+            stores.push(args.remove(index + 1));
+            args.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    stores
+}
+
+// A second launch hands its arguments to the running instance, which may
+// have started in another folder, so a relative store path is resolved
+// against the folder of the launch that passed it.
+fn command_line_entry(
+    args: &[OsString],
+    flag: &str,
+    cwd: Option<&Path>,
+) -> Option<(String, String)> {
+    if args.get(1).is_none_or(|arg| arg != flag) {
         return None;
     }
 
-    let store_root = args.get(2)?.to_string_lossy().into_owned();
+    let store_root = Path::new(args.get(2)?);
     let label = args.get(3)?.to_string_lossy().into_owned();
-    if store_root.is_empty() || label.is_empty() {
+    if store_root.as_os_str().is_empty() || label.is_empty() {
         return None;
     }
+    let store_root = match cwd {
+        Some(cwd) if store_root.is_relative() => cwd.join(store_root),
+        _ => store_root.to_path_buf(),
+    };
+
+    Some((store_root.to_string_lossy().into_owned(), label))
+}
+
+fn command_line_pass_file(args: &[OsString], cwd: Option<&Path>) -> Option<OpenPassFile> {
+    command_line_entry(args, "--open-entry", cwd)
+        .map(|(store_root, label)| OpenPassFile::from_label(store_root, label))
+}
+
+fn command_line_copy_entry(args: &[OsString], cwd: Option<&Path>) -> Option<PassEntry> {
+    command_line_entry(args, "--copy-entry", cwd)
+        .map(|(store_root, label)| PassEntry::from_label(store_root, label))
+}
 
+// `--show` and `--copy` take a label like `pass show` does and look for it
+// in the configured stores, in order.
+fn command_line_label(args: &[OsString], flag: &str) -> Option<String> {
+    if args.get(1).is_none_or(|arg| arg != flag) {
+        return None;
+    }
+
+    let label = normalize_password_entry_label(&args.get(2)?.to_string_lossy());
+    (!label.is_empty()).then_some(label)
+}
+
+fn command_line_show_label(args: &[OsString], stores: &[String]) -> Option<OpenPassFile> {
+    let label = command_line_label(args, "--show")?;
+    let store_root = store_containing_password_entry(stores, &label)?;
     Some(OpenPassFile::from_label(store_root, label))
 }
 
+fn command_line_copy_label(args: &[OsString], stores: &[String]) -> Option<PassEntry> {
+    let label = command_line_label(args, "--copy")?;
+    let store_root = store_containing_password_entry(stores, &label)?;
+    Some(PassEntry::from_label(store_root, label))
+}
+
+// A label that isn't in any store still lands in the search field, so the
+// closest matches are one click away.
+fn command_line_label_query(args: &[OsString]) -> Option<String> {
+    command_line_label(args, "--show").or_else(|| command_line_label(args, "--copy"))
+}
+
+fn command_line_synchronize(args: &[OsString]) -> bool {
+    args.get(1).is_some_and(|arg| arg == "--sync")
+}
+
 fn command_line_query(args: &[OsString]) -> Option<String> {
-    if args.len() <= 1 || args.get(1).is_some_and(|arg| arg == "--open-entry") {
+    if args.len() <= 1
+        || args
+            .get(1)
+            .is_some_and(|arg| ENTRY_COMMAND_FLAGS.iter().any(|flag| arg == flag))
+    {
         return None;
     }
 
@@ -476,6 +656,8 @@ fn register_app_actions(app: &Application) {
     let app_for_shortcuts = app.clone();
     shortcuts_action.connect_activate(move |_, _| match build_shortcuts_window() {
         Ok(shortcuts) => {
+            // Configurable rows look up their accelerators through the app.
+            shortcuts.set_application(Some(&app_for_shortcuts));
             if let Some(active_window) = app_for_shortcuts.active_window() {
                 shortcuts.set_transient_for(Some(&active_window));
             }
@@ -580,8 +762,10 @@ fn get_pass_version(settings: &Preferences) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        command_line_pass_file, command_line_query, quoted_pixbuf_loader_name,
-        rewrite_pixbuf_loader_cache,
+        command_line_copy_entry, command_line_copy_label, command_line_label_query,
+        command_line_pass_file, command_line_query, command_line_show_label,
+        command_line_synchronize, quoted_pixbuf_loader_name, rewrite_pixbuf_loader_cache,
+        take_store_flags, take_verbose_flag,
     };
     use std::ffi::OsString;
     use std::path::Path;
@@ -595,12 +779,123 @@ mod tests {
             OsString::from("work/alice/github"),
         ];
 
-        let pass_file = command_line_pass_file(&args).expect("expected pass file");
+        let pass_file = command_line_pass_file(&args, None).expect("expected pass file");
         assert_eq!(pass_file.store_path(), "/tmp/store");
         assert_eq!(pass_file.label(), "work/alice/github".to_string());
         assert_eq!(command_line_query(&args), None);
     }
 
+    #[test]
+    fn copy_entry_command_line_is_parsed() {
+        let args = vec![
+            OsString::from("keycord"),
+            OsString::from("--copy-entry"),
+            OsString::from("/tmp/store"),
+            OsString::from("work/alice/github"),
+        ];
+
+        let entry = command_line_copy_entry(&args, None).expect("expected entry");
+        assert_eq!(entry.store_path, "/tmp/store");
+        assert_eq!(entry.label(), "work/alice/github".to_string());
+        assert!(command_line_pass_file(&args, None).is_none());
+        assert_eq!(command_line_query(&args), None);
+    }
+
+    #[test]
+    fn relative_store_paths_follow_the_launching_folder() {
+        let args = vec![
+            OsString::from("keycord"),
+            OsString::from("--open-entry"),
+            OsString::from("store"),
+            OsString::from("mail/github"),
+        ];
+
+        let pass_file = command_line_pass_file(&args, Some(Path::new("/home/alice")))
+            .expect("expected pass file");
+        assert_eq!(pass_file.store_path(), "/home/alice/store");
+
+        let entry = command_line_copy_entry(
+            &[
+                OsString::from("keycord"),
+                OsString::from("--copy-entry"),
+                OsString::from("/tmp/store"),
+                OsString::from("mail/github"),
+            ],
+            Some(Path::new("/home/alice")),
+        )
+        .expect("expected entry");
+        assert_eq!(entry.store_path, "/tmp/store");
+    }
+
+    #[test]
+    fn show_and_copy_labels_resolve_in_the_configured_stores() {
+        let store = std::env::temp_dir().join(format!("keycord-show-{}", std::process::id()));
+        std::fs::create_dir_all(store.join("mail")).expect("create store");
+        std::fs::write(store.join("mail/github.gpg"), b"x").expect("write entry");
+        let stores = vec![store.to_string_lossy().to_string()];
+        let show = vec![
+            OsString::from("keycord"),
+            OsString::from("--show"),
+            OsString::from("mail/github"),
+        ];
+        let copy = vec![
+            OsString::from("keycord"),
+            OsString::from("--copy"),
+            OsString::from("mail/gitlab"),
+        ];
+
+        let pass_file = command_line_show_label(&show, &stores).expect("expected pass file");
+        assert_eq!(pass_file.store_path(), stores[0]);
+        assert_eq!(pass_file.label(), "mail/github".to_string());
+        assert!(command_line_copy_label(&copy, &stores).is_none());
+        assert_eq!(
+            command_line_label_query(&copy),
+            Some("mail/gitlab".to_string())
+        );
+        assert_eq!(command_line_query(&show), None);
+        assert_eq!(command_line_query(&copy), None);
+
+        let _ = std::fs::remove_dir_all(store);
+    }
+
+    #[test]
+    fn sync_argument_is_not_a_query() {
+        let args = vec![OsString::from("keycord"), OsString::from("--sync")];
+
+        assert!(command_line_synchronize(&args));
+        assert_eq!(command_line_query(&args), None);
+    }
+
+    #[test]
+    fn verbose_flag_is_removed_from_any_position() {
+        let mut args = vec![
+            OsString::from("keycord"),
+            OsString::from("--sync"),
+            OsString::from("--verbose"),
+        ];
+
+        assert!(take_verbose_flag(&mut args));
+        assert!(command_line_synchronize(&args));
+        assert_eq!(args.len(), 2);
+        assert!(!take_verbose_flag(&mut args));
+    }
+
+    #[test]
+    fn store_flags_are_taken_with_their_paths() {
+        let mut args = vec![
+            OsString::from("keycord"),
+            OsString::from("--store"),
+            OsString::from("work"),
+            OsString::from("--sync"),
+        ];
+
+        assert_eq!(take_store_flags(&mut args), vec![OsString::from("work")]);
+        assert_eq!(
+            args,
+            vec![OsString::from("keycord"), OsString::from("--sync")]
+        );
+    }
+
     #[test]
     fn free_form_arguments_become_a_query() {
         let args = vec![
@@ -616,7 +911,7 @@ mod tests {
             command_line_query(&args),
             Some("find otp and user alice".to_string())
         );
-        assert!(command_line_pass_file(&args).is_none());
+        assert!(command_line_pass_file(&args, None).is_none());
     }
 
     #[test]
@@ -0,0 +1,83 @@
+use crate::backend::{
+    read_password_entry_with_progress, PasswordEntryError, PasswordEntryReadProgress,
+};
+use crate::password::entry_files::normalize_password_entry_label;
+use zeroize::Zeroizing;
+
+const ALIAS_FIELD: &str = "alias:";
+
+// An alias has an empty password line and a single `alias: other/entry`
+// line, so `pass` and other tools that copy the first line find no password
+// instead of a wrong one. Anything more in the file makes it a normal item.
+pub fn pass_file_alias_target(contents: &str) -> Option<String> {
+    let mut lines = contents.lines();
+    if !lines.next()?.trim().is_empty() {
+        return None;
+    }
+    let mut lines = lines.filter(|line| !line.trim().is_empty());
+    let line = lines.next()?;
+    if lines.next().is_some() {
+        return None;
+    }
+    let target = normalize_password_entry_label(line.trim().strip_prefix(ALIAS_FIELD)?);
+    (!target.is_empty()).then_some(target)
+}
+
+pub fn alias_pass_file_contents(target_label: &str) -> String {
+    format!("\n{ALIAS_FIELD} {target_label}\n")
+}
+
+// Follows one alias at most, so an alias of an alias shows the second alias
+// instead of looping. Returns the target's label when `label` is an alias,
+// with the target's contents.
+pub fn read_password_entry_following_alias(
+    store_root: &str,
+    label: &str,
+    report_progress: &mut dyn FnMut(PasswordEntryReadProgress),
+) -> Result<(Option<String>, Zeroizing<String>), PasswordEntryError> {
+    let contents = Zeroizing::new(read_password_entry_with_progress(
+        store_root,
+        label,
+        report_progress,
+    )?);
+    let Some(target) = pass_file_alias_target(&contents).filter(|target| target != label) else {
+        return Ok((None, contents));
+    };
+
+    let target_contents = Zeroizing::new(read_password_entry_with_progress(
+        store_root,
+        &target,
+        report_progress,
+    )?);
+    Ok((Some(target), target_contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{alias_pass_file_contents, pass_file_alias_target};
+
+    #[test]
+    fn aliases_have_no_password_and_a_single_alias_line() {
+        assert_eq!(
+            pass_file_alias_target("\nalias: work/mail\n"),
+            Some("work/mail".to_string())
+        );
+        assert_eq!(
+            pass_file_alias_target("\nalias:/work//mail"),
+            Some("work/mail".to_string())
+        );
+        assert_eq!(pass_file_alias_target("alias: work/mail"), None);
+        assert_eq!(pass_file_alias_target("secret\nalias: work/mail"), None);
+        assert_eq!(pass_file_alias_target("\nalias: work/mail\nuser: me"), None);
+        assert_eq!(pass_file_alias_target("\naliases: work/mail"), None);
+        assert_eq!(pass_file_alias_target("\nalias:"), None);
+    }
+
+    #[test]
+    fn alias_contents_point_back_at_their_target() {
+        assert_eq!(
+            pass_file_alias_target(&alias_pass_file_contents("sites/github.com/alice")),
+            Some("sites/github.com/alice".to_string())
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use super::page::PasswordPageState;
+use crate::backend::{
+    delete_password_attachment, list_password_attachments, read_password_attachment,
+    save_password_attachment,
+};
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::password::file::clear_box_children;
+use crate::password::opened::get_opened_pass_file;
+use crate::support::background::{spawn_result_task, spawn_worker};
+use crate::support::file_picker::choose_local_file_path;
+use crate::support::paths::app_cache_path;
+use crate::support::secure_fs::{ensure_private_dir, write_private_file};
+use crate::support::ui::flat_icon_button_with_tooltip;
+use crate::support::uri::launch_default_uri;
+use adw::gio;
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog, ApplicationWindow, ResponseAppearance, Toast};
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+const OPENED_ATTACHMENTS_DIR_NAME: &str = "keycord-attachments";
+
+// Decrypted copies go in the runtime directory, which only this user can
+// read, or in the app cache when there is none. Never in the shared temp
+// folder. Each process keeps its copies in its own folder.
+fn opened_attachments_root() -> Option<PathBuf> {
+    dirs_next::runtime_dir()
+        .map(|dir| dir.join(OPENED_ATTACHMENTS_DIR_NAME))
+        .or_else(|| app_cache_path(OPENED_ATTACHMENTS_DIR_NAME))
+}
+
+fn opened_attachments_dir() -> Option<PathBuf> {
+    Some(opened_attachments_root()?.join(std::process::id().to_string()))
+}
+
+fn opened_attachment_path(dir: &Path, file_name: &str) -> PathBuf {
+    let file_name = Path::new(file_name)
+        .file_name()
+        .map_or_else(|| "attachment".into(), ToOwned::to_owned);
+    dir.join(file_name)
+}
+
+// Only this process's copies, which are few and small, so quitting doesn't
+// wait long.
+pub fn clear_opened_attachments() {
+    if let Some(dir) = opened_attachments_dir() {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_is_running(pid: &str) -> bool {
+    Path::new("/proc").join(pid).exists()
+}
+
+// Without a way to tell, copies of other processes are left alone.
+#[cfg(not(target_os = "linux"))]
+const fn process_is_running(_pid: &str) -> bool {
+    true
+}
+
+// Copies left behind by a Keycord that didn't quit cleanly are removed in
+// the background at startup.
+pub fn clear_stale_opened_attachments() {
+    let Some(root) = opened_attachments_root() else {
+        return;
+    };
+    let spawned = spawn_worker("attachment-cleanup", move || {
+        let Ok(entries) = fs::read_dir(&root) else {
+            return;
+        };
+        let own = std::process::id().to_string();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name != own && !process_is_running(&name) {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    });
+    if let Err(err) = spawned {
+        log_error(format!("Failed to clear old attachment copies: {err}"));
+    }
+}
+
+fn write_opened_attachment(file_name: &str, bytes: &[u8]) -> Result<PathBuf, String> {
+    let dir = opened_attachments_dir()
+        .ok_or_else(|| "No private folder for opened attachments.".to_string())?;
+    if let Some(root) = dir.parent() {
+        ensure_private_dir(root).map_err(|err| err.to_string())?;
+    }
+    ensure_private_dir(&dir).map_err(|err| err.to_string())?;
+    let path = opened_attachment_path(&dir, file_name);
+    write_private_file(&path, bytes).map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
+pub fn sync_password_attachments(state: &PasswordPageState) {
+    clear_box_children(&state.attachments_box);
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        state.attachments_box.set_visible(false);
+        state.attach_button.set_visible(false);
+        return;
+    };
+
+    let entry_exists = state.saved_entry_exists.get();
+    state
+        .attach_button
+        .set_visible(entry_exists && !state.read_only.get());
+    let names = if entry_exists {
+        list_password_attachments(pass_file.store_path(), &pass_file.label())
+    } else {
+        Vec::new()
+    };
+    for name in &names {
+        state.attachments_box.append(&attachment_row(state, name));
+    }
+    state.attachments_box.set_visible(!names.is_empty());
+}
+
+fn attachment_row(state: &PasswordPageState, name: &str) -> ActionRow {
+    let row = ActionRow::builder()
+        .title(name)
+        .subtitle(gettext("Attachment"))
+        .build();
+    row.set_margin_start(15);
+    row.set_margin_end(15);
+    row.set_margin_bottom(6);
+    row.add_css_class("card");
+
+    let open_button = flat_icon_button_with_tooltip("document-open-symbolic", "Open attachment");
+    {
+        let state = state.clone();
+        let name = name.to_string();
+        open_button.connect_clicked(move |_| open_password_attachment(&state, &name));
+    }
+    row.add_suffix(&open_button);
+
+    let remove_button = flat_icon_button_with_tooltip("user-trash-symbolic", "Remove attachment");
+    {
+        let state = state.clone();
+        let name = name.to_string();
+        remove_button.connect_clicked(move |_| remove_password_attachment(&state, &name));
+    }
+    remove_button.set_visible(!state.read_only.get());
+    row.add_suffix(&remove_button);
+    row
+}
+
+pub fn attach_file_to_current_entry(state: &PasswordPageState, window: &ApplicationWindow) {
+    if get_opened_pass_file(&state.nav).is_none() || !state.saved_entry_exists.get() {
+        return;
+    }
+
+    let state = state.clone();
+    choose_local_file_path(
+        window,
+        "Attach file",
+        "Attach",
+        &state.overlay.clone(),
+        move |path| {
+            let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+                return;
+            };
+            let Some(file_name) = Path::new(&path)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+            else {
+                return;
+            };
+
+            let store_root = pass_file.store_path().to_string();
+            let label = pass_file.label();
+            let state_for_result = state.clone();
+            let overlay_for_disconnect = state.overlay.clone();
+            spawn_result_task(
+                move || {
+                    let bytes = Zeroizing::new(fs::read(&path).map_err(|err| err.to_string())?);
+                    save_password_attachment(&store_root, &label, &file_name, &bytes)
+                        .map_err(|err| err.to_string())
+                },
+                move |result| match result {
+                    Ok(()) => {
+                        sync_password_attachments(&state_for_result);
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&gettext("Attached.")));
+                    }
+                    Err(err) => {
+                        log_error(format!("Failed to attach a file: {err}"));
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&gettext("Couldn't attach the file.")));
+                    }
+                },
+                move || {
+                    overlay_for_disconnect
+                        .add_toast(Toast::new(&gettext("Couldn't attach the file.")));
+                },
+            );
+        },
+    );
+}
+
+fn open_password_attachment(state: &PasswordPageState, name: &str) {
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let name = name.to_string();
+    let overlay = state.overlay.clone();
+    let overlay_for_disconnect = state.overlay.clone();
+    spawn_result_task(
+        move || {
+            let bytes = Zeroizing::new(
+                read_password_attachment(&store_root, &label, &name)
+                    .map_err(|err| err.to_string())?,
+            );
+            write_opened_attachment(&name, &bytes)
+        },
+        move |result| match result {
+            Ok(path) => {
+                let uri = gio::File::for_path(&path).uri();
+                launch_default_uri(&uri, move |result| {
+                    if let Err(err) = result {
+                        log_error(format!("Failed to open an attachment: {err}"));
+                        overlay.add_toast(Toast::new(&gettext("Couldn't open the attachment.")));
+                    }
+                });
+            }
+            Err(err) => {
+                log_error(format!("Failed to decrypt an attachment: {err}"));
+                overlay.add_toast(Toast::new(&gettext("Couldn't open the attachment.")));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't open the attachment.")));
+        },
+    );
+}
+
+fn remove_password_attachment(state: &PasswordPageState, name: &str) {
+    if get_opened_pass_file(&state.nav).is_none() {
+        return;
+    }
+
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Remove this attachment?"))
+        .body(gettext("The file is deleted from the store."))
+        .build();
+    let cancel = gettext("Cancel");
+    let remove = gettext("Remove");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("remove", remove.as_str())]);
+    dialog.set_response_appearance("remove", ResponseAppearance::Destructive);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("cancel"));
+
+    let state_for_response = state.clone();
+    let name = name.to_string();
+    dialog.connect_response(None, move |_, response| {
+        if response == "remove" {
+            delete_attachment_in_background(&state_for_response, &name);
+        }
+    });
+    dialog.present(Some(&state.nav));
+}
+
+fn delete_attachment_in_background(state: &PasswordPageState, name: &str) {
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let name = name.to_string();
+    let state_for_result = state.clone();
+    let overlay_for_disconnect = state.overlay.clone();
+    spawn_result_task(
+        move || delete_password_attachment(&store_root, &label, &name),
+        move |result| match result {
+            Ok(()) => {
+                sync_password_attachments(&state_for_result);
+                state_for_result
+                    .overlay
+                    .add_toast(Toast::new(&gettext("Attachment removed.")));
+            }
+            Err(err) => {
+                log_error(format!("Failed to remove an attachment: {err}"));
+                state_for_result
+                    .overlay
+                    .add_toast(Toast::new(&gettext(err.delete_toast_message())));
+            }
+        },
+        move || {
+            overlay_for_disconnect
+                .add_toast(Toast::new(&gettext("Couldn't remove the attachment.")));
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::opened_attachment_path;
+    use std::path::Path;
+
+    #[test]
+    fn opened_attachment_paths_stay_inside_the_runtime_folder() {
+        let dir = Path::new("/run/user/1000/keycord-attachments/42");
+        let path = opened_attachment_path(dir, "../codes.pdf");
+
+        assert_eq!(path, dir.join("codes.pdf"));
+    }
+}
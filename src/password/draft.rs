@@ -0,0 +1,125 @@
+use crate::backend::{read_password_entry, save_password_draft, PasswordEntryError};
+use crate::password::entry_files::normalize_password_entry_label;
+use crate::support::paths::app_state_path;
+use crate::support::secure_fs::write_private_file;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+const DRAFTS_DIR_NAME: &str = "drafts";
+const DRAFT_LABEL: &str = "draft";
+const RECIPIENTS_FILE_NAME: &str = ".gpg-id";
+
+pub fn password_drafts_dir() -> Option<PathBuf> {
+    app_state_path(DRAFTS_DIR_NAME)
+}
+
+// One folder per item, named by a hash so the item's path doesn't show up
+// in the state folder.
+fn draft_dir_in(dir: &Path, store_root: &str, label: &str) -> PathBuf {
+    let digest = Sha256::digest(format!("{store_root}\n{label}").as_bytes());
+    let mut name = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(&mut name, "{byte:02x}");
+    }
+    dir.join(name)
+}
+
+fn draft_dir(store_root: &str, label: &str) -> Option<PathBuf> {
+    Some(draft_dir_in(&password_drafts_dir()?, store_root, label))
+}
+
+// The `.gpg-id` closest to the item, the same one a save would encrypt to.
+fn recipients_file_for_label(store_root: &str, label: &str) -> Option<PathBuf> {
+    let label = normalize_password_entry_label(label);
+    let mut dir = Path::new(&label).parent();
+    while let Some(current) = dir {
+        let candidate = Path::new(store_root)
+            .join(current)
+            .join(RECIPIENTS_FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = current.parent();
+    }
+    None
+}
+
+// Drafts are encrypted for the same recipients as the item, so they are no
+// easier to read than the store itself.
+pub fn save_password_entry_draft(
+    store_root: &str,
+    label: &str,
+    contents: &str,
+) -> Result<(), String> {
+    let dir = draft_dir(store_root, label).ok_or_else(|| "No folder for drafts.".to_string())?;
+    let recipients = recipients_file_for_label(store_root, label)
+        .ok_or_else(|| "No recipients were found for this item.".to_string())?;
+    let recipients = fs::read(recipients).map_err(|err| err.to_string())?;
+    write_private_file(&dir.join(RECIPIENTS_FILE_NAME), recipients)
+        .map_err(|err| err.to_string())?;
+    save_password_draft(&dir.to_string_lossy(), DRAFT_LABEL, contents)
+        .map_err(|err| err.to_string())
+}
+
+pub fn password_entry_draft_exists(store_root: &str, label: &str) -> bool {
+    draft_dir(store_root, label).is_some_and(|dir| dir.join(format!("{DRAFT_LABEL}.gpg")).is_file())
+}
+
+pub fn read_password_entry_draft(
+    store_root: &str,
+    label: &str,
+) -> Result<Zeroizing<String>, PasswordEntryError> {
+    let dir = draft_dir(store_root, label).unwrap_or_default();
+    read_password_entry(&dir.to_string_lossy(), DRAFT_LABEL).map(Zeroizing::new)
+}
+
+pub fn discard_password_entry_draft(store_root: &str, label: &str) {
+    if let Some(dir) = draft_dir(store_root, label) {
+        let _ = fs::remove_dir_all(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{draft_dir_in, recipients_file_for_label};
+    use std::fs;
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn draft_folders_hide_the_item_path() {
+        let dir = Path::new("/state/drafts");
+        let draft = draft_dir_in(dir, "/stores/work", "mail/alice");
+
+        assert_eq!(draft.parent(), Some(dir));
+        assert!(!draft.to_string_lossy().contains("alice"));
+        assert_ne!(draft, draft_dir_in(dir, "/stores/home", "mail/alice"));
+    }
+
+    #[test]
+    fn drafts_use_the_closest_recipients_file() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-draft-{nanos}"));
+        fs::create_dir_all(store.join("team/mail")).expect("create store dir");
+        fs::write(store.join(".gpg-id"), "root\n").expect("write root recipients");
+        fs::write(store.join("team/.gpg-id"), "team\n").expect("write team recipients");
+        let root = store.to_string_lossy();
+
+        assert_eq!(
+            recipients_file_for_label(&root, "team/mail/alice"),
+            Some(store.join("team/.gpg-id"))
+        );
+        assert_eq!(
+            recipients_file_for_label(&root, "github"),
+            Some(store.join(".gpg-id"))
+        );
+
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+}
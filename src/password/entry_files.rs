@@ -1,7 +1,17 @@
-use std::path::{Component, Path};
+use crate::password::index::indexed_directory_listing;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
 
 pub const STANDARD_PASSWORD_ENTRY_EXTENSION: &str = "gpg";
 pub const FIDO2_PASSWORD_ENTRY_EXTENSION: &str = "keycord";
+const STORE_METADATA_DIR_NAMES: [&str; 2] = [".git", ".extensions"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PasswordEntryWalkOptions {
+    pub include_hidden: bool,
+}
 
 pub const fn password_entry_extension(uses_fido2: bool) -> &'static str {
     if uses_fido2 {
@@ -24,6 +34,132 @@ pub fn is_password_entry_extension(extension: &str) -> bool {
     )
 }
 
+// A store can be reached through a symlink or a bind mount, so two different
+// paths may name the same folder. Paths that can't be resolved are compared
+// as written.
+pub fn canonical_store_root(store_root: &Path) -> PathBuf {
+    fs::canonicalize(store_root).unwrap_or_else(|_| store_root.to_path_buf())
+}
+
+fn is_hidden_name(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+fn is_store_metadata_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| STORE_METADATA_DIR_NAMES.contains(&name))
+}
+
+// The one walk over a store's folders, shared by the item list and the
+// backend. Like `pass`, it never looks inside `.git` or `.extensions`, and
+// hidden files and folders are only included when asked for. Symlinked
+// folders are followed once, wherever they point, so a link back up the tree
+// can't loop forever.
+pub fn collect_password_entry_paths(
+    store_root: &Path,
+    options: PasswordEntryWalkOptions,
+) -> io::Result<Vec<PathBuf>> {
+    let mut entries = Vec::new();
+    walk_password_store(store_root, options, |_, dir_entries, _| {
+        entries.extend(dir_entries);
+    })?;
+    Ok(entries)
+}
+
+// Folders without items or subfolders, such as ones made before their first
+// item. Their parents aren't listed, since they show up on the way down.
+pub fn collect_empty_password_folders(
+    store_root: &Path,
+    options: PasswordEntryWalkOptions,
+) -> io::Result<Vec<PathBuf>> {
+    let mut folders = Vec::new();
+    walk_password_store(store_root, options, |dir, dir_entries, has_subfolders| {
+        if dir != store_root && dir_entries.is_empty() && !has_subfolders {
+            folders.push(dir.to_path_buf());
+        }
+    })?;
+    Ok(folders)
+}
+
+fn walk_password_store(
+    store_root: &Path,
+    options: PasswordEntryWalkOptions,
+    mut visit: impl FnMut(&Path, Vec<PathBuf>, bool),
+) -> io::Result<()> {
+    if !store_root.exists() {
+        return Ok(());
+    }
+
+    let mut visited_dirs = HashSet::new();
+    let mut pending_dirs = vec![(store_root.to_path_buf(), true)];
+
+    while let Some((dir, is_root)) = pending_dirs.pop() {
+        let canonical_dir = match fs::canonicalize(&dir) {
+            Ok(canonical_dir) => canonical_dir,
+            Err(err) if is_root => return Err(err),
+            Err(_) => continue,
+        };
+        if !visited_dirs.insert(canonical_dir) {
+            continue;
+        }
+
+        let listing = match indexed_directory_listing(&dir) {
+            Ok(listing) => listing,
+            Err(err) if is_root => return Err(err),
+            Err(_) => continue,
+        };
+
+        let dir_entries = listing
+            .files
+            .iter()
+            .filter(|path| options.include_hidden || !is_hidden_name(path))
+            .filter(|path| is_password_entry_file(path))
+            .cloned()
+            .collect();
+
+        let pending_before = pending_dirs.len();
+        for child_dir in listing.dirs.iter().rev() {
+            if is_store_metadata_dir(child_dir) {
+                continue;
+            }
+            if !options.include_hidden && is_hidden_name(child_dir) {
+                continue;
+            }
+            pending_dirs.push((child_dir.clone(), false));
+        }
+        visit(&dir, dir_entries, pending_dirs.len() > pending_before);
+    }
+
+    Ok(())
+}
+
+// The first store that holds `label`, in the order the stores are listed.
+pub fn store_containing_password_entry(stores: &[String], label: &str) -> Option<String> {
+    let label = normalize_password_entry_label(label);
+    if label.is_empty() {
+        return None;
+    }
+
+    stores
+        .iter()
+        .find(|store| {
+            [
+                STANDARD_PASSWORD_ENTRY_EXTENSION,
+                FIDO2_PASSWORD_ENTRY_EXTENSION,
+            ]
+            .iter()
+            .any(|extension| {
+                Path::new(store)
+                    .join(format!("{label}.{extension}"))
+                    .is_file()
+            })
+        })
+        .cloned()
+}
+
 pub fn label_from_password_entry_path(store_root: &Path, entry_path: &Path) -> Option<String> {
     let relative = entry_path.strip_prefix(store_root).ok()?;
     label_from_password_entry_relative_path(relative)
@@ -75,12 +211,15 @@ pub fn label_from_password_entry_relative_path(relative: &Path) -> Option<String
 #[cfg(test)]
 mod tests {
     use super::{
-        is_password_entry_file, label_from_password_entry_path,
-        label_from_password_entry_relative_path, normalize_password_entry_label,
-        password_entry_extension, FIDO2_PASSWORD_ENTRY_EXTENSION,
+        collect_empty_password_folders, collect_password_entry_paths, is_password_entry_file,
+        label_from_password_entry_path, label_from_password_entry_relative_path,
+        normalize_password_entry_label, password_entry_extension, store_containing_password_entry,
+        PasswordEntryWalkOptions, FIDO2_PASSWORD_ENTRY_EXTENSION,
         STANDARD_PASSWORD_ENTRY_EXTENSION,
     };
+    use std::fs;
     use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn password_entry_extensions_distinguish_standard_and_fido2_entries() {
@@ -132,4 +271,147 @@ mod tests {
         assert!(is_password_entry_file(Path::new("team/service.keycord")));
         assert!(!is_password_entry_file(Path::new("team/service.txt")));
     }
+
+    #[test]
+    fn store_walks_skip_git_and_extensions_even_with_hidden_entries() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-walk-{nanos}"));
+        fs::create_dir_all(store.join(".git/objects")).expect("create git dir");
+        fs::create_dir_all(store.join(".extensions")).expect("create extensions dir");
+        fs::create_dir_all(store.join(".archive")).expect("create hidden dir");
+        fs::write(store.join(".git/objects/stray.gpg"), b"x").expect("write git file");
+        fs::write(store.join(".extensions/tool.gpg"), b"x").expect("write extension file");
+        fs::write(store.join(".archive/old.gpg"), b"x").expect("write hidden entry");
+        fs::write(store.join("service.gpg"), b"x").expect("write entry");
+
+        let visible = collect_password_entry_paths(&store, PasswordEntryWalkOptions::default())
+            .expect("collect visible entries");
+        assert_eq!(visible, vec![store.join("service.gpg")]);
+
+        let mut all = collect_password_entry_paths(
+            &store,
+            PasswordEntryWalkOptions {
+                include_hidden: true,
+            },
+        )
+        .expect("collect all entries");
+        all.sort();
+        assert_eq!(
+            all,
+            vec![store.join(".archive/old.gpg"), store.join("service.gpg")]
+        );
+
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+
+    #[test]
+    fn empty_folder_walks_list_only_the_deepest_empty_folders() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-empty-{nanos}"));
+        fs::create_dir_all(store.join("team/new/deeper")).expect("create nested folders");
+        fs::create_dir_all(store.join("team/used")).expect("create used folder");
+        fs::create_dir_all(store.join("recipients")).expect("create recipients folder");
+        fs::create_dir_all(store.join(".git/refs")).expect("create git dir");
+        fs::write(store.join("team/used/service.gpg"), b"x").expect("write entry");
+        fs::write(store.join("recipients/.gpg-id"), b"key").expect("write recipients");
+
+        let mut folders =
+            collect_empty_password_folders(&store, PasswordEntryWalkOptions::default())
+                .expect("collect empty folders");
+        folders.sort();
+        assert_eq!(
+            folders,
+            vec![store.join("recipients"), store.join("team/new/deeper")]
+        );
+
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn store_walks_stop_at_symlink_loops() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-walk-loop-{nanos}"));
+        fs::create_dir_all(store.join("team")).expect("create store dir");
+        fs::write(store.join("team/service.gpg"), b"x").expect("write entry");
+        std::os::unix::fs::symlink(&store, store.join("team/loop")).expect("create loop");
+
+        let entries = collect_password_entry_paths(&store, PasswordEntryWalkOptions::default())
+            .expect("collect entries");
+        assert_eq!(entries, vec![store.join("team/service.gpg")]);
+
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn store_walks_follow_symlinked_folders_outside_the_store_once() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("passwordstore-walk-outside-{nanos}"));
+        let store = root.join("store");
+        let outside = root.join("outside");
+        fs::create_dir_all(store.join("team")).expect("create store dir");
+        fs::create_dir_all(&outside).expect("create outside dir");
+        fs::write(store.join("team/service.gpg"), b"x").expect("write entry");
+        fs::write(outside.join("other.gpg"), b"x").expect("write outside entry");
+        std::os::unix::fs::symlink(&outside, store.join("linked")).expect("link outside");
+        std::os::unix::fs::symlink(store.join("team"), store.join("alias")).expect("link inside");
+        std::os::unix::fs::symlink(&store, outside.join("back")).expect("link back");
+
+        // Like pass, the link into a synced folder is followed. The alias and
+        // the link back to the store name folders that were already listed.
+        let entries = collect_password_entry_paths(&store, PasswordEntryWalkOptions::default())
+            .expect("collect entries");
+        assert_eq!(entries.len(), 2);
+        assert!(entries
+            .iter()
+            .any(|entry| entry.ends_with("linked/other.gpg")));
+        assert!(entries.iter().any(|entry| entry.ends_with("service.gpg")));
+
+        fs::remove_dir_all(root).expect("remove test root");
+    }
+
+    #[test]
+    fn labels_resolve_to_the_first_store_that_holds_them() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("passwordstore-find-{nanos}"));
+        let work = root.join("work");
+        let personal = root.join("personal");
+        fs::create_dir_all(work.join("mail")).expect("create work store");
+        fs::create_dir_all(personal.join("mail")).expect("create personal store");
+        fs::write(work.join("mail/github.keycord"), b"x").expect("write work entry");
+        fs::write(personal.join("mail/github.gpg"), b"x").expect("write personal entry");
+        let stores = vec![
+            personal.to_string_lossy().to_string(),
+            work.to_string_lossy().to_string(),
+        ];
+
+        assert_eq!(
+            store_containing_password_entry(&stores, "/mail/github"),
+            Some(stores[0].clone())
+        );
+        assert_eq!(
+            store_containing_password_entry(&stores[1..], "mail/github"),
+            Some(stores[1].clone())
+        );
+        assert_eq!(store_containing_password_entry(&stores, "mail"), None);
+        assert_eq!(store_containing_password_entry(&stores, ""), None);
+
+        let _ = fs::remove_dir_all(root);
+    }
 }
@@ -0,0 +1,194 @@
+use crate::i18n::{gettext, ngettext};
+use crate::password::file::searchable_pass_fields;
+use adw::gio::{self, Notification};
+use adw::prelude::*;
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+const EXPIRY_FIELD_KEYS: &[&str] = &["expires", "expiry"];
+const EXPIRY_NOTIFICATION_ID: &str = "expiring-items";
+
+thread_local! {
+    static EXPIRY_REMINDER_SENT: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpiryStatus {
+    Expired,
+    ExpiresSoon(i64),
+    Current,
+}
+
+impl ExpiryStatus {
+    pub const fn needs_rotation(self) -> bool {
+        matches!(self, Self::Expired | Self::ExpiresSoon(_))
+    }
+
+    pub fn label(self) -> Option<String> {
+        match self {
+            Self::Expired => Some(gettext("Expired")),
+            Self::ExpiresSoon(0) => Some(gettext("Expires today")),
+            Self::ExpiresSoon(days) => Some(
+                ngettext(
+                    "Expires in {count} day",
+                    "Expires in {count} days",
+                    days as usize,
+                )
+                .replace("{count}", &days.to_string()),
+            ),
+            Self::Current => None,
+        }
+    }
+}
+
+// Dates are kept as days since 1970-01-01 so they compare without a calendar
+// library. Only the ISO `YYYY-MM-DD` form is accepted.
+pub fn parse_expiry_date(value: &str) -> Option<i64> {
+    let mut parts = value.trim().splitn(3, '-');
+    let year = parts.next()?;
+    let month = parts.next()?;
+    let day = parts.next()?;
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+
+    let year = year.parse::<i64>().ok()?;
+    let month = month.parse::<i64>().ok()?;
+    let day = day.parse::<i64>().ok()?;
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    Some(days_from_civil(year, month, day))
+}
+
+pub fn pass_file_expiry_date(contents: &str) -> Option<(String, i64)> {
+    searchable_pass_fields(contents)
+        .into_iter()
+        .filter(|field| EXPIRY_FIELD_KEYS.contains(&field.key.as_str()))
+        .find_map(|field| {
+            let day = parse_expiry_date(&field.value)?;
+            Some((field.value.trim().to_string(), day))
+        })
+}
+
+pub fn today() -> i64 {
+    if let Ok(now) = adw::glib::DateTime::now_local() {
+        return days_from_civil(
+            i64::from(now.year()),
+            i64::from(now.month()),
+            i64::from(now.day_of_month()),
+        );
+    }
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| (duration.as_secs() / 86_400) as i64)
+}
+
+pub const fn expiry_status(expires: i64, today: i64) -> ExpiryStatus {
+    let remaining = expires - today;
+    if remaining < 0 {
+        ExpiryStatus::Expired
+    } else if remaining <= EXPIRY_WARNING_DAYS {
+        ExpiryStatus::ExpiresSoon(remaining)
+    } else {
+        ExpiryStatus::Current
+    }
+}
+
+pub fn pass_file_expiry_status(contents: &str) -> Option<ExpiryStatus> {
+    let (_, expires) = pass_file_expiry_date(contents)?;
+    Some(expiry_status(expires, today()))
+}
+
+// Reminders are sent once per app run so reloading the list doesn't repeat them.
+pub fn send_expiry_reminder(count: usize) {
+    if count == 0 || EXPIRY_REMINDER_SENT.with(Cell::get) {
+        return;
+    }
+    let Some(app) = gio::Application::default() else {
+        return;
+    };
+
+    let notification = Notification::new(
+        &ngettext(
+            "{count} item needs rotation",
+            "{count} items need rotation",
+            count,
+        )
+        .replace("{count}", &count.to_string()),
+    );
+    notification.set_body(Some(&gettext(
+        "Search for “find needs rotation” to see them.",
+    )));
+    app.send_notification(Some(EXPIRY_NOTIFICATION_ID), &notification);
+    EXPIRY_REMINDER_SENT.with(|sent| sent.set(true));
+}
+
+const fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+const fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+const fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expiry_status, parse_expiry_date, pass_file_expiry_date, ExpiryStatus};
+
+    #[test]
+    fn expiry_dates_count_days_since_the_epoch() {
+        assert_eq!(parse_expiry_date("1970-01-01"), Some(0));
+        assert_eq!(parse_expiry_date("2000-03-01"), Some(11_017));
+        assert_eq!(parse_expiry_date(" 2024-02-29 "), Some(19_782));
+    }
+
+    #[test]
+    fn invalid_expiry_dates_are_ignored() {
+        for value in ["2023-02-29", "2025-13-01", "2025-1-1", "next year", ""] {
+            assert_eq!(parse_expiry_date(value), None, "{value}");
+        }
+    }
+
+    #[test]
+    fn expiry_field_is_read_from_pass_files() {
+        assert_eq!(
+            pass_file_expiry_date("secret\nusername: alice\nexpires: 2025-12-01\n"),
+            Some(("2025-12-01".to_string(), 20_423))
+        );
+        assert_eq!(
+            pass_file_expiry_date("secret\nExpiry: 2025-12-01"),
+            Some(("2025-12-01".to_string(), 20_423))
+        );
+        assert_eq!(pass_file_expiry_date("secret\nexpires: soon"), None);
+        assert_eq!(pass_file_expiry_date("secret"), None);
+    }
+
+    #[test]
+    fn expiry_status_warns_before_the_date() {
+        assert_eq!(expiry_status(99, 100), ExpiryStatus::Expired);
+        assert_eq!(expiry_status(100, 100), ExpiryStatus::ExpiresSoon(0));
+        assert_eq!(expiry_status(130, 100), ExpiryStatus::ExpiresSoon(30));
+        assert_eq!(expiry_status(131, 100), ExpiryStatus::Current);
+        assert!(ExpiryStatus::Expired.needs_rotation());
+        assert!(!ExpiryStatus::Current.needs_rotation());
+    }
+}
@@ -0,0 +1,88 @@
+const CLIP_DIRECTIVE_KEY: &str = "clip";
+
+fn field_line(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    Some((key.trim(), value.trim()))
+}
+
+fn numbered_line(contents: &str, line: usize) -> Option<String> {
+    contents
+        .lines()
+        .nth(line.checked_sub(1)?)
+        .map(str::to_string)
+}
+
+// Like `pass -c N`, an explicit line number copies that whole line. Without
+// one, a `clip:` line picks what gets copied: a line number, or the name of a
+// field whose value is copied. Anything else copies the password line.
+pub fn pass_file_clipboard_text(contents: &str, line: Option<usize>) -> Option<String> {
+    if let Some(line) = line {
+        return numbered_line(contents, line);
+    }
+
+    let directive = contents.lines().skip(1).find_map(|line| {
+        let (key, value) = field_line(line)?;
+        key.eq_ignore_ascii_case(CLIP_DIRECTIVE_KEY)
+            .then_some(value)
+    });
+    let password = || contents.lines().next().unwrap_or_default().to_string();
+    let Some(directive) = directive.filter(|value| !value.is_empty()) else {
+        return Some(password());
+    };
+
+    if let Ok(line) = directive.parse::<usize>() {
+        return numbered_line(contents, line).or_else(|| Some(password()));
+    }
+
+    let field_value = contents.lines().skip(1).find_map(|line| {
+        let (key, value) = field_line(line)?;
+        (key.eq_ignore_ascii_case(directive) && !key.eq_ignore_ascii_case(CLIP_DIRECTIVE_KEY))
+            .then(|| value.to_string())
+    });
+    Some(field_value.unwrap_or_else(password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pass_file_clipboard_text;
+
+    const CONTENTS: &str = "hunter2\nusername: alice\npin: 1234\nclip: PIN";
+
+    #[test]
+    fn explicit_lines_copy_the_whole_line() {
+        assert_eq!(
+            pass_file_clipboard_text(CONTENTS, Some(2)).as_deref(),
+            Some("username: alice")
+        );
+        assert_eq!(pass_file_clipboard_text(CONTENTS, Some(0)), None);
+        assert_eq!(pass_file_clipboard_text(CONTENTS, Some(9)), None);
+    }
+
+    #[test]
+    fn clip_directive_picks_a_field_or_a_line() {
+        assert_eq!(
+            pass_file_clipboard_text(CONTENTS, None).as_deref(),
+            Some("1234")
+        );
+        assert_eq!(
+            pass_file_clipboard_text("hunter2\nsecond line\nclip: 2", None).as_deref(),
+            Some("second line")
+        );
+    }
+
+    #[test]
+    fn missing_or_unknown_directives_copy_the_password() {
+        assert_eq!(
+            pass_file_clipboard_text("hunter2\nusername: alice", None).as_deref(),
+            Some("hunter2")
+        );
+        assert_eq!(
+            pass_file_clipboard_text("hunter2\nclip: token", None).as_deref(),
+            Some("hunter2")
+        );
+        assert_eq!(
+            pass_file_clipboard_text("hunter2\nclip: 7", None).as_deref(),
+            Some("hunter2")
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use super::parse::parse_structured_pass_lines;
+use super::types::StructuredPassLine;
+use crate::i18n::{gettext, ngettext};
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PassFileChangeKind {
+    Unchanged,
+    Changed,
+    Added,
+    Removed,
+}
+
+impl PassFileChangeKind {
+    fn between(previous: Option<&str>, next: Option<&str>) -> Self {
+        match (previous, next) {
+            (None, Some(_)) => Self::Added,
+            (Some(_), None) => Self::Removed,
+            (previous, next) if previous == next => Self::Unchanged,
+            _ => Self::Changed,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Self::Unchanged => gettext("Unchanged"),
+            Self::Changed => gettext("Changed"),
+            Self::Added => gettext("Added"),
+            Self::Removed => gettext("Removed"),
+        }
+    }
+}
+
+// Changes never carry values, so the review dialog can't leak a secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PassFileChange {
+    Password(PassFileChangeKind),
+    Username(PassFileChangeKind),
+    Otp(PassFileChangeKind),
+    Field {
+        name: String,
+        kind: PassFileChangeKind,
+    },
+    Notes {
+        added: usize,
+        removed: usize,
+    },
+}
+
+impl PassFileChange {
+    pub const fn is_change(&self) -> bool {
+        match self {
+            Self::Password(kind) | Self::Username(kind) | Self::Otp(kind) => {
+                !matches!(kind, PassFileChangeKind::Unchanged)
+            }
+            Self::Field { kind, .. } => !matches!(kind, PassFileChangeKind::Unchanged),
+            Self::Notes { added, removed } => *added > 0 || *removed > 0,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        match self {
+            Self::Password(_) => gettext("Password"),
+            Self::Username(_) => gettext("Username"),
+            Self::Otp(_) => gettext("OTP"),
+            Self::Field { name, .. } => name.clone(),
+            Self::Notes { .. } => gettext("Notes"),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::Password(kind) | Self::Username(kind) | Self::Otp(kind) => kind.label(),
+            Self::Field { kind, .. } => kind.label(),
+            Self::Notes { added, removed } => {
+                let mut parts = Vec::new();
+                if *added > 0 {
+                    parts.push(
+                        ngettext("{count} line added", "{count} lines added", *added)
+                            .replace("{count}", &added.to_string()),
+                    );
+                }
+                if *removed > 0 {
+                    parts.push(
+                        ngettext("{count} line removed", "{count} lines removed", *removed)
+                            .replace("{count}", &removed.to_string()),
+                    );
+                }
+                parts.join(", ")
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct PassFileParts {
+    password: String,
+    username: Option<String>,
+    otp: Option<String>,
+    fields: Vec<(String, String)>,
+    notes: Vec<String>,
+}
+
+fn pass_file_parts(contents: &str) -> PassFileParts {
+    let (password, lines) = parse_structured_pass_lines(contents);
+    let mut parts = PassFileParts {
+        password,
+        ..PassFileParts::default()
+    };
+    for (line, value) in lines {
+        match line {
+            StructuredPassLine::Username(_) => parts.username = value,
+            StructuredPassLine::Otp(_) => parts.otp = value,
+            StructuredPassLine::Field(template) => {
+                parts
+                    .fields
+                    .push((template.title, value.unwrap_or_default()));
+            }
+            StructuredPassLine::Preserved(line) if line.trim().is_empty() => {}
+            StructuredPassLine::Preserved(line) => parts.notes.push(line),
+        }
+    }
+    parts
+}
+
+// Fields are matched by name, and repeated names by position, so reordering
+// lines isn't reported as a change.
+fn field_changes(previous: &[(String, String)], next: &[(String, String)]) -> Vec<PassFileChange> {
+    let mut previous_by_name = HashMap::<String, Vec<&str>>::new();
+    for (name, value) in previous {
+        previous_by_name
+            .entry(name.to_lowercase())
+            .or_default()
+            .push(value.as_str());
+    }
+
+    let mut seen = HashMap::<String, usize>::new();
+    let mut changes = Vec::new();
+    for (name, value) in next {
+        let key = name.to_lowercase();
+        let index = seen.entry(key.clone()).or_default();
+        let previous_value = previous_by_name
+            .get(&key)
+            .and_then(|values| values.get(*index))
+            .copied();
+        *index += 1;
+        changes.push(PassFileChange::Field {
+            name: name.clone(),
+            kind: PassFileChangeKind::between(previous_value, Some(value)),
+        });
+    }
+
+    for (name, _) in previous {
+        let key = name.to_lowercase();
+        let remaining = seen.entry(key).or_default();
+        if *remaining > 0 {
+            *remaining -= 1;
+            continue;
+        }
+        changes.push(PassFileChange::Field {
+            name: name.clone(),
+            kind: PassFileChangeKind::Removed,
+        });
+    }
+
+    changes
+}
+
+fn notes_change(previous: &[String], next: &[String]) -> PassFileChange {
+    let mut remaining = HashMap::<&str, usize>::new();
+    for line in previous {
+        *remaining.entry(line.as_str()).or_default() += 1;
+    }
+
+    let mut added = 0;
+    for line in next {
+        match remaining.get_mut(line.as_str()) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => added += 1,
+        }
+    }
+
+    PassFileChange::Notes {
+        added,
+        removed: remaining.values().sum(),
+    }
+}
+
+pub fn pass_file_changes(previous: &str, next: &str) -> Vec<PassFileChange> {
+    let previous = pass_file_parts(previous);
+    let next = pass_file_parts(next);
+
+    let mut changes = vec![PassFileChange::Password(PassFileChangeKind::between(
+        Some(previous.password.as_str()),
+        Some(next.password.as_str()),
+    ))];
+    if previous.username.is_some() || next.username.is_some() {
+        changes.push(PassFileChange::Username(PassFileChangeKind::between(
+            previous.username.as_deref(),
+            next.username.as_deref(),
+        )));
+    }
+    if previous.otp.is_some() || next.otp.is_some() {
+        changes.push(PassFileChange::Otp(PassFileChangeKind::between(
+            previous.otp.as_deref(),
+            next.otp.as_deref(),
+        )));
+    }
+    changes.extend(
+        field_changes(&previous.fields, &next.fields)
+            .into_iter()
+            .filter(PassFileChange::is_change),
+    );
+    let notes = notes_change(&previous.notes, &next.notes);
+    if notes.is_change() {
+        changes.push(notes);
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pass_file_changes, PassFileChange, PassFileChangeKind};
+
+    #[test]
+    fn pass_file_changes_report_each_part() {
+        assert_eq!(
+            pass_file_changes(
+                "old\nusername: alice\nurl: https://example.com\npin: 1234\nfirst note",
+                "new\nusername: alice\nurl: https://example.org\nemail: alice@example.com\nfirst note\nsecond note\nthird note",
+            ),
+            vec![
+                PassFileChange::Password(PassFileChangeKind::Changed),
+                PassFileChange::Username(PassFileChangeKind::Unchanged),
+                PassFileChange::Field {
+                    name: "url".to_string(),
+                    kind: PassFileChangeKind::Changed,
+                },
+                PassFileChange::Field {
+                    name: "email".to_string(),
+                    kind: PassFileChangeKind::Added,
+                },
+                PassFileChange::Field {
+                    name: "pin".to_string(),
+                    kind: PassFileChangeKind::Removed,
+                },
+                PassFileChange::Notes {
+                    added: 2,
+                    removed: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reordered_fields_are_not_changes() {
+        let changes = pass_file_changes(
+            "secret\nurl: https://example.com\nemail: a@example.com\nnote",
+            "secret\nemail: a@example.com\nurl: https://example.com\nnote",
+        );
+        assert_eq!(
+            changes,
+            vec![PassFileChange::Password(PassFileChangeKind::Unchanged)]
+        );
+        assert!(!changes.iter().any(PassFileChange::is_change));
+    }
+
+    #[test]
+    fn otp_changes_are_reported_without_values() {
+        assert_eq!(
+            pass_file_changes(
+                "secret\notpauth://totp/Test?secret=OLD",
+                "secret\notpauth://totp/Test?secret=NEW",
+            )[1],
+            PassFileChange::Otp(PassFileChangeKind::Changed)
+        );
+    }
+}
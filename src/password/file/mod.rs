@@ -1,16 +1,23 @@
+mod clip;
 mod compose;
+mod diff;
+mod notes;
 mod parse;
 mod row_ui;
 mod types;
 mod url;
 
+pub use self::clip::pass_file_clipboard_text;
 #[cfg(test)]
 pub use self::compose::structured_pass_contents_from_values;
 pub use self::compose::{
-    apply_pass_file_template_contents, clean_pass_file_contents,
+    apply_pass_file_template_contents, clean_pass_file_contents, new_pass_file_contents_for_path,
     new_pass_file_contents_from_template, pass_file_has_missing_template_fields,
+    pass_file_is_secure_note, pass_file_needs_raw_editor, replace_field_value_contents,
     structured_pass_contents, sync_username_row, sync_username_row_from_parsed_lines,
 };
+pub use self::diff::{pass_file_changes, PassFileChange};
+pub use self::notes::{connect_notes_preview_links, structured_notes_markup};
 #[cfg(test)]
 pub use self::parse::structured_username_value;
 pub use self::parse::{
@@ -23,5 +30,4 @@ pub use self::types::UsernameFieldTemplate;
 pub use self::types::{
     DynamicFieldRow, DynamicFieldTemplate, OtpFieldTemplate, StructuredPassLine,
 };
-#[cfg(test)]
-pub use self::url::uri_to_open;
+pub use self::url::{open_pass_file_website, pass_file_website, uri_to_open};
@@ -0,0 +1,216 @@
+use super::types::{is_url_field_key, StructuredPassLine};
+use super::url::{open_uri_in_browser, uri_to_open};
+use adw::glib;
+use adw::gtk::Label;
+use adw::prelude::*;
+use adw::ToastOverlay;
+
+fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn link_markup(uri: &str, text: &str) -> String {
+    format!(
+        "<a href=\"{}\">{}</a>",
+        escape_markup(uri),
+        escape_markup(text)
+    )
+}
+
+fn bare_url_len(text: &str) -> Option<usize> {
+    if !text.starts_with("https://") && !text.starts_with("http://") {
+        return None;
+    }
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    let url = text[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+    uri_to_open(url).map(|_| url.len())
+}
+
+fn markdown_link(text: &str) -> Option<(String, usize)> {
+    let rest = text.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let label = &rest[..label_end];
+    let target = &rest[label_end + 2..];
+    let target_end = target.find(')')?;
+    let uri =
+        uri_to_open(&target[..target_end]).filter(|_| target[..target_end].contains("://"))?;
+    Some((link_markup(&uri, label), 1 + label_end + 2 + target_end + 1))
+}
+
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<&'a str> {
+    let inner = text.strip_prefix(delimiter)?;
+    let end = inner.find(delimiter)?;
+    let inner = &inner[..end];
+    (!inner.is_empty() && !inner.starts_with(' ') && !inner.ends_with(' ')).then_some(inner)
+}
+
+// Covers the Markdown people tend to write in notes: `code`, **bold**,
+// *italic* or _italic_, [links](https://...), and bare web addresses.
+fn inline_markup(text: &str) -> String {
+    let mut markup = String::new();
+    let mut rest = text;
+    while let Some(ch) = rest.chars().next() {
+        let word_boundary = !matches!(
+            text[..text.len() - rest.len()].chars().last(),
+            Some(previous) if previous.is_alphanumeric()
+        );
+        if let Some(code) = delimited(rest, "`") {
+            markup.push_str(&format!("<tt>{}</tt>", escape_markup(code)));
+            rest = &rest[code.len() + 2..];
+        } else if let Some(bold) = delimited(rest, "**") {
+            markup.push_str(&format!("<b>{}</b>", inline_markup(bold)));
+            rest = &rest[bold.len() + 4..];
+        } else if let Some(italic) =
+            delimited(rest, "*").or_else(|| delimited(rest, "_").filter(|_| word_boundary))
+        {
+            markup.push_str(&format!("<i>{}</i>", inline_markup(italic)));
+            rest = &rest[italic.len() + 2..];
+        } else if let Some((link, len)) = markdown_link(rest) {
+            markup.push_str(&link);
+            rest = &rest[len..];
+        } else if let Some(len) = bare_url_len(rest) {
+            markup.push_str(&link_markup(&rest[..len], &rest[..len]));
+            rest = &rest[len..];
+        } else {
+            markup.push_str(&escape_markup(&ch.to_string()));
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    markup
+}
+
+fn note_line_markup(line: &str) -> String {
+    let content = line.trim_start();
+    let indent = "  ".repeat((line.len() - content.len()) / 2);
+    let heading_level = content.chars().take_while(|ch| *ch == '#').count();
+    if (1..=6).contains(&heading_level) && content[heading_level..].starts_with(' ') {
+        let heading = inline_markup(content[heading_level..].trim());
+        return if heading_level <= 2 {
+            format!("<big><b>{heading}</b></big>")
+        } else {
+            format!("<b>{heading}</b>")
+        };
+    }
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = content.strip_prefix(bullet) {
+            return format!("{indent}• {}", inline_markup(item));
+        }
+    }
+    if let Some(quote) = content.strip_prefix("> ") {
+        return format!("{indent}<i>{}</i>", inline_markup(quote));
+    }
+    format!("{indent}{}", inline_markup(content))
+}
+
+fn field_line_markup(key: &str, value: &str) -> String {
+    let value = if is_url_field_key(key) {
+        uri_to_open(value).map_or_else(|| inline_markup(value), |uri| link_markup(&uri, value))
+    } else {
+        inline_markup(value)
+    };
+    format!("<b>{}:</b> {value}", escape_markup(key.trim()))
+}
+
+// Secrets stay out of the formatted view: sensitive fields and OTP URLs are
+// left to their own rows.
+pub fn structured_notes_markup(lines: &[(StructuredPassLine, Option<String>)]) -> String {
+    let rendered = lines
+        .iter()
+        .filter_map(|(line, value)| match line {
+            StructuredPassLine::Field(template) if template.sensitive => None,
+            StructuredPassLine::Field(template) => Some(field_line_markup(
+                &template.raw_key,
+                value.as_deref().unwrap_or_default(),
+            )),
+            StructuredPassLine::Username(template) => Some(field_line_markup(
+                &template.raw_key,
+                value.as_deref().unwrap_or_default(),
+            )),
+            StructuredPassLine::Otp(_) => None,
+            StructuredPassLine::Preserved(line) => Some(note_line_markup(line)),
+        })
+        .collect::<Vec<_>>();
+    let start = rendered
+        .iter()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(rendered.len());
+    let end = rendered
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .map_or(start, |end| end + 1);
+    rendered[start..end].join("\n")
+}
+
+pub fn connect_notes_preview_links(label: &Label, overlay: &ToastOverlay) {
+    let overlay = overlay.clone();
+    label.connect_activate_link(move |_, uri| {
+        if let Some(uri) = uri_to_open(uri) {
+            open_uri_in_browser(&uri, &overlay);
+        }
+        glib::Propagation::Stop
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inline_markup, note_line_markup, structured_notes_markup};
+    use crate::password::file::parse_structured_pass_lines;
+
+    #[test]
+    fn inline_markdown_is_rendered_and_escaped() {
+        assert_eq!(
+            inline_markup("**PIN** is `1 < 2` & *fine*"),
+            "<b>PIN</b> is <tt>1 &lt; 2</tt> &amp; <i>fine</i>"
+        );
+        assert_eq!(inline_markup("2 * 3 * 4"), "2 * 3 * 4");
+        assert_eq!(inline_markup("snake_case_name"), "snake_case_name");
+        assert_eq!(
+            inline_markup("[docs](https://example.com/a?b=1&c=2)"),
+            "<a href=\"https://example.com/a?b=1&amp;c=2\">docs</a>"
+        );
+        assert_eq!(
+            inline_markup("[bad](javascript://alert(1))"),
+            "[bad](javascript://alert(1))"
+        );
+    }
+
+    #[test]
+    fn bare_urls_become_links_without_trailing_punctuation() {
+        assert_eq!(
+            inline_markup("see https://example.com/help."),
+            "see <a href=\"https://example.com/help\">https://example.com/help</a>."
+        );
+    }
+
+    #[test]
+    fn note_lines_render_headings_lists_and_quotes() {
+        assert_eq!(note_line_markup("# Recovery"), "<big><b>Recovery</b></big>");
+        assert_eq!(note_line_markup("### Codes"), "<b>Codes</b>");
+        assert_eq!(note_line_markup("  - second"), "  • second");
+        assert_eq!(note_line_markup("> careful"), "<i>careful</i>");
+        assert_eq!(note_line_markup("#hashtag"), "#hashtag");
+    }
+
+    #[test]
+    fn notes_markup_links_url_fields_and_skips_secrets() {
+        let (_, lines) = parse_structured_pass_lines(
+            "secret\nurl: example.com\npin: 1234\notpauth://totp/x?secret=ABC\n\n# Notes\n- first",
+        );
+
+        assert_eq!(
+            structured_notes_markup(&lines),
+            "<b>url:</b> <a href=\"https://example.com/\">example.com</a>\n\n<big><b>Notes</b></big>\n• first"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use super::types::{
-    is_otpauth_line, is_sensitive_field, is_username_field_key, DynamicFieldTemplate,
-    OtpFieldTemplate, StructuredPassLine, UsernameFieldTemplate,
+    is_concealed_field, is_otpauth_line, is_sensitive_field, is_username_field_key,
+    DynamicFieldTemplate, OtpFieldTemplate, StructuredPassLine, UsernameFieldTemplate,
+    CONCEALED_FIELD_PREFIX,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -47,18 +48,23 @@ pub fn canonical_search_field_key(key: &str) -> Option<String> {
     Some(key.to_ascii_lowercase())
 }
 
+pub(super) fn searchable_line_key(line: &StructuredPassLine) -> Option<String> {
+    match line {
+        StructuredPassLine::Username(_) => Some("username".to_string()),
+        StructuredPassLine::Otp(_) => None,
+        StructuredPassLine::Field(template) if template.concealed => None,
+        StructuredPassLine::Field(template) => canonical_search_field_key(&template.title),
+        StructuredPassLine::Preserved(_) => None,
+    }
+}
+
 pub fn searchable_pass_fields(contents: &str) -> Vec<SearchablePassField> {
     let (_, structured_lines) = parse_structured_pass_lines(contents);
     structured_lines
         .into_iter()
         .filter_map(|(line, value)| {
             let value = value?;
-            let key = match line {
-                StructuredPassLine::Username(_) => Some("username".to_string()),
-                StructuredPassLine::Otp(_) => None,
-                StructuredPassLine::Field(template) => canonical_search_field_key(&template.title),
-                StructuredPassLine::Preserved(_) => None,
-            }?;
+            let key = searchable_line_key(&line)?;
             let normalized_value = value.to_lowercase();
 
             Some(SearchablePassField {
@@ -84,6 +90,19 @@ pub fn parse_structured_pass_lines(
                 );
             }
 
+            if let Some((raw_key, title, raw_value)) = concealed_field_line(line) {
+                return (
+                    StructuredPassLine::Field(DynamicFieldTemplate {
+                        raw_key: raw_key.to_string(),
+                        title: title.to_string(),
+                        separator_spacing: leading_spacing(raw_value),
+                        sensitive: true,
+                        concealed: true,
+                    }),
+                    Some(trim_leading_spacing(raw_value)),
+                );
+            }
+
             let Some((raw_key, raw_value)) = line.split_once(':') else {
                 return (StructuredPassLine::Preserved(line.to_string()), None);
             };
@@ -118,7 +137,8 @@ pub fn parse_structured_pass_lines(
                     raw_key: raw_key.to_string(),
                     title,
                     separator_spacing: leading_spacing(raw_value),
-                    sensitive: is_sensitive_field(raw_key),
+                    sensitive: is_sensitive_field(raw_key) || is_concealed_field(raw_key),
+                    concealed: is_concealed_field(raw_key),
                 }),
                 Some(trim_leading_spacing(raw_value)),
             )
@@ -128,6 +148,30 @@ pub fn parse_structured_pass_lines(
     (password, structured)
 }
 
+fn concealed_field_line(line: &str) -> Option<(&str, &str, &str)> {
+    let start = line.len() - line.trim_start().len();
+    let prefix_end = start + CONCEALED_FIELD_PREFIX.len();
+    if !line
+        .get(start..prefix_end)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(CONCEALED_FIELD_PREFIX))
+    {
+        return None;
+    }
+
+    let (title, raw_value) = line[prefix_end..].split_once(':')?;
+    let title = title.trim();
+    if title.is_empty()
+        || raw_value.starts_with("//")
+        || is_username_field_key(title)
+        || is_otpauth_line(title, raw_value, line)
+    {
+        return None;
+    }
+
+    let key_end = line.len() - raw_value.len() - 1;
+    Some((&line[..key_end], title, raw_value))
+}
+
 fn leading_spacing(value: &str) -> String {
     value
         .chars()
@@ -143,7 +187,10 @@ fn trim_leading_spacing(value: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{pass_file_has_otp, searchable_pass_fields, SearchablePassField};
+    use super::{
+        parse_structured_pass_lines, pass_file_has_otp, searchable_pass_fields, SearchablePassField,
+    };
+    use crate::password::file::StructuredPassLine;
 
     fn field(key: &str, value: &str) -> SearchablePassField {
         SearchablePassField {
@@ -206,4 +253,25 @@ mod tests {
             vec![field("url", "https://example.com")]
         );
     }
+
+    #[test]
+    fn concealed_fields_are_masked_and_left_out_of_search() {
+        let contents = "secret\nsecret:Recovery: AAAA-BBBB\nbackup codes: 1234\nurl: https://example.com\nsecret: https://example.com";
+        assert_eq!(
+            searchable_pass_fields(contents),
+            vec![
+                field("url", "https://example.com"),
+                field("secret", "https://example.com"),
+            ]
+        );
+
+        let (_, lines) = parse_structured_pass_lines(contents);
+        let StructuredPassLine::Field(template) = &lines[0].0 else {
+            panic!("expected a field");
+        };
+        assert_eq!(template.title, "Recovery");
+        assert_eq!(template.raw_key, "secret:Recovery");
+        assert!(template.concealed && template.sensitive);
+        assert_eq!(lines[0].1.as_deref(), Some("AAAA-BBBB"));
+    }
 }
@@ -14,6 +14,8 @@ const SENSITIVE_FIELD_HINTS: [&str; 8] = [
     "phrase",
     "credential",
 ];
+pub(super) const CONCEALED_FIELD_PREFIX: &str = "secret:";
+const CONCEALED_FIELD_HINTS: [&str; 5] = ["recovery", "backup code", "pin", "puk", "seed"];
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct DynamicFieldTemplate {
@@ -21,6 +23,7 @@ pub struct DynamicFieldTemplate {
     pub(super) title: String,
     pub(super) separator_spacing: String,
     pub(super) sensitive: bool,
+    pub(super) concealed: bool,
 }
 
 impl DynamicFieldTemplate {
@@ -39,11 +42,13 @@ impl DynamicFieldTemplate {
             return Err("Use Add OTP secret instead.");
         }
 
+        let concealed = is_concealed_field(title);
         Ok(Self {
             raw_key: title.to_string(),
             title: title.to_string(),
             separator_spacing: " ".to_string(),
-            sensitive: sensitive.unwrap_or_else(|| Self::suggested_sensitive(title)),
+            sensitive: concealed || sensitive.unwrap_or_else(|| Self::suggested_sensitive(title)),
+            concealed,
         })
     }
 
@@ -87,6 +92,18 @@ pub enum StructuredPassLine {
     Preserved(String),
 }
 
+impl StructuredPassLine {
+    // Lines whose value is hidden in the editor: OTP secrets and sensitive
+    // or concealed fields.
+    pub const fn hides_value(&self) -> bool {
+        match self {
+            Self::Field(template) => template.sensitive || template.concealed,
+            Self::Otp(_) => true,
+            Self::Username(_) | Self::Preserved(_) => false,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub enum DynamicFieldRow {
     Plain(EntryRow),
@@ -153,8 +170,22 @@ pub(super) fn is_sensitive_field(key: &str) -> bool {
     SENSITIVE_FIELD_HINTS.iter().any(|hint| key.contains(hint))
 }
 
+pub(super) fn is_concealed_field(key: &str) -> bool {
+    let words = key
+        .to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let words = format!(" {words} ");
+    CONCEALED_FIELD_HINTS
+        .iter()
+        .any(|hint| words.contains(&format!(" {hint}")))
+}
+
 pub(super) fn is_url_field_key(key: &str) -> bool {
-    key.trim().eq_ignore_ascii_case("url")
+    let key = key.trim();
+    key.eq_ignore_ascii_case("url") || key.eq_ignore_ascii_case("website")
 }
 
 #[cfg(test)]
@@ -169,6 +200,21 @@ mod tests {
         assert_eq!(template.title, "API Token");
         assert_eq!(template.separator_spacing, " ");
         assert!(template.sensitive);
+        assert!(!template.concealed);
+    }
+
+    #[test]
+    fn recovery_fields_are_concealed_even_when_not_marked_sensitive() {
+        let template =
+            DynamicFieldTemplate::new("Recovery codes", Some(false)).expect("create template");
+
+        assert!(template.concealed);
+        assert!(template.sensitive);
+        assert!(
+            !DynamicFieldTemplate::new("Shipping address", None)
+                .expect("create template")
+                .concealed
+        );
     }
 
     #[test]
@@ -1,10 +1,15 @@
+use super::parse::parse_structured_pass_lines;
+use super::types::{is_url_field_key, StructuredPassLine};
+use crate::clipboard::set_clipboard_text;
 use crate::i18n::gettext;
 use crate::logging::log_error;
+use crate::preferences::Preferences;
 use crate::support::ui::flat_icon_button_with_tooltip;
 use crate::support::uri::launch_default_uri;
 use adw::prelude::*;
 use adw::{EntryRow, Toast, ToastOverlay};
 use url::Url;
+use zeroize::Zeroizing;
 
 pub fn uri_to_open(value: &str) -> Option<String> {
     let value = value.trim();
@@ -25,6 +30,52 @@ pub fn uri_to_open(value: &str) -> Option<String> {
     }
 }
 
+fn first_web_address(text: &str) -> Option<String> {
+    text.split_whitespace()
+        .filter_map(|word| word.find("http").map(|start| &word[start..]))
+        .filter(|word| word.starts_with("https://") || word.starts_with("http://"))
+        .map(|word| word.trim_end_matches(['.', ',', ';', ':', '!', '?', ')', '>', '"']))
+        .find_map(uri_to_open)
+}
+
+// A `url:` or `website:` field wins over web addresses written in the notes.
+// Sensitive fields and OTP lines are never searched.
+pub fn pass_file_website(contents: &str) -> Option<String> {
+    let (_, lines) = parse_structured_pass_lines(contents);
+    let field_website = lines.iter().find_map(|(line, value)| match line {
+        StructuredPassLine::Field(template) if is_url_field_key(&template.raw_key) => {
+            value.as_deref().and_then(uri_to_open)
+        }
+        _ => None,
+    });
+    field_website.or_else(|| {
+        lines
+            .iter()
+            .zip(contents.lines().skip(1))
+            .filter(|((line, _), _)| match line {
+                StructuredPassLine::Field(template) => !template.sensitive,
+                StructuredPassLine::Otp(_) => false,
+                StructuredPassLine::Username(_) | StructuredPassLine::Preserved(_) => true,
+            })
+            .find_map(|(_, text)| first_web_address(text))
+    })
+}
+
+pub fn open_pass_file_website(contents: &str, overlay: &ToastOverlay) {
+    let Some(uri) = pass_file_website(contents) else {
+        overlay.add_toast(Toast::new(&gettext("This item doesn't have a website.")));
+        return;
+    };
+
+    if Preferences::new().copy_password_on_open_website() {
+        let password = Zeroizing::new(contents.lines().next().unwrap_or_default().to_string());
+        if !password.is_empty() && set_clipboard_text(&password, overlay, None) {
+            overlay.add_toast(Toast::new(&gettext("Copied the password.")));
+        }
+    }
+    open_uri_in_browser(&uri, overlay);
+}
+
 pub(super) fn add_open_url_suffix(
     row: &EntryRow,
     text: impl Fn() -> String + 'static,
@@ -38,16 +89,55 @@ pub(super) fn add_open_url_suffix(
             return;
         };
 
-        let overlay = overlay.clone();
-        let uri_for_log = uri.clone();
-        launch_default_uri(&uri, move |result| {
-            if let Err(error) = result {
-                log_error(format!(
-                    "Failed to open URL in the default browser.\nURL: {uri_for_log}\nerror: {error}"
-                ));
-                overlay.add_toast(Toast::new(&gettext("Couldn't open the link.")));
-            }
-        });
+        open_uri_in_browser(&uri, &overlay);
     });
     row.add_suffix(&button);
 }
+
+pub(super) fn open_uri_in_browser(uri: &str, overlay: &ToastOverlay) {
+    let overlay = overlay.clone();
+    let uri_for_log = uri.to_string();
+    launch_default_uri(uri, move |result| {
+        if let Err(error) = result {
+            log_error(format!(
+                "Failed to open URL in the default browser.\nURL: {uri_for_log}\nerror: {error}"
+            ));
+            overlay.add_toast(Toast::new(&gettext("Couldn't open the link.")));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pass_file_website;
+
+    #[test]
+    fn website_prefers_url_fields_over_notes() {
+        assert_eq!(
+            pass_file_website("secret\nsee https://docs.example.com\nwebsite: example.com"),
+            Some("https://example.com/".to_string())
+        );
+    }
+
+    #[test]
+    fn website_falls_back_to_web_addresses_in_notes() {
+        assert_eq!(
+            pass_file_website("secret\nLog in at (https://example.com/login)."),
+            Some("https://example.com/login".to_string())
+        );
+        assert_eq!(
+            pass_file_website("secret\nhttps://example.org"),
+            Some("https://example.org/".to_string())
+        );
+    }
+
+    #[test]
+    fn website_skips_sensitive_fields_and_otp_urls() {
+        assert_eq!(
+            pass_file_website(
+                "secret\nrecovery: https://hidden.example.com\notpauth://totp/x?secret=ABC"
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+// Listings changed this recently are read again next time. Some filesystems
+// only keep modification times to the second, so a second change within that
+// window would otherwise go unnoticed.
+const RECENTLY_MODIFIED_WINDOW: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DirectoryListing {
+    pub files: Vec<PathBuf>,
+    pub dirs: Vec<PathBuf>,
+}
+
+#[derive(Debug)]
+struct IndexedDirectory {
+    modified: SystemTime,
+    listing: Arc<DirectoryListing>,
+}
+
+fn store_index() -> &'static Mutex<HashMap<PathBuf, IndexedDirectory>> {
+    static INDEX: OnceLock<Mutex<HashMap<PathBuf, IndexedDirectory>>> = OnceLock::new();
+    INDEX.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn listing_can_be_reused(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified)
+        .is_ok_and(|age| age >= RECENTLY_MODIFIED_WINDOW)
+}
+
+fn read_directory_listing(dir: &Path) -> io::Result<DirectoryListing> {
+    let mut listing = DirectoryListing::default();
+    for entry_result in fs::read_dir(dir)? {
+        let Ok(entry) = entry_result else { continue };
+        let Ok(mut file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            let Ok(metadata) = fs::metadata(entry.path()) else {
+                continue;
+            };
+            file_type = metadata.file_type();
+        }
+
+        if file_type.is_dir() {
+            listing.dirs.push(entry.path());
+        } else if file_type.is_file() {
+            listing.files.push(entry.path());
+        }
+    }
+    Ok(listing)
+}
+
+// Walking a store means one `read_dir` per folder, which is slow on network
+// filesystems. A folder's modification time changes whenever items are added,
+// removed, or renamed in it, so unchanged folders reuse their last listing
+// and only changed folders are read again.
+pub fn indexed_directory_listing(dir: &Path) -> io::Result<Arc<DirectoryListing>> {
+    let modified = fs::metadata(dir)?.modified().ok();
+    let mut index = store_index()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let (Some(modified), Some(indexed)) = (modified, index.get(dir)) {
+        if indexed.modified == modified {
+            return Ok(indexed.listing.clone());
+        }
+    }
+
+    let listing = Arc::new(read_directory_listing(dir)?);
+    if let Some(previous) = index.remove(dir) {
+        for removed_dir in previous
+            .listing
+            .dirs
+            .iter()
+            .filter(|previous_dir| !listing.dirs.contains(previous_dir))
+        {
+            index.retain(|path, _| !path.starts_with(removed_dir));
+        }
+    }
+    if let Some(modified) = modified {
+        if listing_can_be_reused(modified, SystemTime::now()) {
+            index.insert(
+                dir.to_path_buf(),
+                IndexedDirectory {
+                    modified,
+                    listing: listing.clone(),
+                },
+            );
+        }
+    }
+    Ok(listing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{indexed_directory_listing, listing_can_be_reused, RECENTLY_MODIFIED_WINDOW};
+    use crate::logging::log_info;
+    use std::fs;
+    use std::path::Path;
+    use std::thread;
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn recently_modified_listings_are_read_again() {
+        let now = SystemTime::now();
+
+        assert!(!listing_can_be_reused(now, now));
+        assert!(!listing_can_be_reused(now + Duration::from_secs(5), now));
+        assert!(listing_can_be_reused(now - RECENTLY_MODIFIED_WINDOW, now));
+    }
+
+    #[test]
+    fn indexed_listings_pick_up_new_items() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-index-{nanos}"));
+        fs::create_dir_all(store.join("work")).expect("create store dir");
+        fs::write(store.join("first.gpg"), b"x").expect("write first secret");
+
+        let listing = indexed_directory_listing(&store).expect("list store");
+        assert_eq!(listing.files, vec![store.join("first.gpg")]);
+        assert_eq!(listing.dirs, vec![store.join("work")]);
+
+        fs::write(store.join("second.gpg"), b"x").expect("write second secret");
+        let mut files = indexed_directory_listing(&store)
+            .expect("list store again")
+            .files
+            .clone();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![store.join("first.gpg"), store.join("second.gpg")]
+        );
+
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+
+    fn list_folders(store: &Path, folders: usize) -> Duration {
+        let started = Instant::now();
+        for folder in 0..folders {
+            indexed_directory_listing(&store.join(format!("folder-{folder}")))
+                .expect("list store folder");
+        }
+        started.elapsed()
+    }
+
+    // Compares building the index for 10k items in 1k folders with reusing
+    // it. Run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn benchmark_index_rebuild() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-benchmark-index-{nanos}"));
+        let folders = 1_000;
+        for index in 0..10_000 {
+            let folder = store.join(format!("folder-{}", index % folders));
+            fs::create_dir_all(&folder).expect("create store folder");
+            fs::write(folder.join(format!("item-{index}.gpg")), b"x").expect("write secret");
+        }
+        thread::sleep(RECENTLY_MODIFIED_WINDOW);
+
+        let rebuild = list_folders(&store, folders);
+        let reuse = list_folders(&store, folders);
+
+        log_info(format!(
+            "index rebuild took {} ms, reuse took {} ms",
+            rebuild.as_millis(),
+            reuse.as_millis()
+        ));
+        assert!(
+            rebuild < Duration::from_secs(2),
+            "index rebuild took {rebuild:?}"
+        );
+        assert!(reuse < rebuild, "reusing the index took {reuse:?}");
+        fs::remove_dir_all(store).expect("remove test store");
+    }
+}
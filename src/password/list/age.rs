@@ -0,0 +1,181 @@
+use super::password_list_render_cycle_is_current;
+use super::row::sync_password_row_age;
+use crate::i18n::{gettext, ngettext};
+use crate::logging::log_error;
+use crate::password::model::PassEntry;
+use crate::support::background::spawn_result_task;
+use crate::support::git::{
+    store_git_entry_ages, store_git_entry_history, StoreGitEntryAges, StoreGitEntryCommit,
+};
+use crate::support::object_data::non_null_to_string_option;
+use crate::support::ui::{dialog_content_shell, local_time_text};
+use adw::gtk::{ListBox, Widget};
+use adw::prelude::*;
+use adw::{ActionRow, Dialog, PreferencesGroup, PreferencesPage, Toast, ToastOverlay};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+fn now_seconds() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|now| i64::try_from(now.as_secs()).ok())
+        .unwrap_or_default()
+}
+
+fn counted(singular: &str, plural: &str, count: i64) -> String {
+    ngettext(singular, plural, usize::try_from(count).unwrap_or_default())
+        .replace("{count}", &count.to_string())
+}
+
+// Rough on purpose: the row only needs to say whether a password is fresh
+// or years old.
+fn entry_age_label(seconds_ago: i64) -> String {
+    let days = seconds_ago.max(0) / DAY_SECONDS;
+    match days {
+        0 => gettext("Today"),
+        1..=13 => counted("{count} day ago", "{count} days ago", days),
+        14..=59 => counted("{count} week ago", "{count} weeks ago", days / 7),
+        60..=364 => counted("{count} month ago", "{count} months ago", days / 30),
+        _ => counted("{count} year ago", "{count} years ago", days / 365),
+    }
+}
+
+pub(super) fn password_row_age_label(committed_at: i64) -> String {
+    entry_age_label(now_seconds() - committed_at)
+}
+
+// Ages come from Git, so they load after the rows are shown and fill in as
+// soon as every store has answered.
+pub(super) fn load_password_list_entry_ages(list: &ListBox, generation: u64) {
+    let mut roots = BTreeSet::new();
+    let mut index = 0;
+    while let Some(row) = list.row_at_index(index) {
+        if let Some(root) = non_null_to_string_option(&row, "root") {
+            roots.insert(root);
+        }
+        index += 1;
+    }
+    if roots.is_empty() {
+        return;
+    }
+
+    let list = list.clone();
+    spawn_result_task(
+        move || {
+            roots
+                .into_iter()
+                .filter_map(|root| match store_git_entry_ages(&root) {
+                    Ok(ages) => Some((root, ages)),
+                    Err(err) => {
+                        log_error(format!("Failed to read item ages for '{root}': {err}"));
+                        None
+                    }
+                })
+                .collect::<HashMap<String, Arc<StoreGitEntryAges>>>()
+        },
+        move |ages| {
+            if !password_list_render_cycle_is_current(&list, generation) {
+                return;
+            }
+            let mut index = 0;
+            while let Some(row) = list.row_at_index(index) {
+                index += 1;
+                let (Some(root), Some(label)) = (
+                    non_null_to_string_option(&row, "root"),
+                    non_null_to_string_option(&row, "label"),
+                ) else {
+                    continue;
+                };
+                let committed_at = ages
+                    .get(&root)
+                    .and_then(|store_ages| store_ages.get(&label))
+                    .copied();
+                sync_password_row_age(&row, committed_at);
+            }
+        },
+        || {},
+    );
+}
+
+fn history_row(commit: &StoreGitEntryCommit) -> ActionRow {
+    let subject = if commit.subject.is_empty() {
+        commit.short_oid.clone()
+    } else {
+        commit.subject.clone()
+    };
+    ActionRow::builder()
+        .title(subject)
+        .subtitle(format!(
+            "{} · {} · {}",
+            local_time_text(commit.committed_at),
+            commit.author,
+            commit.short_oid
+        ))
+        .use_markup(false)
+        .build()
+}
+
+pub(super) fn present_entry_history_dialog(
+    parent: &impl IsA<Widget>,
+    entry: &PassEntry,
+    overlay: &ToastOverlay,
+) {
+    let store_path = entry.store_path.clone();
+    let label = entry.label();
+    let parent = parent.clone().upcast::<Widget>();
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    spawn_result_task(
+        {
+            let label = label.clone();
+            move || store_git_entry_history(&store_path, &label)
+        },
+        move |result| match result {
+            Ok(commits) if !commits.is_empty() => {
+                let group = PreferencesGroup::new();
+                for commit in &commits {
+                    group.add(&history_row(commit));
+                }
+                let page = PreferencesPage::new();
+                page.add(&group);
+                let title = gettext("History");
+                let dialog = Dialog::builder()
+                    .title(title.as_str())
+                    .content_width(640)
+                    .content_height(480)
+                    .child(&dialog_content_shell(&title, Some(&label), &page))
+                    .build();
+                dialog.present(Some(&parent));
+            }
+            Ok(_) => {
+                overlay.add_toast(Toast::new(&gettext("This item has no Git history yet.")));
+            }
+            Err(err) => {
+                log_error(format!("Failed to read the history of '{label}': {err}"));
+                overlay.add_toast(Toast::new(&gettext("Couldn't read the history.")));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't read the history.")));
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_age_label, DAY_SECONDS};
+
+    #[test]
+    fn entry_ages_round_to_the_nearest_useful_unit() {
+        assert_eq!(entry_age_label(60), "Today");
+        assert_eq!(entry_age_label(-60), "Today");
+        assert_eq!(entry_age_label(DAY_SECONDS), "1 day ago");
+        assert_eq!(entry_age_label(20 * DAY_SECONDS), "2 weeks ago");
+        assert_eq!(entry_age_label(90 * DAY_SECONDS), "3 months ago");
+        assert_eq!(entry_age_label(800 * DAY_SECONDS), "2 years ago");
+    }
+}
@@ -0,0 +1,168 @@
+use crate::backend::host_gpg_available;
+use crate::i18n::gettext;
+use crate::preferences::Preferences;
+use crate::store::labels::StoreLabels;
+use crate::store::management::NUMBERED_STORE_SHORTCUT_COUNT;
+use crate::support::actions::activate_widget_action;
+use crate::support::background::spawn_result_task;
+use crate::support::git::{git_command_available, has_git_repository};
+use crate::support::object_data::{cloned_data, set_cloned_data};
+use crate::support::runtime::has_host_permission;
+use adw::gtk::ListBox;
+use adw::prelude::*;
+use adw::Banner;
+
+const DEGRADED_MODE_BANNER_KEY: &str = "degraded-mode-banner";
+const DEGRADED_MODE_KEY: &str = "degraded-mode";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DegradedMode {
+    GpgMissing,
+    NoGitRepository { store: String, slot: usize },
+    SettingsFile,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct DegradedModeChecks {
+    host_backend_without_gpg: bool,
+    // Configured stores that have no Git repository, in store order.
+    stores_without_git: Vec<(usize, String)>,
+    settings_file: bool,
+}
+
+// Only one banner fits, so the mode that breaks the most is shown first:
+// without gpg nothing opens, without Git one store can't sync, and a
+// settings file only hides the settings from the desktop.
+fn degraded_mode(checks: DegradedModeChecks) -> Option<DegradedMode> {
+    if checks.host_backend_without_gpg {
+        return Some(DegradedMode::GpgMissing);
+    }
+    if let Some((index, store)) = checks
+        .stores_without_git
+        .into_iter()
+        .find(|(index, _)| *index < NUMBERED_STORE_SHORTCUT_COUNT)
+    {
+        return Some(DegradedMode::NoGitRepository {
+            store,
+            slot: index + 1,
+        });
+    }
+    checks.settings_file.then_some(DegradedMode::SettingsFile)
+}
+
+fn collect_degraded_mode_checks() -> DegradedModeChecks {
+    let settings = Preferences::new();
+    let git_available = has_host_permission() && git_command_available();
+    let stores_without_git = if git_available {
+        settings
+            .store_roots()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, root)| !has_git_repository(root))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    DegradedModeChecks {
+        host_backend_without_gpg: settings.uses_host_command_backend() && !host_gpg_available(),
+        stores_without_git,
+        settings_file: cfg!(target_os = "linux") && settings.uses_settings_file(),
+    }
+}
+
+fn degraded_mode_title(mode: &DegradedMode) -> String {
+    match mode {
+        DegradedMode::GpgMissing => {
+            gettext("GnuPG wasn't found, so the Host backend can't open or save items.")
+        }
+        DegradedMode::NoGitRepository { store, .. } => {
+            let settings = Preferences::new();
+            let label = StoreLabels::for_stores(&settings, &settings.store_roots()).label(store);
+            gettext("{store} has no Git repository, so it can't sync or keep history.")
+                .replace("{store}", &label)
+        }
+        DegradedMode::SettingsFile => {
+            gettext("The settings schema isn't installed, so settings are kept in a file instead.")
+        }
+    }
+}
+
+fn degraded_mode_action(mode: &DegradedMode) -> String {
+    match mode {
+        DegradedMode::GpgMissing => "win.open-preferences".to_string(),
+        DegradedMode::NoGitRepository { slot, .. } => format!("win.open-store-git-{slot}"),
+        DegradedMode::SettingsFile => "win.open-docs".to_string(),
+    }
+}
+
+fn show_degraded_mode(banner: &Banner, mode: Option<DegradedMode>) {
+    if let Some(mode) = &mode {
+        banner.set_title(&degraded_mode_title(mode));
+    }
+    banner.set_revealed(mode.is_some());
+    set_cloned_data(banner, DEGRADED_MODE_KEY, mode);
+}
+
+// The banner stays until the cause is gone; the checks run again each time
+// the list reloads, which is what every fix ends with.
+pub fn connect_degraded_mode_banner(list: &ListBox, banner: &Banner) {
+    banner.set_button_label(Some(&gettext("Fix…")));
+    banner.connect_button_clicked(|banner| {
+        let Some(mode) =
+            cloned_data::<_, Option<DegradedMode>>(banner, DEGRADED_MODE_KEY).flatten()
+        else {
+            return;
+        };
+        activate_widget_action(banner, &degraded_mode_action(&mode));
+    });
+    set_cloned_data(list, DEGRADED_MODE_BANNER_KEY, banner.clone());
+}
+
+pub(super) fn refresh_degraded_mode_banner(list: &ListBox) {
+    let Some(banner) = cloned_data::<_, Banner>(list, DEGRADED_MODE_BANNER_KEY) else {
+        return;
+    };
+
+    spawn_result_task(
+        collect_degraded_mode_checks,
+        move |checks| show_degraded_mode(&banner, degraded_mode(checks)),
+        || {},
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{degraded_mode, DegradedMode, DegradedModeChecks};
+
+    #[test]
+    fn the_most_limiting_degraded_mode_is_shown() {
+        let checks = DegradedModeChecks {
+            host_backend_without_gpg: false,
+            stores_without_git: vec![(1, "/stores/work".to_string())],
+            settings_file: true,
+        };
+        assert_eq!(
+            degraded_mode(checks.clone()),
+            Some(DegradedMode::NoGitRepository {
+                store: "/stores/work".to_string(),
+                slot: 2,
+            })
+        );
+        assert_eq!(
+            degraded_mode(DegradedModeChecks {
+                host_backend_without_gpg: true,
+                ..checks
+            }),
+            Some(DegradedMode::GpgMissing)
+        );
+        assert_eq!(
+            degraded_mode(DegradedModeChecks {
+                settings_file: true,
+                ..DegradedModeChecks::default()
+            }),
+            Some(DegradedMode::SettingsFile)
+        );
+        assert_eq!(degraded_mode(DegradedModeChecks::default()), None);
+    }
+}
@@ -0,0 +1,169 @@
+use super::row::sync_password_folder_row_stats;
+use super::search::{indexed_needs_attention, SearchRowFieldIndexState, SEARCH_FIELDS_KEY};
+use super::{
+    password_list_row_action_kind, password_list_row_depth, password_list_row_is_folder,
+    password_list_row_store_path,
+};
+use crate::support::object_data::cloned_data;
+use adw::gtk::{ListBox, ListBoxRow};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FolderStatsRow<'a> {
+    Folder {
+        store_path: &'a str,
+        depth: usize,
+    },
+    Entry {
+        store_path: &'a str,
+        depth: usize,
+        flagged: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct FolderStats {
+    entries: usize,
+    flagged: usize,
+}
+
+// Counts every item below a folder, nested folders included, the same way
+// the search visibility walks the folder chain.
+fn password_list_folder_stats(rows: &[FolderStatsRow<'_>]) -> Vec<Option<FolderStats>> {
+    let mut stats = vec![None; rows.len()];
+    let mut current_store = None::<&str>;
+    let mut folder_stack = Vec::<usize>::new();
+
+    for (index, row) in rows.iter().enumerate() {
+        let (FolderStatsRow::Folder { store_path, depth }
+        | FolderStatsRow::Entry {
+            store_path, depth, ..
+        }) = *row;
+        if current_store != Some(store_path) {
+            current_store = Some(store_path);
+            folder_stack.clear();
+        }
+        folder_stack.truncate(depth);
+
+        match *row {
+            FolderStatsRow::Folder { .. } => {
+                stats[index] = Some(FolderStats::default());
+                folder_stack.push(index);
+            }
+            FolderStatsRow::Entry { flagged, .. } => {
+                for folder_index in &folder_stack {
+                    if let Some(folder) = stats[*folder_index].as_mut() {
+                        folder.entries += 1;
+                        folder.flagged += usize::from(flagged);
+                    }
+                }
+            }
+        }
+    }
+
+    stats
+}
+
+fn row_needs_attention(row: &ListBoxRow) -> bool {
+    matches!(
+        cloned_data(row, SEARCH_FIELDS_KEY),
+        Some(SearchRowFieldIndexState::Indexed(fields)) if indexed_needs_attention(&fields)
+    )
+}
+
+// Flags only show up once the items have been indexed, which happens for
+// field searches and expiry reminders. Counts are always there.
+pub(super) fn sync_password_list_folder_stats(list: &ListBox) {
+    let mut rows = Vec::new();
+    let mut index = 0;
+    while let Some(row) = list.row_at_index(index) {
+        index += 1;
+        if password_list_row_action_kind(&row).is_some() {
+            continue;
+        }
+        let Some(store_path) = password_list_row_store_path(&row) else {
+            continue;
+        };
+        let is_folder = password_list_row_is_folder(&row);
+        let flagged = !is_folder && row_needs_attention(&row);
+        rows.push((row, store_path, is_folder, flagged));
+    }
+
+    let stats_rows = rows
+        .iter()
+        .map(|(row, store_path, is_folder, flagged)| {
+            let depth = password_list_row_depth(row);
+            if *is_folder {
+                FolderStatsRow::Folder { store_path, depth }
+            } else {
+                FolderStatsRow::Entry {
+                    store_path,
+                    depth,
+                    flagged: *flagged,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    for ((row, ..), stats) in rows.iter().zip(password_list_folder_stats(&stats_rows)) {
+        if let Some(stats) = stats {
+            sync_password_folder_row_stats(row, stats.entries, stats.flagged);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{password_list_folder_stats, FolderStats, FolderStatsRow};
+
+    #[test]
+    fn folder_stats_count_items_in_nested_folders() {
+        let rows = [
+            FolderStatsRow::Folder {
+                store_path: "/a",
+                depth: 0,
+            },
+            FolderStatsRow::Entry {
+                store_path: "/a",
+                depth: 1,
+                flagged: true,
+            },
+            FolderStatsRow::Folder {
+                store_path: "/a",
+                depth: 1,
+            },
+            FolderStatsRow::Entry {
+                store_path: "/a",
+                depth: 2,
+                flagged: false,
+            },
+            FolderStatsRow::Entry {
+                store_path: "/a",
+                depth: 0,
+                flagged: true,
+            },
+            FolderStatsRow::Entry {
+                store_path: "/b",
+                depth: 1,
+                flagged: true,
+            },
+        ];
+
+        assert_eq!(
+            password_list_folder_stats(&rows),
+            vec![
+                Some(FolderStats {
+                    entries: 2,
+                    flagged: 1,
+                }),
+                None,
+                Some(FolderStats {
+                    entries: 1,
+                    flagged: 0,
+                }),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+}
@@ -1,35 +1,57 @@
+mod age;
+mod degraded;
+mod folder_stats;
+mod onboarding;
 mod placeholder;
 mod row;
 mod search;
+mod watch;
 
+use self::age::load_password_list_entry_ages;
+pub use self::degraded::connect_degraded_mode_banner;
+use self::degraded::refresh_degraded_mode_banner;
+use self::folder_stats::sync_password_list_folder_stats;
 use self::placeholder::{
     register_placeholder_state, show_loading_placeholder, show_resolved_placeholder,
 };
 use self::row::{
     activate_selected_password_row_action, append_clear_search_action_row,
     append_new_password_action_row, append_password_folder_row, append_password_row,
-    SelectedPasswordRowAction,
+    PasswordRowLayout, SelectedPasswordRowAction, PASSWORD_LIST_SECTION_KEY,
 };
-use self::search::{search_controller_for_list, SearchFilterController};
-use crate::backend::password_entry_is_readable;
+use self::search::{
+    connect_search_contents_toggle, search_controller_for_list, SearchFilterController,
+};
+use self::watch::{
+    stop_watching_password_list, watch_password_list_folders, watched_password_list_folders,
+};
+use crate::backend::{password_entry_is_readable, store_write_generation};
 use crate::logging::{log_error, log_info};
 use crate::password::model::{
-    collect_all_password_items_with_options, CollectItemsOptions, PassEntry,
+    collect_all_password_items_with_options, collect_empty_password_folders,
+    labels_in_several_stores, CollectItemsOptions, PassEntry, PassFolder,
+};
+use crate::password::strength::{
+    item_strength_words, register_strength_dictionary, sync_strength_dictionaries,
+    ITEM_STRENGTH_DICTIONARY,
 };
-use crate::preferences::{PasswordListSortMode, Preferences};
-use crate::store::labels::shortened_store_label_map;
+use crate::preferences::{PasswordListDensity, PasswordListSortMode, Preferences};
+use crate::store::labels::StoreLabels;
 use crate::support::background::spawn_result_task;
 use crate::support::git::password_store_git_state_summary;
-use crate::support::object_data::{cloned_data, non_null_to_string_option, set_cloned_data};
+use crate::support::object_data::{
+    cloned_data, non_null_to_string_option, set_cloned_data, take_data,
+};
 use crate::support::runtime::has_host_permission;
 use crate::support::ui::{clear_list_box, connect_search_list_arrow_navigation};
 use adw::glib::{self, Propagation};
 use adw::gtk::{
-    gdk, Button, EventControllerKey, ListBox, ListBoxRow, PropagationPhase, SearchEntry, Widget,
+    gdk, Button, EventControllerKey, Label, ListBox, ListBoxRow, PropagationPhase, SearchEntry,
+    ToggleButton, Widget,
 };
 use adw::prelude::*;
 use adw::ToastOverlay;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashSet};
 use std::rc::Rc;
 use std::sync::{Mutex, OnceLock};
 
@@ -108,6 +130,8 @@ impl PasswordListActions {
 }
 
 const PASSWORD_LIST_RENDER_GENERATION_KEY: &str = "password-list-render-generation";
+const PASSWORD_LIST_SOURCE_KEY: &str = "password-list-source";
+const PASSWORD_LIST_CONTENTS_KEY: &str = "password-list-contents";
 const PASSWORD_ROW_RENDER_BATCH_SIZE: usize = 100;
 const PASSWORD_LIST_ROW_KIND_KEY: &str = "password-list-row-kind";
 const PASSWORD_LIST_ROW_DEPTH_KEY: &str = "password-list-row-depth";
@@ -155,8 +179,11 @@ struct PasswordFolderTree {
 
 #[derive(Clone)]
 struct PasswordListRenderContext {
-    store_labels: Rc<HashMap<String, String>>,
+    store_labels: Rc<StoreLabels>,
+    shared_labels: Rc<HashSet<String>>,
+    read_only_stores: Rc<HashSet<String>>,
     sort_mode: PasswordListSortMode,
+    density: PasswordListDensity,
     has_store_dirs: bool,
     generation: u64,
 }
@@ -234,6 +261,81 @@ const fn list_action_context(
     }
 }
 
+// What the rows on screen were read from. Going back to the list keeps them
+// as long as no write, store setting, or visibility toggle changed since, and
+// nothing changed the store folders from outside.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PasswordListSource {
+    store_writes: u64,
+    store_roots: Vec<String>,
+    read_only_roots: Vec<String>,
+    sort_mode: PasswordListSortMode,
+    density: PasswordListDensity,
+    show_hidden: bool,
+    show_duplicates: bool,
+}
+
+impl PasswordListSource {
+    fn current(settings: &Preferences, show_hidden: bool, show_duplicates: bool) -> Self {
+        let store_roots = settings.store_roots();
+        let read_only_roots = store_roots
+            .iter()
+            .filter(|root| settings.store_is_read_only(root))
+            .cloned()
+            .collect();
+        Self {
+            store_writes: store_write_generation(),
+            store_roots,
+            read_only_roots,
+            sort_mode: settings.password_list_sort_mode(),
+            density: settings.password_list_density(),
+            show_hidden,
+            show_duplicates,
+        }
+    }
+}
+
+pub fn show_passwords(
+    list: &ListBox,
+    actions: &PasswordListActions,
+    overlay: &ToastOverlay,
+    should_show_list_actions: Rc<dyn Fn() -> bool>,
+    show_hidden: bool,
+    show_duplicates: bool,
+) {
+    let source = PasswordListSource::current(&Preferences::new(), show_hidden, show_duplicates);
+    if cloned_data::<_, PasswordListSource>(list, PASSWORD_LIST_SOURCE_KEY).as_ref()
+        != Some(&source)
+    {
+        load_passwords_async(
+            list,
+            actions,
+            overlay,
+            should_show_list_actions,
+            show_hidden,
+            show_duplicates,
+        );
+        return;
+    }
+
+    // A load that is still running sets the actions itself when it finishes.
+    let Some(contents) = cloned_data::<_, ListContents>(list, PASSWORD_LIST_CONTENTS_KEY) else {
+        return;
+    };
+    if should_show_list_actions() {
+        update_list_actions(
+            actions,
+            list_action_context(
+                true,
+                !source.store_roots.is_empty(),
+                contents,
+                has_host_permission(),
+            ),
+        );
+    }
+    autofocus_first_password_list_row_if_needed(list);
+}
+
 pub fn load_passwords_async(
     list: &ListBox,
     actions: &PasswordListActions,
@@ -243,18 +345,38 @@ pub fn load_passwords_async(
     show_duplicates: bool,
 ) {
     clear_list_box(list);
+    stop_watching_password_list(list);
     let render_generation = start_password_list_render_cycle(list);
 
     let settings = Preferences::new();
     prune_missing_store_dirs(&settings);
+    let store_roots = settings.store_roots();
+    set_cloned_data(
+        list,
+        PASSWORD_LIST_SOURCE_KEY,
+        PasswordListSource::current(&settings, show_hidden, show_duplicates),
+    );
+    let _ = take_data::<_, ListContents>(list, PASSWORD_LIST_CONTENTS_KEY);
     let has_store_dirs = !settings.stores().is_empty();
     let sort_mode = settings.password_list_sort_mode();
-    let store_labels = Rc::new(shortened_store_label_map(&settings.store_roots()));
+    let density = settings.password_list_density();
+    let personal_strength_words = settings.personal_strength_words();
+    sync_strength_dictionaries(&settings);
+    sync_password_list_section_headers(list, sort_mode == PasswordListSortMode::TopFolder);
+    let store_labels = Rc::new(StoreLabels::for_stores(&settings, &store_roots));
+    let read_only_stores = Rc::new(
+        settings
+            .store_roots()
+            .into_iter()
+            .filter(|root| settings.store_is_read_only(root))
+            .collect::<HashSet<_>>(),
+    );
     if let Some(controller) = search_controller_for_list(list) {
         controller.begin_reload(has_store_dirs);
     }
     let git_available = has_host_permission();
     log_store_git_state(&settings);
+    refresh_degraded_mode_banner(list);
 
     if should_show_list_actions() {
         actions.git.set_visible(false);
@@ -273,41 +395,62 @@ pub fn load_passwords_async(
     let should_show_list_actions_for_disconnect = should_show_list_actions.clone();
     spawn_result_task(
         move || {
-            collect_all_password_items_with_options(collect_items_options(
-                show_hidden,
-                show_duplicates,
-            ))
-            .into_iter()
-            .map(|item| {
-                let label = item.label();
-                let readable = password_entry_is_readable(&item.store_path, &label);
-                (item, readable)
-            })
-            .collect::<Vec<_>>()
+            let options = collect_items_options(show_hidden, show_duplicates);
+            let items = collect_all_password_items_with_options(options);
+            if personal_strength_words {
+                let labels = items.iter().map(PassEntry::label).collect::<Vec<_>>();
+                register_strength_dictionary(
+                    ITEM_STRENGTH_DICTIONARY,
+                    item_strength_words(labels.iter().map(String::as_str)),
+                );
+            }
+            let shared_labels = labels_in_several_stores(&items);
+            let items = items
+                .into_iter()
+                .map(|item| {
+                    let label = item.label();
+                    let readable = password_entry_is_readable(&item.store_path, &label);
+                    (item, readable)
+                })
+                .collect::<Vec<_>>();
+            // Only the folder view has a place to show folders without items.
+            let empty_folders = if sort_mode == PasswordListSortMode::StorePath {
+                collect_empty_password_folders(options)
+            } else {
+                Vec::new()
+            };
+            (items, empty_folders, shared_labels)
         },
-        move |items| {
+        move |(items, empty_folders, shared_labels)| {
             if !password_list_render_cycle_is_current(&list_clone, render_generation) {
                 return;
             }
 
-            let show_list_actions = should_show_list_actions_for_result();
-            let context = list_action_context(
-                show_list_actions,
-                has_store_dirs,
-                if items.is_empty() {
-                    ListContents::Empty
-                } else {
-                    ListContents::Populated
-                },
-                git_available,
+            let contents = if items.is_empty() && empty_folders.is_empty() {
+                ListContents::Empty
+            } else {
+                ListContents::Populated
+            };
+            set_cloned_data(&list_clone, PASSWORD_LIST_CONTENTS_KEY, contents);
+            watch_password_list_folders(
+                &list_clone,
+                &store_roots,
+                &watched_password_list_folders(&store_roots, items.iter().map(|(item, _)| item)),
             );
+            let show_list_actions = should_show_list_actions_for_result();
+            let context =
+                list_action_context(show_list_actions, has_store_dirs, contents, git_available);
             render_password_rows_in_batches(
                 &list_clone,
                 &overlay_clone,
                 items,
+                empty_folders,
                 PasswordListRenderContext {
                     store_labels: store_labels.clone(),
+                    shared_labels: Rc::new(shared_labels),
+                    read_only_stores: read_only_stores.clone(),
                     sort_mode,
+                    density,
                     has_store_dirs,
                     generation: render_generation,
                 },
@@ -318,6 +461,7 @@ pub fn load_passwords_async(
                         if show_list_actions {
                             update_list_actions(&actions, context);
                         }
+                        sync_password_list_folder_stats(&list);
                         if let Some(controller) = search_controller_for_list(&list) {
                             controller.finish_reload(&list);
                         } else {
@@ -328,6 +472,9 @@ pub fn load_passwords_async(
                             );
                         }
                         autofocus_first_password_list_row_if_needed(&list);
+                        if git_available {
+                            load_password_list_entry_ages(&list, render_generation);
+                        }
                     }
                 },
             );
@@ -336,6 +483,8 @@ pub fn load_passwords_async(
             if !password_list_render_cycle_is_current(&list_for_disconnect, render_generation) {
                 return;
             }
+            let _ =
+                take_data::<_, PasswordListSource>(&list_for_disconnect, PASSWORD_LIST_SOURCE_KEY);
 
             let show_list_actions = should_show_list_actions_for_disconnect();
             let context = list_action_context(
@@ -360,10 +509,11 @@ fn render_password_rows_in_batches(
     list: &ListBox,
     overlay: &ToastOverlay,
     items: Vec<(PassEntry, bool)>,
+    empty_folders: Vec<PassFolder>,
     render_context: PasswordListRenderContext,
     on_complete: impl FnOnce() + 'static,
 ) {
-    let rows = build_password_list_rows(items, render_context.sort_mode);
+    let rows = build_password_list_rows(items, empty_folders, render_context.sort_mode);
     let show_new_password_action =
         should_append_new_password_action_row(render_context.has_store_dirs, !rows.is_empty());
     let show_clear_search_action = should_append_clear_search_action_row(!rows.is_empty());
@@ -375,7 +525,14 @@ fn render_password_rows_in_batches(
     let list = list.clone();
     let overlay = overlay.clone();
     let store_labels = render_context.store_labels;
+    let shared_labels = render_context.shared_labels;
+    let read_only_stores = render_context.read_only_stores;
     let generation = render_context.generation;
+    let layout = PasswordRowLayout {
+        compact: render_context.density == PasswordListDensity::Compact,
+        grouped: render_context.sort_mode == PasswordListSortMode::TopFolder,
+        shared_label: false,
+    };
     let mut rows = rows.into_iter();
     let mut on_complete = Some(on_complete);
     glib::idle_add_local(move || {
@@ -403,29 +560,39 @@ fn render_password_rows_in_batches(
                     folder_path,
                     depth,
                 } => {
-                    let store_label = store_labels
-                        .get(&store_path)
-                        .map_or(store_path.as_str(), String::as_str);
+                    let store_label = store_labels.label(&store_path);
+                    let writable = !read_only_stores.contains(&store_path);
                     append_password_folder_row(
                         &list,
                         &store_path,
-                        password_list_folder_title(&folder_path),
-                        &password_list_folder_subtitle(store_label, &folder_path),
+                        &folder_path,
+                        &password_list_folder_subtitle(&store_label, &folder_path),
                         depth,
+                        writable,
+                        &overlay,
                     );
                 }
                 RenderedPasswordListRow::Entry {
                     item,
                     readable,
                     depth,
-                } => append_password_row(
-                    &list,
-                    item,
-                    readable,
-                    &overlay,
-                    store_labels.clone(),
-                    depth,
-                ),
+                } => {
+                    let writable = !read_only_stores.contains(&item.store_path);
+                    let layout = PasswordRowLayout {
+                        shared_label: shared_labels.contains(&item.label()),
+                        ..layout
+                    };
+                    append_password_row(
+                        &list,
+                        item,
+                        readable,
+                        writable,
+                        &overlay,
+                        store_labels.clone(),
+                        depth,
+                        layout,
+                    );
+                }
             }
         }
 
@@ -433,12 +600,50 @@ fn render_password_rows_in_batches(
     });
 }
 
+fn password_list_section_header(title: &str) -> Label {
+    let header = Label::new(Some(title));
+    header.set_xalign(0.0);
+    header.set_margin_top(12);
+    header.set_margin_bottom(6);
+    header.set_margin_start(12);
+    header.set_margin_end(12);
+    header.add_css_class("heading");
+    header
+}
+
+// A header goes above the first visible row of each section, so it follows
+// the rows that search leaves on screen.
+fn sync_password_list_section_headers(list: &ListBox, grouped: bool) {
+    if !grouped {
+        list.unset_header_func();
+        return;
+    }
+
+    list.set_header_func(|row, before| {
+        let section = non_null_to_string_option(row, PASSWORD_LIST_SECTION_KEY);
+        let previous =
+            before.and_then(|before| non_null_to_string_option(before, PASSWORD_LIST_SECTION_KEY));
+        let Some(section) = section.filter(|section| previous.as_ref() != Some(section)) else {
+            row.set_header(None::<&Widget>);
+            return;
+        };
+        let current = row
+            .header()
+            .and_then(|header| header.downcast::<Label>().ok())
+            .is_some_and(|header| header.text() == section);
+        if !current {
+            row.set_header(Some(&password_list_section_header(&section)));
+        }
+    });
+}
+
 fn build_password_list_rows(
     items: Vec<(PassEntry, bool)>,
+    empty_folders: Vec<PassFolder>,
     sort_mode: PasswordListSortMode,
 ) -> Vec<RenderedPasswordListRow> {
     match sort_mode {
-        PasswordListSortMode::Filename => items
+        PasswordListSortMode::Filename | PasswordListSortMode::TopFolder => items
             .into_iter()
             .map(|(item, readable)| RenderedPasswordListRow::Entry {
                 item,
@@ -446,41 +651,48 @@ fn build_password_list_rows(
                 depth: 0,
             })
             .collect(),
-        PasswordListSortMode::StorePath => build_store_path_password_list_rows(items),
+        PasswordListSortMode::StorePath => {
+            build_store_path_password_list_rows(items, empty_folders)
+        }
     }
 }
 
 fn build_store_path_password_list_rows(
     items: Vec<(PassEntry, bool)>,
+    empty_folders: Vec<PassFolder>,
 ) -> Vec<RenderedPasswordListRow> {
-    let mut rows = Vec::new();
-    let mut current_store_path = None::<String>;
-    let mut tree = PasswordFolderTree::default();
-
+    let mut trees = BTreeMap::<String, PasswordFolderTree>::new();
     for (item, readable) in items {
-        if current_store_path.as_deref() != Some(item.store_path.as_str()) {
-            if let Some(store_path) = current_store_path.replace(item.store_path.clone()) {
-                append_store_folder_rows(&mut rows, &store_path, &tree, 0, None);
-                tree = PasswordFolderTree::default();
-            }
-        }
-
-        insert_password_tree_entry(&mut tree, item, readable);
+        let tree = trees.entry(item.store_path.clone()).or_default();
+        insert_password_tree_entry(tree, item, readable);
     }
-
-    if let Some(store_path) = current_store_path {
-        append_store_folder_rows(&mut rows, &store_path, &tree, 0, None);
+    for folder in empty_folders {
+        let tree = trees.entry(folder.store_path).or_default();
+        let _ = password_tree_folder(tree, &folder.relative_path);
     }
 
+    let mut rows = Vec::new();
+    for (store_path, tree) in &trees {
+        append_store_folder_rows(&mut rows, store_path, tree, 0, None);
+    }
     rows
 }
 
-fn insert_password_tree_entry(tree: &mut PasswordFolderTree, item: PassEntry, readable: bool) {
+fn password_tree_folder<'a>(
+    tree: &'a mut PasswordFolderTree,
+    relative_path: &str,
+) -> &'a mut PasswordFolderTree {
     let mut node = tree;
-    for segment in password_list_folder_segments(&item.relative_path) {
+    for segment in password_list_folder_segments(relative_path) {
         node = node.folders.entry(segment).or_default();
     }
-    node.entries.push((item, readable));
+    node
+}
+
+fn insert_password_tree_entry(tree: &mut PasswordFolderTree, item: PassEntry, readable: bool) {
+    password_tree_folder(tree, &item.relative_path)
+        .entries
+        .push((item, readable));
 }
 
 fn append_store_folder_rows(
@@ -605,6 +817,7 @@ const fn collect_items_options(show_hidden: bool, show_duplicates: bool) -> Coll
 pub fn setup_search_filter(
     list: &ListBox,
     search_entry: &SearchEntry,
+    search_contents_button: &ToggleButton,
     header_focus_target: &Widget,
     placeholder_stack: &adw::gtk::Stack,
     placeholder_status: &adw::StatusPage,
@@ -628,14 +841,38 @@ pub fn setup_search_filter(
     let list_for_entry = list.clone();
     search_entry.connect_search_changed(move |entry| {
         controller_for_entry.update_query(entry.text().as_str());
-        controller_for_entry.refresh_row_visibility(&list_for_entry);
-        controller_for_entry.start_indexing_if_needed(&list_for_entry);
-        list_for_entry.invalidate_filter();
-        controller_for_entry.update_placeholder(&list_for_entry);
+        controller_for_entry.refresh(&list_for_entry);
     });
+    connect_search_contents_toggle(list, search_entry, search_contents_button);
 
     connect_search_list_arrow_navigation(list, search_entry, password_list_row_is_focusable);
     connect_home_list_up_navigation(list, search_entry, header_focus_target);
+    connect_search_entry_keys(list, search_entry);
+}
+
+// Enter acts on the first match as if it was clicked, and Escape clears the
+// search before it hides it, so finding and copying an item works without
+// the mouse.
+fn connect_search_entry_keys(list: &ListBox, search_entry: &SearchEntry) {
+    let list_for_activate = list.clone();
+    search_entry.connect_activate(move |_| {
+        if let Some(row) = first_password_search_match(&list_for_activate) {
+            list_for_activate.select_row(Some(&row));
+            row.activate();
+        }
+    });
+
+    let list_for_stop = list.clone();
+    search_entry.connect_stop_search(move |entry| {
+        if !entry.text().is_empty() {
+            entry.set_text("");
+            return;
+        }
+        entry.set_visible(false);
+        if !focus_first_password_list_row(&list_for_stop) {
+            list_for_stop.grab_focus();
+        }
+    });
 }
 
 pub fn clear_password_search(search_entry: &SearchEntry, list: &ListBox) {
@@ -667,6 +904,24 @@ pub fn connect_selected_pass_file_shortcuts(list: &ListBox, overlay: &ToastOverl
     list.add_controller(controller);
 }
 
+// The copy password and copy username actions use the shortcuts set in
+// Preferences. While the list is showing they copy from the selected item.
+pub fn copy_from_selected_pass_file(
+    list: &ListBox,
+    overlay: &ToastOverlay,
+    username: bool,
+) -> bool {
+    if !list.is_mapped() {
+        return false;
+    }
+    let action = if username {
+        SelectedPasswordRowAction::CopyUsername
+    } else {
+        SelectedPasswordRowAction::Copy
+    };
+    activate_selected_password_row_action(list, overlay, action)
+}
+
 pub fn focus_first_password_list_row(list: &ListBox) -> bool {
     let Some(row) = first_password_list_row(list) else {
         return false;
@@ -743,6 +998,19 @@ fn focused_password_list_row_is_first(list: &ListBox) -> bool {
     focused_row.is_ancestor(list) && focused_row.index() == first_row.index()
 }
 
+// Items and folders come before the rows that offer to create an item or
+// clear the search, which only answer Enter when nothing else matched.
+fn first_password_search_match(list: &ListBox) -> Option<ListBoxRow> {
+    let mut index = 0;
+    while let Some(row) = list.row_at_index(index) {
+        if password_list_row_is_focusable(&row) && password_list_row_action_kind(&row).is_none() {
+            return Some(row);
+        }
+        index += 1;
+    }
+    first_password_list_row(list)
+}
+
 fn first_password_list_row(list: &ListBox) -> Option<ListBoxRow> {
     let mut index = 0;
     loop {
@@ -812,6 +1080,10 @@ pub(crate) fn password_list_row_action_kind(row: &ListBoxRow) -> Option<Password
     }
 }
 
+pub(crate) fn password_list_row_open_requested(row: &ListBoxRow) -> bool {
+    row::take_password_row_open_request(row)
+}
+
 pub(crate) fn refresh_password_list_filter(list: &ListBox) {
     if let Some(controller) = search_controller_for_list(list) {
         controller.refresh_row_visibility(list);
@@ -938,7 +1210,7 @@ mod tests {
         RenderedPasswordListRow, StoreSetup, Visibility,
     };
     use crate::password::list::row::SelectedPasswordRowAction;
-    use crate::password::model::{CollectItemsOptions, PassEntry};
+    use crate::password::model::{CollectItemsOptions, PassEntry, PassFolder};
     use crate::preferences::PasswordListSortMode;
     use adw::gtk::gdk;
 
@@ -1131,6 +1403,13 @@ mod tests {
             selected_pass_file_shortcut_action(gdk::Key::c, gdk::ModifierType::CONTROL_MASK),
             Some(SelectedPasswordRowAction::Copy)
         );
+        assert_eq!(
+            selected_pass_file_shortcut_action(
+                gdk::Key::C,
+                gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::SHIFT_MASK,
+            ),
+            None
+        );
         assert_eq!(
             selected_pass_file_shortcut_action(gdk::Key::F2, gdk::ModifierType::empty()),
             Some(SelectedPasswordRowAction::RenameFile)
@@ -1150,7 +1429,7 @@ mod tests {
         assert_eq!(
             selected_pass_file_shortcut_action(
                 gdk::Key::c,
-                gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::SHIFT_MASK,
+                gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::ALT_MASK,
             ),
             None
         );
@@ -1171,6 +1450,7 @@ mod tests {
                 (PassEntry::from_label("/tmp/store", "accounts/github"), true),
                 (PassEntry::from_label("/tmp/store", "github"), false),
             ],
+            Vec::new(),
             PasswordListSortMode::Filename,
         );
 
@@ -1201,6 +1481,7 @@ mod tests {
                 (PassEntry::from_label("/tmp/work", "work/alice/slack"), true),
                 (PassEntry::from_label("/tmp/work", "work/bob/matrix"), true),
             ],
+            Vec::new(),
             PasswordListSortMode::StorePath,
         );
 
@@ -1266,6 +1547,7 @@ mod tests {
                     true,
                 ),
             ],
+            Vec::new(),
             PasswordListSortMode::StorePath,
         );
 
@@ -1296,6 +1578,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn store_path_sort_rows_show_empty_folders() {
+        let rows = build_password_list_rows(
+            vec![(PassEntry::from_label("/tmp/personal", "work/github"), true)],
+            vec![
+                PassFolder {
+                    store_path: "/tmp/personal".to_string(),
+                    relative_path: "work/new/".to_string(),
+                },
+                PassFolder {
+                    store_path: "/tmp/work".to_string(),
+                    relative_path: "team/".to_string(),
+                },
+            ],
+            PasswordListSortMode::StorePath,
+        );
+
+        assert_eq!(
+            rows,
+            vec![
+                RenderedPasswordListRow::Folder {
+                    store_path: "/tmp/personal".to_string(),
+                    folder_path: "work".to_string(),
+                    depth: 0,
+                },
+                RenderedPasswordListRow::Folder {
+                    store_path: "/tmp/personal".to_string(),
+                    folder_path: "work/new".to_string(),
+                    depth: 1,
+                },
+                RenderedPasswordListRow::Entry {
+                    item: PassEntry::from_label("/tmp/personal", "work/github"),
+                    readable: true,
+                    depth: 1,
+                },
+                RenderedPasswordListRow::Folder {
+                    store_path: "/tmp/work".to_string(),
+                    folder_path: "team".to_string(),
+                    depth: 0,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn folder_segments_ignore_empty_path_parts() {
         assert_eq!(
@@ -1316,6 +1642,7 @@ mod tests {
                 PassEntry::from_label("/tmp/store", &format!("{relative_path}/entry")),
                 true,
             )],
+            Vec::new(),
             PasswordListSortMode::StorePath,
         );
 
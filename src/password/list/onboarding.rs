@@ -0,0 +1,84 @@
+use crate::backend::host_gpg_available;
+use crate::i18n::gettext;
+use crate::preferences::Preferences;
+use crate::support::background::spawn_result_task;
+use crate::support::runtime::supports_host_command_features;
+use adw::gtk::{Align, Box as GtkBox, Button, Label, Orientation};
+use adw::prelude::*;
+
+pub(super) const ONBOARDING_TITLE: &str = "Welcome to Keycord";
+pub(super) const ONBOARDING_DESCRIPTION: &str = "Keycord keeps each password in its own encrypted file, the same way pass does. A folder of those files is a store. Set one up to get started.";
+
+// The three ways into a first store. Each button runs the same window action
+// as the header buttons, so the usual folder picker, clone dialog, and key
+// page take it from there and reload the list once the store is saved.
+#[derive(Clone)]
+pub(super) struct PasswordListOnboarding {
+    root: GtkBox,
+    gpg_note: Label,
+}
+
+impl PasswordListOnboarding {
+    pub(super) fn new() -> Self {
+        let root = GtkBox::builder()
+            .orientation(Orientation::Vertical)
+            .spacing(12)
+            .halign(Align::Center)
+            .build();
+
+        let create = onboarding_button("Create a new store", "win.open-store-picker");
+        create.add_css_class("suggested-action");
+        root.append(&create);
+        if supports_host_command_features() {
+            root.append(&onboarding_button("Restore from Git", "win.git-clone"));
+        }
+        root.append(&onboarding_button(
+            "Use an existing folder",
+            "win.open-store-picker",
+        ));
+
+        let gpg_note = Label::builder()
+            .label(gettext(
+                "GnuPG wasn't found. Install gpg, or switch Backend to Integrated in Preferences.",
+            ))
+            .wrap(true)
+            .justify(adw::gtk::Justification::Center)
+            .max_width_chars(40)
+            .visible(false)
+            .build();
+        gpg_note.add_css_class("dim-label");
+        root.append(&gpg_note);
+
+        Self { root, gpg_note }
+    }
+
+    pub(super) const fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    // The host backend can't encrypt anything without gpg, so say so before
+    // the user picks a folder rather than after the first save fails.
+    pub(super) fn refresh(&self) {
+        if !Preferences::new().uses_host_command_backend() {
+            self.gpg_note.set_visible(false);
+            return;
+        }
+
+        let gpg_note = self.gpg_note.clone();
+        spawn_result_task(
+            host_gpg_available,
+            move |available| gpg_note.set_visible(!available),
+            || {},
+        );
+    }
+}
+
+fn onboarding_button(label: &str, action: &str) -> Button {
+    let button = Button::builder()
+        .label(gettext(label))
+        .action_name(action)
+        .width_request(240)
+        .build();
+    button.add_css_class("pill");
+    button
+}
@@ -1,3 +1,4 @@
+use super::onboarding::{PasswordListOnboarding, ONBOARDING_DESCRIPTION, ONBOARDING_TITLE};
 use crate::i18n::gettext;
 use crate::support::object_data::{cloned_data, set_cloned_data};
 use adw::glib;
@@ -16,6 +17,7 @@ pub(super) struct PasswordListPlaceholderState {
     status: StatusPage,
     spinner: Spinner,
     list_view: ScrolledWindow,
+    onboarding: PasswordListOnboarding,
 }
 
 impl PasswordListPlaceholderState {
@@ -25,6 +27,7 @@ impl PasswordListPlaceholderState {
             title: LOADING_TITLE,
             description: Some(LOADING_DESCRIPTION),
             spinner: true,
+            onboarding: false,
         });
     }
 
@@ -36,13 +39,15 @@ impl PasswordListPlaceholderState {
                     title: "No items yet",
                     description: Some("Add an item to get started."),
                     spinner: false,
+                    onboarding: false,
                 }
             } else {
                 PlaceholderPresentation {
                     icon_name: APP_ID,
-                    title: "No folders added",
-                    description: Some("Open Preferences to add a store folder."),
+                    title: ONBOARDING_TITLE,
+                    description: Some(ONBOARDING_DESCRIPTION),
                     spinner: false,
+                    onboarding: true,
                 }
             }
         } else {
@@ -51,6 +56,7 @@ impl PasswordListPlaceholderState {
                 title: "No matches",
                 description: Some("Try another query."),
                 spinner: false,
+                onboarding: false,
             }
         };
         self.sync(list, presentation);
@@ -70,6 +76,12 @@ impl PasswordListPlaceholderState {
         self.status.set_title(&gettext(presentation.title));
         let description = presentation.description.map(gettext);
         self.status.set_description(description.as_deref());
+        if presentation.onboarding {
+            self.status.set_child(Some(self.onboarding.widget()));
+            self.onboarding.refresh();
+        } else {
+            self.status.set_child(Some(&self.spinner));
+        }
         self.spinner.set_visible(presentation.spinner);
         self.spinner.set_spinning(presentation.spinner);
         self.stack.set_visible_child(&self.status);
@@ -82,6 +94,7 @@ struct PlaceholderPresentation {
     title: &'static str,
     description: Option<&'static str>,
     spinner: bool,
+    onboarding: bool,
 }
 
 pub(super) fn register_placeholder_state(
@@ -99,6 +112,7 @@ pub(super) fn register_placeholder_state(
             status: status.clone(),
             spinner: spinner.clone(),
             list_view: list_view.clone(),
+            onboarding: PasswordListOnboarding::new(),
         },
     );
 }
@@ -170,9 +184,12 @@ fn build_empty_password_list_placeholder(symbolic: &str, has_store_dirs: bool) -
             .description(gettext("Add an item to get started."))
             .build()
     } else {
+        let onboarding = PasswordListOnboarding::new();
+        onboarding.refresh();
         builder
-            .title(gettext("No folders added"))
-            .description(gettext("Open Preferences to add a store folder."))
+            .title(gettext(ONBOARDING_TITLE))
+            .description(gettext(ONBOARDING_DESCRIPTION))
+            .child(onboarding.widget())
             .build()
     }
 }
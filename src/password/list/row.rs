@@ -1,48 +1,76 @@
-use super::search::{SearchRowFieldIndexState, SEARCH_FIELDS_KEY};
+use super::age::{password_row_age_label, present_entry_history_dialog};
+use super::search::{
+    indexed_expiry_status, SearchRowFieldIndexState, SEARCH_CONTENT_MATCHES_KEY, SEARCH_FIELDS_KEY,
+};
 use super::{
-    refresh_password_list_filter, PasswordListActionRowKind, PASSWORD_LIST_ROW_DEPTH_KEY,
-    PASSWORD_LIST_ROW_EXPANDED_KEY, PASSWORD_LIST_ROW_KIND_ENTRY, PASSWORD_LIST_ROW_KIND_FOLDER,
-    PASSWORD_LIST_ROW_KIND_KEY, PASSWORD_LIST_ROW_STORE_PATH_KEY,
+    password_list_folder_title, refresh_password_list_filter, PasswordListActionRowKind,
+    PASSWORD_LIST_ROW_DEPTH_KEY, PASSWORD_LIST_ROW_EXPANDED_KEY, PASSWORD_LIST_ROW_KIND_ENTRY,
+    PASSWORD_LIST_ROW_KIND_FOLDER, PASSWORD_LIST_ROW_KIND_KEY, PASSWORD_LIST_ROW_STORE_PATH_KEY,
+};
+use crate::backend::{
+    read_password_entry, rename_password_entry, rename_password_folder, save_password_entry,
+};
+use crate::clipboard::{
+    copy_password_entry_line_to_clipboard, copy_password_entry_to_clipboard,
+    copy_password_entry_username_to_clipboard,
 };
-use crate::backend::rename_password_entry;
-use crate::clipboard::copy_password_entry_to_clipboard;
-use crate::i18n::gettext;
+use crate::i18n::{gettext, ngettext};
 use crate::logging::log_error;
+use crate::password::alias::alias_pass_file_contents;
 use crate::password::entry_files::normalize_password_entry_label;
+use crate::password::expiry::ExpiryStatus;
+use crate::password::file::open_pass_file_website;
 use crate::password::model::{OpenPassFile, PassEntry};
+use crate::password::new_folder::new_folder_problem;
+use crate::password::opened::{get_opened_pass_file, set_opened_pass_file};
+use crate::password::protected::{confirm_protected_entry_change, ProtectedChange};
 use crate::password::undo::{
     delete_entry_with_optional_undo, move_entry_between_stores_action, move_entry_to_store,
     push_undo_action, rename_entry_action, unavailable_undo_action, unavailable_undo_message,
     UndoError,
 };
 use crate::preferences::Preferences;
-use crate::store::labels::{shortened_store_label_for_path, shortened_store_labels};
+use crate::store::appearance::store_chip_markup;
+use crate::store::labels::{shortened_store_labels, StoreLabels};
+use crate::support::activity::record_activity;
 use crate::support::background::spawn_result_task;
-use crate::support::object_data::{cloned_data, set_cloned_data, set_string_data};
+use crate::support::object_data::{cloned_data, set_cloned_data, set_string_data, take_data};
 use crate::support::ui::{dim_label_icon, flat_icon_button, flat_icon_button_with_tooltip};
 use crate::support::uri::launch_default_uri;
 use crate::window::create_main_window;
 use adw::gio::{Menu, SimpleAction, SimpleActionGroup};
+use adw::glib;
 use adw::gtk::{
-    Button, DropDown, Image, ListBox, ListBoxRow, MenuButton, Stack, StringList,
-    INVALID_LIST_POSITION,
+    gdk, Button, DropDown, EventSequenceState, GestureClick, Image, Label, ListBox, ListBoxRow,
+    MenuButton, Stack, StringList, INVALID_LIST_POSITION,
 };
 use adw::prelude::*;
-use adw::{ActionRow, EntryRow, Toast, ToastOverlay};
+use adw::{ActionRow, AlertDialog, EntryRow, PreferencesGroup, SpinRow, Toast, ToastOverlay};
 use std::cell::{Cell, RefCell};
-use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use zeroize::Zeroizing;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) struct PasswordRowLayout {
+    pub(super) compact: bool,
+    pub(super) grouped: bool,
+    // Another store has an item with the same path, so the row names its
+    // store even where the layout would leave it out.
+    pub(super) shared_label: bool,
+}
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum TextEditMode {
     RenameFile,
     MoveWithinStore,
+    CreateAlias,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) enum SelectedPasswordRowAction {
     Copy,
+    CopyUsername,
     RenameFile,
     MoveWithinStore,
     Delete,
@@ -51,29 +79,43 @@ pub(super) enum SelectedPasswordRowAction {
 const UNREADABLE_PASSWORD_ROW_TOOLTIP: &str =
     "This item can't be opened with the private keys currently available in the app. File actions are still available, but copy and move-to-store are disabled until a compatible private key is available.";
 const PASSWORD_ROW_STATE_KEY: &str = "password-row-state";
+pub(super) const PASSWORD_LIST_SECTION_KEY: &str = "password-list-section";
 const PASSWORD_FOLDER_ROW_STATE_KEY: &str = "password-folder-row-state";
+const PASSWORD_ROW_OPEN_REQUEST_KEY: &str = "password-row-open-request";
 const OPEN_IN_NEW_WINDOW_LABEL: &str = "Open in New Window";
+const RENAME_FOLDER_BODY: &str =
+    "Every item in the folder moves along. Enter a path to move the folder elsewhere.";
+const COPY_LINE_DEFAULT: f64 = 2.0;
+const COPY_LINE_MAX: f64 = 99.0;
 const PASSWORD_LIST_INDENT_WIDTH: i32 = 18;
 const PASSWORD_LIST_MAX_INDENT_DEPTH: usize = 8;
 
-fn password_row_menu_entries(readable: bool) -> Vec<(&'static str, &'static str)> {
+fn password_row_menu_entries(readable: bool, writable: bool) -> Vec<(&'static str, &'static str)> {
     let mut entries = Vec::new();
     if readable {
+        entries.push(("Open", "entry.open"));
         entries.push((OPEN_IN_NEW_WINDOW_LABEL, "entry.open-new-window"));
+        entries.push(("Open website", "entry.open-website"));
+        entries.push(("Copy line…", "entry.copy-line"));
     }
-    entries.push(("Rename pass file", "entry.rename-file"));
-    entries.push(("Move pass file", "entry.move"));
-    if readable {
-        entries.push(("Move to store", "entry.move-store"));
+    if writable {
+        entries.push(("Rename pass file", "entry.rename-file"));
+        entries.push(("Move pass file", "entry.move"));
+        entries.push(("Create alias", "entry.create-alias"));
+        if readable {
+            entries.push(("Move to store", "entry.move-store"));
+        }
     }
     entries.push(("Open in File Manager", "entry.open-in-file-manager"));
-    entries.push(("Delete", "entry.delete"));
+    if writable {
+        entries.push(("Delete", "entry.delete"));
+    }
     entries
 }
 
 fn text_edit_apply_button_visible(mode: TextEditMode, value: &str) -> bool {
     match mode {
-        TextEditMode::RenameFile => !value.trim().is_empty(),
+        TextEditMode::RenameFile | TextEditMode::CreateAlias => !value.trim().is_empty(),
         TextEditMode::MoveWithinStore => true,
     }
 }
@@ -82,10 +124,15 @@ fn text_edit_apply_button_visible(mode: TextEditMode, value: &str) -> bool {
 struct PasswordRowState {
     item: Rc<RefCell<PassEntry>>,
     readable: bool,
+    writable: bool,
+    layout: PasswordRowLayout,
     row: ListBoxRow,
     stack: Stack,
     action_row: ActionRow,
-    store_labels: Rc<HashMap<String, String>>,
+    expiry_badge: Label,
+    age_button: Button,
+    store_chip: Label,
+    store_labels: Rc<StoreLabels>,
     text_edit_row: EntryRow,
     store_edit_row: ActionRow,
     store_dropdown: DropDown,
@@ -96,8 +143,13 @@ struct PasswordRowState {
 #[derive(Clone)]
 struct PasswordFolderRowState {
     row: ListBoxRow,
+    store_path: String,
+    folder_path: String,
+    writable: bool,
     folder_icon: Image,
     expand_icon: Image,
+    count_label: Label,
+    attention_badge: Label,
     expanded: Rc<Cell<bool>>,
 }
 
@@ -105,9 +157,11 @@ pub(super) fn append_password_row(
     list: &ListBox,
     item: PassEntry,
     readable: bool,
+    writable: bool,
     overlay: &ToastOverlay,
-    store_labels: Rc<HashMap<String, String>>,
+    store_labels: Rc<StoreLabels>,
     depth: usize,
+    layout: PasswordRowLayout,
 ) {
     let row = ListBoxRow::new();
     row.set_activatable(readable);
@@ -134,7 +188,13 @@ pub(super) fn append_password_row(
         .has_frame(false)
         .css_classes(vec!["flat"])
         .build();
+    let expiry_badge = build_expiry_badge();
+    let age_button = build_age_button();
+    let store_chip = build_store_chip();
     action_row.add_prefix(&unreadable_icon);
+    action_row.add_prefix(&store_chip);
+    action_row.add_suffix(&age_button);
+    action_row.add_suffix(&expiry_badge);
     action_row.add_suffix(&copy_button);
     action_row.add_suffix(&menu_button);
 
@@ -162,9 +222,14 @@ pub(super) fn append_password_row(
     let state = PasswordRowState {
         item: Rc::new(RefCell::new(item)),
         readable,
+        writable,
+        layout,
         row: row.clone(),
         stack,
         action_row,
+        expiry_badge,
+        age_button: age_button.clone(),
+        store_chip,
         store_labels,
         text_edit_row,
         store_edit_row,
@@ -176,8 +241,9 @@ pub(super) fn append_password_row(
     sync_password_row_display(&state);
     set_cloned_data(&row, SEARCH_FIELDS_KEY, SearchRowFieldIndexState::Unindexed);
 
-    configure_password_row_menu(&menu_button, &state, readable, list, overlay);
+    configure_password_row_menu(&menu_button, &state, list, overlay);
     connect_copy_action(&state, &copy_button, overlay);
+    connect_age_action(&state, &age_button, overlay);
     connect_text_edit_actions(&state, list, &text_cancel_button, overlay);
     connect_store_move_actions(
         &state,
@@ -193,15 +259,17 @@ pub(super) fn append_password_row(
 pub(super) fn append_password_folder_row(
     list: &ListBox,
     store_path: &str,
-    title: &str,
+    folder_path: &str,
     subtitle: &str,
     depth: usize,
+    writable: bool,
+    overlay: &ToastOverlay,
 ) {
     let row = ListBoxRow::new();
     row.set_activatable(true);
 
     let action_row = ActionRow::builder()
-        .title(title)
+        .title(password_list_folder_title(folder_path))
         .subtitle(subtitle)
         .subtitle_lines(1)
         .activatable(true)
@@ -209,7 +277,22 @@ pub(super) fn append_password_folder_row(
     action_row.set_margin_start(password_list_indent(depth));
     let folder_icon = dim_label_icon("folder-open-symbolic");
     let expand_icon = dim_label_icon("go-down-symbolic");
+    let menu_button = MenuButton::builder()
+        .icon_name("view-more-symbolic")
+        .has_frame(false)
+        .css_classes(vec!["flat"])
+        .visible(writable)
+        .build();
+    let count_label = Label::builder()
+        .valign(adw::gtk::Align::Center)
+        .css_classes(vec!["caption", "dim-label"])
+        .build();
+    let attention_badge = build_expiry_badge();
+    attention_badge.add_css_class("error");
     action_row.add_prefix(&folder_icon);
+    action_row.add_suffix(&attention_badge);
+    action_row.add_suffix(&count_label);
+    action_row.add_suffix(&menu_button);
     action_row.add_suffix(&expand_icon);
 
     row.set_child(Some(&action_row));
@@ -226,12 +309,18 @@ pub(super) fn append_password_folder_row(
     );
     let state = PasswordFolderRowState {
         row: row.clone(),
+        store_path: store_path.to_string(),
+        folder_path: folder_path.to_string(),
+        writable,
         folder_icon,
         expand_icon,
+        count_label,
+        attention_badge,
         expanded: Rc::new(Cell::new(false)),
     };
     set_cloned_data(&row, PASSWORD_FOLDER_ROW_STATE_KEY, state.clone());
     sync_password_folder_row_display(&state);
+    configure_password_folder_row_menu(&menu_button, &state, list, overlay);
     list.append(&row);
 }
 
@@ -283,6 +372,17 @@ fn append_password_list_action_row(
     list.append(&row);
 }
 
+// Rows can be set to copy when activated, so the menu's Open activates the
+// row with a mark that asks for the item page instead.
+fn request_password_row_open(row: &ListBoxRow) {
+    set_cloned_data(row, PASSWORD_ROW_OPEN_REQUEST_KEY, true);
+    row.activate();
+}
+
+pub(super) fn take_password_row_open_request(row: &ListBoxRow) -> bool {
+    take_data::<_, bool>(row, PASSWORD_ROW_OPEN_REQUEST_KEY).unwrap_or_default()
+}
+
 pub(super) fn toggle_password_folder_row(row: &ListBoxRow) -> bool {
     let Some(state): Option<PasswordFolderRowState> =
         cloned_data(row, PASSWORD_FOLDER_ROW_STATE_KEY)
@@ -298,18 +398,22 @@ pub(super) fn toggle_password_folder_row(row: &ListBoxRow) -> bool {
 fn configure_password_row_menu(
     menu_button: &MenuButton,
     state: &PasswordRowState,
-    readable: bool,
     list: &ListBox,
     overlay: &ToastOverlay,
 ) {
     let menu = Menu::new();
-    for (label, action) in password_row_menu_entries(readable) {
+    for (label, action) in password_row_menu_entries(state.readable, state.writable) {
         menu.append(Some(&gettext(label)), Some(action));
     }
     menu_button.set_menu_model(Some(&menu));
 
     let actions = SimpleActionGroup::new();
 
+    {
+        let row = state.row.clone();
+        add_menu_action(&actions, "open", move || request_password_row_open(&row));
+    }
+
     {
         let state = state.clone();
         let overlay = overlay.clone();
@@ -318,6 +422,22 @@ fn configure_password_row_menu(
         });
     }
 
+    {
+        let state = state.clone();
+        let overlay = overlay.clone();
+        add_menu_action(&actions, "open-website", move || {
+            open_entry_website(&state.item.borrow(), &overlay);
+        });
+    }
+
+    {
+        let state = state.clone();
+        let overlay = overlay.clone();
+        add_menu_action(&actions, "copy-line", move || {
+            present_copy_line_dialog(&state, &overlay);
+        });
+    }
+
     {
         let state = state.clone();
         add_menu_action(&actions, "rename-file", move || {
@@ -337,6 +457,14 @@ fn configure_password_row_menu(
         });
     }
 
+    {
+        let state = state.clone();
+        add_menu_action(&actions, "create-alias", move || {
+            let folder = state.item.borrow().relative_path.clone();
+            enter_text_edit_mode(&state, TextEditMode::CreateAlias, &folder);
+        });
+    }
+
     {
         let state = state.clone();
         let overlay = overlay.clone();
@@ -365,23 +493,235 @@ fn configure_password_row_menu(
     menu_button.insert_action_group("entry", Some(&actions));
 }
 
+// Copies one line of the item, like `pass -c N`. Line 1 is the password.
+fn present_copy_line_dialog(state: &PasswordRowState, overlay: &ToastOverlay) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Copy line"))
+        .body(gettext("Line 1 is the password."))
+        .build();
+    let line_row = SpinRow::with_range(1.0, COPY_LINE_MAX, 1.0);
+    line_row.set_title(&gettext("Line"));
+    line_row.set_value(COPY_LINE_DEFAULT);
+    let group = PreferencesGroup::new();
+    group.add(&line_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let copy = gettext("Copy");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("copy", copy.as_str())]);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("copy"));
+
+    let item = state.item.clone();
+    let overlay = overlay.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response != "copy" {
+            return;
+        }
+        let line = usize::try_from(line_row.value_as_int()).unwrap_or(1);
+        copy_password_entry_line_to_clipboard(item.borrow().clone(), overlay.clone(), line);
+    });
+    dialog.present(Some(&state.row));
+}
+
+fn configure_password_folder_row_menu(
+    menu_button: &MenuButton,
+    state: &PasswordFolderRowState,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+) {
+    let menu = Menu::new();
+    menu.append(Some(&gettext("Rename folder")), Some("folder.rename"));
+    menu.append(Some(&gettext("Folder keys")), Some("folder.keys"));
+    menu_button.set_menu_model(Some(&menu));
+
+    let actions = SimpleActionGroup::new();
+    {
+        let state = state.clone();
+        let list = list.clone();
+        let overlay = overlay.clone();
+        add_menu_action(&actions, "rename", move || {
+            present_rename_folder_dialog(&state, &list, &overlay);
+        });
+    }
+    {
+        let state = state.clone();
+        add_menu_action(&actions, "keys", move || {
+            let target = (state.store_path.clone(), state.folder_path.clone()).to_variant();
+            let _ = state
+                .row
+                .activate_action("win.open-folder-keys", Some(&target));
+        });
+    }
+    menu_button.insert_action_group("folder", Some(&actions));
+}
+
+fn present_rename_folder_dialog(
+    state: &PasswordFolderRowState,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Rename folder"))
+        .body(gettext(RENAME_FOLDER_BODY))
+        .build();
+    let folder_row = EntryRow::new();
+    folder_row.set_title(&gettext("Folder"));
+    folder_row.set_text(&state.folder_path);
+    let group = PreferencesGroup::new();
+    group.add(&folder_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let rename = gettext("Rename");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("rename", rename.as_str())]);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("rename"));
+    {
+        let dialog = dialog.clone();
+        folder_row.connect_changed(move |row| {
+            let problem = new_folder_problem(&row.text());
+            dialog.set_response_enabled("rename", problem.is_none());
+            dialog.set_body(&gettext(problem.unwrap_or(RENAME_FOLDER_BODY)));
+        });
+    }
+
+    let state = state.clone();
+    let list = list.clone();
+    let overlay = overlay.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response != "rename" {
+            return;
+        }
+        let new_folder = normalize_password_entry_label(&folder_row.text());
+        if new_folder != state.folder_path {
+            rename_folder(&state, &list, &overlay, new_folder);
+        }
+    });
+    dialog.present(Some(&state.row));
+}
+
+fn rename_folder(
+    state: &PasswordFolderRowState,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+    new_folder: String,
+) {
+    let store_path = state.store_path.clone();
+    let old_folder = state.folder_path.clone();
+    let list = list.clone();
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    spawn_result_task(
+        {
+            let store_path = store_path.clone();
+            let old_folder = old_folder.clone();
+            let new_folder = new_folder.clone();
+            move || rename_password_folder(&store_path, &old_folder, &new_folder)
+        },
+        move |result| match result {
+            Ok(()) => {
+                record_activity(format!("Renamed folder {old_folder} to {new_folder}"));
+                follow_opened_pass_file_into_folder(&list, &store_path, &old_folder, &new_folder);
+                overlay.add_toast(Toast::new(&gettext("Folder renamed.")));
+                request_password_list_reload(&list);
+            }
+            Err(err) => {
+                log_error(format!("Failed to rename password folder: {err}"));
+                overlay.add_toast(Toast::new(&gettext(err.rename_folder_toast_message())));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't rename the folder.")));
+        },
+    );
+}
+
+// Keeps the open item pointing at its file after its folder moved, so saving
+// it later doesn't recreate the old path.
+fn follow_opened_pass_file_into_folder(
+    list: &ListBox,
+    store_path: &str,
+    old_folder: &str,
+    new_folder: &str,
+) {
+    let Some(opened) = get_opened_pass_file(list) else {
+        return;
+    };
+    if opened.store_path() != store_path {
+        return;
+    }
+    if let Some(label) = label_in_renamed_folder(&opened.label(), old_folder, new_folder) {
+        set_opened_pass_file(
+            list,
+            OpenPassFile::from_label_with_mode(store_path, label, opened.username_fallback_mode()),
+        );
+    }
+}
+
+fn label_in_renamed_folder(label: &str, old_folder: &str, new_folder: &str) -> Option<String> {
+    label
+        .strip_prefix(old_folder)
+        .and_then(|rest| rest.strip_prefix('/'))
+        .map(|rest| format!("{new_folder}/{rest}"))
+}
+
+fn open_entry_website(entry: &PassEntry, overlay: &ToastOverlay) {
+    let store_path = entry.store_path.clone();
+    let label = entry.label();
+    let overlay_for_disconnect = overlay.clone();
+    let overlay = overlay.clone();
+    spawn_result_task(
+        move || read_password_entry(&store_path, &label).map(Zeroizing::new),
+        move |result| match result {
+            Ok(contents) => open_pass_file_website(&contents, &overlay),
+            Err(err) => {
+                log_error(format!("Failed to read an item to open its website: {err}"));
+                overlay.add_toast(Toast::new(&gettext("Couldn't open the website.")));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't open the website.")));
+        },
+    );
+}
+
 fn add_menu_action(actions: &SimpleActionGroup, name: &str, activate: impl Fn() + 'static) {
     let action = SimpleAction::new(name, None);
     action.connect_activate(move |_, _| activate());
     actions.add_action(&action);
 }
 
+// A middle click on the row copies like the copy button, so the password
+// can be pasted without opening the item.
 fn connect_copy_action(state: &PasswordRowState, button: &Button, overlay: &ToastOverlay) {
-    let overlay = overlay.clone();
-    let state = state.clone();
-    let copied_button = button.clone();
-    button.connect_clicked(move |_| {
-        copy_password_entry_to_clipboard(
-            state.item.borrow().clone(),
-            overlay.clone(),
-            Some(copied_button.clone()),
-        );
+    let copy = {
+        let overlay = overlay.clone();
+        let state = state.clone();
+        let copied_button = button.clone();
+        Rc::new(move || {
+            copy_password_entry_to_clipboard(
+                state.item.borrow().clone(),
+                overlay.clone(),
+                Some(copied_button.clone()),
+            );
+        })
+    };
+
+    let copy_for_button = copy.clone();
+    button.connect_clicked(move |_| copy_for_button());
+
+    let middle_click = GestureClick::new();
+    middle_click.set_button(gdk::BUTTON_MIDDLE);
+    let readable = state.readable;
+    let stack = state.stack.clone();
+    middle_click.connect_released(move |gesture, _, _, _| {
+        if readable && stack.visible_child_name().as_deref() == Some("display") {
+            gesture.set_state(EventSequenceState::Claimed);
+            copy();
+        }
     });
+    state.action_row.add_controller(middle_click);
 }
 
 fn enter_text_edit_mode(state: &PasswordRowState, mode: TextEditMode, value: &str) {
@@ -389,6 +729,7 @@ fn enter_text_edit_mode(state: &PasswordRowState, mode: TextEditMode, value: &st
     let title = match mode {
         TextEditMode::RenameFile => gettext("Rename pass file"),
         TextEditMode::MoveWithinStore => gettext("Move pass file"),
+        TextEditMode::CreateAlias => gettext("Alias name"),
     };
     state.text_edit_row.set_title(&title);
     state.text_edit_row.set_text(value);
@@ -425,7 +766,12 @@ fn connect_text_edit_actions(
     }
     text_edit_row.connect_apply(move |row| {
         let entry = state.item.borrow().clone();
-        let new_label = match *state.text_edit_mode.borrow() {
+        let mode = *state.text_edit_mode.borrow();
+        if mode == TextEditMode::CreateAlias {
+            create_entry_alias(&state, &entry, row.text().as_str(), &list, &overlay);
+            return;
+        }
+        let new_label = match mode {
             TextEditMode::RenameFile => match renamed_file_label(&entry, row.text().as_str()) {
                 Ok(new_label) => new_label,
                 Err(message) => {
@@ -434,6 +780,7 @@ fn connect_text_edit_actions(
                 }
             },
             TextEditMode::MoveWithinStore => moved_file_label(&entry, row.text().as_str()),
+            TextEditMode::CreateAlias => None,
         };
 
         let Some(new_label) = new_label else {
@@ -441,30 +788,110 @@ fn connect_text_edit_actions(
             return;
         };
 
-        let old_label = entry.label();
-        match rename_password_entry(&entry.store_path, &old_label, &new_label) {
+        let state = state.clone();
+        let list = list.clone();
+        let overlay = overlay.clone();
+        confirm_protected_entry_change(row, &entry.label(), ProtectedChange::Move, move || {
+            rename_current_entry(&state, &list, &overlay, &entry, &new_label)
+        });
+    });
+}
+
+fn rename_current_entry(
+    state: &PasswordRowState,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+    entry: &PassEntry,
+    new_label: &str,
+) {
+    let old_label = entry.label();
+    match rename_password_entry(&entry.store_path, &old_label, new_label) {
+        Ok(()) => {
+            *state.item.borrow_mut() = PassEntry::from_label(entry.store_path.clone(), new_label);
+            push_row_undo_action(
+                &state.row,
+                state.readable,
+                rename_entry_action(entry, new_label),
+            );
+            sync_password_row_display(state);
+            show_password_row_display(state);
+            request_password_list_reload(list);
+        }
+        Err(err) => {
+            log_error(format!("Failed to move or rename password entry: {err}"));
+            overlay.add_toast(Toast::new(&gettext(err.rename_toast_message())));
+        }
+    }
+}
+
+fn alias_label(entry: &PassEntry, name: &str) -> Result<Option<String>, &'static str> {
+    let label = normalize_password_entry_label(name);
+    if label.is_empty() {
+        return Ok(None);
+    }
+    if label == entry.label() {
+        return Err("An alias needs a name of its own.");
+    }
+    Ok(Some(label))
+}
+
+fn create_entry_alias(
+    state: &PasswordRowState,
+    entry: &PassEntry,
+    name: &str,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+) {
+    let alias = match alias_label(entry, name) {
+        Ok(Some(alias)) => alias,
+        Ok(None) => {
+            show_password_row_display(state);
+            return;
+        }
+        Err(message) => {
+            overlay.add_toast(Toast::new(&gettext(message)));
+            return;
+        }
+    };
+
+    let store = entry.store_path.clone();
+    let contents = alias_pass_file_contents(&entry.label());
+    let alias_for_task = alias.clone();
+    let state = state.clone();
+    let list = list.clone();
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    spawn_result_task(
+        move || save_password_entry(&store, &alias_for_task, &contents, false),
+        move |result| match result {
             Ok(()) => {
-                *state.item.borrow_mut() =
-                    PassEntry::from_label(entry.store_path.clone(), &new_label);
-                push_row_undo_action(
-                    &state.row,
-                    state.readable,
-                    rename_entry_action(&entry, &new_label),
-                );
-                sync_password_row_display(&state);
+                record_activity(format!(
+                    "Created alias {alias} for {}",
+                    state.item.borrow().label()
+                ));
                 show_password_row_display(&state);
                 request_password_list_reload(&list);
+                overlay.add_toast(Toast::new(&gettext("Created alias.")));
             }
             Err(err) => {
-                log_error(format!("Failed to move or rename password entry: {err}"));
-                overlay.add_toast(Toast::new(&gettext(err.rename_toast_message())));
+                log_error(format!("Failed to create alias '{alias}': {err}"));
+                overlay.add_toast(Toast::new(&gettext(err.save_toast_message())));
             }
-        }
-    });
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't create the alias.")));
+        },
+    );
 }
 
 fn enter_store_edit_mode(state: &PasswordRowState, overlay: &ToastOverlay) {
-    let stores = Preferences::new().store_roots();
+    let settings = Preferences::new();
+    let current_store = state.item.borrow().store_path.clone();
+    let stores = settings
+        .store_roots()
+        .into_iter()
+        .filter(|store| *store == current_store || !settings.store_is_read_only(store))
+        .collect::<Vec<_>>();
     if stores.len() < 2 {
         overlay.add_toast(Toast::new(&gettext("Add another store first.")));
         return;
@@ -478,7 +905,7 @@ fn enter_store_edit_mode(state: &PasswordRowState, overlay: &ToastOverlay) {
     state.store_dropdown.set_selected(
         stores
             .iter()
-            .position(|store| store == &state.item.borrow().store_path)
+            .position(|store| *store == current_store)
             .and_then(|index| u32::try_from(index).ok())
             .unwrap_or(INVALID_LIST_POSITION),
     );
@@ -521,40 +948,69 @@ fn connect_store_move_actions(
             return;
         }
 
-        let overlay_for_disconnect = overlay.clone();
-        let state_for_result = state.clone();
-        let overlay_for_result = overlay.clone();
-        let list_for_result = list.clone();
-        let entry_for_task = entry.clone();
-        let target_store_for_task = target_store.clone();
-        spawn_result_task(
-            move || move_entry_to_store(&entry_for_task, &target_store_for_task),
-            move |result| match result {
-                Ok(updated_entry) => {
-                    push_undo_action(
-                        &state_for_result.row,
-                        move_entry_between_stores_action(&entry, &target_store),
-                    );
-                    *state_for_result.item.borrow_mut() = updated_entry;
-                    sync_password_row_display(&state_for_result);
-                    show_password_row_display(&state_for_result);
-                    request_password_list_reload(&list_for_result);
-                    overlay_for_result.add_toast(Toast::new(&gettext("Moved.")));
-                }
-                Err(err) => {
-                    log_undo_error("move password entry to another store", &err);
-                    overlay_for_result.add_toast(Toast::new(&gettext(err.toast_message())));
-                }
-            },
-            move || {
-                overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't move the item.")));
-            },
-        );
+        let row = state.row.clone();
+        let state = state.clone();
+        let list = list.clone();
+        let overlay = overlay.clone();
+        confirm_protected_entry_change(&row, &entry.label(), ProtectedChange::Move, move || {
+            move_current_entry_to_store(&state, &list, &overlay, &entry, &target_store)
+        });
     });
 }
 
+fn move_current_entry_to_store(
+    state: &PasswordRowState,
+    list: &ListBox,
+    overlay: &ToastOverlay,
+    entry: &PassEntry,
+    target_store: &str,
+) {
+    let overlay_for_disconnect = overlay.clone();
+    let state_for_result = state.clone();
+    let overlay_for_result = overlay.clone();
+    let list_for_result = list.clone();
+    let entry_for_task = entry.clone();
+    let entry = entry.clone();
+    let target_store_for_task = target_store.to_string();
+    let target_store = target_store.to_string();
+    spawn_result_task(
+        move || move_entry_to_store(&entry_for_task, &target_store_for_task),
+        move |result| match result {
+            Ok(updated_entry) => {
+                push_undo_action(
+                    &state_for_result.row,
+                    move_entry_between_stores_action(&entry, &target_store),
+                );
+                *state_for_result.item.borrow_mut() = updated_entry;
+                sync_password_row_display(&state_for_result);
+                show_password_row_display(&state_for_result);
+                request_password_list_reload(&list_for_result);
+                overlay_for_result.add_toast(Toast::new(&gettext("Moved.")));
+            }
+            Err(err) => {
+                log_undo_error("move password entry to another store", &err);
+                overlay_for_result.add_toast(Toast::new(&gettext(err.toast_message())));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't move the item.")));
+        },
+    );
+}
+
 fn delete_current_entry(state: &PasswordRowState, list: &ListBox, overlay: &ToastOverlay) {
+    let label = state.item.borrow().label();
+    let state_for_delete = state.clone();
+    let list = list.clone();
+    let overlay = overlay.clone();
+    confirm_protected_entry_change(&state.row, &label, ProtectedChange::Delete, move || {
+        delete_entry(&state_for_delete, &list, &overlay);
+    });
+}
+
+fn delete_entry(state: &PasswordRowState, list: &ListBox, overlay: &ToastOverlay) {
     let entry = state.item.borrow().clone();
+    let label = entry.label();
     let row = state.row.clone();
     let list = list.clone();
     let overlay = overlay.clone();
@@ -563,6 +1019,7 @@ fn delete_current_entry(state: &PasswordRowState, list: &ListBox, overlay: &Toas
         move || delete_entry_with_optional_undo(&entry),
         move |result| match result {
             Ok(undo_action) => {
+                record_activity(format!("Deleted {label}"));
                 if let Some(undo_action) = undo_action {
                     if let Some(message) = unavailable_undo_message(&undo_action) {
                         overlay.add_toast(Toast::new(&gettext(message)));
@@ -588,6 +1045,15 @@ pub(super) fn activate_selected_password_row_action(
     overlay: &ToastOverlay,
     action: SelectedPasswordRowAction,
 ) -> bool {
+    if let Some(folder_state) = focused_password_row(list).and_then(|row| {
+        cloned_data::<_, PasswordFolderRowState>(&row, PASSWORD_FOLDER_ROW_STATE_KEY)
+    }) {
+        if action != SelectedPasswordRowAction::RenameFile || !folder_state.writable {
+            return false;
+        }
+        present_rename_folder_dialog(&folder_state, list, overlay);
+        return true;
+    }
     let Some(state) = focused_password_row_state(list) else {
         return false;
     };
@@ -600,7 +1066,18 @@ pub(super) fn activate_selected_password_row_action(
             copy_password_entry_to_clipboard(state.item.borrow().clone(), overlay.clone(), None);
             true
         }
-        SelectedPasswordRowAction::Copy => false,
+        SelectedPasswordRowAction::CopyUsername if state.readable => {
+            copy_password_entry_username_to_clipboard(state.item.borrow().clone(), overlay.clone());
+            true
+        }
+        SelectedPasswordRowAction::Copy | SelectedPasswordRowAction::CopyUsername => false,
+        SelectedPasswordRowAction::RenameFile
+        | SelectedPasswordRowAction::MoveWithinStore
+        | SelectedPasswordRowAction::Delete
+            if !state.writable =>
+        {
+            false
+        }
         SelectedPasswordRowAction::RenameFile => {
             let entry = state.item.borrow().clone();
             enter_text_edit_mode(&state, TextEditMode::RenameFile, &entry.basename);
@@ -671,12 +1148,60 @@ fn focused_password_row(list: &ListBox) -> Option<ListBoxRow> {
 
 fn sync_password_row_display(state: &PasswordRowState) {
     let item = state.item.borrow();
-    let store_label = shortened_store_label_for_path(&item.store_path, &state.store_labels);
-    state.action_row.set_title(&item.basename);
-    state
-        .action_row
-        .set_subtitle(&password_row_subtitle(&item.relative_path, &store_label));
-    state.action_row.set_tooltip_text(None);
+    let store_label = state.store_labels.label(&item.store_path);
+    let color = state.store_labels.color(&item.store_path);
+    if state.layout.shared_label {
+        state
+            .store_chip
+            .set_markup(&shared_store_chip_markup(color, &store_label));
+        state.store_chip.set_tooltip_text(Some(&gettext(
+            "Another store has an item with the same name.",
+        )));
+        state.store_chip.set_visible(true);
+    } else if let Some(color) = color {
+        state.store_chip.set_markup(&store_chip_markup(color));
+        state.store_chip.set_tooltip_text(Some(&store_label));
+        state.store_chip.set_visible(true);
+    } else {
+        state.store_chip.set_visible(false);
+    }
+    let folder = if state.layout.grouped {
+        folder_below_top_folder(&item.relative_path)
+    } else {
+        &item.relative_path
+    };
+    if state.layout.compact {
+        state
+            .action_row
+            .set_title(&compact_password_row_title(folder, &item.basename));
+        state.action_row.set_subtitle("");
+        state
+            .action_row
+            .set_tooltip_text(Some(&password_row_path(&item.relative_path, &store_label)));
+    } else if state.layout.grouped {
+        state
+            .action_row
+            .set_title(&glib::markup_escape_text(&item.basename));
+        state
+            .action_row
+            .set_subtitle(&glib::markup_escape_text(folder));
+        state.action_row.set_tooltip_text(None);
+    } else {
+        state
+            .action_row
+            .set_title(&glib::markup_escape_text(&item.basename));
+        state
+            .action_row
+            .set_subtitle(&password_row_subtitle(&item.relative_path, &store_label));
+        state.action_row.set_tooltip_text(None);
+    }
+    if state.layout.grouped {
+        set_string_data(
+            &state.row,
+            PASSWORD_LIST_SECTION_KEY,
+            password_row_section_title(&store_label, item.top_folder()),
+        );
+    }
 
     set_string_data(&state.row, "root", item.store_path.clone());
     set_string_data(&state.row, "label", item.label());
@@ -707,11 +1232,41 @@ fn sync_password_folder_row_display(state: &PasswordFolderRowState) {
     set_cloned_data(&state.row, PASSWORD_LIST_ROW_EXPANDED_KEY, expanded);
 }
 
+pub(super) fn sync_password_folder_row_stats(row: &ListBoxRow, entries: usize, flagged: usize) {
+    let Some(state) = cloned_data::<_, PasswordFolderRowState>(row, PASSWORD_FOLDER_ROW_STATE_KEY)
+    else {
+        return;
+    };
+    state.count_label.set_label(&entries.to_string());
+    state.count_label.set_tooltip_text(Some(&counted_text(
+        "{count} item",
+        "{count} items",
+        entries,
+    )));
+
+    let badge = &state.attention_badge;
+    if flagged == 0 {
+        badge.set_visible(false);
+        return;
+    }
+    badge.set_label(&flagged.to_string());
+    badge.set_tooltip_text(Some(&counted_text(
+        "{count} item is expired or weak",
+        "{count} items are expired or weak",
+        flagged,
+    )));
+    badge.set_visible(true);
+}
+
+fn counted_text(singular: &str, plural: &str, count: usize) -> String {
+    ngettext(singular, plural, count).replace("{count}", &count.to_string())
+}
+
 fn password_list_indent(depth: usize) -> i32 {
     (depth.min(PASSWORD_LIST_MAX_INDENT_DEPTH) as i32) * PASSWORD_LIST_INDENT_WIDTH
 }
 
-fn password_row_subtitle(relative_path: &str, store_label: &str) -> String {
+fn password_row_path(relative_path: &str, store_label: &str) -> String {
     if relative_path.is_empty() {
         store_label.to_string()
     } else {
@@ -719,6 +1274,147 @@ fn password_row_subtitle(relative_path: &str, store_label: &str) -> String {
     }
 }
 
+// Row titles and subtitles are markup, and store names and folders can hold
+// `&` or `<`.
+fn password_row_subtitle(relative_path: &str, store_label: &str) -> String {
+    glib::markup_escape_text(&password_row_path(relative_path, store_label)).to_string()
+}
+
+// Grouped rows sit below a header for their store and first folder, so they
+// only show the folders under it.
+fn folder_below_top_folder(relative_path: &str) -> &str {
+    relative_path.split_once('/').map_or("", |(_, below)| below)
+}
+
+fn password_row_section_title(store_label: &str, top_folder: &str) -> String {
+    if top_folder.is_empty() {
+        store_label.to_string()
+    } else {
+        format!("{store_label}/{top_folder}")
+    }
+}
+
+// Compact rows fit on one line, so the folder moves in front of the name and
+// the store only shows on hover.
+fn compact_password_row_title(relative_path: &str, basename: &str) -> String {
+    let basename = glib::markup_escape_text(basename);
+    if relative_path.is_empty() {
+        basename.to_string()
+    } else {
+        format!(
+            "<span alpha=\"55%\">{}</span>{basename}",
+            glib::markup_escape_text(relative_path)
+        )
+    }
+}
+
+fn shared_store_chip_markup(color: Option<&str>, store_label: &str) -> String {
+    let name = format!("<small>{}</small>", glib::markup_escape_text(store_label));
+    match color {
+        Some(color) => format!("{} {name}", store_chip_markup(color)),
+        None => name,
+    }
+}
+
+fn build_store_chip() -> Label {
+    let chip = Label::new(None);
+    chip.set_valign(adw::gtk::Align::Center);
+    chip.set_visible(false);
+    chip
+}
+
+fn build_age_button() -> Button {
+    let label = Label::new(None);
+    label.add_css_class("caption");
+    label.add_css_class("dim-label");
+    let button = Button::builder()
+        .child(&label)
+        .valign(adw::gtk::Align::Center)
+        .tooltip_text(gettext("Show history"))
+        .visible(false)
+        .build();
+    button.add_css_class("flat");
+    button
+}
+
+fn connect_age_action(state: &PasswordRowState, button: &Button, overlay: &ToastOverlay) {
+    let state = state.clone();
+    let overlay = overlay.clone();
+    button.connect_clicked(move |button| {
+        present_entry_history_dialog(button, &state.item.borrow(), &overlay);
+    });
+}
+
+// Shows when the item was last committed. Rows without Git history keep the
+// space free.
+pub(super) fn sync_password_row_age(row: &ListBoxRow, committed_at: Option<i64>) {
+    let Some(state) = cloned_data::<_, PasswordRowState>(row, PASSWORD_ROW_STATE_KEY) else {
+        return;
+    };
+    let button = &state.age_button;
+    let Some(committed_at) = committed_at else {
+        button.set_visible(false);
+        return;
+    };
+    if let Some(label) = button.child().and_downcast::<Label>() {
+        label.set_label(&password_row_age_label(committed_at));
+    }
+    button.set_visible(true);
+}
+
+fn build_expiry_badge() -> Label {
+    let badge = Label::new(None);
+    badge.set_valign(adw::gtk::Align::Center);
+    badge.add_css_class("caption");
+    badge.set_visible(false);
+    badge
+}
+
+// While searching in contents, the subtitle shows the matching lines in
+// place of the path.
+pub(super) fn sync_password_row_content_matches(row: &ListBoxRow) {
+    let Some(state) = cloned_data::<_, PasswordRowState>(row, PASSWORD_ROW_STATE_KEY) else {
+        return;
+    };
+    match cloned_data::<_, Vec<String>>(row, SEARCH_CONTENT_MATCHES_KEY) {
+        Some(snippets) if !snippets.is_empty() => {
+            state.action_row.set_subtitle_lines(snippets.len() as i32);
+            state.action_row.set_subtitle(&snippets.join("\n"));
+        }
+        _ => {
+            state.action_row.set_subtitle_lines(1);
+            sync_password_row_display(&state);
+        }
+    }
+}
+
+// The badge only knows about rows the search index has read, so it shows up
+// after an index-backed search or an expiry reminder check.
+pub(super) fn sync_password_row_expiry_badge(row: &ListBoxRow) {
+    let Some(state) = cloned_data::<_, PasswordRowState>(row, PASSWORD_ROW_STATE_KEY) else {
+        return;
+    };
+    let status = match cloned_data(row, SEARCH_FIELDS_KEY) {
+        Some(SearchRowFieldIndexState::Indexed(fields)) => indexed_expiry_status(&fields),
+        _ => None,
+    };
+
+    let badge = &state.expiry_badge;
+    badge.remove_css_class("error");
+    badge.remove_css_class("warning");
+    let Some(label) = status.and_then(ExpiryStatus::label) else {
+        badge.set_visible(false);
+        return;
+    };
+    badge.add_css_class(if status == Some(ExpiryStatus::Expired) {
+        "error"
+    } else {
+        "warning"
+    });
+    badge.set_label(&label);
+    badge.set_visible(true);
+}
+
 fn build_unreadable_password_icon(visible: bool) -> Image {
     let icon = dim_label_icon("dialog-warning-symbolic");
     icon.set_tooltip_text(Some(&gettext(UNREADABLE_PASSWORD_ROW_TOOLTIP)));
@@ -834,8 +1530,10 @@ fn push_row_undo_action(
 #[cfg(test)]
 mod tests {
     use super::{
-        entry_parent_directory, moved_file_label, password_row_menu_entries, password_row_subtitle,
-        renamed_file_label, text_edit_apply_button_visible, TextEditMode, OPEN_IN_NEW_WINDOW_LABEL,
+        alias_label, compact_password_row_title, entry_parent_directory, folder_below_top_folder,
+        label_in_renamed_folder, moved_file_label, password_row_menu_entries,
+        password_row_section_title, password_row_subtitle, renamed_file_label,
+        text_edit_apply_button_visible, TextEditMode, OPEN_IN_NEW_WINDOW_LABEL,
     };
     use crate::backend::{PasswordEntryError, PasswordEntryWriteError};
     use crate::password::model::PassEntry;
@@ -851,6 +1549,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn renamed_folders_carry_only_their_own_items() {
+        assert_eq!(
+            label_in_renamed_folder("team/ops/db", "team", "work/team"),
+            Some("work/team/ops/db".to_string())
+        );
+        assert_eq!(label_in_renamed_folder("teams/db", "team", "work"), None);
+        assert_eq!(label_in_renamed_folder("team", "team", "work"), None);
+    }
+
     #[test]
     fn rename_pass_file_rejects_nested_names() {
         let entry = PassEntry::from_label("/tmp/store", "work/alice/github");
@@ -870,6 +1578,21 @@ mod tests {
         assert_eq!(moved_file_label(&entry, ""), Some("github".to_string()));
     }
 
+    #[test]
+    fn aliases_need_a_name_other_than_their_target() {
+        let entry = PassEntry::from_label("/tmp/store", "work/mail");
+
+        assert_eq!(
+            alias_label(&entry, " personal//mail "),
+            Ok(Some("personal/mail".to_string()))
+        );
+        assert_eq!(alias_label(&entry, "  "), Ok(None));
+        assert_eq!(
+            alias_label(&entry, "work/mail"),
+            Err("An alias needs a name of its own.")
+        );
+    }
+
     #[test]
     fn rename_text_edit_apply_hides_for_empty_values() {
         assert!(!text_edit_apply_button_visible(
@@ -900,6 +1623,28 @@ mod tests {
             password_row_subtitle("", ".../work/.password-store"),
             ".../work/.password-store".to_string()
         );
+        assert_eq!(
+            password_row_subtitle("mail/", "R&D <team>"),
+            "R&amp;D &lt;team&gt;/mail/".to_string()
+        );
+    }
+
+    #[test]
+    fn grouped_rows_only_show_folders_below_their_section() {
+        assert_eq!(folder_below_top_folder("mail/alice/"), "alice/");
+        assert_eq!(folder_below_top_folder("mail/"), "");
+        assert_eq!(folder_below_top_folder(""), "");
+        assert_eq!(password_row_section_title("work", "mail"), "work/mail");
+        assert_eq!(password_row_section_title("work", ""), "work");
+    }
+
+    #[test]
+    fn compact_titles_dim_the_folder_in_front_of_the_name() {
+        assert_eq!(
+            compact_password_row_title("work/<team>/", "mail"),
+            "<span alpha=\"55%\">work/&lt;team&gt;/</span>mail".to_string()
+        );
+        assert_eq!(compact_password_row_title("", "a&b"), "a&amp;b".to_string());
     }
 
     #[test]
@@ -928,15 +1673,29 @@ mod tests {
 
     #[test]
     fn readable_rows_offer_open_in_new_window() {
-        assert!(password_row_menu_entries(true)
+        assert!(password_row_menu_entries(true, true)
             .iter()
             .any(|(label, _)| *label == OPEN_IN_NEW_WINDOW_LABEL));
     }
 
     #[test]
     fn unreadable_rows_hide_open_in_new_window() {
-        assert!(!password_row_menu_entries(false)
+        assert!(!password_row_menu_entries(false, true)
             .iter()
             .any(|(label, _)| *label == OPEN_IN_NEW_WINDOW_LABEL));
     }
+
+    #[test]
+    fn read_only_rows_hide_file_changes() {
+        assert_eq!(
+            password_row_menu_entries(true, false),
+            vec![
+                ("Open", "entry.open"),
+                (OPEN_IN_NEW_WINDOW_LABEL, "entry.open-new-window"),
+                ("Open website", "entry.open-website"),
+                ("Copy line…", "entry.copy-line"),
+                ("Open in File Manager", "entry.open-in-file-manager"),
+            ]
+        );
+    }
 }
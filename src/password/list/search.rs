@@ -1,29 +1,48 @@
+mod contents;
 mod index;
 mod query;
 #[cfg(test)]
 mod tests;
 
+use self::contents::{build_content_search_batch, content_search_pattern, ContentSearchBatch};
 use self::index::{
-    build_search_index_batch, collect_unindexed_requests, find_row, is_stale_index_batch,
-    list_is_empty, row_field_index_state, SearchIndexBatch,
+    build_search_index_batch, collect_content_search_requests, collect_unindexed_requests,
+    find_row, is_stale_index_batch, list_is_empty, row_field_index_state, SearchIndexBatch,
 };
+pub(super) use self::query::{indexed_expiry_status, indexed_needs_attention};
 use self::query::{parse_search_query, row_matches_query, SearchQuery};
+use super::folder_stats::sync_password_list_folder_stats;
 use super::placeholder::{show_loading_placeholder, show_resolved_placeholder};
+use super::row::{sync_password_row_content_matches, sync_password_row_expiry_badge};
 use super::{
     password_list_folder_row_is_expanded, password_list_row_action_kind, password_list_row_depth,
     password_list_row_is_folder, password_list_row_store_path, PasswordListActionRowKind,
 };
+use crate::backend::forget_content_search_entries;
+use crate::password::expiry::send_expiry_reminder;
 use crate::password::file::SearchablePassField;
+use crate::preferences::Preferences;
 use crate::store::support::StoreSupportCache;
 use crate::support::background::spawn_result_task;
-use crate::support::object_data::{cloned_data, non_null_to_string_option, set_cloned_data};
-use adw::gtk::{ListBox, ListBoxRow};
+use crate::support::object_data::{
+    cloned_data, non_null_to_string_option, set_cloned_data, take_data,
+};
+use adw::glib;
+use adw::gtk::{ListBox, ListBoxRow, SearchEntry, ToggleButton};
+use adw::prelude::*;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 const SEARCH_CONTROLLER_KEY: &str = "search-controller";
 pub(super) const SEARCH_FIELDS_KEY: &str = "search-fields";
+pub(super) const SEARCH_CONTENT_MATCHES_KEY: &str = "search-content-matches";
 const SEARCH_VISIBILITY_KEY: &str = "search-visibility";
+// Searching in contents waits until typing pauses, so a word typed letter
+// by letter starts one search instead of one per letter.
+const CONTENT_SEARCH_DELAY: Duration = Duration::from_millis(300);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum FilterablePasswordListRow {
@@ -46,8 +65,15 @@ pub(super) struct SearchFilterController {
 
 struct SearchFilterState {
     query: RefCell<SearchQuery>,
+    query_text: RefCell<String>,
     generation: Cell<u64>,
     indexing_generation: Cell<Option<u64>>,
+    search_contents: Cell<bool>,
+    content_generation: Cell<u64>,
+    // Shared with the running search, so it can skip the items it hasn't
+    // read yet once a newer search starts.
+    content_current: Arc<AtomicU64>,
+    content_searching: Cell<bool>,
     has_store_dirs: Cell<bool>,
     loading: Cell<bool>,
 }
@@ -64,8 +90,13 @@ impl SearchFilterController {
         Self {
             state: Rc::new(SearchFilterState {
                 query: RefCell::new(SearchQuery::Empty),
+                query_text: RefCell::new(String::new()),
                 generation: Cell::new(0),
                 indexing_generation: Cell::new(None),
+                search_contents: Cell::new(false),
+                content_generation: Cell::new(0),
+                content_current: Arc::new(AtomicU64::new(0)),
+                content_searching: Cell::new(false),
                 has_store_dirs: Cell::new(false),
                 loading: Cell::new(false),
             }),
@@ -78,12 +109,38 @@ impl SearchFilterController {
 
     pub(super) fn update_query(&self, query: &str) {
         *self.state.query.borrow_mut() = parse_search_query(query);
+        *self.state.query_text.borrow_mut() = query.to_string();
+    }
+
+    pub(super) fn set_search_contents(&self, active: bool) {
+        self.state.search_contents.set(active);
+    }
+
+    // Reruns the search after the query or the contents toggle changed.
+    pub(super) fn refresh(&self, list: &ListBox) {
+        self.start_content_search(list);
+        self.refresh_row_visibility(list);
+        self.start_indexing_if_needed(list);
+        list.invalidate_filter();
+        self.update_placeholder(list);
+    }
+
+    fn searches_contents(&self) -> bool {
+        self.state.search_contents.get()
+            && !self.state.query_text.borrow().trim().is_empty()
+            && Preferences::new().search_item_contents()
     }
 
     pub(super) fn refresh_row_visibility(&self, list: &ListBox) {
         let query = self.state.query.borrow().clone();
         let query_is_empty = query.is_empty();
-        let rows = collect_filterable_rows(list, &query);
+        let rows = if self.searches_contents() {
+            collect_filterable_rows(list, false, row_has_content_matches)
+        } else {
+            collect_filterable_rows(list, query.uses_advanced_features(), |row| {
+                password_entry_matches_query(row, &query)
+            })
+        };
         let visibility = password_list_row_visibility(&rows, query_is_empty);
         let has_visible_results = visibility.iter().any(|(_, visible)| *visible);
 
@@ -117,10 +174,12 @@ impl SearchFilterController {
             .set(self.state.generation.get().wrapping_add(1).max(1));
         self.state.indexing_generation.set(None);
         self.state.loading.set(true);
+        self.next_content_generation();
     }
 
     pub(super) fn finish_reload(&self, list: &ListBox) {
         self.state.loading.set(false);
+        self.start_content_search(list);
         self.refresh_row_visibility(list);
         self.start_indexing_if_needed(list);
         list.invalidate_filter();
@@ -134,7 +193,7 @@ impl SearchFilterController {
     }
 
     pub(super) fn start_indexing_if_needed(&self, list: &ListBox) {
-        if !self.state.query.borrow().requires_index() {
+        if !self.state.query.borrow().requires_index() && !Preferences::new().expiry_reminders() {
             return;
         }
 
@@ -162,6 +221,90 @@ impl SearchFilterController {
         );
     }
 
+    fn next_content_generation(&self) -> u64 {
+        let generation = self.state.content_generation.get().wrapping_add(1).max(1);
+        self.state.content_generation.set(generation);
+        self.state
+            .content_current
+            .store(generation, Ordering::Relaxed);
+        self.state.content_searching.set(false);
+        generation
+    }
+
+    // Only the redacted snippets are kept on the rows. Decrypted items are
+    // reused from the backend's search cache while no store write happens.
+    fn start_content_search(&self, list: &ListBox) {
+        let generation = self.next_content_generation();
+        for_each_row(list, |row| {
+            if take_data::<_, Vec<String>>(&row, SEARCH_CONTENT_MATCHES_KEY).is_some() {
+                sync_password_row_content_matches(&row);
+            }
+        });
+        if !self.searches_contents() {
+            return;
+        }
+        if content_search_pattern(&self.state.query_text.borrow()).is_none() {
+            return;
+        }
+
+        self.state.content_searching.set(true);
+        let controller = self.clone();
+        let list = list.clone();
+        glib::timeout_add_local_once(CONTENT_SEARCH_DELAY, move || {
+            controller.run_content_search(&list, generation);
+        });
+    }
+
+    fn run_content_search(&self, list: &ListBox, generation: u64) {
+        if self.state.content_generation.get() != generation {
+            return;
+        }
+        let pattern = content_search_pattern(&self.state.query_text.borrow());
+        let requests = collect_content_search_requests(list);
+        let Some(pattern) = pattern.filter(|_| !requests.is_empty()) else {
+            self.state.content_searching.set(false);
+            self.update_placeholder(list);
+            return;
+        };
+
+        let current = self.state.content_current.clone();
+        let controller_for_result = self.clone();
+        let list_for_result = list.clone();
+        let controller_for_disconnect = self.clone();
+        let list_for_disconnect = list.clone();
+        spawn_result_task(
+            move || build_content_search_batch(generation, current, pattern, requests),
+            move |batch| controller_for_result.apply_content_search_batch(&list_for_result, batch),
+            move || {
+                if controller_for_disconnect.state.content_generation.get() == generation {
+                    controller_for_disconnect.state.content_searching.set(false);
+                    controller_for_disconnect.update_placeholder(&list_for_disconnect);
+                }
+            },
+        );
+    }
+
+    fn apply_content_search_batch(&self, list: &ListBox, batch: ContentSearchBatch) {
+        if self.state.content_generation.get() != batch.generation {
+            return;
+        }
+
+        self.state.content_searching.set(false);
+        for result in batch.results {
+            if result.snippets.is_empty() {
+                continue;
+            }
+            if let Some(row) = find_row(list, &result.root, &result.label) {
+                set_cloned_data(&row, SEARCH_CONTENT_MATCHES_KEY, result.snippets);
+                sync_password_row_content_matches(&row);
+            }
+        }
+
+        self.refresh_row_visibility(list);
+        list.invalidate_filter();
+        self.update_placeholder(list);
+    }
+
     pub(super) fn update_placeholder(&self, list: &ListBox) {
         if self.should_show_loading_placeholder(list) {
             show_loading_placeholder(list);
@@ -180,11 +323,22 @@ impl SearchFilterController {
             self.state.indexing_generation.set(None);
         }
 
+        let mut needs_rotation = 0;
         for result in batch.results {
             if let Some(row) = find_row(list, &result.root, &result.label) {
+                if let SearchRowFieldIndexState::Indexed(fields) = &result.state {
+                    needs_rotation += usize::from(
+                        indexed_expiry_status(fields).is_some_and(|status| status.needs_rotation()),
+                    );
+                }
                 set_cloned_data(&row, SEARCH_FIELDS_KEY, result.state);
+                sync_password_row_expiry_badge(&row);
             }
         }
+        sync_password_list_folder_stats(list);
+        if Preferences::new().expiry_reminders() {
+            send_expiry_reminder(needs_rotation);
+        }
 
         self.refresh_row_visibility(list);
         list.invalidate_filter();
@@ -206,6 +360,7 @@ impl SearchFilterController {
 
     fn should_show_loading_placeholder(&self, list: &ListBox) -> bool {
         self.state.loading.get()
+            || self.state.content_searching.get()
             || (self.state.query.borrow().requires_index()
                 && self.state.indexing_generation.get() == Some(self.state.generation.get())
                 && !list_is_empty(list))
@@ -218,11 +373,11 @@ pub(super) fn search_controller_for_list(list: &ListBox) -> Option<SearchFilterC
 
 fn collect_filterable_rows(
     list: &ListBox,
-    query: &SearchQuery,
+    uses_advanced_features: bool,
+    matches_query: impl Fn(&ListBoxRow) -> bool,
 ) -> Vec<(ListBoxRow, FilterablePasswordListRow)> {
     let mut rows = Vec::new();
     let mut store_support = StoreSupportCache::default();
-    let uses_advanced_features = query.uses_advanced_features();
     for_each_row(list, |row| {
         if password_list_row_action_kind(&row).is_some() {
             return;
@@ -251,7 +406,7 @@ fn collect_filterable_rows(
             FilterablePasswordListRow::Entry {
                 matches_query: store_support
                     .supports_advanced_search(&store_path, uses_advanced_features)
-                    && password_entry_matches_query(&row, query),
+                    && matches_query(&row),
                 depth,
                 store_path,
             },
@@ -260,6 +415,44 @@ fn collect_filterable_rows(
     rows
 }
 
+fn row_has_content_matches(row: &ListBoxRow) -> bool {
+    cloned_data::<_, Vec<String>>(row, SEARCH_CONTENT_MATCHES_KEY)
+        .is_some_and(|snippets| !snippets.is_empty())
+}
+
+pub(super) fn connect_search_contents_toggle(
+    list: &ListBox,
+    search_entry: &SearchEntry,
+    button: &ToggleButton,
+) {
+    // Searching contents decrypts every item, so the button only shows up
+    // once it is turned on in Preferences.
+    let sync_visibility = {
+        let button = button.clone();
+        move |search_entry: &SearchEntry| {
+            button.set_visible(
+                search_entry.is_visible() && Preferences::new().search_item_contents(),
+            );
+            if !search_entry.is_visible() {
+                forget_content_search_entries();
+            }
+        }
+    };
+    sync_visibility(search_entry);
+    search_entry.connect_visible_notify(sync_visibility);
+    let list = list.clone();
+    button.connect_toggled(move |button| {
+        let Some(controller) = search_controller_for_list(&list) else {
+            return;
+        };
+        controller.set_search_contents(button.is_active());
+        if !button.is_active() {
+            forget_content_search_entries();
+        }
+        controller.refresh(&list);
+    });
+}
+
 fn password_entry_matches_query(row: &ListBoxRow, query: &SearchQuery) -> bool {
     let label = non_null_to_string_option(row, "label").unwrap_or_default();
     let store_label = non_null_to_string_option(row, "store-label").unwrap_or_default();
@@ -0,0 +1,182 @@
+use super::index::SearchIndexRequest;
+use crate::backend::{read_password_entries_in_parallel, read_password_entry_for_search};
+use crate::password::file::parse_structured_pass_lines;
+use adw::glib::markup_escape_text;
+use regex::{Regex, RegexBuilder};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+const MAX_CONTENT_MATCHES_PER_ENTRY: usize = 3;
+const CONTENT_SNIPPET_CONTEXT_CHARS: usize = 24;
+const REDACTED_LINE: &str = "••••••••";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct ContentSearchResult {
+    pub(super) root: String,
+    pub(super) label: String,
+    pub(super) snippets: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct ContentSearchBatch {
+    pub(super) generation: u64,
+    pub(super) results: Vec<ContentSearchResult>,
+}
+
+// Like `pass grep`, the query is a pattern, but matching ignores case so
+// plain words find what people expect.
+pub(super) fn content_search_pattern(query: &str) -> Option<Regex> {
+    if query.trim().is_empty() {
+        return None;
+    }
+
+    RegexBuilder::new(query).case_insensitive(true).build().ok()
+}
+
+// `current` holds the generation of the newest search. Items that weren't
+// read yet when a newer search starts are skipped.
+pub(super) fn build_content_search_batch(
+    generation: u64,
+    current: Arc<AtomicU64>,
+    pattern: Regex,
+    requests: Vec<SearchIndexRequest>,
+) -> ContentSearchBatch {
+    let results = read_password_entries_in_parallel(requests, move |request| {
+        let snippets = if current.load(Ordering::Relaxed) == generation {
+            read_password_entry_for_search(&request.root, &request.label)
+                .map(|contents| content_match_snippets(&contents, &pattern))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        ContentSearchResult {
+            root: request.root,
+            label: request.label,
+            snippets,
+        }
+    })
+    .into_iter()
+    .flatten()
+    .collect();
+
+    ContentSearchBatch {
+        generation,
+        results,
+    }
+}
+
+// One markup line per matching line, with the match in bold. The password,
+// OTP secrets, and sensitive fields can match, but their text is never shown.
+pub(super) fn content_match_snippets(contents: &str, pattern: &Regex) -> Vec<String> {
+    let (_, structured) = parse_structured_pass_lines(contents);
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let found = pattern.find(line)?;
+            let hidden = index == 0
+                || structured
+                    .get(index - 1)
+                    .is_some_and(|(template, _)| template.hides_value());
+            if hidden {
+                return Some(REDACTED_LINE.to_string());
+            }
+            if found.is_empty() {
+                return None;
+            }
+            Some(highlighted_snippet(line, found.start(), found.end()))
+        })
+        .take(MAX_CONTENT_MATCHES_PER_ENTRY)
+        .collect()
+}
+
+fn highlighted_snippet(line: &str, start: usize, end: usize) -> String {
+    let before = &line[..start];
+    let after = &line[end..];
+    let before_chars = before.chars().count();
+    let before = if before_chars > CONTENT_SNIPPET_CONTEXT_CHARS {
+        let skip = before_chars - CONTENT_SNIPPET_CONTEXT_CHARS;
+        format!("…{}", before.chars().skip(skip).collect::<String>())
+    } else {
+        before.to_string()
+    };
+    let after = if after.chars().count() > CONTENT_SNIPPET_CONTEXT_CHARS {
+        format!(
+            "{}…",
+            after
+                .chars()
+                .take(CONTENT_SNIPPET_CONTEXT_CHARS)
+                .collect::<String>()
+        )
+    } else {
+        after.to_string()
+    };
+
+    format!(
+        "{}<b>{}</b>{}",
+        markup_escape_text(before.trim_start()),
+        markup_escape_text(&line[start..end]),
+        markup_escape_text(after.trim_end())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{content_match_snippets, content_search_pattern};
+
+    #[test]
+    fn content_matches_highlight_lines_and_redact_the_password() {
+        let pattern = content_search_pattern("ALICE").expect("valid pattern");
+        let contents = "alice-password\nusername: alice\nnotes: <shared> with bob";
+
+        assert_eq!(
+            content_match_snippets(contents, &pattern),
+            vec!["••••••••", "username: <b>alice</b>"]
+        );
+        assert_eq!(
+            content_match_snippets(
+                contents,
+                &content_search_pattern("shared").expect("valid pattern")
+            ),
+            vec!["notes: &lt;<b>shared</b>&gt; with bob"]
+        );
+    }
+
+    #[test]
+    fn content_matches_redact_secret_fields_and_otp_seeds() {
+        let pattern = content_search_pattern("4242").expect("valid pattern");
+
+        assert_eq!(
+            content_match_snippets(
+                "password\npin: 4242\notpauth://totp/x?secret=4242\nnotes: 4242",
+                &pattern
+            ),
+            vec!["••••••••", "••••••••", "notes: <b>4242</b>"]
+        );
+        assert_eq!(
+            content_match_snippets("password\nsecret: 4242\nnotes: 4242", &pattern),
+            vec!["••••••••", "notes: <b>4242</b>"]
+        );
+    }
+
+    #[test]
+    fn content_search_skips_blank_and_invalid_patterns() {
+        assert!(content_search_pattern("  ").is_none());
+        assert!(content_search_pattern("(").is_none());
+    }
+
+    #[test]
+    fn long_lines_are_trimmed_around_the_match() {
+        let pattern = content_search_pattern("needle").expect("valid pattern");
+        let line = format!("x\n{}needle{}", "a".repeat(40), "b".repeat(40));
+
+        assert_eq!(
+            content_match_snippets(&line, &pattern),
+            vec![format!(
+                "…{}<b>needle</b>{}…",
+                "a".repeat(24),
+                "b".repeat(24)
+            )]
+        );
+    }
+}
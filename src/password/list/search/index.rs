@@ -1,6 +1,7 @@
-use super::query::{OTP_SEARCH_KEY, WEAK_PASSWORD_SEARCH_KEY};
+use super::query::{EXPIRES_SEARCH_KEY, OTP_SEARCH_KEY, WEAK_PASSWORD_SEARCH_KEY};
 use super::{SearchRowFieldIndexState, SEARCH_FIELDS_KEY};
-use crate::backend::read_password_entry;
+use crate::backend::{read_password_entries_in_parallel, read_password_entry};
+use crate::password::expiry::pass_file_expiry_date;
 use crate::password::file::{pass_file_has_otp, searchable_pass_fields, SearchablePassField};
 use crate::password::strength::weak_password_reason;
 use crate::store::support::StoreSupportCache;
@@ -30,19 +31,32 @@ pub(super) fn build_search_index_batch(
     generation: u64,
     requests: Vec<SearchIndexRequest>,
 ) -> SearchIndexBatch {
-    let results = requests
-        .into_iter()
-        .map(|request| SearchIndexResult {
-            root: request.root.clone(),
-            label: request.label.clone(),
-            state: match read_password_entry(&request.root, &request.label) {
-                Ok(contents) => {
-                    SearchRowFieldIndexState::Indexed(indexed_fields_for_contents(&contents))
-                }
-                Err(_) => SearchRowFieldIndexState::Unavailable,
-            },
+    // An item whose read failed outright is marked unavailable, so it isn't
+    // asked for again.
+    let items = requests
+        .iter()
+        .map(|request| (request.root.clone(), request.label.clone()))
+        .collect::<Vec<_>>();
+    let results = read_password_entries_in_parallel(requests, |request| SearchIndexResult {
+        state: match read_password_entry(&request.root, &request.label) {
+            Ok(contents) => {
+                SearchRowFieldIndexState::Indexed(indexed_fields_for_contents(&contents))
+            }
+            Err(_) => SearchRowFieldIndexState::Unavailable,
+        },
+        root: request.root,
+        label: request.label,
+    })
+    .into_iter()
+    .zip(items)
+    .map(|(result, (root, label))| {
+        result.unwrap_or(SearchIndexResult {
+            state: SearchRowFieldIndexState::Unavailable,
+            root,
+            label,
         })
-        .collect();
+    })
+    .collect();
 
     SearchIndexBatch {
         generation,
@@ -51,13 +65,26 @@ pub(super) fn build_search_index_batch(
 }
 
 pub(super) fn collect_unindexed_requests(list: &ListBox) -> Vec<SearchIndexRequest> {
+    collect_entry_read_requests(list, |row| {
+        matches!(
+            row_field_index_state(row),
+            SearchRowFieldIndexState::Unindexed
+        )
+    })
+}
+
+pub(super) fn collect_content_search_requests(list: &ListBox) -> Vec<SearchIndexRequest> {
+    collect_entry_read_requests(list, |_| true)
+}
+
+fn collect_entry_read_requests(
+    list: &ListBox,
+    include: impl Fn(&ListBoxRow) -> bool,
+) -> Vec<SearchIndexRequest> {
     let mut requests = Vec::new();
     let mut store_support = StoreSupportCache::default();
     for_each_row(list, |row| {
-        if !matches!(
-            row_field_index_state(&row),
-            SearchRowFieldIndexState::Unindexed
-        ) {
+        if !include(&row) {
             return;
         }
 
@@ -119,6 +146,13 @@ pub(super) fn indexed_fields_for_contents(contents: &str) -> Vec<SearchablePassF
             normalized_value: reason.to_lowercase(),
         });
     }
+    if let Some((value, expires)) = pass_file_expiry_date(contents) {
+        fields.push(SearchablePassField {
+            key: EXPIRES_SEARCH_KEY.to_string(),
+            value,
+            normalized_value: expires.to_string(),
+        });
+    }
 
     fields
 }
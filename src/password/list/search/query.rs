@@ -1,7 +1,9 @@
 use super::SearchRowFieldIndexState;
+use crate::password::expiry::{expiry_status, today, ExpiryStatus};
 use crate::password::file::{canonical_search_field_key, SearchablePassField};
 use regex::Regex;
 
+pub(super) const EXPIRES_SEARCH_KEY: &str = "__meta_expires";
 pub(super) const OTP_SEARCH_KEY: &str = "__meta_otp";
 pub(super) const STORE_PATH_SEARCH_KEY: &str = "store path";
 pub(super) const STORE_SEARCH_KEY: &str = "store";
@@ -28,6 +30,8 @@ pub(super) enum StructuredSearchQuery {
     Clause(SearchClause),
     Otp,
     WeakPassword,
+    Expired,
+    NeedsRotation,
     Not(Box<StructuredSearchQuery>),
     And(Box<StructuredSearchQuery>, Box<StructuredSearchQuery>),
     Or(Box<StructuredSearchQuery>, Box<StructuredSearchQuery>),
@@ -181,7 +185,7 @@ impl StructuredSearchQuery {
     fn requires_index(&self) -> bool {
         match self {
             Self::Clause(clause) => !clause.can_match_without_index(),
-            Self::Otp | Self::WeakPassword => true,
+            Self::Otp | Self::WeakPassword | Self::Expired | Self::NeedsRotation => true,
             Self::Not(query) => query.requires_index(),
             Self::And(left, right) | Self::Or(left, right) => {
                 left.requires_index() || right.requires_index()
@@ -367,6 +371,10 @@ impl<'a> StructuredSearchParser<'a> {
             Some(StructuredSearchQuery::Otp)
         } else if self.parse_weak_password_predicate() {
             Some(StructuredSearchQuery::WeakPassword)
+        } else if self.consume_keyword("EXPIRED") {
+            Some(StructuredSearchQuery::Expired)
+        } else if self.parse_needs_rotation_predicate() {
+            Some(StructuredSearchQuery::NeedsRotation)
         } else {
             Some(StructuredSearchQuery::Clause(self.parse_clause()?))
         }
@@ -386,6 +394,20 @@ impl<'a> StructuredSearchParser<'a> {
         true
     }
 
+    fn parse_needs_rotation_predicate(&mut self) -> bool {
+        let start = self.pos;
+        if !self.consume_keyword("NEEDS") {
+            return false;
+        }
+
+        self.skip_whitespace();
+        if self.consume_keyword("ROTATION") {
+            return true;
+        }
+        self.pos = start;
+        false
+    }
+
     fn parse_clause(&mut self) -> Option<SearchClause> {
         let (raw_field, field_was_quoted) = self.parse_field()?;
         self.skip_whitespace();
@@ -735,6 +757,12 @@ fn structured_query_matches(
         }
         StructuredSearchQuery::Otp => indexed_fields.is_some_and(has_otp),
         StructuredSearchQuery::WeakPassword => indexed_fields.is_some_and(has_weak_password),
+        StructuredSearchQuery::Expired => indexed_fields
+            .and_then(indexed_expiry_status)
+            .is_some_and(|status| status == ExpiryStatus::Expired),
+        StructuredSearchQuery::NeedsRotation => indexed_fields
+            .and_then(indexed_expiry_status)
+            .is_some_and(ExpiryStatus::needs_rotation),
         StructuredSearchQuery::Not(query) => {
             !structured_query_matches(metadata_fields, indexed_fields, query)
         }
@@ -755,6 +783,22 @@ fn has_weak_password(fields: &[SearchablePassField]) -> bool {
         .any(|field| field.key == WEAK_PASSWORD_SEARCH_KEY)
 }
 
+pub(in crate::password::list) fn indexed_needs_attention(fields: &[SearchablePassField]) -> bool {
+    has_weak_password(fields) || indexed_expiry_status(fields) == Some(ExpiryStatus::Expired)
+}
+
+pub(in crate::password::list) fn indexed_expiry_status(
+    fields: &[SearchablePassField],
+) -> Option<ExpiryStatus> {
+    let expires = fields
+        .iter()
+        .find(|field| field.key == EXPIRES_SEARCH_KEY)?
+        .normalized_value
+        .parse::<i64>()
+        .ok()?;
+    Some(expiry_status(expires, today()))
+}
+
 fn has_otp(fields: &[SearchablePassField]) -> bool {
     fields.iter().any(|field| field.key == OTP_SEARCH_KEY)
 }
@@ -1,11 +1,14 @@
-use super::index::is_stale_index_batch;
+use super::index::{build_search_index_batch, is_stale_index_batch, SearchIndexRequest};
 use super::query::{
     parse_search_query, row_matches_query, SearchClause, SearchComparison, SearchQuery,
-    StructuredSearchQuery, OTP_SEARCH_KEY, STORE_PATH_SEARCH_KEY, STORE_SEARCH_KEY,
-    WEAK_PASSWORD_SEARCH_KEY,
+    StructuredSearchQuery, EXPIRES_SEARCH_KEY, OTP_SEARCH_KEY, STORE_PATH_SEARCH_KEY,
+    STORE_SEARCH_KEY, WEAK_PASSWORD_SEARCH_KEY,
 };
 use super::{advanced_search_includes_store, SearchRowFieldIndexState};
+use crate::backend::save_password_entry;
+use crate::backend::test_support::SystemBackendTestEnv;
 use crate::i18n::gettext;
+use crate::password::expiry::today;
 use crate::password::file::SearchablePassField;
 
 fn clause(field: &str, comparison: SearchComparison, value: &str) -> StructuredSearchQuery {
@@ -763,6 +766,46 @@ fn weak_password_queries_match_only_rows_with_the_weak_password_flag() {
     ));
 }
 
+#[test]
+fn expiry_keywords_parse_as_structured_predicates() {
+    assert_eq!(
+        parse_search_query("find expired"),
+        SearchQuery::Structured(StructuredSearchQuery::Expired)
+    );
+    assert_eq!(
+        parse_search_query("find needs rotation or weak"),
+        SearchQuery::Structured(or(StructuredSearchQuery::NeedsRotation, weak_password()))
+    );
+    assert_eq!(
+        parse_search_query("find needs contains soon"),
+        SearchQuery::Structured(clause("needs", SearchComparison::Contains, "soon"))
+    );
+}
+
+#[test]
+fn expiry_queries_compare_the_indexed_date_with_today() {
+    let expiry = |offset: i64| {
+        SearchRowFieldIndexState::Indexed(vec![SearchablePassField {
+            key: EXPIRES_SEARCH_KEY.to_string(),
+            value: String::new(),
+            normalized_value: (today() + offset).to_string(),
+        }])
+    };
+    let expired = SearchQuery::Structured(StructuredSearchQuery::Expired);
+    let needs_rotation = SearchQuery::Structured(StructuredSearchQuery::NeedsRotation);
+
+    assert!(matches_query("alice", &expiry(-1), &expired));
+    assert!(!matches_query("alice", &expiry(0), &expired));
+    assert!(matches_query("alice", &expiry(-1), &needs_rotation));
+    assert!(matches_query("alice", &expiry(10), &needs_rotation));
+    assert!(!matches_query("alice", &expiry(90), &needs_rotation));
+    assert!(!matches_query(
+        "alice",
+        &indexed_fields(&[("username", "alice")]),
+        &needs_rotation,
+    ));
+}
+
 #[test]
 fn otp_queries_match_only_rows_with_the_otp_flag() {
     assert!(matches_query(
@@ -997,3 +1040,44 @@ fn generation_checks_mark_mismatched_batches_as_stale() {
     assert!(is_stale_index_batch(2, 1));
     assert!(!is_stale_index_batch(2, 2));
 }
+
+#[test]
+fn search_index_reads_fields_through_the_store_backend() {
+    let env = SystemBackendTestEnv::new();
+    let backend = env.use_memory_store_backend();
+    let store_root = env.store_root().to_string_lossy().to_string();
+    save_password_entry(&store_root, "work/mail", "hunter2\nusername: alice", false)
+        .expect("save mail entry");
+    save_password_entry(
+        &store_root,
+        "home/bank",
+        "correct horse\nusername: bob",
+        false,
+    )
+    .expect("save bank entry");
+    assert_eq!(backend.labels(&store_root), vec!["home/bank", "work/mail"]);
+
+    let requests = ["work/mail", "home/bank", "home/missing"]
+        .into_iter()
+        .map(|label| SearchIndexRequest {
+            root: store_root.clone(),
+            label: label.to_string(),
+        })
+        .collect();
+    let batch = build_search_index_batch(1, requests);
+
+    let query = parse_search_query("find user alice");
+    let matches = batch
+        .results
+        .iter()
+        .filter(|result| {
+            row_matches_query(&result.label, "store", &result.root, &result.state, &query)
+        })
+        .map(|result| result.label.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(matches, vec!["work/mail"]);
+    assert_eq!(
+        batch.results[2].state,
+        SearchRowFieldIndexState::Unavailable
+    );
+}
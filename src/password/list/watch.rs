@@ -0,0 +1,201 @@
+use super::{PasswordListSource, PASSWORD_LIST_SOURCE_KEY};
+use crate::backend::{publish_store_event, subscribe_store_events, StoreEvent};
+use crate::logging::log_error;
+use crate::password::model::PassEntry;
+use crate::support::object_data::{cloned_data, set_cloned_data, take_data};
+use adw::gio::{self, prelude::*};
+use adw::gtk::ListBox;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+const PASSWORD_LIST_WATCH_KEY: &str = "password-list-watch";
+const PASSWORD_LIST_FOLLOWS_EVENTS_KEY: &str = "password-list-follows-store-events";
+
+// Each watched folder takes an inotify watch, so a store with more folders
+// than this only gets its root and Git history watched.
+const MAX_WATCHED_FOLDERS: usize = 512;
+
+#[derive(Clone)]
+struct PasswordListWatch(Vec<gio::FileMonitor>);
+
+impl PasswordListWatch {
+    fn cancel(&self) {
+        for monitor in &self.0 {
+            monitor.cancel();
+        }
+    }
+}
+
+// The store roots and every folder that holds an item, each with the store
+// it belongs to. A new folder shows up as a change in its parent, so folders
+// without items aren't needed.
+pub(super) fn watched_password_list_folders<'a>(
+    store_roots: &[String],
+    items: impl IntoIterator<Item = &'a PassEntry>,
+) -> Vec<(String, PathBuf)> {
+    let mut folders = store_roots
+        .iter()
+        .map(|root| (root.clone(), PathBuf::from(root)))
+        .collect::<BTreeSet<_>>();
+    for item in items {
+        let mut folder = Path::new(&item.store_path).to_path_buf();
+        for part in item
+            .relative_path
+            .split('/')
+            .filter(|part| !part.is_empty())
+        {
+            folder.push(part);
+            folders.insert((item.store_path.clone(), folder.clone()));
+        }
+    }
+    if folders.len() > MAX_WATCHED_FOLDERS {
+        return store_roots
+            .iter()
+            .map(|root| (root.clone(), PathBuf::from(root)))
+            .collect();
+    }
+    folders.into_iter().collect()
+}
+
+// Git appends to `logs/HEAD` on every commit, pull, or checkout, whether it
+// came from Keycord, `pass`, or a terminal. `git status` doesn't touch it.
+fn git_history_log(store_root: &Path) -> PathBuf {
+    store_root.join(".git").join("logs").join("HEAD")
+}
+
+fn mark_password_list_changed(list: &ListBox) {
+    let _ = take_data::<_, PasswordListSource>(list, PASSWORD_LIST_SOURCE_KEY);
+    stop_watching_password_list(list);
+}
+
+// Changes from outside are announced like Keycord's own writes, so the list
+// and the entry cache follow them the same way.
+fn connect_change_monitor(store_root: &str, monitor: &gio::FileMonitor) {
+    let store_root = store_root.to_string();
+    monitor.connect_changed(move |_, _, _, event| {
+        if matches!(
+            event,
+            gio::FileMonitorEvent::Changed
+                | gio::FileMonitorEvent::Created
+                | gio::FileMonitorEvent::Deleted
+                | gio::FileMonitorEvent::Renamed
+                | gio::FileMonitorEvent::MovedIn
+                | gio::FileMonitorEvent::MovedOut
+        ) {
+            publish_store_event(StoreEvent::ChangedOutside {
+                store: store_root.clone(),
+            });
+        }
+    });
+}
+
+// Decrypting an item changes nothing on disk, so only the other events make
+// the list read its stores again.
+fn follow_store_events(list: &ListBox) {
+    if cloned_data::<_, bool>(list, PASSWORD_LIST_FOLLOWS_EVENTS_KEY).is_some() {
+        return;
+    }
+    set_cloned_data(list, PASSWORD_LIST_FOLLOWS_EVENTS_KEY, true);
+    let list = list.downgrade();
+    subscribe_store_events(move |event| {
+        if matches!(event, StoreEvent::Decrypted { .. }) {
+            return;
+        }
+        if let Some(list) = list.upgrade() {
+            mark_password_list_changed(&list);
+        }
+    });
+}
+
+fn watch_path(path: &Path, folder: bool) -> Option<gio::FileMonitor> {
+    let file = gio::File::for_path(path);
+    let monitor = if folder {
+        file.monitor_directory(gio::FileMonitorFlags::WATCH_MOVES, gio::Cancellable::NONE)
+    } else {
+        file.monitor_file(gio::FileMonitorFlags::NONE, gio::Cancellable::NONE)
+    };
+    monitor
+        .inspect_err(|err| {
+            log_error(format!(
+                "Failed to watch '{}' for store changes: {err}",
+                path.display()
+            ));
+        })
+        .ok()
+}
+
+// Going back to the list reuses its rows until a store event says something
+// changed. Keycord announces its own writes and syncs, and these monitors
+// announce the rest.
+pub(super) fn watch_password_list_folders(
+    list: &ListBox,
+    store_roots: &[String],
+    folders: &[(String, PathBuf)],
+) {
+    stop_watching_password_list(list);
+    follow_store_events(list);
+    let monitors =
+        folders
+            .iter()
+            .filter_map(|(root, folder)| Some((root, watch_path(folder, true)?)))
+            .chain(store_roots.iter().filter_map(|root| {
+                Some((root, watch_path(&git_history_log(Path::new(root)), false)?))
+            }))
+            .map(|(root, monitor)| {
+                connect_change_monitor(root, &monitor);
+                monitor
+            })
+            .collect::<Vec<_>>();
+    set_cloned_data(list, PASSWORD_LIST_WATCH_KEY, PasswordListWatch(monitors));
+}
+
+pub(super) fn stop_watching_password_list(list: &ListBox) {
+    if let Some(watch) = take_data::<_, PasswordListWatch>(list, PASSWORD_LIST_WATCH_KEY) {
+        watch.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{watched_password_list_folders, MAX_WATCHED_FOLDERS};
+    use crate::password::model::PassEntry;
+    use std::path::PathBuf;
+
+    fn entry(store: &str, relative_path: &str) -> PassEntry {
+        PassEntry {
+            basename: "item".to_string(),
+            relative_path: relative_path.to_string(),
+            store_path: store.to_string(),
+        }
+    }
+
+    #[test]
+    fn list_watches_store_roots_and_item_folders() {
+        let items = [
+            entry("/store", "team/mail/"),
+            entry("/store", "team/"),
+            entry("/store", ""),
+        ];
+
+        assert_eq!(
+            watched_password_list_folders(&["/store".to_string()], &items),
+            vec![
+                ("/store".to_string(), PathBuf::from("/store")),
+                ("/store".to_string(), PathBuf::from("/store/team")),
+                ("/store".to_string(), PathBuf::from("/store/team/mail")),
+            ]
+        );
+    }
+
+    #[test]
+    fn large_stores_only_watch_their_roots() {
+        let items = (0..=MAX_WATCHED_FOLDERS)
+            .map(|index| entry("/store", &format!("folder-{index}/")))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            watched_password_list_folders(&["/store".to_string()], &items),
+            vec![("/store".to_string(), PathBuf::from("/store"))]
+        );
+    }
+}
@@ -1,11 +1,18 @@
+pub mod alias;
+pub mod attachments;
+pub mod draft;
 pub mod entry_files;
+pub mod expiry;
 pub mod file;
 pub mod generation;
+pub mod index;
 pub mod list;
 pub mod model;
+pub mod new_folder;
 pub mod new_item;
 pub mod opened;
 pub mod otp;
 pub mod page;
+pub mod protected;
 pub mod strength;
 pub mod undo;
@@ -1,11 +1,12 @@
 use crate::password::entry_files::{
-    label_from_password_entry_path, normalize_password_entry_label,
+    canonical_store_root, collect_empty_password_folders as collect_empty_folder_paths,
+    collect_password_entry_paths, label_from_password_entry_path, normalize_password_entry_label,
+    PasswordEntryWalkOptions,
 };
 use crate::preferences::{PasswordListSortMode, Preferences, UsernameFallbackMode};
 use crate::store::recipients::store_is_supported_in_current_build;
 
-use std::collections::BTreeMap;
-use std::fs;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
 use std::path::{Path, PathBuf};
 
@@ -46,6 +47,11 @@ impl PassEntry {
         }
     }
 
+    // The first folder below the store, or "" for items at the top.
+    pub fn top_folder(&self) -> &str {
+        self.relative_path.split('/').next().unwrap_or_default()
+    }
+
     fn folder_username_from_path(&self) -> Option<String> {
         self.relative_path
             .trim_end_matches('/')
@@ -278,6 +284,82 @@ pub fn collect_all_password_items_with_options(options: CollectItemsOptions) ->
     result
 }
 
+// Labels held by more than one store, such as `email/gmail` in both a
+// personal and a work store. Rows for them name their store.
+pub fn labels_in_several_stores(items: &[PassEntry]) -> HashSet<String> {
+    let mut first_store = HashMap::<String, &str>::new();
+    let mut shared = HashSet::new();
+    for item in items {
+        let label = item.label();
+        match first_store.get(&label) {
+            Some(store) if *store != item.store_path => {
+                shared.insert(label);
+            }
+            Some(_) => {}
+            None => {
+                first_store.insert(label, &item.store_path);
+            }
+        }
+    }
+    shared
+}
+
+// A folder in a store that holds no items yet. `relative_path` ends in a
+// slash, like the folder part of a `PassEntry`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PassFolder {
+    pub store_path: String,
+    pub relative_path: String,
+}
+
+pub fn collect_empty_password_folders(options: CollectItemsOptions) -> Vec<PassFolder> {
+    let roots = Preferences::new().paths();
+    let mut folders = Vec::new();
+    for base in &roots {
+        if !store_is_supported_in_current_build(&base.to_string_lossy()) {
+            continue;
+        }
+        let paths = collect_empty_folder_paths(
+            base,
+            PasswordEntryWalkOptions {
+                include_hidden: options.show_hidden,
+            },
+        )
+        .unwrap_or_default();
+        folders.extend(
+            paths
+                .iter()
+                .filter(|path| {
+                    options.show_duplicates || !belongs_to_nested_store(base, path, &roots)
+                })
+                .filter_map(|path| empty_folder_relative_path(base, path))
+                .map(|relative_path| PassFolder {
+                    store_path: base.to_string_lossy().to_string(),
+                    relative_path,
+                }),
+        );
+    }
+    folders
+}
+
+// Folders inside a store that is itself configured are listed with that
+// store only, the same way its items are.
+fn belongs_to_nested_store(base: &Path, path: &Path, roots: &[PathBuf]) -> bool {
+    roots
+        .iter()
+        .any(|root| root != base && root.starts_with(base) && path.starts_with(root))
+}
+
+fn empty_folder_relative_path(base: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(base).ok()?;
+    let mut folder = String::new();
+    for segment in relative.iter() {
+        folder.push_str(segment.to_str()?);
+        folder.push('/');
+    }
+    (!folder.is_empty()).then_some(folder)
+}
+
 fn sort_password_items(items: &mut [PassEntry], mode: PasswordListSortMode) {
     items.sort_by(|left, right| match mode {
         PasswordListSortMode::StorePath => left
@@ -290,14 +372,24 @@ fn sort_password_items(items: &mut [PassEntry], mode: PasswordListSortMode) {
             .cmp(&right.basename)
             .then_with(|| left.store_path.cmp(&right.store_path))
             .then_with(|| left.relative_path.cmp(&right.relative_path)),
+        PasswordListSortMode::TopFolder => left
+            .store_path
+            .cmp(&right.store_path)
+            .then_with(|| left.top_folder().cmp(right.top_folder()))
+            .then_with(|| left.basename.cmp(&right.basename))
+            .then_with(|| left.relative_path.cmp(&right.relative_path)),
     });
 }
 
 fn collapse_duplicate_store_entries(items: Vec<PassEntry>) -> Vec<PassEntry> {
     let mut longest_path_items = BTreeMap::<PathBuf, PassEntry>::new();
+    let mut canonical_roots = BTreeMap::<String, PathBuf>::new();
 
     for item in items {
-        let secret_path = absolute_secret_path(&item);
+        let canonical_root = canonical_roots
+            .entry(item.store_path.clone())
+            .or_insert_with(|| canonical_store_root(Path::new(&item.store_path)));
+        let secret_path = absolute_secret_path(canonical_root, &item);
         match longest_path_items.entry(secret_path) {
             std::collections::btree_map::Entry::Vacant(entry) => {
                 entry.insert(item);
@@ -321,10 +413,8 @@ fn filter_duplicate_store_entries(items: Vec<PassEntry>, show_duplicates: bool)
     }
 }
 
-fn absolute_secret_path(item: &PassEntry) -> PathBuf {
-    Path::new(&item.store_path)
-        .join(&item.relative_path)
-        .join(&item.basename)
+fn absolute_secret_path(store_root: &Path, item: &PassEntry) -> PathBuf {
+    store_root.join(&item.relative_path).join(&item.basename)
 }
 
 fn should_prefer_duplicate_entry(current: &PassEntry, candidate: &PassEntry) -> bool {
@@ -334,12 +424,6 @@ fn should_prefer_duplicate_entry(current: &PassEntry, candidate: &PassEntry) ->
         || (candidate_len == current_len && candidate.store_path.cmp(&current.store_path).is_gt())
 }
 
-fn is_hidden_name(path: &Path) -> bool {
-    path.file_name()
-        .and_then(|name| name.to_str())
-        .is_some_and(|name| name.starts_with('.'))
-}
-
 fn secret_label_from_path(base: &Path, path: &Path) -> Option<String> {
     label_from_password_entry_path(base, path)
 }
@@ -350,56 +434,24 @@ fn collect_items_in_dir(
     out: &mut Vec<PassEntry>,
     options: CollectItemsOptions,
 ) -> io::Result<()> {
-    if !root.exists() {
-        return Ok(());
-    }
-
-    let mut pending_dirs = vec![(root.to_path_buf(), true)];
-
-    while let Some((dir, is_root)) = pending_dirs.pop() {
-        let entries = match fs::read_dir(&dir) {
-            Ok(entries) => entries,
-            Err(err) if is_root => return Err(err),
-            Err(_) => continue,
+    let paths = collect_password_entry_paths(
+        root,
+        PasswordEntryWalkOptions {
+            include_hidden: options.show_hidden,
+        },
+    )?;
+    for path in paths {
+        let Some(label) = secret_label_from_path(base, &path) else {
+            continue;
         };
-        let mut child_dirs = Vec::new();
-
-        for entry_result in entries {
-            let Ok(entry) = entry_result else { continue };
-
-            let path = entry.path();
-            let Ok(file_type) = entry.file_type() else {
-                continue;
-            };
-
-            if file_type.is_dir() {
-                if !options.show_hidden && is_hidden_name(&path) {
-                    continue;
-                }
-                child_dirs.push(path);
-                continue;
-            }
-
-            if !file_type.is_file() || (!options.show_hidden && is_hidden_name(&path)) {
-                continue;
-            }
-
-            let Some(label) = secret_label_from_path(base, &path) else {
-                continue;
-            };
-            if label.is_empty() {
-                continue;
-            }
-
-            out.push(PassEntry::from_label(
-                base.to_string_lossy().to_string(),
-                label,
-            ));
+        if label.is_empty() {
+            continue;
         }
 
-        for child_dir in child_dirs.into_iter().rev() {
-            pending_dirs.push((child_dir, false));
-        }
+        out.push(PassEntry::from_label(
+            base.to_string_lossy().to_string(),
+            label,
+        ));
     }
 
     Ok(())
@@ -408,12 +460,15 @@ fn collect_items_in_dir(
 #[cfg(test)]
 mod tests {
     use super::{
-        collapse_duplicate_store_entries, collect_items_in_dir, filter_duplicate_store_entries,
+        belongs_to_nested_store, collapse_duplicate_store_entries, collect_items_in_dir,
+        empty_folder_relative_path, filter_duplicate_store_entries, labels_in_several_stores,
         sort_password_items, CollectItemsOptions, OpenPassFile, PassEntry, UsernameFallbackError,
     };
+    use crate::logging::log_info;
     use crate::preferences::{PasswordListSortMode, UsernameFallbackMode};
     use std::fs;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
     fn item_order(items: &[PassEntry]) -> Vec<(String, String)> {
         items
@@ -666,6 +721,31 @@ mod tests {
         fs::remove_dir_all(store).expect("remove test store");
     }
 
+    #[test]
+    fn empty_folders_keep_their_path_inside_the_store() {
+        let base = Path::new("/tmp/store");
+        assert_eq!(
+            empty_folder_relative_path(base, Path::new("/tmp/store/team/new")).as_deref(),
+            Some("team/new/")
+        );
+        assert_eq!(empty_folder_relative_path(base, base), None);
+
+        let roots = vec![
+            PathBuf::from("/tmp/store"),
+            PathBuf::from("/tmp/store/work"),
+        ];
+        assert!(belongs_to_nested_store(
+            base,
+            Path::new("/tmp/store/work/new"),
+            &roots
+        ));
+        assert!(!belongs_to_nested_store(
+            base,
+            Path::new("/tmp/store/team/new"),
+            &roots
+        ));
+    }
+
     #[test]
     fn duplicate_entries_keep_the_deepest_store_root() {
         let items = vec![
@@ -681,6 +761,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn labels_in_several_stores_are_found_across_stores_only() {
+        let items = vec![
+            PassEntry::from_label("/stores/home", "email/gmail"),
+            PassEntry::from_label("/stores/work", "email/gmail"),
+            PassEntry::from_label("/stores/home", "bank"),
+            PassEntry::from_label("/stores/work", "email/outlook"),
+            PassEntry::from_label("/stores/work", "email/outlook"),
+        ];
+
+        let shared = labels_in_several_stores(&items);
+
+        assert_eq!(shared.len(), 1);
+        assert!(shared.contains("email/gmail"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stores_reached_through_a_symlink_are_not_listed_twice() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("passwordstore-linked-{nanos}"));
+        let store = base.join("synced");
+        let link = base.join("password-store");
+        fs::create_dir_all(store.join("team")).expect("create store dir");
+        fs::write(store.join("team").join("github.gpg"), b"x").expect("write secret");
+        std::os::unix::fs::symlink(&store, &link).expect("link store");
+
+        let mut items = Vec::new();
+        for root in [&store, &link] {
+            collect_items_in_dir(root, root, &mut items, CollectItemsOptions::default())
+                .expect("collect secrets");
+        }
+        let collapsed = collapse_duplicate_store_entries(items);
+
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed[0].label(), "team/github");
+
+        fs::remove_dir_all(base).expect("remove test store");
+    }
+
     #[test]
     fn duplicate_entries_can_be_left_visible() {
         let items = vec![
@@ -736,4 +859,69 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn top_folder_sort_groups_by_first_folder_then_file_name() {
+        let mut items = vec![
+            PassEntry::from_label("/tmp/personal", "mail/zulu/alice"),
+            PassEntry::from_label("/tmp/personal", "mail/bob"),
+            PassEntry::from_label("/tmp/personal", "github"),
+            PassEntry::from_label("/tmp/personal", "bank/card"),
+        ];
+
+        sort_password_items(&mut items, PasswordListSortMode::TopFolder);
+
+        assert_eq!(
+            item_order(&items),
+            vec![
+                ("/tmp/personal".to_string(), "github".to_string()),
+                ("/tmp/personal".to_string(), "bank/card".to_string()),
+                ("/tmp/personal".to_string(), "mail/zulu/alice".to_string()),
+                ("/tmp/personal".to_string(), "mail/bob".to_string()),
+            ]
+        );
+    }
+
+    // Spreads items over 100 folders, about the shape of a large real store.
+    fn synthetic_store(name: &str, items: usize) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("passwordstore-{name}-{nanos}"));
+        for index in 0..items {
+            let folder = store.join(format!("folder-{}", index % 100));
+            fs::create_dir_all(&folder).expect("create store folder");
+            fs::write(folder.join(format!("item-{index}.gpg")), b"x").expect("write secret");
+        }
+        store
+    }
+
+    // Timing checks for large stores. They are slow and depend on the
+    // machine, so they only run with `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn benchmark_listing_large_stores() {
+        for (items, budget) in [
+            (1_000, Duration::from_millis(250)),
+            (10_000, Duration::from_secs(2)),
+        ] {
+            let store = synthetic_store("benchmark-list", items);
+
+            let started = Instant::now();
+            let mut listed = Vec::new();
+            collect_items_in_dir(&store, &store, &mut listed, CollectItemsOptions::default())
+                .expect("list synthetic store");
+            sort_password_items(&mut listed, PasswordListSortMode::StorePath);
+            let elapsed = started.elapsed();
+
+            log_info(format!(
+                "listing {items} items took {} ms",
+                elapsed.as_millis()
+            ));
+            assert_eq!(listed.len(), items);
+            assert!(elapsed < budget, "listing {items} items took {elapsed:?}");
+            fs::remove_dir_all(store).expect("remove test store");
+        }
+    }
 }
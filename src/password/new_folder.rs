@@ -0,0 +1,152 @@
+use super::entry_files::normalize_password_entry_label;
+use super::new_item::{available_store_roots, DISALLOWED_PATH_CHARACTERS};
+use crate::backend::create_password_folder;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::store::labels::shortened_store_labels;
+use crate::support::actions::{activate_widget_action, register_window_action};
+use crate::support::background::spawn_result_task;
+use adw::gtk::StringList;
+use adw::prelude::*;
+use adw::{
+    AlertDialog, ApplicationWindow, ComboRow, EntryRow, PreferencesGroup, Toast, ToastOverlay,
+};
+
+const NEW_FOLDER_BODY: &str = "Empty folders stay on this device until an item is saved in them.";
+
+pub(crate) fn new_folder_problem(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim().trim_end_matches(['/', '\\']);
+    if trimmed.is_empty() {
+        return Some("Enter a folder name.");
+    }
+    if trimmed
+        .chars()
+        .any(|ch| ch.is_control() || DISALLOWED_PATH_CHARACTERS.contains(&ch))
+    {
+        return Some("Names can't contain < > : \" | ? or *.");
+    }
+    if normalize_password_entry_label(trimmed)
+        .split('/')
+        .any(|part| part.starts_with('.'))
+    {
+        return Some("Names and folders can't start with a dot.");
+    }
+    None
+}
+
+fn create_folder(
+    window: &ApplicationWindow,
+    overlay: &ToastOverlay,
+    store: String,
+    folder: String,
+) {
+    let window = window.clone();
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    let folder_for_task = folder.clone();
+    spawn_result_task(
+        move || create_password_folder(&store, &folder_for_task),
+        move |result| match result {
+            Ok(()) => {
+                overlay.add_toast(Toast::new(&gettext("Folder created.")));
+                activate_widget_action(&window, "win.reload-password-list");
+            }
+            Err(err) => {
+                log_error(format!("Failed to create folder '{folder}': {err}"));
+                overlay.add_toast(Toast::new(&gettext(err.create_folder_toast_message())));
+            }
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't create the folder.")));
+        },
+    );
+}
+
+// Git doesn't track empty folders, so a new folder stays on this device until
+// an item is saved in it.
+fn present_new_folder_dialog(window: &ApplicationWindow, overlay: &ToastOverlay) {
+    let stores = available_store_roots();
+    if stores.is_empty() {
+        overlay.add_toast(Toast::new(&gettext("Add a writable store first.")));
+        return;
+    }
+
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Create folder"))
+        .body(gettext(NEW_FOLDER_BODY))
+        .build();
+    let labels = shortened_store_labels(&stores);
+    let label_refs = labels.iter().map(String::as_str).collect::<Vec<_>>();
+    let store_row = ComboRow::new();
+    store_row.set_title(&gettext("Store"));
+    store_row.set_model(Some(&StringList::new(&label_refs)));
+    store_row.set_visible(stores.len() > 1);
+    let folder_row = EntryRow::new();
+    folder_row.set_title(&gettext("Folder"));
+    let group = PreferencesGroup::new();
+    group.add(&store_row);
+    group.add(&folder_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let create = gettext("Create");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("create", create.as_str())]);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("create"));
+    dialog.set_response_enabled("create", false);
+    {
+        let dialog = dialog.clone();
+        folder_row.connect_changed(move |row| {
+            let problem = new_folder_problem(&row.text());
+            dialog.set_response_enabled("create", problem.is_none());
+            dialog.set_body(&gettext(problem.unwrap_or(NEW_FOLDER_BODY)));
+        });
+    }
+
+    let window_for_response = window.clone();
+    let overlay = overlay.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response != "create" {
+            return;
+        }
+        let Some(store) = stores
+            .get(store_row.selected() as usize)
+            .or_else(|| stores.first())
+            .cloned()
+        else {
+            return;
+        };
+        let folder = normalize_password_entry_label(folder_row.text().trim());
+        create_folder(&window_for_response, &overlay, store, folder);
+    });
+    dialog.present(Some(window));
+}
+
+pub fn register_open_new_folder_action(window: &ApplicationWindow, overlay: &ToastOverlay) {
+    let window_for_dialog = window.clone();
+    let overlay = overlay.clone();
+    register_window_action(window, "open-new-folder", move || {
+        present_new_folder_dialog(&window_for_dialog, &overlay);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::new_folder_problem;
+
+    #[test]
+    fn new_folder_names_allow_nesting_but_not_dots_or_blanks() {
+        assert_eq!(new_folder_problem("team/mail"), None);
+        assert_eq!(new_folder_problem("team/mail/"), None);
+        assert_eq!(new_folder_problem("  "), Some("Enter a folder name."));
+        assert_eq!(
+            new_folder_problem("team/.hidden"),
+            Some("Names and folders can't start with a dot.")
+        );
+        assert_eq!(new_folder_problem("team//mail"), None);
+        assert_eq!(
+            new_folder_problem("team?"),
+            Some("Names can't contain < > : \" | ? or *.")
+        );
+    }
+}
@@ -1,39 +1,64 @@
 use crate::i18n::gettext;
+use crate::password::entry_files::{
+    collect_password_entry_paths, label_from_password_entry_path, normalize_password_entry_label,
+    PasswordEntryWalkOptions,
+};
+use crate::password::file::uri_to_open;
 use crate::preferences::Preferences;
-use crate::store::labels::shortened_store_labels;
+use crate::store::labels::StoreLabels;
 use crate::support::actions::register_window_action;
-use crate::support::ui::{connect_entry_row_apply_button_to_nonempty_text, dialog_content_shell};
+use crate::support::background::spawn_result_task;
+use crate::support::ui::dialog_content_shell;
 use adw::gtk::{Align, Box as GtkBox, Label, StringList, INVALID_LIST_POSITION};
 use adw::prelude::*;
-use adw::{ApplicationWindow, ComboRow, Dialog, EntryRow, PreferencesGroup, PreferencesPage};
-use std::cell::RefCell;
+use adw::{
+    ActionRow, ApplicationWindow, ComboRow, Dialog, EntryRow, HeaderBar, PreferencesGroup,
+    PreferencesPage, WindowTitle,
+};
+use std::cell::{Cell, RefCell};
+use std::path::Path;
 use std::rc::Rc;
+use url::Url;
+
+const MAX_FOLDER_SUGGESTIONS: usize = 5;
+pub(super) const DISALLOWED_PATH_CHARACTERS: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
 
 #[derive(Clone)]
 pub struct NewPasswordDialogState {
     pub dialog: Dialog,
+    pub title: WindowTitle,
+    pub secure_note: Rc<Cell<bool>>,
     pub path_entry: EntryRow,
     pub store_dropdown: ComboRow,
     pub error_label: Label,
+    pub warning_label: Label,
+    pub suggestions_group: PreferencesGroup,
+    pub suggestion_rows: Rc<RefCell<Vec<ActionRow>>>,
     pub store_roots: Rc<RefCell<Vec<String>>>,
+    pub existing_labels: Rc<RefCell<Vec<String>>>,
+    pub other_store_labels: Rc<RefCell<Vec<(String, String)>>>,
 }
 
-pub(crate) fn build_new_password_dialog() -> (Dialog, ComboRow, EntryRow, Label) {
+pub(crate) fn build_new_password_dialog() -> NewPasswordDialogState {
     let store_dropdown = ComboRow::new();
     store_dropdown.set_title(&gettext("Store"));
     store_dropdown.set_visible(false);
 
     let path_entry = EntryRow::new();
     path_entry.set_title(&gettext("Path or name"));
-    path_entry.set_show_apply_button(true);
-    connect_entry_row_apply_button_to_nonempty_text(&path_entry);
+    path_entry.set_show_apply_button(false);
 
     let group = PreferencesGroup::new();
     group.add(&store_dropdown);
     group.add(&path_entry);
 
+    let suggestions_group = PreferencesGroup::new();
+    suggestions_group.set_title(&gettext("Suggestions"));
+    suggestions_group.set_visible(false);
+
     let page = PreferencesPage::new();
     page.add(&group);
+    page.add(&suggestions_group);
 
     let error_label = Label::new(None);
     error_label.set_halign(Align::Start);
@@ -46,32 +71,300 @@ pub(crate) fn build_new_password_dialog() -> (Dialog, ComboRow, EntryRow, Label)
     error_label.set_margin_bottom(18);
     error_label.set_visible(false);
 
+    let warning_label = Label::new(None);
+    warning_label.set_halign(Align::Start);
+    warning_label.set_wrap(true);
+    warning_label.add_css_class("warning");
+    warning_label.add_css_class("caption");
+    warning_label.set_margin_start(18);
+    warning_label.set_margin_end(18);
+    warning_label.set_margin_bottom(18);
+    warning_label.set_visible(false);
+
     let content = GtkBox::new(adw::gtk::Orientation::Vertical, 0);
     content.append(&page);
     content.append(&error_label);
+    content.append(&warning_label);
 
     let title = gettext("New item");
     let subtitle = gettext("Create a new pass file.");
+    let shell = dialog_content_shell(&title, Some(&subtitle), &content);
+    let title_widget = shell
+        .first_child()
+        .and_downcast::<HeaderBar>()
+        .and_then(|header| header.title_widget())
+        .and_downcast::<WindowTitle>()
+        .unwrap_or_default();
     let dialog = Dialog::builder()
         .title(&title)
         .content_height(280)
         .content_width(800)
         .follows_content_size(true)
-        .child(&dialog_content_shell(&title, Some(&subtitle), &content))
+        .child(&shell)
         .build();
 
+    let state = NewPasswordDialogState {
+        dialog,
+        title: title_widget,
+        secure_note: Rc::new(Cell::new(false)),
+        path_entry,
+        store_dropdown,
+        error_label,
+        warning_label,
+        suggestions_group,
+        suggestion_rows: Rc::new(RefCell::new(Vec::new())),
+        store_roots: Rc::new(RefCell::new(Vec::new())),
+        existing_labels: Rc::new(RefCell::new(Vec::new())),
+        other_store_labels: Rc::new(RefCell::new(Vec::new())),
+    };
+
     {
-        let error_label = error_label.clone();
-        path_entry.connect_changed(move |_| {
-            error_label.set_visible(false);
-        });
+        let state_for_change = state.clone();
+        state
+            .path_entry
+            .connect_changed(move |_| sync_new_password_path_feedback(&state_for_change));
+    }
+    {
+        let state_for_store = state.clone();
+        state
+            .store_dropdown
+            .connect_selected_notify(move |_| load_new_password_store_index(&state_for_store));
     }
 
-    (dialog, store_dropdown, path_entry, error_label)
+    state
+}
+
+fn store_entry_labels(store_root: &str) -> Vec<String> {
+    let root = Path::new(store_root);
+    collect_password_entry_paths(root, PasswordEntryWalkOptions::default())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|path| label_from_password_entry_path(root, path))
+        .collect()
 }
 
-fn available_store_roots() -> Vec<String> {
-    Preferences::new().store_roots()
+// Read-only stores can't take the new item, but an item there with the
+// same name would still sit next to it in the list.
+fn other_store_entry_labels(store_root: &str) -> Vec<(String, String)> {
+    Preferences::new()
+        .store_roots()
+        .into_iter()
+        .filter(|root| root != store_root)
+        .flat_map(|root| {
+            store_entry_labels(&root)
+                .into_iter()
+                .map(move |label| (root.clone(), label))
+        })
+        .collect()
+}
+
+fn load_new_password_store_index(state: &NewPasswordDialogState) {
+    state.existing_labels.borrow_mut().clear();
+    state.other_store_labels.borrow_mut().clear();
+    sync_new_password_path_feedback(state);
+    let Some(store_root) = selected_new_password_store(state) else {
+        return;
+    };
+
+    let state = state.clone();
+    let store_for_task = store_root.clone();
+    spawn_result_task(
+        move || {
+            (
+                store_entry_labels(&store_for_task),
+                other_store_entry_labels(&store_for_task),
+            )
+        },
+        move |(labels, other_labels)| {
+            if selected_new_password_store(&state).as_deref() != Some(store_root.as_str()) {
+                return;
+            }
+            *state.existing_labels.borrow_mut() = labels;
+            *state.other_store_labels.borrow_mut() = other_labels;
+            sync_new_password_path_feedback(&state);
+        },
+        || {},
+    );
+}
+
+fn new_password_path_problem(text: &str, existing_labels: &[String]) -> Option<&'static str> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if trimmed.ends_with('/') || trimmed.ends_with('\\') {
+        return Some("Add a name after the last slash.");
+    }
+    if trimmed
+        .chars()
+        .any(|ch| ch.is_control() || DISALLOWED_PATH_CHARACTERS.contains(&ch))
+    {
+        return Some("Names can't contain < > : \" | ? or *.");
+    }
+
+    let label = normalize_password_entry_label(trimmed);
+    if label
+        .split('/')
+        .any(|part| part == "." || part == ".." || part.starts_with('.'))
+    {
+        return Some("Names and folders can't start with a dot.");
+    }
+    if existing_labels.iter().any(|existing| existing == &label) {
+        return Some("An item with that name already exists.");
+    }
+    None
+}
+
+fn other_stores_with_label(text: &str, other_store_labels: &[(String, String)]) -> Vec<String> {
+    let label = normalize_password_entry_label(text.trim());
+    let mut stores = other_store_labels
+        .iter()
+        .filter(|(_, existing)| *existing == label)
+        .map(|(store, _)| store.clone())
+        .collect::<Vec<_>>();
+    stores.dedup();
+    stores
+}
+
+fn store_folders(existing_labels: &[String]) -> Vec<String> {
+    let mut folders = existing_labels
+        .iter()
+        .flat_map(|label| {
+            label
+                .match_indices('/')
+                .map(|(index, _)| label[..index].to_string())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+// Suggests folders that continue what has been typed so far, so `wo` offers
+// `work/` and `work/` offers `work/mail/`. Only the next level is offered.
+fn folder_suggestions(text: &str, existing_labels: &[String]) -> Vec<String> {
+    let typed = text.trim_start().replace('\\', "/");
+    let typed_lower = typed.to_lowercase();
+    let depth = typed.matches('/').count();
+    store_folders(existing_labels)
+        .into_iter()
+        .filter(|folder| folder.matches('/').count() == depth)
+        .map(|folder| format!("{folder}/"))
+        .filter(|folder| folder.to_lowercase().starts_with(&typed_lower) && *folder != typed)
+        .take(MAX_FOLDER_SUGGESTIONS)
+        .collect()
+}
+
+fn website_folder_name(website_folder: &str, host: &str, username: &str) -> String {
+    let website_folder = normalize_password_entry_label(website_folder);
+    let mut name = String::new();
+    if !website_folder.is_empty() {
+        name.push_str(&website_folder);
+        name.push('/');
+    }
+    name.push_str(host);
+    name.push('/');
+    name.push_str(username);
+    name
+}
+
+// A pasted address such as `https://me@github.com/login` becomes
+// `web/github.com/me`. Without a user name in the address, the suggestion
+// ends at the site folder so the account name can be typed next.
+fn suggested_name_from_url(text: &str, website_folder: &str) -> Option<String> {
+    let text = text.trim();
+    if !text.contains("://") && !text.starts_with("www.") {
+        return None;
+    }
+    let uri = uri_to_open(text)?;
+    let url = Url::parse(&uri).ok()?;
+    let host = url.host_str()?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    Some(website_folder_name(website_folder, host, url.username()))
+}
+
+fn clear_new_password_suggestions(state: &NewPasswordDialogState) {
+    for row in state.suggestion_rows.borrow_mut().drain(..) {
+        state.suggestions_group.remove(&row);
+    }
+    state.suggestions_group.set_visible(false);
+}
+
+fn append_new_password_suggestion(state: &NewPasswordDialogState, title: &str, value: String) {
+    let row = ActionRow::builder()
+        .title(title)
+        .subtitle(value.as_str())
+        .activatable(true)
+        .build();
+    let path_entry = state.path_entry.clone();
+    row.connect_activated(move |_| {
+        path_entry.set_text(&value);
+        path_entry.grab_focus();
+        path_entry.set_position(-1);
+    });
+    state.suggestions_group.add(&row);
+    state.suggestion_rows.borrow_mut().push(row);
+    state.suggestions_group.set_visible(true);
+}
+
+fn sync_new_password_path_feedback(state: &NewPasswordDialogState) {
+    let text = state.path_entry.text();
+    let existing_labels = state.existing_labels.borrow().clone();
+    let problem = new_password_path_problem(&text, &existing_labels);
+    match problem {
+        Some(message) => show_new_password_dialog_error(state, message),
+        None => clear_new_password_dialog_error(state),
+    }
+    state
+        .path_entry
+        .set_show_apply_button(!text.trim().is_empty() && problem.is_none());
+    sync_new_password_shared_name_warning(state, &text, problem.is_none());
+
+    clear_new_password_suggestions(state);
+    if let Some(name) =
+        suggested_name_from_url(&text, &Preferences::new().new_item_website_folder())
+    {
+        append_new_password_suggestion(state, &gettext("Name it after the website"), name);
+        return;
+    }
+    for folder in folder_suggestions(&text, &existing_labels) {
+        append_new_password_suggestion(state, &gettext("Folder"), folder);
+    }
+}
+
+// Opening the item by name alone could then pick the wrong store, so the
+// dialog says where the name is already taken. It doesn't block saving.
+fn sync_new_password_shared_name_warning(state: &NewPasswordDialogState, text: &str, valid: bool) {
+    let stores = if valid && !text.trim().is_empty() {
+        other_stores_with_label(text, &state.other_store_labels.borrow())
+    } else {
+        Vec::new()
+    };
+    if stores.is_empty() {
+        state.warning_label.set_visible(false);
+        return;
+    }
+
+    let store_labels = StoreLabels::for_stores(&Preferences::new(), &stores);
+    let names = stores
+        .iter()
+        .map(|store| store_labels.label(store))
+        .collect::<Vec<_>>()
+        .join(", ");
+    state.warning_label.set_label(
+        &gettext("An item with this name is also in {stores}.").replace("{stores}", &names),
+    );
+    state.warning_label.set_visible(true);
+}
+
+pub(super) fn available_store_roots() -> Vec<String> {
+    let settings = Preferences::new();
+    settings
+        .store_roots()
+        .into_iter()
+        .filter(|store| !settings.store_is_read_only(store))
+        .collect()
 }
 
 fn resolve_selected_store(stores: &[String], selected: Option<&str>) -> Option<String> {
@@ -90,7 +383,11 @@ fn selected_store_position(stores: &[String], selected: Option<&str>) -> u32 {
 
 pub fn sync_new_password_store_selector(state: &NewPasswordDialogState) {
     let stores = available_store_roots();
-    let labels = shortened_store_labels(&stores);
+    let store_labels = StoreLabels::for_stores(&Preferences::new(), &stores);
+    let labels = stores
+        .iter()
+        .map(|store| store_labels.label(store))
+        .collect::<Vec<_>>();
     let selected = selected_new_password_store(state);
     state.store_roots.borrow_mut().clone_from(&stores);
     state.store_dropdown.set_visible(stores.len() > 1);
@@ -116,16 +413,38 @@ pub fn register_open_new_password_action(
     window: &ApplicationWindow,
     state: &NewPasswordDialogState,
 ) {
-    let window_for_action = window.clone();
-    let window_for_dialog = window.clone();
-    let state = state.clone();
-    register_window_action(&window_for_action, "open-new-password", move || {
-        sync_new_password_store_selector(&state);
-        state.path_entry.set_text("");
-        clear_new_password_dialog_error(&state);
-        state.dialog.present(Some(&window_for_dialog));
-        state.path_entry.grab_focus();
-    });
+    for (action, secure_note) in [("open-new-password", false), ("open-new-secure-note", true)] {
+        let window_for_dialog = window.clone();
+        let state = state.clone();
+        register_window_action(window, action, move || {
+            present_new_password_dialog(&window_for_dialog, &state, secure_note);
+        });
+    }
+}
+
+fn present_new_password_dialog(
+    window: &ApplicationWindow,
+    state: &NewPasswordDialogState,
+    secure_note: bool,
+) {
+    let (title, subtitle) = if secure_note {
+        ("New secure note", "Create a note without a password.")
+    } else {
+        ("New item", "Create a new pass file.")
+    };
+    state.secure_note.set(secure_note);
+    state.dialog.set_title(&gettext(title));
+    state.title.set_title(&gettext(title));
+    state.title.set_subtitle(&gettext(subtitle));
+    sync_new_password_store_selector(state);
+    state.path_entry.set_text("");
+    load_new_password_store_index(state);
+    state.dialog.present(Some(window));
+    state.path_entry.grab_focus();
+}
+
+pub fn new_password_dialog_path_problem(state: &NewPasswordDialogState) -> Option<&'static str> {
+    new_password_path_problem(&state.path_entry.text(), &state.existing_labels.borrow())
 }
 
 pub fn show_new_password_dialog_error(state: &NewPasswordDialogState, message: &str) {
@@ -139,7 +458,10 @@ pub fn clear_new_password_dialog_error(state: &NewPasswordDialogState) {
 
 #[cfg(test)]
 mod tests {
-    use super::{resolve_selected_store, selected_store_position};
+    use super::{
+        folder_suggestions, new_password_path_problem, other_stores_with_label,
+        resolve_selected_store, selected_store_position, suggested_name_from_url,
+    };
     use adw::gtk::INVALID_LIST_POSITION;
 
     #[test]
@@ -166,4 +488,75 @@ mod tests {
         assert_eq!(selected_store_position(&stores, Some("/missing/store")), 0);
         assert_eq!(selected_store_position(&[], None), INVALID_LIST_POSITION);
     }
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| (*label).to_string()).collect()
+    }
+
+    #[test]
+    fn path_problems_cover_slashes_characters_dots_and_collisions() {
+        let existing = labels(&["work/mail", "personal"]);
+
+        assert_eq!(new_password_path_problem("", &existing), None);
+        assert_eq!(new_password_path_problem("work/new", &existing), None);
+        assert_eq!(
+            new_password_path_problem("work/", &existing),
+            Some("Add a name after the last slash.")
+        );
+        assert_eq!(
+            new_password_path_problem("work/a?b", &existing),
+            Some("Names can't contain < > : \" | ? or *.")
+        );
+        assert_eq!(
+            new_password_path_problem("work/../mail", &existing),
+            Some("Names and folders can't start with a dot.")
+        );
+        assert_eq!(
+            new_password_path_problem(" work//mail ", &existing),
+            Some("An item with that name already exists.")
+        );
+    }
+
+    #[test]
+    fn folder_suggestions_offer_the_next_level_only() {
+        let existing = labels(&["work/mail/alice", "work/vpn", "web/github.com/me", "bank"]);
+
+        assert_eq!(
+            folder_suggestions("w", &existing),
+            vec!["web/".to_string(), "work/".to_string()]
+        );
+        assert_eq!(
+            folder_suggestions("work/", &existing),
+            vec!["work/mail/".to_string()]
+        );
+        assert!(folder_suggestions("work/mail/", &existing).is_empty());
+    }
+
+    #[test]
+    fn pasted_urls_suggest_a_website_name() {
+        assert_eq!(
+            suggested_name_from_url("https://me@www.github.com/login", "web"),
+            Some("web/github.com/me".to_string())
+        );
+        assert_eq!(
+            suggested_name_from_url("www.example.com", ""),
+            Some("example.com/".to_string())
+        );
+        assert_eq!(suggested_name_from_url("work/mail", "web"), None);
+    }
+
+    #[test]
+    fn names_taken_in_other_stores_are_reported_per_store() {
+        let other = vec![
+            ("/stores/work".to_string(), "email/gmail".to_string()),
+            ("/stores/work".to_string(), "bank".to_string()),
+            ("/stores/shared".to_string(), "email/gmail".to_string()),
+        ];
+
+        assert_eq!(
+            other_stores_with_label(" email\\gmail ", &other),
+            vec!["/stores/work".to_string(), "/stores/shared".to_string()]
+        );
+        assert!(other_stores_with_label("email/outlook", &other).is_empty());
+    }
 }
@@ -2,20 +2,32 @@ mod countdown;
 mod url;
 
 use self::countdown::OtpCountdownCircle;
-use self::url::{otp_display, otp_secret_from_url, replace_otp_secret};
-use super::file::{structured_otp_line, OtpFieldTemplate, StructuredPassLine};
+use self::url::{
+    is_hotp_url, next_hotp_code, otp_display, otp_secret_from_url, replace_otp_secret,
+};
+use super::file::{
+    parse_structured_pass_lines, structured_otp_line, OtpFieldTemplate, StructuredPassLine,
+};
 use crate::i18n::gettext;
 use crate::logging::log_error;
 use adw::glib::{self, ControlFlow};
-use adw::gtk::GestureClick;
+use adw::gtk::{Align, Button, GestureClick};
 use adw::prelude::*;
 use adw::{PasswordEntryRow, Toast, ToastOverlay};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::time::Duration;
+use zeroize::Zeroizing;
 
 const EMPTY_OTP_URL: &str = "otpauth://totp/Keycord?issuer=Keycord&secret=&digits=6&period=30";
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HotpStep {
+    pub code: String,
+    pub url: String,
+    pub contents: Zeroizing<String>,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum OtpMode {
     Live,
@@ -31,14 +43,21 @@ pub struct PasswordOtpState {
     mode: Rc<Cell<OtpMode>>,
     refresh_generation: Rc<Cell<u64>>,
     countdown: OtpCountdownCircle,
+    next_button: Button,
 }
 
 impl PasswordOtpState {
     pub fn new(row: &PasswordEntryRow, overlay: &ToastOverlay) -> Self {
         let countdown = OtpCountdownCircle::new();
+        let next_button = Button::from_icon_name("view-refresh-symbolic");
+        next_button.add_css_class("flat");
+        next_button.set_valign(Align::Center);
+        next_button.set_tooltip_text(Some(&gettext("Next code")));
+        next_button.set_visible(false);
 
         row.set_activatable(true);
         row.add_suffix(countdown.widget());
+        row.add_suffix(&next_button);
 
         let state = Self {
             row: row.clone(),
@@ -48,6 +67,7 @@ impl PasswordOtpState {
             mode: Rc::new(Cell::new(OtpMode::Live)),
             refresh_generation: Rc::new(Cell::new(0)),
             countdown,
+            next_button,
         };
         state.connect_row_signals();
         state
@@ -66,6 +86,7 @@ impl PasswordOtpState {
         self.countdown.set_visible(false);
         self.countdown.set_fraction(0.0);
         self.countdown.set_tooltip_text(None);
+        self.next_button.set_visible(false);
     }
 
     pub fn sync_from_parsed_lines(
@@ -166,6 +187,25 @@ impl PasswordOtpState {
         self.template.borrow().is_some()
     }
 
+    pub fn is_hotp(&self) -> bool {
+        self.url.borrow().as_deref().is_some_and(is_hotp_url)
+    }
+
+    pub fn connect_next_code<F: Fn() + 'static>(&self, f: F) {
+        self.next_button.connect_clicked(move |_| f());
+    }
+
+    pub fn set_next_code_sensitive(&self, sensitive: bool) {
+        self.next_button.set_sensitive(sensitive);
+    }
+
+    pub fn show_hotp_code(&self, step: &HotpStep) {
+        *self.url.borrow_mut() = Some(step.url.clone());
+        self.mode.set(OtpMode::Live);
+        self.render(false);
+        self.row.set_text(&step.code);
+    }
+
     fn url_for_current_secret(&self) -> Option<String> {
         let current_url = self.url.borrow().clone()?;
         Some(replace_otp_secret(&current_url, &self.row.text()))
@@ -222,6 +262,7 @@ impl PasswordOtpState {
         self.row.set_show_apply_button(true);
         self.row.set_text(&secret);
         self.countdown.set_visible(false);
+        self.next_button.set_visible(false);
     }
 
     fn focus_editor(&self) {
@@ -241,9 +282,19 @@ impl PasswordOtpState {
             return;
         };
 
-        self.row.set_title(&gettext("OTP code"));
         self.row.set_editable(false);
         self.row.set_show_apply_button(false);
+        if is_hotp_url(&url) {
+            // Each HOTP code uses up a counter value, so one is only made on request.
+            self.row.set_title(&gettext("HOTP code"));
+            self.row.set_text("");
+            self.countdown.set_visible(false);
+            self.next_button.set_visible(true);
+            return;
+        }
+
+        self.row.set_title(&gettext("OTP code"));
+        self.next_button.set_visible(false);
         self.countdown.set_visible(true);
 
         match otp_display(&url) {
@@ -309,6 +360,34 @@ impl PasswordOtpState {
     }
 }
 
+// Only the otpauth line changes, so the rest of the entry is written back as it
+// was saved, even when the editor holds unsaved changes.
+pub fn next_hotp_step(contents: &str) -> Result<Option<HotpStep>, String> {
+    let (_, lines) = parse_structured_pass_lines(contents);
+    let Some((_, url)) = structured_otp_line(&lines).filter(|(_, url)| is_hotp_url(url)) else {
+        return Ok(None);
+    };
+    let (code, next_url) = next_hotp_code(&url)?;
+    let Some((password, rest)) = contents.split_once('\n') else {
+        return Ok(None);
+    };
+    let contents = Zeroizing::new(format!("{password}\n{}", rest.replacen(&url, &next_url, 1)));
+
+    Ok(Some(HotpStep {
+        code,
+        url: next_url,
+        contents,
+    }))
+}
+
+// Counter-based codes are left out: using one raises the counter, which
+// has to be saved, and only the item page does that.
+pub fn current_totp_code(contents: &str) -> Option<String> {
+    let (_, lines) = parse_structured_pass_lines(contents);
+    let (_, url) = structured_otp_line(&lines).filter(|(_, url)| !is_hotp_url(url))?;
+    otp_display(&url).ok().map(|(code, _, _)| code)
+}
+
 fn otp_secret_is_blank(url: &str) -> bool {
     otp_secret_from_url(url)
         .unwrap_or_default()
@@ -318,9 +397,13 @@ fn otp_secret_is_blank(url: &str) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::url::{otp_display, otp_period, otp_secret_from_url, replace_otp_secret};
-    use super::{otp_secret_is_blank, EMPTY_OTP_URL};
+    use super::url::{
+        hotp_counter, is_hotp_url, next_hotp_code, otp_display, otp_period, otp_secret_from_url,
+        replace_otp_secret, totp_from_url,
+    };
+    use super::{current_totp_code, next_hotp_step, otp_secret_is_blank, HotpStep, EMPTY_OTP_URL};
     use totp_rs::TOTP;
+    use zeroize::Zeroizing;
 
     #[test]
     fn otp_secret_is_read_from_otpauth_url() {
@@ -389,4 +472,131 @@ mod tests {
         assert!(remaining > 0);
         assert_eq!(period, 30);
     }
+
+    #[test]
+    fn current_totp_codes_skip_counter_based_and_missing_otp_lines() {
+        let code = current_totp_code("secret\notpauth://totp/Test?secret=JBSWY3DPEHPK3PXP")
+            .expect("render TOTP code");
+
+        assert_eq!(code.len(), 6);
+        assert_eq!(current_totp_code(&format!("secret\n{RFC_4226_URL}")), None);
+        assert_eq!(current_totp_code("secret\nuser: alice"), None);
+    }
+
+    const RFC_4226_URL: &str =
+        "otpauth://hotp/Test?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&counter=0";
+
+    #[test]
+    fn hotp_urls_are_told_apart_from_totp_urls() {
+        assert!(is_hotp_url(RFC_4226_URL));
+        assert!(is_hotp_url("otpauth://HOTP/Test?secret=ABC"));
+        assert!(!is_hotp_url("otpauth://totp/Test?secret=ABC"));
+        assert!(!is_hotp_url("not a url"));
+    }
+
+    #[test]
+    fn hotp_counter_defaults_to_zero() {
+        assert_eq!(hotp_counter("otpauth://hotp/Test?secret=ABC"), 0);
+        assert_eq!(
+            hotp_counter("otpauth://hotp/Test?secret=ABC&counter=41"),
+            41
+        );
+    }
+
+    #[test]
+    fn next_hotp_code_matches_rfc_4226_vectors() {
+        let (code, url) = next_hotp_code(RFC_4226_URL).expect("first HOTP code");
+        assert_eq!(code, "287082");
+        assert_eq!(hotp_counter(&url), 1);
+
+        let (code, url) = next_hotp_code(&url).expect("second HOTP code");
+        assert_eq!(code, "359152");
+        assert_eq!(hotp_counter(&url), 2);
+
+        let (code, url) =
+            next_hotp_code("otpauth://hotp/Test?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&counter=4")
+                .expect("sixth HOTP code");
+        assert_eq!(code, "254676");
+        assert_eq!(hotp_counter(&url), 5);
+    }
+
+    #[test]
+    fn next_hotp_step_only_rewrites_the_otp_line() {
+        let contents = format!("secret\nusername: alice\notpauth: {RFC_4226_URL}\nnotes");
+        assert_eq!(
+            next_hotp_step(&contents).expect("HOTP step"),
+            Some(HotpStep {
+                code: "287082".to_string(),
+                url: RFC_4226_URL.replace("counter=0", "counter=1"),
+                contents: Zeroizing::new(format!(
+                    "secret\nusername: alice\notpauth: {}\nnotes",
+                    RFC_4226_URL.replace("counter=0", "counter=1")
+                )),
+            })
+        );
+        assert_eq!(
+            next_hotp_step("secret\notpauth://totp/Test?secret=ABC").expect("TOTP entry"),
+            None
+        );
+    }
+
+    const RFC_6238_SHA1_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    const RFC_6238_SHA256_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+    const RFC_6238_SHA512_SECRET: &str =
+        "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA";
+
+    fn code_at(url: &str, time: u64) -> String {
+        totp_from_url(url).expect("parse OTP URL").generate(time)
+    }
+
+    #[test]
+    fn totp_codes_follow_algorithm_and_digit_parameters() {
+        let sha1 = format!("otpauth://totp/Test?secret={RFC_6238_SHA1_SECRET}&digits=8");
+        assert_eq!(code_at(&sha1, 59), "94287082");
+        assert_eq!(code_at(&sha1, 1_111_111_109), "07081804");
+
+        let sha256 = format!(
+            "otpauth://totp/Test?secret={RFC_6238_SHA256_SECRET}&algorithm=SHA256&digits=8"
+        );
+        assert_eq!(code_at(&sha256, 59), "46119246");
+        assert_eq!(code_at(&sha256, 1_111_111_109), "68084774");
+
+        let sha512 = format!(
+            "otpauth://totp/Test?secret={RFC_6238_SHA512_SECRET}&algorithm=SHA512&digits=8"
+        );
+        assert_eq!(code_at(&sha512, 59), "90693936");
+        assert_eq!(code_at(&sha512, 1_111_111_109), "25091201");
+
+        let seven_digits = format!("otpauth://totp/Test?secret={RFC_6238_SHA1_SECRET}&digits=7");
+        assert_eq!(code_at(&seven_digits, 59), "4287082");
+    }
+
+    #[test]
+    fn otp_parameters_are_read_regardless_of_spelling() {
+        let url = format!(
+            "otpauth://totp/Test?Secret={RFC_6238_SHA256_SECRET}&Algorithm=sha-256&Digits=8"
+        );
+        assert_eq!(code_at(&url, 59), "46119246");
+    }
+
+    #[test]
+    fn otp_period_is_honored_and_zero_falls_back() {
+        let url = format!("otpauth://totp/Test?secret={RFC_6238_SHA1_SECRET}&period=60");
+        assert_eq!(totp_from_url(&url).expect("60 second OTP").step, 60);
+
+        let url = format!("otpauth://totp/Test?secret={RFC_6238_SHA1_SECRET}&period=0");
+        assert_eq!(totp_from_url(&url).expect("zero period OTP").step, 30);
+    }
+
+    #[test]
+    fn steam_guard_codes_use_five_letters() {
+        for url in [
+            format!("otpauth://steam/Steam:alice?secret={RFC_6238_SHA1_SECRET}"),
+            format!("otpauth://totp/Steam:alice?secret={RFC_6238_SHA1_SECRET}&issuer=Steam"),
+            format!("otpauth://totp/alice?secret={RFC_6238_SHA1_SECRET}&encoder=steam"),
+        ] {
+            assert_eq!(code_at(&url, 59), "PV9M4", "{url}");
+            assert_eq!(code_at(&url, 1_111_111_109), "PY4YB", "{url}");
+        }
+    }
 }
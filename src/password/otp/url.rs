@@ -3,16 +3,26 @@ use totp_rs::TOTP;
 use url::Url;
 
 const DEFAULT_OTP_PERIOD: u64 = 30;
+const HOTP_HOST: &str = "hotp";
+const STEAM_HOST: &str = "steam";
 
 pub(super) fn otp_display(url: &str) -> Result<(String, u64, u64), String> {
-    let normalized_url = normalized_otp_url(url)?;
-    let totp = TOTP::from_url_unchecked(&normalized_url).map_err(|err| err.to_string())?;
-    let period = otp_period(&normalized_url);
+    let totp = totp_from_url(url)?;
+    let period = totp.step;
     let remaining = otp_remaining_seconds(period);
     let code = totp.generate_current().map_err(|err| err.to_string())?;
     Ok((code, remaining, period))
 }
 
+// totp-rs reads the algorithm, digits, and Steam hints from the URL. The period
+// is set here so a zero or unreadable value falls back to 30 seconds.
+pub(super) fn totp_from_url(url: &str) -> Result<TOTP, String> {
+    let normalized_url = normalized_otp_url(url)?;
+    let mut totp = TOTP::from_url_unchecked(&normalized_url).map_err(|err| err.to_string())?;
+    totp.step = otp_period(&normalized_url);
+    Ok(totp)
+}
+
 pub(super) fn otp_period(url: &str) -> u64 {
     query_param(url, "period")
         .and_then(|value| value.parse::<u64>().ok())
@@ -25,23 +35,84 @@ pub(super) fn otp_secret_from_url(url: &str) -> Option<String> {
 }
 
 pub(super) fn replace_otp_secret(url: &str, secret: &str) -> String {
-    let normalized_secret = normalize_otp_secret(secret);
+    replace_query_param(url, "secret", &normalize_otp_secret(secret))
+}
+
+pub(super) fn is_hotp_url(url: &str) -> bool {
+    Url::parse(url.trim()).is_ok_and(|parsed| {
+        parsed.scheme().eq_ignore_ascii_case("otpauth")
+            && parsed
+                .host_str()
+                .is_some_and(|host| host.eq_ignore_ascii_case(HOTP_HOST))
+    })
+}
+
+pub(super) fn hotp_counter(url: &str) -> u64 {
+    query_param(url, "counter")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(0)
+}
+
+// Like pass-otp, the counter is bumped before the code is generated, so the
+// stored counter always belongs to the last code that was handed out.
+pub(super) fn next_hotp_code(url: &str) -> Result<(String, String), String> {
+    let counter = hotp_counter(url)
+        .checked_add(1)
+        .ok_or_else(|| "HOTP counter is out of range".to_string())?;
+    let code = hotp_code(url, counter)?;
+    Ok((
+        code,
+        replace_query_param(url, "counter", &counter.to_string()),
+    ))
+}
+
+// totp-rs only reads `totp` URLs. HOTP is the same calculation with the
+// counter in place of the time step, so a one-second period lets the counter
+// be passed as the timestamp.
+fn hotp_code(url: &str, counter: u64) -> Result<String, String> {
+    let mut parsed = Url::parse(&normalized_otp_url(url)?).map_err(|err| err.to_string())?;
+    parsed
+        .set_host(Some("totp"))
+        .map_err(|err| err.to_string())?;
+    let pairs = parsed
+        .query_pairs()
+        .filter(|(key, _)| {
+            !key.eq_ignore_ascii_case("counter") && !key.eq_ignore_ascii_case("period")
+        })
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    {
+        let mut query = parsed.query_pairs_mut();
+        query.clear();
+        query.extend_pairs(
+            pairs
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str())),
+        );
+        query.append_pair("period", "1");
+    }
+
+    let totp = TOTP::from_url_unchecked(parsed.as_str()).map_err(|err| err.to_string())?;
+    Ok(totp.generate(counter))
+}
+
+fn replace_query_param(url: &str, name: &str, replacement: &str) -> String {
     if let Ok(mut parsed) = Url::parse(url.trim()) {
-        let mut found_secret = false;
+        let mut found = false;
         let mut pairs = parsed
             .query_pairs()
             .map(|(key, value)| {
-                if key.eq_ignore_ascii_case("secret") {
-                    found_secret = true;
-                    (key.into_owned(), normalized_secret.clone())
+                if key.eq_ignore_ascii_case(name) {
+                    found = true;
+                    (key.into_owned(), replacement.to_string())
                 } else {
                     (key.into_owned(), value.into_owned())
                 }
             })
             .collect::<Vec<_>>();
 
-        if !found_secret {
-            pairs.push(("secret".to_string(), normalized_secret));
+        if !found {
+            pairs.push((name.to_string(), replacement.to_string()));
         }
 
         {
@@ -66,23 +137,23 @@ pub(super) fn replace_otp_secret(url: &str, secret: &str) -> String {
         None => (without_fragment, ""),
     };
 
-    let mut found_secret = false;
+    let mut found = false;
     let mut parts = query
         .split('&')
         .filter(|part| !part.is_empty())
         .map(|part| {
             if let Some((key, _)) = part.split_once('=') {
-                if key.eq_ignore_ascii_case("secret") {
-                    found_secret = true;
-                    return format!("{key}={normalized_secret}");
+                if key.eq_ignore_ascii_case(name) {
+                    found = true;
+                    return format!("{key}={replacement}");
                 }
             }
             part.to_string()
         })
         .collect::<Vec<_>>();
 
-    if !found_secret {
-        parts.push(format!("secret={normalized_secret}"));
+    if !found {
+        parts.push(format!("{name}={replacement}"));
     }
 
     let mut rebuilt = if parts.is_empty() {
@@ -119,23 +190,33 @@ fn query_param(url: &str, key: &str) -> Option<String> {
     })
 }
 
+// Apps disagree on the spelling of otpauth parameters. totp-rs only reads
+// lowercase keys and `SHA256`-style algorithms, and KeePassXC marks Steam
+// codes with `encoder=steam` instead of a `steam` host.
 fn normalized_otp_url(url: &str) -> Result<String, String> {
     let mut parsed = Url::parse(url.trim()).map_err(|err| err.to_string())?;
-    let mut found_secret = false;
+    let mut steam = false;
     let pairs = parsed
         .query_pairs()
-        .map(|(key, value)| {
-            if key.eq_ignore_ascii_case("secret") {
-                found_secret = true;
-                (key.into_owned(), normalize_otp_secret(&value))
-            } else {
-                (key.into_owned(), value.into_owned())
+        .filter_map(|(key, value)| {
+            let key = key.to_ascii_lowercase();
+            match key.as_str() {
+                "secret" => Some((key, normalize_otp_secret(&value))),
+                "algorithm" => Some((key, normalize_otp_algorithm(&value))),
+                "digits" | "period" | "counter" => Some((key, value.trim().to_string())),
+                "encoder" if value.trim().eq_ignore_ascii_case(STEAM_HOST) => {
+                    steam = true;
+                    None
+                }
+                _ => Some((key, value.into_owned())),
             }
         })
         .collect::<Vec<_>>();
 
-    if !found_secret {
-        return Ok(parsed.into());
+    if steam {
+        parsed
+            .set_host(Some(STEAM_HOST))
+            .map_err(|err| err.to_string())?;
     }
 
     {
@@ -151,6 +232,10 @@ fn normalized_otp_url(url: &str) -> Result<String, String> {
     Ok(parsed.into())
 }
 
+fn normalize_otp_algorithm(algorithm: &str) -> String {
+    algorithm.trim().replace('-', "").to_ascii_uppercase()
+}
+
 fn normalize_otp_secret(secret: &str) -> String {
     let without_spacing = secret
         .chars()
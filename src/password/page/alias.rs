@@ -0,0 +1,35 @@
+use super::{open_password_entry_page, PasswordPageState};
+use crate::i18n::gettext;
+use crate::password::model::OpenPassFile;
+use crate::password::opened::get_opened_pass_file;
+use adw::prelude::*;
+
+// An opened alias stays the opened item and shows its original read-only,
+// so a save can't overwrite the alias with the original's contents.
+pub(super) fn sync_password_alias_banner(state: &PasswordPageState, target: Option<&str>) {
+    if let Some(target) = target {
+        state
+            .alias_banner
+            .set_title(&gettext("This is an alias of {item}.").replace("{item}", target));
+    }
+    state.alias_banner.set_revealed(target.is_some());
+    *state.alias_target.borrow_mut() = target.map(str::to_string);
+}
+
+pub fn connect_password_alias_banner(state: &PasswordPageState) {
+    let state_for_open = state.clone();
+    state.alias_banner.connect_button_clicked(move |_| {
+        let Some(target) = state_for_open.alias_target.borrow().clone() else {
+            return;
+        };
+        let Some(alias) = get_opened_pass_file(&state_for_open.nav) else {
+            return;
+        };
+        let original = OpenPassFile::from_label_with_mode(
+            alias.store_path(),
+            target,
+            alias.username_fallback_mode(),
+        );
+        open_password_entry_page(&state_for_open, original, false);
+    });
+}
@@ -0,0 +1,116 @@
+use adw::gtk::{pango, Text};
+use adw::prelude::*;
+use adw::{PasswordEntryRow, StyleManager};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum PasswordCharacterClass {
+    Letter,
+    Digit,
+    Symbol,
+}
+
+pub(super) fn password_character_class(ch: char) -> PasswordCharacterClass {
+    if ch.is_ascii_digit() {
+        PasswordCharacterClass::Digit
+    } else if ch.is_alphabetic() {
+        PasswordCharacterClass::Letter
+    } else {
+        PasswordCharacterClass::Symbol
+    }
+}
+
+// Adwaita's default accent and warning colors, which large type uses for
+// digits and symbols through its style classes.
+fn character_class_color(class: PasswordCharacterClass, dark: bool) -> Option<(u8, u8, u8)> {
+    match (class, dark) {
+        (PasswordCharacterClass::Letter, _) => None,
+        (PasswordCharacterClass::Digit, false) => Some((0x1c, 0x71, 0xd8)),
+        (PasswordCharacterClass::Digit, true) => Some((0x78, 0xae, 0xed)),
+        (PasswordCharacterClass::Symbol, false) => Some((0x9c, 0x6e, 0x03)),
+        (PasswordCharacterClass::Symbol, true) => Some((0xf8, 0xe4, 0x5c)),
+    }
+}
+
+fn password_character_attributes(password: &str, dark: bool) -> pango::AttrList {
+    let attributes = pango::AttrList::new();
+    for (start, ch) in password.char_indices() {
+        let Some((red, green, blue)) = character_class_color(password_character_class(ch), dark)
+        else {
+            continue;
+        };
+        let mut color = pango::AttrColor::new_foreground(
+            u16::from(red) * 257,
+            u16::from(green) * 257,
+            u16::from(blue) * 257,
+        );
+        color.set_start_index(start as u32);
+        color.set_end_index((start + ch.len_utf8()) as u32);
+        attributes.insert(color);
+    }
+    attributes
+}
+
+// The attributes index into the password itself, so they only apply while
+// it is shown and not to the dots that mask it.
+fn sync_password_character_colors(text: &Text) {
+    if !text.visibility() {
+        text.set_attributes(None);
+        return;
+    }
+
+    let dark = StyleManager::default().is_dark();
+    text.set_attributes(Some(&password_character_attributes(&text.text(), dark)));
+}
+
+pub fn connect_password_character_colors(entry: &PasswordEntryRow) {
+    let Some(text) = entry.delegate().and_downcast::<Text>() else {
+        return;
+    };
+
+    text.connect_visibility_notify(sync_password_character_colors);
+    text.connect_changed(|text| sync_password_character_colors(text));
+
+    let text = text.downgrade();
+    StyleManager::default().connect_dark_notify(move |_| {
+        if let Some(text) = text.upgrade() {
+            sync_password_character_colors(&text);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{password_character_attributes, password_character_class, PasswordCharacterClass};
+
+    #[test]
+    fn digits_and_symbols_are_told_apart_from_letters() {
+        assert_eq!(
+            password_character_class('a'),
+            PasswordCharacterClass::Letter
+        );
+        assert_eq!(
+            password_character_class('Ö'),
+            PasswordCharacterClass::Letter
+        );
+        assert_eq!(password_character_class('0'), PasswordCharacterClass::Digit);
+        assert_eq!(
+            password_character_class('#'),
+            PasswordCharacterClass::Symbol
+        );
+        assert_eq!(
+            password_character_class(' '),
+            PasswordCharacterClass::Symbol
+        );
+    }
+
+    #[test]
+    fn only_digits_and_symbols_are_colored() {
+        let attributes = password_character_attributes("aÖ1#", false);
+        let ranges = attributes
+            .attributes()
+            .into_iter()
+            .map(|attribute| (attribute.start_index(), attribute.end_index()))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(3, 4), (4, 5)]);
+    }
+}
@@ -0,0 +1,54 @@
+use super::PasswordPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::password::opened::{get_opened_pass_file, is_opened_pass_file};
+use crate::support::background::spawn_result_task;
+use crate::support::git::{store_git_entry_dates, StoreGitEntryDates};
+use crate::support::ui::local_time_text;
+use adw::prelude::*;
+
+fn entry_dates_text(dates: StoreGitEntryDates) -> String {
+    let created = local_time_text(dates.created_at);
+    if dates.modified_at == dates.created_at {
+        return gettext("Created {created}").replace("{created}", &created);
+    }
+    gettext("Created {created}, last changed {modified}")
+        .replace("{created}", &created)
+        .replace("{modified}", &local_time_text(dates.modified_at))
+}
+
+// The dates come from the store's Git history, so stores without Git and
+// items that were never committed show nothing.
+pub(super) fn sync_password_entry_dates(state: &PasswordPageState) {
+    state.dates_label.set_visible(false);
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+    if !state.saved_entry_exists.get() {
+        return;
+    }
+
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let state = state.clone();
+    spawn_result_task(
+        move || store_git_entry_dates(&store_root, &label),
+        move |result| {
+            if !is_opened_pass_file(&state.nav, &pass_file) {
+                return;
+            }
+            match result {
+                Ok(Some(dates)) => {
+                    state.dates_label.set_text(&entry_dates_text(dates));
+                    state.dates_label.set_visible(true);
+                }
+                Ok(None) => {}
+                Err(err) => log_error(format!(
+                    "Failed to read the dates of '{}': {err}",
+                    pass_file.label()
+                )),
+            }
+        },
+        || {},
+    );
+}
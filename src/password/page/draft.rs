@@ -0,0 +1,118 @@
+use super::editor::{current_editor_contents, sync_editor_contents};
+use super::state::PasswordPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::password::draft::{
+    discard_password_entry_draft, password_entry_draft_exists, read_password_entry_draft,
+    save_password_entry_draft,
+};
+use crate::password::opened::{get_opened_pass_file, is_opened_pass_file};
+use crate::support::background::spawn_result_task;
+use crate::support::ui::visible_navigation_page_is;
+use adw::glib;
+use adw::prelude::*;
+use adw::Toast;
+use std::time::Duration;
+
+const DRAFT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(10);
+
+pub(super) fn sync_password_draft_banner(state: &PasswordPageState, store_root: &str, label: &str) {
+    state
+        .draft_banner
+        .set_revealed(!state.read_only.get() && password_entry_draft_exists(store_root, label));
+}
+
+fn password_editor_is_editing(state: &PasswordPageState) -> bool {
+    (visible_navigation_page_is(&state.nav, &state.page) && state.editor_is_open())
+        || visible_navigation_page_is(&state.nav, &state.raw_page)
+}
+
+// While the restore banner is up, the draft on disk is the one from the
+// earlier session and is kept until it is restored or the item is saved.
+fn autosave_password_draft(state: &PasswordPageState) {
+    if state.read_only.get() || !password_editor_is_editing(state) {
+        return;
+    }
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+    let contents = current_editor_contents(state);
+    if contents == *state.drafted_contents.borrow() {
+        return;
+    }
+
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let unsaved = contents != *state.saved_contents.borrow();
+    *state.drafted_contents.borrow_mut() = contents.clone();
+    if !unsaved {
+        if !state.draft_banner.is_revealed() {
+            discard_password_entry_draft(&store_root, &label);
+        }
+        return;
+    }
+
+    state.draft_banner.set_revealed(false);
+    spawn_result_task(
+        move || {
+            save_password_entry_draft(&store_root, &label, &contents).map_err(|err| (label, err))
+        },
+        |result| {
+            if let Err((label, err)) = result {
+                log_error(format!("Failed to save a draft of '{label}': {err}"));
+            }
+        },
+        || {},
+    );
+}
+
+fn restore_password_draft(state: &PasswordPageState) {
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+    state.draft_banner.set_revealed(false);
+
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let state = state.clone();
+    spawn_result_task(
+        move || read_password_entry_draft(&store_root, &label),
+        move |result| {
+            if !is_opened_pass_file(&state.nav, &pass_file) {
+                return;
+            }
+            match result {
+                Ok(contents) => {
+                    sync_editor_contents(&state, &contents, Some(&pass_file));
+                    *state.drafted_contents.borrow_mut() = contents;
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Restored the draft.")));
+                }
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to restore the draft of '{}': {err}",
+                        pass_file.label()
+                    ));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't restore the draft.")));
+                }
+            }
+        },
+        || {},
+    );
+}
+
+pub fn connect_password_draft_autosave(state: &PasswordPageState) {
+    let state_for_restore = state.clone();
+    state.draft_banner.connect_button_clicked(move |_| {
+        restore_password_draft(&state_for_restore);
+    });
+
+    let state = state.clone();
+    glib::timeout_add_local(DRAFT_AUTOSAVE_INTERVAL, move || {
+        autosave_password_draft(&state);
+        glib::ControlFlow::Continue
+    });
+}
@@ -1,7 +1,7 @@
 use super::super::file::{
     dynamic_field_row, parse_structured_pass_lines, rebuild_dynamic_fields_from_lines,
-    structured_pass_contents, sync_username_row_from_parsed_lines, DynamicFieldTemplate,
-    OtpFieldTemplate, StructuredPassLine,
+    structured_notes_markup, structured_pass_contents, sync_username_row_from_parsed_lines,
+    DynamicFieldTemplate, OtpFieldTemplate, StructuredPassLine,
 };
 use super::{refresh_apply_template_button, refresh_password_analysis_label, PasswordPageState};
 use crate::password::model::OpenPassFile;
@@ -9,22 +9,25 @@ use crate::preferences::Preferences;
 use crate::support::ui::visible_navigation_page_is;
 use adw::glib;
 use adw::prelude::*;
+use zeroize::Zeroizing;
 
-pub(super) fn structured_editor_contents(state: &PasswordPageState) -> String {
-    structured_pass_contents(
+pub(super) fn structured_editor_contents(state: &PasswordPageState) -> Zeroizing<String> {
+    Zeroizing::new(structured_pass_contents(
         &state.entry.text(),
         &state.username.text(),
         state.otp.current_url().as_deref(),
         &state.structured_templates.borrow(),
         &state.dynamic_rows.borrow(),
-    )
+    ))
 }
 
-pub(super) fn current_editor_contents(state: &PasswordPageState) -> String {
-    if visible_navigation_page_is(&state.nav, &state.raw_page) {
-        let buffer = state.text.buffer();
-        let (start, end) = buffer.bounds();
-        buffer.text(&start, &end, false).to_string()
+pub(super) fn editing_raw_contents(state: &PasswordPageState) -> bool {
+    state.raw_only.get() || visible_navigation_page_is(&state.nav, &state.raw_page)
+}
+
+pub(super) fn current_editor_contents(state: &PasswordPageState) -> Zeroizing<String> {
+    if editing_raw_contents(state) {
+        state.raw_buffer().contents()
     } else {
         structured_editor_contents(state)
     }
@@ -37,7 +40,7 @@ pub(super) fn sync_editor_contents(
 ) {
     let (password, structured_lines) = parse_structured_pass_lines(contents);
     state.entry.set_text(&password);
-    state.text.buffer().set_text(contents);
+    state.raw_buffer().set_contents(contents);
     rebuild_dynamic_fields_from_lines(
         &state.dynamic_box,
         &state.overlay,
@@ -54,6 +57,19 @@ pub(super) fn sync_editor_contents(
         .generator_controls
         .set_settings(&Preferences::new().password_generation_settings());
     sync_otp_add_button(state);
+    refresh_notes_preview(state);
+}
+
+pub(super) fn refresh_notes_preview(state: &PasswordPageState) {
+    if !state.notes_preview_button.is_active() || !state.notes_preview_button.is_visible() {
+        state.notes_preview_label.set_visible(false);
+        return;
+    }
+
+    let (_, structured_lines) = parse_structured_pass_lines(&structured_editor_contents(state));
+    let markup = structured_notes_markup(&structured_lines);
+    state.notes_preview_label.set_markup(&markup);
+    state.notes_preview_label.set_visible(!markup.is_empty());
 }
 
 pub(super) fn add_empty_otp_secret(state: &PasswordPageState) {
@@ -65,9 +81,8 @@ pub(super) fn add_empty_otp_secret(state: &PasswordPageState) {
     state.otp.add_empty_secret();
     sync_otp_add_button(state);
     state
-        .text
-        .buffer()
-        .set_text(&structured_editor_contents(state));
+        .raw_buffer()
+        .set_contents(&structured_editor_contents(state));
     refresh_apply_template_button(state);
 }
 
@@ -89,9 +104,8 @@ pub(super) fn add_empty_dynamic_field(
 
     state.dynamic_rows.borrow_mut().push(row);
     state
-        .text
-        .buffer()
-        .set_text(&structured_editor_contents(state));
+        .raw_buffer()
+        .set_contents(&structured_editor_contents(state));
     refresh_apply_template_button(state);
     Ok(())
 }
@@ -108,6 +122,11 @@ pub(super) fn focus_field_add_row(state: &PasswordPageState) {
 }
 
 pub(super) fn focus_password_row(state: &PasswordPageState) {
+    if state.secure_note.get() {
+        focus_field_add_row(state);
+        return;
+    }
+
     if let Some(delegate) = state.entry.delegate() {
         glib::idle_add_local_once(move || {
             delegate.grab_focus();
@@ -0,0 +1,152 @@
+use super::character_colors::{password_character_class, PasswordCharacterClass};
+use super::editor::current_editor_contents;
+use super::state::PasswordPageState;
+use crate::i18n::gettext;
+use crate::support::ui::visible_navigation_page_is;
+use adw::glib::Propagation;
+use adw::gtk::{
+    pango, Align, Box as GtkBox, EventControllerKey, FlowBox, GestureClick, Label, Orientation,
+    PropagationPhase, SelectionMode,
+};
+use adw::prelude::*;
+use adw::{ApplicationWindow, Toast, Window};
+use zeroize::Zeroizing;
+
+const LARGE_TYPE_SCALE: f64 = 5.0;
+
+// A space would be invisible on its own, so it gets the open box that
+// keyboards print on the space bar.
+fn large_type_glyph(ch: char) -> String {
+    if ch == ' ' {
+        "␣".to_string()
+    } else {
+        ch.to_string()
+    }
+}
+
+fn large_type_attributes() -> pango::AttrList {
+    let attributes = pango::AttrList::new();
+    attributes.insert(pango::AttrString::new_family("monospace"));
+    attributes.insert(pango::AttrFloat::new_scale(LARGE_TYPE_SCALE));
+    attributes.insert(pango::AttrInt::new_weight(pango::Weight::Bold));
+    attributes
+}
+
+fn large_type_cell(position: usize, ch: char, attributes: &pango::AttrList) -> GtkBox {
+    let glyph = Label::new(Some(&large_type_glyph(ch)));
+    glyph.set_attributes(Some(attributes));
+    match password_character_class(ch) {
+        PasswordCharacterClass::Letter => {}
+        PasswordCharacterClass::Digit => glyph.add_css_class("accent"),
+        PasswordCharacterClass::Symbol => glyph.add_css_class("warning"),
+    }
+
+    let index = Label::new(Some(&position.to_string()));
+    index.add_css_class("dim-label");
+    index.add_css_class("caption");
+
+    let cell = GtkBox::new(Orientation::Vertical, 6);
+    cell.append(&glyph);
+    cell.append(&index);
+    cell
+}
+
+fn present_large_type_window(parent: &ApplicationWindow, password: &str) {
+    let attributes = large_type_attributes();
+    let characters = FlowBox::new();
+    characters.set_selection_mode(SelectionMode::None);
+    characters.set_can_focus(false);
+    characters.set_homogeneous(true);
+    characters.set_max_children_per_line(16);
+    characters.set_column_spacing(12);
+    characters.set_row_spacing(24);
+    characters.set_valign(Align::Center);
+    characters.set_halign(Align::Center);
+    for (index, ch) in password.chars().enumerate() {
+        characters.append(&large_type_cell(index + 1, ch, &attributes));
+    }
+
+    let hint = Label::new(Some(&gettext("Press any key or click to close.")));
+    hint.add_css_class("dim-label");
+
+    let content = GtkBox::new(Orientation::Vertical, 48);
+    content.set_valign(Align::Center);
+    content.set_margin_start(48);
+    content.set_margin_end(48);
+    content.append(&characters);
+    content.append(&hint);
+
+    let window = Window::builder()
+        .title(gettext("Large type"))
+        .modal(true)
+        .transient_for(parent)
+        .content(&content)
+        .build();
+
+    let keys = EventControllerKey::new();
+    keys.set_propagation_phase(PropagationPhase::Capture);
+    let window_for_keys = window.clone();
+    keys.connect_key_pressed(move |_, _, _, _| {
+        window_for_keys.close();
+        Propagation::Stop
+    });
+    window.add_controller(keys);
+
+    let click = GestureClick::new();
+    let window_for_click = window.clone();
+    click.connect_released(move |_, _, _, _| window_for_click.close());
+    window.add_controller(click);
+
+    // There is no way to keep a window out of screenshots on Linux, so the
+    // password goes away as soon as anything else takes focus.
+    window.connect_is_active_notify(|window| {
+        if !window.is_active() {
+            window.close();
+        }
+    });
+
+    window.fullscreen();
+    window.present();
+}
+
+// Both ways of getting a password out without the clipboard take it from
+// the open item, saved or not.
+pub(super) fn current_item_password(state: &PasswordPageState) -> Option<Zeroizing<String>> {
+    let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
+    let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
+        return None;
+    }
+
+    let password = Zeroizing::new(
+        current_editor_contents(state)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    );
+    if password.trim().is_empty() {
+        state
+            .overlay
+            .add_toast(Toast::new(&gettext("This item has no password.")));
+        return None;
+    }
+    Some(password)
+}
+
+pub fn show_current_password_large_type(state: &PasswordPageState, window: &ApplicationWindow) {
+    if let Some(password) = current_item_password(state) {
+        present_large_type_window(window, &password);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::large_type_glyph;
+
+    #[test]
+    fn spaces_stay_visible_in_large_type() {
+        assert_eq!(large_type_glyph(' '), "␣");
+        assert_eq!(large_type_glyph('x'), "x");
+    }
+}
@@ -0,0 +1,134 @@
+use super::editor::sync_editor_contents;
+use super::state::PasswordPageState;
+use super::{
+    password_page_has_unsaved_changes, password_page_window, save_confirmed_password_entry,
+};
+use crate::i18n::gettext;
+use crate::password::draft::discard_password_entry_draft;
+use crate::password::opened::get_opened_pass_file;
+use crate::support::actions::activate_widget_action;
+use crate::support::ui::visible_navigation_page_is;
+use adw::glib::Propagation;
+use adw::prelude::*;
+use adw::{AlertDialog, ApplicationWindow, ResponseAppearance};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PasswordPageExit {
+    Back,
+    CloseWindow,
+}
+
+fn password_page_exit_action(exit: PasswordPageExit) -> Option<&'static str> {
+    match exit {
+        PasswordPageExit::Back => Some("win.back"),
+        PasswordPageExit::CloseWindow => None,
+    }
+}
+
+// Going back from the raw text page returns to the structured editor with
+// the same contents, so only leaving the item itself needs a question.
+fn exit_leaves_password_editor(state: &PasswordPageState, exit: PasswordPageExit) -> bool {
+    let on_editor = visible_navigation_page_is(&state.nav, &state.page) && state.editor_is_open();
+    let on_raw_page = visible_navigation_page_is(&state.nav, &state.raw_page);
+    match exit {
+        PasswordPageExit::Back => on_editor,
+        PasswordPageExit::CloseWindow => on_editor || on_raw_page,
+    }
+}
+
+fn leave_password_page(state: &PasswordPageState, exit: PasswordPageExit) {
+    match password_page_exit_action(exit) {
+        Some(action) => activate_widget_action(&state.nav, action),
+        None => {
+            if let Some(window) = password_page_window(state) {
+                window.close();
+            }
+        }
+    }
+}
+
+fn discard_unsaved_password_changes(state: &PasswordPageState) {
+    let saved_contents = state.saved_contents.borrow().clone();
+    let pass_file = get_opened_pass_file(&state.nav);
+    sync_editor_contents(state, &saved_contents, pass_file.as_ref());
+    *state.drafted_contents.borrow_mut() = saved_contents;
+    if let Some(pass_file) = pass_file {
+        discard_password_entry_draft(pass_file.store_path(), &pass_file.label());
+    }
+}
+
+// A save that succeeds goes back on its own, so only closing the window has
+// to be remembered until then.
+fn save_before_leaving_password_page(state: &PasswordPageState, exit: PasswordPageExit) {
+    state
+        .close_after_save
+        .set(exit == PasswordPageExit::CloseWindow);
+    save_confirmed_password_entry(state);
+}
+
+// Returns true when the question was shown and the caller should stay put.
+pub fn confirm_leaving_password_page(state: &PasswordPageState, exit: PasswordPageExit) -> bool {
+    if state.read_only.get()
+        || !exit_leaves_password_editor(state, exit)
+        || !password_page_has_unsaved_changes(state)
+    {
+        return false;
+    }
+
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Save changes?"))
+        .body(gettext("This item has changes that are not saved yet."))
+        .build();
+    let cancel = gettext("Cancel");
+    let discard = gettext("Discard");
+    let save = gettext("Save");
+    dialog.add_responses(&[
+        ("cancel", cancel.as_str()),
+        ("discard", discard.as_str()),
+        ("save", save.as_str()),
+    ]);
+    dialog.set_response_appearance("discard", ResponseAppearance::Destructive);
+    dialog.set_response_appearance("save", ResponseAppearance::Suggested);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("save"));
+
+    let state_for_response = state.clone();
+    dialog.connect_response(None, move |_, response| match response {
+        "discard" => {
+            discard_unsaved_password_changes(&state_for_response);
+            leave_password_page(&state_for_response, exit);
+        }
+        "save" => save_before_leaving_password_page(&state_for_response, exit),
+        _ => {}
+    });
+    dialog.present(Some(&state.nav));
+    true
+}
+
+pub fn connect_password_page_close_guard(window: &ApplicationWindow, state: &PasswordPageState) {
+    let state = state.clone();
+    window.connect_close_request(move |_| {
+        if confirm_leaving_password_page(&state, PasswordPageExit::CloseWindow) {
+            Propagation::Stop
+        } else {
+            Propagation::Proceed
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{password_page_exit_action, PasswordPageExit};
+
+    #[test]
+    fn leaving_the_editor_repeats_the_action_that_was_held_back() {
+        assert_eq!(
+            password_page_exit_action(PasswordPageExit::Back),
+            Some("win.back")
+        );
+        assert_eq!(
+            password_page_exit_action(PasswordPageExit::CloseWindow),
+            None
+        );
+    }
+}
@@ -1,32 +1,49 @@
+mod alias;
+mod character_colors;
+mod dates;
+mod draft;
 mod editor;
+mod large_type;
+mod leave;
 mod linux;
+mod privacy;
+mod review;
+mod spell_out;
 mod standard;
 mod state;
+mod store_row;
+mod type_password;
 
 use super::file::{
-    apply_pass_file_template_contents, clean_pass_file_contents,
-    new_pass_file_contents_from_template, pass_file_has_missing_template_fields,
-    structured_pass_contents,
+    apply_pass_file_template_contents, clean_pass_file_contents, connect_notes_preview_links,
+    new_pass_file_contents_for_path, new_pass_file_contents_from_template, open_pass_file_website,
+    pass_file_has_missing_template_fields, pass_file_is_secure_note, pass_file_needs_raw_editor,
+    pass_file_website, structured_pass_contents,
 };
 use super::generation::generate_password;
-use super::list::{load_passwords_async, PasswordListActions};
+use super::list::{show_passwords, PasswordListActions};
 use crate::backend::{
-    import_ripasso_private_key_bytes, password_entry_fido2_recipient_count,
-    read_password_entry_with_progress, rename_password_entry,
+    import_ripasso_private_key_bytes, password_entry_fido2_recipient_count, rename_password_entry,
     ripasso_private_key_requires_passphrase, save_password_entry,
-    save_password_entry_with_progress, ManagedRipassoPrivateKey, PasswordEntryError,
-    PasswordEntryReadProgress, PasswordEntryWriteError, PasswordEntryWriteProgress,
-    PrivateKeyError,
+    save_password_entry_with_progress, update_password_entry, ManagedRipassoPrivateKey,
+    PasswordEntryError, PasswordEntryReadProgress, PasswordEntryWriteError,
+    PasswordEntryWriteProgress, PrivateKeyError,
 };
 use crate::clipboard::set_clipboard_text;
 use crate::i18n::gettext;
 use crate::logging::log_error;
+use crate::password::alias::read_password_entry_following_alias;
+use crate::password::attachments::sync_password_attachments;
+use crate::password::draft::discard_password_entry_draft;
 use crate::password::entry_files::normalize_password_entry_label;
+use crate::password::expiry::{pass_file_expiry_status, ExpiryStatus};
 use crate::password::model::{OpenPassFile, UsernameFallbackError};
 use crate::password::opened::{
     clear_opened_pass_file, get_opened_pass_file, is_opened_pass_file,
     refresh_opened_pass_file_from_contents, set_opened_pass_file,
 };
+use crate::password::otp::next_hotp_step;
+use crate::password::protected::{confirm_protected_entry_change, ProtectedChange};
 use crate::password::strength::weak_password_reason;
 use crate::password::undo::{push_undo_action, restore_saved_entry_action};
 use crate::preferences::Preferences;
@@ -34,8 +51,12 @@ use crate::private_key::dialog::{
     build_private_key_progress_dialog, present_private_key_password_dialog, PrivateKeyDialogHandle,
 };
 use crate::private_key::sync::{sync_private_keys_with_host, PrivateKeySyncDirection};
+use crate::store::appearance::store_entry_subtitle;
 use crate::support::actions::activate_widget_action;
-use crate::support::background::{spawn_progress_result_task, spawn_result_task_with_finalizer};
+use crate::support::activity::record_activity;
+use crate::support::background::{
+    spawn_progress_result_task, spawn_result_task, spawn_result_task_with_finalizer,
+};
 use crate::support::ui::{
     navigation_stack_is_root, pop_navigation_to_root, push_navigation_page_if_needed,
     visible_navigation_page_is,
@@ -48,19 +69,37 @@ use adw::{ApplicationWindow, Dialog, Toast};
 use secrecy::{ExposeSecret, SecretString};
 use std::rc::Rc;
 use std::string::ToString;
-
+use zeroize::Zeroizing;
+
+pub use self::alias::connect_password_alias_banner;
+use self::alias::sync_password_alias_banner;
+pub use self::character_colors::connect_password_character_colors;
+use self::dates::sync_password_entry_dates;
+pub use self::draft::connect_password_draft_autosave;
+use self::draft::sync_password_draft_banner;
 use self::editor::{
     add_empty_dynamic_field, add_empty_otp_secret as add_empty_otp_secret_to_editor,
-    current_editor_contents, focus_field_add_row, focus_password_row, structured_editor_contents,
-    sync_editor_contents,
+    current_editor_contents, editing_raw_contents, focus_field_add_row, focus_password_row,
+    refresh_notes_preview, structured_editor_contents, sync_editor_contents,
+};
+pub use self::large_type::show_current_password_large_type;
+pub use self::leave::{
+    confirm_leaving_password_page, connect_password_page_close_guard, PasswordPageExit,
 };
 use self::linux as platform;
 use self::platform::handle_open_password_entry_error;
+pub use self::privacy::connect_password_page_privacy;
+use self::review::review_changes_before_save;
+pub use self::spell_out::connect_password_spell_out;
 pub use self::state::PasswordPageState;
 use self::state::{
     reset_password_editor, show_password_editor_chrome, show_password_editor_fields,
-    show_password_loading_state, show_password_status_message, sync_saved_password_state,
+    show_password_loading_state, show_password_status_message, sync_raw_only_password_editor,
+    sync_read_only_password_editor, sync_saved_password_state, sync_secure_note_editor,
 };
+pub use self::store_row::connect_password_store_row;
+use self::store_row::sync_new_item_store_row;
+pub use self::type_password::type_current_password;
 
 fn password_open_failure_message(error: Option<&PasswordEntryError>) -> &'static str {
     error
@@ -158,10 +197,10 @@ enum PasswordPageDisplay {
 
 struct PasswordSaveContext {
     pass_file: OpenPassFile,
-    contents: String,
+    contents: Zeroizing<String>,
     previous_store: String,
     previous_label: String,
-    previous_contents: String,
+    previous_contents: Zeroizing<String>,
     previous_entry_exists: bool,
     target_label: Option<String>,
 }
@@ -196,11 +235,12 @@ fn validate_password_save_contents(contents: &str) -> Result<(), String> {
 }
 
 fn prepared_password_save_contents(
-    contents: String,
+    contents: Zeroizing<String>,
     clear_empty_fields_before_save: bool,
-) -> String {
-    if clear_empty_fields_before_save {
-        clean_pass_file_contents(&contents)
+    editing_raw: bool,
+) -> Zeroizing<String> {
+    if clear_empty_fields_before_save && !editing_raw {
+        Zeroizing::new(clean_pass_file_contents(&contents))
     } else {
         contents
     }
@@ -210,25 +250,28 @@ fn prepare_password_save_context(state: &PasswordPageState) -> Result<PasswordSa
     let pass_file =
         get_opened_pass_file(&state.nav).ok_or_else(|| "Open an item first.".to_string())?;
     let preferences = Preferences::new();
-    let editor_contents = current_editor_contents(state);
+    let editing_raw = editing_raw_contents(state);
 
-    let otp_url = state
-        .otp
-        .current_url_for_save()
-        .map_err(ToString::to_string)?;
-    let contents = if visible_navigation_page_is(&state.nav, &state.raw_page) {
-        editor_contents
+    let contents = if editing_raw {
+        current_editor_contents(state)
     } else {
-        structured_pass_contents(
+        let otp_url = state
+            .otp
+            .current_url_for_save()
+            .map_err(ToString::to_string)?;
+        Zeroizing::new(structured_pass_contents(
             &state.entry.text(),
             &state.username.text(),
             otp_url.as_deref(),
             &state.structured_templates.borrow(),
             &state.dynamic_rows.borrow(),
-        )
+        ))
     };
-    let contents =
-        prepared_password_save_contents(contents, preferences.clear_empty_fields_before_save());
+    let contents = prepared_password_save_contents(
+        contents,
+        preferences.clear_empty_fields_before_save(),
+        editing_raw,
+    );
     let target_label = pass_file
         .updated_label_from_username(&state.username.text())
         .map_err(|err| username_fallback_failure_message(err).to_string())?;
@@ -282,9 +325,14 @@ fn finish_password_save(
     show_password_editor_fields(state);
     sync_editor_contents(state, &save_context.contents, updated_pass_file.as_ref());
     sync_saved_password_state(state, &save_context.contents, true);
+    sync_raw_only_password_editor(state, pass_file_needs_raw_editor(&save_context.contents));
+    sync_password_attachments(state);
+    sync_password_entry_dates(state);
     let current_label = updated_pass_file
         .as_ref()
         .map_or_else(|| save_context.previous_label.clone(), OpenPassFile::label);
+    discard_password_entry_draft(&save_context.previous_store, &save_context.previous_label);
+    discard_password_entry_draft(save_context.pass_file.store_path(), &current_label);
     if !save_context.previous_entry_exists
         || save_context.previous_contents != save_context.contents
         || save_context.previous_label != current_label
@@ -302,7 +350,18 @@ fn finish_password_save(
             ),
         );
     }
+    record_activity(if save_context.previous_entry_exists {
+        format!("Edited {current_label}")
+    } else {
+        format!("Created {current_label}")
+    });
     state.overlay.add_toast(Toast::new(&gettext("Saved.")));
+    if state.close_after_save.replace(false) {
+        if let Some(window) = password_page_window(state) {
+            window.close();
+            return;
+        }
+    }
     activate_widget_action(&state.nav, "win.back");
 }
 
@@ -416,7 +475,7 @@ pub fn open_password_entry_page(
     show_password_loading_state(
         state,
         opened_pass_file.title(),
-        &pass_label,
+        &store_entry_subtitle(opened_pass_file.store_path(), &pass_label),
         fido2_recipient_count,
     );
     if push_page {
@@ -435,7 +494,7 @@ pub fn open_password_entry_page(
             let mut report_progress = move |progress: PasswordEntryReadProgress| {
                 let _ = progress_tx.send(progress);
             };
-            read_password_entry_with_progress(
+            read_password_entry_following_alias(
                 &store_for_thread,
                 &label_for_thread,
                 &mut report_progress,
@@ -457,16 +516,37 @@ pub fn open_password_entry_page(
             }
 
             match result {
-                Ok(output) => {
+                Ok((alias_target, output)) => {
+                    let opened_pass_file = opened_pass_file_for_result;
                     let updated_pass_file = refresh_opened_pass_file_from_contents(
                         &state_for_result.nav,
-                        &opened_pass_file_for_result,
+                        &opened_pass_file,
                         &output,
                     );
+                    sync_secure_note_editor(&state_for_result, pass_file_is_secure_note(&output));
                     show_password_editor_fields(&state_for_result);
                     sync_editor_contents(&state_for_result, &output, updated_pass_file.as_ref());
                     sync_saved_password_state(&state_for_result, &output, true);
-                    focus_password_row(&state_for_result);
+                    let raw_only = pass_file_needs_raw_editor(&output);
+                    sync_raw_only_password_editor(&state_for_result, raw_only);
+                    sync_read_only_password_editor(
+                        &state_for_result,
+                        alias_target.is_some()
+                            || Preferences::new().store_is_read_only(opened_pass_file.store_path()),
+                    );
+                    sync_password_alias_banner(&state_for_result, alias_target.as_deref());
+                    sync_password_attachments(&state_for_result);
+                    sync_password_entry_dates(&state_for_result);
+                    sync_password_draft_banner(
+                        &state_for_result,
+                        opened_pass_file.store_path(),
+                        &opened_pass_file.label(),
+                    );
+                    if raw_only {
+                        show_raw_pass_file_page(&state_for_result);
+                    } else {
+                        focus_password_row(&state_for_result);
+                    }
                 }
                 Err(err) => {
                     log_error(format!("Failed to open password entry: {err}"));
@@ -496,6 +576,7 @@ pub fn begin_new_password_entry(
     path: &str,
     store_root: Option<String>,
     add_dialog: &Dialog,
+    secure_note: bool,
 ) -> Result<(), &'static str> {
     let path = normalize_password_entry_label(path);
     let path = path.as_str();
@@ -508,29 +589,54 @@ pub fn begin_new_password_entry(
     if store_root.trim().is_empty() {
         return Err("Add a store folder first.");
     }
-    let template_contents =
-        new_pass_file_contents_from_template(&settings.new_pass_file_template());
-    let opened_pass_file = OpenPassFile::from_label(store_root, path);
+    if settings.store_is_read_only(&store_root) {
+        return Err("This store is read-only.");
+    }
+    let template = settings.new_pass_file_template();
+    let template_contents = if secure_note {
+        String::new()
+    } else if settings.fill_fields_from_path() {
+        new_pass_file_contents_for_path(&template, path)
+    } else {
+        new_pass_file_contents_from_template(&template)
+    };
+    let opened_pass_file = OpenPassFile::from_label(store_root.clone(), path);
     set_opened_pass_file(&state.nav, opened_pass_file.clone());
     let template_pass_file =
         refresh_opened_pass_file_from_contents(&state.nav, &opened_pass_file, &template_contents)
             .or_else(|| get_opened_pass_file(&state.nav));
 
-    show_password_editor_chrome(state, "New item", path);
+    show_password_editor_chrome(
+        state,
+        if secure_note {
+            "New secure note"
+        } else {
+            "New item"
+        },
+        path,
+    );
+    sync_secure_note_editor(state, secure_note);
     show_password_editor_fields(state);
+    sync_raw_only_password_editor(state, false);
+    sync_read_only_password_editor(state, false);
     state.otp.clear();
     push_navigation_page_if_needed(&state.nav, &state.page);
 
     add_dialog.force_close();
     sync_editor_contents(state, &template_contents, template_pass_file.as_ref());
     sync_saved_password_state(state, &template_contents, false);
+    sync_new_item_store_row(state, &store_root);
+    sync_password_draft_banner(state, &store_root, path);
     focus_password_row(state);
     Ok(())
 }
 
 pub fn show_raw_pass_file_page(state: &PasswordPageState) {
-    let contents = structured_editor_contents(state);
-    state.text.buffer().set_text(&contents);
+    if !state.raw_only.get() {
+        state
+            .raw_buffer()
+            .set_contents(&structured_editor_contents(state));
+    }
 
     let subtitle = get_opened_pass_file(&state.nav).map_or_else(
         || APP_WINDOW_TITLE.to_string(),
@@ -550,7 +656,7 @@ pub fn add_empty_otp_secret(state: &PasswordPageState) {
 }
 
 pub fn focus_add_pass_field_input(state: &PasswordPageState) {
-    if !visible_navigation_page_is(&state.nav, &state.page) || !state.entry.is_visible() {
+    if !visible_navigation_page_is(&state.nav, &state.page) || !state.editor_is_open() {
         return;
     }
 
@@ -558,7 +664,7 @@ pub fn focus_add_pass_field_input(state: &PasswordPageState) {
 }
 
 pub fn add_pass_field_from_input(state: &PasswordPageState) {
-    if !visible_navigation_page_is(&state.nav, &state.page) || !state.entry.is_visible() {
+    if !visible_navigation_page_is(&state.nav, &state.page) || !state.editor_is_open() {
         return;
     }
 
@@ -572,19 +678,21 @@ pub fn refresh_apply_template_button(state: &PasswordPageState) {
     let contents = current_editor_contents(state);
     sync_apply_template_button(state, &contents);
     sync_import_private_key_button(state, &contents);
+    sync_open_website_button(state, &contents);
+    sync_expiry_label(state, &contents);
 }
 
 pub fn apply_pass_file_template(state: &PasswordPageState) {
     let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
     let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
-    if (!editing_structured || !state.entry.is_visible()) && !editing_raw {
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
         return;
     }
 
     let contents = current_editor_contents(state);
     let templated_contents =
         apply_pass_file_template_contents(&contents, &Preferences::new().new_pass_file_template());
-    if templated_contents == contents {
+    if templated_contents == *contents {
         return;
     }
 
@@ -616,6 +724,34 @@ fn sync_import_private_key_button(state: &PasswordPageState, contents: &str) {
         .set_visible(armored_private_key_block_from_contents(contents).is_some());
 }
 
+fn sync_open_website_button(state: &PasswordPageState, contents: &str) {
+    state
+        .open_website_button
+        .set_visible(pass_file_website(contents).is_some());
+}
+
+fn sync_expiry_label(state: &PasswordPageState, contents: &str) {
+    let label = &state.expiry_label;
+    label.remove_css_class("error");
+    label.remove_css_class("warning");
+    let Some(status) = pass_file_expiry_status(contents) else {
+        label.set_visible(false);
+        return;
+    };
+    let Some(text) = status.label() else {
+        label.set_visible(false);
+        return;
+    };
+
+    label.add_css_class(if status == ExpiryStatus::Expired {
+        "error"
+    } else {
+        "warning"
+    });
+    label.set_label(&text);
+    label.set_visible(true);
+}
+
 fn armored_private_key_block_from_contents(contents: &str) -> Option<&str> {
     let start = contents.find(ARMORED_PRIVATE_KEY_BEGIN)?;
     let remaining = &contents[start..];
@@ -755,7 +891,7 @@ fn prompt_private_key_passphrase_from_pass_file(state: &PasswordPageState, bytes
 pub fn import_private_key_from_current_pass_file(state: &PasswordPageState) {
     let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
     let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
-    if (!editing_structured || !state.entry.is_visible()) && !editing_raw {
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
         return;
     }
 
@@ -781,13 +917,13 @@ pub fn import_private_key_from_current_pass_file(state: &PasswordPageState) {
 pub fn clean_pass_file(state: &PasswordPageState) {
     let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
     let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
-    if (!editing_structured || !state.entry.is_visible()) && !editing_raw {
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
         return;
     }
 
     let contents = current_editor_contents(state);
     let cleaned_contents = clean_pass_file_contents(&contents);
-    if cleaned_contents == contents {
+    if cleaned_contents == *contents {
         return;
     }
 
@@ -821,7 +957,7 @@ pub fn revert_unsaved_password_changes(state: &PasswordPageState) -> bool {
 }
 
 pub fn generate_password_entry(state: &PasswordPageState) {
-    if !state.entry.is_visible() {
+    if !state.entry.is_visible() || state.raw_only.get() {
         return;
     }
 
@@ -830,12 +966,20 @@ pub fn generate_password_entry(state: &PasswordPageState) {
     refresh_password_analysis_label(state);
     if !visible_navigation_page_is(&state.nav, &state.raw_page) {
         state
-            .text
-            .buffer()
-            .set_text(&structured_editor_contents(state));
+            .raw_buffer()
+            .set_contents(&structured_editor_contents(state));
     }
 }
 
+pub fn connect_notes_preview(state: &PasswordPageState) {
+    connect_notes_preview_links(&state.notes_preview_label, &state.overlay);
+    let state = state.clone();
+    state
+        .notes_preview_button
+        .clone()
+        .connect_toggled(move |_| refresh_notes_preview(&state));
+}
+
 pub fn toggle_password_options(state: &PasswordPageState) {
     if !visible_navigation_page_is(&state.nav, &state.page) || !state.entry.is_visible() {
         return;
@@ -849,20 +993,38 @@ pub fn toggle_password_options(state: &PasswordPageState) {
 pub fn copy_current_password(state: &PasswordPageState) {
     let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
     let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
-    if (!editing_structured || !state.entry.is_visible()) && !editing_raw {
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
         return;
     }
 
-    let password = current_editor_contents(state)
-        .lines()
-        .next()
-        .unwrap_or_default()
-        .to_string();
+    let password = Zeroizing::new(
+        current_editor_contents(state)
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+    );
+    if password.trim().is_empty() {
+        state
+            .overlay
+            .add_toast(Toast::new(&gettext("This item has no password.")));
+        return;
+    }
     if set_clipboard_text(&password, &state.overlay, None) {
         state.overlay.add_toast(Toast::new(&gettext("Copied.")));
     }
 }
 
+pub fn open_current_website(state: &PasswordPageState) {
+    let editing_structured = visible_navigation_page_is(&state.nav, &state.page);
+    let editing_raw = visible_navigation_page_is(&state.nav, &state.raw_page);
+    if (!editing_structured || !state.editor_is_open()) && !editing_raw {
+        return;
+    }
+
+    open_pass_file_website(&current_editor_contents(state), &state.overlay);
+}
+
 pub fn copy_current_username(state: &PasswordPageState) {
     if !visible_navigation_page_is(&state.nav, &state.page) || !state.username.is_visible() {
         return;
@@ -877,13 +1039,93 @@ pub fn copy_current_otp(state: &PasswordPageState) {
     if !visible_navigation_page_is(&state.nav, &state.page) || !state.otp.row.is_visible() {
         return;
     }
+    if state.otp.is_hotp() {
+        use_next_hotp_code(state);
+        return;
+    }
 
     if set_clipboard_text(state.otp.row.text().as_str(), &state.overlay, None) {
         state.overlay.add_toast(Toast::new(&gettext("Copied.")));
     }
 }
 
+// The counter is saved before the code is shown so other pass-otp clients
+// never hand out the same code. It is read again from disk, because a sync or
+// another client may have used a code since the page opened.
+fn use_next_hotp_code(state: &PasswordPageState) {
+    if state.read_only.get() {
+        state
+            .overlay
+            .add_toast(Toast::new(&gettext("This store is read-only.")));
+        return;
+    }
+    let Some(pass_file) = get_opened_pass_file(&state.nav) else {
+        return;
+    };
+
+    state.otp.set_next_code_sensitive(false);
+    let store_root = pass_file.store_path().to_string();
+    let label = pass_file.label();
+    let state_for_result = state.clone();
+    let state_for_disconnect = state.clone();
+    spawn_result_task(
+        move || {
+            update_password_entry(&store_root, &label, |contents| {
+                Ok(next_hotp_step(contents)?.map(|step| (step.contents.clone(), step)))
+            })
+        },
+        move |result| {
+            let state = state_for_result;
+            state.otp.set_next_code_sensitive(true);
+            if !is_opened_pass_file(&state.nav, &pass_file) {
+                return;
+            }
+            match result {
+                Ok(Some(step)) => {
+                    sync_saved_password_state(&state, &step.contents, true);
+                    state.otp.show_hotp_code(&step);
+                    if set_clipboard_text(&step.code, &state.overlay, None) {
+                        state.overlay.add_toast(Toast::new(&gettext("Copied.")));
+                    }
+                }
+                Ok(None) => {
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't load the code.")));
+                }
+                Err(err) => {
+                    log_error(format!("Failed to save the HOTP counter: {err}"));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext(password_save_failure_message(&err))));
+                }
+            }
+        },
+        move || {
+            state_for_disconnect.otp.set_next_code_sensitive(true);
+            log_error("HOTP counter worker disconnected unexpectedly.".to_string());
+            state_for_disconnect
+                .overlay
+                .add_toast(Toast::new(&gettext("Can't save changes.")));
+        },
+    );
+}
+
+pub fn connect_password_otp(state: &PasswordPageState) {
+    let state_for_next = state.clone();
+    state
+        .otp
+        .connect_next_code(move || use_next_hotp_code(&state_for_next));
+}
+
 fn save_current_password_entry_impl(state: &PasswordPageState, allow_git_unlock_prompt: bool) {
+    if state.read_only.get() {
+        state
+            .overlay
+            .add_toast(Toast::new(&gettext("This store is read-only.")));
+        return;
+    }
+
     let save_context = match prepare_password_save_context(state) {
         Ok(save_context) => save_context,
         Err(message) => {
@@ -915,8 +1157,33 @@ fn save_current_password_entry_impl(state: &PasswordPageState, allow_git_unlock_
     handle_password_save_result(state, &save_context, result);
 }
 
+fn save_reviewed_password_entry(state: &PasswordPageState) {
+    if !review_changes_before_save(state) {
+        save_current_password_entry_impl(state, true);
+    }
+}
+
+// New items are only created, so just saved items in a protected folder ask
+// for the item's name before the review.
+pub(super) fn save_confirmed_password_entry(state: &PasswordPageState) {
+    let saved_label = (!state.read_only.get() && state.saved_entry_exists.get())
+        .then(|| get_opened_pass_file(&state.nav))
+        .flatten()
+        .map(|pass_file| pass_file.label());
+    let Some(label) = saved_label else {
+        save_reviewed_password_entry(state);
+        return;
+    };
+
+    let state_for_save = state.clone();
+    confirm_protected_entry_change(&state.nav, &label, ProtectedChange::Save, move || {
+        save_reviewed_password_entry(&state_for_save);
+    });
+}
+
 pub fn save_current_password_entry(state: &PasswordPageState) {
-    save_current_password_entry_impl(state, true);
+    state.close_after_save.set(false);
+    save_confirmed_password_entry(state);
 }
 
 pub(super) fn save_current_password_entry_without_git_unlock_prompt(state: &PasswordPageState) {
@@ -943,7 +1210,7 @@ pub fn show_password_list_page(
         &state.find,
         &state.save,
     );
-    load_passwords_async(
+    show_passwords(
         &state.list,
         &list_actions,
         &state.overlay,
@@ -992,6 +1259,7 @@ mod tests {
     };
     use crate::password::model::{OpenPassFile, UsernameFallbackError};
     use crate::preferences::UsernameFallbackMode;
+    use zeroize::Zeroizing;
 
     fn expected_missing_private_key_open_failure_message() -> &'static str {
         "Add a private key in Preferences."
@@ -1192,17 +1460,34 @@ mod tests {
     fn prepared_password_save_contents_can_auto_clean_empty_fields() {
         assert_eq!(
             prepared_password_save_contents(
-                "secret\nusername:\nurl: https://example.com".to_string(),
-                true
-            ),
-            "secret\nurl: https://example.com".to_string()
+                Zeroizing::new("secret\nusername:\nurl: https://example.com".to_string()),
+                true,
+                false
+            )
+            .as_str(),
+            "secret\nurl: https://example.com"
         );
         assert_eq!(
             prepared_password_save_contents(
-                "secret\nusername:\nurl: https://example.com".to_string(),
+                Zeroizing::new("secret\nusername:\nurl: https://example.com".to_string()),
+                false,
                 false
-            ),
-            "secret\nusername:\nurl: https://example.com".to_string()
+            )
+            .as_str(),
+            "secret\nusername:\nurl: https://example.com"
+        );
+    }
+
+    #[test]
+    fn prepared_password_save_contents_keeps_raw_edits_byte_for_byte() {
+        assert_eq!(
+            prepared_password_save_contents(
+                Zeroizing::new("secret\r\nusername:\r\n".to_string()),
+                true,
+                true
+            )
+            .as_str(),
+            "secret\r\nusername:\r\n"
         );
     }
 
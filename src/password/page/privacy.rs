@@ -0,0 +1,95 @@
+use super::state::PasswordPageState;
+use crate::preferences::Preferences;
+use adw::gtk::{CheckButton, Text};
+use adw::prelude::*;
+use adw::{glib, ApplicationWindow};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+// Neither GTK nor the desktop portals let an app opt out of screen capture,
+// so privacy mode keeps secrets out of captures by not drawing them at all
+// while another window has focus.
+const fn password_page_opacity(privacy_mode: bool, window_active: bool) -> f64 {
+    if privacy_mode && !window_active {
+        0.0
+    } else {
+        1.0
+    }
+}
+
+fn sync_password_page_privacy(state: &PasswordPageState, privacy_mode: bool, window_active: bool) {
+    let opacity = password_page_opacity(privacy_mode, window_active);
+    state.page.set_opacity(opacity);
+    state.raw_page.set_opacity(opacity);
+}
+
+// The peek button of the password row only toggles the visibility of its
+// text, so masking the text again also resets the button.
+fn connect_password_brief_reveal(state: &PasswordPageState) {
+    let Some(text) = state.entry.delegate().and_downcast::<Text>() else {
+        return;
+    };
+
+    let pending = Rc::new(RefCell::new(None::<glib::SourceId>));
+    text.connect_visibility_notify(move |text| {
+        if let Some(source) = pending.borrow_mut().take() {
+            source.remove();
+        }
+        let settings = Preferences::new();
+        if !text.visibility() || !settings.reveal_passwords_briefly() {
+            return;
+        }
+        let reveal = Duration::from_secs(settings.password_reveal_seconds().into());
+
+        let text = text.downgrade();
+        let pending_for_timeout = pending.clone();
+        let source = glib::timeout_add_local_once(reveal, move || {
+            pending_for_timeout.borrow_mut().take();
+            if let Some(text) = text.upgrade() {
+                text.set_visibility(false);
+            }
+        });
+        pending.borrow_mut().replace(source);
+    });
+}
+
+pub fn connect_password_page_privacy(
+    window: &ApplicationWindow,
+    state: &PasswordPageState,
+    privacy_mode_check: &CheckButton,
+) {
+    connect_password_brief_reveal(state);
+
+    {
+        let state = state.clone();
+        window.connect_is_active_notify(move |window| {
+            sync_password_page_privacy(
+                &state,
+                Preferences::new().privacy_mode(),
+                window.is_active(),
+            );
+        });
+    }
+
+    // The check is read directly, since it may flip before the setting is
+    // saved. Turning privacy mode off brings a blanked page back right away.
+    let state = state.clone();
+    let window = window.downgrade();
+    privacy_mode_check.connect_active_notify(move |check| {
+        let window_active = window.upgrade().is_none_or(|window| window.is_active());
+        sync_password_page_privacy(&state, check.is_active(), window_active);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::password_page_opacity;
+
+    #[test]
+    fn privacy_mode_blanks_the_entry_page_only_in_the_background() {
+        assert_eq!(password_page_opacity(true, false), 0.0);
+        assert_eq!(password_page_opacity(true, true), 1.0);
+        assert_eq!(password_page_opacity(false, false), 1.0);
+    }
+}
@@ -0,0 +1,63 @@
+use super::{prepare_password_save_context, save_current_password_entry_impl, PasswordPageState};
+use crate::i18n::gettext;
+use crate::password::file::{pass_file_changes, PassFileChange};
+use crate::preferences::Preferences;
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog, PreferencesGroup, ResponseAppearance};
+
+// New items and saves that only rename or reformat an item skip the review.
+fn changes_to_review(state: &PasswordPageState) -> Option<Vec<PassFileChange>> {
+    if state.read_only.get()
+        || !state.saved_entry_exists.get()
+        || !Preferences::new().review_changes_before_save()
+    {
+        return None;
+    }
+
+    let save_context = prepare_password_save_context(state).ok()?;
+    let changes = pass_file_changes(&save_context.previous_contents, &save_context.contents);
+    changes
+        .iter()
+        .any(PassFileChange::is_change)
+        .then_some(changes)
+}
+
+pub(super) fn review_changes_before_save(state: &PasswordPageState) -> bool {
+    let Some(changes) = changes_to_review(state) else {
+        return false;
+    };
+
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Save changes?"))
+        .body(gettext("These parts of the item will be overwritten."))
+        .build();
+    let group = PreferencesGroup::new();
+    for change in &changes {
+        let row = ActionRow::builder()
+            .title(change.title())
+            .subtitle(change.description())
+            .use_markup(false)
+            .build();
+        if !change.is_change() {
+            row.add_css_class("dim-label");
+        }
+        group.add(&row);
+    }
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let save = gettext("Save");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("save", save.as_str())]);
+    dialog.set_response_appearance("save", ResponseAppearance::Suggested);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("save"));
+
+    let state_for_response = state.clone();
+    dialog.connect_response(None, move |_, response| {
+        if response == "save" {
+            save_current_password_entry_impl(&state_for_response, true);
+        }
+    });
+    dialog.present(Some(&state.nav));
+    true
+}
@@ -0,0 +1,169 @@
+use crate::i18n::gettext;
+use crate::support::actions::activate_widget_action;
+use adw::gtk::{Box as GtkBox, Button, Label, MenuButton, Orientation, Popover};
+use adw::prelude::*;
+use adw::PasswordEntryRow;
+use zeroize::Zeroizing;
+
+const SPELL_OUT_GROUP_SIZE: usize = 4;
+
+const NATO_ALPHABET: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+const DIGIT_WORDS: [&str; 10] = [
+    "Zero", "One", "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine",
+];
+
+// Symbol names are read aloud in the listener's language, so they are
+// translated; the NATO words themselves are the same everywhere.
+fn symbol_word(symbol: char) -> Option<&'static str> {
+    Some(match symbol {
+        ' ' => "Space",
+        '!' => "Exclamation mark",
+        '"' => "Double quote",
+        '#' => "Hash",
+        '$' => "Dollar",
+        '%' => "Percent",
+        '&' => "Ampersand",
+        '\'' => "Apostrophe",
+        '(' => "Open parenthesis",
+        ')' => "Close parenthesis",
+        '*' => "Asterisk",
+        '+' => "Plus",
+        ',' => "Comma",
+        '-' => "Dash",
+        '.' => "Dot",
+        '/' => "Slash",
+        ':' => "Colon",
+        ';' => "Semicolon",
+        '<' => "Less than",
+        '=' => "Equals",
+        '>' => "Greater than",
+        '?' => "Question mark",
+        '@' => "At",
+        '[' => "Open bracket",
+        '\\' => "Backslash",
+        ']' => "Close bracket",
+        '^' => "Caret",
+        '_' => "Underscore",
+        '`' => "Backtick",
+        '{' => "Open brace",
+        '|' => "Vertical bar",
+        '}' => "Close brace",
+        '~' => "Tilde",
+        _ => return None,
+    })
+}
+
+// Capital letters are written in capitals, so `aB` reads `alfa-BRAVO`.
+// Anything without a name is shown as it is.
+fn spelled_out_character(ch: char) -> String {
+    if ch.is_ascii_alphabetic() {
+        let word = NATO_ALPHABET[usize::from(ch.to_ascii_lowercase() as u8 - b'a')];
+        return if ch.is_ascii_uppercase() {
+            word.to_uppercase()
+        } else {
+            word.to_lowercase()
+        };
+    }
+    if let Some(digit) = ch.to_digit(10) {
+        return DIGIT_WORDS[digit as usize].to_string();
+    }
+    symbol_word(ch).map_or_else(|| ch.to_string(), gettext)
+}
+
+fn spelled_out_password(password: &str) -> Zeroizing<String> {
+    let characters = password.chars().collect::<Zeroizing<Vec<char>>>();
+    let mut text = Zeroizing::new(String::new());
+    for (index, group) in characters.chunks(SPELL_OUT_GROUP_SIZE).enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        let words = group
+            .iter()
+            .map(|ch| Zeroizing::new(spelled_out_character(*ch)))
+            .collect::<Vec<_>>();
+        text.push_str(&format!("{}.  ", index + 1));
+        for (position, word) in words.iter().enumerate() {
+            if position > 0 {
+                text.push_str(" - ");
+            }
+            text.push_str(word);
+        }
+    }
+    text
+}
+
+// The popover is filled each time it opens and emptied when it closes, so
+// the spelled out password only exists while it is on screen.
+pub fn connect_password_spell_out(entry: &PasswordEntryRow, button: &MenuButton) {
+    let label = Label::new(None);
+    label.set_xalign(0.0);
+    label.add_css_class("monospace");
+
+    let large_type = Button::with_label(&gettext("Show in large type"));
+    large_type.add_css_class("flat");
+
+    let type_password = Button::with_label(&gettext("Type into another window"));
+    type_password.add_css_class("flat");
+
+    let content = GtkBox::new(Orientation::Vertical, 6);
+    content.set_margin_top(6);
+    content.set_margin_bottom(6);
+    content.set_margin_start(6);
+    content.set_margin_end(6);
+    content.append(&label);
+    content.append(&large_type);
+    content.append(&type_password);
+
+    let popover = Popover::new();
+    popover.set_child(Some(&content));
+    let popover_for_large_type = popover.clone();
+    large_type.connect_clicked(move |button| {
+        popover_for_large_type.popdown();
+        activate_widget_action(button, "win.show-large-type");
+    });
+    let popover_for_type_password = popover.clone();
+    type_password.connect_clicked(move |button| {
+        popover_for_type_password.popdown();
+        activate_widget_action(button, "win.type-password");
+    });
+    let label_for_close = label.clone();
+    popover.connect_closed(move |_| label_for_close.set_label(""));
+    button.set_popover(Some(&popover));
+
+    let entry = entry.clone();
+    button.set_create_popup_func(move |_| {
+        let password = Zeroizing::new(entry.text().to_string());
+        if password.is_empty() {
+            label.set_label(&gettext("There is no password to spell out."));
+        } else {
+            label.set_label(&spelled_out_password(&password));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{spelled_out_character, spelled_out_password};
+
+    #[test]
+    fn letters_digits_and_symbols_get_spoken_names() {
+        assert_eq!(spelled_out_character('a'), "alfa");
+        assert_eq!(spelled_out_character('X'), "X-RAY");
+        assert_eq!(spelled_out_character('7'), "Seven");
+        assert_eq!(spelled_out_character('-'), "Dash");
+        assert_eq!(spelled_out_character('é'), "é");
+    }
+
+    #[test]
+    fn passwords_are_spelled_out_in_numbered_groups_of_four() {
+        assert_eq!(
+            spelled_out_password("aB3!xy").as_str(),
+            "1.  alfa - BRAVO - Three - Exclamation mark\n2.  x-ray - yankee"
+        );
+    }
+}
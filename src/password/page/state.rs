@@ -3,13 +3,19 @@ use super::super::file::{
 };
 use super::super::generation::PasswordGenerationControls;
 use super::super::otp::PasswordOtpState;
+use super::alias::sync_password_alias_banner;
 use super::password_open_status_text;
+use crate::support::secrets::SecretTextBuffer;
 use crate::window::navigation::{show_secondary_page_chrome, HasWindowChrome};
 use adw::gtk::{Box as GtkBox, Button, Label, ListBox, Revealer, TextView, ToggleButton};
 use adw::prelude::*;
-use adw::{EntryRow, NavigationPage, PasswordEntryRow, StatusPage, ToastOverlay, WindowTitle};
+use adw::{
+    Banner, ComboRow, EntryRow, NavigationPage, PasswordEntryRow, StatusPage, ToastOverlay,
+    WindowTitle,
+};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use zeroize::Zeroizing;
 
 #[derive(Clone)]
 pub struct PasswordPageState {
@@ -26,8 +32,15 @@ pub struct PasswordPageState {
     pub raw: Button,
     pub win: WindowTitle,
     pub status: StatusPage,
+    pub alias_banner: Banner,
+    pub alias_target: Rc<RefCell<Option<String>>>,
+    pub draft_banner: Banner,
+    pub store_row: ComboRow,
+    pub store_row_roots: Rc<RefCell<Vec<String>>>,
     pub entry: PasswordEntryRow,
     pub password_analysis_label: Label,
+    pub expiry_label: Label,
+    pub dates_label: Label,
     pub username: EntryRow,
     pub otp: PasswordOtpState,
     pub field_add_row: EntryRow,
@@ -35,17 +48,39 @@ pub struct PasswordPageState {
     pub clean_button: Button,
     pub otp_add_button: Button,
     pub import_private_key_button: Button,
+    pub attach_button: Button,
     pub editor_save_button: Button,
     pub generator_settings_button: ToggleButton,
     pub generator_settings_revealer: Revealer,
     pub generator_controls: PasswordGenerationControls,
     pub dynamic_box: GtkBox,
+    pub attachments_box: GtkBox,
+    pub notes_preview_label: Label,
+    pub notes_preview_button: ToggleButton,
+    pub open_website_button: Button,
     pub structured_templates: Rc<RefCell<Vec<StructuredPassLine>>>,
     pub dynamic_rows: Rc<RefCell<Vec<DynamicFieldRow>>>,
     pub text: TextView,
     pub overlay: ToastOverlay,
-    pub saved_contents: Rc<RefCell<String>>,
+    pub saved_contents: Rc<RefCell<Zeroizing<String>>>,
+    pub drafted_contents: Rc<RefCell<Zeroizing<String>>>,
     pub saved_entry_exists: Rc<Cell<bool>>,
+    pub raw_only: Rc<Cell<bool>>,
+    pub read_only: Rc<Cell<bool>>,
+    pub secure_note: Rc<Cell<bool>>,
+    pub close_after_save: Rc<Cell<bool>>,
+}
+
+impl PasswordPageState {
+    pub(super) fn raw_buffer(&self) -> SecretTextBuffer {
+        SecretTextBuffer::new(self.text.buffer())
+    }
+
+    // Secure notes hide the password row, so the row alone can't tell
+    // whether the editor is showing an item.
+    pub(super) fn editor_is_open(&self) -> bool {
+        self.entry.is_visible() || (self.secure_note.get() && !self.status.is_visible())
+    }
 }
 
 pub(super) fn show_password_editor_chrome(state: &PasswordPageState, title: &str, subtitle: &str) {
@@ -60,11 +95,16 @@ fn set_password_editor_action_visibility(
     editor_save_button: bool,
     raw: bool,
 ) {
+    state.store_row.set_visible(false);
     state.field_add_row.set_visible(field_add_row);
+    state.notes_preview_button.set_visible(field_add_row);
     state.template_button.set_visible(false);
     state.clean_button.set_visible(clean_button);
     state.otp_add_button.set_visible(false);
     state.import_private_key_button.set_visible(false);
+    state.open_website_button.set_visible(false);
+    state.expiry_label.set_visible(false);
+    state.attach_button.set_visible(false);
     state.editor_save_button.set_visible(editor_save_button);
     state.raw.set_visible(raw);
 }
@@ -77,6 +117,10 @@ fn hide_password_editor_fields(state: &PasswordPageState) {
     set_password_editor_action_visibility(state, false, false, false, false);
     hide_password_generator_settings(state);
     state.dynamic_box.set_visible(false);
+    state.attachments_box.set_visible(false);
+    state.dates_label.set_visible(false);
+    state.notes_preview_label.set_visible(false);
+    state.draft_banner.set_revealed(false);
 }
 
 pub(super) fn show_password_status_message(
@@ -97,6 +141,7 @@ pub(super) fn show_password_loading_state(
     fido2_recipient_count: usize,
 ) {
     state.username.set_text("");
+    sync_password_alias_banner(state, None);
     show_password_editor_chrome(state, title, subtitle);
     let (status_title, status_description) = password_open_status_text(fido2_recipient_count);
     show_password_status_message(state, status_title, status_description);
@@ -104,11 +149,44 @@ pub(super) fn show_password_loading_state(
 
 pub(super) fn show_password_editor_fields(state: &PasswordPageState) {
     state.status.set_visible(false);
-    state.entry.set_visible(true);
+    state.entry.set_visible(!state.secure_note.get());
     set_password_editor_action_visibility(state, true, true, true, true);
     hide_password_generator_settings(state);
 }
 
+pub(super) fn sync_secure_note_editor(state: &PasswordPageState, secure_note: bool) {
+    state.secure_note.set(secure_note);
+    if !state.status.is_visible() {
+        state.entry.set_visible(!secure_note);
+    }
+    if secure_note {
+        state.password_analysis_label.set_visible(false);
+        hide_password_generator_settings(state);
+    }
+}
+
+pub(super) fn sync_raw_only_password_editor(state: &PasswordPageState, raw_only: bool) {
+    state.raw_only.set(raw_only);
+    state.entry.set_sensitive(!raw_only);
+    state.username.set_sensitive(!raw_only);
+    state.dynamic_box.set_sensitive(!raw_only);
+    state.field_add_row.set_sensitive(!raw_only);
+    state.otp.row.set_sensitive(!raw_only);
+}
+
+pub(super) fn sync_read_only_password_editor(state: &PasswordPageState, read_only: bool) {
+    state.read_only.set(read_only);
+    state.entry.set_editable(!read_only);
+    state.username.set_editable(!read_only);
+    state.text.set_editable(!read_only);
+    if read_only {
+        state.field_add_row.set_visible(false);
+        state.clean_button.set_visible(false);
+        state.editor_save_button.set_visible(false);
+        state.attach_button.set_visible(false);
+    }
+}
+
 pub(super) fn reset_password_editor(state: &PasswordPageState) {
     state.entry.set_text("");
     state.password_analysis_label.set_visible(false);
@@ -119,11 +197,23 @@ pub(super) fn reset_password_editor(state: &PasswordPageState) {
     hide_password_generator_settings(state);
     clear_box_children(&state.dynamic_box);
     state.dynamic_box.set_visible(false);
+    clear_box_children(&state.attachments_box);
+    state.attachments_box.set_visible(false);
+    state.dates_label.set_visible(false);
+    state.notes_preview_label.set_text("");
+    state.notes_preview_label.set_visible(false);
     state.structured_templates.borrow_mut().clear();
     state.dynamic_rows.borrow_mut().clear();
-    state.text.buffer().set_text("");
+    state.raw_buffer().clear();
     state.saved_contents.borrow_mut().clear();
+    state.drafted_contents.borrow_mut().clear();
+    state.close_after_save.set(false);
+    state.draft_banner.set_revealed(false);
+    sync_password_alias_banner(state, None);
     state.saved_entry_exists.set(false);
+    sync_raw_only_password_editor(state, false);
+    sync_read_only_password_editor(state, false);
+    state.secure_note.set(false);
 }
 
 fn hide_password_generator_settings(state: &PasswordPageState) {
@@ -136,6 +226,10 @@ pub(super) fn sync_saved_password_state(
     contents: &str,
     entry_exists: bool,
 ) {
-    *state.saved_contents.borrow_mut() = contents.to_string();
+    *state.saved_contents.borrow_mut() = Zeroizing::new(contents.to_string());
+    *state.drafted_contents.borrow_mut() = Zeroizing::new(contents.to_string());
     state.saved_entry_exists.set(entry_exists);
+    if entry_exists {
+        state.store_row.set_visible(false);
+    }
 }
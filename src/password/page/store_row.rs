@@ -0,0 +1,57 @@
+use super::super::new_item::available_store_roots;
+use super::PasswordPageState;
+use crate::password::opened::{get_opened_pass_file, set_opened_pass_file};
+use crate::preferences::Preferences;
+use crate::store::labels::StoreLabels;
+use adw::gtk::StringList;
+use adw::prelude::*;
+
+// A new item can still move to another store until its first save, so the
+// editor keeps the choice from the add dialog and lets the user change it.
+pub(super) fn sync_new_item_store_row(state: &PasswordPageState, store_root: &str) {
+    let stores = available_store_roots();
+    let labels = StoreLabels::for_stores(&Preferences::new(), &stores);
+    let label_texts = stores
+        .iter()
+        .map(|store| labels.label(store))
+        .collect::<Vec<_>>();
+    let label_refs = label_texts.iter().map(String::as_str).collect::<Vec<_>>();
+    let position = stores.iter().position(|store| store == store_root);
+
+    state.store_row_roots.borrow_mut().clone_from(&stores);
+    state
+        .store_row
+        .set_model(Some(&StringList::new(&label_refs)));
+    if let Some(position) = position.and_then(|position| u32::try_from(position).ok()) {
+        state.store_row.set_selected(position);
+    }
+    state
+        .store_row
+        .set_visible(stores.len() > 1 && position.is_some());
+}
+
+pub fn connect_password_store_row(state: &PasswordPageState) {
+    let state_for_notify = state.clone();
+    state.store_row.connect_selected_notify(move |row| {
+        let state = &state_for_notify;
+        if !row.is_visible() || state.saved_entry_exists.get() {
+            return;
+        }
+        let Some(store_root) = state
+            .store_row_roots
+            .borrow()
+            .get(row.selected() as usize)
+            .cloned()
+        else {
+            return;
+        };
+        let Some(mut pass_file) = get_opened_pass_file(&state.nav) else {
+            return;
+        };
+        if pass_file.entry.store_path == store_root {
+            return;
+        }
+        pass_file.entry.store_path = store_root;
+        set_opened_pass_file(&state.nav, pass_file);
+    });
+}
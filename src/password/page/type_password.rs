@@ -0,0 +1,41 @@
+use super::large_type::current_item_password;
+use super::state::PasswordPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::support::remote_desktop::{keysym_for_char, start_remote_keyboard, RemoteDesktopError};
+use adw::glib;
+use adw::prelude::*;
+use adw::{ApplicationWindow, Toast};
+use std::time::Duration;
+use zeroize::Zeroizing;
+
+// Long enough for the window behind Keycord to take focus back after it
+// is minimized.
+const TYPE_PASSWORD_DELAY: Duration = Duration::from_millis(750);
+
+// The desktop asks for permission each time, and the password goes to
+// whatever had focus before Keycord, without touching the clipboard.
+pub fn type_current_password(state: &PasswordPageState, window: &ApplicationWindow) {
+    let Some(password) = current_item_password(state) else {
+        return;
+    };
+    let keysyms = Zeroizing::new(password.chars().map(keysym_for_char).collect::<Vec<_>>());
+
+    let window = window.clone();
+    let overlay = state.overlay.clone();
+    start_remote_keyboard(move |result| match result {
+        Ok(keyboard) => {
+            window.minimize();
+            glib::timeout_add_local_once(TYPE_PASSWORD_DELAY, move || {
+                keyboard.type_keysyms(&keysyms);
+            });
+        }
+        Err(RemoteDesktopError::Cancelled) => {}
+        Err(RemoteDesktopError::Failed(err)) => {
+            log_error(format!("Failed to type the password: {err}"));
+            overlay.add_toast(Toast::new(&gettext(
+                "Couldn't type the password. The desktop doesn't allow typing for apps.",
+            )));
+        }
+    });
+}
@@ -0,0 +1,122 @@
+use crate::i18n::gettext;
+use crate::preferences::Preferences;
+use adw::gtk::Widget;
+use adw::prelude::*;
+use adw::{AlertDialog, EntryRow, PreferencesGroup, ResponseAppearance};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtectedChange {
+    Save,
+    Move,
+    Delete,
+}
+
+impl ProtectedChange {
+    const fn heading(self) -> &'static str {
+        match self {
+            Self::Save => "Save protected item?",
+            Self::Move => "Move protected item?",
+            Self::Delete => "Delete protected item?",
+        }
+    }
+
+    const fn response(self) -> &'static str {
+        match self {
+            Self::Save => "Save",
+            Self::Move => "Move",
+            Self::Delete => "Delete",
+        }
+    }
+}
+
+fn label_in_folders(label: &str, folders: &[String]) -> bool {
+    folders.iter().any(|folder| {
+        label
+            .strip_prefix(folder.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+    })
+}
+
+pub fn entry_is_protected(label: &str) -> bool {
+    label_in_folders(label, &Preferences::new().protected_folders())
+}
+
+// Like deleting a repository on a forge, the item's full name has to be
+// typed, so a stray click or key press can't change it.
+fn present_protected_entry_dialog(
+    parent: &impl IsA<Widget>,
+    label: &str,
+    change: ProtectedChange,
+    confirm: impl Fn() + 'static,
+) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext(change.heading()))
+        .body(
+            gettext("{label} is in a protected folder. Type its name to continue.")
+                .replace("{label}", label),
+        )
+        .build();
+    let name_row = EntryRow::new();
+    name_row.set_title(&gettext("Item name"));
+    let group = PreferencesGroup::new();
+    group.add(&name_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let response = gettext(change.response());
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("confirm", response.as_str())]);
+    dialog.set_response_appearance(
+        "confirm",
+        if change == ProtectedChange::Delete {
+            ResponseAppearance::Destructive
+        } else {
+            ResponseAppearance::Suggested
+        },
+    );
+    dialog.set_response_enabled("confirm", false);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("confirm"));
+    {
+        let dialog = dialog.clone();
+        let label = label.to_string();
+        name_row.connect_changed(move |row| {
+            dialog.set_response_enabled("confirm", row.text().trim() == label);
+        });
+    }
+
+    dialog.connect_response(None, move |_, response| {
+        if response == "confirm" {
+            confirm();
+        }
+    });
+    dialog.present(Some(parent));
+}
+
+// Runs the change right away unless the item is in a protected folder.
+pub fn confirm_protected_entry_change(
+    parent: &impl IsA<Widget>,
+    label: &str,
+    change: ProtectedChange,
+    confirm: impl Fn() + 'static,
+) {
+    if entry_is_protected(label) {
+        present_protected_entry_dialog(parent, label, change, confirm);
+    } else {
+        confirm();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::label_in_folders;
+
+    #[test]
+    fn only_items_below_a_protected_folder_are_protected() {
+        let folders = vec!["shared/prod".to_string()];
+        assert!(label_in_folders("shared/prod/database", &folders));
+        assert!(label_in_folders("shared/prod/eu/vpn", &folders));
+        assert!(!label_in_folders("shared/production/database", &folders));
+        assert!(!label_in_folders("shared/prod", &folders));
+        assert!(!label_in_folders("shared/dev/database", &folders));
+    }
+}
@@ -1,4 +1,6 @@
 use crate::i18n::gettext;
+use crate::preferences::Preferences;
+use std::sync::Mutex;
 
 const COMMON_WEAK_PASSWORDS: &[&str] = &[
     "000000",
@@ -20,6 +22,103 @@ const COMMON_WEAK_PASSWORDS: &[&str] = &[
     "welcome",
 ];
 
+// Shorter words would match by chance inside unrelated passwords.
+const MIN_DICTIONARY_WORD_LENGTH: usize = 4;
+// A password that is a dictionary word plus a few digits or symbols is as
+// easy to guess as the word, so at least this much must be left over.
+const MIN_CHARACTERS_BESIDES_WORD: usize = 8;
+pub const CUSTOM_STRENGTH_DICTIONARY: &str = "custom";
+pub const ITEM_STRENGTH_DICTIONARY: &str = "items";
+
+struct StrengthDictionary {
+    name: String,
+    words: Vec<String>,
+}
+
+// Checks run on worker threads, so the registered dictionaries are shared
+// behind a lock instead of read from the preferences each time.
+static STRENGTH_DICTIONARIES: Mutex<Vec<StrengthDictionary>> = Mutex::new(Vec::new());
+
+// Common letter swaps are undone, so `C0mp@ny` is caught as well as `Company`.
+// Every character maps to one character, so lengths stay comparable.
+fn folded_for_dictionary(text: &str) -> String {
+    text.chars()
+        .flat_map(char::to_lowercase)
+        .map(|ch| match ch {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            other => other,
+        })
+        .collect()
+}
+
+fn strength_dictionary_words(words: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut words = words
+        .into_iter()
+        .map(|word| folded_for_dictionary(word.trim()))
+        .filter(|word| word.chars().count() >= MIN_DICTIONARY_WORD_LENGTH)
+        .collect::<Vec<_>>();
+    words.sort();
+    words.dedup();
+    words
+}
+
+// Registering a dictionary under a name that is already in use replaces it,
+// and registering no words removes it.
+pub fn register_strength_dictionary(name: &str, words: impl IntoIterator<Item = String>) {
+    let words = strength_dictionary_words(words);
+    let mut dictionaries = STRENGTH_DICTIONARIES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    dictionaries.retain(|dictionary| dictionary.name != name);
+    if !words.is_empty() {
+        dictionaries.push(StrengthDictionary {
+            name: name.to_string(),
+            words,
+        });
+    }
+}
+
+// Folder names, site names, and usernames or email addresses used as item
+// names are the words someone is most likely to build a password from.
+pub fn item_strength_words<'a>(labels: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    labels
+        .into_iter()
+        .flat_map(|label| label.split(['/', '@', '.', '-', '_', ' ']))
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn sync_strength_dictionaries(settings: &Preferences) {
+    if settings.personal_strength_words() {
+        register_strength_dictionary(CUSTOM_STRENGTH_DICTIONARY, settings.strength_words());
+    } else {
+        register_strength_dictionary(CUSTOM_STRENGTH_DICTIONARY, Vec::new());
+        register_strength_dictionary(ITEM_STRENGTH_DICTIONARY, Vec::new());
+    }
+}
+
+fn built_on_word<'a>(folded: &str, words: impl IntoIterator<Item = &'a String>) -> bool {
+    let length = folded.chars().count();
+    words.into_iter().any(|word| {
+        folded.contains(word.as_str())
+            && length.saturating_sub(word.chars().count()) < MIN_CHARACTERS_BESIDES_WORD
+    })
+}
+
+fn built_on_dictionary_word(password: &str) -> bool {
+    let folded = folded_for_dictionary(password.trim());
+    STRENGTH_DICTIONARIES
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .any(|dictionary| built_on_word(&folded, &dictionary.words))
+}
+
 pub fn weak_password_reason(password: &str) -> Option<String> {
     if password.is_empty() {
         return Some(gettext("Password is empty"));
@@ -32,6 +131,9 @@ pub fn weak_password_reason(password: &str) -> Option<String> {
     if COMMON_WEAK_PASSWORDS.contains(&normalized.as_str()) {
         return Some(gettext("Matches a common password"));
     }
+    if built_on_dictionary_word(password) {
+        return Some(gettext("Built on one of your own words"));
+    }
     if repeated_single_character(password) {
         return Some(gettext("Repeated single character"));
     }
@@ -118,7 +220,10 @@ fn unique_char_count(password: &str) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use super::weak_password_reason;
+    use super::{
+        built_on_word, folded_for_dictionary, item_strength_words, strength_dictionary_words,
+        weak_password_reason,
+    };
     use crate::i18n::gettext;
 
     #[test]
@@ -146,4 +251,35 @@ mod tests {
         assert_eq!(weak_password_reason("correct horse battery staple"), None);
         assert_eq!(weak_password_reason("Aq7!mB9#zR4@tN2$"), None);
     }
+
+    #[test]
+    fn passwords_built_on_a_dictionary_word_are_weak() {
+        let words = strength_dictionary_words(["CompanyName".to_string(), "abc".to_string()]);
+        assert_eq!(words, vec!["companyname".to_string()]);
+
+        assert!(built_on_word(
+            &folded_for_dictionary("CompanyName2024!"),
+            &words
+        ));
+        assert!(built_on_word(
+            &folded_for_dictionary("C0mp@nyN4me#1"),
+            &words
+        ));
+        assert!(!built_on_word(
+            &folded_for_dictionary("companyname-correct-horse-battery"),
+            &words
+        ));
+        assert!(!built_on_word(
+            &folded_for_dictionary("Aq7!mB9#zR4@tN2$"),
+            &words
+        ));
+    }
+
+    #[test]
+    fn item_names_are_split_into_words() {
+        assert_eq!(
+            strength_dictionary_words(item_strength_words(["sites/github.com/alice@example.org"])),
+            vec!["alice", "example", "github", "sites"]
+        );
+    }
 }
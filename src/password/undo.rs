@@ -1,6 +1,7 @@
 use crate::backend::{
-    delete_password_entry, password_entry_fido2_recipient_count, read_password_entry,
-    rename_password_entry, save_password_entry, PasswordEntryError, PasswordEntryWriteError,
+    delete_password_entry, list_password_attachments, password_entry_fido2_recipient_count,
+    read_password_attachment, read_password_entry, rename_password_entry, save_password_attachment,
+    save_password_entry, PasswordEntryError, PasswordEntryWriteError,
 };
 use crate::password::model::PassEntry;
 use crate::window::session::window_session_for_widget;
@@ -8,6 +9,7 @@ use crate::window::session::window_session_for_widget;
 use crate::window::session::WindowSessionState;
 use adw::gtk::Widget;
 use adw::prelude::*;
+use zeroize::Zeroizing;
 
 const UNAVAILABLE_UNDO_MESSAGE: &str = "Can't undo that change.";
 
@@ -182,8 +184,11 @@ pub fn delete_entry_with_optional_undo(entry: &PassEntry) -> Result<Option<UndoA
     }
 }
 
+// Deleting an item also deletes its attachments, and the undo snapshot only
+// keeps the item itself.
 fn should_delete_without_undo_snapshot(entry: &PassEntry) -> bool {
     password_entry_fido2_recipient_count(&entry.store_path, &entry.label()) > 0
+        || !list_password_attachments(&entry.store_path, &entry.label()).is_empty()
 }
 
 fn can_delete_without_undo_after_read_error(error: &PasswordEntryError) -> bool {
@@ -271,6 +276,15 @@ fn restore_saved_entry(
             .map_err(UndoError::Write);
     }
 
+    // Renaming back keeps the attachments, which deleting the renamed item
+    // would take with it.
+    if previous_store == current_store {
+        rename_password_entry(current_store, current_label, previous_label)
+            .map_err(UndoError::Rename)?;
+        return save_password_entry(previous_store, previous_label, previous_contents, true)
+            .map_err(UndoError::Write);
+    }
+
     save_password_entry(previous_store, previous_label, previous_contents, false)
         .map_err(UndoError::Write)?;
 
@@ -294,6 +308,15 @@ fn move_entry_between_stores(
 ) -> Result<(), UndoError> {
     let contents = read_password_entry(source_store, label).map_err(UndoError::Read)?;
     save_password_entry(target_store, label, &contents, false).map_err(UndoError::Write)?;
+    if let Err(copy_error) = copy_password_attachments(source_store, target_store, label) {
+        if let Err(rollback_error) = delete_password_entry(target_store, label) {
+            return Err(UndoError::Rollback {
+                action_error: copy_error,
+                rollback_error,
+            });
+        }
+        return Err(UndoError::Write(copy_error));
+    }
 
     if let Err(delete_error) = delete_password_entry(source_store, label) {
         if let Err(rollback_error) = delete_password_entry(target_store, label) {
@@ -308,6 +331,19 @@ fn move_entry_between_stores(
     Ok(())
 }
 
+// Each attachment is encrypted again for the target store's recipients.
+fn copy_password_attachments(
+    source_store: &str,
+    target_store: &str,
+    label: &str,
+) -> Result<(), PasswordEntryWriteError> {
+    for name in list_password_attachments(source_store, label) {
+        let bytes = Zeroizing::new(read_password_attachment(source_store, label, &name)?);
+        save_password_attachment(target_store, label, &name, &bytes)?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
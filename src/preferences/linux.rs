@@ -63,7 +63,14 @@ impl Preferences {
 
     pub fn command_with_envs(&self, envs: &[(&str, &str)]) -> Command {
         let (program, args) = split_command_line(&self.command_value());
-        build_command(program, args, envs)
+        let mut cmd = build_command(program, args, envs);
+        if let Some((name, email)) = self.git_identity() {
+            for role in ["AUTHOR", "COMMITTER"] {
+                cmd.env(format!("GIT_{role}_NAME"), &name)
+                    .env(format!("GIT_{role}_EMAIL"), &email);
+            }
+        }
+        cmd
     }
 
     pub fn host_program_command(&self, program: &str, args: &[&str]) -> Command {
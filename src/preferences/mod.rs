@@ -3,24 +3,94 @@ use adw::gio::{self, prelude::*, Settings};
 use adw::glib::BoolError;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 mod command_backend;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(not(target_os = "linux"))]
 mod non_linux;
+mod profiles;
 mod restricted;
 mod storage;
+mod transfer;
 
+pub use self::profiles::WorkspaceProfile;
 use self::restricted::default_store_dirs;
 use self::storage::{load_file_prefs, save_file_prefs, PreferenceFile};
+pub use self::transfer::{
+    parse_settings_export, settings_export_toml, SettingsExport, SettingsImportError,
+    SettingsImportSummary, SETTINGS_EXPORT_FILE_NAME,
+};
+use crate::support::portable::{is_portable, portable_relative_path, resolve_portable_path};
 use crate::support::runtime::supports_host_command_features;
 
 const DEFAULT_NEW_PASS_FILE_TEMPLATE: &str = "username:\nemail:\nurl:";
+const DEFAULT_NEW_ITEM_WEBSITE_FOLDER: &str = "web";
 const DEFAULT_WINDOW_WIDTH: i32 = 850;
 const DEFAULT_WINDOW_HEIGHT: i32 = 600;
+pub const MAX_CLIPBOARD_CLEAR_SECONDS: u32 = 600;
+pub const MAX_AUTO_LOCK_MINUTES: u32 = 240;
+pub const MAX_PASSWORD_REVEAL_SECONDS: u32 = 300;
+pub const MAX_LARGE_FILE_LIMIT_MIB: u32 = 1024;
 const APP_ID: &str = env!("APP_ID");
 
+// Stores named by `PASSWORD_STORE_DIR` or `--store` are listed after the
+// configured ones for as long as Keycord runs, and are never saved.
+static SESSION_STORE_DIRS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn session_store_dirs() -> Vec<String> {
+    SESSION_STORE_DIRS
+        .lock()
+        .map(|stores| stores.clone())
+        .unwrap_or_default()
+}
+
+fn same_store_dir(left: &str, right: &str) -> bool {
+    let left = Preferences::expand_path(left);
+    let right = Preferences::expand_path(right);
+    left.trim_end_matches('/') == right.trim_end_matches('/')
+}
+
+// Returns whether the store is new to this session.
+pub fn add_session_store(store: &str) -> bool {
+    let store = store.trim();
+    if store.is_empty() {
+        return false;
+    }
+    let Ok(mut stores) = SESSION_STORE_DIRS.lock() else {
+        return false;
+    };
+    if stores.iter().any(|known| same_store_dir(known, store)) {
+        return false;
+    }
+    stores.push(store.to_string());
+    true
+}
+
+fn with_session_stores(mut stores: Vec<String>, session: &[String]) -> Vec<String> {
+    for store in session {
+        if !stores.iter().any(|known| same_store_dir(known, store)) {
+            stores.push(store.clone());
+        }
+    }
+    stores
+}
+
+fn without_session_stores(
+    stores: Vec<String>,
+    configured: &[String],
+    session: &[String],
+) -> Vec<String> {
+    stores
+        .into_iter()
+        .filter(|store| {
+            !session.iter().any(|extra| same_store_dir(extra, store))
+                || configured.iter().any(|known| same_store_dir(known, store))
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum BackendKind {
@@ -96,6 +166,7 @@ pub enum PasswordListSortMode {
     Filename,
     #[default]
     StorePath,
+    TopFolder,
 }
 
 impl PasswordListSortMode {
@@ -103,6 +174,7 @@ impl PasswordListSortMode {
         match self {
             Self::Filename => "filename",
             Self::StorePath => "store-path",
+            Self::TopFolder => "top-folder",
         }
     }
 
@@ -110,11 +182,114 @@ impl PasswordListSortMode {
         match value.trim().to_ascii_lowercase().as_str() {
             "filename" | "file" | "name" => Self::Filename,
             "store-path" | "store" | "path" | "folder" | "folders" => Self::StorePath,
+            "top-folder" | "grouped" | "sections" => Self::TopFolder,
             _ => Self::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PasswordListDensity {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl PasswordListDensity {
+    pub const fn stored_value(self) -> &'static str {
+        match self {
+            Self::Comfortable => "comfortable",
+            Self::Compact => "compact",
+        }
+    }
+
+    pub fn from_stored(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "compact" | "dense" => Self::Compact,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PasswordListActivation {
+    #[default]
+    Open,
+    CopyPassword,
+    CopyOtp,
+}
+
+impl PasswordListActivation {
+    pub const fn stored_value(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::CopyPassword => "copy-password",
+            Self::CopyOtp => "copy-otp",
+        }
+    }
+
+    pub fn from_stored(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "copy-password" | "password" | "copy" => Self::CopyPassword,
+            "copy-otp" | "otp" => Self::CopyOtp,
+            _ => Self::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoreWebDavFolder {
+    pub store: String,
+    pub url: String,
+    #[serde(default)]
+    pub login_entry: String,
+}
+
+impl StoreWebDavFolder {
+    fn from_stored((store, url, login_entry): (String, String, String)) -> Self {
+        Self {
+            store,
+            url,
+            login_entry,
+        }
+    }
+
+    fn stored_value(&self) -> (String, String, String) {
+        (
+            self.store.clone(),
+            self.url.clone(),
+            self.login_entry.clone(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct StoreAppearance {
+    pub store: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(default)]
+    pub color: String,
+}
+
+impl StoreAppearance {
+    fn from_stored((store, label, color): (String, String, String)) -> Self {
+        Self {
+            store,
+            label,
+            color,
+        }
+    }
+
+    fn stored_value(&self) -> (String, String, String) {
+        (self.store.clone(), self.label.clone(), self.color.clone())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Preferences {
     settings: Option<Settings>,
@@ -152,7 +327,39 @@ impl Preferences {
         )
     }
 
+    // The writes land together or not at all. GSettings holds them back until
+    // every one succeeded, and the settings file is put back on a failure.
+    fn write_preferences_together(
+        &self,
+        write: impl FnOnce() -> Result<(), BoolError>,
+    ) -> Result<(), BoolError> {
+        let Some(settings) = self.settings.as_ref() else {
+            let before = load_file_prefs();
+            return write().inspect_err(|_| {
+                let _ = save_file_prefs(&before);
+            });
+        };
+        settings.delay();
+        let result = write();
+        if result.is_ok() {
+            settings.apply();
+        } else {
+            settings.revert();
+        }
+        result
+    }
+
+    // Without the schema, settings go to a file only Keycord reads.
+    pub fn uses_settings_file(&self) -> bool {
+        self.settings.is_none()
+    }
+
+    // GSettings live on the machine, so a portable copy keeps its settings
+    // in the file on the drive.
     fn try_settings() -> Option<Settings> {
+        if is_portable() {
+            return None;
+        }
         let source = gio::SettingsSchemaSource::default()?;
         let _schema = source.lookup(APP_ID, true)?;
         Some(Settings::new(APP_ID))
@@ -177,8 +384,107 @@ impl Preferences {
         notices
     }
 
+    fn normalized_strength_words(words: Vec<String>) -> Vec<String> {
+        let mut words = words
+            .into_iter()
+            .map(|word| word.trim().to_string())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+        words.dedup();
+        words
+    }
+
+    // Folders are matched as item label prefixes, so slashes around them and
+    // empty names are dropped.
+    fn normalized_protected_folders(folders: Vec<String>) -> Vec<String> {
+        let mut folders = folders
+            .into_iter()
+            .map(|folder| folder.trim().trim_matches('/').to_string())
+            .filter(|folder| !folder.is_empty())
+            .collect::<Vec<_>>();
+        folders.sort();
+        folders.dedup();
+        folders
+    }
+
+    fn parsed_shortcut_overrides(entries: Vec<String>) -> Vec<(String, String)> {
+        let mut overrides: Vec<(String, String)> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let Some((action, accel)) = entry.split_once('=') else {
+                continue;
+            };
+            let (action, accel) = (action.trim(), accel.trim());
+            if action.is_empty() || accel.is_empty() {
+                continue;
+            }
+            overrides.retain(|(existing, _)| existing != action);
+            overrides.push((action.to_string(), accel.to_string()));
+        }
+        overrides
+    }
+
+    fn normalized_store_webdav_folders(folders: Vec<StoreWebDavFolder>) -> Vec<StoreWebDavFolder> {
+        let mut normalized: Vec<StoreWebDavFolder> = Vec::with_capacity(folders.len());
+        for folder in folders {
+            let folder = StoreWebDavFolder {
+                store: folder.store.trim().to_string(),
+                url: folder.url.trim().to_string(),
+                login_entry: folder.login_entry.trim().to_string(),
+            };
+            if folder.store.is_empty() || folder.url.is_empty() {
+                continue;
+            }
+            normalized.retain(|existing| !Self::same_store(&existing.store, &folder.store));
+            normalized.push(folder);
+        }
+        normalized
+    }
+
+    fn normalized_store_appearances(appearances: Vec<StoreAppearance>) -> Vec<StoreAppearance> {
+        let mut normalized: Vec<StoreAppearance> = Vec::with_capacity(appearances.len());
+        for appearance in appearances {
+            let appearance = StoreAppearance {
+                store: appearance.store.trim().to_string(),
+                label: appearance.label.trim().to_string(),
+                color: appearance.color.trim().to_ascii_lowercase(),
+            };
+            if appearance.store.is_empty()
+                || (appearance.label.is_empty() && appearance.color.is_empty())
+            {
+                continue;
+            }
+            normalized.retain(|existing| !Self::same_store(&existing.store, &appearance.store));
+            normalized.push(appearance);
+        }
+        normalized
+    }
+
+    fn normalized_read_only_stores(stores: Vec<String>) -> Vec<String> {
+        let mut normalized: Vec<String> = Vec::with_capacity(stores.len());
+        for store in stores {
+            let store = store.trim().to_string();
+            if store.is_empty() {
+                continue;
+            }
+            normalized.retain(|existing| !Self::same_store(existing, &store));
+            normalized.push(store);
+        }
+        normalized
+    }
+
+    fn same_store(left: &str, right: &str) -> bool {
+        Self::expand_path(left).trim_end_matches('/')
+            == Self::expand_path(right).trim_end_matches('/')
+    }
+
+    // Relative stores only exist in portable mode, and the rest of Keycord
+    // only ever sees them resolved against the portable folder.
     fn resolved_store_dirs(stores: Option<Vec<String>>) -> Vec<String> {
-        stores.unwrap_or_else(default_store_dirs)
+        stores
+            .unwrap_or_else(default_store_dirs)
+            .into_iter()
+            .map(|store| resolve_portable_path(&store))
+            .collect()
     }
 
     pub fn store_roots(&self) -> Vec<String> {
@@ -226,6 +532,24 @@ impl Preferences {
         )
     }
 
+    pub fn new_item_website_folder(&self) -> String {
+        self.read_preference(
+            |settings| settings.string("new-item-website-folder").to_string(),
+            |cfg| {
+                cfg.new_item_website_folder
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_NEW_ITEM_WEBSITE_FOLDER.to_string())
+            },
+        )
+    }
+
+    pub fn fill_fields_from_path(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("fill-fields-from-path"),
+            |cfg| cfg.fill_fields_from_path.unwrap_or(true),
+        )
+    }
+
     pub fn clear_empty_fields_before_save(&self) -> bool {
         self.read_preference(
             |settings| settings.boolean("clear-empty-fields-before-save"),
@@ -233,6 +557,169 @@ impl Preferences {
         )
     }
 
+    pub fn window_is_maximized(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("window-maximized"),
+            |cfg| cfg.window_maximized.unwrap_or(false),
+        )
+    }
+
+    pub fn search_visible(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("search-visible"),
+            |cfg| cfg.search_visible.unwrap_or(false),
+        )
+    }
+
+    pub fn privacy_mode(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("privacy-mode"),
+            |cfg| cfg.privacy_mode.unwrap_or(false),
+        )
+    }
+
+    pub fn reveal_passwords_briefly(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("reveal-passwords-briefly"),
+            |cfg| cfg.reveal_passwords_briefly.unwrap_or(false),
+        )
+    }
+
+    pub fn password_reveal_seconds(&self) -> u32 {
+        self.read_preference(
+            |settings| settings.uint("password-reveal-seconds"),
+            |cfg| cfg.password_reveal_seconds.unwrap_or(10),
+        )
+        .clamp(1, MAX_PASSWORD_REVEAL_SECONDS)
+    }
+
+    // The timeouts below apply while no workspace profile is active; see
+    // `clipboard_clear_seconds` and `auto_lock_minutes` for the ones in use.
+    pub fn default_clipboard_clear_seconds(&self) -> u32 {
+        self.read_preference(
+            |settings| settings.uint("clipboard-clear-seconds"),
+            |cfg| cfg.clipboard_clear_seconds.unwrap_or(45),
+        )
+        .min(MAX_CLIPBOARD_CLEAR_SECONDS)
+    }
+
+    pub fn default_auto_lock_minutes(&self) -> u32 {
+        self.read_preference(
+            |settings| settings.uint("auto-lock-minutes"),
+            |cfg| cfg.auto_lock_minutes.unwrap_or(0),
+        )
+        .min(MAX_AUTO_LOCK_MINUTES)
+    }
+
+    pub fn search_item_contents(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("search-item-contents"),
+            |cfg| cfg.search_item_contents.unwrap_or(false),
+        )
+    }
+
+    pub fn activity_log_enabled(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("activity-log"),
+            |cfg| cfg.activity_log.unwrap_or(true),
+        )
+    }
+
+    pub fn remember_key_passwords(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("remember-key-passwords"),
+            |cfg| cfg.remember_key_passwords.unwrap_or(true),
+        )
+    }
+
+    pub fn app_pin_hash(&self) -> Option<String> {
+        let hash = self.read_preference(
+            |settings| settings.string("app-pin-hash").to_string(),
+            |cfg| cfg.app_pin_hash.clone().unwrap_or_default(),
+        );
+        Some(hash).filter(|hash| !hash.is_empty())
+    }
+
+    pub fn has_app_pin(&self) -> bool {
+        self.app_pin_hash().is_some()
+    }
+
+    pub fn copy_password_on_open_website(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("copy-password-on-open-website"),
+            |cfg| cfg.copy_password_on_open_website.unwrap_or(false),
+        )
+    }
+
+    pub fn expiry_reminders(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("expiry-reminders"),
+            |cfg| cfg.expiry_reminders.unwrap_or(false),
+        )
+    }
+
+    pub fn git_gpg_diffs(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("git-gpg-diffs"),
+            |cfg| cfg.git_gpg_diffs.unwrap_or(false),
+        )
+    }
+
+    pub fn large_file_limit_mib(&self) -> u32 {
+        self.read_preference(
+            |settings| settings.uint("large-file-limit-mib"),
+            |cfg| cfg.large_file_limit_mib.unwrap_or(5),
+        )
+        .min(MAX_LARGE_FILE_LIMIT_MIB)
+    }
+
+    pub fn personal_strength_words(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("personal-strength-words"),
+            |cfg| cfg.personal_strength_words.unwrap_or(true),
+        )
+    }
+
+    pub fn strength_words(&self) -> Vec<String> {
+        Self::normalized_strength_words(self.read_preference(
+            |settings| {
+                settings
+                    .strv("strength-words")
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            },
+            |cfg| cfg.strength_words.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn protected_folders(&self) -> Vec<String> {
+        Self::normalized_protected_folders(self.read_preference(
+            |settings| {
+                settings
+                    .strv("protected-folders")
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            },
+            |cfg| cfg.protected_folders.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn refuse_large_files(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("refuse-large-files"),
+            |cfg| cfg.refuse_large_files.unwrap_or(false),
+        )
+    }
+
+    pub fn review_changes_before_save(&self) -> bool {
+        self.read_preference(
+            |settings| settings.boolean("review-changes-before-save"),
+            |cfg| cfg.review_changes_before_save.unwrap_or(true),
+        )
+    }
+
     pub fn password_generation_settings(&self) -> PasswordGenerationSettings {
         self.read_preference(
             |settings| {
@@ -272,7 +759,28 @@ impl Preferences {
         )
     }
 
+    pub fn password_list_density(&self) -> PasswordListDensity {
+        self.read_preference(
+            |settings| PasswordListDensity::from_stored(&settings.string("password-list-density")),
+            |cfg| cfg.password_list_density.unwrap_or_default(),
+        )
+    }
+
+    pub fn password_list_activation(&self) -> PasswordListActivation {
+        self.read_preference(
+            |settings| {
+                PasswordListActivation::from_stored(&settings.string("password-list-activation"))
+            },
+            |cfg| cfg.password_list_activation.unwrap_or_default(),
+        )
+    }
+
     pub fn stores(&self) -> Vec<String> {
+        with_session_stores(self.configured_stores(), &session_store_dirs())
+    }
+
+    // The stores from the settings, without the ones opened for this run.
+    pub fn configured_stores(&self) -> Vec<String> {
         self.read_preference(
             |settings| {
                 Self::resolved_store_dirs(settings.user_value("password-store-dirs").map(|_| {
@@ -292,6 +800,11 @@ impl Preferences {
     }
 
     pub fn set_stores(&self, stores: Vec<String>) -> Result<(), BoolError> {
+        let stores =
+            without_session_stores(stores, &self.configured_stores(), &session_store_dirs())
+                .into_iter()
+                .map(|store| portable_relative_path(&store))
+                .collect::<Vec<_>>();
         let settings_stores = stores.clone();
         self.write_preference(
             |settings| settings.set_strv("password-store-dirs", settings_stores.clone()),
@@ -315,6 +828,20 @@ impl Preferences {
         )
     }
 
+    pub fn set_window_maximized(&self, maximized: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("window-maximized", maximized),
+            |cfg| cfg.window_maximized = Some(maximized),
+        )
+    }
+
+    pub fn set_search_visible(&self, visible: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("search-visible", visible),
+            |cfg| cfg.search_visible = Some(visible),
+        )
+    }
+
     pub fn set_new_pass_file_template(&self, template: &str) -> Result<(), BoolError> {
         self.write_preference(
             |settings| settings.set_string("new-pass-file-template", template),
@@ -322,6 +849,20 @@ impl Preferences {
         )
     }
 
+    pub fn set_new_item_website_folder(&self, folder: &str) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_string("new-item-website-folder", folder),
+            |cfg| cfg.new_item_website_folder = Some(folder.to_string()),
+        )
+    }
+
+    pub fn set_fill_fields_from_path(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("fill-fields-from-path", enabled),
+            |cfg| cfg.fill_fields_from_path = Some(enabled),
+        )
+    }
+
     pub fn set_clear_empty_fields_before_save(&self, enabled: bool) -> Result<(), BoolError> {
         self.write_preference(
             |settings| settings.set_boolean("clear-empty-fields-before-save", enabled),
@@ -329,6 +870,141 @@ impl Preferences {
         )
     }
 
+    pub fn set_privacy_mode(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("privacy-mode", enabled),
+            |cfg| cfg.privacy_mode = Some(enabled),
+        )
+    }
+
+    pub fn set_reveal_passwords_briefly(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("reveal-passwords-briefly", enabled),
+            |cfg| cfg.reveal_passwords_briefly = Some(enabled),
+        )
+    }
+
+    pub fn set_password_reveal_seconds(&self, seconds: u32) -> Result<(), BoolError> {
+        let seconds = seconds.clamp(1, MAX_PASSWORD_REVEAL_SECONDS);
+        self.write_preference(
+            |settings| settings.set_uint("password-reveal-seconds", seconds),
+            |cfg| cfg.password_reveal_seconds = Some(seconds),
+        )
+    }
+
+    pub fn set_default_clipboard_clear_seconds(&self, seconds: u32) -> Result<(), BoolError> {
+        let seconds = seconds.min(MAX_CLIPBOARD_CLEAR_SECONDS);
+        self.write_preference(
+            |settings| settings.set_uint("clipboard-clear-seconds", seconds),
+            |cfg| cfg.clipboard_clear_seconds = Some(seconds),
+        )
+    }
+
+    pub fn set_default_auto_lock_minutes(&self, minutes: u32) -> Result<(), BoolError> {
+        let minutes = minutes.min(MAX_AUTO_LOCK_MINUTES);
+        self.write_preference(
+            |settings| settings.set_uint("auto-lock-minutes", minutes),
+            |cfg| cfg.auto_lock_minutes = Some(minutes),
+        )
+    }
+
+    pub fn set_search_item_contents(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("search-item-contents", enabled),
+            |cfg| cfg.search_item_contents = Some(enabled),
+        )
+    }
+
+    pub fn set_activity_log_enabled(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("activity-log", enabled),
+            |cfg| cfg.activity_log = Some(enabled),
+        )
+    }
+
+    pub fn set_remember_key_passwords(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("remember-key-passwords", enabled),
+            |cfg| cfg.remember_key_passwords = Some(enabled),
+        )
+    }
+
+    pub fn set_app_pin_hash(&self, hash: Option<&str>) -> Result<(), BoolError> {
+        let hash = hash.unwrap_or_default();
+        self.write_preference(
+            |settings| settings.set_string("app-pin-hash", hash),
+            |cfg| cfg.app_pin_hash = Some(hash.to_string()).filter(|hash| !hash.is_empty()),
+        )
+    }
+
+    pub fn set_copy_password_on_open_website(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("copy-password-on-open-website", enabled),
+            |cfg| cfg.copy_password_on_open_website = Some(enabled),
+        )
+    }
+
+    pub fn set_expiry_reminders(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("expiry-reminders", enabled),
+            |cfg| cfg.expiry_reminders = Some(enabled),
+        )
+    }
+
+    pub fn set_git_gpg_diffs(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("git-gpg-diffs", enabled),
+            |cfg| cfg.git_gpg_diffs = Some(enabled),
+        )
+    }
+
+    pub fn set_large_file_limit_mib(&self, mib: u32) -> Result<(), BoolError> {
+        let mib = mib.min(MAX_LARGE_FILE_LIMIT_MIB);
+        self.write_preference(
+            |settings| settings.set_uint("large-file-limit-mib", mib),
+            |cfg| cfg.large_file_limit_mib = Some(mib),
+        )
+    }
+
+    pub fn set_personal_strength_words(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("personal-strength-words", enabled),
+            |cfg| cfg.personal_strength_words = Some(enabled),
+        )
+    }
+
+    pub fn set_strength_words(&self, words: &[String]) -> Result<(), BoolError> {
+        let words = Self::normalized_strength_words(words.to_vec());
+        let settings_words = words.clone();
+        self.write_preference(
+            |settings| settings.set_strv("strength-words", settings_words),
+            |cfg| cfg.strength_words = Some(words),
+        )
+    }
+
+    pub fn set_protected_folders(&self, folders: &[String]) -> Result<(), BoolError> {
+        let folders = Self::normalized_protected_folders(folders.to_vec());
+        let settings_folders = folders.clone();
+        self.write_preference(
+            |settings| settings.set_strv("protected-folders", settings_folders),
+            |cfg| cfg.protected_folders = Some(folders),
+        )
+    }
+
+    pub fn set_refuse_large_files(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("refuse-large-files", enabled),
+            |cfg| cfg.refuse_large_files = Some(enabled),
+        )
+    }
+
+    pub fn set_review_changes_before_save(&self, enabled: bool) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_boolean("review-changes-before-save", enabled),
+            |cfg| cfg.review_changes_before_save = Some(enabled),
+        )
+    }
+
     pub fn set_password_generation_settings(
         &self,
         settings: &PasswordGenerationSettings,
@@ -364,6 +1040,23 @@ impl Preferences {
         )
     }
 
+    pub fn set_password_list_density(&self, density: PasswordListDensity) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_string("password-list-density", density.stored_value()),
+            |cfg| cfg.password_list_density = Some(density),
+        )
+    }
+
+    pub fn set_password_list_activation(
+        &self,
+        activation: PasswordListActivation,
+    ) -> Result<(), BoolError> {
+        self.write_preference(
+            |settings| settings.set_string("password-list-activation", activation.stored_value()),
+            |cfg| cfg.password_list_activation = Some(activation),
+        )
+    }
+
     pub fn prune_missing_stores(&self) -> Result<bool, BoolError> {
         let stores = self.stores();
         let existing = stores
@@ -414,6 +1107,124 @@ impl Preferences {
         )
     }
 
+    pub fn store_webdav_folders(&self) -> Vec<StoreWebDavFolder> {
+        Self::normalized_store_webdav_folders(self.read_preference(
+            |settings| {
+                settings
+                    .get::<Vec<(String, String, String)>>("store-webdav-folders")
+                    .into_iter()
+                    .map(StoreWebDavFolder::from_stored)
+                    .collect()
+            },
+            |cfg| cfg.store_webdav_folders.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn store_webdav_folder(&self, store: &str) -> Option<StoreWebDavFolder> {
+        self.store_webdav_folders()
+            .into_iter()
+            .find(|folder| Self::same_store(&folder.store, store))
+    }
+
+    pub fn set_store_webdav_folder(
+        &self,
+        store: &str,
+        folder: Option<StoreWebDavFolder>,
+    ) -> Result<(), BoolError> {
+        let mut folders = self
+            .store_webdav_folders()
+            .into_iter()
+            .filter(|existing| !Self::same_store(&existing.store, store))
+            .collect::<Vec<_>>();
+        folders.extend(folder);
+        let folders = Self::normalized_store_webdav_folders(folders);
+        let settings_folders = folders
+            .iter()
+            .map(StoreWebDavFolder::stored_value)
+            .collect::<Vec<_>>();
+        self.write_preference(
+            |settings| settings.set("store-webdav-folders", settings_folders.clone()),
+            |cfg| cfg.store_webdav_folders = Some(folders),
+        )
+    }
+
+    pub fn store_appearances(&self) -> Vec<StoreAppearance> {
+        Self::normalized_store_appearances(self.read_preference(
+            |settings| {
+                settings
+                    .get::<Vec<(String, String, String)>>("store-appearances")
+                    .into_iter()
+                    .map(StoreAppearance::from_stored)
+                    .collect()
+            },
+            |cfg| cfg.store_appearances.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn store_appearance(&self, store: &str) -> Option<StoreAppearance> {
+        self.store_appearances()
+            .into_iter()
+            .find(|appearance| Self::same_store(&appearance.store, store))
+    }
+
+    pub fn set_store_appearance(
+        &self,
+        store: &str,
+        appearance: Option<StoreAppearance>,
+    ) -> Result<(), BoolError> {
+        let mut appearances = self
+            .store_appearances()
+            .into_iter()
+            .filter(|existing| !Self::same_store(&existing.store, store))
+            .collect::<Vec<_>>();
+        appearances.extend(appearance);
+        let appearances = Self::normalized_store_appearances(appearances);
+        let settings_appearances = appearances
+            .iter()
+            .map(StoreAppearance::stored_value)
+            .collect::<Vec<_>>();
+        self.write_preference(
+            |settings| settings.set("store-appearances", settings_appearances.clone()),
+            |cfg| cfg.store_appearances = Some(appearances),
+        )
+    }
+
+    pub fn read_only_stores(&self) -> Vec<String> {
+        Self::normalized_read_only_stores(self.read_preference(
+            |settings| {
+                settings
+                    .strv("read-only-stores")
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            },
+            |cfg| cfg.read_only_stores.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn store_is_read_only(&self, store: &str) -> bool {
+        self.read_only_stores()
+            .iter()
+            .any(|read_only| Self::same_store(read_only, store))
+    }
+
+    pub fn set_store_read_only(&self, store: &str, read_only: bool) -> Result<(), BoolError> {
+        let mut stores = self
+            .read_only_stores()
+            .into_iter()
+            .filter(|existing| !Self::same_store(existing, store))
+            .collect::<Vec<_>>();
+        if read_only {
+            stores.push(store.to_string());
+        }
+        let stores = Self::normalized_read_only_stores(stores);
+        let settings_stores = stores.clone();
+        self.write_preference(
+            |settings| settings.set_strv("read-only-stores", settings_stores.clone()),
+            |cfg| cfg.read_only_stores = Some(stores),
+        )
+    }
+
     pub fn hidden_notices(&self) -> Vec<String> {
         Self::normalized_hidden_notices(self.read_preference(
             |settings| {
@@ -451,14 +1262,50 @@ impl Preferences {
             |cfg| cfg.hidden_notices = Some(hidden_notices),
         )
     }
+
+    pub fn shortcut_overrides(&self) -> Vec<(String, String)> {
+        Self::parsed_shortcut_overrides(self.read_preference(
+            |settings| {
+                settings
+                    .strv("shortcut-overrides")
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect()
+            },
+            |cfg| cfg.shortcut_overrides.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub fn set_shortcut_override(
+        &self,
+        action: &str,
+        accel: Option<&str>,
+    ) -> Result<(), BoolError> {
+        let mut entries = self
+            .shortcut_overrides()
+            .into_iter()
+            .filter(|(existing, _)| existing != action)
+            .map(|(action, accel)| format!("{action}={accel}"))
+            .collect::<Vec<_>>();
+        if let Some(accel) = accel.map(str::trim).filter(|accel| !accel.is_empty()) {
+            entries.push(format!("{action}={accel}"));
+        }
+        let settings_entries = entries.clone();
+        self.write_preference(
+            |settings| settings.set_strv("shortcut-overrides", settings_entries.clone()),
+            |cfg| cfg.shortcut_overrides = Some(entries),
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        default_backend_kind, default_store_dirs, BackendKind, PasswordListSortMode, Preferences,
+        default_backend_kind, default_store_dirs, BackendKind, PasswordListActivation,
+        PasswordListDensity, PasswordListSortMode, Preferences, StoreAppearance, StoreWebDavFolder,
         UsernameFallbackMode, DEFAULT_WINDOW_HEIGHT, DEFAULT_WINDOW_WIDTH,
     };
+    use super::{with_session_stores, without_session_stores};
     use crate::password::generation::PasswordGenerationSettings;
     use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -473,6 +1320,19 @@ mod tests {
         assert_eq!(default_store_dirs(), expected_default_store_dirs());
     }
 
+    #[test]
+    fn session_stores_are_listed_after_configured_ones_and_never_saved() {
+        let configured = vec!["/stores/personal".to_string()];
+        let session = vec!["/stores/personal/".to_string(), "/stores/work".to_string()];
+
+        let stores = with_session_stores(configured.clone(), &session);
+        assert_eq!(stores, vec!["/stores/personal", "/stores/work"]);
+        assert_eq!(
+            without_session_stores(stores, &configured, &session),
+            configured
+        );
+    }
+
     #[test]
     fn store_dirs_default_when_unset() {
         assert_eq!(
@@ -610,6 +1470,7 @@ mod tests {
     fn password_list_sort_mode_storage_accepts_current_names() {
         assert_eq!(PasswordListSortMode::Filename.stored_value(), "filename");
         assert_eq!(PasswordListSortMode::StorePath.stored_value(), "store-path");
+        assert_eq!(PasswordListSortMode::TopFolder.stored_value(), "top-folder");
         assert_eq!(
             PasswordListSortMode::from_stored("filename"),
             PasswordListSortMode::Filename
@@ -633,6 +1494,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn store_appearances_are_trimmed_and_unique_per_store() {
+        let appearance = |store: &str, label: &str, color: &str| StoreAppearance {
+            store: store.to_string(),
+            label: label.to_string(),
+            color: color.to_string(),
+        };
+
+        assert_eq!(
+            Preferences::normalized_store_appearances(vec![
+                appearance("/stores/work", "Old", "red"),
+                appearance("/stores/empty", " ", ""),
+                appearance("/stores/work/", " Work ", " Blue "),
+            ]),
+            vec![appearance("/stores/work/", "Work", "blue")]
+        );
+    }
+
+    #[test]
+    fn store_webdav_folders_are_trimmed_and_unique_per_store() {
+        let folder = |store: &str, url: &str| StoreWebDavFolder {
+            store: store.to_string(),
+            url: url.to_string(),
+            login_entry: " dav/login ".to_string(),
+        };
+
+        assert_eq!(
+            Preferences::normalized_store_webdav_folders(vec![
+                folder("/stores/work", "https://dav.example/old"),
+                folder("/stores/empty", " "),
+                folder("/stores/work/", " https://dav.example/new "),
+            ]),
+            vec![StoreWebDavFolder {
+                store: "/stores/work/".to_string(),
+                url: "https://dav.example/new".to_string(),
+                login_entry: "dav/login".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn read_only_stores_are_trimmed_and_unique_per_store() {
+        assert_eq!(
+            Preferences::normalized_read_only_stores(vec![
+                "/stores/team".to_string(),
+                " ".to_string(),
+                " /stores/team/ ".to_string(),
+                "/stores/shared".to_string(),
+            ]),
+            vec!["/stores/team/".to_string(), "/stores/shared".to_string()]
+        );
+    }
+
+    #[test]
+    fn shortcut_overrides_skip_malformed_entries_and_keep_the_last_per_action() {
+        assert_eq!(
+            Preferences::parsed_shortcut_overrides(vec![
+                "win.copy-password=<Primary><Shift>c".to_string(),
+                "missing-separator".to_string(),
+                "win.open-tools= ".to_string(),
+                " win.copy-password = <Primary>j ".to_string(),
+            ]),
+            vec![("win.copy-password".to_string(), "<Primary>j".to_string())]
+        );
+    }
+
+    #[test]
+    fn password_list_density_defaults_to_comfortable_rows() {
+        assert_eq!(
+            PasswordListDensity::default(),
+            PasswordListDensity::Comfortable
+        );
+        assert_eq!(
+            PasswordListDensity::from_stored("compact"),
+            PasswordListDensity::Compact
+        );
+        assert_eq!(
+            PasswordListDensity::from_stored("unexpected"),
+            PasswordListDensity::Comfortable
+        );
+    }
+
+    #[test]
+    fn password_list_activation_defaults_to_opening_the_item() {
+        assert_eq!(
+            PasswordListActivation::default(),
+            PasswordListActivation::Open
+        );
+        assert_eq!(
+            PasswordListActivation::from_stored(" Copy-OTP "),
+            PasswordListActivation::CopyOtp
+        );
+        assert_eq!(
+            PasswordListActivation::from_stored(
+                PasswordListActivation::CopyPassword.stored_value()
+            ),
+            PasswordListActivation::CopyPassword
+        );
+        assert_eq!(
+            PasswordListActivation::from_stored("unexpected"),
+            PasswordListActivation::Open
+        );
+    }
+
     #[test]
     fn password_list_sort_mode_invalid_values_fall_back_to_store_path() {
         assert_eq!(
@@ -651,11 +1616,105 @@ mod tests {
         assert!(!Preferences::new().audit_use_commit_history_recipients());
     }
 
+    #[test]
+    fn website_items_default_to_the_web_folder() {
+        assert_eq!(Preferences::new().new_item_website_folder(), "web");
+    }
+
+    #[test]
+    fn filling_fields_from_the_path_defaults_to_enabled() {
+        assert!(Preferences::new().fill_fields_from_path());
+    }
+
     #[test]
     fn clear_empty_fields_before_save_defaults_to_disabled() {
         assert!(!Preferences::new().clear_empty_fields_before_save());
     }
 
+    #[test]
+    fn privacy_mode_defaults_to_disabled() {
+        assert!(!Preferences::new().privacy_mode());
+    }
+
+    #[test]
+    fn security_settings_default_to_clearing_the_clipboard_only() {
+        let settings = Preferences::new();
+
+        assert_eq!(settings.default_clipboard_clear_seconds(), 45);
+        assert_eq!(settings.default_auto_lock_minutes(), 0);
+        assert_eq!(settings.password_reveal_seconds(), 10);
+        assert!(!settings.search_item_contents());
+    }
+
+    #[test]
+    fn brief_password_reveal_defaults_to_disabled() {
+        assert!(!Preferences::new().reveal_passwords_briefly());
+    }
+
+    #[test]
+    fn activity_log_defaults_to_enabled() {
+        assert!(Preferences::new().activity_log_enabled());
+    }
+
+    #[test]
+    fn key_passwords_can_be_remembered_by_default() {
+        assert!(Preferences::new().remember_key_passwords());
+    }
+
+    #[test]
+    fn app_pin_defaults_to_off() {
+        assert_eq!(Preferences::new().app_pin_hash(), None);
+        assert!(!Preferences::new().has_app_pin());
+    }
+
+    #[test]
+    fn opening_a_website_does_not_copy_the_password_by_default() {
+        assert!(!Preferences::new().copy_password_on_open_website());
+    }
+
+    #[test]
+    fn expiry_reminders_default_to_disabled() {
+        assert!(!Preferences::new().expiry_reminders());
+    }
+
+    #[test]
+    fn decrypted_git_diffs_default_to_disabled() {
+        assert!(!Preferences::new().git_gpg_diffs());
+    }
+
+    #[test]
+    fn passwords_are_checked_against_personal_words_by_default() {
+        let settings = Preferences::new();
+        assert!(settings.personal_strength_words());
+        assert!(settings.strength_words().is_empty());
+    }
+
+    #[test]
+    fn protected_folders_drop_slashes_and_duplicates() {
+        assert!(Preferences::new().protected_folders().is_empty());
+        assert_eq!(
+            Preferences::normalized_protected_folders(vec![
+                " /shared/prod/ ".to_string(),
+                "shared/prod".to_string(),
+                "/".to_string(),
+                "bank".to_string(),
+            ]),
+            vec!["bank".to_string(), "shared/prod".to_string()]
+        );
+    }
+
+    #[test]
+    fn large_files_are_flagged_but_not_refused_by_default() {
+        let settings = Preferences::new();
+        assert_eq!(settings.large_file_limit_mib(), 5);
+        assert!(!settings.refuse_large_files());
+    }
+
+    #[test]
+    fn reviewing_changes_before_save_defaults_to_enabled() {
+        assert!(Preferences::new().review_changes_before_save());
+    }
+
     #[test]
     fn invalid_window_dimensions_fall_back_to_the_default_size() {
         assert_eq!(
@@ -679,4 +1738,12 @@ mod tests {
             (DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT)
         );
     }
+
+    #[test]
+    fn window_state_defaults_to_a_plain_window_without_search() {
+        let preferences = Preferences::new();
+
+        assert!(!preferences.window_is_maximized());
+        assert!(!preferences.search_visible());
+    }
 }
@@ -0,0 +1,283 @@
+use super::Preferences;
+use adw::gio::prelude::*;
+use adw::glib::BoolError;
+use serde::{Deserialize, Serialize};
+
+type StoredWorkspaceProfile = (String, Vec<String>, String, String, String, u32, u32);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct WorkspaceProfile {
+    pub name: String,
+    #[serde(default)]
+    pub stores: Vec<String>,
+    #[serde(default)]
+    pub private_key: String,
+    #[serde(default)]
+    pub git_name: String,
+    #[serde(default)]
+    pub git_email: String,
+    #[serde(default)]
+    pub clipboard_clear_seconds: u32,
+    #[serde(default)]
+    pub auto_lock_minutes: u32,
+}
+
+impl WorkspaceProfile {
+    fn from_stored(
+        (
+            name,
+            stores,
+            private_key,
+            git_name,
+            git_email,
+            clipboard_clear_seconds,
+            auto_lock_minutes,
+        ): StoredWorkspaceProfile,
+    ) -> Self {
+        Self {
+            name,
+            stores,
+            private_key,
+            git_name,
+            git_email,
+            clipboard_clear_seconds,
+            auto_lock_minutes,
+        }
+    }
+
+    fn stored_value(&self) -> StoredWorkspaceProfile {
+        (
+            self.name.clone(),
+            self.stores.clone(),
+            self.private_key.clone(),
+            self.git_name.clone(),
+            self.git_email.clone(),
+            self.clipboard_clear_seconds,
+            self.auto_lock_minutes,
+        )
+    }
+
+    fn normalized(self) -> Self {
+        Self {
+            name: self.name.trim().to_string(),
+            stores: self
+                .stores
+                .into_iter()
+                .map(|store| store.trim().to_string())
+                .filter(|store| !store.is_empty())
+                .collect(),
+            private_key: self.private_key.trim().to_string(),
+            git_name: self.git_name.trim().to_string(),
+            git_email: self.git_email.trim().to_string(),
+            ..self
+        }
+    }
+
+    pub fn git_identity(&self) -> Option<(String, String)> {
+        (!self.git_name.is_empty() && !self.git_email.is_empty())
+            .then(|| (self.git_name.clone(), self.git_email.clone()))
+    }
+}
+
+fn normalized_workspace_profiles(profiles: Vec<WorkspaceProfile>) -> Vec<WorkspaceProfile> {
+    let mut normalized: Vec<WorkspaceProfile> = Vec::with_capacity(profiles.len());
+    for profile in profiles.into_iter().map(WorkspaceProfile::normalized) {
+        if profile.name.is_empty() {
+            continue;
+        }
+        normalized.retain(|existing| existing.name != profile.name);
+        normalized.push(profile);
+    }
+    normalized
+}
+
+impl Preferences {
+    pub fn workspace_profiles(&self) -> Vec<WorkspaceProfile> {
+        normalized_workspace_profiles(self.read_preference(
+            |settings| {
+                settings
+                    .get::<Vec<StoredWorkspaceProfile>>("workspace-profiles")
+                    .into_iter()
+                    .map(WorkspaceProfile::from_stored)
+                    .collect()
+            },
+            |cfg| cfg.workspace_profiles.clone().unwrap_or_default(),
+        ))
+    }
+
+    pub(super) fn set_workspace_profiles(
+        &self,
+        profiles: Vec<WorkspaceProfile>,
+    ) -> Result<(), BoolError> {
+        let profiles = normalized_workspace_profiles(profiles);
+        let settings_profiles = profiles
+            .iter()
+            .map(WorkspaceProfile::stored_value)
+            .collect::<Vec<_>>();
+        self.write_preference(
+            |settings| settings.set("workspace-profiles", settings_profiles.clone()),
+            |cfg| cfg.workspace_profiles = Some(profiles),
+        )
+    }
+
+    pub fn active_workspace_profile(&self) -> Option<WorkspaceProfile> {
+        let name = self.read_preference(
+            |settings| settings.string("active-workspace-profile").to_string(),
+            |cfg| cfg.active_workspace_profile.clone().unwrap_or_default(),
+        );
+        let name = name.trim();
+        self.workspace_profiles()
+            .into_iter()
+            .find(|profile| profile.name == name)
+    }
+
+    fn set_active_workspace_profile_name(&self, name: &str) -> Result<(), BoolError> {
+        let name = name.trim().to_string();
+        let settings_name = name.clone();
+        self.write_preference(
+            |settings| settings.set_string("active-workspace-profile", &settings_name),
+            |cfg| cfg.active_workspace_profile = (!name.is_empty()).then_some(name),
+        )
+    }
+
+    pub fn current_setup_as_workspace_profile(&self, name: &str) -> WorkspaceProfile {
+        let active = self.active_workspace_profile().unwrap_or_default();
+        WorkspaceProfile {
+            name: name.to_string(),
+            stores: self.configured_stores(),
+            private_key: self.ripasso_own_fingerprint().unwrap_or_default(),
+            clipboard_clear_seconds: self.clipboard_clear_seconds(),
+            auto_lock_minutes: self.auto_lock_minutes(),
+            ..active
+        }
+    }
+
+    pub fn save_workspace_profile(
+        &self,
+        previous_name: Option<&str>,
+        profile: WorkspaceProfile,
+    ) -> Result<(), BoolError> {
+        let profile = profile.normalized();
+        let was_active = self
+            .active_workspace_profile()
+            .is_some_and(|active| previous_name == Some(active.name.as_str()));
+        let mut profiles = self
+            .workspace_profiles()
+            .into_iter()
+            .filter(|existing| {
+                Some(existing.name.as_str()) != previous_name && existing.name != profile.name
+            })
+            .collect::<Vec<_>>();
+        let name = profile.name.clone();
+        profiles.push(profile);
+        self.set_workspace_profiles(profiles)?;
+        if was_active || previous_name.is_none() {
+            self.set_active_workspace_profile_name(&name)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove_workspace_profile(&self, name: &str) -> Result<(), BoolError> {
+        if self
+            .active_workspace_profile()
+            .is_some_and(|active| active.name == name)
+        {
+            self.set_active_workspace_profile_name("")?;
+        }
+        self.set_workspace_profiles(
+            self.workspace_profiles()
+                .into_iter()
+                .filter(|profile| profile.name != name)
+                .collect(),
+        )
+    }
+
+    pub fn activate_workspace_profile(&self, name: &str) -> Result<bool, BoolError> {
+        let Some(profile) = self
+            .workspace_profiles()
+            .into_iter()
+            .find(|profile| profile.name == name)
+        else {
+            return Ok(false);
+        };
+
+        if let Some(active) = self.active_workspace_profile() {
+            if active.name == profile.name {
+                return Ok(false);
+            }
+            let snapshot = self.current_setup_as_workspace_profile(&active.name);
+            self.save_workspace_profile(Some(&active.name), snapshot)?;
+        }
+
+        if !profile.stores.is_empty() {
+            self.set_stores(profile.stores.clone())?;
+        }
+        if !profile.private_key.is_empty() {
+            self.set_ripasso_own_fingerprint(Some(&profile.private_key))?;
+        }
+        self.set_active_workspace_profile_name(&profile.name)?;
+        Ok(true)
+    }
+
+    pub fn git_identity(&self) -> Option<(String, String)> {
+        self.active_workspace_profile()
+            .and_then(|profile| profile.git_identity())
+    }
+
+    pub fn clipboard_clear_seconds(&self) -> u32 {
+        self.active_workspace_profile().map_or_else(
+            || self.default_clipboard_clear_seconds(),
+            |profile| profile.clipboard_clear_seconds,
+        )
+    }
+
+    pub fn auto_lock_minutes(&self) -> u32 {
+        self.active_workspace_profile().map_or_else(
+            || self.default_auto_lock_minutes(),
+            |profile| profile.auto_lock_minutes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalized_workspace_profiles, WorkspaceProfile};
+
+    #[test]
+    fn workspace_profiles_are_trimmed_and_unique_by_name() {
+        let profile = |name: &str, git_email: &str| WorkspaceProfile {
+            name: name.to_string(),
+            stores: vec![" ~/.password-store ".to_string(), " ".to_string()],
+            git_email: git_email.to_string(),
+            ..WorkspaceProfile::default()
+        };
+
+        let profiles = normalized_workspace_profiles(vec![
+            profile("Work", "old@example.com"),
+            profile(" ", ""),
+            profile(" Work ", " new@example.com "),
+        ]);
+
+        assert_eq!(profiles.len(), 1);
+        assert_eq!(profiles[0].name, "Work");
+        assert_eq!(profiles[0].stores, vec!["~/.password-store".to_string()]);
+        assert_eq!(profiles[0].git_email, "new@example.com");
+    }
+
+    #[test]
+    fn git_identity_needs_both_name_and_email() {
+        let mut profile = WorkspaceProfile {
+            name: "Work".to_string(),
+            git_name: "Alice".to_string(),
+            ..WorkspaceProfile::default()
+        };
+        assert_eq!(profile.git_identity(), None);
+
+        profile.git_email = "alice@example.com".to_string();
+        assert_eq!(
+            profile.git_identity(),
+            Some(("Alice".to_string(), "alice@example.com".to_string()))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::Preferences;
+use crate::support::portable::{is_portable, PORTABLE_STORE_DIR_NAME};
 use adw::gio::prelude::*;
 use adw::glib::BoolError;
 use std::env;
@@ -31,6 +32,9 @@ impl Preferences {
 }
 
 pub(super) fn default_store_dirs() -> Vec<String> {
+    if is_portable() {
+        return vec![PORTABLE_STORE_DIR_NAME.to_string()];
+    }
     env::var("HOME")
         .map(|home| vec![format!("{home}/.password-store")])
         .unwrap_or_default()
@@ -1,11 +1,14 @@
-use super::{PasswordListSortMode, UsernameFallbackMode};
+use super::{
+    PasswordListActivation, PasswordListDensity, PasswordListSortMode, StoreAppearance,
+    StoreWebDavFolder, UsernameFallbackMode, WorkspaceProfile,
+};
 use crate::password::generation::PasswordGenerationSettings;
+use crate::support::paths::app_config_file;
 use crate::support::secure_fs::write_private_file;
 use crate::support::toml_safety::{parse_toml_with_limits, PREFERENCE_FILE_TOML_LIMITS};
 use adw::glib::{bool_error, BoolError};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub(super) struct PreferenceFile {
@@ -14,26 +17,49 @@ pub(super) struct PreferenceFile {
     pub(super) password_store_dirs: Option<Vec<String>>,
     pub(super) window_width: Option<i32>,
     pub(super) window_height: Option<i32>,
+    pub(super) window_maximized: Option<bool>,
+    pub(super) search_visible: Option<bool>,
     pub(super) new_pass_file_template: Option<String>,
+    pub(super) new_item_website_folder: Option<String>,
+    pub(super) fill_fields_from_path: Option<bool>,
     pub(super) clear_empty_fields_before_save: Option<bool>,
+    pub(super) privacy_mode: Option<bool>,
+    pub(super) reveal_passwords_briefly: Option<bool>,
+    pub(super) password_reveal_seconds: Option<u32>,
+    pub(super) clipboard_clear_seconds: Option<u32>,
+    pub(super) auto_lock_minutes: Option<u32>,
+    pub(super) search_item_contents: Option<bool>,
+    pub(super) activity_log: Option<bool>,
+    pub(super) remember_key_passwords: Option<bool>,
+    pub(super) app_pin_hash: Option<String>,
+    pub(super) copy_password_on_open_website: Option<bool>,
+    pub(super) expiry_reminders: Option<bool>,
+    pub(super) review_changes_before_save: Option<bool>,
     pub(super) password_generation: Option<PasswordGenerationSettings>,
     pub(super) username_fallback_mode: Option<UsernameFallbackMode>,
     pub(super) password_list_sort_mode: Option<PasswordListSortMode>,
+    pub(super) password_list_density: Option<PasswordListDensity>,
+    pub(super) password_list_activation: Option<PasswordListActivation>,
     pub(super) ripasso_own_fingerprint: Option<String>,
     pub(super) sync_private_keys_with_host: Option<bool>,
     pub(super) audit_use_commit_history_recipients: Option<bool>,
+    pub(super) git_gpg_diffs: Option<bool>,
+    pub(super) large_file_limit_mib: Option<u32>,
+    pub(super) refuse_large_files: Option<bool>,
+    pub(super) personal_strength_words: Option<bool>,
+    pub(super) strength_words: Option<Vec<String>>,
+    pub(super) protected_folders: Option<Vec<String>>,
     pub(super) hidden_notices: Option<Vec<String>>,
-}
-
-fn config_path() -> PathBuf {
-    dirs_next::config_dir().map_or_else(
-        || PathBuf::from(format!("{}.toml", env!("CARGO_PKG_NAME"))),
-        |dir| dir.join(format!("{}.toml", env!("CARGO_PKG_NAME"))),
-    )
+    pub(super) shortcut_overrides: Option<Vec<String>>,
+    pub(super) store_webdav_folders: Option<Vec<StoreWebDavFolder>>,
+    pub(super) read_only_stores: Option<Vec<String>>,
+    pub(super) store_appearances: Option<Vec<StoreAppearance>>,
+    pub(super) workspace_profiles: Option<Vec<WorkspaceProfile>>,
+    pub(super) active_workspace_profile: Option<String>,
 }
 
 pub(super) fn load_file_prefs() -> PreferenceFile {
-    let path = config_path();
+    let path = app_config_file();
     fs::read_to_string(&path).map_or_else(
         |_| PreferenceFile::default(),
         |data| {
@@ -44,7 +70,7 @@ pub(super) fn load_file_prefs() -> PreferenceFile {
 }
 
 pub(super) fn save_file_prefs(cfg: &PreferenceFile) -> Result<(), BoolError> {
-    let path = config_path();
+    let path = app_config_file();
 
     let toml =
         toml::to_string_pretty(cfg).map_err(|e| bool_error!("Failed to serialize config: {e}"))?;
@@ -0,0 +1,392 @@
+use super::{Preferences, StoreAppearance, WorkspaceProfile};
+use crate::password::generation::PasswordGenerationSettings;
+use crate::support::toml_safety::{parse_toml_with_limits, PREFERENCE_FILE_TOML_LIMITS};
+use adw::gio::prelude::*;
+use adw::glib::BoolError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub const SETTINGS_EXPORT_FILE_NAME: &str = concat!(env!("CARGO_PKG_NAME"), "-settings.toml");
+const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+// Only the setup that makes sense on another machine is exported. Window
+// state, the app PIN, and hidden notices stay on this device.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SettingsExport {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
+    pub stores: Vec<String>,
+    pub pass_command: Option<String>,
+    pub new_pass_file_template: Option<String>,
+    pub new_item_website_folder: Option<String>,
+    pub password_generation: Option<PasswordGenerationSettings>,
+    #[serde(default)]
+    pub shortcuts: Vec<String>,
+    #[serde(default)]
+    pub workspace_profiles: Vec<WorkspaceProfile>,
+    #[serde(default)]
+    pub store_appearances: Vec<StoreAppearance>,
+    #[serde(default)]
+    pub read_only_stores: Vec<String>,
+}
+
+// What an import changes that's worth a look before it's saved: stores that
+// get added and a different `pass` command, which runs on every read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SettingsImportSummary {
+    pub new_stores: Vec<String>,
+    pub pass_command: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsImportError {
+    Unreadable(String),
+    Invalid(&'static str),
+}
+
+impl SettingsImportError {
+    pub const fn message(&self) -> &'static str {
+        match self {
+            Self::Unreadable(_) => "That file isn't a Keycord settings file.",
+            Self::Invalid(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for SettingsImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(detail) => f.write_str(detail),
+            Self::Invalid(message) => f.write_str(message),
+        }
+    }
+}
+
+pub fn settings_export_toml(export: &SettingsExport) -> Result<String, String> {
+    toml::to_string_pretty(export).map_err(|err| format!("Failed to serialize settings: {err}"))
+}
+
+// Rejects the whole file on the first problem, before anything is shown or
+// saved.
+pub fn parse_settings_export(data: &str) -> Result<SettingsExport, SettingsImportError> {
+    let export: SettingsExport =
+        parse_toml_with_limits(data, PREFERENCE_FILE_TOML_LIMITS, "settings file")
+            .map_err(SettingsImportError::Unreadable)?;
+    if export.version == 0 {
+        return Err(SettingsImportError::Unreadable(
+            "The settings file has no version.".to_string(),
+        ));
+    }
+    if export.version > SETTINGS_EXPORT_VERSION {
+        return Err(SettingsImportError::Invalid(
+            "These settings come from a newer version of Keycord.",
+        ));
+    }
+    if export
+        .stores
+        .iter()
+        .chain(&export.read_only_stores)
+        .chain(
+            export
+                .store_appearances
+                .iter()
+                .map(|appearance| &appearance.store),
+        )
+        .any(|store| store.trim().is_empty())
+    {
+        return Err(SettingsImportError::Invalid(
+            "The settings file has a store without a folder.",
+        ));
+    }
+    if export
+        .pass_command
+        .as_deref()
+        .is_some_and(|command| command.trim().is_empty())
+    {
+        return Err(SettingsImportError::Invalid(
+            "The settings file has an empty pass command.",
+        ));
+    }
+    if !export.shortcuts.iter().all(|entry| {
+        entry
+            .split_once('=')
+            .is_some_and(|(action, accel)| !action.trim().is_empty() && !accel.trim().is_empty())
+    }) {
+        return Err(SettingsImportError::Invalid(
+            "The settings file has a shortcut that can't be used.",
+        ));
+    }
+    if export
+        .workspace_profiles
+        .iter()
+        .any(|profile| profile.name.trim().is_empty())
+    {
+        return Err(SettingsImportError::Invalid(
+            "The settings file has a profile without a name.",
+        ));
+    }
+    Ok(export)
+}
+
+fn merged_by<T>(current: Vec<T>, imported: Vec<T>, same: impl Fn(&T, &T) -> bool) -> Vec<T> {
+    let mut merged = current;
+    for item in imported {
+        if let Some(existing) = merged.iter_mut().find(|existing| same(existing, &item)) {
+            *existing = item;
+        } else {
+            merged.push(item);
+        }
+    }
+    merged
+}
+
+fn shortcut_action(entry: &str) -> &str {
+    entry
+        .split_once('=')
+        .map_or(entry, |(action, _)| action)
+        .trim()
+}
+
+// Lists are merged: imported stores, shortcuts, profiles, and store
+// settings are added or replace the ones with the same key, and everything
+// else stays. Single values only change when the file sets them.
+fn merged_settings(current: SettingsExport, imported: SettingsExport) -> SettingsExport {
+    SettingsExport {
+        version: SETTINGS_EXPORT_VERSION,
+        stores: merged_by(current.stores, imported.stores, |left, right| {
+            Preferences::same_store(left, right)
+        }),
+        pass_command: imported.pass_command.or(current.pass_command),
+        new_pass_file_template: imported
+            .new_pass_file_template
+            .or(current.new_pass_file_template),
+        new_item_website_folder: imported
+            .new_item_website_folder
+            .or(current.new_item_website_folder),
+        password_generation: imported.password_generation.or(current.password_generation),
+        shortcuts: merged_by(current.shortcuts, imported.shortcuts, |left, right| {
+            shortcut_action(left) == shortcut_action(right)
+        }),
+        workspace_profiles: merged_by(
+            current.workspace_profiles,
+            imported.workspace_profiles,
+            |left, right| left.name.trim() == right.name.trim(),
+        ),
+        store_appearances: merged_by(
+            current.store_appearances,
+            imported.store_appearances,
+            |left, right| Preferences::same_store(&left.store, &right.store),
+        ),
+        read_only_stores: merged_by(
+            current.read_only_stores,
+            imported.read_only_stores,
+            |left, right| Preferences::same_store(left, right),
+        ),
+    }
+}
+
+fn settings_import_summary_for(
+    current: &SettingsExport,
+    imported: &SettingsExport,
+) -> SettingsImportSummary {
+    let mut new_stores = Vec::<String>::new();
+    for store in &imported.stores {
+        if !current
+            .stores
+            .iter()
+            .chain(&new_stores)
+            .any(|existing| Preferences::same_store(existing, store))
+        {
+            new_stores.push(store.clone());
+        }
+    }
+    let pass_command = imported
+        .pass_command
+        .as_deref()
+        .map(str::trim)
+        .filter(|command| current.pass_command.as_deref().map(str::trim) != Some(*command))
+        .map(str::to_string);
+    SettingsImportSummary {
+        new_stores,
+        pass_command,
+    }
+}
+
+impl Preferences {
+    pub fn settings_import_summary(&self, imported: &SettingsExport) -> SettingsImportSummary {
+        settings_import_summary_for(&self.settings_export(), imported)
+    }
+
+    pub fn settings_export(&self) -> SettingsExport {
+        SettingsExport {
+            version: SETTINGS_EXPORT_VERSION,
+            stores: self.stores(),
+            pass_command: Some(self.command_value()),
+            new_pass_file_template: Some(self.new_pass_file_template()),
+            new_item_website_folder: Some(self.new_item_website_folder()),
+            password_generation: Some(self.password_generation_settings()),
+            shortcuts: self
+                .shortcut_overrides()
+                .into_iter()
+                .map(|(action, accel)| format!("{action}={accel}"))
+                .collect(),
+            workspace_profiles: self.workspace_profiles(),
+            store_appearances: self.store_appearances(),
+            read_only_stores: self.read_only_stores(),
+        }
+    }
+
+    pub fn import_settings(&self, imported: SettingsExport) -> Result<(), BoolError> {
+        let merged = merged_settings(self.settings_export(), imported);
+        self.write_preferences_together(|| self.write_imported_settings(merged))
+    }
+
+    fn write_imported_settings(&self, merged: SettingsExport) -> Result<(), BoolError> {
+        self.set_stores(merged.stores)?;
+        if let Some(command) = merged.pass_command.as_deref() {
+            self.set_command(command.trim())?;
+        }
+        if let Some(template) = merged.new_pass_file_template.as_deref() {
+            self.set_new_pass_file_template(template)?;
+        }
+        if let Some(folder) = merged.new_item_website_folder.as_deref() {
+            self.set_new_item_website_folder(folder)?;
+        }
+        if let Some(generation) = merged.password_generation.as_ref() {
+            self.set_password_generation_settings(generation)?;
+        }
+
+        let shortcuts = Self::parsed_shortcut_overrides(merged.shortcuts)
+            .into_iter()
+            .map(|(action, accel)| format!("{action}={accel}"))
+            .collect::<Vec<_>>();
+        let settings_shortcuts = shortcuts.clone();
+        self.write_preference(
+            |settings| settings.set_strv("shortcut-overrides", settings_shortcuts.clone()),
+            |cfg| cfg.shortcut_overrides = Some(shortcuts),
+        )?;
+
+        self.set_workspace_profiles(merged.workspace_profiles)?;
+
+        let appearances = Self::normalized_store_appearances(merged.store_appearances);
+        let settings_appearances = appearances
+            .iter()
+            .map(StoreAppearance::stored_value)
+            .collect::<Vec<_>>();
+        self.write_preference(
+            |settings| settings.set("store-appearances", settings_appearances.clone()),
+            |cfg| cfg.store_appearances = Some(appearances),
+        )?;
+
+        let read_only = Self::normalized_read_only_stores(merged.read_only_stores);
+        let settings_read_only = read_only.clone();
+        self.write_preference(
+            |settings| settings.set_strv("read-only-stores", settings_read_only.clone()),
+            |cfg| cfg.read_only_stores = Some(read_only),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        merged_settings, parse_settings_export, settings_import_summary_for, SettingsExport,
+        SettingsImportError,
+    };
+    use crate::preferences::WorkspaceProfile;
+
+    #[test]
+    fn settings_files_are_checked_before_import() {
+        assert!(matches!(
+            parse_settings_export("stores = [\"/a\"]"),
+            Err(SettingsImportError::Unreadable(_))
+        ));
+        assert_eq!(
+            parse_settings_export("version = 2"),
+            Err(SettingsImportError::Invalid(
+                "These settings come from a newer version of Keycord."
+            ))
+        );
+        assert_eq!(
+            parse_settings_export("version = 1\nshortcuts = [\"open-search\"]"),
+            Err(SettingsImportError::Invalid(
+                "The settings file has a shortcut that can't be used."
+            ))
+        );
+        assert_eq!(
+            parse_settings_export("version = 1\nstores = [\"/a\"]")
+                .expect("settings should parse")
+                .stores,
+            vec!["/a".to_string()]
+        );
+    }
+
+    #[test]
+    fn imported_settings_merge_into_the_current_setup() {
+        let current = SettingsExport {
+            version: 1,
+            stores: vec!["/a".to_string(), "/b/".to_string()],
+            pass_command: Some("pass".to_string()),
+            shortcuts: vec!["search=<Ctrl>f".to_string(), "lock=<Ctrl>l".to_string()],
+            workspace_profiles: vec![WorkspaceProfile {
+                name: "Work".to_string(),
+                git_name: "Old".to_string(),
+                ..WorkspaceProfile::default()
+            }],
+            ..SettingsExport::default()
+        };
+        let imported = SettingsExport {
+            version: 1,
+            stores: vec!["/b".to_string(), "/c".to_string()],
+            new_item_website_folder: Some("sites".to_string()),
+            shortcuts: vec!["search=<Ctrl>k".to_string()],
+            workspace_profiles: vec![WorkspaceProfile {
+                name: "Work".to_string(),
+                git_name: "New".to_string(),
+                ..WorkspaceProfile::default()
+            }],
+            ..SettingsExport::default()
+        };
+
+        let merged = merged_settings(current, imported);
+
+        assert_eq!(merged.stores, vec!["/a", "/b", "/c"]);
+        assert_eq!(merged.pass_command.as_deref(), Some("pass"));
+        assert_eq!(merged.new_item_website_folder.as_deref(), Some("sites"));
+        assert_eq!(merged.shortcuts, vec!["search=<Ctrl>k", "lock=<Ctrl>l"]);
+        assert_eq!(merged.workspace_profiles.len(), 1);
+        assert_eq!(merged.workspace_profiles[0].git_name, "New");
+    }
+
+    #[test]
+    fn import_summary_lists_new_stores_and_a_changed_pass_command() {
+        let current = SettingsExport {
+            version: 1,
+            stores: vec!["/a".to_string()],
+            pass_command: Some("pass".to_string()),
+            ..SettingsExport::default()
+        };
+        let imported = SettingsExport {
+            version: 1,
+            stores: vec!["/a/".to_string(), "/b".to_string(), "/b".to_string()],
+            pass_command: Some(" pass ".to_string()),
+            ..SettingsExport::default()
+        };
+        let summary = settings_import_summary_for(&current, &imported);
+        assert_eq!(summary.new_stores, vec!["/b".to_string()]);
+        assert_eq!(summary.pass_command, None);
+
+        let imported = SettingsExport {
+            pass_command: Some("/opt/pass-wrapper".to_string()),
+            ..imported
+        };
+        assert_eq!(
+            settings_import_summary_for(&current, &imported)
+                .pass_command
+                .as_deref(),
+            Some("/opt/pass-wrapper")
+        );
+    }
+}
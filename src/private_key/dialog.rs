@@ -4,10 +4,11 @@ use crate::support::ui::{
     connect_password_entry_row_apply_button_to_nonempty_text, dialog_content_shell,
     wrapped_dialog_body,
 };
-use adw::gtk::{Align, Box as GtkBox, Button, Label, Orientation, Spinner};
+use adw::gtk::{Align, Box as GtkBox, Button, CheckButton, Label, Orientation, Spinner};
 use adw::prelude::*;
 use adw::{
-    ApplicationWindow, Dialog, PasswordEntryRow, PreferencesGroup, PreferencesPage, ToastOverlay,
+    ActionRow, ApplicationWindow, Dialog, PasswordEntryRow, PreferencesGroup, PreferencesPage,
+    ToastOverlay,
 };
 use secrecy::{ExposeSecret, SecretString};
 use std::cell::Cell;
@@ -363,6 +364,31 @@ pub fn present_private_key_unlock_dialog_with_close_handler<F, G>(
 ) where
     F: Fn(PrivateKeyUnlockRequest) + 'static,
     G: Fn() + 'static,
+{
+    present_private_key_unlock_dialog_with_remember_option(
+        window,
+        title,
+        subtitle,
+        kind,
+        false,
+        move |request, _| on_submit(request),
+        on_close,
+    );
+}
+
+// `offer_remember` adds a "Remember in keyring" row; the callback receives
+// whether it was ticked.
+pub fn present_private_key_unlock_dialog_with_remember_option<F, G>(
+    window: &ApplicationWindow,
+    title: &str,
+    subtitle: Option<&str>,
+    kind: PrivateKeyUnlockKind,
+    offer_remember: bool,
+    on_submit: F,
+    on_close: G,
+) where
+    F: Fn(PrivateKeyUnlockRequest, bool) + 'static,
+    G: Fn() + 'static,
 {
     let on_submit = Rc::new(on_submit);
     let password_row = PasswordEntryRow::new();
@@ -373,6 +399,19 @@ pub fn present_private_key_unlock_dialog_with_close_handler<F, G>(
     let password_group = PreferencesGroup::builder().build();
     password_group.add(&password_row);
 
+    let remember_check = offer_remember.then(|| {
+        let check = CheckButton::new();
+        check.set_valign(Align::Center);
+        let row = ActionRow::builder()
+            .title(gettext("Remember in keyring"))
+            .subtitle(gettext("Unlock this key without asking on this device."))
+            .activatable_widget(&check)
+            .build();
+        row.add_suffix(&check);
+        password_group.add(&row);
+        check
+    });
+
     let page = PreferencesPage::new();
     page.add(&password_group);
 
@@ -441,7 +480,8 @@ pub fn present_private_key_unlock_dialog_with_close_handler<F, G>(
             }
             PrivateKeyUnlockKind::Fido2SecurityKey => PrivateKeyUnlockRequest::Fido2(Some(input)),
         };
-        on_submit_for_apply(request);
+        let remember = remember_check.as_ref().is_some_and(CheckButton::is_active);
+        on_submit_for_apply(request, remember);
     });
 
     {
@@ -460,7 +500,7 @@ pub fn present_private_key_unlock_dialog_with_close_handler<F, G>(
             submitted_for_button.set(true);
             password_row_for_button.set_text("");
             dialog_handle_for_button.force_close();
-            on_submit_for_button(PrivateKeyUnlockRequest::HardwareExternal);
+            on_submit_for_button(PrivateKeyUnlockRequest::HardwareExternal, false);
         });
     }
 
@@ -0,0 +1,181 @@
+use crate::logging::{run_command_output, run_command_with_input, CommandLogOptions};
+use crate::preferences::Preferences;
+use crate::support::runtime::require_host_command_features;
+use crate::support::secrets::string_from_secret_utf8;
+use secrecy::SecretString;
+use std::process::Output;
+
+const SECRET_TOOL_PROGRAM: &str = "secret-tool";
+const KEY_PASSWORD_SECRET_TYPE: &str = "key-password";
+const APP_PIN_SECRET_TYPE: &str = "app-pin";
+
+// Key passwords go through libsecret's `secret-tool`, so they land in
+// whichever Secret Service the desktop runs, such as GNOME Keyring or KWallet.
+fn secret_attributes(secret_type: &str) -> Vec<String> {
+    vec![
+        "application".to_string(),
+        env!("CARGO_PKG_NAME").to_string(),
+        "type".to_string(),
+        secret_type.to_string(),
+    ]
+}
+
+fn key_password_attributes(fingerprint: Option<&str>) -> Vec<String> {
+    let mut attributes = secret_attributes(KEY_PASSWORD_SECRET_TYPE);
+    if let Some(fingerprint) = fingerprint {
+        attributes.push("fingerprint".to_string());
+        attributes.push(fingerprint.to_ascii_lowercase());
+    }
+    attributes
+}
+
+fn secret_tool_args<'a>(
+    command: &'a str,
+    extra: &'a [String],
+    attributes: &'a [String],
+) -> Vec<&'a str> {
+    std::iter::once(command)
+        .chain(extra.iter().map(String::as_str))
+        .chain(attributes.iter().map(String::as_str))
+        .collect()
+}
+
+fn secret_tool_error(action: &str, output: &Output) -> String {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        format!("{action} failed: {}", output.status)
+    } else {
+        format!("{action} failed: {stderr}")
+    }
+}
+
+fn run_secret_tool(args: &[&str], context: &str) -> Result<Output, String> {
+    require_host_command_features()?;
+    let mut cmd = Preferences::new().host_program_command(SECRET_TOOL_PROGRAM, args);
+    run_command_output(&mut cmd, context, CommandLogOptions::SENSITIVE)
+        .map_err(|err| format!("Failed to run {SECRET_TOOL_PROGRAM}: {err}"))
+}
+
+fn store_secret(
+    label: &str,
+    attributes: &[String],
+    secret: &str,
+    context: &str,
+) -> Result<(), String> {
+    require_host_command_features()?;
+    let label = vec!["--label".to_string(), label.to_string()];
+    let args = secret_tool_args("store", &label, attributes);
+    let mut cmd = Preferences::new().host_program_command(SECRET_TOOL_PROGRAM, &args);
+    let output = run_command_with_input(&mut cmd, context, secret, CommandLogOptions::SENSITIVE)?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(secret_tool_error("secret-tool store", &output))
+    }
+}
+
+fn lookup_secret(attributes: &[String], context: &str) -> Result<Option<SecretString>, String> {
+    let output = run_secret_tool(&secret_tool_args("lookup", &[], attributes), context)?;
+    // `secret-tool lookup` exits with 1 and prints nothing when there is no match.
+    if !output.status.success() {
+        return if output.stderr.is_empty() {
+            Ok(None)
+        } else {
+            Err(secret_tool_error("secret-tool lookup", &output))
+        };
+    }
+
+    let password = string_from_secret_utf8(output.stdout)?;
+    Ok((!password.is_empty()).then(|| SecretString::from(password)))
+}
+
+fn clear_secrets(attributes: &[String], context: &str) -> Result<(), String> {
+    let output = run_secret_tool(&secret_tool_args("clear", &[], attributes), context)?;
+    if output.status.success() || output.stderr.is_empty() {
+        Ok(())
+    } else {
+        Err(secret_tool_error("secret-tool clear", &output))
+    }
+}
+
+pub fn remember_key_password(fingerprint: &str, password: &str) -> Result<(), String> {
+    store_secret(
+        &format!("Keycord key password ({fingerprint})"),
+        &key_password_attributes(Some(fingerprint)),
+        password,
+        "Remember key password in the keyring",
+    )
+}
+
+pub fn remembered_key_password(fingerprint: &str) -> Result<Option<SecretString>, String> {
+    lookup_secret(
+        &key_password_attributes(Some(fingerprint)),
+        "Look up key password in the keyring",
+    )
+}
+
+fn clear_key_passwords(fingerprint: Option<&str>) -> Result<(), String> {
+    clear_secrets(
+        &key_password_attributes(fingerprint),
+        "Forget key passwords in the keyring",
+    )
+}
+
+pub fn forget_key_password(fingerprint: &str) -> Result<(), String> {
+    clear_key_passwords(Some(fingerprint))
+}
+
+pub fn forget_all_key_passwords() -> Result<(), String> {
+    clear_key_passwords(None)
+}
+
+// The app PIN's hash is kept next to the key passwords, so it can't be read
+// or swapped out through the preferences file.
+pub fn remember_app_pin_hash(hash: &str) -> Result<(), String> {
+    store_secret(
+        "Keycord app PIN",
+        &secret_attributes(APP_PIN_SECRET_TYPE),
+        hash,
+        "Remember app PIN in the keyring",
+    )
+}
+
+pub fn remembered_app_pin_hash() -> Result<Option<SecretString>, String> {
+    lookup_secret(
+        &secret_attributes(APP_PIN_SECRET_TYPE),
+        "Look up app PIN in the keyring",
+    )
+}
+
+pub fn forget_app_pin_hash() -> Result<(), String> {
+    clear_secrets(
+        &secret_attributes(APP_PIN_SECRET_TYPE),
+        "Forget app PIN in the keyring",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{key_password_attributes, secret_tool_args};
+
+    #[test]
+    fn key_password_lookups_are_scoped_to_keycord_and_the_key() {
+        let attributes = key_password_attributes(Some("ABCDEF"));
+        assert_eq!(
+            secret_tool_args("lookup", &[], &attributes),
+            vec![
+                "lookup",
+                "application",
+                "keycord",
+                "type",
+                "key-password",
+                "fingerprint",
+                "abcdef"
+            ]
+        );
+        assert_eq!(
+            secret_tool_args("clear", &[], &key_password_attributes(None)),
+            vec!["clear", "application", "keycord", "type", "key-password"]
+        );
+    }
+}
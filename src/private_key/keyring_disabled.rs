@@ -0,0 +1,31 @@
+use secrecy::SecretString;
+
+const KEYRING_UNSUPPORTED: &str = "The system keyring isn't available on this platform.";
+
+pub fn remember_key_password(_fingerprint: &str, _password: &str) -> Result<(), String> {
+    Err(KEYRING_UNSUPPORTED.to_string())
+}
+
+pub const fn remembered_key_password(_fingerprint: &str) -> Result<Option<SecretString>, String> {
+    Ok(None)
+}
+
+pub const fn forget_key_password(_fingerprint: &str) -> Result<(), String> {
+    Ok(())
+}
+
+pub const fn forget_all_key_passwords() -> Result<(), String> {
+    Ok(())
+}
+
+pub fn remember_app_pin_hash(_hash: &str) -> Result<(), String> {
+    Err(KEYRING_UNSUPPORTED.to_string())
+}
+
+pub const fn remembered_app_pin_hash() -> Result<Option<SecretString>, String> {
+    Ok(None)
+}
+
+pub const fn forget_app_pin_hash() -> Result<(), String> {
+    Ok(())
+}
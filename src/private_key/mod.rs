@@ -1,4 +1,9 @@
 pub mod dialog;
 pub mod git;
+#[cfg(target_os = "linux")]
+pub mod keyring;
+#[cfg(not(target_os = "linux"))]
+#[path = "keyring_disabled.rs"]
+pub mod keyring;
 pub mod sync;
 pub mod unlock;
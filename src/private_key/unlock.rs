@@ -7,15 +7,22 @@ use crate::backend::{
 use crate::fido2_recipient::{fido2_recipient_title, is_fido2_recipient_string};
 use crate::i18n::gettext;
 use crate::logging::log_error;
+use crate::preferences::Preferences;
 use crate::private_key::dialog::{
     build_private_key_progress_dialog, present_fido2_pin_setup_dialog_with_close_handler,
-    present_private_key_unlock_dialog_with_close_handler, PrivateKeyDialogHandle,
+    present_private_key_unlock_dialog_with_close_handler,
+    present_private_key_unlock_dialog_with_remember_option, PrivateKeyDialogHandle,
+};
+use crate::private_key::keyring::{
+    forget_key_password, remember_key_password, remembered_key_password,
 };
 use crate::support::actions::activate_widget_action;
-use crate::support::background::spawn_result_task_with_finalizer;
+use crate::support::background::{spawn_result_task, spawn_result_task_with_finalizer};
+use crate::support::runtime::supports_host_command_features;
 use adw::{glib, prelude::*, ApplicationWindow, Toast, ToastOverlay};
 use secrecy::ExposeSecret;
 use std::rc::Rc;
+use zeroize::Zeroizing;
 
 fn toast_overlay_window(overlay: &ToastOverlay) -> Option<ApplicationWindow> {
     overlay
@@ -150,6 +157,7 @@ fn handle_managed_fido2_unlock_retry(
                 &overlay_for_submit,
                 fingerprint_for_submit.clone(),
                 request,
+                KeyPasswordSource::Prompt,
                 &after_unlock_for_submit,
                 &on_finish_for_submit,
             );
@@ -327,11 +335,31 @@ fn start_private_key_fido2_pin_setup_for_action(
     });
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KeyPasswordSource {
+    Prompt,
+    PromptAndRemember,
+    Keyring,
+}
+
+fn password_to_remember(
+    request: &PrivateKeyUnlockRequest,
+    source: KeyPasswordSource,
+) -> Option<Zeroizing<String>> {
+    match (request, source) {
+        (PrivateKeyUnlockRequest::Password(password), KeyPasswordSource::PromptAndRemember) => {
+            Some(Zeroizing::new(password.expose_secret().to_string()))
+        }
+        _ => None,
+    }
+}
+
 fn start_private_key_unlock_for_action(
     window: &ApplicationWindow,
     overlay: &ToastOverlay,
     fingerprint: String,
     request: PrivateKeyUnlockRequest,
+    source: KeyPasswordSource,
     after_unlock: &Rc<dyn Fn()>,
     on_finish: &Rc<dyn Fn(bool)>,
 ) {
@@ -357,12 +385,48 @@ fn start_private_key_unlock_for_action(
 
     glib::idle_add_local_once(move || {
         spawn_result_task_with_finalizer(
-            move || unlock_ripasso_private_key_for_session(&fingerprint_for_worker, request),
+            move || {
+                let password = password_to_remember(&request, source);
+                let result =
+                    unlock_ripasso_private_key_for_session(&fingerprint_for_worker, request);
+                let remembered = match (&result, password) {
+                    (Ok(_), Some(password)) => {
+                        Some(remember_key_password(&fingerprint_for_worker, &password))
+                    }
+                    _ => None,
+                };
+                (result, remembered)
+            },
             move || progress_dialog.force_close(),
-            move |result: Result<ManagedRipassoPrivateKey, PrivateKeyError>| match result {
+            move |(result, remembered): (
+                Result<ManagedRipassoPrivateKey, PrivateKeyError>,
+                Option<Result<(), String>>,
+            )| match result {
                 Ok(_) => {
+                    if let Some(Err(err)) = remembered {
+                        log_error(format!("Failed to remember key password: {err}"));
+                        overlay.add_toast(Toast::new(&gettext(
+                            "Couldn't save the key password in the keyring.",
+                        )));
+                    }
                     finish_unlock_success(&window_for_result, &after_unlock, &on_finish_for_result);
                 }
+                // A remembered password that no longer works is dropped, and
+                // the usual prompt takes over.
+                Err(err) if source == KeyPasswordSource::Keyring => {
+                    log_error(format!(
+                        "Failed to unlock ripasso private key with the remembered password: {err}"
+                    ));
+                    forget_remembered_key_password(fingerprint.clone());
+                    present_private_key_password_prompt(
+                        &window_for_result,
+                        &overlay,
+                        fingerprint.clone(),
+                        kind,
+                        after_unlock.clone(),
+                        on_finish_for_result.clone(),
+                    );
+                }
                 Err(err)
                     if err.is_fido2_pin_not_set()
                         && handle_managed_fido2_pin_setup_retry(
@@ -573,19 +637,6 @@ pub fn prompt_private_key_unlock_for_action(
         on_finish(false);
         return;
     };
-    let key_title = if is_fido2_recipient_string(&fingerprint) {
-        fido2_recipient_title(&fingerprint)
-    } else {
-        match ripasso_private_key_title(&fingerprint) {
-            Ok(title) => Some(title),
-            Err(err) => {
-                log_error(format!(
-                    "Failed to read private key title for '{fingerprint}': {err}"
-                ));
-                None
-            }
-        }
-    };
     let kind = private_key_unlock_kind(&fingerprint);
     if is_fido2_recipient_string(&fingerprint) {
         start_fido2_recipient_unlock_for_action(
@@ -605,27 +656,139 @@ pub fn prompt_private_key_unlock_for_action(
             overlay,
             fingerprint,
             PrivateKeyUnlockRequest::Fido2(None),
+            KeyPasswordSource::Prompt,
             &after_unlock,
             &on_finish,
         );
         return;
     }
 
+    if !offers_keyring(kind) {
+        present_private_key_password_prompt(
+            &window,
+            overlay,
+            fingerprint,
+            kind,
+            after_unlock,
+            on_finish,
+        );
+        return;
+    }
+
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    let window_for_disconnect = window.clone();
+    let fingerprint_for_lookup = fingerprint.clone();
+    let fingerprint_for_disconnect = fingerprint.clone();
+    let after_unlock_for_disconnect = after_unlock.clone();
+    let on_finish_for_disconnect = on_finish.clone();
+    spawn_result_task(
+        move || remembered_key_password(&fingerprint_for_lookup),
+        move |result| match result {
+            Ok(Some(password)) => start_private_key_unlock_for_action(
+                &window,
+                &overlay,
+                fingerprint,
+                PrivateKeyUnlockRequest::Password(password),
+                KeyPasswordSource::Keyring,
+                &after_unlock,
+                &on_finish,
+            ),
+            Ok(None) => {
+                present_private_key_password_prompt(
+                    &window,
+                    &overlay,
+                    fingerprint,
+                    kind,
+                    after_unlock,
+                    on_finish,
+                );
+            }
+            Err(err) => {
+                log_error(format!(
+                    "Failed to look up the remembered key password: {err}"
+                ));
+                present_private_key_password_prompt(
+                    &window,
+                    &overlay,
+                    fingerprint,
+                    kind,
+                    after_unlock,
+                    on_finish,
+                );
+            }
+        },
+        move || {
+            present_private_key_password_prompt(
+                &window_for_disconnect,
+                &overlay_for_disconnect,
+                fingerprint_for_disconnect,
+                kind,
+                after_unlock_for_disconnect,
+                on_finish_for_disconnect,
+            );
+        },
+    );
+}
+
+fn offers_keyring(kind: PrivateKeyUnlockKind) -> bool {
+    matches!(kind, PrivateKeyUnlockKind::Password)
+        && supports_host_command_features()
+        && Preferences::new().remember_key_passwords()
+}
+
+fn forget_remembered_key_password(fingerprint: String) {
+    spawn_result_task(
+        move || forget_key_password(&fingerprint),
+        |result| {
+            if let Err(err) = result {
+                log_error(format!(
+                    "Failed to forget the remembered key password: {err}"
+                ));
+            }
+        },
+        || {},
+    );
+}
+
+fn present_private_key_password_prompt(
+    window: &ApplicationWindow,
+    overlay: &ToastOverlay,
+    fingerprint: String,
+    kind: PrivateKeyUnlockKind,
+    after_unlock: Rc<dyn Fn()>,
+    on_finish: Rc<dyn Fn(bool)>,
+) {
+    let key_title = match ripasso_private_key_title(&fingerprint) {
+        Ok(title) => Some(title),
+        Err(err) => {
+            log_error(format!(
+                "Failed to read private key title for '{fingerprint}': {err}"
+            ));
+            None
+        }
+    };
     let window_for_submit = window.clone();
     let overlay_for_submit = overlay.clone();
     let on_finish_for_close = on_finish.clone();
-    present_private_key_unlock_dialog_with_close_handler(
-        &window,
-        overlay,
+    present_private_key_unlock_dialog_with_remember_option(
+        window,
         "Unlock key",
         key_title.as_deref(),
         kind,
-        move |request| {
+        offers_keyring(kind),
+        move |request, remember| {
+            let source = if remember {
+                KeyPasswordSource::PromptAndRemember
+            } else {
+                KeyPasswordSource::Prompt
+            };
             start_private_key_unlock_for_action(
                 &window_for_submit,
                 &overlay_for_submit,
                 fingerprint.clone(),
                 request,
+                source,
                 &after_unlock,
                 &on_finish,
             );
@@ -636,8 +799,9 @@ pub fn prompt_private_key_unlock_for_action(
 
 #[cfg(test)]
 mod tests {
-    use super::private_key_unlock_progress_description;
-    use crate::backend::PrivateKeyUnlockKind;
+    use super::{password_to_remember, private_key_unlock_progress_description, KeyPasswordSource};
+    use crate::backend::{PrivateKeyUnlockKind, PrivateKeyUnlockRequest};
+    use secrecy::SecretString;
 
     #[test]
     fn unlock_progress_copy_is_fido_specific_only_for_fido_keys() {
@@ -654,4 +818,22 @@ mod tests {
             "Wait a moment."
         );
     }
+
+    #[test]
+    fn only_ticked_password_prompts_are_remembered() {
+        let password = || PrivateKeyUnlockRequest::Password(SecretString::from("hunter2"));
+        assert_eq!(
+            password_to_remember(&password(), KeyPasswordSource::PromptAndRemember)
+                .as_deref()
+                .map(String::as_str),
+            Some("hunter2")
+        );
+        assert!(password_to_remember(&password(), KeyPasswordSource::Prompt).is_none());
+        assert!(password_to_remember(&password(), KeyPasswordSource::Keyring).is_none());
+        assert!(password_to_remember(
+            &PrivateKeyUnlockRequest::HardwareExternal,
+            KeyPasswordSource::PromptAndRemember
+        )
+        .is_none());
+    }
 }
@@ -1,15 +1,22 @@
+use crate::backend::{
+    preferred_ripasso_private_key_fingerprint_for_entry,
+    ripasso_private_key_requires_session_unlock,
+};
+use crate::i18n::{gettext, pgettext};
 use crate::logging::{log_error, log_info};
 use crate::password::model::{
     collect_all_password_items_with_options, CollectItemsOptions, PassEntry,
 };
-use crate::store::labels::shortened_store_labels;
+use crate::preferences::Preferences;
+use crate::store::labels::StoreLabels;
+use crate::support::fuzzy::fuzzy_term_score;
 
 use adw::gio::{self, BusNameOwnerFlags, BusType, DBusConnection, DBusInterfaceInfo, DBusNodeInfo};
 use adw::glib::{self, ExitCode, MainLoop, Variant};
 use adw::prelude::ToVariant;
 use sha2::{Digest, Sha256};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::process::Command;
 use std::rc::Rc;
@@ -20,6 +27,8 @@ const SEARCH_PROVIDER_OBJECT_PATH: &str = env!("SEARCH_PROVIDER_OBJECT_PATH");
 const SEARCH_PROVIDER_INTERFACE: &str = "org.gnome.Shell.SearchProvider2";
 const SEARCH_PROVIDER_RESULT_LIMIT: usize = 24;
 const RESULT_ID_SEPARATOR: char = '\u{1f}';
+const ENTRY_RESULT_ICON: &str = "dialog-password-symbolic";
+const FOLDER_RESULT_ICON: &str = "folder-symbolic";
 const SEARCH_PROVIDER_XML: &str = r#"
 <node>
   <interface name="org.gnome.Shell.SearchProvider2">
@@ -54,6 +63,7 @@ pub(crate) fn is_search_provider_command(args: &[OsString]) -> bool {
 }
 
 pub(crate) fn run() -> ExitCode {
+    crate::i18n::init();
     let node_info = match DBusNodeInfo::for_xml(SEARCH_PROVIDER_XML) {
         Ok(node_info) => node_info,
         Err(err) => {
@@ -155,16 +165,19 @@ fn handle_get_initial_result_set(parameters: &Variant) -> Result<Option<Variant>
         return Ok(Some((Vec::<String>::new(),).to_variant()));
     };
 
-    Ok(Some((search_result_ids(&terms),).to_variant()))
+    Ok(Some((search_result_ids(&terms, None),).to_variant()))
 }
 
 fn handle_get_subsearch_result_set(parameters: &Variant) -> Result<Option<Variant>, glib::Error> {
-    let Some((_previous_results, terms)) = parameters.get::<(Vec<String>, Vec<String>)>() else {
+    let Some((previous_results, terms)) = parameters.get::<(Vec<String>, Vec<String>)>() else {
         log_error("Search provider GetSubsearchResultSet received invalid parameters.".to_string());
         return Ok(Some((Vec::<String>::new(),).to_variant()));
     };
 
-    Ok(Some((search_result_ids(&terms),).to_variant()))
+    let previous_results = previous_results.into_iter().collect::<HashSet<_>>();
+    Ok(Some(
+        (search_result_ids(&terms, Some(&previous_results)),).to_variant(),
+    ))
 }
 
 fn handle_get_result_metas(parameters: &Variant) -> Result<Option<Variant>, glib::Error> {
@@ -174,10 +187,12 @@ fn handle_get_result_metas(parameters: &Variant) -> Result<Option<Variant>, glib
     };
 
     let store_labels = store_label_map();
+    let entries = collect_all_password_items_with_options(CollectItemsOptions::default());
+    let locked_stores = locked_store_paths(&entries);
     let metas = identifiers
         .into_iter()
         .map(|identifier| {
-            meta_for_identifier(&identifier, &store_labels)
+            meta_for_identifier(&identifier, &entries, &store_labels, &locked_stores)
                 .unwrap_or_else(|| fallback_meta(&identifier))
         })
         .collect::<Vec<_>>();
@@ -191,13 +206,18 @@ fn handle_activate_result(parameters: &Variant) -> Result<Option<Variant>, glib:
         return Ok(None);
     };
 
-    match decode_result_target(&identifier) {
-        Some((store_path, label)) => {
+    match decode_result_id(&identifier) {
+        Some(SearchProviderResult::Folder { relative_path, .. }) => {
+            if let Err(err) = launch_search_query(&relative_path) {
+                log_error(format!("Failed to launch Keycord folder search: {err}"));
+            }
+        }
+        Some(SearchProviderResult::Entry(entry)) => {
             if let Err(err) = launch_app(
                 [
-                    OsString::from("--open-entry"),
-                    OsString::from(store_path),
-                    OsString::from(label),
+                    OsString::from("--copy-entry"),
+                    OsString::from(entry.store_path),
+                    OsString::from(entry.label()),
                 ]
                 .as_slice(),
             ) {
@@ -231,20 +251,68 @@ fn handle_launch_search(parameters: &Variant) -> Result<Option<Variant>, glib::E
     Ok(None)
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SearchProviderResult {
+    Entry(PassEntry),
+    Folder {
+        store_path: String,
+        relative_path: String,
+    },
+}
+
+impl SearchProviderResult {
+    fn store_path(&self) -> &str {
+        match self {
+            Self::Entry(entry) => &entry.store_path,
+            Self::Folder { store_path, .. } => store_path,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Self::Entry(entry) => entry.basename.clone(),
+            Self::Folder { relative_path, .. } => relative_path.trim_end_matches('/').to_string(),
+        }
+    }
+
+    const fn icon(&self) -> &'static str {
+        match self {
+            Self::Entry(_) => ENTRY_RESULT_ICON,
+            Self::Folder { .. } => FOLDER_RESULT_ICON,
+        }
+    }
+
+    fn id(&self) -> String {
+        match self {
+            Self::Entry(entry) => encode_result_id(&entry.store_path, &entry.label()),
+            Self::Folder {
+                store_path,
+                relative_path,
+            } => encode_result_id(store_path, relative_path),
+        }
+    }
+}
+
 fn meta_for_identifier(
     identifier: &str,
+    entries: &[PassEntry],
     store_labels: &HashMap<String, String>,
+    locked_stores: &HashSet<String>,
 ) -> Option<HashMap<String, Variant>> {
-    let entry = decode_result_id(identifier)?;
+    let result = find_result(identifier, entries)?;
 
     let mut meta = HashMap::new();
     meta.insert("id".to_string(), identifier.to_variant());
-    meta.insert("name".to_string(), entry.basename.to_variant());
-    let description = entry_description(&entry, store_labels);
+    meta.insert("name".to_string(), result.name().to_variant());
+    let description = result_description(
+        &result,
+        store_labels.get(result.store_path()).map(String::as_str),
+        locked_stores.contains(result.store_path()),
+    );
     if !description.is_empty() {
         meta.insert("description".to_string(), description.to_variant());
     }
-    meta.insert("gicon".to_string(), APP_ID.to_variant());
+    meta.insert("gicon".to_string(), result.icon().to_variant());
     Some(meta)
 }
 
@@ -256,17 +324,59 @@ fn fallback_meta(identifier: &str) -> HashMap<String, Variant> {
     meta
 }
 
-fn entry_description(entry: &PassEntry, store_labels: &HashMap<String, String>) -> String {
-    store_labels
-        .get(&entry.store_path)
-        .cloned()
-        .unwrap_or_default()
+fn result_description(
+    result: &SearchProviderResult,
+    store_label: Option<&str>,
+    locked: bool,
+) -> String {
+    let mut parts = Vec::new();
+    if matches!(result, SearchProviderResult::Folder { .. }) {
+        parts.push(gettext("Folder"));
+    }
+    if let Some(store_label) = store_label.filter(|label| !label.is_empty()) {
+        parts.push(store_label.to_string());
+    }
+    if locked {
+        parts.push(pgettext("item state", "Locked"));
+    }
+    parts.join(" · ")
+}
+
+fn store_is_locked(entry: &PassEntry) -> bool {
+    if !Preferences::new().uses_integrated_backend() {
+        return false;
+    }
+
+    preferred_ripasso_private_key_fingerprint_for_entry(&entry.store_path, &entry.label())
+        .and_then(|fingerprint| ripasso_private_key_requires_session_unlock(&fingerprint))
+        .unwrap_or(false)
+}
+
+fn locked_store_paths(entries: &[PassEntry]) -> HashSet<String> {
+    let mut checked = HashSet::new();
+    let mut locked = HashSet::new();
+    for entry in entries {
+        if !checked.insert(entry.store_path.clone()) {
+            continue;
+        }
+        if store_is_locked(entry) {
+            locked.insert(entry.store_path.clone());
+        }
+    }
+    locked
 }
 
 fn store_label_map() -> HashMap<String, String> {
-    let stores = crate::preferences::Preferences::new().store_roots();
-    let labels = shortened_store_labels(&stores);
-    stores.into_iter().zip(labels).collect()
+    let settings = Preferences::new();
+    let stores = settings.store_roots();
+    let labels = StoreLabels::for_stores(&settings, &stores);
+    stores
+        .into_iter()
+        .map(|store| {
+            let label = labels.label(&store);
+            (store, label)
+        })
+        .collect()
 }
 
 fn join_search_terms(terms: &[String]) -> String {
@@ -296,16 +406,11 @@ fn launch_app(args: &[OsString]) -> Result<(), String> {
         .map_err(|err| format!("Failed to spawn Keycord: {err}"))
 }
 
-fn decode_result_target(identifier: &str) -> Option<(String, String)> {
-    let entry = decode_result_id(identifier)?;
-    Some((entry.store_path.clone(), entry.label()))
-}
-
-fn encode_result_id(entry: &PassEntry) -> String {
+fn encode_result_id(store_path: &str, target: &str) -> String {
     let mut digest = Sha256::new();
-    digest.update(entry.store_path.as_bytes());
+    digest.update(store_path.as_bytes());
     digest.update([RESULT_ID_SEPARATOR as u8]);
-    digest.update(entry.label().as_bytes());
+    digest.update(target.as_bytes());
     digest
         .finalize()
         .into_iter()
@@ -313,81 +418,170 @@ fn encode_result_id(entry: &PassEntry) -> String {
         .collect()
 }
 
-fn decode_result_id(identifier: &str) -> Option<PassEntry> {
-    if identifier.len() != 64 || !identifier.chars().all(|c| c.is_ascii_hexdigit()) {
+fn is_result_id(identifier: &str) -> bool {
+    identifier.len() == 64 && identifier.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn decode_result_id(identifier: &str) -> Option<SearchProviderResult> {
+    if !is_result_id(identifier) {
         return None;
     }
 
-    collect_all_password_items_with_options(CollectItemsOptions::default())
-        .into_iter()
-        .find(|entry| encode_result_id(entry) == identifier)
+    find_result(
+        identifier,
+        &collect_all_password_items_with_options(CollectItemsOptions::default()),
+    )
+}
+
+fn find_result(identifier: &str, entries: &[PassEntry]) -> Option<SearchProviderResult> {
+    if !is_result_id(identifier) {
+        return None;
+    }
+
+    entries.iter().find_map(|entry| {
+        let result = SearchProviderResult::Entry(entry.clone());
+        if result.id() == identifier {
+            return Some(result);
+        }
+        entry_folders(entry)
+            .into_iter()
+            .map(|relative_path| SearchProviderResult::Folder {
+                store_path: entry.store_path.clone(),
+                relative_path,
+            })
+            .find(|folder| folder.id() == identifier)
+    })
 }
 
-fn search_provider_entries(terms: &[String], limit: usize) -> Vec<PassEntry> {
+fn entry_folders(entry: &PassEntry) -> Vec<String> {
+    entry
+        .relative_path
+        .match_indices('/')
+        .map(|(index, _)| entry.relative_path[..=index].to_string())
+        .collect()
+}
+
+// Matches are ranked by how closely every term fits, so "gh alice" puts
+// `sites/github.com/alice` first. A refinement only looks at the results
+// Shell already has.
+fn search_provider_results(
+    terms: &[String],
+    limit: usize,
+    previous_results: Option<&HashSet<String>>,
+) -> Vec<SearchProviderResult> {
     let terms = normalized_search_terms(terms);
     if terms.is_empty() {
         return Vec::new();
     }
 
     let store_labels = store_label_map();
+    let mut seen_folders = HashSet::new();
     let mut matches = Vec::new();
     for entry in collect_all_password_items_with_options(CollectItemsOptions::default()) {
-        if !search_provider_entry_matches(
-            &entry,
-            store_labels.get(&entry.store_path).map(String::as_str),
-            &terms,
-        ) {
-            continue;
+        let store_label = store_labels.get(&entry.store_path).map(String::as_str);
+        for relative_path in entry_folders(&entry) {
+            if !seen_folders.insert((entry.store_path.clone(), relative_path.clone())) {
+                continue;
+            }
+            if let Some(score) = search_provider_folder_score(&relative_path, store_label, &terms) {
+                matches.push((
+                    score,
+                    SearchProviderResult::Folder {
+                        store_path: entry.store_path.clone(),
+                        relative_path,
+                    },
+                ));
+            }
         }
 
-        matches.push(entry);
-        if matches.len() >= limit {
-            break;
+        if let Some(score) = search_provider_entry_score(&entry, store_label, &terms) {
+            matches.push((score, SearchProviderResult::Entry(entry)));
         }
     }
 
+    if let Some(previous_results) = previous_results {
+        matches.retain(|(_, result)| previous_results.contains(&result.id()));
+    }
+    ranked_search_provider_results(matches, limit)
+}
+
+fn ranked_search_provider_results(
+    mut matches: Vec<(u32, SearchProviderResult)>,
+    limit: usize,
+) -> Vec<SearchProviderResult> {
+    matches.sort_by(|(left_score, left), (right_score, right)| {
+        right_score
+            .cmp(left_score)
+            .then_with(|| left.name().len().cmp(&right.name().len()))
+    });
     matches
+        .into_iter()
+        .map(|(_, result)| result)
+        .take(limit)
+        .collect()
 }
 
 fn normalized_search_terms(terms: &[String]) -> Vec<String> {
     terms
         .iter()
-        .map(|term| term.trim().to_ascii_lowercase())
+        .map(|term| term.trim().to_lowercase())
         .filter(|term| !term.is_empty())
         .collect()
 }
 
-fn search_provider_entry_matches(
+fn term_score(text: &str, store_label: &str, term: &str) -> Option<u32> {
+    fuzzy_term_score(text, term).max(fuzzy_term_score(store_label, term))
+}
+
+fn search_provider_entry_score(
     entry: &PassEntry,
     store_label: Option<&str>,
     terms: &[String],
-) -> bool {
-    let label = entry.label().to_ascii_lowercase();
-    let store_label = store_label.unwrap_or_default().to_ascii_lowercase();
-    terms
+) -> Option<u32> {
+    let label = entry.label();
+    let store_label = store_label.unwrap_or_default();
+    terms.iter().try_fold(0, |total, term| {
+        Some(total + term_score(&label, store_label, term)?)
+    })
+}
+
+fn search_provider_folder_score(
+    relative_path: &str,
+    store_label: Option<&str>,
+    terms: &[String],
+) -> Option<u32> {
+    if !terms
         .iter()
-        .all(|term| label.contains(term) || store_label.contains(term))
+        .any(|term| fuzzy_term_score(relative_path, term).is_some())
+    {
+        return None;
+    }
+    let store_label = store_label.unwrap_or_default();
+    terms.iter().try_fold(0, |total, term| {
+        Some(total + term_score(relative_path, store_label, term)?)
+    })
 }
 
-fn search_result_ids(terms: &[String]) -> Vec<String> {
-    search_provider_entries(terms, SEARCH_PROVIDER_RESULT_LIMIT)
-        .into_iter()
-        .map(|entry| encode_result_id(&entry))
+fn search_result_ids(terms: &[String], previous_results: Option<&HashSet<String>>) -> Vec<String> {
+    search_provider_results(terms, SEARCH_PROVIDER_RESULT_LIMIT, previous_results)
+        .iter()
+        .map(SearchProviderResult::id)
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        decode_result_id, encode_result_id, join_search_terms, normalized_search_terms,
-        search_provider_entry_matches,
+        decode_result_id, entry_folders, find_result, join_search_terms, normalized_search_terms,
+        ranked_search_provider_results, result_description, search_provider_entry_score,
+        search_provider_folder_score, SearchProviderResult, ENTRY_RESULT_ICON, FOLDER_RESULT_ICON,
     };
     use crate::password::model::PassEntry;
 
     #[test]
     fn result_ids_are_opaque_hashes() {
         let entry = PassEntry::from_label("/tmp/store", "work/alice/github");
-        let identifier = encode_result_id(&entry);
+        let identifier = SearchProviderResult::Entry(entry).id();
 
         assert_eq!(identifier.len(), 64);
         assert!(identifier.chars().all(|c| c.is_ascii_hexdigit()));
@@ -422,15 +616,98 @@ mod tests {
     fn shell_search_matches_labels_and_store_labels_only() {
         let entry = PassEntry::from_label("/tmp/store", "work/alice/github");
 
-        assert!(search_provider_entry_matches(
+        assert!(search_provider_entry_score(
             &entry,
             Some("Work"),
             &["alice".to_string(), "work".to_string()]
-        ));
-        assert!(!search_provider_entry_matches(
-            &entry,
+        )
+        .is_some());
+        assert!(
+            search_provider_entry_score(&entry, Some("Work"), &["example.com".to_string()])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn folder_results_resolve_separately_from_entries() {
+        let entry = PassEntry::from_label("/tmp/store", "work/alice/github");
+        assert_eq!(
+            entry_folders(&entry),
+            vec!["work/".to_string(), "work/alice/".to_string()]
+        );
+
+        let folder = SearchProviderResult::Folder {
+            store_path: "/tmp/store".to_string(),
+            relative_path: "work/alice/".to_string(),
+        };
+        let entries = vec![entry.clone()];
+        assert_eq!(find_result(&folder.id(), &entries), Some(folder.clone()));
+        assert_eq!(
+            find_result(&SearchProviderResult::Entry(entry.clone()).id(), &entries),
+            Some(SearchProviderResult::Entry(entry.clone()))
+        );
+        assert_ne!(folder.id(), SearchProviderResult::Entry(entry.clone()).id());
+        assert_eq!(folder.name(), "work/alice");
+        assert_eq!(folder.icon(), FOLDER_RESULT_ICON);
+        assert_eq!(SearchProviderResult::Entry(entry).icon(), ENTRY_RESULT_ICON);
+    }
+
+    #[test]
+    fn folder_matches_need_a_term_in_the_folder_path() {
+        assert!(search_provider_folder_score(
+            "work/alice/",
             Some("Work"),
-            &["example.com".to_string()]
-        ));
+            &["alice".to_string(), "work".to_string()]
+        )
+        .is_some());
+        assert!(
+            search_provider_folder_score("personal/", Some("Work"), &["work".to_string()])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn shell_search_ranks_fuzzy_multi_term_matches() {
+        let terms = normalized_search_terms(&["GH".to_string(), "alice".to_string()]);
+        let close = PassEntry::from_label("/tmp/store", "sites/github.com/alice");
+        let spread = PassEntry::from_label("/tmp/store", "sites/gmail.com/thalice");
+        let other = PassEntry::from_label("/tmp/store", "sites/github.com/bob");
+
+        let close_score = search_provider_entry_score(&close, None, &terms);
+        let spread_score = search_provider_entry_score(&spread, None, &terms);
+        assert!(close_score.is_some());
+        assert!(spread_score.is_some());
+        assert_eq!(search_provider_entry_score(&other, None, &terms), None);
+
+        let ranked = ranked_search_provider_results(
+            vec![
+                (
+                    spread_score.unwrap_or_default(),
+                    SearchProviderResult::Entry(spread),
+                ),
+                (
+                    close_score.unwrap_or_default(),
+                    SearchProviderResult::Entry(close.clone()),
+                ),
+            ],
+            1,
+        );
+        assert_eq!(ranked, vec![SearchProviderResult::Entry(close)]);
+    }
+
+    #[test]
+    fn result_descriptions_show_the_store_and_lock_state() {
+        let entry = SearchProviderResult::Entry(PassEntry::from_label("/tmp/store", "github"));
+        let folder = SearchProviderResult::Folder {
+            store_path: "/tmp/store".to_string(),
+            relative_path: "work/".to_string(),
+        };
+
+        assert_eq!(result_description(&entry, Some("Work"), false), "Work");
+        assert_eq!(
+            result_description(&entry, Some("Work"), true),
+            "Work · Locked"
+        );
+        assert_eq!(result_description(&folder, None, false), "Folder");
     }
 }
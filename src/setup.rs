@@ -1,3 +1,4 @@
+use crate::logging::log_debug;
 use adw::gio::{self, ResourceLookupFlags};
 use std::io::{Error, ErrorKind};
 use std::os::unix::fs::PermissionsExt;
@@ -257,6 +258,11 @@ Icon={APP_ID}
 Terminal=false
 Categories=System;Security;
 StartupNotify=true
+Actions=synchronize;
+
+[Desktop Action synchronize]
+Name=Synchronize
+Exec={exec} --sync
 ",
     );
 
@@ -313,7 +319,7 @@ Exec={} --search-provider
 
 fn extract_icon(apps_dir: &Path) -> std::io::Result<()> {
     let resource_path = format!("{}/scalable/apps/{}.svg", RESOURCE_ID, APP_ID);
-    println!("Looking up resource: {resource_path}");
+    log_debug(format!("Looking up resource: {resource_path}"));
     let bytes = gio::resources_lookup_data(&resource_path, ResourceLookupFlags::NONE)
         .map_err(|e| Error::new(ErrorKind::NotFound, format!("Resource not found: {e}")))?;
     let out_path = apps_dir.join(format!("{}.svg", APP_ID));
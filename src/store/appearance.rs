@@ -0,0 +1,201 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::{Preferences, StoreAppearance};
+use crate::store::recipients_page::StoreRecipientsPageState;
+use crate::support::actions::activate_widget_action;
+use crate::support::ui::dim_label_icon;
+use adw::glib::markup_escape_text;
+use adw::gtk::{Image, StringList};
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog, ComboRow, EntryRow, PreferencesGroup, Toast};
+
+// The GNOME accent colors, so a store chip sits well next to the rest of the
+// app in light and dark styles.
+const STORE_COLORS: [(&str, &str, &str); 9] = [
+    ("blue", "Blue", "#3584e4"),
+    ("teal", "Teal", "#2190a4"),
+    ("green", "Green", "#3a944a"),
+    ("yellow", "Yellow", "#c88800"),
+    ("orange", "Orange", "#ed5b00"),
+    ("red", "Red", "#e62d42"),
+    ("pink", "Pink", "#d56199"),
+    ("purple", "Purple", "#9141ac"),
+    ("slate", "Slate", "#6f8396"),
+];
+
+pub fn store_color_hex(color: &str) -> Option<&'static str> {
+    STORE_COLORS
+        .iter()
+        .find(|(name, _, _)| *name == color)
+        .map(|(_, _, hex)| *hex)
+}
+
+pub fn store_chip_markup(color_hex: &str) -> String {
+    format!(
+        "<span foreground=\"{}\">●</span>",
+        markup_escape_text(color_hex)
+    )
+}
+
+// The entry header names the store only when it has a label of its own, so
+// single-store setups keep the plain item path. Both are escaped, since the
+// subtitle is read as markup.
+pub fn store_entry_subtitle(store: &str, label: &str) -> String {
+    let label = markup_escape_text(label);
+    Preferences::new()
+        .store_appearance(store)
+        .filter(|appearance| !appearance.label.is_empty())
+        .map_or_else(
+            || label.to_string(),
+            |appearance| format!("{} · {label}", markup_escape_text(&appearance.label)),
+        )
+}
+
+fn color_combo_position(color: &str) -> u32 {
+    STORE_COLORS
+        .iter()
+        .position(|(name, _, _)| *name == color)
+        .and_then(|position| u32::try_from(position + 1).ok())
+        .unwrap_or(0)
+}
+
+fn color_for_combo_position(position: u32) -> String {
+    usize::try_from(position)
+        .ok()
+        .and_then(|position| position.checked_sub(1))
+        .and_then(|index| STORE_COLORS.get(index))
+        .map(|(name, _, _)| (*name).to_string())
+        .unwrap_or_default()
+}
+
+fn color_combo_model() -> StringList {
+    let labels = std::iter::once(gettext("None"))
+        .chain(STORE_COLORS.iter().map(|(_, label, _)| gettext(label)))
+        .collect::<Vec<_>>();
+    let label_refs = labels.iter().map(String::as_str).collect::<Vec<_>>();
+    StringList::new(&label_refs)
+}
+
+fn appearance_row_subtitle(appearance: Option<&StoreAppearance>) -> String {
+    appearance
+        .filter(|appearance| !appearance.label.is_empty())
+        .map_or_else(
+            || gettext("Give this store a name and color in the item list."),
+            |appearance| appearance.label.clone(),
+        )
+}
+
+pub fn append_store_appearance_row(state: &StoreRecipientsPageState, store: &str) {
+    let appearance = Preferences::new().store_appearance(store);
+    let row = ActionRow::builder()
+        .title(gettext("Label and color"))
+        .subtitle(appearance_row_subtitle(appearance.as_ref()))
+        .build();
+    row.set_activatable(true);
+    row.add_prefix(&dim_label_icon("color-select-symbolic"));
+    row.add_suffix(&Image::from_icon_name("go-next-symbolic"));
+    state.platform.git_list.add(&row);
+    state.git_rows.borrow_mut().push(row.clone().upcast());
+
+    let state = state.clone();
+    let store = store.to_string();
+    row.connect_activated(move |row| {
+        present_store_appearance_dialog(&state, row, &store);
+    });
+}
+
+fn present_store_appearance_dialog(state: &StoreRecipientsPageState, row: &ActionRow, store: &str) {
+    let appearance = Preferences::new().store_appearance(store);
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Label and color"))
+        .body(gettext(
+            "Items from this store show the label and a dot in this color.",
+        ))
+        .build();
+    let label_row = EntryRow::new();
+    label_row.set_title(&gettext("Label"));
+    label_row.set_text(
+        appearance
+            .as_ref()
+            .map_or("", |appearance| appearance.label.as_str()),
+    );
+    let color_row = ComboRow::new();
+    color_row.set_title(&gettext("Color"));
+    color_row.set_model(Some(&color_combo_model()));
+    color_row.set_selected(color_combo_position(
+        appearance
+            .as_ref()
+            .map_or("", |appearance| appearance.color.as_str()),
+    ));
+    let group = PreferencesGroup::new();
+    group.add(&label_row);
+    group.add(&color_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let save = gettext("Save");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("save", save.as_str())]);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("save"));
+
+    let state = state.clone();
+    let row = row.clone();
+    let store = store.to_string();
+    dialog.connect_response(None, move |_, response| {
+        if response != "save" {
+            return;
+        }
+        let appearance = StoreAppearance {
+            store: store.clone(),
+            label: label_row.text().to_string(),
+            color: color_for_combo_position(color_row.selected()),
+        };
+        match Preferences::new().set_store_appearance(&store, Some(appearance)) {
+            Ok(()) => {
+                row.set_subtitle(&appearance_row_subtitle(
+                    Preferences::new().store_appearance(&store).as_ref(),
+                ));
+                activate_widget_action(&state.window, "win.reload-password-list");
+            }
+            Err(err) => {
+                log_error(format!(
+                    "Failed to save the label and color for '{store}': {err}"
+                ));
+                state
+                    .platform
+                    .overlay
+                    .add_toast(Toast::new(&gettext("Couldn't save that setting.")));
+            }
+        }
+    });
+    dialog.present(Some(&state.window));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        color_combo_position, color_for_combo_position, store_chip_markup, store_color_hex,
+    };
+
+    #[test]
+    fn store_colors_round_trip_through_the_color_row() {
+        assert_eq!(color_combo_position(""), 0);
+        assert_eq!(color_for_combo_position(0), "");
+        assert_eq!(
+            color_for_combo_position(color_combo_position("teal")),
+            "teal"
+        );
+        assert_eq!(color_combo_position("magenta"), 0);
+        assert_eq!(color_for_combo_position(99), "");
+    }
+
+    #[test]
+    fn store_chips_use_the_named_color() {
+        assert_eq!(store_color_hex("red"), Some("#e62d42"));
+        assert_eq!(store_color_hex("magenta"), None);
+        assert_eq!(
+            store_chip_markup("#3584e4"),
+            "<span foreground=\"#3584e4\">●</span>"
+        );
+    }
+}
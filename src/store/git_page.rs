@@ -1,12 +1,23 @@
-use crate::i18n::gettext;
+use crate::backend::{lock_store_for_sync, publish_store_event, StoreEvent};
+use crate::i18n::{gettext, ngettext};
 use crate::logging::log_error;
+use crate::preferences::Preferences;
+use crate::store::appearance::append_store_appearance_row;
+use crate::store::git_restore::present_restore_deleted_dialog;
 use crate::store::management::NUMBERED_STORE_SHORTCUT_COUNT;
+use crate::store::read_only::append_store_read_only_row;
 use crate::store::recipients_page::{StoreRecipientsMode, StoreRecipientsPageState};
+use crate::store::webdav::append_store_webdav_row;
 use crate::support::actions::activate_widget_action;
+use crate::support::activity::record_activity;
 use crate::support::background::spawn_result_task_with_finalizer;
 use crate::support::git::{
-    add_store_git_remote, list_store_git_remotes, remove_store_git_remote, rename_store_git_remote,
-    set_store_git_remote_url, store_git_repository_status, sync_store_repository, StoreGitHead,
+    add_store_git_remote, enable_store_git_versioning, fetch_full_store_git_history,
+    large_store_files, list_store_git_remotes, normalized_sparse_folders, remove_store_git_remote,
+    rename_store_git_remote, set_store_git_remote_url, set_store_git_sparse_folders,
+    set_store_git_upstream_remote, store_git_history_is_partial, store_git_repository_status,
+    store_git_sparse_folders, store_git_upstream_remote, sync_store_repository,
+    test_store_git_remote, GitRemote, LargeStoreFile, LargeStoreFiles, StoreGitHead,
     StoreGitRepositoryStatus,
 };
 use crate::support::runtime::{has_host_permission, supports_host_command_features};
@@ -21,11 +32,12 @@ use crate::window::append_optional_host_access_group_row;
 use crate::window::navigation::{show_secondary_page_chrome, HasWindowChrome, APP_WINDOW_TITLE};
 use crate::window::preferences_search::PreferencesPageSearchState;
 use adw::gio::{prelude::*, SimpleAction};
-use adw::gtk::{Align, Box as GtkBox, Button, Image, Label, Orientation, Widget};
+use adw::gtk::{Align, Box as GtkBox, Button, Image, Label, Orientation, StringList, Widget};
 use adw::prelude::*;
 use adw::{
-    ActionRow, ApplicationWindow, Dialog, EntryRow, NavigationPage, NavigationView,
-    PreferencesGroup, PreferencesPage, StatusPage, Toast, ToastOverlay, WindowTitle,
+    ActionRow, AlertDialog, ApplicationWindow, ComboRow, Dialog, EntryRow, NavigationPage,
+    NavigationView, PreferencesGroup, PreferencesPage, ResponseAppearance, StatusPage, Toast,
+    ToastOverlay, WindowTitle,
 };
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
@@ -81,6 +93,8 @@ fn set_git_busy_actions_enabled(window: &ApplicationWindow, enabled: bool) {
         "context-save",
         "context-undo",
         "open-new-password",
+        "open-new-secure-note",
+        "open-new-folder",
         "toggle-find",
         "open-git",
         "open-raw-pass-file",
@@ -171,10 +185,6 @@ fn translated_branch_message(template: &str, branch: &str) -> String {
     gettext(template).replace("{branch}", branch)
 }
 
-fn translated_count_message(template: &str, count: usize) -> String {
-    gettext(template).replace("{count}", &count.to_string())
-}
-
 fn append_translated_action_row_with_button(
     list: &PreferencesGroup,
     title: &str,
@@ -198,7 +208,9 @@ fn append_translated_action_row_with_button(
 
 fn repository_subtitle(status: &StoreGitRepositoryStatus) -> String {
     if !status.has_repository {
-        return gettext("No Git repository yet. Add a remote to initialize one.");
+        return gettext(
+            "No Git repository yet. Enable Git versioning or add a remote to start one.",
+        );
     }
     if status.dirty && status.has_outgoing_commits && status.has_incoming_commits {
         return gettext(
@@ -267,8 +279,12 @@ fn remote_count_subtitle(status: &StoreGitRepositoryStatus) -> String {
 
     match status.remotes.len() {
         0 => gettext("No remotes configured."),
-        1 => gettext("1 remote configured."),
-        count => translated_count_message("{count} remotes configured.", count),
+        count => ngettext(
+            "{count} remote configured.",
+            "{count} remotes configured.",
+            count,
+        )
+        .replace("{count}", &count.to_string()),
     }
 }
 
@@ -637,6 +653,11 @@ fn append_remote_row(
     row.set_activatable(false);
     row.add_prefix(&dim_label_icon("git-symbolic"));
 
+    let test_button =
+        flat_icon_button_with_tooltip("network-transmit-receive-symbolic", "Test connection");
+    test_button.set_sensitive(has_host_permission());
+    row.add_suffix(&test_button);
+
     let edit_button = flat_icon_button_with_tooltip("edit-symbolic", "Edit remote");
     row.add_suffix(&edit_button);
 
@@ -680,6 +701,45 @@ fn append_remote_row(
         );
     });
 
+    let store_for_test = store.to_string();
+    let overlay_for_test = state.overlay.clone();
+    let name_for_test = name.to_string();
+    test_button.connect_clicked(move |button| {
+        button.set_sensitive(false);
+        let button = button.clone();
+        let overlay_for_result = overlay_for_test.clone();
+        let overlay_for_disconnect = overlay_for_test.clone();
+        let store_for_worker = store_for_test.clone();
+        let name_for_worker = name_for_test.clone();
+        let store_for_result = store_for_test.clone();
+        let name_for_result = name_for_test.clone();
+        spawn_result_task_with_finalizer(
+            move || test_store_git_remote(&store_for_worker, &name_for_worker),
+            move || button.set_sensitive(true),
+            move |result| match result {
+                Ok(()) => {
+                    overlay_for_result.add_toast(Toast::new(
+                        &gettext("Connected to {remote}.").replace("{remote}", &name_for_result),
+                    ));
+                }
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to reach Git remote '{name_for_result}' of '{store_for_result}': {err}"
+                    ));
+                    overlay_for_result.add_toast(Toast::new(
+                        &gettext("Couldn't reach {remote}. Check the URL and your credentials.")
+                            .replace("{remote}", &name_for_result),
+                    ));
+                }
+            },
+            move || {
+                overlay_for_disconnect.add_toast(Toast::new(&gettext(
+                    "The connection test stopped unexpectedly.",
+                )));
+            },
+        );
+    });
+
     let store_for_delete = store.to_string();
     let state_for_delete = state.clone();
     let name_for_delete = name.to_string();
@@ -704,6 +764,72 @@ fn append_remote_row(
     });
 }
 
+fn upstream_row_position(remotes: &[GitRemote], upstream: Option<&str>) -> u32 {
+    upstream
+        .and_then(|upstream| remotes.iter().position(|remote| remote.name == upstream))
+        .and_then(|index| u32::try_from(index + 1).ok())
+        .unwrap_or(0)
+}
+
+// The remote `git pull` and `git push` use for this branch outside Keycord.
+// Keycord's own sync goes through every remote either way.
+fn append_upstream_row(
+    state: &StoreGitPageState,
+    store: &str,
+    branch: &str,
+    remotes: &[GitRemote],
+) {
+    let upstream = match store_git_upstream_remote(store, branch) {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            log_error(format!(
+                "Failed to read the Git upstream of '{branch}' in '{store}': {err}"
+            ));
+            None
+        }
+    };
+
+    let not_set = gettext("Not set");
+    let mut labels = vec![not_set.as_str()];
+    labels.extend(remotes.iter().map(|remote| remote.name.as_str()));
+    let row = ComboRow::builder()
+        .title(gettext("Upstream"))
+        .subtitle(translated_branch_message(
+            "The remote that git pull and git push use for '{branch}'.",
+            branch,
+        ))
+        .model(&StringList::new(&labels))
+        .selected(upstream_row_position(remotes, upstream.as_deref()))
+        .build();
+    row.add_prefix(&dim_label_icon("send-to-symbolic"));
+    row.set_sensitive(has_host_permission());
+    add_tracked_preferences_group_child(&state.status_list, state.status_rows.as_ref(), &row);
+
+    let state_for_change = state.clone();
+    let store = store.to_string();
+    let branch = branch.to_string();
+    let remote_names = remotes
+        .iter()
+        .map(|remote| remote.name.clone())
+        .collect::<Vec<_>>();
+    row.connect_selected_notify(move |row| {
+        let remote = (row.selected() as usize)
+            .checked_sub(1)
+            .and_then(|index| remote_names.get(index));
+        let toast = match set_store_git_upstream_remote(&store, &branch, remote.map(String::as_str))
+        {
+            Ok(()) => gettext("Upstream updated."),
+            Err(err) => {
+                log_error(format!(
+                    "Failed to set the Git upstream of '{branch}' in '{store}': {err}"
+                ));
+                gettext("Couldn't change the upstream.")
+            }
+        };
+        state_for_change.overlay.add_toast(Toast::new(&toast));
+    });
+}
+
 pub fn rebuild_store_git_page(state: &StoreGitPageState) {
     clear_tracked_preferences_group(&state.remotes_list, state.remote_rows.as_ref());
     clear_tracked_preferences_group(&state.actions_list, state.action_rows.as_ref());
@@ -805,6 +931,14 @@ pub fn rebuild_store_git_page(state: &StoreGitPageState) {
 
             let _ = append_optional_host_access_group_row(&state.access_list, &state.overlay);
 
+            // A plain store reads and saves without Git, so there is nothing
+            // to sync until versioning is turned on.
+            if !status.has_repository {
+                append_enable_git_versioning_row(state, &store);
+                state.search.sync();
+                return;
+            }
+
             let sync_state = state.clone();
             let store_for_sync = store.clone();
             let sync_row = append_translated_action_row_with_button(
@@ -842,18 +976,39 @@ pub fn rebuild_store_git_page(state: &StoreGitPageState) {
                     let store_for_worker = store_for_sync.clone();
                     let store_for_result = store_for_sync.clone();
                     spawn_result_task_with_finalizer(
-                        move || sync_store_repository(&store_for_worker),
+                        move || {
+                            let _lock = lock_store_for_sync(&store_for_worker)?;
+                            let result = sync_store_repository(&store_for_worker);
+                            if result.is_ok() {
+                                record_activity(format!("Synced {store_for_worker} with Git"));
+                                publish_store_event(StoreEvent::Synced {
+                                    store: store_for_worker.clone(),
+                                });
+                            }
+                            result
+                        },
                         move || {
                             finish_git_operation(&state_for_finalize);
                             rebuild_store_git_page(&state_for_finalize);
                             sync_related_views(&state_for_finalize);
                         },
                         move |result| match result {
-                            Ok(()) => {
+                            Ok(summary) if summary.conflict_copies.is_empty() => {
                                 state_for_result
                                     .overlay
                                     .add_toast(Toast::new(&gettext("Store synced.")));
                             }
+                            Ok(summary) => {
+                                let count = summary.conflict_copies.len();
+                                state_for_result.overlay.add_toast(Toast::new(
+                                    &ngettext(
+                                        "Store synced. {count} item changed on both sides, so its incoming copy was kept as a conflict item.",
+                                        "Store synced. {count} items changed on both sides, so their incoming copies were kept as conflict items.",
+                                        count,
+                                    )
+                                    .replace("{count}", &count.to_string()),
+                                ));
+                            }
                             Err(err) => {
                                 log_error(format!(
                                     "Failed to sync password store '{store_for_result}': {err}"
@@ -878,6 +1033,11 @@ pub fn rebuild_store_git_page(state: &StoreGitPageState) {
             sync_row.set_sensitive(sync_allowed(&status));
             sync_row.set_activatable(sync_allowed(&status));
 
+            if store_git_history_is_partial(&store) && !status.remotes.is_empty() {
+                append_fetch_full_history_row(state, &store);
+            }
+            append_restore_deleted_row(state, &store);
+
             let row = append_status_row(
                 &state.status_list,
                 "Branch",
@@ -885,6 +1045,15 @@ pub fn rebuild_store_git_page(state: &StoreGitPageState) {
                 "object-select-symbolic",
             );
             state.status_rows.borrow_mut().push(row.upcast());
+            append_large_files_row(state, &store);
+            append_sparse_folders_row(state, &store);
+
+            if let StoreGitHead::Branch(branch) | StoreGitHead::UnbornBranch(branch) = &status.head
+            {
+                if !status.remotes.is_empty() {
+                    append_upstream_row(state, &store, branch, &status.remotes);
+                }
+            }
         }
         Err(err) => {
             log_error(format!("Failed to inspect Git state for '{store}': {err}"));
@@ -900,6 +1069,307 @@ pub fn rebuild_store_git_page(state: &StoreGitPageState) {
     state.search.sync();
 }
 
+fn append_enable_git_versioning_row(state: &StoreGitPageState, store: &str) {
+    let enable_state = state.clone();
+    let store_for_enable = store.to_string();
+    let row = append_translated_action_row_with_button(
+        &state.status_list,
+        &gettext("Enable Git versioning"),
+        &gettext("Start a Git repository with the current items as the first commit."),
+        "git-symbolic",
+        move || {
+            begin_git_operation(&enable_state, "Enabling Git versioning");
+
+            let state_for_finalize = enable_state.clone();
+            let state_for_result = enable_state.clone();
+            let state_for_disconnect = enable_state.clone();
+            let store_for_worker = store_for_enable.clone();
+            let store_for_result = store_for_enable.clone();
+            spawn_result_task_with_finalizer(
+                move || enable_store_git_versioning(&store_for_worker),
+                move || {
+                    finish_git_operation(&state_for_finalize);
+                    rebuild_store_git_page(&state_for_finalize);
+                    sync_related_views(&state_for_finalize);
+                },
+                move |result| match result {
+                    Ok(large_files) => {
+                        record_activity(format!("Enabled Git versioning for {store_for_result}"));
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&git_versioning_enabled_message(&large_files)));
+                    }
+                    Err(err) => {
+                        log_error(format!(
+                            "Failed to enable Git versioning for '{store_for_result}': {err}"
+                        ));
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&gettext("Couldn't enable Git versioning.")));
+                    }
+                },
+                move || {
+                    state_for_disconnect.overlay.add_toast(Toast::new(&gettext(
+                        "Enabling Git versioning stopped unexpectedly.",
+                    )));
+                },
+            );
+        },
+    );
+    state.status_rows.borrow_mut().push(row.clone().upcast());
+    row.set_sensitive(has_host_permission());
+    row.set_activatable(has_host_permission());
+}
+
+fn git_versioning_enabled_message(large_files: &LargeStoreFiles) -> String {
+    let count = large_files.files.len();
+    if count == 0 {
+        return gettext("Git versioning enabled.");
+    }
+
+    let message = if large_files.left_out {
+        ngettext(
+            "Git versioning enabled. {count} large file was left out of Git.",
+            "Git versioning enabled. {count} large files were left out of Git.",
+            count,
+        )
+    } else {
+        ngettext(
+            "Git versioning enabled. {count} large file was committed and will slow down sync.",
+            "Git versioning enabled. {count} large files were committed and will slow down sync.",
+            count,
+        )
+    };
+    message.replace("{count}", &count.to_string())
+}
+
+fn large_files_subtitle(files: &[LargeStoreFile]) -> String {
+    let paths = files
+        .iter()
+        .map(|file| format!("{} ({} MiB)", file.path, file.mib()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    ngettext(
+        "{files} is over the large file limit and slows down sync. Keep it outside the store, or attach files up to 1 MiB to an item instead.",
+        "{files} are over the large file limit and slow down sync. Keep them outside the store, or attach files up to 1 MiB to an item instead.",
+        files.len(),
+    )
+    .replace("{files}", &paths)
+}
+
+// Files dropped into the store folder by hand end up in every clone once
+// they are committed, so they are pointed out before anyone syncs them.
+fn append_large_files_row(state: &StoreGitPageState, store: &str) {
+    let files = match large_store_files(store) {
+        Ok(files) => files,
+        Err(err) => {
+            log_error(format!(
+                "Failed to look for large files in '{store}': {err}"
+            ));
+            return;
+        }
+    };
+    if files.is_empty() {
+        return;
+    }
+
+    let row = append_status_row(
+        &state.status_list,
+        "Large files",
+        &large_files_subtitle(&files),
+        "dialog-warning-symbolic",
+    );
+    state.status_rows.borrow_mut().push(row.upcast());
+}
+
+fn sparse_folders_subtitle(folders: Option<&[String]>) -> String {
+    match folders {
+        Some(folders) if !folders.is_empty() => {
+            gettext("Only {folders} and the files at the top of the store are on this device.")
+                .replace("{folders}", &folders.join(", "))
+        }
+        _ => gettext("Every folder is on this device. Choose folders to keep only those."),
+    }
+}
+
+// Large team stores can keep just the folders someone works with. Listing,
+// search, and sync then only see those, and the rest stays in Git.
+fn append_sparse_folders_row(state: &StoreGitPageState, store: &str) {
+    let folders = match store_git_sparse_folders(store) {
+        Ok(folders) => folders,
+        Err(err) => {
+            log_error(format!(
+                "Failed to read the checked-out folders of '{store}': {err}"
+            ));
+            return;
+        }
+    };
+
+    let choose_state = state.clone();
+    let store_for_choose = store.to_string();
+    let folders_for_choose = folders.clone().unwrap_or_default();
+    let row = append_translated_action_row_with_button(
+        &state.status_list,
+        &gettext("Checked-out folders"),
+        &sparse_folders_subtitle(folders.as_deref()),
+        "folder-symbolic",
+        move || {
+            present_sparse_folders_dialog(&choose_state, &store_for_choose, &folders_for_choose)
+        },
+    );
+    state.status_rows.borrow_mut().push(row.clone().upcast());
+    row.set_sensitive(has_host_permission());
+    row.set_activatable(has_host_permission());
+}
+
+fn present_sparse_folders_dialog(state: &StoreGitPageState, store: &str, folders: &[String]) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Checked-out folders"))
+        .body(gettext(
+            "Only these folders are kept on this device. Leave empty to check out every folder.",
+        ))
+        .build();
+    let folders_row = EntryRow::new();
+    folders_row.set_title(&gettext("Folders, separated by commas"));
+    folders_row.set_text(&folders.join(", "));
+    let group = PreferencesGroup::new();
+    group.add(&folders_row);
+    dialog.set_extra_child(Some(&group));
+
+    let cancel = gettext("Cancel");
+    let apply = gettext("Apply");
+    dialog.add_responses(&[("cancel", cancel.as_str()), ("apply", apply.as_str())]);
+    dialog.set_response_appearance("apply", ResponseAppearance::Suggested);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("apply"));
+
+    let state = state.clone();
+    let store = store.to_string();
+    dialog.connect_response(None, move |_, response| {
+        if response != "apply" {
+            return;
+        }
+        let folders = normalized_sparse_folders(folders_row.text().split(','));
+        begin_git_operation(&state, "Changing checked-out folders");
+
+        let state_for_finalize = state.clone();
+        let state_for_result = state.clone();
+        let state_for_disconnect = state.clone();
+        let store_for_worker = store.clone();
+        let store_for_result = store.clone();
+        spawn_result_task_with_finalizer(
+            move || set_store_git_sparse_folders(&store_for_worker, &folders),
+            move || {
+                finish_git_operation(&state_for_finalize);
+                rebuild_store_git_page(&state_for_finalize);
+                sync_related_views(&state_for_finalize);
+            },
+            move |result| match result {
+                Ok(()) => {
+                    record_activity(format!(
+                        "Changed the checked-out folders of {store_for_result}"
+                    ));
+                    state_for_result
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Checked-out folders changed.")));
+                }
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to change the checked-out folders of '{store_for_result}': {err}"
+                    ));
+                    state_for_result.overlay.add_toast(Toast::new(&gettext(
+                        "Couldn't change the checked-out folders.",
+                    )));
+                }
+            },
+            move || {
+                state_for_disconnect.overlay.add_toast(Toast::new(&gettext(
+                    "Changing checked-out folders stopped unexpectedly.",
+                )));
+            },
+        );
+    });
+    dialog.present(Some(&state.window));
+}
+
+fn append_fetch_full_history_row(state: &StoreGitPageState, store: &str) {
+    let fetch_state = state.clone();
+    let store_for_fetch = store.to_string();
+    let row = append_translated_action_row_with_button(
+        &state.status_list,
+        &gettext("Fetch full history"),
+        &gettext("This store only has recent history. Download the rest from the remote."),
+        "folder-download-symbolic",
+        move || {
+            begin_git_operation(&fetch_state, "Fetching history");
+
+            let state_for_finalize = fetch_state.clone();
+            let state_for_result = fetch_state.clone();
+            let state_for_disconnect = fetch_state.clone();
+            let store_for_worker = store_for_fetch.clone();
+            let store_for_result = store_for_fetch.clone();
+            spawn_result_task_with_finalizer(
+                move || fetch_full_store_git_history(&store_for_worker),
+                move || {
+                    finish_git_operation(&state_for_finalize);
+                    rebuild_store_git_page(&state_for_finalize);
+                },
+                move |result| match result {
+                    Ok(()) => {
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&gettext("Full history fetched.")));
+                    }
+                    Err(err) => {
+                        log_error(format!(
+                            "Failed to fetch full Git history for '{store_for_result}': {err}"
+                        ));
+                        state_for_result
+                            .overlay
+                            .add_toast(Toast::new(&gettext("Couldn't fetch the full history.")));
+                    }
+                },
+                move || {
+                    state_for_disconnect.overlay.add_toast(Toast::new(&gettext(
+                        "Fetching history stopped unexpectedly.",
+                    )));
+                },
+            );
+        },
+    );
+    state.status_rows.borrow_mut().push(row.clone().upcast());
+    row.set_sensitive(has_host_permission());
+    row.set_activatable(has_host_permission());
+}
+
+fn append_restore_deleted_row(state: &StoreGitPageState, store: &str) {
+    let restore_state = state.clone();
+    let store_for_restore = store.to_string();
+    let row = append_translated_action_row_with_button(
+        &state.status_list,
+        &gettext("Restore deleted item"),
+        &gettext("Bring back an item that was deleted, from this store's Git history."),
+        "edit-undo-symbolic",
+        move || {
+            let state_for_restored = restore_state.clone();
+            present_restore_deleted_dialog(
+                &restore_state.window,
+                &store_for_restore,
+                &restore_state.overlay,
+                move || {
+                    rebuild_store_git_page(&state_for_restored);
+                    sync_related_views(&state_for_restored);
+                },
+            );
+        },
+    );
+    state.status_rows.borrow_mut().push(row.clone().upcast());
+    // Restoring writes an item, so read-only stores can't use it.
+    let enabled = supports_host_command_features() && !Preferences::new().store_is_read_only(store);
+    row.set_sensitive(enabled);
+    row.set_activatable(enabled);
+}
+
 fn remote_dialog_apply_enabled(name: &str, url: &str) -> bool {
     !name.trim().is_empty() && !url.trim().is_empty()
 }
@@ -945,40 +1415,42 @@ pub fn show_store_git_page_from_recipients(state: &StoreGitPageState, store: imp
 
 pub fn rebuild_store_recipients_git_row(state: &StoreRecipientsPageState) {
     clear_tracked_preferences_group(&state.platform.git_list, state.git_rows.as_ref());
-    if !supports_host_command_features() {
-        state.platform.git_group.set_visible(false);
-        return;
-    }
     let Some(request) = state.current_request() else {
         state.platform.git_group.set_visible(false);
         return;
     };
 
-    let visible = matches!(
-        request.mode,
-        StoreRecipientsMode::Edit | StoreRecipientsMode::Create
-    );
+    let host_features = supports_host_command_features();
+    let editing = matches!(request.mode, StoreRecipientsMode::Edit);
+    let visible = editing || (host_features && matches!(request.mode, StoreRecipientsMode::Create));
     state.platform.git_group.set_visible(visible);
     if !visible {
         return;
     }
 
-    let store = request.store.clone();
-    let row_state = store_git_row_state_for_store(&store);
-    let git_page = state.platform.store_git_page.clone();
-    let row = append_translated_action_row_with_button(
-        &state.platform.git_list,
-        &gettext("Git remotes"),
-        &row_state.subtitle,
-        "go-next-symbolic",
-        move || {
-            show_store_git_page_from_recipients(&git_page, store.clone());
-        },
-    );
-    state.git_rows.borrow_mut().push(row.clone().upcast());
-    row.add_prefix(&dim_label_icon("git-symbolic"));
-    row.set_sensitive(row_state.enabled);
-    row.set_activatable(row_state.enabled);
+    if host_features {
+        let store = request.store.clone();
+        let row_state = store_git_row_state_for_store(&store);
+        let git_page = state.platform.store_git_page.clone();
+        let row = append_translated_action_row_with_button(
+            &state.platform.git_list,
+            &gettext("Git remotes"),
+            &row_state.subtitle,
+            "go-next-symbolic",
+            move || {
+                show_store_git_page_from_recipients(&git_page, store.clone());
+            },
+        );
+        state.git_rows.borrow_mut().push(row.clone().upcast());
+        row.add_prefix(&dim_label_icon("git-symbolic"));
+        row.set_sensitive(row_state.enabled);
+        row.set_activatable(row_state.enabled);
+        append_store_webdav_row(state, &request.store);
+    }
+    if editing {
+        append_store_appearance_row(state, &request.store);
+        append_store_read_only_row(state, &request.store);
+    }
 }
 
 #[cfg(test)]
@@ -986,8 +1458,8 @@ mod tests {
     use super::{
         next_autofilled_remote_name, next_available_remote_name, remote_count_subtitle,
         remote_dialog_apply_enabled, remote_dialog_error_message, remote_name_exists,
-        remote_url_exists, store_git_row_state, suggested_remote_name_from_url, StoreGitHead,
-        StoreGitRepositoryStatus,
+        remote_url_exists, store_git_row_state, suggested_remote_name_from_url,
+        upstream_row_position, StoreGitHead, StoreGitRepositoryStatus,
     };
     use crate::i18n::gettext;
     use crate::support::git::GitRemote;
@@ -1020,6 +1492,21 @@ mod tests {
         assert!(state.enabled);
     }
 
+    #[test]
+    fn upstream_row_puts_not_set_before_the_remotes() {
+        let remotes = ["origin", "backup"]
+            .into_iter()
+            .map(|name| GitRemote {
+                name: name.to_string(),
+                url: format!("ssh://example.test/{name}.git"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(upstream_row_position(&remotes, None), 0);
+        assert_eq!(upstream_row_position(&remotes, Some("backup")), 2);
+        assert_eq!(upstream_row_position(&remotes, Some("missing")), 0);
+    }
+
     #[test]
     fn remote_name_autofill_suggests_origin_for_non_empty_urls() {
         assert_eq!(
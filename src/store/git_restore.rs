@@ -0,0 +1,147 @@
+use crate::backend::restore_deleted_password_entry;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::support::activity::record_activity;
+use crate::support::background::{spawn_result_task, spawn_result_task_with_finalizer};
+use crate::support::git::{store_git_deleted_entries, StoreGitDeletedEntry};
+use crate::support::ui::{
+    append_info_group_row, dialog_content_shell, flat_icon_button_with_tooltip, local_time_text,
+};
+use adw::prelude::*;
+use adw::{
+    ActionRow, ApplicationWindow, Dialog, PreferencesGroup, PreferencesPage, Toast, ToastOverlay,
+};
+use std::rc::Rc;
+
+fn deleted_entry_subtitle(entry: &StoreGitDeletedEntry) -> String {
+    gettext("Deleted {date} by {author}")
+        .replace("{date}", &local_time_text(entry.deleted_at))
+        .replace("{author}", &entry.author)
+}
+
+fn append_deleted_entry_row(
+    group: &PreferencesGroup,
+    dialog: &Dialog,
+    store: &str,
+    entry: StoreGitDeletedEntry,
+    overlay: &ToastOverlay,
+    on_restored: &Rc<dyn Fn()>,
+) {
+    let row = ActionRow::builder()
+        .title(entry.label.as_str())
+        .subtitle(deleted_entry_subtitle(&entry))
+        .use_markup(false)
+        .build();
+    let button = flat_icon_button_with_tooltip("edit-undo-symbolic", "Restore");
+    button.set_valign(adw::gtk::Align::Center);
+    row.add_suffix(&button);
+    row.set_activatable_widget(Some(&button));
+    group.add(&row);
+
+    let dialog = dialog.clone();
+    let store = store.to_string();
+    let overlay = overlay.clone();
+    let on_restored = on_restored.clone();
+    button.connect_clicked(move |button| {
+        button.set_sensitive(false);
+        let button = button.clone();
+        let dialog = dialog.clone();
+        let overlay_for_result = overlay.clone();
+        let overlay_for_disconnect = overlay.clone();
+        let on_restored = on_restored.clone();
+        let store_for_worker = store.clone();
+        let store_for_result = store.clone();
+        let entry_for_worker = entry.clone();
+        let label = entry.label.clone();
+        spawn_result_task_with_finalizer(
+            move || {
+                let result = restore_deleted_password_entry(&store_for_worker, &entry_for_worker)
+                    .map_err(|err| err.to_string());
+                if result.is_ok() {
+                    record_activity(format!(
+                        "Restored {} in {store_for_worker} from Git history",
+                        entry_for_worker.label
+                    ));
+                }
+                result
+            },
+            move || button.set_sensitive(true),
+            move |result| match result {
+                Ok(()) => {
+                    dialog.close();
+                    on_restored();
+                    overlay_for_result.add_toast(Toast::new(
+                        &gettext("Restored {item}.").replace("{item}", &label),
+                    ));
+                }
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to restore '{label}' in '{store_for_result}' from Git history: {err}"
+                    ));
+                    overlay_for_result
+                        .add_toast(Toast::new(&gettext("Couldn't restore that item.")));
+                }
+            },
+            move || {
+                overlay_for_disconnect
+                    .add_toast(Toast::new(&gettext("Restoring stopped unexpectedly.")));
+            },
+        );
+    });
+}
+
+// Lists items whose files were removed in a past commit. Restoring one
+// saves its last version again, encrypted for the current recipients, and
+// records that in a new commit.
+pub fn present_restore_deleted_dialog(
+    window: &ApplicationWindow,
+    store: &str,
+    overlay: &ToastOverlay,
+    on_restored: impl Fn() + 'static,
+) {
+    let window = window.clone();
+    let overlay = overlay.clone();
+    let overlay_for_disconnect = overlay.clone();
+    let store = store.to_string();
+    let store_for_worker = store.clone();
+    let on_restored: Rc<dyn Fn()> = Rc::new(on_restored);
+    spawn_result_task(
+        move || store_git_deleted_entries(&store_for_worker),
+        move |result| {
+            let entries = match result {
+                Ok(entries) => entries,
+                Err(err) => {
+                    log_error(format!("Failed to find deleted items in '{store}': {err}"));
+                    overlay.add_toast(Toast::new(&gettext("Couldn't read the history.")));
+                    return;
+                }
+            };
+
+            let group = PreferencesGroup::new();
+            let page = PreferencesPage::new();
+            page.add(&group);
+            let title = gettext("Restore deleted item");
+            let dialog = Dialog::builder()
+                .title(title.as_str())
+                .content_width(640)
+                .content_height(480)
+                .child(&dialog_content_shell(&title, Some(&store), &page))
+                .build();
+
+            if entries.is_empty() {
+                append_info_group_row(
+                    &group,
+                    "No deleted items",
+                    "Nothing has been deleted from this store's Git history.",
+                );
+            }
+            for entry in entries {
+                append_deleted_entry_row(&group, &dialog, &store, entry, &overlay, &on_restored);
+            }
+            dialog.present(Some(&window));
+        },
+        move || {
+            overlay_for_disconnect.add_toast(Toast::new(&gettext("Couldn't read the history.")));
+        },
+    );
+}
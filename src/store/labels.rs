@@ -1,3 +1,5 @@
+use crate::preferences::Preferences;
+use crate::store::appearance::store_color_hex;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -53,6 +55,42 @@ pub fn shortened_store_label_map(stores: &[String]) -> HashMap<String, String> {
         .collect()
 }
 
+// What the item list shows for each store: the label someone gave it, or the
+// shortest unique path, and an optional color.
+#[derive(Clone, Debug, Default)]
+pub struct StoreLabels {
+    labels: HashMap<String, String>,
+    colors: HashMap<String, &'static str>,
+}
+
+impl StoreLabels {
+    pub fn for_stores(settings: &Preferences, stores: &[String]) -> Self {
+        let mut labels = shortened_store_label_map(stores);
+        let mut colors = HashMap::new();
+        for store in stores {
+            let Some(appearance) = settings.store_appearance(store) else {
+                continue;
+            };
+            if !appearance.label.is_empty() {
+                labels.insert(store.clone(), appearance.label);
+            }
+            if let Some(color) = store_color_hex(&appearance.color) {
+                colors.insert(store.clone(), color);
+            }
+        }
+
+        Self { labels, colors }
+    }
+
+    pub fn label(&self, store_path: &str) -> String {
+        shortened_store_label_for_path(store_path, &self.labels)
+    }
+
+    pub fn color(&self, store_path: &str) -> Option<&'static str> {
+        self.colors.get(store_path).copied()
+    }
+}
+
 pub fn shortened_store_label_for_path(
     store_path: &str,
     store_labels: &HashMap<String, String>,
@@ -67,7 +105,10 @@ pub fn shortened_store_label_for_path(
 mod tests {
     use super::{
         shortened_store_label_for_path, shortened_store_label_map, shortened_store_labels,
+        StoreLabels,
     };
+    use crate::backend::test_support::SystemBackendTestEnv;
+    use crate::preferences::{Preferences, StoreAppearance};
 
     #[test]
     fn store_labels_use_short_unique_suffixes() {
@@ -110,4 +151,33 @@ mod tests {
             "/tmp/custom-store".to_string()
         );
     }
+
+    #[test]
+    #[expect(
+        clippy::significant_drop_tightening,
+        reason = "SystemBackendTestEnv must stay alive for the full test to keep the preferences in place."
+    )]
+    fn store_labels_prefer_the_chosen_label_and_color() {
+        let env = SystemBackendTestEnv::new();
+        let store = env.store_root().to_string_lossy().to_string();
+        let other = "/tmp/other/.password-store".to_string();
+        let settings = Preferences::new();
+        settings
+            .set_store_appearance(
+                &store,
+                Some(StoreAppearance {
+                    store: store.clone(),
+                    label: "Work".to_string(),
+                    color: "teal".to_string(),
+                }),
+            )
+            .expect("save store appearance");
+
+        let labels = StoreLabels::for_stores(&settings, &[store.clone(), other.clone()]);
+
+        assert_eq!(labels.label(&store), "Work");
+        assert_eq!(labels.color(&store), Some("#2190a4"));
+        assert_eq!(labels.label(&other), ".../other/.password-store");
+        assert_eq!(labels.color(&other), None);
+    }
 }
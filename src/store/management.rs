@@ -1,10 +1,12 @@
 mod clone;
+mod details;
 mod dialogs;
 mod import;
 
 use self::clone::append_store_clone_row;
 #[cfg(target_os = "linux")]
 pub use self::clone::prompt_store_clone;
+use self::details::{load_store_row_details, open_store_folder};
 pub use self::import::{
     initialize_store_import_page, schedule_store_import_row, StoreImportChrome,
     StoreImportControls, StoreImportPageState, StoreImportPageWidgets, StoreImportToolRowState,
@@ -13,19 +15,21 @@ use super::recipients::{
     read_store_recipients, store_is_supported_in_current_build, store_recipients_subtitle,
 };
 pub use super::recipients_page::{
-    connect_store_recipients_controls, register_store_recipients_reload_action,
-    register_store_recipients_save_action, show_store_recipients_create_page,
-    show_store_recipients_edit_page, sync_store_recipients_page_header, StoreRecipientsPageState,
-    StoreRecipientsPlatformState, StoreRecipientsRequest,
+    connect_store_recipients_controls, register_store_folder_recipients_action,
+    register_store_recipients_reload_action, register_store_recipients_save_action,
+    show_store_recipients_create_page, show_store_recipients_edit_page,
+    sync_store_recipients_page_header, StoreRecipientsPageState, StoreRecipientsPlatformState,
+    StoreRecipientsRequest,
 };
 use crate::i18n::gettext;
 use crate::logging::log_error;
+use crate::password::entry_files::canonical_store_root;
 use crate::preferences::Preferences;
 use crate::support::actions::{activate_widget_action, register_window_action};
 use crate::support::file_picker::choose_local_folder_path;
 use crate::support::ui::{
     append_action_row_with_button, append_info_row, clear_list_box, dim_label_icon,
-    flat_icon_button,
+    flat_icon_button_with_tooltip,
 };
 use adw::gtk::ListBox;
 use adw::prelude::*;
@@ -36,7 +40,11 @@ use std::path::Path;
 use std::rc::Rc;
 
 fn updated_stores_after_add(stores: &[String], new_store: &str) -> Option<Vec<String>> {
-    if stores.iter().any(|store| store == new_store) {
+    let new_store_root = canonical_store_root(Path::new(new_store));
+    if stores
+        .iter()
+        .any(|store| store == new_store || canonical_store_root(Path::new(store)) == new_store_root)
+    {
         return None;
     }
 
@@ -165,11 +173,12 @@ pub fn rebuild_stores_list(
         return;
     }
 
-    for store in &stores {
+    for (store, root) in stores.iter().zip(settings.store_roots()) {
         append_store_row(
             stores_list,
             settings,
             store,
+            &root,
             recipients_page,
             before_navigation.clone(),
         );
@@ -220,15 +229,15 @@ fn append_store_row(
     list: &ListBox,
     settings: &Preferences,
     store: &str,
+    root: &str,
     recipients_page: &StoreRecipientsPageState,
     before_navigation: Option<Rc<dyn Fn()>>,
 ) {
     let store_supported = store_is_supported_in_current_build(store);
-    let row = ActionRow::builder()
-        .title(store)
-        .subtitle(store_recipients_subtitle(store))
-        .build();
+    let summary = store_recipients_subtitle(store);
+    let row = ActionRow::builder().title(store).subtitle(&summary).build();
     row.set_activatable(store_supported);
+    load_store_row_details(&row, root, summary);
 
     if store_supported {
         row.add_suffix(&dim_label_icon("go-next-symbolic"));
@@ -237,11 +246,20 @@ fn append_store_row(
         row.add_prefix(&dim_label_icon("dialog-warning-symbolic"));
     }
 
-    let delete_button = flat_icon_button("window-close-symbolic");
+    let open_button = flat_icon_button_with_tooltip("folder-open-symbolic", "Open in Files");
+    row.add_suffix(&open_button);
+    let delete_button = flat_icon_button_with_tooltip(
+        "window-close-symbolic",
+        "Remove from list. The folder is kept.",
+    );
     row.add_suffix(&delete_button);
 
     list.append(&row);
 
+    let root = root.to_string();
+    let overlay = recipients_page.platform.overlay.clone();
+    open_button.connect_clicked(move |_| open_store_folder(&root, &overlay));
+
     let settings = settings.clone();
     let list = list.clone();
     let store = store.to_string();
@@ -439,6 +457,28 @@ mod tests {
         assert_eq!(updated_stores_after_add(&stores, "/tmp/one"), None);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn adding_a_symlink_to_a_configured_store_is_a_no_op() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("clock before unix epoch")
+            .as_nanos();
+        let base = std::env::temp_dir().join(format!("passwordstore-add-link-{nanos}"));
+        let store = base.join("synced");
+        let link = base.join("password-store");
+        std::fs::create_dir_all(&store).expect("create store dir");
+        std::os::unix::fs::symlink(&store, &link).expect("link store");
+
+        let stores = vec![store.to_string_lossy().to_string()];
+        assert_eq!(
+            updated_stores_after_add(&stores, link.to_string_lossy().as_ref()),
+            None
+        );
+
+        std::fs::remove_dir_all(base).expect("remove test store");
+    }
+
     #[test]
     fn deleting_a_store_removes_only_the_requested_entry() {
         let stores = vec![
@@ -1,25 +1,42 @@
 use super::{
     dialogs::build_progress_dialog, open_store_folder_picker, rebuild_stores_list,
-    refresh_after_store_list_change, updated_stores_after_add, StoreRecipientsPageState,
+    refresh_after_store_list_change, show_store_recipients_edit_page, updated_stores_after_add,
+    StoreRecipientsPageState,
 };
+use crate::backend::recipients_with_private_key;
 use crate::i18n::gettext;
 use crate::logging::log_error;
 use crate::preferences::Preferences;
+use crate::store::recipients::{
+    read_store_standard_recipients_for_scope, relevant_store_recipient_scopes,
+    ROOT_STORE_RECIPIENTS_SCOPE,
+};
 use crate::support::background::spawn_result_task;
+use crate::support::git::{
+    git_remote_sign_in, git_remote_url_error, ssh_config_host_aliases, StoreCloneOptions,
+};
 use crate::support::runtime::supports_host_command_features;
 use crate::support::ui::{
     append_action_row_with_button, connect_entry_row_apply_button_to_nonempty_text,
     dialog_content_shell, dim_label_icon,
 };
 use crate::window::clone_store_repository;
-use adw::gtk::{Align, Box as GtkBox, Label, ListBox, Orientation};
+use adw::gtk::{Align, Box as GtkBox, CheckButton, Label, ListBox, Orientation};
 use adw::prelude::*;
 use adw::{
-    ActionRow, ApplicationWindow, Dialog, EntryRow, PreferencesGroup, PreferencesPage, Toast,
-    ToastOverlay,
+    ActionRow, AlertDialog, ApplicationWindow, Dialog, EntryRow, PreferencesGroup, PreferencesPage,
+    Toast, ToastOverlay,
 };
+use std::path::Path;
 use std::rc::Rc;
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum ClonedStoreKeyAccess {
+    Readable,
+    NoMatchingKey,
+    OnlySubfolders(Vec<String>),
+}
+
 fn build_clone_progress_dialog(window: &ApplicationWindow, store: &str) -> Dialog {
     build_progress_dialog(
         window,
@@ -30,23 +47,197 @@ fn build_clone_progress_dialog(window: &ApplicationWindow, store: &str) -> Dialo
 }
 
 fn clone_url_dialog_error_message(url: &str) -> Option<&'static str> {
-    url.trim().is_empty().then_some("Enter a repository URL.")
+    git_remote_url_error(url)
+}
+
+// Hosts are offered while the host part is still being typed, keeping any
+// `user@` in front of it.
+fn ssh_host_completion(text: &str, alias: &str) -> Option<String> {
+    let text = text.trim();
+    if text.contains([':', '/']) {
+        return None;
+    }
+    let (user, host) = match text.rsplit_once('@') {
+        Some((user, host)) => (format!("{user}@"), host),
+        None => (String::new(), text),
+    };
+    (alias.starts_with(host) && alias != host).then(|| format!("{user}{alias}:"))
+}
+
+struct CloneUrlFeedback {
+    error_label: Label,
+    sign_in_row: ActionRow,
+    ssh_hosts_group: PreferencesGroup,
+    ssh_host_rows: Vec<(String, ActionRow)>,
+}
+
+impl CloneUrlFeedback {
+    fn sync(&self, text: &str) {
+        let error = if text.trim().is_empty() {
+            None
+        } else {
+            clone_url_dialog_error_message(text)
+        };
+        self.error_label
+            .set_label(&error.map(gettext).unwrap_or_default());
+        self.error_label.set_visible(error.is_some());
+
+        let sign_in = git_remote_sign_in(text);
+        if let Some(sign_in) = sign_in {
+            self.sign_in_row
+                .set_subtitle(&gettext(sign_in.description()));
+        }
+        self.sign_in_row.set_visible(sign_in.is_some());
+
+        let mut any_host = false;
+        for (alias, row) in &self.ssh_host_rows {
+            let visible = ssh_host_completion(text, alias).is_some();
+            row.set_visible(visible);
+            any_host |= visible;
+        }
+        self.ssh_hosts_group.set_visible(any_host);
+    }
+}
+
+// Each scope is a folder with its own .gpg-id. A scope is readable when any
+// of its recipients has a private key for the backend in use.
+fn cloned_store_key_access(scopes: &[(String, bool)]) -> ClonedStoreKeyAccess {
+    if scopes.iter().all(|(_, readable)| *readable) {
+        return ClonedStoreKeyAccess::Readable;
+    }
+    if scopes
+        .iter()
+        .any(|(scope, readable)| scope == ROOT_STORE_RECIPIENTS_SCOPE && *readable)
+    {
+        return ClonedStoreKeyAccess::Readable;
+    }
+
+    let readable = scopes
+        .iter()
+        .filter(|(scope, readable)| *readable && scope != ROOT_STORE_RECIPIENTS_SCOPE)
+        .map(|(scope, _)| scope.clone())
+        .collect::<Vec<_>>();
+    if readable.is_empty() {
+        ClonedStoreKeyAccess::NoMatchingKey
+    } else {
+        ClonedStoreKeyAccess::OnlySubfolders(readable)
+    }
+}
+
+fn check_cloned_store_keys(store: &str) -> ClonedStoreKeyAccess {
+    let scopes = relevant_store_recipient_scopes(store)
+        .into_iter()
+        .map(|scope| {
+            let recipients = read_store_standard_recipients_for_scope(store, &scope);
+            (scope, recipients)
+        })
+        .collect::<Vec<_>>();
+    let mut recipients = scopes
+        .iter()
+        .flat_map(|(_, recipients)| recipients.iter().cloned())
+        .collect::<Vec<_>>();
+    recipients.sort();
+    recipients.dedup();
+    let available = recipients_with_private_key(&recipients);
+
+    let scopes = scopes
+        .into_iter()
+        .map(|(scope, recipients)| {
+            let readable = recipients
+                .iter()
+                .any(|recipient| available.contains(recipient));
+            (scope, readable)
+        })
+        .collect::<Vec<_>>();
+    cloned_store_key_access(&scopes)
+}
+
+fn stores_with_replacement(stores: &[String], current: &str, replacement: &str) -> Vec<String> {
+    stores
+        .iter()
+        .map(|store| {
+            if store == current {
+                replacement.to_string()
+            } else {
+                store.clone()
+            }
+        })
+        .collect()
+}
+
+fn clone_option_row(title: &str, subtitle: &str) -> (ActionRow, CheckButton) {
+    let check = CheckButton::new();
+    check.set_valign(Align::Center);
+
+    let row = ActionRow::builder()
+        .title(gettext(title))
+        .subtitle(gettext(subtitle))
+        .activatable_widget(&check)
+        .build();
+    row.add_suffix(&check);
+    (row, check)
 }
 
 fn present_clone_url_dialog<F>(window: &ApplicationWindow, store: &str, on_submit: F)
 where
-    F: Fn(String) + 'static,
+    F: Fn(String, StoreCloneOptions) + 'static,
 {
     let url_row = EntryRow::new();
     url_row.set_title(&gettext("Repository URL"));
     url_row.set_show_apply_button(true);
     connect_entry_row_apply_button_to_nonempty_text(&url_row);
 
+    let sign_in_row = ActionRow::builder()
+        .title(gettext("Sign-in"))
+        .activatable(false)
+        .visible(false)
+        .build();
+
     let group = PreferencesGroup::builder().build();
     group.add(&url_row);
+    group.add(&sign_in_row);
+
+    let ssh_hosts_group = PreferencesGroup::builder()
+        .title(gettext("SSH hosts"))
+        .description(gettext("From your SSH config."))
+        .visible(false)
+        .build();
+    let ssh_host_rows = ssh_config_host_aliases()
+        .into_iter()
+        .map(|alias| {
+            let row = ActionRow::builder().title(&alias).activatable(true).build();
+            let url_row = url_row.clone();
+            let alias_for_row = alias.clone();
+            row.connect_activated(move |_| {
+                if let Some(text) = ssh_host_completion(&url_row.text(), &alias_for_row) {
+                    url_row.set_text(&text);
+                    url_row.grab_focus();
+                    url_row.set_position(-1);
+                }
+            });
+            ssh_hosts_group.add(&row);
+            (alias, row)
+        })
+        .collect::<Vec<_>>();
+
+    let (shallow_row, shallow_check) = clone_option_row(
+        "Only recent history",
+        "Download the latest commit only. Older history can be fetched later.",
+    );
+    let (single_branch_row, single_branch_check) = clone_option_row(
+        "Only the default branch",
+        "Skip other branches on the remote.",
+    );
+    let options_group = PreferencesGroup::builder()
+        .title(gettext("Large stores"))
+        .build();
+    options_group.add(&shallow_row);
+    options_group.add(&single_branch_row);
 
     let page = PreferencesPage::new();
     page.add(&group);
+    page.add(&ssh_hosts_group);
+    page.add(&options_group);
 
     let error_label = Label::new(None);
     error_label.set_halign(Align::Start);
@@ -65,7 +256,7 @@ where
 
     let dialog = Dialog::builder()
         .title(gettext("Restore password store"))
-        .content_height(280)
+        .content_height(420)
         .content_width(800)
         .follows_content_size(true)
         .child(&dialog_content_shell(
@@ -87,22 +278,30 @@ where
         error_label_for_apply.set_visible(false);
 
         dialog_clone.close();
-        on_submit(url);
+        on_submit(
+            url,
+            StoreCloneOptions {
+                shallow: shallow_check.is_active(),
+                single_branch: single_branch_check.is_active(),
+            },
+        );
     });
 
-    {
-        let error_label = error_label.clone();
-        url_row.connect_changed(move |_| {
-            error_label.set_visible(false);
-        });
-    }
+    let feedback = CloneUrlFeedback {
+        error_label,
+        sign_in_row,
+        ssh_hosts_group,
+        ssh_host_rows,
+    };
+    feedback.sync("");
+    url_row.connect_changed(move |row| feedback.sync(&row.text()));
 
     dialog.present(Some(window));
 }
 
 pub fn prompt_store_clone<F>(window: &ApplicationWindow, overlay: &ToastOverlay, on_submit: F)
 where
-    F: Fn(String, String) + 'static,
+    F: Fn(String, String, StoreCloneOptions) + 'static,
 {
     let window = window.clone();
     let overlay = overlay.clone();
@@ -119,9 +318,11 @@ where
             let window_for_dialog = window.clone();
             let store_for_dialog = store.clone();
             let on_submit = on_submit.clone();
-            present_clone_url_dialog(&window_for_dialog, &store_for_dialog, move |url| {
-                on_submit(store.clone(), url)
-            });
+            present_clone_url_dialog(
+                &window_for_dialog,
+                &store_for_dialog,
+                move |url, options| on_submit(store.clone(), url, options),
+            );
         },
     );
 }
@@ -174,7 +375,7 @@ pub(super) fn append_store_clone_row(
             let overlay_for_clone = overlay.clone();
             let recipients_page_for_clone = recipients_page.clone();
             let before_navigation_for_clone = before_navigation.clone();
-            prompt_store_clone(&window, &overlay, move |store, url| {
+            prompt_store_clone(&window, &overlay, move |store, url, options| {
                 start_store_clone(
                     &window_for_clone,
                     &stores_list_for_clone,
@@ -183,6 +384,7 @@ pub(super) fn append_store_clone_row(
                     &recipients_page_for_clone,
                     store,
                     url,
+                    options,
                     before_navigation_for_clone.clone(),
                 );
             });
@@ -190,6 +392,129 @@ pub(super) fn append_store_clone_row(
     );
 }
 
+fn check_cloned_store_access(
+    window: &ApplicationWindow,
+    stores_list: &ListBox,
+    settings: &Preferences,
+    overlay: &ToastOverlay,
+    recipients_page: &StoreRecipientsPageState,
+    store: String,
+    before_navigation: Option<Rc<dyn Fn()>>,
+) {
+    let window = window.clone();
+    let stores_list = stores_list.clone();
+    let settings = settings.clone();
+    let overlay = overlay.clone();
+    let recipients_page = recipients_page.clone();
+    let store_for_thread = store.clone();
+    spawn_result_task(
+        move || check_cloned_store_keys(&store_for_thread),
+        move |access| {
+            present_cloned_store_key_guidance(
+                &window,
+                &stores_list,
+                &settings,
+                &overlay,
+                &recipients_page,
+                &store,
+                access,
+                before_navigation.clone(),
+            );
+        },
+        || {},
+    );
+}
+
+fn present_cloned_store_key_guidance(
+    window: &ApplicationWindow,
+    stores_list: &ListBox,
+    settings: &Preferences,
+    overlay: &ToastOverlay,
+    recipients_page: &StoreRecipientsPageState,
+    store: &str,
+    access: ClonedStoreKeyAccess,
+    before_navigation: Option<Rc<dyn Fn()>>,
+) {
+    let folders = match access {
+        ClonedStoreKeyAccess::Readable => return,
+        ClonedStoreKeyAccess::NoMatchingKey => Vec::new(),
+        ClonedStoreKeyAccess::OnlySubfolders(folders) => folders,
+    };
+
+    let dialog = if folders.is_empty() {
+        AlertDialog::builder()
+            .heading(gettext("No private key for this store"))
+            .body(gettext(
+                "The restored store is encrypted for keys that aren't in your keyring. Import your private key, or ask someone with access to add your key to the store.",
+            ))
+            .build()
+    } else {
+        AlertDialog::builder()
+            .heading(gettext("You can only open part of this store"))
+            .body(gettext(
+                "Your keys can open the folders below. Use one of them as the store, or import the private key for the rest.",
+            ))
+            .build()
+    };
+
+    if !folders.is_empty() {
+        let group = PreferencesGroup::new();
+        for folder in folders {
+            let row = ActionRow::builder()
+                .title(&folder)
+                .subtitle(gettext("Use as store"))
+                .activatable(true)
+                .build();
+            row.add_suffix(&adw::gtk::Image::from_icon_name("go-next-symbolic"));
+            let dialog = dialog.clone();
+            let stores_list = stores_list.clone();
+            let settings = settings.clone();
+            let overlay = overlay.clone();
+            let recipients_page = recipients_page.clone();
+            let store = store.to_string();
+            let before_navigation = before_navigation.clone();
+            row.connect_activated(move |_| {
+                dialog.close();
+                let subfolder = Path::new(&store).join(&folder);
+                let subfolder = subfolder.to_string_lossy().to_string();
+                let stores = stores_with_replacement(&settings.stores(), &store, &subfolder);
+                if let Err(err) = settings.set_stores(stores) {
+                    log_error(format!("Failed to save stores: {err}"));
+                    overlay.add_toast(Toast::new(&gettext("Couldn't add that folder.")));
+                    return;
+                }
+                rebuild_stores_list(
+                    &stores_list,
+                    &settings,
+                    &recipients_page,
+                    before_navigation.clone(),
+                );
+                refresh_after_store_list_change(&recipients_page);
+                overlay.add_toast(Toast::new(
+                    &gettext("Using {folder} as the store.").replace("{folder}", &folder),
+                ));
+            });
+            group.add(&row);
+        }
+        dialog.set_extra_child(Some(&group));
+    }
+
+    let close = gettext("Not Now");
+    let keys = gettext("Store Keys");
+    dialog.add_responses(&[("close", close.as_str()), ("keys", keys.as_str())]);
+    dialog.set_close_response("close");
+    dialog.set_default_response(Some("keys"));
+
+    let recipients_page = recipients_page.clone();
+    let store = store.to_string();
+    dialog.connect_response(None, move |_, response| {
+        if response == "keys" {
+            show_store_recipients_edit_page(&recipients_page, store.clone());
+        }
+    });
+    dialog.present(Some(window));
+}
+
 fn start_store_clone(
     window: &ApplicationWindow,
     stores_list: &ListBox,
@@ -198,9 +523,11 @@ fn start_store_clone(
     recipients_page: &StoreRecipientsPageState,
     store: String,
     url: String,
+    options: StoreCloneOptions,
     before_navigation: Option<Rc<dyn Fn()>>,
 ) {
     let progress_dialog = build_clone_progress_dialog(window, &store);
+    let window = window.clone();
     let progress_dialog_for_disconnect = progress_dialog.clone();
     let stores_list = stores_list.clone();
     let settings = settings.clone();
@@ -214,7 +541,7 @@ fn start_store_clone(
     let stores_list_for_result = stores_list;
     let recipients_page_for_result = recipients_page;
     spawn_result_task(
-        move || clone_store_repository(&url, &store_for_thread),
+        move || clone_store_repository(&url, &store_for_thread, options),
         move |result| match result {
             Ok(()) => {
                 progress_dialog.force_close();
@@ -235,6 +562,15 @@ fn start_store_clone(
                 );
                 refresh_after_store_list_change(&recipients_page_for_result);
                 overlay.add_toast(Toast::new(&gettext("Store restored.")));
+                check_cloned_store_access(
+                    &window,
+                    &stores_list_for_result,
+                    &settings_for_result,
+                    &overlay,
+                    &recipients_page_for_result,
+                    store_for_result.clone(),
+                    before_navigation.clone(),
+                );
             }
             Err(message) => {
                 progress_dialog.force_close();
@@ -253,7 +589,55 @@ fn start_store_clone(
 
 #[cfg(test)]
 mod tests {
-    use super::clone_url_dialog_error_message;
+    use super::{
+        clone_url_dialog_error_message, cloned_store_key_access, ssh_host_completion,
+        stores_with_replacement, ClonedStoreKeyAccess,
+    };
+
+    fn scopes(values: &[(&str, bool)]) -> Vec<(String, bool)> {
+        values
+            .iter()
+            .map(|(scope, readable)| (scope.to_string(), *readable))
+            .collect()
+    }
+
+    #[test]
+    fn cloned_store_is_readable_when_the_root_key_is_available() {
+        assert_eq!(
+            cloned_store_key_access(&scopes(&[(".", true), ("team", false)])),
+            ClonedStoreKeyAccess::Readable
+        );
+        assert_eq!(cloned_store_key_access(&[]), ClonedStoreKeyAccess::Readable);
+    }
+
+    #[test]
+    fn cloned_store_without_any_key_needs_an_import() {
+        assert_eq!(
+            cloned_store_key_access(&scopes(&[(".", false), ("team", false)])),
+            ClonedStoreKeyAccess::NoMatchingKey
+        );
+    }
+
+    #[test]
+    fn cloned_store_suggests_the_subfolders_that_can_be_decrypted() {
+        assert_eq!(
+            cloned_store_key_access(&scopes(&[
+                (".", false),
+                ("personal", true),
+                ("team", false),
+            ])),
+            ClonedStoreKeyAccess::OnlySubfolders(vec!["personal".to_string()])
+        );
+    }
+
+    #[test]
+    fn replacing_a_store_keeps_the_order() {
+        let stores = vec!["/a".to_string(), "/b".to_string(), "/c".to_string()];
+        assert_eq!(
+            stores_with_replacement(&stores, "/b", "/b/personal"),
+            vec!["/a", "/b/personal", "/c"]
+        );
+    }
 
     #[test]
     fn clone_url_dialog_requires_a_repository_url() {
@@ -270,4 +654,18 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn ssh_hosts_complete_the_host_part_only() {
+        assert_eq!(ssh_host_completion("wo", "work"), Some("work:".to_string()));
+        assert_eq!(
+            ssh_host_completion("git@wo", "work"),
+            Some("git@work:".to_string())
+        );
+        assert_eq!(ssh_host_completion("", "work"), Some("work:".to_string()));
+        assert_eq!(ssh_host_completion("work", "work"), None);
+        assert_eq!(ssh_host_completion("work:pass", "work"), None);
+        assert_eq!(ssh_host_completion("https://wo", "work"), None);
+        assert_eq!(ssh_host_completion("home", "work"), None);
+    }
 }
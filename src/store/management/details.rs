@@ -0,0 +1,163 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::password::entry_files::canonical_store_root;
+use crate::support::background::spawn_result_task;
+use crate::support::git::{
+    list_store_git_remotes, store_git_branch, store_git_upstream_remote, GitRemote,
+};
+use crate::support::sync_history::last_store_sync;
+use crate::support::ui::local_time_text;
+use crate::support::uri::launch_default_uri;
+use adw::glib;
+use adw::prelude::*;
+use adw::{ActionRow, Toast, ToastOverlay};
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct StoreRowDetails {
+    path: String,
+    remote: Option<GitRemote>,
+    branch: Option<String>,
+    last_sync: Option<i64>,
+}
+
+// The remote the branch tracks is the one sync pulls from first, so it is
+// the one worth naming. Stores without tracking fall back to `origin`.
+fn displayed_store_remote(remotes: Vec<GitRemote>, upstream: Option<&str>) -> Option<GitRemote> {
+    let position = upstream
+        .and_then(|upstream| remotes.iter().position(|remote| remote.name == upstream))
+        .or_else(|| remotes.iter().position(|remote| remote.name == "origin"))
+        .unwrap_or_default();
+    remotes.into_iter().nth(position)
+}
+
+fn read_store_row_details(root: &str) -> StoreRowDetails {
+    let branch = store_git_branch(root).unwrap_or_else(|err| {
+        log_error(format!("Failed to read the Git branch of '{root}': {err}"));
+        None
+    });
+    let remotes = list_store_git_remotes(root).unwrap_or_else(|err| {
+        log_error(format!("Failed to read the Git remotes of '{root}': {err}"));
+        Vec::new()
+    });
+    let upstream = branch
+        .as_deref()
+        .and_then(|branch| store_git_upstream_remote(root, branch).ok().flatten());
+
+    StoreRowDetails {
+        path: canonical_store_root(Path::new(root)).display().to_string(),
+        remote: displayed_store_remote(remotes, upstream.as_deref()),
+        branch,
+        last_sync: last_store_sync(root),
+    }
+}
+
+fn store_row_details_lines(details: &StoreRowDetails) -> Vec<String> {
+    let mut lines = vec![details.path.clone()];
+    if let Some(remote) = &details.remote {
+        lines.push(gettext("Remote: {url}").replace("{url}", &remote.url));
+    }
+    if let Some(branch) = &details.branch {
+        lines.push(gettext("Branch: {branch}").replace("{branch}", branch));
+    }
+    match details.last_sync {
+        Some(seconds) => {
+            lines.push(gettext("Last synced {time}").replace("{time}", &local_time_text(seconds)));
+        }
+        None if details.remote.is_some() => {
+            lines.push(gettext("Not synced on this device yet"));
+        }
+        None => {}
+    }
+    lines
+}
+
+// Reading the remote and branch runs Git, so the row shows up with its
+// recipients first and gets the rest once Git answers.
+pub(super) fn load_store_row_details(row: &ActionRow, root: &str, summary: String) {
+    let row = row.clone();
+    let root = root.to_string();
+    spawn_result_task(
+        move || read_store_row_details(&root),
+        move |details| {
+            let mut subtitle = glib::markup_escape_text(&summary).to_string();
+            for line in store_row_details_lines(&details) {
+                subtitle.push('\n');
+                subtitle.push_str(&glib::markup_escape_text(&line));
+            }
+            row.set_subtitle(&subtitle);
+        },
+        || {},
+    );
+}
+
+pub(super) fn open_store_folder(root: &str, overlay: &ToastOverlay) {
+    let folder_uri = adw::gio::File::for_path(root).uri();
+    let overlay = overlay.clone();
+    let folder_uri_for_log = folder_uri.clone();
+    launch_default_uri(&folder_uri, move |result| {
+        if let Err(error) = result {
+            log_error(format!(
+                "Failed to open store folder in the file manager.\nfolder: {folder_uri_for_log}\nerror: {error}"
+            ));
+            overlay.add_toast(Toast::new(&gettext("Couldn't open the folder.")));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{displayed_store_remote, store_row_details_lines, StoreRowDetails};
+    use crate::support::git::GitRemote;
+
+    fn remote(name: &str) -> GitRemote {
+        GitRemote {
+            name: name.to_string(),
+            url: format!("https://example.com/{name}.git"),
+        }
+    }
+
+    #[test]
+    fn store_rows_name_the_tracked_remote_first() {
+        let remotes = vec![remote("backup"), remote("origin"), remote("work")];
+
+        assert_eq!(
+            displayed_store_remote(remotes.clone(), Some("work")),
+            Some(remote("work"))
+        );
+        assert_eq!(
+            displayed_store_remote(remotes.clone(), None),
+            Some(remote("origin"))
+        );
+        assert_eq!(
+            displayed_store_remote(vec![remote("backup")], Some("gone")),
+            Some(remote("backup"))
+        );
+        assert_eq!(displayed_store_remote(Vec::new(), None), None);
+    }
+
+    #[test]
+    fn unsynced_remotes_say_so_and_local_stores_only_show_their_folder() {
+        let local = StoreRowDetails {
+            path: "/stores/home".to_string(),
+            ..StoreRowDetails::default()
+        };
+        assert_eq!(store_row_details_lines(&local), vec!["/stores/home"]);
+
+        let remote_store = StoreRowDetails {
+            path: "/stores/work".to_string(),
+            remote: Some(remote("origin")),
+            branch: Some("main".to_string()),
+            last_sync: None,
+        };
+        assert_eq!(
+            store_row_details_lines(&remote_store),
+            vec![
+                "/stores/work",
+                "Remote: https://example.com/origin.git",
+                "Branch: main",
+                "Not synced on this device yet",
+            ]
+        );
+    }
+}
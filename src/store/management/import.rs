@@ -1,4 +1,4 @@
-use crate::i18n::gettext;
+use crate::i18n::{gettext, ngettext};
 use crate::logging::log_error;
 use crate::preferences::Preferences;
 use crate::store::labels::shortened_store_labels;
@@ -17,7 +17,7 @@ use crate::support::ui::{
 use crate::window::navigation::{
     show_secondary_page_chrome, HasWindowChrome, WindowNavigationState,
 };
-use adw::gtk::{Button, Image, ListBox, ScrolledWindow, Stack};
+use adw::gtk::{Button, CheckButton, Image, ListBox, ScrolledWindow, Stack};
 use adw::prelude::*;
 use adw::{
     ActionRow, ApplicationWindow, ComboRow, EntryRow, NavigationPage, PasswordEntryRow, StatusPage,
@@ -221,6 +221,7 @@ pub struct StoreImportPageState {
     pub source_clear_button: Button,
     pub source_password_row: PasswordEntryRow,
     pub target_path_row: EntryRow,
+    pub organize_check: CheckButton,
     pub import_button: Button,
     pub store_roots: Rc<RefCell<Vec<String>>>,
     pub import_sources: Rc<RefCell<Vec<String>>>,
@@ -247,6 +248,8 @@ pub struct StoreImportControls<'a> {
     pub source_clear_button: &'a Button,
     pub source_password_row: &'a PasswordEntryRow,
     pub target_path_row: &'a EntryRow,
+    pub organize_row: &'a ActionRow,
+    pub organize_check: &'a CheckButton,
     pub import_button: &'a Button,
 }
 
@@ -257,6 +260,11 @@ pub struct StoreImportPageWidgets<'a> {
 
 impl StoreImportPageState {
     pub fn new(widgets: StoreImportPageWidgets<'_>) -> Self {
+        let organize_check = widgets.controls.organize_check.clone();
+        widgets
+            .controls
+            .organize_row
+            .connect_activated(move |_| organize_check.set_active(!organize_check.is_active()));
         Self {
             window: widgets.chrome.window.clone(),
             navigation: widgets.chrome.navigation.clone(),
@@ -273,6 +281,7 @@ impl StoreImportPageState {
             source_clear_button: widgets.controls.source_clear_button.clone(),
             source_password_row: widgets.controls.source_password_row.clone(),
             target_path_row: widgets.controls.target_path_row.clone(),
+            organize_check: widgets.controls.organize_check.clone(),
             import_button: widgets.controls.import_button.clone(),
             store_roots: Rc::new(RefCell::new(Vec::new())),
             import_sources: Rc::new(RefCell::new(Vec::new())),
@@ -311,6 +320,7 @@ fn reset_store_import_form(state: &StoreImportPageState) {
         .set_subtitle(&gettext(import_source_subtitle(None)));
     state.source_password_row.set_text("");
     state.target_path_row.set_text("");
+    state.organize_check.set_active(false);
 }
 
 fn sync_store_import_models(
@@ -442,6 +452,7 @@ pub fn initialize_store_import_page(state: &StoreImportPageState) {
                 source_path: state.source_path.borrow().clone(),
                 source_password: SecretString::from(state.source_password_row.text().as_str()),
                 target_path: normalize_optional_text(&state.target_path_row.text()),
+                organize_by_site: state.organize_check.is_active(),
             };
             start_pass_import(&state, request);
         });
@@ -450,20 +461,32 @@ pub fn initialize_store_import_page(state: &StoreImportPageState) {
 
 fn finish_pass_import(
     state: &StoreImportPageState,
-    result: Result<(), String>,
+    result: Result<usize, String>,
     store_root: &str,
     source: &str,
 ) {
     set_store_import_loading(state, false);
 
     match result {
-        Ok(()) => {
+        Ok(0) => {
             reset_store_import_form(state);
             pop_store_import_page_if_visible(state);
             state
                 .overlay
                 .add_toast(Toast::new(&gettext("Passwords imported.")));
         }
+        Ok(sorted) => {
+            reset_store_import_form(state);
+            pop_store_import_page_if_visible(state);
+            state.overlay.add_toast(Toast::new(
+                &ngettext(
+                    "Passwords imported. Sorted {count} item into site folders.",
+                    "Passwords imported. Sorted {count} items into site folders.",
+                    sorted,
+                )
+                .replace("{count}", &sorted.to_string()),
+            ));
+        }
         Err(err) => {
             log_error(format!(
                 "Failed to import passwords into '{}' from '{}': {err}",
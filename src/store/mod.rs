@@ -1,6 +1,10 @@
+pub mod appearance;
 pub mod git_page;
+pub mod git_restore;
 pub mod labels;
 pub mod management;
+pub mod read_only;
 pub mod recipients;
 pub mod recipients_page;
 pub mod support;
+pub mod webdav;
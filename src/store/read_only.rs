@@ -0,0 +1,51 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::Preferences;
+use crate::store::recipients_page::StoreRecipientsPageState;
+use crate::support::ui::dim_label_icon;
+use adw::gtk::{Align, CheckButton};
+use adw::prelude::*;
+use adw::{ActionRow, Toast};
+
+const fn read_only_toast_message(read_only: bool) -> &'static str {
+    if read_only {
+        "Store is now read-only."
+    } else {
+        "Store can be changed again."
+    }
+}
+
+pub fn append_store_read_only_row(state: &StoreRecipientsPageState, store: &str) {
+    let check = CheckButton::new();
+    check.set_valign(Align::Center);
+    check.set_active(Preferences::new().store_is_read_only(store));
+
+    let row = ActionRow::builder()
+        .title(gettext("Read-only"))
+        .subtitle(gettext(
+            "Block adding, editing, and removing items in this store. Syncing still brings in changes from others.",
+        ))
+        .activatable_widget(&check)
+        .build();
+    row.add_prefix(&dim_label_icon("changes-prevent-symbolic"));
+    row.add_suffix(&check);
+    state.platform.git_list.add(&row);
+    state.git_rows.borrow_mut().push(row.upcast());
+
+    let overlay = state.platform.overlay.clone();
+    let store = store.to_string();
+    check.connect_toggled(move |check| {
+        let read_only = check.is_active();
+        match Preferences::new().set_store_read_only(&store, read_only) {
+            Ok(()) => {
+                overlay.add_toast(Toast::new(&gettext(read_only_toast_message(read_only))));
+            }
+            Err(err) => {
+                log_error(format!(
+                    "Failed to save the read-only setting for '{store}': {err}"
+                ));
+                overlay.add_toast(Toast::new(&gettext("Couldn't save that setting.")));
+            }
+        }
+    });
+}
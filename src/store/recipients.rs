@@ -3,7 +3,7 @@ use crate::fido2_recipient::{
     build_fido2_recipient_string, is_fido2_recipient_string, parse_fido2_recipient_metadata_line,
     parse_fido2_recipient_string, FIDO2_RECIPIENTS_FILE_NAME,
 };
-use crate::i18n::gettext;
+use crate::i18n::{gettext, ngettext};
 use crate::support::runtime::supports_fidostore_features;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
@@ -54,6 +54,24 @@ fn fido2_recipients_path_for_scope(store_root: &str, scope: &str) -> PathBuf {
     store_recipients_scope_directory(store_root, scope).join(FIDO2_RECIPIENTS_FILE_NAME)
 }
 
+// A folder without its own recipient files uses the nearest parent that has
+// them, the same way `pass` picks a `.gpg-id`.
+pub fn effective_store_recipient_scope(store_root: &str, scope: &str) -> String {
+    let mut scope = normalized_store_recipients_scope(scope);
+    while scope != ROOT_STORE_RECIPIENTS_SCOPE {
+        if standard_recipients_path_for_scope(store_root, &scope).is_file()
+            || fido2_recipients_path_for_scope(store_root, &scope).is_file()
+        {
+            return scope;
+        }
+        scope = scope
+            .rsplit_once('/')
+            .map_or(ROOT_STORE_RECIPIENTS_SCOPE, |(parent, _)| parent)
+            .to_string();
+    }
+    scope
+}
+
 pub fn read_store_standard_recipients(store_root: &str) -> Vec<String> {
     read_store_standard_recipients_for_scope(store_root, ROOT_STORE_RECIPIENTS_SCOPE)
 }
@@ -200,8 +218,8 @@ pub fn store_recipients_subtitle(store_root: &str) -> String {
     let recipients = read_store_recipients(store_root);
     match recipients.len() {
         0 => gettext("No recipients set"),
-        1 => gettext("1 recipient"),
-        count => gettext("{count} recipients").replace("{count}", &count.to_string()),
+        count => ngettext("{count} recipient", "{count} recipients", count)
+            .replace("{count}", &count.to_string()),
     }
 }
 
@@ -320,9 +338,10 @@ pub fn stores_with_preferred_first(stores: &[String], preferred: &str) -> Vec<St
 #[cfg(test)]
 mod tests {
     use super::{
-        append_standard_recipients, normalize_standard_recipient, parse_fido2_recipients,
-        parse_standard_recipients, read_store_private_key_requirement_for_scope,
-        read_store_recipients_for_scope, relevant_store_recipient_scopes, split_store_recipients,
+        append_standard_recipients, effective_store_recipient_scope, normalize_standard_recipient,
+        parse_fido2_recipients, parse_standard_recipients,
+        read_store_private_key_requirement_for_scope, read_store_recipients_for_scope,
+        relevant_store_recipient_scopes, split_store_recipients,
         store_is_supported_in_current_build, store_recipients_subtitle,
         store_uses_fido2_recipients, stores_with_preferred_first, ROOT_STORE_RECIPIENTS_SCOPE,
         UNSUPPORTED_FIDOSTORE_MESSAGE,
@@ -428,6 +447,36 @@ mod tests {
         fs::remove_dir_all(store).expect("remove temporary store");
     }
 
+    #[test]
+    fn folders_without_recipients_use_the_nearest_parent_scope() {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time before unix epoch")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("keycord-store-effective-{timestamp}"));
+        fs::create_dir_all(store.join("team/ops/db")).expect("create store directories");
+        fs::write(store.join(".gpg-id"), "root@example.com\n").expect("write root recipients");
+        fs::write(store.join("team/.gpg-id"), "team@example.com\n")
+            .expect("write nested recipients");
+        let store_root = store.to_string_lossy().to_string();
+
+        assert_eq!(
+            effective_store_recipient_scope(&store_root, "team/ops/db"),
+            "team"
+        );
+        assert_eq!(effective_store_recipient_scope(&store_root, "team"), "team");
+        assert_eq!(
+            effective_store_recipient_scope(&store_root, "other"),
+            ROOT_STORE_RECIPIENTS_SCOPE
+        );
+        assert_eq!(
+            effective_store_recipient_scope(&store_root, ROOT_STORE_RECIPIENTS_SCOPE),
+            ROOT_STORE_RECIPIENTS_SCOPE
+        );
+
+        fs::remove_dir_all(store).expect("remove temporary store");
+    }
+
     #[test]
     fn relevant_scopes_include_only_root_and_nested_files_that_differ_from_root() {
         let timestamp = SystemTime::now()
@@ -1,8 +1,10 @@
 use super::list::rebuild_store_recipients_list;
 use super::mode::ensure_standard_recipient_actions_allowed;
+use super::save::queue_store_recipients_autosave;
 use super::sync::sync_private_keys_to_host_if_enabled;
 use super::{
-    present_store_recipients_dialog, sync_store_recipients_page_header, StoreRecipientsPageState,
+    present_store_recipients_dialog, sync_store_recipients_page_header, StoreRecipientsMode,
+    StoreRecipientsPageState,
 };
 use crate::backend::{
     generate_fido2_private_key, generate_ripasso_private_key, set_fido2_security_key_pin,
@@ -135,10 +137,10 @@ fn finish_private_key_generation(
     result: Result<ManagedRipassoPrivateKey, PrivateKeyError>,
 ) {
     match result {
-        Ok(_) => {
+        Ok(key) => {
             clear_private_key_generation_form(state);
             pop_private_key_generation_page_if_visible(state);
-            finish_generated_key(state);
+            finish_generated_key(state, &key);
         }
         Err(err) => {
             log_error(format!("Failed to generate private key: {err}"));
@@ -150,9 +152,26 @@ fn finish_private_key_generation(
     }
 }
 
-fn finish_generated_key(state: &StoreRecipientsPageState) {
+// A new store without any keys yet takes the key that was just made, so a
+// first store is ready as soon as its key exists.
+fn generated_key_starts_new_store(
+    mode: Option<StoreRecipientsMode>,
+    recipients: &[String],
+) -> bool {
+    mode == Some(StoreRecipientsMode::Create) && recipients.is_empty()
+}
+
+fn finish_generated_key(state: &StoreRecipientsPageState, key: &ManagedRipassoPrivateKey) {
     let _ = sync_private_keys_to_host_if_enabled(state);
+    let mode = state.request.borrow().as_ref().map(|request| request.mode);
+    let starts_store = generated_key_starts_new_store(mode, &state.recipients.borrow());
+    if starts_store {
+        state.recipients.borrow_mut().push(key.fingerprint.clone());
+    }
     rebuild_store_recipients_list(state);
+    if starts_store {
+        queue_store_recipients_autosave(state);
+    }
     activate_widget_action(&state.window, "win.reload-password-list");
     state
         .platform
@@ -195,7 +214,7 @@ fn finish_fido2_private_key_generation(
     result: Result<ManagedRipassoPrivateKey, PrivateKeyError>,
 ) {
     match result {
-        Ok(_) => finish_generated_key(state),
+        Ok(key) => finish_generated_key(state, &key),
         Err(err) => {
             log_error(format!("Failed to generate FIDO2-protected key: {err}"));
             state
@@ -549,10 +568,28 @@ pub(super) fn connect_private_key_generate_controls(state: &StoreRecipientsPageS
 #[cfg(test)]
 mod tests {
     use super::{
-        next_autofilled_value, private_key_generation_apply_enabled, suggested_email_from_name,
-        suggested_name_from_email, validate_private_key_generation_request,
+        generated_key_starts_new_store, next_autofilled_value,
+        private_key_generation_apply_enabled, suggested_email_from_name, suggested_name_from_email,
+        validate_private_key_generation_request, StoreRecipientsMode,
     };
 
+    #[test]
+    fn generated_keys_only_start_new_stores_without_keys() {
+        assert!(generated_key_starts_new_store(
+            Some(StoreRecipientsMode::Create),
+            &[]
+        ));
+        assert!(!generated_key_starts_new_store(
+            Some(StoreRecipientsMode::Create),
+            &["ABCDEF".to_string()]
+        ));
+        assert!(!generated_key_starts_new_store(
+            Some(StoreRecipientsMode::Edit),
+            &[]
+        ));
+        assert!(!generated_key_starts_new_store(None, &[]));
+    }
+
     #[test]
     fn generation_request_requires_name_email_and_matching_passwords() {
         assert_eq!(
@@ -399,16 +399,24 @@ fn available_recipient_scopes(state: &StoreRecipientsPageState) -> Vec<String> {
     let Some(request) = state.current_request() else {
         return vec![ROOT_STORE_RECIPIENTS_SCOPE.to_string()];
     };
-    if !Preferences::new().uses_integrated_backend() {
-        return vec![ROOT_STORE_RECIPIENTS_SCOPE.to_string()];
-    }
 
-    let scopes = relevant_store_recipient_scopes(&request.store);
+    let pending = state.pending_recipient_scope.borrow().clone();
+    recipient_scopes_with_pending(relevant_store_recipient_scopes(&request.store), pending)
+}
+
+// A folder opened from the list stays selectable before its `.gpg-id` exists.
+fn recipient_scopes_with_pending(mut scopes: Vec<String>, pending: Option<String>) -> Vec<String> {
     if scopes.is_empty() {
-        vec![ROOT_STORE_RECIPIENTS_SCOPE.to_string()]
-    } else {
-        scopes
+        scopes.push(ROOT_STORE_RECIPIENTS_SCOPE.to_string());
+    }
+    if let Some(pending) = pending.filter(|pending| !scopes.contains(pending)) {
+        let position = scopes
+            .iter()
+            .position(|scope| scope != ROOT_STORE_RECIPIENTS_SCOPE && scope > &pending)
+            .unwrap_or(scopes.len());
+        scopes.insert(position, pending);
     }
+    scopes
 }
 
 fn show_recipient_scope_selector(scopes: &[String]) -> bool {
@@ -1336,10 +1344,11 @@ mod tests {
         effective_private_key_verification_warning, fido2_recipient_remove_block_message,
         merge_available_private_keys, private_key_delete_block_message,
         private_key_toggle_block_message, private_key_verification_warning, recipient_scope_label,
-        selected_available_private_key_count, show_all_fido2_keys_required_info,
-        show_recipient_scope_selector, show_require_all_private_keys_option,
-        show_store_options_title_above_git_row, unresolved_private_key_recipients,
-        AvailablePrivateKey, HostGpgPrivateKeySummary, PrivateKeyVerificationWarning,
+        recipient_scopes_with_pending, selected_available_private_key_count,
+        show_all_fido2_keys_required_info, show_recipient_scope_selector,
+        show_require_all_private_keys_option, show_store_options_title_above_git_row,
+        unresolved_private_key_recipients, AvailablePrivateKey, HostGpgPrivateKeySummary,
+        PrivateKeyVerificationWarning,
     };
     use crate::backend::{
         ConnectedSmartcardKey, ManagedRipassoHardwareKey, ManagedRipassoPrivateKey,
@@ -1357,6 +1366,33 @@ mod tests {
         .expect("build recipient")
     }
 
+    #[test]
+    fn pending_folder_scopes_join_the_sorted_scope_list_once() {
+        let scopes = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| (*value).to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            recipient_scopes_with_pending(scopes(&[".", "ops"]), Some("dev".to_string())),
+            scopes(&[".", "dev", "ops"])
+        );
+        assert_eq!(
+            recipient_scopes_with_pending(Vec::new(), Some("team".to_string())),
+            scopes(&[".", "team"])
+        );
+        assert_eq!(
+            recipient_scopes_with_pending(scopes(&[".", "team"]), Some("team".to_string())),
+            scopes(&[".", "team"])
+        );
+        assert_eq!(
+            recipient_scopes_with_pending(Vec::new(), None),
+            scopes(&["."])
+        );
+    }
+
     #[test]
     fn merged_private_keys_prefer_managed_duplicates() {
         let managed = ManagedRipassoPrivateKey {
@@ -1,6 +1,7 @@
 use super::recipients::{
-    read_store_private_key_requirement, read_store_private_key_requirement_for_scope,
-    read_store_recipients, read_store_recipients_for_scope, store_is_supported_in_current_build,
+    effective_store_recipient_scope, read_store_private_key_requirement,
+    read_store_private_key_requirement_for_scope, read_store_recipients,
+    read_store_recipients_for_scope, store_is_supported_in_current_build,
     ROOT_STORE_RECIPIENTS_SCOPE, UNSUPPORTED_FIDOSTORE_MESSAGE,
 };
 use crate::backend::DiscoveredHardwareToken;
@@ -14,6 +15,8 @@ use crate::support::ui::{
 };
 use crate::window::navigation::{show_secondary_page_chrome, HasWindowChrome, APP_WINDOW_TITLE};
 use crate::window::preferences_search::PreferencesPageSearchState;
+use adw::gio::SimpleAction;
+use adw::glib::StaticVariantType;
 use adw::gtk::{Button, CheckButton, ScrolledWindow, Stack, Widget};
 use adw::prelude::*;
 use adw::{
@@ -113,6 +116,7 @@ pub struct StoreRecipientsPageState {
     pub saved_recipients: Rc<RefCell<Vec<String>>>,
     pub recipient_scope_dirs: Rc<RefCell<Vec<String>>>,
     pub selected_recipient_scope: Rc<RefCell<String>>,
+    pub pending_recipient_scope: Rc<RefCell<Option<String>>>,
     pub private_key_requirement: Rc<Cell<StoreRecipientsPrivateKeyRequirement>>,
     pub saved_private_key_requirement: Rc<Cell<StoreRecipientsPrivateKeyRequirement>>,
     pub save_in_flight: Rc<Cell<bool>>,
@@ -239,9 +243,10 @@ pub(super) fn load_store_recipients_scope(
     } else {
         scope
     };
-    let recipients = read_store_recipients_for_scope(store_root, normalized_scope);
+    let source_scope = effective_store_recipient_scope(store_root, normalized_scope);
+    let recipients = read_store_recipients_for_scope(store_root, &source_scope);
     let private_key_requirement =
-        read_store_private_key_requirement_for_scope(store_root, normalized_scope);
+        read_store_private_key_requirement_for_scope(store_root, &source_scope);
     *state.selected_recipient_scope.borrow_mut() = normalized_scope.to_string();
     *state.recipients.borrow_mut() = recipients.clone();
     *state.saved_recipients.borrow_mut() = recipients;
@@ -303,8 +308,10 @@ fn show_store_recipients_page(
     request: StoreRecipientsRequest,
     initial_recipients: Vec<String>,
     private_key_requirement: StoreRecipientsPrivateKeyRequirement,
+    folder: Option<String>,
 ) {
     let mode = request.mode;
+    let store = request.store.clone();
     *state.request.borrow_mut() = Some(request);
     *state.recipient_scope_dirs.borrow_mut() = Vec::new();
     *state.selected_recipient_scope.borrow_mut() = ROOT_STORE_RECIPIENTS_SCOPE.to_string();
@@ -314,6 +321,10 @@ fn show_store_recipients_page(
     state
         .saved_private_key_requirement
         .set(private_key_requirement);
+    if let Some(folder) = &folder {
+        load_store_recipients_scope(state, &store, folder);
+    }
+    *state.pending_recipient_scope.borrow_mut() = folder;
     state.save_in_flight.set(false);
     state.save_queued.set(false);
     state.reopen_after_subpage.set(false);
@@ -339,6 +350,7 @@ pub fn show_store_recipients_create_page(
         StoreRecipientsRequest::create(store),
         initial_recipients,
         StoreRecipientsPrivateKeyRequirement::AnyManagedKey,
+        None,
     );
 }
 
@@ -357,9 +369,56 @@ pub fn show_store_recipients_edit_page(state: &StoreRecipientsPageState, store:
         StoreRecipientsRequest::edit(store.clone()),
         read_store_recipients(&store),
         read_store_private_key_requirement(&store),
+        None,
+    );
+}
+
+// Opens the store keys on one folder. A folder without its own `.gpg-id`
+// starts from the keys it inherits and gets its own file on the first change,
+// like `pass init -p`.
+pub fn show_store_folder_recipients_page(
+    state: &StoreRecipientsPageState,
+    store: impl Into<String>,
+    folder: &str,
+) {
+    let store = store.into();
+    if !store_is_supported_in_current_build(&store) {
+        state
+            .platform
+            .overlay
+            .add_toast(Toast::new(&gettext(UNSUPPORTED_FIDOSTORE_MESSAGE)));
+        return;
+    }
+
+    show_store_recipients_page(
+        state,
+        StoreRecipientsRequest::edit(store.clone()),
+        read_store_recipients(&store),
+        read_store_private_key_requirement(&store),
+        Some(folder.trim_end_matches('/').to_string()),
     );
 }
 
+pub fn register_store_folder_recipients_action(
+    window: &ApplicationWindow,
+    state: &StoreRecipientsPageState,
+) {
+    let action = SimpleAction::new(
+        "open-folder-keys",
+        Some(&<(String, String)>::static_variant_type()),
+    );
+    let state = state.clone();
+    action.connect_activate(move |_, parameter| {
+        let Some((store, folder)) =
+            parameter.and_then(|parameter| parameter.get::<(String, String)>())
+        else {
+            return;
+        };
+        show_store_folder_recipients_page(&state, store, &folder);
+    });
+    window.add_action(&action);
+}
+
 #[cfg(test)]
 mod tests {
     use super::StoreRecipientsMode;
@@ -0,0 +1,212 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::password::entry_files::normalize_password_entry_label;
+use crate::preferences::{Preferences, StoreWebDavFolder};
+use crate::store::git_page::rebuild_store_recipients_git_row;
+use crate::store::recipients_page::StoreRecipientsPageState;
+use crate::support::ui::{
+    append_action_group_row_with_button, dialog_content_shell, dim_label_icon,
+};
+use crate::support::webdav::validate_webdav_folder_url;
+use adw::gtk::{Align, Box as GtkBox, Image, Label, Orientation};
+use adw::prelude::*;
+use adw::{ActionRow, Dialog, EntryRow, PreferencesGroup, PreferencesPage, Toast};
+
+fn webdav_row_subtitle(folder: Option<&StoreWebDavFolder>) -> String {
+    folder.map_or_else(
+        || gettext("Sync this store with a WebDAV or Nextcloud folder."),
+        |folder| folder.url.clone(),
+    )
+}
+
+fn webdav_folder_from_input(
+    store: &str,
+    url: &str,
+    login_entry: &str,
+) -> Result<StoreWebDavFolder, String> {
+    let url = validate_webdav_folder_url(url)?;
+    Ok(StoreWebDavFolder {
+        store: store.to_string(),
+        url: url.to_string(),
+        login_entry: normalize_password_entry_label(login_entry.trim()),
+    })
+}
+
+pub fn append_store_webdav_row(state: &StoreRecipientsPageState, store: &str) {
+    let folder = Preferences::new().store_webdav_folder(store);
+    let row = ActionRow::builder()
+        .title(gettext("WebDAV folder"))
+        .subtitle(webdav_row_subtitle(folder.as_ref()))
+        .build();
+    row.set_activatable(true);
+    row.add_prefix(&dim_label_icon("folder-remote-symbolic"));
+    row.add_suffix(&Image::from_icon_name("go-next-symbolic"));
+    state.platform.git_list.add(&row);
+    state.git_rows.borrow_mut().push(row.clone().upcast());
+
+    let state = state.clone();
+    let store = store.to_string();
+    row.connect_activated(move |_| {
+        present_store_webdav_dialog(&state, &store);
+    });
+}
+
+fn save_store_webdav_folder(
+    state: &StoreRecipientsPageState,
+    store: &str,
+    folder: Option<StoreWebDavFolder>,
+) -> bool {
+    let removed = folder.is_none();
+    match Preferences::new().set_store_webdav_folder(store, folder) {
+        Ok(()) => {
+            rebuild_store_recipients_git_row(state);
+            let message = if removed {
+                "WebDAV sync turned off."
+            } else {
+                "WebDAV folder saved."
+            };
+            state
+                .platform
+                .overlay
+                .add_toast(Toast::new(&gettext(message)));
+            true
+        }
+        Err(err) => {
+            log_error(format!(
+                "Failed to save the WebDAV folder for '{store}': {err}"
+            ));
+            state
+                .platform
+                .overlay
+                .add_toast(Toast::new(&gettext("Couldn't save the WebDAV folder.")));
+            false
+        }
+    }
+}
+
+fn present_store_webdav_dialog(state: &StoreRecipientsPageState, store: &str) {
+    let folder = Preferences::new().store_webdav_folder(store);
+    let url_row = EntryRow::new();
+    url_row.set_title(&gettext("Folder URL"));
+    url_row.set_text(folder.as_ref().map_or("", |folder| folder.url.as_str()));
+    url_row.set_show_apply_button(true);
+    let login_row = EntryRow::new();
+    login_row.set_title(&gettext("Login item"));
+    login_row.set_text(
+        folder
+            .as_ref()
+            .map_or("", |folder| folder.login_entry.as_str()),
+    );
+    login_row.set_show_apply_button(true);
+
+    let group = PreferencesGroup::builder()
+        .description(gettext(
+            "Keycord uploads only encrypted items. The login item holds the WebDAV password on its first line and the username in a username field.",
+        ))
+        .build();
+    group.add(&url_row);
+    group.add(&login_row);
+
+    let page = PreferencesPage::new();
+    page.add(&group);
+
+    let error_label = Label::new(None);
+    error_label.set_halign(Align::Start);
+    error_label.set_wrap(true);
+    error_label.add_css_class("error");
+    error_label.add_css_class("caption");
+    error_label.set_margin_top(6);
+    error_label.set_margin_start(18);
+    error_label.set_margin_end(18);
+    error_label.set_margin_bottom(18);
+    error_label.set_visible(false);
+
+    let content = GtkBox::new(Orientation::Vertical, 0);
+    content.append(&page);
+    content.append(&error_label);
+
+    let dialog = Dialog::builder()
+        .title(gettext("WebDAV folder"))
+        .content_height(320)
+        .content_width(800)
+        .follows_content_size(true)
+        .child(&dialog_content_shell(
+            "WebDAV folder",
+            Some(store),
+            &content,
+        ))
+        .build();
+
+    if folder.is_some() {
+        let remove_group = PreferencesGroup::new();
+        let state = state.clone();
+        let store = store.to_string();
+        let dialog = dialog.clone();
+        append_action_group_row_with_button(
+            &remove_group,
+            "Stop syncing",
+            "Forget this WebDAV folder. Files stay on the server.",
+            "user-trash-symbolic",
+            move || {
+                if save_store_webdav_folder(&state, &store, None) {
+                    dialog.close();
+                }
+            },
+        );
+        page.add(&remove_group);
+    }
+
+    let submit = {
+        let state = state.clone();
+        let store = store.to_string();
+        let dialog = dialog.clone();
+        let url_row = url_row.clone();
+        let login_row = login_row.clone();
+        let error_label = error_label.clone();
+        move || match webdav_folder_from_input(&store, &url_row.text(), &login_row.text()) {
+            Ok(folder) => {
+                error_label.set_visible(false);
+                if save_store_webdav_folder(&state, &store, Some(folder)) {
+                    dialog.close();
+                }
+            }
+            Err(message) => {
+                error_label.set_label(&gettext(&message));
+                error_label.set_visible(true);
+            }
+        }
+    };
+    {
+        let submit = submit.clone();
+        url_row.connect_apply(move |_| submit());
+    }
+    login_row.connect_apply(move |_| submit());
+
+    for row in [&url_row, &login_row] {
+        let error_label = error_label.clone();
+        row.connect_changed(move |_| {
+            error_label.set_visible(false);
+        });
+    }
+
+    dialog.present(Some(&state.window));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::webdav_folder_from_input;
+
+    #[test]
+    fn webdav_folder_input_is_validated_and_normalized() {
+        let folder = webdav_folder_from_input(
+            "/stores/work",
+            " https://cloud.example/dav/pass ",
+            " dav//login/ ",
+        )
+        .expect("valid WebDAV folder");
+
+        assert_eq!(folder.url, "https://cloud.example/dav/pass");
+        assert_eq!(folder.login_entry, "dav/login");
+        assert!(webdav_folder_from_input("/stores/work", "ftp://cloud.example", "").is_err());
+    }
+}
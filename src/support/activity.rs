@@ -0,0 +1,94 @@
+use crate::logging::log_error;
+use crate::preferences::Preferences;
+use crate::support::paths::app_state_path;
+use crate::support::secure_fs::append_private_file;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const ACTIVITY_LOG_FILE_NAME: &str = "activity.log";
+
+// The activity log lives next to other per-device state, outside the stores,
+// so it never ends up in Git history.
+pub fn activity_log_path() -> Option<PathBuf> {
+    app_state_path(ACTIVITY_LOG_FILE_NAME)
+}
+
+fn activity_log_line(timestamp: &str, message: &str) -> String {
+    let message = message
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("{timestamp} {message}\n")
+}
+
+fn activity_timestamp() -> String {
+    adw::glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y-%m-%d %H:%M:%S"))
+        .map(|value| value.to_string())
+        .unwrap_or_default()
+}
+
+pub fn record_activity(message: impl AsRef<str>) {
+    if !Preferences::new().activity_log_enabled() {
+        return;
+    }
+    let Some(path) = activity_log_path() else {
+        return;
+    };
+
+    let line = activity_log_line(&activity_timestamp(), message.as_ref());
+    if let Err(err) = append_private_file(&path, line) {
+        log_error(format!("Failed to write the activity log: {err}"));
+    }
+}
+
+pub fn read_activity_log() -> io::Result<String> {
+    let Some(path) = activity_log_path() else {
+        return Ok(String::new());
+    };
+    read_activity_log_at(&path)
+}
+
+fn read_activity_log_at(path: &Path) -> io::Result<String> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err),
+    }
+}
+
+pub fn purge_activity_log() -> io::Result<()> {
+    let Some(path) = activity_log_path() else {
+        return Ok(());
+    };
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{activity_log_line, read_activity_log_at};
+
+    #[test]
+    fn activity_log_lines_stay_on_one_line() {
+        assert_eq!(
+            activity_log_line("2025-12-01 09:30:00", "Synced\n work store\n"),
+            "2025-12-01 09:30:00 Synced work store\n"
+        );
+    }
+
+    #[test]
+    fn missing_activity_logs_read_as_empty() {
+        assert_eq!(
+            read_activity_log_at(&std::env::temp_dir().join("keycord-missing-activity.log"))
+                .expect("read missing log"),
+            ""
+        );
+    }
+}
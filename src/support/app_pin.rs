@@ -0,0 +1,174 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::random;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+pub const APP_PIN_MIN_LENGTH: usize = 4;
+const APP_PIN_HASH_SCHEME: &str = "argon2id";
+const APP_PIN_SALT_LEN: usize = 16;
+const APP_PIN_DIGEST_LEN: usize = 32;
+const APP_PIN_MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(&mut hex, "{byte:02x}");
+    }
+    hex
+}
+
+fn from_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+// A short PIN is easy to guess offline, so it goes through Argon2id, which
+// makes every guess cost memory as well as time.
+fn app_pin_digest(pin: &str, salt: &[u8], params: Params) -> Option<[u8; APP_PIN_DIGEST_LEN]> {
+    let mut digest = [0; APP_PIN_DIGEST_LEN];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(pin.as_bytes(), salt, &mut digest)
+        .ok()?;
+    Some(digest)
+}
+
+fn app_pin_hash_with_params(pin: &str, salt: &[u8], params: &Params) -> Option<String> {
+    let digest = app_pin_digest(pin, salt, params.clone())?;
+    Some(format!(
+        "{APP_PIN_HASH_SCHEME}${}${}${}${}${}",
+        params.m_cost(),
+        params.t_cost(),
+        params.p_cost(),
+        to_hex(salt),
+        to_hex(&digest)
+    ))
+}
+
+fn digests_match(expected: &[u8], actual: &[u8]) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .fold(0, |diff, (left, right)| diff | (left ^ right))
+            == 0
+}
+
+pub fn app_pin_is_valid(pin: &str) -> bool {
+    pin.chars().count() >= APP_PIN_MIN_LENGTH && !pin.chars().any(char::is_whitespace)
+}
+
+pub fn hash_app_pin(pin: &str) -> Result<String, String> {
+    let salt = random::<[u8; APP_PIN_SALT_LEN]>();
+    app_pin_hash_with_params(pin, &salt, &Params::default())
+        .ok_or_else(|| "Failed to hash the app PIN.".to_string())
+}
+
+// Each wrong PIN doubles the wait before the next try, up to a minute.
+pub fn app_pin_retry_delay(failed_attempts: u32) -> Duration {
+    if failed_attempts == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_secs(1 << (failed_attempts - 1).min(6)).min(APP_PIN_MAX_RETRY_DELAY)
+}
+
+pub fn app_pin_matches(stored: &str, pin: &str) -> bool {
+    let mut parts = stored.split('$');
+    let (
+        Some(APP_PIN_HASH_SCHEME),
+        Some(m_cost),
+        Some(t_cost),
+        Some(p_cost),
+        Some(salt),
+        Some(expected),
+        None,
+    ) = (
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+        parts.next(),
+    )
+    else {
+        return false;
+    };
+    let (Ok(m_cost), Ok(t_cost), Ok(p_cost), Some(salt), Some(expected)) = (
+        m_cost.parse::<u32>(),
+        t_cost.parse::<u32>(),
+        p_cost.parse::<u32>(),
+        from_hex(salt),
+        from_hex(expected),
+    ) else {
+        return false;
+    };
+    let Ok(params) = Params::new(m_cost, t_cost, p_cost, Some(APP_PIN_DIGEST_LEN)) else {
+        return false;
+    };
+    app_pin_digest(pin, &salt, params).is_some_and(|actual| digests_match(&expected, &actual))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        app_pin_hash_with_params, app_pin_is_valid, app_pin_matches, app_pin_retry_delay,
+        hash_app_pin,
+    };
+    use argon2::Params;
+    use std::time::Duration;
+
+    #[test]
+    fn app_pin_hashes_are_salted_and_verifiable() {
+        let first = hash_app_pin("2468").expect("hash PIN");
+        let second = hash_app_pin("2468").expect("hash PIN");
+        assert_ne!(first, second);
+        assert!(!first.contains("2468"));
+        assert!(app_pin_matches(&first, "2468"));
+        assert!(app_pin_matches(&second, "2468"));
+        assert!(!app_pin_matches(&first, "2469"));
+    }
+
+    #[test]
+    fn app_pin_hashes_keep_their_parameters() {
+        let params = Params::new(64, 1, 1, Some(32)).expect("params");
+        let stored =
+            app_pin_hash_with_params("1234", b"saltsalt", &params).expect("hash with params");
+        assert!(stored.starts_with("argon2id$64$1$1$73616c7473616c74$"));
+        assert!(app_pin_matches(&stored, "1234"));
+        assert!(!app_pin_matches(&stored, "4321"));
+    }
+
+    #[test]
+    fn wrong_pins_wait_longer_each_time() {
+        assert_eq!(app_pin_retry_delay(0), Duration::ZERO);
+        assert_eq!(app_pin_retry_delay(1), Duration::from_secs(1));
+        assert_eq!(app_pin_retry_delay(3), Duration::from_secs(4));
+        assert_eq!(app_pin_retry_delay(20), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn malformed_app_pin_hashes_never_match() {
+        for stored in [
+            "",
+            "1234",
+            "md5$1$00$00",
+            "argon2id$0$1$1$00$00",
+            "argon2id$64$1$1$zz$00",
+        ] {
+            assert!(!app_pin_matches(stored, "1234"), "{stored}");
+        }
+    }
+
+    #[test]
+    fn app_pins_need_four_characters_without_spaces() {
+        assert!(app_pin_is_valid("1234"));
+        assert!(app_pin_is_valid("café"));
+        assert!(!app_pin_is_valid("123"));
+        assert!(!app_pin_is_valid("12 34"));
+    }
+}
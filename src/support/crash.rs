@@ -0,0 +1,208 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::support::paths::app_state_path;
+use crate::support::secure_fs::write_private_file;
+use crate::support::uri::launch_default_uri;
+use adw::gtk::{ScrolledWindow, TextView, Widget, WrapMode};
+use adw::prelude::*;
+use adw::{glib, AlertDialog, Application};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CRASH_REPORT_DIR_NAME: &str = "crash-reports";
+const PENDING_CRASH_REPORT_FILE_NAME: &str = "pending";
+const CRASH_REPORT_DIALOG_BODY: &str = "A report was saved to {path}. Nothing was sent anywhere. Attach the report to a bug to help fix this.";
+
+thread_local! {
+    static CRASH_REPORT_APP: RefCell<Option<(glib::WeakRef<Application>, &'static str)>> =
+        const { RefCell::new(None) };
+}
+
+pub fn crash_report_dir() -> Option<PathBuf> {
+    app_state_path(CRASH_REPORT_DIR_NAME)
+}
+
+fn panic_payload_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+fn crash_report_text(
+    thread: &str,
+    message: &str,
+    location: Option<&str>,
+    backtrace: &str,
+) -> String {
+    format!(
+        "{} {}\nthread: {thread}\nmessage: {message}\nlocation: {}\n\nbacktrace:\n{backtrace}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        location.unwrap_or("unknown"),
+    )
+}
+
+fn save_crash_report(report: &str) -> Result<PathBuf, String> {
+    let dir = crash_report_dir().ok_or_else(|| "No folder for crash reports.".to_string())?;
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let path = dir.join(format!("crash-{seconds}.txt"));
+    write_private_file(&path, report).map_err(|err| err.to_string())?;
+    write_private_file(
+        &dir.join(PENDING_CRASH_REPORT_FILE_NAME),
+        path.to_string_lossy().as_bytes(),
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
+// Reports stay on this device. The dialog only shows them so they can be
+// attached to a bug by hand.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let current = thread::current();
+        let report = crash_report_text(
+            current.name().unwrap_or("unnamed"),
+            panic_payload_message(info.payload()),
+            info.location().map(ToString::to_string).as_deref(),
+            &Backtrace::force_capture().to_string(),
+        );
+        match save_crash_report(&report) {
+            Ok(path) => {
+                log_error(format!("Saved a crash report to '{}'.", path.display()));
+                // A worker thread that panics leaves the app running, so the
+                // main loop shows the report once it gets to it.
+                glib::idle_add_once(show_pending_crash_report);
+            }
+            Err(err) => log_error(format!("Failed to save a crash report: {err}")),
+        }
+        default_hook(info);
+    }));
+}
+
+fn take_pending_crash_report() -> Option<(PathBuf, String)> {
+    let pending = crash_report_dir()?.join(PENDING_CRASH_REPORT_FILE_NAME);
+    let path = PathBuf::from(fs::read_to_string(&pending).ok()?.trim());
+    let _ = fs::remove_file(&pending);
+    let report = fs::read_to_string(&path).ok()?;
+    Some((path, report))
+}
+
+fn present_crash_report_dialog(parent: &Widget, path: &str, report: &str, issue_url: &'static str) {
+    let body = gettext(CRASH_REPORT_DIALOG_BODY).replace("{path}", path);
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Keycord ran into a problem"))
+        .body(body)
+        .build();
+
+    let text = TextView::new();
+    text.set_editable(false);
+    text.set_monospace(true);
+    text.set_wrap_mode(WrapMode::WordChar);
+    text.buffer().set_text(report);
+    let scrolled = ScrolledWindow::builder()
+        .child(&text)
+        .min_content_height(200)
+        .build();
+    dialog.set_extra_child(Some(&scrolled));
+
+    let close = gettext("Close");
+    let copy = gettext("Copy report");
+    let report_bug = gettext("Report bug");
+    dialog.add_responses(&[
+        ("close", close.as_str()),
+        ("copy", copy.as_str()),
+        ("report", report_bug.as_str()),
+    ]);
+    dialog.set_close_response("close");
+    dialog.set_default_response(Some("report"));
+
+    let report = report.to_string();
+    dialog.connect_response(None, move |dialog, response| match response {
+        "copy" => dialog.clipboard().set_text(&report),
+        "report" => launch_default_uri(issue_url, |result| {
+            if let Err(err) = result {
+                log_error(format!("Failed to open the issue tracker: {err}"));
+            }
+        }),
+        _ => {}
+    });
+    dialog.present(Some(parent));
+}
+
+// Runs on the main loop. Without a window the report stays pending until
+// the first one opens.
+fn show_pending_crash_report() {
+    let Some((app, issue_url)) = CRASH_REPORT_APP.with(|target| target.borrow().clone()) else {
+        return;
+    };
+    let Some(window) = app.upgrade().and_then(|app| app.active_window()) else {
+        return;
+    };
+    if let Some((path, report)) = take_pending_crash_report() {
+        present_crash_report_dialog(
+            window.upcast_ref(),
+            &path.display().to_string(),
+            &report,
+            issue_url,
+        );
+    }
+}
+
+// A crash that ended the app shows when the first window opens at the next
+// start. Reports from worker threads are shown by the panic hook.
+pub fn watch_for_crash_reports(app: &Application, issue_url: &'static str) {
+    CRASH_REPORT_APP.with(|target| target.replace(Some((app.downgrade(), issue_url))));
+    let handler = Rc::new(RefCell::new(None));
+    let handler_for_added = handler.clone();
+    let id = app.connect_window_added(move |app, _| {
+        if let Some(id) = handler_for_added.borrow_mut().take() {
+            app.disconnect(id);
+        }
+        glib::idle_add_local_once(show_pending_crash_report);
+    });
+    handler.replace(Some(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crash_report_text, panic_payload_message};
+
+    #[test]
+    fn crash_reports_name_the_thread_message_and_location() {
+        let report = crash_report_text(
+            "keycord-result-task",
+            "called `Option::unwrap()` on a `None` value",
+            Some("src/window/mod.rs:10:5"),
+            "0: main",
+        );
+
+        assert!(report.starts_with(concat!(
+            env!("CARGO_PKG_NAME"),
+            " ",
+            env!("CARGO_PKG_VERSION")
+        )));
+        assert!(report.contains("thread: keycord-result-task\n"));
+        assert!(report.contains("location: src/window/mod.rs:10:5\n"));
+        assert!(report.ends_with("backtrace:\n0: main"));
+    }
+
+    #[test]
+    fn panic_messages_come_from_str_and_string_payloads() {
+        assert_eq!(panic_payload_message(&"static"), "static");
+        assert_eq!(panic_payload_message(&"owned".to_string()), "owned");
+        assert_eq!(panic_payload_message(&1_u8), "unknown panic");
+    }
+}
@@ -0,0 +1,84 @@
+const SEGMENT_SUBSTRING_SCORE: u32 = 400;
+const SUBSTRING_SCORE: u32 = 300;
+const SUBSEQUENCE_SCORE: u32 = 100;
+const SEGMENT_START_BONUS: u32 = 10;
+const CONSECUTIVE_BONUS: u32 = 5;
+
+fn is_segment_separator(c: char) -> bool {
+    matches!(c, '/' | '.' | '-' | '_' | ' ' | '@')
+}
+
+fn starts_segment(text: &[char], index: usize) -> bool {
+    index == 0 || is_segment_separator(text[index - 1])
+}
+
+// Letters spread out in order only count when the first one starts a path
+// segment, so "gh" finds "github" but a short term doesn't match everything.
+fn subsequence_score(text: &[char], term: &[char]) -> Option<u32> {
+    let first = text
+        .iter()
+        .enumerate()
+        .position(|(index, c)| *c == term[0] && starts_segment(text, index))?;
+
+    let mut score = SUBSEQUENCE_SCORE + SEGMENT_START_BONUS;
+    let mut previous = first;
+    for c in &term[1..] {
+        let index = previous + 1 + text[previous + 1..].iter().position(|t| t == c)?;
+        if index == previous + 1 {
+            score += CONSECUTIVE_BONUS;
+        } else if starts_segment(text, index) {
+            score += SEGMENT_START_BONUS;
+        }
+        previous = index;
+    }
+    Some(score.min(SUBSTRING_SCORE - 1))
+}
+
+// Scores one lowercase term against some text. A substring at the start of
+// a path segment ranks first, then any substring, then letters in order.
+pub fn fuzzy_term_score(text: &str, term: &str) -> Option<u32> {
+    let text = text.to_lowercase();
+    if term.is_empty() {
+        return Some(0);
+    }
+
+    let mut best = None;
+    for (index, _) in text.match_indices(term) {
+        let at_segment_start = text[..index]
+            .chars()
+            .next_back()
+            .is_none_or(is_segment_separator);
+        if at_segment_start {
+            return Some(SEGMENT_SUBSTRING_SCORE);
+        }
+        best = Some(SUBSTRING_SCORE);
+    }
+    best.or_else(|| {
+        let text = text.chars().collect::<Vec<_>>();
+        let term = term.chars().collect::<Vec<_>>();
+        subsequence_score(&text, &term)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_term_score;
+
+    #[test]
+    fn fuzzy_terms_match_letters_in_order_from_a_segment_start() {
+        assert!(fuzzy_term_score("sites/github.com/alice", "gh").is_some());
+        assert!(fuzzy_term_score("sites/github.com/alice", "alice").is_some());
+        assert_eq!(fuzzy_term_score("sites/github.com/bob", "alice"), None);
+        assert_eq!(fuzzy_term_score("work/mail", "ah"), None);
+    }
+
+    #[test]
+    fn closer_matches_rank_higher() {
+        let segment = fuzzy_term_score("work/github", "git").expect("segment match");
+        let substring = fuzzy_term_score("work/legit", "git").expect("substring match");
+        let spread = fuzzy_term_score("work/gsuite-it", "git").expect("spread match");
+
+        assert!(segment > substring);
+        assert!(substring > spread);
+    }
+}
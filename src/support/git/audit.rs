@@ -3,6 +3,7 @@ use super::command::{
     run_store_remote_git_command,
 };
 use super::repository::has_git_repository;
+use super::sparse::{sparse_folder_pathspecs, store_git_sparse_folders};
 use crate::backend::{available_host_gpg_public_certs, available_standard_public_certs};
 use crate::fido2_recipient::FIDO2_RECIPIENTS_FILE_NAME;
 use crate::logging::{log_error, run_command_with_input, CommandLogOptions};
@@ -243,7 +244,10 @@ pub fn load_store_git_audit_commit_page(
 ) -> Result<StoreGitAuditCommitPage, String> {
     let all_certs = audit_available_standard_public_certs()?;
     let branch_tip_context = load_tree_recipient_context(store_root, full_ref, &all_certs)?;
-    let summaries = read_commit_summaries(store_root, full_ref, page)?;
+    // A sparse store only audits the history of the folders it checked out.
+    let pathspecs =
+        sparse_folder_pathspecs(&store_git_sparse_folders(store_root)?.unwrap_or_default());
+    let summaries = read_commit_summaries(store_root, full_ref, page, &pathspecs)?;
     let has_more = summaries.len() > STORE_GIT_AUDIT_PAGE_SIZE;
     let summaries = summaries
         .into_iter()
@@ -262,7 +266,7 @@ pub fn load_store_git_audit_commit_page(
             .get(&summary.oid)
             .ok_or_else(|| format!("Missing raw commit data for {}.", summary.oid))?;
         let parsed = parse_commit_object(raw_commit)?;
-        let changed_paths = read_commit_changed_paths(store_root, &summary.oid)?;
+        let changed_paths = read_commit_changed_paths(store_root, &summary.oid, &pathspecs)?;
         let verification = verify_commit(
             store_root,
             full_ref,
@@ -395,6 +399,7 @@ fn read_commit_summaries(
     store_root: &str,
     full_ref: &str,
     page: usize,
+    pathspecs: &[String],
 ) -> Result<Vec<CommitSummary>, String> {
     let skip = page.saturating_mul(STORE_GIT_AUDIT_PAGE_SIZE);
     let limit = STORE_GIT_AUDIT_PAGE_SIZE + 1;
@@ -407,6 +412,9 @@ fn read_commit_summaries(
                 .arg(format!("--skip={skip}"))
                 .arg(format!("-n{limit}"))
                 .arg("--format=%H%x00%h%x00%s%x00%an <%ae>%x00%aI%x00%cn <%ce>%x00%cI%x00");
+            if !pathspecs.is_empty() {
+                cmd.arg("--").args(pathspecs);
+            }
         },
         CommandLogOptions::DEFAULT,
     )?;
@@ -611,6 +619,7 @@ fn parse_host_git_signature_summaries(
 fn read_commit_changed_paths(
     store_root: &str,
     oid: &str,
+    pathspecs: &[String],
 ) -> Result<Vec<StoreGitAuditPathChange>, String> {
     let output = run_store_git_command(
         store_root,
@@ -622,6 +631,9 @@ fn read_commit_changed_paths(
                 .arg("--no-ext-diff")
                 .arg("--no-color")
                 .arg(oid);
+            if !pathspecs.is_empty() {
+                cmd.arg("--").args(pathspecs);
+            }
         },
         CommandLogOptions::DEFAULT,
     )?;
@@ -0,0 +1,299 @@
+use std::fs;
+use std::path::PathBuf;
+
+const REMOTE_URL_SCHEMES: [&str; 5] = ["https", "http", "ssh", "git", "file"];
+const DEFAULT_SSH_KEY_FILES: [&str; 4] = ["id_ed25519", "id_ecdsa", "id_rsa", "id_ed25519_sk"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitRemoteSignIn {
+    None,
+    SshAgent,
+    SshKeyFile,
+    SshPassword,
+    HttpCredentials,
+}
+
+impl GitRemoteSignIn {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::None => "No sign-in needed.",
+            Self::SshAgent => "Signs in with a key from your SSH agent.",
+            Self::SshKeyFile => "Signs in with your SSH key file. Git may ask for its passphrase.",
+            Self::SshPassword => "No SSH key was found, so Git will ask for a password.",
+            Self::HttpCredentials => {
+                "Git asks for a username and password or token, unless a credential helper has one."
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GitRemoteUrlKind {
+    Local,
+    Http,
+    Ssh,
+    Anonymous,
+}
+
+fn remote_url_kind(url: &str) -> Result<GitRemoteUrlKind, &'static str> {
+    if url.is_empty() {
+        return Err("Enter a repository URL.");
+    }
+    if url.chars().any(char::is_whitespace) {
+        return Err("Repository URLs can't contain spaces.");
+    }
+
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let scheme = scheme.to_ascii_lowercase();
+        if !REMOTE_URL_SCHEMES.contains(&scheme.as_str()) {
+            return Err("Use an https, ssh, git, or file URL.");
+        }
+        if scheme == "file" {
+            return Ok(GitRemoteUrlKind::Local);
+        }
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if host.is_empty() || path.is_empty() {
+            return Err("Add the repository path after the host.");
+        }
+        return Ok(match scheme.as_str() {
+            "https" | "http" => GitRemoteUrlKind::Http,
+            "ssh" => GitRemoteUrlKind::Ssh,
+            _ => GitRemoteUrlKind::Anonymous,
+        });
+    }
+
+    if url.starts_with(['/', '~', '.']) {
+        return Ok(GitRemoteUrlKind::Local);
+    }
+
+    // Git reads `host:path` as SSH only when no slash comes before the
+    // colon, the same way it tells them apart from local paths.
+    match url.split_once(':') {
+        Some((host, path)) if !host.contains('/') => {
+            if host.is_empty() || host.ends_with('@') {
+                Err("Add a host before the colon.")
+            } else if path.is_empty() {
+                Err("Add the repository path after the host.")
+            } else {
+                Ok(GitRemoteUrlKind::Ssh)
+            }
+        }
+        _ => Err("Use a URL like https://host/repo.git or host:repo.git."),
+    }
+}
+
+pub fn git_remote_url_error(url: &str) -> Option<&'static str> {
+    remote_url_kind(url.trim()).err()
+}
+
+fn ssh_remote_host(url: &str) -> Option<&str> {
+    let authority = match url.split_once("://") {
+        Some((_, rest)) => rest.split('/').next()?,
+        None => url.split_once(':')?.0,
+    };
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = if url.contains("://") {
+        host.split(':').next()?
+    } else {
+        host
+    };
+    (!host.is_empty()).then_some(host)
+}
+
+fn ssh_config_keyword_value(line: &str) -> Option<(String, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let split = line.find(|ch: char| ch.is_whitespace() || ch == '=')?;
+    let (keyword, value) = line.split_at(split);
+    let value = value.trim_start_matches(|ch: char| ch.is_whitespace() || ch == '=');
+    Some((keyword.to_ascii_lowercase(), value.trim()))
+}
+
+fn is_plain_ssh_host_pattern(pattern: &str) -> bool {
+    !pattern.is_empty() && !pattern.starts_with('!') && !pattern.contains(['*', '?'])
+}
+
+// Only plain names are offered; wildcard blocks such as `Host *` hold
+// defaults, not hosts to connect to.
+fn ssh_config_host_aliases_in(contents: &str) -> Vec<String> {
+    let mut aliases = Vec::<String>::new();
+    for (keyword, value) in contents.lines().filter_map(ssh_config_keyword_value) {
+        if keyword != "host" {
+            continue;
+        }
+        for pattern in value.split_whitespace() {
+            if is_plain_ssh_host_pattern(pattern) && !aliases.iter().any(|alias| alias == pattern) {
+                aliases.push(pattern.to_string());
+            }
+        }
+    }
+    aliases
+}
+
+fn ssh_config_identity_files_in(contents: &str, host: &str) -> Vec<String> {
+    let mut applies = true;
+    let mut files = Vec::new();
+    for (keyword, value) in contents.lines().filter_map(ssh_config_keyword_value) {
+        match keyword.as_str() {
+            "host" => {
+                applies = value
+                    .split_whitespace()
+                    .any(|pattern| pattern == host || pattern == "*");
+            }
+            "match" => applies = false,
+            "identityfile" if applies => files.push(value.trim_matches('"').to_string()),
+            _ => {}
+        }
+    }
+    files
+}
+
+fn ssh_dir() -> Option<PathBuf> {
+    Some(dirs_next::home_dir()?.join(".ssh"))
+}
+
+fn read_ssh_config() -> String {
+    ssh_dir()
+        .and_then(|dir| fs::read_to_string(dir.join("config")).ok())
+        .unwrap_or_default()
+}
+
+pub fn ssh_config_host_aliases() -> Vec<String> {
+    ssh_config_host_aliases_in(&read_ssh_config())
+}
+
+fn expand_ssh_path(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs_next::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn ssh_key_file_exists(host: &str) -> bool {
+    let configured = ssh_config_identity_files_in(&read_ssh_config(), host)
+        .into_iter()
+        .map(|path| expand_ssh_path(&path));
+    let defaults = ssh_dir()
+        .into_iter()
+        .flat_map(|dir| DEFAULT_SSH_KEY_FILES.map(|name| dir.join(name)));
+    configured.chain(defaults).any(|path| path.is_file())
+}
+
+pub fn git_remote_sign_in(url: &str) -> Option<GitRemoteSignIn> {
+    let url = url.trim();
+    Some(match remote_url_kind(url).ok()? {
+        GitRemoteUrlKind::Local | GitRemoteUrlKind::Anonymous => GitRemoteSignIn::None,
+        GitRemoteUrlKind::Http => GitRemoteSignIn::HttpCredentials,
+        GitRemoteUrlKind::Ssh if std::env::var_os("SSH_AUTH_SOCK").is_some() => {
+            GitRemoteSignIn::SshAgent
+        }
+        GitRemoteUrlKind::Ssh if ssh_remote_host(url).is_some_and(ssh_key_file_exists) => {
+            GitRemoteSignIn::SshKeyFile
+        }
+        GitRemoteUrlKind::Ssh => GitRemoteSignIn::SshPassword,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        remote_url_kind, ssh_config_host_aliases_in, ssh_config_identity_files_in, ssh_remote_host,
+        GitRemoteUrlKind,
+    };
+
+    #[test]
+    fn remote_urls_are_sorted_by_how_git_reaches_them() {
+        assert_eq!(
+            remote_url_kind("https://example.com/me/pass.git"),
+            Ok(GitRemoteUrlKind::Http)
+        );
+        assert_eq!(
+            remote_url_kind("ssh://git@example.com:2222/me/pass.git"),
+            Ok(GitRemoteUrlKind::Ssh)
+        );
+        assert_eq!(
+            remote_url_kind("git@example.com:me/pass.git"),
+            Ok(GitRemoteUrlKind::Ssh)
+        );
+        assert_eq!(remote_url_kind("work:pass"), Ok(GitRemoteUrlKind::Ssh));
+        assert_eq!(
+            remote_url_kind("git://example.com/pass.git"),
+            Ok(GitRemoteUrlKind::Anonymous)
+        );
+        assert_eq!(
+            remote_url_kind("/srv/git/pass.git"),
+            Ok(GitRemoteUrlKind::Local)
+        );
+        assert_eq!(
+            remote_url_kind("file:///srv/git/pass.git"),
+            Ok(GitRemoteUrlKind::Local)
+        );
+    }
+
+    #[test]
+    fn broken_remote_urls_explain_what_is_missing() {
+        assert!(remote_url_kind("").is_err());
+        assert!(remote_url_kind("https://example.com/my pass.git").is_err());
+        assert!(remote_url_kind("ftp://example.com/pass.git").is_err());
+        assert!(remote_url_kind("https://example.com").is_err());
+        assert!(remote_url_kind("git@example.com:").is_err());
+        assert!(remote_url_kind("git@:pass.git").is_err());
+        assert!(remote_url_kind("example.com").is_err());
+        assert!(remote_url_kind("folder/pass:git").is_err());
+    }
+
+    #[test]
+    fn ssh_hosts_come_from_both_url_forms() {
+        assert_eq!(
+            ssh_remote_host("git@example.com:me/pass.git"),
+            Some("example.com")
+        );
+        assert_eq!(
+            ssh_remote_host("ssh://git@example.com:2222/me/pass.git"),
+            Some("example.com")
+        );
+        assert_eq!(ssh_remote_host("work:pass"), Some("work"));
+    }
+
+    #[test]
+    fn ssh_config_offers_plain_host_aliases_once() {
+        let config = "\
+# personal
+Host work work-backup
+    HostName git.example.com
+Host=home
+Host * !bastion
+    IdentityFile ~/.ssh/default
+host work
+    User git
+";
+        assert_eq!(
+            ssh_config_host_aliases_in(config),
+            vec!["work", "work-backup", "home"]
+        );
+    }
+
+    #[test]
+    fn ssh_identity_files_follow_matching_and_wildcard_hosts() {
+        let config = "\
+Host work
+    IdentityFile ~/.ssh/work
+Host home
+    IdentityFile ~/.ssh/home
+Host *
+    IdentityFile \"~/.ssh/default\"
+";
+        assert_eq!(
+            ssh_config_identity_files_in(config, "work"),
+            vec!["~/.ssh/work", "~/.ssh/default"]
+        );
+        assert_eq!(
+            ssh_config_identity_files_in(config, "example.com"),
+            vec!["~/.ssh/default"]
+        );
+    }
+}
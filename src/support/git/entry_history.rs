@@ -0,0 +1,294 @@
+use super::command::{git_command_error, git_output_text, run_store_git_command};
+use super::repository::has_git_repository;
+use crate::logging::CommandLogOptions;
+use crate::password::entry_files::{
+    label_from_password_entry_relative_path, FIDO2_PASSWORD_ENTRY_EXTENSION,
+    STANDARD_PASSWORD_ENTRY_EXTENSION,
+};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
+
+const STORE_GIT_ENTRY_HISTORY_LIMIT: usize = 50;
+const RECORD_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+// When each item was last committed, as Unix seconds, keyed by item label.
+pub type StoreGitEntryAges = HashMap<String, i64>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoreGitEntryCommit {
+    pub short_oid: String,
+    pub committed_at: i64,
+    pub author: String,
+    pub subject: String,
+}
+
+// When an item was first added and last changed, as Unix seconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StoreGitEntryDates {
+    pub created_at: i64,
+    pub modified_at: i64,
+}
+
+type EntryAgesCache = HashMap<String, (String, Arc<StoreGitEntryAges>)>;
+
+fn entry_ages_cache() -> &'static Mutex<EntryAgesCache> {
+    static CACHE: OnceLock<Mutex<EntryAgesCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub fn store_git_head_oid(root: &str) -> Result<Option<String>, String> {
+    let output = run_store_git_command(
+        root,
+        "Inspect password store Git HEAD",
+        |cmd| {
+            cmd.args(["rev-parse", "-q", "--verify", "HEAD^{commit}"]);
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[1],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+
+    match output.status.code() {
+        Some(0) => git_output_text(&output).map(Some),
+        Some(1) => Ok(None),
+        _ => Err(git_command_error(
+            "git rev-parse -q --verify HEAD^{commit}",
+            &output,
+        )),
+    }
+}
+
+// One walk over the whole history answers every item at once. The result is
+// kept until the store's HEAD moves, so reloading the list is cheap.
+pub fn store_git_entry_ages(root: &str) -> Result<Arc<StoreGitEntryAges>, String> {
+    if !has_git_repository(root) {
+        return Ok(Arc::default());
+    }
+    let Some(head) = store_git_head_oid(root)? else {
+        return Ok(Arc::default());
+    };
+
+    if let Ok(cache) = entry_ages_cache().lock() {
+        if let Some((cached_head, ages)) = cache.get(root) {
+            if *cached_head == head {
+                return Ok(ages.clone());
+            }
+        }
+    }
+
+    let output = run_store_git_command(
+        root,
+        "Read password store item ages",
+        |cmd| {
+            cmd.args([
+                "-c",
+                "core.quotePath=false",
+                "log",
+                "--format=%x1e%ct",
+                "--name-only",
+                &head,
+            ]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git log --name-only", &output));
+    }
+
+    let ages = Arc::new(parse_store_git_entry_ages(&String::from_utf8_lossy(
+        &output.stdout,
+    )));
+    if let Ok(mut cache) = entry_ages_cache().lock() {
+        cache.insert(root.to_string(), (head, ages.clone()));
+    }
+    Ok(ages)
+}
+
+pub fn store_git_entry_history(
+    root: &str,
+    label: &str,
+) -> Result<Vec<StoreGitEntryCommit>, String> {
+    if !has_git_repository(root) || store_git_head_oid(root)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let limit = STORE_GIT_ENTRY_HISTORY_LIMIT.to_string();
+    let paths = [
+        STANDARD_PASSWORD_ENTRY_EXTENSION,
+        FIDO2_PASSWORD_ENTRY_EXTENSION,
+    ]
+    .map(|extension| format!("{label}.{extension}"));
+    let output = run_store_git_command(
+        root,
+        "Read password store item history",
+        |cmd| {
+            cmd.args([
+                "log",
+                "-n",
+                &limit,
+                "--format=%h%x1f%ct%x1f%an%x1f%s",
+                "HEAD",
+                "--",
+            ]);
+            cmd.args(&paths);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git log", &output));
+    }
+
+    Ok(parse_store_git_entry_history(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+// Renames are followed, so an item that was moved keeps the date it was
+// first added.
+pub fn store_git_entry_dates(
+    root: &str,
+    label: &str,
+) -> Result<Option<StoreGitEntryDates>, String> {
+    if !has_git_repository(root) || store_git_head_oid(root)?.is_none() {
+        return Ok(None);
+    }
+    let Some(path) = [
+        STANDARD_PASSWORD_ENTRY_EXTENSION,
+        FIDO2_PASSWORD_ENTRY_EXTENSION,
+    ]
+    .map(|extension| format!("{label}.{extension}"))
+    .into_iter()
+    .find(|path| Path::new(root).join(path).is_file()) else {
+        return Ok(None);
+    };
+
+    let output = run_store_git_command(
+        root,
+        "Read password store item dates",
+        |cmd| {
+            cmd.args(["log", "--follow", "--format=%ct", "HEAD", "--", &path]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git log --follow", &output));
+    }
+
+    Ok(parse_store_git_entry_dates(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+fn parse_store_git_entry_dates(output: &str) -> Option<StoreGitEntryDates> {
+    let mut times = output
+        .lines()
+        .filter_map(|line| line.trim().parse::<i64>().ok());
+    let modified_at = times.next()?;
+    let created_at = times.last().unwrap_or(modified_at);
+    Some(StoreGitEntryDates {
+        created_at,
+        modified_at,
+    })
+}
+
+fn parse_store_git_entry_ages(output: &str) -> StoreGitEntryAges {
+    let mut ages = StoreGitEntryAges::new();
+    for record in output.split(RECORD_SEPARATOR) {
+        let mut lines = record.lines();
+        let Some(committed_at) = lines
+            .next()
+            .and_then(|line| line.trim().parse::<i64>().ok())
+        else {
+            continue;
+        };
+        for path in lines.map(str::trim).filter(|path| !path.is_empty()) {
+            if let Some(label) = label_from_password_entry_relative_path(Path::new(path)) {
+                ages.entry(label).or_insert(committed_at);
+            }
+        }
+    }
+    ages
+}
+
+fn parse_store_git_entry_history(output: &str) -> Vec<StoreGitEntryCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, FIELD_SEPARATOR);
+            let short_oid = fields.next()?.trim().to_string();
+            let committed_at = fields.next()?.trim().parse::<i64>().ok()?;
+            let author = fields.next()?.trim().to_string();
+            let subject = fields.next().unwrap_or_default().trim().to_string();
+            Some(StoreGitEntryCommit {
+                short_oid,
+                committed_at,
+                author,
+                subject,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_store_git_entry_ages, parse_store_git_entry_dates, parse_store_git_entry_history,
+        StoreGitEntryCommit, StoreGitEntryDates,
+    };
+
+    #[test]
+    fn entry_dates_span_the_oldest_and_newest_commit() {
+        assert_eq!(
+            parse_store_git_entry_dates("300\n\n200\n100\n"),
+            Some(StoreGitEntryDates {
+                created_at: 100,
+                modified_at: 300,
+            })
+        );
+        assert_eq!(
+            parse_store_git_entry_dates("300\n"),
+            Some(StoreGitEntryDates {
+                created_at: 300,
+                modified_at: 300,
+            })
+        );
+        assert_eq!(parse_store_git_entry_dates(""), None);
+    }
+
+    #[test]
+    fn entry_ages_keep_the_newest_commit_per_item() {
+        let output = "\u{1e}300\n\nmail/github.gpg\n.gpg-id\n\u{1e}200\n\nmail/github.gpg\nbank.keycord\nnotes.txt\n";
+
+        let ages = parse_store_git_entry_ages(output);
+
+        assert_eq!(ages.get("mail/github"), Some(&300));
+        assert_eq!(ages.get("bank"), Some(&200));
+        assert_eq!(ages.len(), 2);
+    }
+
+    #[test]
+    fn entry_history_reads_one_commit_per_line() {
+        let output = "abc1234\u{1f}300\u{1f}Alice\u{1f}Rotate github\ndef5678\u{1f}200\u{1f}Bob\u{1f}\nbroken\n";
+
+        assert_eq!(
+            parse_store_git_entry_history(output),
+            vec![
+                StoreGitEntryCommit {
+                    short_oid: "abc1234".to_string(),
+                    committed_at: 300,
+                    author: "Alice".to_string(),
+                    subject: "Rotate github".to_string(),
+                },
+                StoreGitEntryCommit {
+                    short_oid: "def5678".to_string(),
+                    committed_at: 200,
+                    author: "Bob".to_string(),
+                    subject: String::new(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use super::command::{
+    git_command_error, git_output_text, run_store_git_command, run_store_remote_git_command,
+};
+use super::remotes::list_store_git_remotes;
+use crate::logging::{log_error, CommandLogOptions};
+use crate::support::runtime::require_host_command_features;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StoreCloneOptions {
+    pub shallow: bool,
+    pub single_branch: bool,
+}
+
+impl StoreCloneOptions {
+    pub fn clone_args(self) -> Vec<&'static str> {
+        let mut args = Vec::new();
+        if self.shallow {
+            args.extend(["--depth", "1"]);
+        }
+        if self.single_branch {
+            args.push("--single-branch");
+        } else if self.shallow {
+            // `--depth` implies a single branch unless told otherwise.
+            args.push("--no-single-branch");
+        }
+        args
+    }
+}
+
+// A single-branch clone fetches one branch by name instead of every branch
+// under `refs/heads/`.
+fn fetch_refspecs_track_single_branch(config: &str) -> bool {
+    config
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .any(|(_, refspec)| !refspec.contains('*'))
+}
+
+fn store_git_tracks_single_branch(root: &str) -> Result<bool, String> {
+    let output = run_store_git_command(
+        root,
+        "Read password store Git fetch refspecs",
+        |cmd| {
+            cmd.args(["config", "--get-regexp", r"^remote\..*\.fetch$"]);
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[1],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    match output.status.code() {
+        Some(0) => Ok(fetch_refspecs_track_single_branch(&git_output_text(
+            &output,
+        )?)),
+        Some(1) => Ok(false),
+        _ => Err(git_command_error("git config --get-regexp", &output)),
+    }
+}
+
+// Shallow and single-branch clones both leave history out.
+pub fn store_git_history_is_partial(root: &str) -> bool {
+    if Path::new(root).join(".git").join("shallow").exists() {
+        return true;
+    }
+    store_git_tracks_single_branch(root).unwrap_or_else(|err| {
+        log_error(format!(
+            "Failed to read the fetched branches of '{root}': {err}"
+        ));
+        false
+    })
+}
+
+fn widen_store_git_remote_branches(root: &str, remote: &str) -> Result<(), String> {
+    let output = run_store_git_command(
+        root,
+        &format!("Track all branches of password store Git remote {remote}"),
+        |cmd| {
+            cmd.args(["remote", "set-branches", remote, "*"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git remote set-branches", &output))
+    }
+}
+
+pub fn fetch_full_store_git_history(root: &str) -> Result<(), String> {
+    require_host_command_features()?;
+    let remotes = list_store_git_remotes(root)?;
+    let Some(first) = remotes.first() else {
+        return Err("Add a Git remote before fetching history.".to_string());
+    };
+
+    for remote in &remotes {
+        widen_store_git_remote_branches(root, &remote.name)?;
+    }
+
+    let unshallow = store_git_history_is_partial(root);
+    let output = run_store_remote_git_command(
+        root,
+        "Fetch full password store Git history",
+        |cmd| {
+            cmd.arg("fetch");
+            if unshallow {
+                cmd.arg("--unshallow");
+            }
+            cmd.arg(&first.name);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git fetch --unshallow", &output));
+    }
+
+    for remote in remotes.iter().skip(1) {
+        let output = run_store_remote_git_command(
+            root,
+            &format!("Fetch password store Git remote {}", remote.name),
+            |cmd| {
+                cmd.args(["fetch", &remote.name]);
+            },
+            CommandLogOptions::DEFAULT,
+        )?;
+        if !output.status.success() {
+            return Err(git_command_error("git fetch", &output));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fetch_refspecs_track_single_branch, StoreCloneOptions};
+
+    #[test]
+    fn clone_options_map_to_git_clone_flags() {
+        assert!(StoreCloneOptions::default().clone_args().is_empty());
+        assert_eq!(
+            StoreCloneOptions {
+                shallow: true,
+                single_branch: true,
+            }
+            .clone_args(),
+            vec!["--depth", "1", "--single-branch"]
+        );
+        assert_eq!(
+            StoreCloneOptions {
+                shallow: false,
+                single_branch: true,
+            }
+            .clone_args(),
+            vec!["--single-branch"]
+        );
+        assert_eq!(
+            StoreCloneOptions {
+                shallow: true,
+                single_branch: false,
+            }
+            .clone_args(),
+            vec!["--depth", "1", "--no-single-branch"]
+        );
+    }
+
+    #[test]
+    fn single_branch_clones_fetch_one_named_branch() {
+        assert!(fetch_refspecs_track_single_branch(
+            "remote.origin.fetch +refs/heads/main:refs/remotes/origin/main"
+        ));
+        assert!(!fetch_refspecs_track_single_branch(
+            "remote.origin.fetch +refs/heads/*:refs/remotes/origin/*\n\
+             remote.backup.fetch +refs/heads/*:refs/remotes/backup/*"
+        ));
+        assert!(!fetch_refspecs_track_single_branch(""));
+    }
+}
@@ -0,0 +1,236 @@
+use super::command::{git_command_error, run_store_git_work_tree_command};
+use crate::logging::CommandLogOptions;
+use crate::preferences::Preferences;
+use crate::support::secure_fs::write_private_file;
+use std::fs;
+use std::path::Path;
+
+const MIB: u64 = 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LargeStoreFile {
+    pub path: String,
+    pub bytes: u64,
+}
+
+impl LargeStoreFile {
+    pub const fn mib(&self) -> u64 {
+        self.bytes / MIB
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LargeStoreFiles {
+    pub files: Vec<LargeStoreFile>,
+    // Whether the files were kept out of the commit instead of only flagged.
+    pub left_out: bool,
+}
+
+fn large_file_limit_bytes() -> Option<u64> {
+    let mib = Preferences::new().large_file_limit_mib();
+    (mib > 0).then(|| u64::from(mib) * MIB)
+}
+
+fn large_files_among(
+    root: &Path,
+    paths: impl IntoIterator<Item = String>,
+    limit: u64,
+) -> Vec<LargeStoreFile> {
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(root.join(&path)).ok()?;
+            (metadata.is_file() && metadata.len() >= limit).then(|| LargeStoreFile {
+                path,
+                bytes: metadata.len(),
+            })
+        })
+        .collect()
+}
+
+fn null_separated_paths(output: &[u8]) -> Vec<String> {
+    output
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| String::from_utf8_lossy(path).into_owned())
+        .collect()
+}
+
+fn store_git_paths(root: &str, context: &str, args: &[&str]) -> Result<Vec<String>, String> {
+    let output = run_store_git_work_tree_command(
+        root,
+        context,
+        |cmd| {
+            cmd.args(args);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(null_separated_paths(&output.stdout))
+    } else {
+        Err(git_command_error(&format!("git {}", args[0]), &output))
+    }
+}
+
+// Anchored to the store root, with every character Git would read as part
+// of a pattern escaped, so the line matches exactly this one file.
+fn exclude_pattern(path: &str) -> String {
+    let mut pattern = String::from("/");
+    for ch in path.chars() {
+        if matches!(ch, '*' | '?' | '[' | '\\' | ' ') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern
+}
+
+fn exclusions_with(existing: &str, files: &[LargeStoreFile]) -> Option<String> {
+    let mut exclusions = existing.to_string();
+    let mut changed = false;
+    for file in files {
+        let pattern = exclude_pattern(&file.path);
+        if exclusions.lines().any(|line| line == pattern) {
+            continue;
+        }
+        if !exclusions.is_empty() && !exclusions.ends_with('\n') {
+            exclusions.push('\n');
+        }
+        exclusions.push_str(&pattern);
+        exclusions.push('\n');
+        changed = true;
+    }
+    changed.then_some(exclusions)
+}
+
+// New files stay on disk but are unstaged and listed in `.git/info/exclude`,
+// so they don't leave the store looking changed and blocking sync.
+fn keep_new_files_out_of_store_git(root: &str, files: &[LargeStoreFile]) -> Result<(), String> {
+    let output = run_store_git_work_tree_command(
+        root,
+        "Unstage large password store files",
+        |cmd| {
+            cmd.args(["rm", "--cached", "-q", "--ignore-unmatch", "--"])
+                .args(files.iter().map(|file| &file.path));
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git rm --cached", &output));
+    }
+
+    let path = Path::new(root).join(".git").join("info").join("exclude");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if let Some(exclusions) = exclusions_with(&existing, files) {
+        write_private_file(&path, exclusions).map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+// Git already tracks these, so unstaging them would commit a deletion that
+// sync then pushes to every device. Putting the committed version back in
+// the index only leaves the new version out.
+fn keep_changes_out_of_store_git(root: &str, files: &[LargeStoreFile]) -> Result<(), String> {
+    let output = run_store_git_work_tree_command(
+        root,
+        "Unstage large password store changes",
+        |cmd| {
+            cmd.args(["restore", "--staged", "--"])
+                .args(files.iter().map(|file| &file.path));
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git restore --staged", &output))
+    }
+}
+
+fn staged_large_files(root: &str, filter: &str, limit: u64) -> Result<Vec<LargeStoreFile>, String> {
+    let staged = store_git_paths(
+        root,
+        "List staged password store files",
+        &[
+            "diff",
+            "--cached",
+            "--name-only",
+            "-z",
+            &format!("--diff-filter={filter}"),
+        ],
+    )?;
+    Ok(large_files_among(Path::new(root), staged, limit))
+}
+
+// Runs between staging and committing, so a large file is flagged before it
+// lands in history, where every clone and sync would have to carry it.
+pub fn check_staged_large_store_files(root: &str) -> Result<LargeStoreFiles, String> {
+    let Some(limit) = large_file_limit_bytes() else {
+        return Ok(LargeStoreFiles::default());
+    };
+
+    let added = staged_large_files(root, "A", limit)?;
+    let modified = staged_large_files(root, "M", limit)?;
+    let left_out =
+        (!added.is_empty() || !modified.is_empty()) && Preferences::new().refuse_large_files();
+    if left_out {
+        if !added.is_empty() {
+            keep_new_files_out_of_store_git(root, &added)?;
+        }
+        if !modified.is_empty() {
+            keep_changes_out_of_store_git(root, &modified)?;
+        }
+    }
+    let files = added.into_iter().chain(modified).collect();
+    Ok(LargeStoreFiles { files, left_out })
+}
+
+pub fn large_store_files(root: &str) -> Result<Vec<LargeStoreFile>, String> {
+    let Some(limit) = large_file_limit_bytes() else {
+        return Ok(Vec::new());
+    };
+
+    let paths = store_git_paths(
+        root,
+        "List password store files",
+        &[
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+        ],
+    )?;
+    Ok(large_files_among(Path::new(root), paths, limit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exclude_pattern, exclusions_with, null_separated_paths, LargeStoreFile};
+
+    fn large(path: &str) -> LargeStoreFile {
+        LargeStoreFile {
+            path: path.to_string(),
+            bytes: 0,
+        }
+    }
+
+    #[test]
+    fn excluded_large_files_match_only_themselves() {
+        assert_eq!(exclude_pattern("backups/disk.img"), "/backups/disk.img");
+        assert_eq!(exclude_pattern("my [old] *.iso"), "/my\\ \\[old]\\ \\*.iso");
+        assert_eq!(
+            exclusions_with("# git ls-files --others", &[large("a.bin"), large("a.bin")]),
+            Some("# git ls-files --others\n/a.bin\n".to_string())
+        );
+        assert_eq!(exclusions_with("/a.bin\n", &[large("a.bin")]), None);
+    }
+
+    #[test]
+    fn git_paths_are_split_on_nul() {
+        assert_eq!(
+            null_separated_paths(b"team/a b.gpg\0video.mp4\0"),
+            vec!["team/a b.gpg".to_string(), "video.mp4".to_string()]
+        );
+    }
+}
@@ -3,9 +3,16 @@ mod audit;
 #[cfg(not(feature = "audit"))]
 #[path = "audit_disabled.rs"]
 mod audit;
+mod clone_url;
 mod command;
+mod entry_history;
+mod history;
+mod large_files;
+mod remote_browse;
 mod remotes;
 mod repository;
+mod restore;
+mod sparse;
 mod status;
 mod sync;
 mod types;
@@ -19,19 +26,39 @@ pub use audit::{
     StoreGitAuditVerification, StoreGitAuditVerificationMethod, StoreGitAuditVerificationMode,
     StoreGitAuditVerificationState, STORE_GIT_AUDIT_PAGE_SIZE,
 };
+pub use clone_url::{
+    git_remote_sign_in, git_remote_url_error, ssh_config_host_aliases, GitRemoteSignIn,
+};
+pub use entry_history::{
+    store_git_entry_ages, store_git_entry_dates, store_git_entry_history, store_git_head_oid,
+    StoreGitEntryAges, StoreGitEntryCommit, StoreGitEntryDates,
+};
+pub use history::{fetch_full_store_git_history, store_git_history_is_partial, StoreCloneOptions};
+pub use large_files::{
+    check_staged_large_store_files, large_store_files, LargeStoreFile, LargeStoreFiles,
+};
+pub use remote_browse::{fetch_remote_store, RemoteStoreItem, RemoteStoreSnapshot};
 pub use remotes::{
     add_store_git_remote, list_store_git_remotes, remove_store_git_remote, rename_store_git_remote,
-    set_store_git_remote_url,
+    set_store_git_remote_url, set_store_git_upstream_remote, store_git_upstream_remote,
+    test_store_git_remote,
 };
 pub use repository::{
-    ensure_store_git_repository, git_command_available, has_git_repository,
-    password_store_git_state_summary,
+    apply_store_git_gpg_diff_preference, enable_store_git_versioning, ensure_store_git_repository,
+    git_command_available, has_git_repository, password_store_git_state_summary,
+    squash_store_git_commits,
+};
+pub use restore::{store_git_deleted_entries, store_git_deleted_entry_file, StoreGitDeletedEntry};
+pub use sparse::{
+    normalized_sparse_folders, set_store_git_sparse_folders, store_git_sparse_folders,
+};
+pub use status::{store_git_branch, store_git_has_local_only_work, store_git_repository_status};
+pub use sync::{
+    recover_store_repository_sync, store_git_sync_needs_recovery, sync_store_repository,
+};
+pub use types::{
+    GitRemote, StoreGitHead, StoreGitRecovery, StoreGitRepositoryStatus, StoreGitSyncSummary,
 };
-pub use status::store_git_repository_status;
-pub use sync::sync_store_repository;
-#[cfg(test)]
-pub use types::GitRemote;
-pub use types::{StoreGitHead, StoreGitRepositoryStatus};
 
 #[cfg(test)]
 mod tests;
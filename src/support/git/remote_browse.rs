@@ -0,0 +1,338 @@
+use super::command::{
+    git_command_error, git_output_text, run_store_git_command, run_store_remote_git_command,
+};
+use crate::fido2_recipient::FIDO2_RECIPIENTS_FILE_NAME;
+use crate::logging::{log_error, CommandLogOptions};
+use crate::support::paths::app_cache_path;
+use crate::support::runtime::require_host_command_features;
+use crate::support::secure_fs::ensure_private_dir;
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const REMOTE_STORES_DIR_NAME: &str = "remote-stores";
+const REMOTE_CHECKOUTS_DIR_NAME: &str = "keycord-remote-items";
+const RECIPIENT_FILE_NAMES: &[&str] = &[".gpg-id", FIDO2_RECIPIENTS_FILE_NAME];
+
+static NEXT_REMOTE_CHECKOUT: AtomicU64 = AtomicU64::new(0);
+
+// A fetched remote store. Its Git folder is bare and only ever holds the
+// latest commit, so nothing is checked out until one item is opened.
+#[derive(Clone, Debug)]
+pub struct RemoteStoreSnapshot {
+    url: String,
+    root: String,
+    commit: String,
+    paths: Vec<String>,
+}
+
+// The few files one item needs to decrypt, written to a private folder that
+// is removed again when this is dropped.
+pub struct RemoteStoreItem {
+    root: PathBuf,
+}
+
+impl RemoteStoreItem {
+    pub fn root(&self) -> String {
+        self.root.to_string_lossy().to_string()
+    }
+}
+
+impl Drop for RemoteStoreItem {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_dir_all(&self.root) {
+            log_error(format!(
+                "Failed to remove remote store item folder '{}': {err}",
+                self.root.display()
+            ));
+        }
+    }
+}
+
+fn hex_digest(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    let mut hex = String::with_capacity(digest.len() * 2);
+    for byte in digest {
+        let _ = write!(&mut hex, "{byte:02x}");
+    }
+    hex
+}
+
+// Each remote gets its own cache folder, so fetching again only downloads
+// what changed since the last look.
+fn remote_store_cache_root(url: &str) -> Result<PathBuf, String> {
+    let base = app_cache_path(REMOTE_STORES_DIR_NAME)
+        .ok_or_else(|| "No cache folder is available.".to_string())?;
+    Ok(base.join(hex_digest(url.trim())))
+}
+
+fn is_safe_store_path(path: &str) -> bool {
+    !path.is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn parse_remote_store_paths(output: &[u8]) -> Vec<String> {
+    output
+        .split(|byte| *byte == 0)
+        .filter_map(|path| std::str::from_utf8(path).ok())
+        .filter(|path| is_safe_store_path(path))
+        .map(str::to_string)
+        .collect()
+}
+
+fn remote_store_labels(paths: &[String]) -> Vec<String> {
+    let mut labels = paths
+        .iter()
+        .filter_map(|path| path.strip_suffix(".gpg"))
+        .filter(|label| !label.is_empty() && !label.ends_with('/'))
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    labels.sort();
+    labels
+}
+
+// The item file plus every recipients file from the store root down to the
+// item's folder, which is what both backends read to decrypt it.
+fn remote_store_item_paths(paths: &[String], label: &str) -> Vec<String> {
+    let folders = std::iter::once("")
+        .chain(label.match_indices('/').map(|(index, _)| &label[..=index]))
+        .collect::<Vec<_>>();
+
+    let mut needed = folders
+        .iter()
+        .flat_map(|folder| {
+            RECIPIENT_FILE_NAMES
+                .iter()
+                .map(move |name| format!("{folder}{name}"))
+        })
+        .filter(|path| paths.contains(path))
+        .collect::<Vec<_>>();
+    needed.push(format!("{label}.gpg"));
+    needed
+}
+
+// Store Git commands run with `--git-dir <root>/.git`, so `init --bare`
+// creates the cache in that folder rather than in `root` itself. Its HEAD
+// shows the cache is already set up.
+fn remote_store_git_dir(root: &str) -> PathBuf {
+    Path::new(root).join(".git")
+}
+
+fn ensure_remote_store_git_dir(root: &str) -> Result<(), String> {
+    if remote_store_git_dir(root).join("HEAD").is_file() {
+        return Ok(());
+    }
+    ensure_private_dir(Path::new(root)).map_err(|err| err.to_string())?;
+    let output = run_store_git_command(
+        root,
+        "Create remote password store cache",
+        |cmd| {
+            cmd.args(["init", "--bare", "--quiet"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git init", &output))
+    }
+}
+
+fn list_remote_store_paths(root: &str, commit: &str) -> Result<Vec<String>, String> {
+    let output = run_store_git_command(
+        root,
+        "List remote password store items",
+        |cmd| {
+            cmd.args(["ls-tree", "-r", "-z", "--name-only", commit]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(parse_remote_store_paths(&output.stdout))
+    } else {
+        Err(git_command_error("git ls-tree", &output))
+    }
+}
+
+pub fn fetch_remote_store(url: &str) -> Result<RemoteStoreSnapshot, String> {
+    require_host_command_features()?;
+    let url = url.trim();
+    if url.is_empty() {
+        return Err("Enter a Git address.".to_string());
+    }
+
+    let root = remote_store_cache_root(url)?.to_string_lossy().to_string();
+    ensure_remote_store_git_dir(&root)?;
+    let output = run_store_remote_git_command(
+        &root,
+        "Fetch remote password store",
+        |cmd| {
+            cmd.args([
+                "fetch",
+                "--depth",
+                "1",
+                "--no-tags",
+                "--quiet",
+                "--",
+                url,
+                "HEAD",
+            ]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git fetch", &output));
+    }
+
+    let output = run_store_git_command(
+        &root,
+        "Read fetched remote password store commit",
+        |cmd| {
+            cmd.args(["rev-parse", "--verify", "FETCH_HEAD^{commit}"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git rev-parse", &output));
+    }
+    let commit = git_output_text(&output)?;
+    let paths = list_remote_store_paths(&root, &commit)?;
+
+    Ok(RemoteStoreSnapshot {
+        url: url.to_string(),
+        root,
+        commit,
+        paths,
+    })
+}
+
+impl RemoteStoreSnapshot {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn labels(&self) -> Vec<String> {
+        remote_store_labels(&self.paths)
+    }
+
+    fn read_blob(&self, path: &str) -> Result<Vec<u8>, String> {
+        let object = format!("{}:{path}", self.commit);
+        let output = run_store_git_command(
+            &self.root,
+            &format!("Read remote password store file {path}"),
+            |cmd| {
+                cmd.args(["cat-file", "blob", &object]);
+            },
+            CommandLogOptions {
+                redact_stdout: true,
+                ..CommandLogOptions::DEFAULT
+            },
+        )?;
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            Err(git_command_error("git cat-file", &output))
+        }
+    }
+
+    pub fn extract_item(&self, label: &str) -> Result<RemoteStoreItem, String> {
+        if !self.paths.contains(&format!("{label}.gpg")) {
+            return Err(format!("'{label}' isn't in the remote store."));
+        }
+
+        let base = dirs_next::runtime_dir().unwrap_or_else(std::env::temp_dir);
+        let root = base.join(REMOTE_CHECKOUTS_DIR_NAME).join(format!(
+            "{}-{}",
+            std::process::id(),
+            NEXT_REMOTE_CHECKOUT.fetch_add(1, Ordering::Relaxed)
+        ));
+        ensure_private_dir(&root).map_err(|err| err.to_string())?;
+        let item = RemoteStoreItem { root };
+
+        for path in remote_store_item_paths(&self.paths, label) {
+            let contents = self.read_blob(&path)?;
+            let target = item.root.join(&path);
+            if let Some(parent) = target.parent() {
+                ensure_private_dir(parent).map_err(|err| err.to_string())?;
+            }
+            fs::write(&target, contents).map_err(|err| err.to_string())?;
+        }
+        Ok(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ensure_remote_store_git_dir, parse_remote_store_paths, remote_store_git_dir,
+        remote_store_item_paths, remote_store_labels,
+    };
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn paths(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_string()).collect()
+    }
+
+    #[test]
+    fn remote_store_listing_keeps_only_safe_item_paths() {
+        let listed = parse_remote_store_paths(
+            b".gpg-id\0team/mail.gpg\0../escape.gpg\0README.md\0team/.fido-id\0",
+        );
+        assert_eq!(
+            listed,
+            paths(&[".gpg-id", "team/mail.gpg", "README.md", "team/.fido-id"])
+        );
+        assert_eq!(remote_store_labels(&listed), paths(&["team/mail"]));
+    }
+
+    #[test]
+    fn remote_store_items_bring_their_recipient_files() {
+        let listed = paths(&[
+            ".gpg-id",
+            "team/.fido-id",
+            "team/ops/.gpg-id",
+            "team/ops/db.gpg",
+            "other/.gpg-id",
+        ]);
+        assert_eq!(
+            remote_store_item_paths(&listed, "team/ops/db"),
+            paths(&[
+                ".gpg-id",
+                "team/.fido-id",
+                "team/ops/.gpg-id",
+                "team/ops/db.gpg"
+            ])
+        );
+        assert_eq!(
+            remote_store_item_paths(&listed, "top"),
+            paths(&[".gpg-id", "top.gpg"])
+        );
+    }
+
+    #[test]
+    fn remote_store_caches_are_created_once() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let root = std::env::temp_dir().join(format!("keycord-remote-cache-{nanos}"));
+        let root_str = root.to_string_lossy().to_string();
+
+        ensure_remote_store_git_dir(&root_str).expect("create remote cache");
+        let git_dir = remote_store_git_dir(&root_str);
+        assert!(git_dir.join("HEAD").is_file());
+        assert!(!root.join("HEAD").exists());
+
+        // Running `git init` again would put the removed template file back.
+        fs::remove_file(git_dir.join("description")).expect("remove description");
+        ensure_remote_store_git_dir(&root_str).expect("reuse remote cache");
+        assert!(!git_dir.join("description").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -1,8 +1,13 @@
-use super::command::{git_command_error, git_output_text, run_store_git_command};
+use super::command::{
+    git_command_error, git_output_text, run_store_git_command, run_store_remote_git_command,
+};
 use super::repository::{ensure_store_git_repository, has_git_repository};
 use super::types::GitRemote;
 use crate::logging::CommandLogOptions;
 use crate::support::runtime::{require_host_command_features, supports_host_command_features};
+use std::time::Duration;
+
+const REMOTE_TEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 pub fn list_store_git_remotes(root: &str) -> Result<Vec<GitRemote>, String> {
     if !has_git_repository(root) || !supports_host_command_features() {
@@ -118,3 +123,87 @@ pub fn remove_store_git_remote(root: &str, name: &str) -> Result<(), String> {
         Err(git_command_error("git remote remove", &output))
     }
 }
+
+// Asks the remote for its branches without fetching anything, which is enough
+// to show whether the URL, network, and credentials work.
+pub fn test_store_git_remote(root: &str, name: &str) -> Result<(), String> {
+    require_host_command_features()?;
+    let output = run_store_remote_git_command(
+        root,
+        &format!("Test password store Git remote {name}"),
+        |cmd| {
+            cmd.args(["ls-remote", "--heads", name]);
+        },
+        CommandLogOptions {
+            timeout: Some(REMOTE_TEST_TIMEOUT),
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git ls-remote", &output))
+    }
+}
+
+pub fn store_git_upstream_remote(root: &str, branch: &str) -> Result<Option<String>, String> {
+    let key = format!("branch.{branch}.remote");
+    let output = run_store_git_command(
+        root,
+        &format!("Read password store Git upstream for {branch}"),
+        |cmd| {
+            cmd.args(["config", "--get", &key]);
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[1],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    match output.status.code() {
+        Some(0) => Ok(Some(git_output_text(&output)?).filter(|remote| !remote.is_empty())),
+        Some(1) => Ok(None),
+        _ => Err(git_command_error("git config --get", &output)),
+    }
+}
+
+fn set_store_git_branch_config(
+    root: &str,
+    branch: &str,
+    field: &str,
+    value: Option<&str>,
+) -> Result<(), String> {
+    let key = format!("branch.{branch}.{field}");
+    let output = run_store_git_command(
+        root,
+        &format!("Update password store Git upstream for {branch}"),
+        |cmd| {
+            cmd.arg("config");
+            match value {
+                Some(value) => cmd.args([key.as_str(), value]),
+                None => cmd.args(["--unset", key.as_str()]),
+            };
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[5],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    match output.status.code() {
+        Some(0) => Ok(()),
+        Some(5) if value.is_none() => Ok(()),
+        _ => Err(git_command_error("git config", &output)),
+    }
+}
+
+// Writes the tracking config directly, the same as `git push -u` does, so a
+// new remote can be chosen before it has the branch.
+pub fn set_store_git_upstream_remote(
+    root: &str,
+    branch: &str,
+    remote: Option<&str>,
+) -> Result<(), String> {
+    require_host_command_features()?;
+    let merge = format!("refs/heads/{branch}");
+    set_store_git_branch_config(root, branch, "remote", remote)?;
+    set_store_git_branch_config(root, branch, "merge", remote.map(|_| merge.as_str()))
+}
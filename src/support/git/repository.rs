@@ -1,7 +1,15 @@
-use super::command::git_command_error;
-use crate::logging::{run_command_output, CommandLogOptions};
+use super::command::{
+    git_command_error, git_output_text, run_store_git_command, run_store_git_work_tree_command,
+};
+use super::entry_history::store_git_head_oid;
+use super::large_files::{check_staged_large_store_files, LargeStoreFiles};
+use crate::logging::{log_error, run_command_output, CommandLogOptions};
 use crate::preferences::Preferences;
-use crate::support::runtime::{has_host_permission, supports_host_command_features};
+use crate::support::runtime::{
+    has_host_permission, require_host_command_features, supports_host_command_features,
+};
+use crate::support::secure_fs::write_private_file;
+use std::fs;
 use std::path::Path;
 #[cfg(any(test, not(feature = "flatpak")))]
 use std::process::Stdio;
@@ -53,17 +61,213 @@ pub fn ensure_store_git_repository(root: &str) -> Result<(), String> {
     .map_err(|err| format!("Failed to run git command: {err}"))?;
 
     if output.status.success() {
+        apply_store_git_gpg_diff_preference(root);
         Ok(())
     } else {
         Err(git_command_error("git init", &output))
     }
 }
 
+// Turns a plain store into a Git one, with everything already in it as the
+// first commit, so history and sync start from the current items.
+pub fn enable_store_git_versioning(root: &str) -> Result<LargeStoreFiles, String> {
+    require_host_command_features()?;
+    ensure_store_git_repository(root)?;
+
+    let output = run_store_git_work_tree_command(
+        root,
+        "Stage password store for the first commit",
+        |cmd| {
+            cmd.args(["add", "--all"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git add", &output));
+    }
+    let large_files = check_staged_large_store_files(root)?;
+
+    let output = run_store_git_work_tree_command(
+        root,
+        "Make the first password store commit",
+        |cmd| {
+            cmd.args(["commit", "--allow-empty", "-m", "Enable Git versioning."]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(large_files)
+    } else {
+        Err(git_command_error("git commit", &output))
+    }
+}
+
+// `pass` commits every write on its own. A batch folds those commits back
+// into one that only covers the written paths, like the Integrated backend
+// makes, and `pass.signcommits` still decides whether it's signed. Large
+// files get the same check as any other commit on the way.
+pub fn squash_store_git_commits(
+    root: &str,
+    base: &str,
+    message: &str,
+    paths: &[String],
+) -> Result<(), String> {
+    require_host_command_features()?;
+    let Some(tip) = store_git_head_oid(root)? else {
+        return Ok(());
+    };
+    if tip == base || paths.is_empty() {
+        return Ok(());
+    }
+
+    let output = run_store_git_command(
+        root,
+        "Read password store commit signing",
+        |cmd| {
+            cmd.args(["config", "--bool", "pass.signcommits"]);
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[1],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    let sign = output.status.success() && git_output_text(&output)? == "true";
+
+    reset_store_git_head(root, base)?;
+    let large_files = match check_staged_large_store_files(root) {
+        Ok(large_files) => large_files,
+        Err(err) => {
+            reset_store_git_head(root, &tip)?;
+            return Err(err);
+        }
+    };
+    // `commit -- <paths>` takes the files from disk, so a file that was left
+    // out also has to be left off the list.
+    let paths = paths
+        .iter()
+        .filter(|path| {
+            !large_files.left_out || !large_files.files.iter().any(|file| &file.path == *path)
+        })
+        .collect::<Vec<_>>();
+    if paths.is_empty() {
+        return Ok(());
+    }
+    let output = run_store_git_work_tree_command(
+        root,
+        "Commit password store batch",
+        |cmd| {
+            cmd.arg("commit");
+            if sign {
+                cmd.arg("-S");
+            }
+            cmd.args(["-m", message, "--"]).args(paths);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        return Ok(());
+    }
+    // Puts the separate commits back, so nothing is left only staged.
+    let err = git_command_error("git commit", &output);
+    reset_store_git_head(root, &tip)?;
+    Err(err)
+}
+
+fn reset_store_git_head(root: &str, commit: &str) -> Result<(), String> {
+    let output = run_store_git_work_tree_command(
+        root,
+        "Move password store Git HEAD",
+        |cmd| {
+            cmd.args(["reset", "--soft", commit]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git reset --soft", &output))
+    }
+}
+
+const GPG_DIFF_ATTRIBUTE: &str = "*.gpg diff=gpg";
+const GPG_DIFF_TEXTCONV: &str =
+    "gpg -d --quiet --yes --compress-algo=none --no-encrypt-to --batch --use-agent";
+
+fn attributes_with_gpg_diff(existing: &str) -> Option<String> {
+    if existing
+        .lines()
+        .any(|line| line.trim() == GPG_DIFF_ATTRIBUTE)
+    {
+        return None;
+    }
+
+    let mut attributes = existing.to_string();
+    if !attributes.is_empty() && !attributes.ends_with('\n') {
+        attributes.push('\n');
+    }
+    attributes.push_str(GPG_DIFF_ATTRIBUTE);
+    attributes.push('\n');
+    Some(attributes)
+}
+
+fn set_store_git_config(root: &str, key: &str, value: &str) -> Result<(), String> {
+    let output = run_store_git_command(
+        root,
+        &format!("Set password store Git config {key}"),
+        |cmd| {
+            cmd.args(["config", key, value]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git config", &output))
+    }
+}
+
+// Lets `git diff` and `git log -p` show decrypted items, like `pass git init`
+// does. The attribute goes in `.git/info/attributes` instead of a tracked
+// `.gitattributes`, so a cloned store doesn't get a change to commit.
+fn configure_store_git_gpg_diffs(root: &str) -> Result<(), String> {
+    let path = Path::new(root).join(".git").join("info").join("attributes");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if let Some(attributes) = attributes_with_gpg_diff(&existing) {
+        write_private_file(&path, attributes).map_err(|err| err.to_string())?;
+    }
+    set_store_git_config(root, "diff.gpg.binary", "true")?;
+    set_store_git_config(root, "diff.gpg.textconv", GPG_DIFF_TEXTCONV)
+}
+
+pub fn apply_store_git_gpg_diff_preference(root: &str) {
+    if !Preferences::new().git_gpg_diffs() {
+        return;
+    }
+    if let Err(err) = configure_store_git_gpg_diffs(root) {
+        log_error(format!(
+            "Failed to set up decrypted Git diffs for {root}: {err}"
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::git_command_available_with;
+    use super::{attributes_with_gpg_diff, git_command_available_with};
     use std::process::Command;
 
+    #[test]
+    fn gpg_diff_attribute_is_added_once() {
+        assert_eq!(
+            attributes_with_gpg_diff(""),
+            Some("*.gpg diff=gpg\n".to_string())
+        );
+        assert_eq!(
+            attributes_with_gpg_diff("*.txt text"),
+            Some("*.txt text\n*.gpg diff=gpg\n".to_string())
+        );
+        assert_eq!(attributes_with_gpg_diff("*.gpg diff=gpg\n"), None);
+    }
+
     #[test]
     fn git_command_probe_accepts_successful_commands() {
         assert!(git_command_available_with(|| Command::new("true")));
@@ -0,0 +1,147 @@
+use super::command::{git_command_error, run_store_git_command};
+use super::entry_history::store_git_head_oid;
+use super::repository::has_git_repository;
+use crate::logging::CommandLogOptions;
+use crate::password::entry_files::label_from_password_entry_relative_path;
+use crate::support::runtime::require_host_command_features;
+use std::collections::HashSet;
+use std::path::Path;
+
+const STORE_GIT_DELETED_ENTRY_LIMIT: usize = 200;
+const RECORD_SEPARATOR: char = '\u{1e}';
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoreGitDeletedEntry {
+    pub label: String,
+    pub path: String,
+    pub deleted_in: String,
+    pub deleted_at: i64,
+    pub author: String,
+}
+
+// Only the newest deletion of each path counts, and items that were added
+// again since then are left out, so every row can be restored as it is.
+pub fn store_git_deleted_entries(root: &str) -> Result<Vec<StoreGitDeletedEntry>, String> {
+    if !has_git_repository(root) || store_git_head_oid(root)?.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let output = run_store_git_command(
+        root,
+        "Find deleted password store items",
+        |cmd| {
+            cmd.args([
+                "-c",
+                "core.quotePath=false",
+                "log",
+                "--diff-filter=D",
+                "--name-only",
+                "--format=%x1e%H%x1f%ct%x1f%an",
+                "HEAD",
+            ]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git log --diff-filter=D", &output));
+    }
+
+    Ok(
+        parse_store_git_deleted_entries(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .filter(|entry| !Path::new(root).join(&entry.path).exists())
+            .take(STORE_GIT_DELETED_ENTRY_LIMIT)
+            .collect(),
+    )
+}
+
+// The file as it was in the commit before the deletion. Git's index and work
+// tree are left alone; the backend decrypts it and saves it again.
+pub fn store_git_deleted_entry_file(
+    root: &str,
+    entry: &StoreGitDeletedEntry,
+) -> Result<Vec<u8>, String> {
+    require_host_command_features()?;
+    let object = format!("{}^:{}", entry.deleted_in, entry.path);
+    let output = run_store_git_command(
+        root,
+        &format!("Read deleted password store item {}", entry.label),
+        |cmd| {
+            cmd.args(["show", &object]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(git_command_error("git show", &output))
+    }
+}
+
+fn parse_store_git_deleted_entries(output: &str) -> Vec<StoreGitDeletedEntry> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for record in output.split(RECORD_SEPARATOR) {
+        let mut lines = record.lines();
+        let Some(header) = lines.next() else {
+            continue;
+        };
+        let mut fields = header.splitn(3, FIELD_SEPARATOR);
+        let (Some(deleted_in), Some(deleted_at), Some(author)) = (
+            fields.next().map(str::trim),
+            fields
+                .next()
+                .and_then(|value| value.trim().parse::<i64>().ok()),
+            fields.next().map(str::trim),
+        ) else {
+            continue;
+        };
+        for path in lines.map(str::trim).filter(|path| !path.is_empty()) {
+            let Some(label) = label_from_password_entry_relative_path(Path::new(path)) else {
+                continue;
+            };
+            if !seen.insert(path.to_string()) {
+                continue;
+            }
+            entries.push(StoreGitDeletedEntry {
+                label,
+                path: path.to_string(),
+                deleted_in: deleted_in.to_string(),
+                deleted_at,
+                author: author.to_string(),
+            });
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_store_git_deleted_entries, StoreGitDeletedEntry};
+
+    #[test]
+    fn deleted_entries_keep_the_newest_deletion_of_each_item() {
+        let output = "\u{1e}bbb\u{1f}300\u{1f}Alice\n\nmail/github.gpg\nnotes.txt\n\u{1e}aaa\u{1f}200\u{1f}Bob\n\nmail/github.gpg\nbank.keycord\n";
+
+        assert_eq!(
+            parse_store_git_deleted_entries(output),
+            vec![
+                StoreGitDeletedEntry {
+                    label: "mail/github".to_string(),
+                    path: "mail/github.gpg".to_string(),
+                    deleted_in: "bbb".to_string(),
+                    deleted_at: 300,
+                    author: "Alice".to_string(),
+                },
+                StoreGitDeletedEntry {
+                    label: "bank".to_string(),
+                    path: "bank.keycord".to_string(),
+                    deleted_in: "aaa".to_string(),
+                    deleted_at: 200,
+                    author: "Bob".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use super::command::{
+    git_command_error, git_output_text, run_store_git_command, run_store_git_work_tree_command,
+};
+use crate::logging::CommandLogOptions;
+
+// Store folders are matched from the store root, so slashes around them are
+// dropped along with empty names and repeats.
+pub fn normalized_sparse_folders<'a>(folders: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut folders = folders
+        .into_iter()
+        .map(|folder| folder.trim().trim_matches('/').to_string())
+        .filter(|folder| !folder.is_empty())
+        .collect::<Vec<_>>();
+    folders.sort();
+    folders.dedup();
+    folders
+}
+
+fn store_git_is_sparse(root: &str) -> Result<bool, String> {
+    let output = run_store_git_command(
+        root,
+        "Read password store sparse checkout setting",
+        |cmd| {
+            cmd.args(["config", "--bool", "core.sparseCheckout"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    // Git exits with 1 when the setting was never written.
+    Ok(output.status.success() && git_output_text(&output)? == "true")
+}
+
+// Returns None when every folder is checked out.
+pub fn store_git_sparse_folders(root: &str) -> Result<Option<Vec<String>>, String> {
+    if !store_git_is_sparse(root)? {
+        return Ok(None);
+    }
+
+    let output = run_store_git_work_tree_command(
+        root,
+        "List password store sparse checkout folders",
+        |cmd| {
+            cmd.args(["sparse-checkout", "list"]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if !output.status.success() {
+        return Err(git_command_error("git sparse-checkout list", &output));
+    }
+    Ok(Some(normalized_sparse_folders(
+        git_output_text(&output)?.lines(),
+    )))
+}
+
+// Cone mode always keeps the files at the top of the store, so the
+// `.gpg-id` there stays in place for new items and re-encryption.
+pub fn set_store_git_sparse_folders(root: &str, folders: &[String]) -> Result<(), String> {
+    let folders = normalized_sparse_folders(folders.iter().map(String::as_str));
+    let output = run_store_git_work_tree_command(
+        root,
+        "Change password store sparse checkout folders",
+        |cmd| {
+            if folders.is_empty() {
+                cmd.args(["sparse-checkout", "disable"]);
+            } else {
+                cmd.args(["sparse-checkout", "set", "--cone", "--"])
+                    .args(&folders);
+            }
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git sparse-checkout", &output))
+    }
+}
+
+// Pathspecs that limit history to the checked-out part of a sparse store:
+// its folders and the files at the top of the store.
+#[cfg(any(feature = "audit", test))]
+pub(super) fn sparse_folder_pathspecs(folders: &[String]) -> Vec<String> {
+    if folders.is_empty() {
+        return Vec::new();
+    }
+    folders
+        .iter()
+        .cloned()
+        .chain(std::iter::once(":(glob)*".to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalized_sparse_folders, sparse_folder_pathspecs};
+
+    #[test]
+    fn sparse_folders_are_normalized() {
+        assert_eq!(
+            normalized_sparse_folders([" /team/ops/ ", "team/ops", "", "/", "personal"]),
+            vec!["personal".to_string(), "team/ops".to_string()]
+        );
+    }
+
+    #[test]
+    fn sparse_history_keeps_the_folders_and_the_store_root() {
+        assert!(sparse_folder_pathspecs(&[]).is_empty());
+        assert_eq!(
+            sparse_folder_pathspecs(&["team/ops".to_string()]),
+            vec!["team/ops".to_string(), ":(glob)*".to_string()]
+        );
+    }
+}
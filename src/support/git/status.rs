@@ -4,6 +4,7 @@ use super::command::{
 };
 use super::remotes::list_store_git_remotes;
 use super::repository::has_git_repository;
+use super::sync::fetch_store_git_remote;
 use super::types::{GitRemote, StoreGitHead, StoreGitRepositoryStatus};
 use crate::logging::{log_error, CommandLogOptions};
 use crate::preferences::Preferences;
@@ -179,6 +180,16 @@ fn files_exist_in_work_tree_with(
     Ok(!git_output_text(&output)?.is_empty())
 }
 
+// Reads only the checked out branch, for places that don't need the slower
+// dirty and ahead or behind checks.
+pub fn store_git_branch(root: &str) -> Result<Option<String>, String> {
+    if !has_git_repository(root) || !supports_host_command_features() {
+        return Ok(None);
+    }
+
+    symbolic_head_branch(root)
+}
+
 pub fn store_git_repository_status(root: &str) -> Result<StoreGitRepositoryStatus, String> {
     if !has_git_repository(root) {
         return Ok(empty_git_status(false));
@@ -209,6 +220,58 @@ pub fn store_git_repository_status(root: &str) -> Result<StoreGitRepositoryStatu
     })
 }
 
+fn store_git_lists_anything(
+    root: &str,
+    context: &str,
+    args: &[&str],
+    work_tree: bool,
+) -> Result<bool, String> {
+    let configure = |cmd: &mut Command| {
+        cmd.args(args);
+    };
+    let output = if work_tree {
+        run_store_git_work_tree_command(root, context, configure, CommandLogOptions::DEFAULT)?
+    } else {
+        run_store_git_command(root, context, configure, CommandLogOptions::DEFAULT)?
+    };
+    if output.status.success() {
+        Ok(!git_output_text(&output)?.is_empty())
+    } else {
+        Err(git_command_error(&format!("git {}", args[0]), &output))
+    }
+}
+
+// Looks for anything a fresh clone wouldn't bring back: ignored and excluded
+// files, stashes, and commits on any local branch that no remote has. The
+// remotes are fetched first, so stale tracking refs can't hide a commit.
+pub fn store_git_has_local_only_work(root: &str) -> Result<bool, String> {
+    for remote in list_store_git_remotes(root)? {
+        fetch_store_git_remote(root, &remote.name)?;
+    }
+
+    Ok(store_git_lists_anything(
+        root,
+        "List password store files that aren't committed",
+        &[
+            "status",
+            "--porcelain",
+            "--ignored",
+            "--untracked-files=all",
+        ],
+        true,
+    )? || store_git_lists_anything(
+        root,
+        "List password store Git stashes",
+        &["stash", "list"],
+        false,
+    )? || store_git_lists_anything(
+        root,
+        "List password store commits that aren't on a remote",
+        &["rev-list", "--branches", "--not", "--remotes"],
+        false,
+    )?)
+}
+
 fn branch_sync_state(
     root: &str,
     branch: Option<&str>,
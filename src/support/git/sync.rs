@@ -1,10 +1,54 @@
 use super::command::{
-    git_command_error, run_store_git_work_tree_command, run_store_remote_git_command,
+    git_command_error, run_store_git_command, run_store_git_work_tree_command,
+    run_store_remote_git_command,
 };
+use super::remotes::store_git_upstream_remote;
 use super::status::{remote_branch_exists, store_git_repository_status};
-use super::types::{StoreGitHead, StoreGitRepositoryStatus};
-use crate::logging::{log_error, CommandLogOptions};
+use super::types::{StoreGitHead, StoreGitRecovery, StoreGitRepositoryStatus, StoreGitSyncSummary};
+use crate::logging::{log_error, log_info, CommandLogOptions};
+use crate::password::entry_files::is_password_entry_file;
 use crate::support::runtime::require_host_command_features;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+const DIVERGED_HISTORY_ERROR: &str = "The remote history was rewritten.";
+
+// A remote whose history was rewritten, for example with a force push,
+// either shares no commits with ours or rejects every plain push. Both are
+// read from Git's machine output, which isn't translated.
+fn git_push_rejected(porcelain: &str) -> bool {
+    porcelain
+        .lines()
+        .any(|line| line.starts_with('!') && line.contains("[rejected]"))
+}
+
+fn store_git_histories_unrelated(root: &str, target: &str) -> Result<bool, String> {
+    let output = run_store_git_command(
+        root,
+        &format!("Find the common password store Git history with {target}"),
+        |cmd| {
+            cmd.args(["merge-base", "HEAD", target]);
+        },
+        CommandLogOptions {
+            accepted_exit_codes: &[1],
+            ..CommandLogOptions::DEFAULT
+        },
+    )?;
+    match output.status.code() {
+        Some(0) => Ok(false),
+        Some(1) if output.stderr.is_empty() => Ok(true),
+        _ => Err(git_command_error("git merge-base", &output)),
+    }
+}
+
+fn diverged_history_error(error: String) -> String {
+    format!("{DIVERGED_HISTORY_ERROR} {error}")
+}
+
+pub fn store_git_sync_needs_recovery(err: &str) -> bool {
+    err.starts_with(DIVERGED_HISTORY_ERROR)
+}
 
 pub(super) fn sync_blocked_by_local_state(status: &StoreGitRepositoryStatus) -> Option<String> {
     if status.dirty && status.has_outgoing_commits && status.has_incoming_commits {
@@ -32,7 +76,7 @@ pub(super) fn sync_blocked_by_local_state(status: &StoreGitRepositoryStatus) ->
     None
 }
 
-fn fetch_store_git_remote(root: &str, remote: &str) -> Result<(), String> {
+pub(super) fn fetch_store_git_remote(root: &str, remote: &str) -> Result<(), String> {
     let output = run_store_remote_git_command(
         root,
         &format!("Fetch password store Git remote {remote}"),
@@ -74,12 +118,170 @@ fn abort_store_git_merge(root: &str) {
     }
 }
 
-fn merge_store_git_remote_branch(root: &str, remote: &str, branch: &str) -> Result<(), String> {
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct UnmergedGitPath {
+    base: Option<String>,
+    ours: Option<String>,
+    theirs: Option<String>,
+}
+
+// `git ls-files -u -z` prints `<mode> <object> <stage>\t<path>` for every
+// unmerged stage of every path.
+fn parse_unmerged_git_paths(output: &str) -> BTreeMap<String, UnmergedGitPath> {
+    let mut paths = BTreeMap::<String, UnmergedGitPath>::new();
+    for record in output.split('\0').filter(|record| !record.is_empty()) {
+        let Some((info, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut fields = info.split_whitespace().skip(1);
+        let (Some(object), Some(stage)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let entry = paths.entry(path.to_string()).or_default();
+        let object = Some(object.to_string());
+        match stage {
+            "1" => entry.base = object,
+            "2" => entry.ours = object,
+            "3" => entry.theirs = object,
+            _ => {}
+        }
+    }
+    paths
+}
+
+// Only encrypted entries that exist on both sides can be kept side by side.
+// Anything else, such as `.gpg-id` or a delete on one side, still aborts the
+// merge so nothing is lost silently.
+fn entry_conflicts_are_resolvable(paths: &BTreeMap<String, UnmergedGitPath>) -> bool {
+    !paths.is_empty()
+        && paths.iter().all(|(path, stages)| {
+            is_password_entry_file(Path::new(path))
+                && stages.ours.is_some()
+                && stages.theirs.is_some()
+        })
+}
+
+// The copy names the remote it came from. A `/` in a remote name would put
+// the copy in another folder, so it is replaced.
+fn conflict_copy_path(path: &str, remote: &str, taken: impl Fn(&str) -> bool) -> String {
+    let source = remote.replace(['/', '\\'], "-");
+    let path = Path::new(path);
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let parent = path
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string())
+        .filter(|parent| !parent.is_empty())
+        .map(|parent| format!("{parent}/"))
+        .unwrap_or_default();
+
+    let mut attempt = 1;
+    loop {
+        let suffix = if attempt == 1 {
+            format!(" (conflict from {source})")
+        } else {
+            format!(" (conflict from {source} {attempt})")
+        };
+        let candidate = format!("{parent}{stem}{suffix}.{extension}");
+        if !taken(&candidate) {
+            return candidate;
+        }
+        attempt += 1;
+    }
+}
+
+fn run_merge_resolution_command(
+    root: &str,
+    context: &str,
+    args: &[&str],
+) -> Result<Vec<u8>, String> {
+    let output = run_store_git_work_tree_command(
+        root,
+        context,
+        |cmd| {
+            cmd.args(args);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(git_command_error(&format!("git {}", args[0]), &output))
+    }
+}
+
+fn resolve_store_git_entry_conflicts(
+    root: &str,
+    remote: &str,
+) -> Result<Option<Vec<String>>, String> {
+    let unmerged = run_merge_resolution_command(
+        root,
+        "List conflicted password store Git paths",
+        &["ls-files", "-u", "-z"],
+    )?;
+    let paths = parse_unmerged_git_paths(&String::from_utf8_lossy(&unmerged));
+    if !entry_conflicts_are_resolvable(&paths) {
+        return Ok(None);
+    }
+
+    let mut copies = Vec::new();
+    for (path, stages) in &paths {
+        let Some(theirs) = stages.theirs.as_deref() else {
+            continue;
+        };
+        let copy = conflict_copy_path(path, remote, |candidate| {
+            Path::new(root).join(candidate).exists() || copies.iter().any(|copy| copy == candidate)
+        });
+        let contents = run_merge_resolution_command(
+            root,
+            "Read incoming password store entry",
+            &["cat-file", "blob", theirs],
+        )?;
+        fs::write(Path::new(root).join(&copy), contents).map_err(|err| err.to_string())?;
+        run_merge_resolution_command(
+            root,
+            "Keep local password store entry",
+            &["checkout", "--ours", "--", path],
+        )?;
+        run_merge_resolution_command(
+            root,
+            "Stage resolved password store entries",
+            &["add", "--", path, &copy],
+        )?;
+        log_info(format!(
+            "Kept the incoming copy of {path} from {remote} as {copy} in {root}."
+        ));
+        copies.push(copy);
+    }
+    run_merge_resolution_command(
+        root,
+        "Finish password store Git merge",
+        &["commit", "--no-edit"],
+    )?;
+    Ok(Some(copies))
+}
+
+fn merge_store_git_remote_branch(
+    root: &str,
+    remote: &str,
+    branch: &str,
+) -> Result<Vec<String>, String> {
     if !remote_branch_exists(root, remote, branch)? {
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     let target = format!("{remote}/{branch}");
+    if store_git_histories_unrelated(root, &target)? {
+        return Err(diverged_history_error(format!(
+            "git merge --no-edit failed: {target} shares no commits with this store."
+        )));
+    }
     let output = run_store_git_work_tree_command(
         root,
         &format!("Merge password store Git branch {target}"),
@@ -92,9 +294,15 @@ fn merge_store_git_remote_branch(root: &str, remote: &str, branch: &str) -> Resu
         },
     )?;
     if output.status.success() {
-        return Ok(());
+        return Ok(Vec::new());
+    }
+    match resolve_store_git_entry_conflicts(root, remote) {
+        Ok(Some(copies)) => return Ok(copies),
+        Ok(None) => {}
+        Err(err) => log_error(format!(
+            "Failed to keep both sides of conflicted password store entries for {root}: {err}"
+        )),
     }
-
     abort_store_git_merge(root);
     Err(git_command_error("git merge --no-edit", &output))
 }
@@ -105,48 +313,211 @@ fn push_store_git_remote_branch(root: &str, remote: &str, branch: &str) -> Resul
         root,
         &format!("Push password store Git branch {branch} to {remote}"),
         |cmd| {
-            cmd.args(["push", remote, &refspec]);
+            cmd.args(["push", "--porcelain", remote, &refspec]);
         },
         CommandLogOptions::DEFAULT,
     )?;
     if output.status.success() {
         Ok(())
+    } else if git_push_rejected(&String::from_utf8_lossy(&output.stdout)) {
+        Err(diverged_history_error(git_command_error(
+            "git push", &output,
+        )))
     } else {
         Err(git_command_error("git push", &output))
     }
 }
 
-pub fn sync_store_repository(root: &str) -> Result<(), String> {
-    require_host_command_features()?;
-    let status = store_git_repository_status(root)?;
-    if !status.has_repository || status.remotes.is_empty() {
+// The lease makes the push fail if someone pushed again since our fetch,
+// so only the rewritten history we already saw gets replaced.
+fn force_push_store_git_remote_branch(
+    root: &str,
+    remote: &str,
+    branch: &str,
+) -> Result<(), String> {
+    let refspec = format!("HEAD:refs/heads/{branch}");
+    let lease = format!("--force-with-lease=refs/heads/{branch}:refs/remotes/{remote}/{branch}");
+    let output = run_store_remote_git_command(
+        root,
+        &format!("Force push password store Git branch {branch} to {remote}"),
+        |cmd| {
+            cmd.args(["push", &lease, remote, &refspec]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(git_command_error("git push --force-with-lease", &output))
+    }
+}
+
+fn rebase_store_git_onto_remote_branch(
+    root: &str,
+    remote: &str,
+    branch: &str,
+) -> Result<(), String> {
+    let target = format!("{remote}/{branch}");
+    let output = run_store_git_work_tree_command(
+        root,
+        &format!("Rebase password store Git branch onto {target}"),
+        |cmd| {
+            cmd.args(["rebase", &target]);
+        },
+        CommandLogOptions::DEFAULT,
+    )?;
+    if output.status.success() {
         return Ok(());
     }
+
+    let abort = run_store_git_work_tree_command(
+        root,
+        "Abort password store Git rebase",
+        |cmd| {
+            cmd.args(["rebase", "--abort"]);
+        },
+        CommandLogOptions::DEFAULT,
+    );
+    if let Err(err) = abort {
+        log_error(format!(
+            "Failed to abort password store rebase for {root}: {err}"
+        ));
+    }
+    Err(git_command_error("git rebase", &output))
+}
+
+fn store_git_sync_branch(status: &StoreGitRepositoryStatus) -> Result<String, String> {
+    match &status.head {
+        StoreGitHead::Branch(branch) => Ok(branch.clone()),
+        StoreGitHead::UnbornBranch(branch) => Err(format!(
+            "Make an initial commit on '{branch}' before syncing this store."
+        )),
+        StoreGitHead::Detached => Err("Check out a branch before syncing this store.".to_string()),
+    }
+}
+
+// Recovers against the branch's upstream remote, or the first remote when
+// none is set. Other remotes catch up on the next regular sync.
+pub fn recover_store_repository_sync(root: &str, recovery: StoreGitRecovery) -> Result<(), String> {
+    require_host_command_features()?;
+    let status = store_git_repository_status(root)?;
     if let Some(reason) = sync_blocked_by_local_state(&status) {
         return Err(reason);
     }
+    let branch = store_git_sync_branch(&status)?;
+    let remote = match store_git_upstream_remote(root, &branch)? {
+        Some(remote) => remote,
+        None => status
+            .remotes
+            .first()
+            .map(|remote| remote.name.clone())
+            .ok_or_else(|| "This store has no remote to sync with.".to_string())?,
+    };
 
-    let branch = match status.head {
-        StoreGitHead::Branch(branch) => branch,
-        StoreGitHead::UnbornBranch(branch) => {
-            return Err(format!(
-                "Make an initial commit on '{branch}' before syncing this store."
-            ));
+    fetch_store_git_remote(root, &remote)?;
+    match recovery {
+        StoreGitRecovery::RebaseOntoRemote => {
+            if remote_branch_exists(root, &remote, &branch)? {
+                rebase_store_git_onto_remote_branch(root, &remote, &branch)?;
+            }
+            push_store_git_remote_branch(root, &remote, &branch)
         }
-        StoreGitHead::Detached => {
-            return Err("Check out a branch before syncing this store.".to_string());
+        StoreGitRecovery::ForcePushWithLease => {
+            force_push_store_git_remote_branch(root, &remote, &branch)
         }
-    };
+    }?;
+    log_info(format!(
+        "Recovered password store sync for {root} with {remote}."
+    ));
+    Ok(())
+}
+
+pub fn sync_store_repository(root: &str) -> Result<StoreGitSyncSummary, String> {
+    require_host_command_features()?;
+    let status = store_git_repository_status(root)?;
+    if !status.has_repository || status.remotes.is_empty() {
+        return Ok(StoreGitSyncSummary::default());
+    }
+    if let Some(reason) = sync_blocked_by_local_state(&status) {
+        return Err(reason);
+    }
+
+    let branch = store_git_sync_branch(&status)?;
 
     for remote in &status.remotes {
         fetch_store_git_remote(root, &remote.name)?;
     }
+    let mut summary = StoreGitSyncSummary::default();
     for remote in &status.remotes {
-        merge_store_git_remote_branch(root, &remote.name, &branch)?;
+        summary
+            .conflict_copies
+            .extend(merge_store_git_remote_branch(root, &remote.name, &branch)?);
     }
     for remote in &status.remotes {
         push_store_git_remote_branch(root, &remote.name, &branch)?;
     }
 
-    Ok(())
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        conflict_copy_path, diverged_history_error, entry_conflicts_are_resolvable,
+        git_push_rejected, parse_unmerged_git_paths, store_git_sync_needs_recovery,
+    };
+
+    #[test]
+    fn rewritten_remote_history_needs_recovery() {
+        assert!(git_push_rejected(
+            "To origin\n!\tHEAD:refs/heads/main\t[rejected] (non-fast-forward)\nDone\n"
+        ));
+        assert!(git_push_rejected(
+            "To origin\n!\tHEAD:refs/heads/main\t[rejected] (fetch first)\nDone\n"
+        ));
+        assert!(!git_push_rejected(
+            "To origin\n!\tHEAD:refs/heads/main\t[remote rejected] (pre-receive hook declined)\nDone\n"
+        ));
+        assert!(!git_push_rejected("fatal: Authentication failed"));
+
+        assert!(store_git_sync_needs_recovery(&diverged_history_error(
+            "git push failed: rejected".to_string()
+        )));
+        assert!(!store_git_sync_needs_recovery("git push failed: rejected"));
+    }
+
+    #[test]
+    fn unmerged_paths_are_grouped_by_stage() {
+        let paths = parse_unmerged_git_paths(
+            "100644 aaa 2\tteam/mail.gpg\0100644 bbb 3\tteam/mail.gpg\0100644 ccc 1\t.gpg-id\0100644 ddd 2\t.gpg-id\0",
+        );
+
+        let mail = &paths["team/mail.gpg"];
+        assert_eq!(mail.base, None);
+        assert_eq!(mail.ours.as_deref(), Some("aaa"));
+        assert_eq!(mail.theirs.as_deref(), Some("bbb"));
+        assert!(!entry_conflicts_are_resolvable(&paths));
+
+        let mut entries_only = paths;
+        entries_only.remove(".gpg-id");
+        assert!(entry_conflicts_are_resolvable(&entries_only));
+    }
+
+    #[test]
+    fn conflict_copies_name_the_remote_and_avoid_existing_paths() {
+        assert_eq!(
+            conflict_copy_path("team/mail.gpg", "origin", |_| false),
+            "team/mail (conflict from origin).gpg"
+        );
+        assert_eq!(
+            conflict_copy_path("mail.keycord", "backup", |candidate| {
+                candidate == "mail (conflict from backup).keycord"
+            }),
+            "mail (conflict from backup 2).keycord"
+        );
+        assert_eq!(
+            conflict_copy_path("mail.gpg", "team/laptop", |_| false),
+            "mail (conflict from team-laptop).gpg"
+        );
+    }
 }
@@ -1,10 +1,12 @@
 use super::command::{configure_store_git_repo_command, git_command_error};
 use super::sync::sync_blocked_by_local_state;
 use super::{
-    add_store_git_remote, has_git_repository, list_store_git_remotes,
+    add_store_git_remote, enable_store_git_versioning, has_git_repository, list_store_git_remotes,
     password_store_git_state_summary, remove_store_git_remote, rename_store_git_remote,
-    set_store_git_remote_url, store_git_repository_status, sync_store_repository, GitRemote,
-    StoreGitHead, StoreGitRepositoryStatus,
+    set_store_git_remote_url, set_store_git_upstream_remote, store_git_deleted_entries,
+    store_git_deleted_entry_file, store_git_has_local_only_work, store_git_repository_status,
+    store_git_upstream_remote, sync_store_repository, GitRemote, StoreGitHead,
+    StoreGitRepositoryStatus,
 };
 use crate::preferences::Preferences;
 use std::fs::{self, File};
@@ -211,6 +213,68 @@ fn git_remote_crud_preserves_existing_history() {
     let _ = fs::remove_dir_all(&remote_b);
 }
 
+#[test]
+fn deleted_items_can_be_read_from_git_history() {
+    let repo = temp_dir_path("restore");
+    init_repo(&repo).expect("initialize repo");
+    commit_file(&repo, "github.gpg", "secret\n", "Add github").expect("create commit");
+    commit_file(&repo, "notes.txt", "notes\n", "Add notes").expect("create commit");
+    git(&repo, &["rm", "-q", "github.gpg", "notes.txt"]).expect("remove files");
+    git(&repo, &["commit", "-m", "Remove github"]).expect("commit removal");
+    let root = repo.to_string_lossy().to_string();
+
+    let deleted = store_git_deleted_entries(&root).expect("list deleted items");
+    assert_eq!(deleted.len(), 1);
+    assert_eq!(deleted[0].label, "github");
+    assert_eq!(deleted[0].author, "Keycord Tests");
+
+    assert_eq!(
+        store_git_deleted_entry_file(&root, &deleted[0]).expect("read deleted item"),
+        b"secret\n"
+    );
+    assert!(!repo.join("github.gpg").exists());
+    assert_eq!(
+        git(&repo, &["status", "--porcelain"]).expect("read status"),
+        ""
+    );
+
+    let _ = fs::remove_dir_all(&repo);
+}
+
+#[test]
+fn git_upstream_remote_can_be_set_before_the_remote_has_the_branch() {
+    let repo = temp_dir_path("upstream");
+    let remote = temp_dir_path("upstream-remote.git");
+    init_repo(&repo).expect("initialize repo");
+    commit_file(&repo, "secret.txt", "one\n", "Initial commit").expect("create commit");
+    init_bare_repo(&remote).expect("initialize bare repo");
+    let root = repo.to_string_lossy().to_string();
+    add_store_git_remote(&root, "backup", remote.to_string_lossy().as_ref()).expect("add remote");
+
+    assert_eq!(
+        store_git_upstream_remote(&root, "main").expect("read upstream"),
+        None
+    );
+    set_store_git_upstream_remote(&root, "main", Some("backup")).expect("set upstream");
+    assert_eq!(
+        store_git_upstream_remote(&root, "main").expect("read upstream"),
+        Some("backup".to_string())
+    );
+    assert_eq!(
+        git(&repo, &["config", "--get", "branch.main.merge"]).expect("read merge ref"),
+        "refs/heads/main"
+    );
+    set_store_git_upstream_remote(&root, "main", None).expect("clear upstream");
+    set_store_git_upstream_remote(&root, "main", None).expect("clear upstream twice");
+    assert_eq!(
+        store_git_upstream_remote(&root, "main").expect("read upstream"),
+        None
+    );
+
+    let _ = fs::remove_dir_all(&repo);
+    let _ = fs::remove_dir_all(&remote);
+}
+
 #[test]
 fn git_status_reports_branch_dirty_state_and_remotes() {
     let repo = temp_dir_path("status");
@@ -369,6 +433,57 @@ fn sync_store_repository_aborts_conflicted_merges() {
     let _ = fs::remove_dir_all(&clone);
 }
 
+#[test]
+fn sync_store_repository_keeps_both_copies_of_entries_added_on_two_machines() {
+    let repo = temp_dir_path("sync-add-add-local");
+    let remote = temp_dir_path("sync-add-add-remote.git");
+    let clone = temp_dir_path("sync-add-add-clone");
+    init_repo(&repo).expect("initialize repo");
+    commit_file(&repo, ".gpg-id", "ABC123\n", "Initial commit").expect("create initial commit");
+    init_bare_repo(&remote).expect("initialize bare repo");
+    add_store_git_remote(
+        repo.to_string_lossy().as_ref(),
+        "origin",
+        remote.to_string_lossy().as_ref(),
+    )
+    .expect("add origin");
+    git(&repo, &["push", "origin", "HEAD:refs/heads/main"]).expect("push local branch");
+
+    clone_repo(&remote, &clone).expect("clone remote");
+    commit_file(&clone, "mail.gpg", "remote\n", "Add mail remotely").expect("add remote entry");
+    git(&clone, &["push", "origin", "HEAD:refs/heads/main"]).expect("push remote entry");
+
+    commit_file(&repo, "mail.gpg", "local\n", "Add mail locally").expect("add local entry");
+
+    let summary =
+        sync_store_repository(repo.to_string_lossy().as_ref()).expect("sync local repository");
+
+    assert_eq!(
+        summary.conflict_copies,
+        vec!["mail (conflict from origin).gpg".to_string()]
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("mail.gpg")).expect("read local entry"),
+        "local\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("mail (conflict from origin).gpg"))
+            .expect("read incoming entry"),
+        "remote\n"
+    );
+    assert!(git(&repo, &["status", "--short"])
+        .expect("read repo status")
+        .is_empty());
+    assert_eq!(
+        git(&repo, &["rev-parse", "HEAD"]).expect("read local head"),
+        branch_head_oid(&remote, "main").expect("read remote head")
+    );
+
+    let _ = fs::remove_dir_all(&repo);
+    let _ = fs::remove_dir_all(&remote);
+    let _ = fs::remove_dir_all(&clone);
+}
+
 #[test]
 fn sync_store_repository_rejects_dirty_worktrees() {
     let repo = temp_dir_path("sync-dirty");
@@ -478,6 +593,23 @@ fn add_store_git_remote_initializes_missing_repository() {
     let _ = fs::remove_dir_all(&remote);
 }
 
+#[test]
+fn enabling_git_versioning_commits_the_current_items() {
+    let repo = temp_dir_path("enable-versioning");
+    init_repo(&repo).expect("initialize repo");
+    write_file(&repo.join("mail.gpg"), "secret").expect("write item");
+
+    enable_store_git_versioning(repo.to_string_lossy().as_ref()).expect("enable versioning");
+
+    assert_eq!(
+        git(&repo, &["log", "--format=%s"]).expect("read log"),
+        "Enable Git versioning."
+    );
+    assert_eq!(git(&repo, &["ls-files"]).expect("list files"), "mail.gpg");
+
+    let _ = fs::remove_dir_all(&repo);
+}
+
 #[test]
 fn sync_store_repository_skips_missing_remote_branch() {
     let repo = temp_dir_path("sync-skip-missing-branch");
@@ -511,3 +643,48 @@ fn sync_store_repository_skips_missing_remote_branch() {
     let _ = fs::remove_dir_all(&repo);
     let _ = fs::remove_dir_all(&remote);
 }
+
+#[test]
+fn local_only_work_includes_excluded_files_stashes_and_other_branches() {
+    let repo = temp_dir_path("local-only-source");
+    let remote = temp_dir_path("local-only-remote.git");
+    let clone = temp_dir_path("local-only-clone");
+    init_repo(&repo).expect("initialize repo");
+    commit_file(&repo, "secret.txt", "one\n", "Initial commit").expect("create commit");
+    init_bare_repo(&remote).expect("initialize bare repo");
+    git(
+        &repo,
+        &[
+            "push",
+            remote.to_string_lossy().as_ref(),
+            "HEAD:refs/heads/main",
+        ],
+    )
+    .expect("push main");
+    clone_repo(&remote, &clone).expect("clone remote");
+    let root = clone.to_string_lossy().to_string();
+    assert_eq!(store_git_has_local_only_work(&root), Ok(false));
+
+    write_file(&clone.join("video.mp4"), "large\n").expect("write excluded file");
+    write_file(
+        &clone.join(".git").join("info").join("exclude"),
+        "/video.mp4\n",
+    )
+    .expect("exclude file");
+    assert_eq!(store_git_has_local_only_work(&root), Ok(true));
+    fs::remove_file(clone.join("video.mp4")).expect("remove excluded file");
+
+    write_file(&clone.join("secret.txt"), "two\n").expect("change file");
+    git(&clone, &["stash"]).expect("stash change");
+    assert_eq!(store_git_has_local_only_work(&root), Ok(true));
+    git(&clone, &["stash", "drop"]).expect("drop stash");
+
+    git(&clone, &["checkout", "-q", "-b", "draft"]).expect("create branch");
+    commit_file(&clone, "draft.txt", "draft\n", "Draft commit").expect("commit on branch");
+    git(&clone, &["checkout", "-q", "main"]).expect("switch back");
+    assert_eq!(store_git_has_local_only_work(&root), Ok(true));
+
+    let _ = fs::remove_dir_all(&repo);
+    let _ = fs::remove_dir_all(&remote);
+    let _ = fs::remove_dir_all(&clone);
+}
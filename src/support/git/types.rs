@@ -20,3 +20,17 @@ pub struct StoreGitRepositoryStatus {
     pub has_incoming_commits: bool,
     pub remotes: Vec<GitRemote>,
 }
+
+// Incoming copies of entries that were added or changed on both sides and
+// kept next to the local entry, as store-relative paths.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StoreGitSyncSummary {
+    pub conflict_copies: Vec<String>,
+}
+
+// How to get a store syncing again after the remote history was rewritten.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreGitRecovery {
+    RebaseOntoRemote,
+    ForcePushWithLease,
+}
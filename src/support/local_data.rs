@@ -0,0 +1,171 @@
+use crate::backend::clear_runtime_secret_state;
+use crate::password::draft::password_drafts_dir;
+use crate::support::activity::activity_log_path;
+use crate::support::crash::crash_report_dir;
+use crate::support::git::{
+    store_git_has_local_only_work, store_git_repository_status, StoreGitHead,
+};
+use crate::support::paths::app_cache_dir;
+use crate::support::sync_history::store_sync_times_path;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// What Keycord keeps on this device besides its settings and the stores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalData {
+    Caches,
+    Drafts,
+    History,
+}
+
+impl LocalData {
+    pub const ALL: [Self; 3] = [Self::Caches, Self::Drafts, Self::History];
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Self::Caches => "Caches",
+            Self::Drafts => "Drafts",
+            Self::History => "Activity and history",
+        }
+    }
+
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Caches => {
+                "Downloaded updates, remote store previews, and items and keys kept in memory."
+            }
+            Self::Drafts => "Unsaved changes kept for items.",
+            Self::History => "The activity log, sync times, and crash reports.",
+        }
+    }
+
+    fn paths(self) -> Vec<PathBuf> {
+        match self {
+            Self::Caches => app_cache_dir().into_iter().collect(),
+            Self::Drafts => password_drafts_dir().into_iter().collect(),
+            Self::History => [
+                activity_log_path(),
+                store_sync_times_path(),
+                crash_report_dir(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        }
+    }
+}
+
+// Symlinks are counted and removed as links, so nothing outside the
+// folder is touched.
+fn path_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path).map_or(0, |entries| {
+        entries
+            .flatten()
+            .map(|entry| path_size(&entry.path()))
+            .sum()
+    })
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    let result = match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+        Err(err) => Err(err),
+    };
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+pub fn local_data_size(data: LocalData) -> u64 {
+    data.paths().iter().map(|path| path_size(path)).sum()
+}
+
+pub fn clear_local_data(data: LocalData) -> Result<(), String> {
+    for path in data.paths() {
+        remove_path(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+    }
+    if data == LocalData::Caches {
+        // Opened items and unlocked keys are kept in memory for a while.
+        clear_runtime_secret_state();
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RemovableStoreClone {
+    pub store: String,
+    pub root: String,
+    pub bytes: u64,
+}
+
+// Only clones that a remote has everything of are offered, so removing the
+// folder loses nothing: no uncommitted changes, no unpushed commits on any
+// branch, no stashes, and no ignored files.
+fn store_clone_is_removable(root: &str) -> bool {
+    store_git_repository_status(root).is_ok_and(|status| {
+        status.has_repository
+            && matches!(status.head, StoreGitHead::Branch(_))
+            && !status.remotes.is_empty()
+            && !status.dirty
+            && !status.has_outgoing_commits
+    }) && store_git_has_local_only_work(root).is_ok_and(|found| !found)
+}
+
+// `stores` pairs each configured store with its expanded folder.
+pub fn removable_store_clones(stores: &[(String, String)]) -> Vec<RemovableStoreClone> {
+    stores
+        .iter()
+        .filter(|(_, root)| store_clone_is_removable(root))
+        .map(|(store, root)| RemovableStoreClone {
+            store: store.clone(),
+            root: root.clone(),
+            bytes: path_size(Path::new(root)),
+        })
+        .collect()
+}
+
+pub fn remove_store_clone(root: &str) -> Result<(), String> {
+    // Checked again in case something changed since the list was shown.
+    if !store_clone_is_removable(root) {
+        return Err(format!("'{root}' has changes that aren't on a remote."));
+    }
+    remove_path(Path::new(root)).map_err(|err| format!("{root}: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{path_size, remove_path};
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn local_data_folders_are_measured_and_removed_whole() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        let root = std::env::temp_dir().join(format!("keycord-local-data-{nanos}"));
+        fs::create_dir_all(root.join("drafts").join("item")).expect("create folders");
+        fs::write(root.join("drafts").join("item").join("draft"), "12345").expect("write draft");
+        fs::write(root.join("activity.log"), "123").expect("write log");
+
+        assert_eq!(path_size(&root), 8);
+        assert_eq!(path_size(&root.join("missing")), 0);
+
+        remove_path(&root.join("drafts")).expect("remove folder");
+        remove_path(&root.join("activity.log")).expect("remove file");
+        remove_path(&root.join("missing")).expect("missing paths are fine");
+        assert_eq!(path_size(&root), 0);
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
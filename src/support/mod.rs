@@ -1,17 +1,33 @@
 pub mod actions;
+pub mod activity;
+pub mod app_pin;
 pub mod background;
+pub mod crash;
 pub mod file_picker;
+#[cfg(target_os = "linux")]
+pub mod fuzzy;
 pub mod git;
 pub mod hardening;
+pub mod local_data;
+#[cfg(target_os = "linux")]
+pub mod network;
 pub mod object_data;
 pub mod pass_import;
+pub mod paths;
+pub mod portable;
+pub mod process;
+pub mod remote_desktop;
 pub mod runtime;
+pub mod secrets;
 pub mod secure_fs;
 #[cfg(feature = "legacy-compat")]
 pub mod startup;
+pub mod startup_timing;
+pub mod sync_history;
 #[cfg(all(target_os = "linux", feature = "setup"))]
 pub mod theme;
 pub mod toml_safety;
 pub mod ui;
 pub mod uri;
 pub mod validation;
+pub mod webdav;
@@ -0,0 +1,46 @@
+use adw::gio::{self, prelude::*};
+
+const NETWORK_ERROR_MARKERS: [&str; 7] = [
+    "could not resolve host",
+    "failed to resolve address",
+    "temporary failure in name resolution",
+    "network is unreachable",
+    "could not connect to server",
+    "connection timed out",
+    "no route to host",
+];
+
+pub fn network_monitor() -> gio::NetworkMonitor {
+    gio::NetworkMonitor::default()
+}
+
+pub fn network_is_available() -> bool {
+    network_monitor().is_network_available()
+}
+
+// Git and curl word these differently per version, so this only looks for
+// the parts that stay the same.
+pub fn is_network_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    NETWORK_ERROR_MARKERS
+        .iter()
+        .any(|marker| message.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_network_error;
+
+    #[test]
+    fn resolver_and_connection_failures_count_as_network_errors() {
+        assert!(is_network_error(
+            "fatal: unable to access 'https://example.com/store.git/': Could not resolve host: example.com"
+        ));
+        assert!(is_network_error(
+            "ssh: Could not resolve hostname example.com: Temporary failure in name resolution"
+        ));
+        assert!(!is_network_error(
+            "Commit or discard local changes before syncing this store."
+        ));
+    }
+}
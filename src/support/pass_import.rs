@@ -1,8 +1,26 @@
-use crate::logging::{run_command_output, run_command_with_input, CommandLogOptions};
+use crate::backend::{read_password_entry, rename_password_entry};
+use crate::logging::{log_error, run_command_output, run_command_with_input, CommandLogOptions};
+use crate::password::entry_files::{
+    collect_password_entry_paths, label_from_password_entry_path, normalize_password_entry_label,
+    PasswordEntryWalkOptions,
+};
+use crate::password::file::{
+    parse_structured_pass_lines, pass_file_website, structured_username_value,
+};
 use crate::preferences::Preferences;
 use crate::support::runtime::require_host_command_features;
 use secrecy::{ExposeSecret, SecretString};
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::path::Path;
 use std::process::{Command, Output};
+use url::Url;
+use zeroize::Zeroizing;
+
+// The full public suffix list is large, so only the common second-level
+// domains under two-letter country codes, such as `co.uk`, are recognized.
+const COUNTRY_SECOND_LEVEL_DOMAINS: [&str; 8] =
+    ["ac", "co", "com", "edu", "gov", "ne", "net", "org"];
 
 #[derive(Clone, Debug)]
 pub struct PassImportRequest {
@@ -11,6 +29,7 @@ pub struct PassImportRequest {
     pub source_path: Option<String>,
     pub source_password: SecretString,
     pub target_path: Option<String>,
+    pub organize_by_site: bool,
 }
 
 impl PartialEq for PassImportRequest {
@@ -20,6 +39,7 @@ impl PartialEq for PassImportRequest {
             && self.source_path == other.source_path
             && self.source_password.expose_secret() == other.source_password.expose_secret()
             && self.target_path == other.target_path
+            && self.organize_by_site == other.organize_by_site
     }
 }
 
@@ -126,8 +146,123 @@ pub fn available_pass_import_sources() -> Result<Vec<String>, String> {
     }
 }
 
-pub fn run_pass_import(request: &PassImportRequest) -> Result<(), String> {
+fn registrable_domain(host: &str) -> Option<String> {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if host.is_empty() || host.contains(':') || host.starts_with('[') {
+        return None;
+    }
+    if host.parse::<IpAddr>().is_ok() {
+        return Some(host);
+    }
+    let labels = host.split('.').collect::<Vec<_>>();
+    let kept = match labels.as_slice() {
+        [_, .., second, country]
+            if labels.len() >= 3
+                && country.len() == 2
+                && COUNTRY_SECOND_LEVEL_DOMAINS.contains(second) =>
+        {
+            3
+        }
+        _ => 2,
+    };
+    Some(labels[labels.len().saturating_sub(kept)..].join("."))
+}
+
+// A user name becomes one path segment, so slashes and a leading dot,
+// which would add a folder or hide the item, are replaced.
+fn site_folder_segment(text: &str) -> Option<String> {
+    let segment = text.trim().replace('/', "-");
+    let segment = segment.trim_start_matches('.');
+    (!segment.is_empty()).then(|| segment.to_string())
+}
+
+// `web/<site>/<username>`, or the item's own name when it has no user
+// name. Items without a website stay where the importer put them.
+fn site_folder_label(contents: &str, label: &str, website_folder: &str) -> Option<String> {
+    let website = Url::parse(&pass_file_website(contents)?).ok()?;
+    let site = registrable_domain(website.host_str()?)?;
+    let (_, lines) = parse_structured_pass_lines(contents);
+    let name = structured_username_value(&lines)
+        .as_deref()
+        .and_then(site_folder_segment)
+        .or_else(|| site_folder_segment(label.rsplit('/').next().unwrap_or(label)))?;
+    let website_folder = normalize_password_entry_label(website_folder);
+    Some(if website_folder.is_empty() {
+        format!("{site}/{name}")
+    } else {
+        format!("{website_folder}/{site}/{name}")
+    })
+}
+
+// Two accounts with the same user name on one site keep both items, as
+// `alice`, `alice-2`, and so on.
+fn unused_label(label: &str, taken: &HashSet<String>) -> String {
+    if !taken.contains(label) {
+        return label.to_string();
+    }
+    (2..)
+        .map(|number| format!("{label}-{number}"))
+        .find(|candidate| !taken.contains(candidate))
+        .unwrap_or_else(|| label.to_string())
+}
+
+fn store_entry_labels(store_root: &str) -> HashSet<String> {
+    let root = Path::new(store_root);
+    collect_password_entry_paths(root, PasswordEntryWalkOptions::default())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|path| label_from_password_entry_path(root, path))
+        .collect()
+}
+
+fn organize_imported_entries(store_root: &str, labels_before: &HashSet<String>) -> usize {
+    let website_folder = Preferences::new().new_item_website_folder();
+    let mut taken = store_entry_labels(store_root);
+    let mut imported = taken.difference(labels_before).cloned().collect::<Vec<_>>();
+    imported.sort();
+
+    let mut sorted = 0;
+    for label in imported {
+        let contents = match read_password_entry(store_root, &label) {
+            Ok(contents) => Zeroizing::new(contents),
+            Err(err) => {
+                log_error(format!("Failed to read imported item '{label}': {err}"));
+                continue;
+            }
+        };
+        let Some(site_label) = site_folder_label(&contents, &label, &website_folder) else {
+            continue;
+        };
+        if site_label == label {
+            continue;
+        }
+
+        taken.remove(&label);
+        let new_label = unused_label(&site_label, &taken);
+        match rename_password_entry(store_root, &label, &new_label) {
+            Ok(()) => {
+                taken.insert(new_label);
+                sorted += 1;
+            }
+            Err(err) => {
+                log_error(format!(
+                    "Failed to move imported item '{label}' to '{new_label}': {err}"
+                ));
+                taken.insert(label);
+            }
+        }
+    }
+    sorted
+}
+
+// Returns how many imported items were sorted into site folders.
+pub fn run_pass_import(request: &PassImportRequest) -> Result<usize, String> {
     require_host_command_features()?;
+    let labels_before = if request.organize_by_site {
+        store_entry_labels(&request.store_root)
+    } else {
+        HashSet::new()
+    };
     let stdin = pass_import_stdin(request.source_password.expose_secret());
     let output = run_store_pass_command_with_input(
         &request.store_root,
@@ -145,20 +280,26 @@ pub fn run_pass_import(request: &PassImportRequest) -> Result<(), String> {
         },
     )?;
 
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(sensitive_command_error("pass import failed", &output))
+    if !output.status.success() {
+        return Err(sensitive_command_error("pass import failed", &output));
     }
+    if !request.organize_by_site {
+        return Ok(0);
+    }
+    Ok(organize_imported_entries(
+        &request.store_root,
+        &labels_before,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        normalize_optional_text, parse_import_sources, pass_import_stdin, sensitive_command_error,
-        strip_ansi_escape_sequences,
+        normalize_optional_text, parse_import_sources, pass_import_stdin, registrable_domain,
+        sensitive_command_error, site_folder_label, strip_ansi_escape_sequences, unused_label,
     };
     use secrecy::ExposeSecret;
+    use std::collections::HashSet;
     use std::process::Command;
 
     #[test]
@@ -189,6 +330,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn imported_items_are_sorted_by_site_and_user_name() {
+        assert_eq!(
+            registrable_domain("login.example.com"),
+            Some("example.com".into())
+        );
+        assert_eq!(
+            registrable_domain("www.bbc.co.uk"),
+            Some("bbc.co.uk".into())
+        );
+        assert_eq!(
+            registrable_domain("shop.example.de"),
+            Some("example.de".into())
+        );
+        assert_eq!(
+            registrable_domain("192.168.1.1"),
+            Some("192.168.1.1".into())
+        );
+        assert_eq!(
+            site_folder_label(
+                "secret\nusername: alice\nurl: https://accounts.google.com/login\n",
+                "import/Google",
+                "web",
+            ),
+            Some("web/google.com/alice".to_string())
+        );
+        assert_eq!(
+            site_folder_label("secret\nurl: example.org\n", "import/.Example", ""),
+            Some("example.org/Example".to_string())
+        );
+        assert_eq!(
+            site_folder_label("secret\nusername: alice\n", "import/mail", "web"),
+            None
+        );
+    }
+
+    #[test]
+    fn sorted_items_never_overwrite_each_other() {
+        let taken = HashSet::from([
+            "web/example.com/alice".to_string(),
+            "web/example.com/alice-2".to_string(),
+        ]);
+        assert_eq!(
+            unused_label("web/example.com/alice", &taken),
+            "web/example.com/alice-3"
+        );
+        assert_eq!(
+            unused_label("web/example.com/bob", &taken),
+            "web/example.com/bob"
+        );
+    }
+
     #[test]
     fn pass_import_stdin_keeps_password_exact_and_ends_with_newline() {
         assert_eq!(pass_import_stdin("").expose_secret(), "\n");
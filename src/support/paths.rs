@@ -0,0 +1,105 @@
+use crate::support::portable::portable_root;
+use std::path::PathBuf;
+
+const APP_DIR_NAME: &str = env!("CARGO_PKG_NAME");
+
+// Per-device files follow the XDG base directories, so a sandbox that
+// points them somewhere else moves everything Keycord writes with them:
+// preferences go in the config folder, history that should survive a
+// cache wipe in the state folder, and files that can be fetched or built
+// again in the cache folder. In portable mode they all live in the
+// portable folder instead.
+fn app_state_dir_from(
+    state_home: Option<PathBuf>,
+    home: Option<PathBuf>,
+    fallback: Option<PathBuf>,
+) -> Option<PathBuf> {
+    let base = state_home
+        .filter(|path| path.is_absolute())
+        .or_else(|| home.map(|home| home.join(".local").join("state")))
+        .or(fallback)?;
+    Some(base.join(APP_DIR_NAME))
+}
+
+pub fn app_state_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("state"));
+    }
+    if cfg!(target_os = "linux") {
+        app_state_dir_from(
+            std::env::var_os("XDG_STATE_HOME").map(PathBuf::from),
+            dirs_next::home_dir(),
+            dirs_next::data_local_dir(),
+        )
+    } else {
+        app_state_dir_from(None, None, dirs_next::data_local_dir())
+    }
+}
+
+pub fn app_state_path(name: &str) -> Option<PathBuf> {
+    Some(app_state_dir()?.join(name))
+}
+
+pub fn app_cache_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("cache"));
+    }
+    Some(
+        dirs_next::cache_dir()
+            .or_else(dirs_next::data_local_dir)?
+            .join(APP_DIR_NAME),
+    )
+}
+
+pub fn app_cache_path(name: &str) -> Option<PathBuf> {
+    Some(app_cache_dir()?.join(name))
+}
+
+// Keys the integrated backend keeps on this device.
+pub fn app_data_dir() -> Option<PathBuf> {
+    if let Some(root) = portable_root() {
+        return Some(root.join("data"));
+    }
+    Some(dirs_next::data_local_dir()?.join(APP_DIR_NAME))
+}
+
+// The preferences file sits directly in the config folder, as it always
+// has, so existing setups keep their settings.
+pub fn app_config_file() -> PathBuf {
+    let file_name = format!("{APP_DIR_NAME}.toml");
+    if let Some(root) = portable_root() {
+        return root.join("config").join(file_name);
+    }
+    dirs_next::config_dir().map_or_else(|| PathBuf::from(&file_name), |dir| dir.join(&file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::app_state_dir_from;
+    use std::path::PathBuf;
+
+    #[test]
+    fn state_dir_prefers_the_xdg_state_folder() {
+        assert_eq!(
+            app_state_dir_from(
+                Some(PathBuf::from("/state")),
+                Some(PathBuf::from("/home/alice")),
+                None,
+            ),
+            Some(PathBuf::from("/state/keycord"))
+        );
+        assert_eq!(
+            app_state_dir_from(
+                Some(PathBuf::from("relative")),
+                Some(PathBuf::from("/home/alice")),
+                None,
+            ),
+            Some(PathBuf::from("/home/alice/.local/state/keycord"))
+        );
+        assert_eq!(
+            app_state_dir_from(None, None, Some(PathBuf::from("/data"))),
+            Some(PathBuf::from("/data/keycord"))
+        );
+        assert_eq!(app_state_dir_from(None, None, None), None);
+    }
+}
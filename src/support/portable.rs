@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const PORTABLE_DIR_ENV: &str = "KEYCORD_PORTABLE_DIR";
+const PORTABLE_DIR_NAME: &str = "keycord-portable";
+pub const PORTABLE_STORE_DIR_NAME: &str = "store";
+
+static PORTABLE_ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// A folder named `keycord-portable` next to the executable turns portable
+// mode on, and `KEYCORD_PORTABLE_DIR` picks another folder. A relative
+// folder is taken from the executable's folder, so it follows the drive.
+fn portable_root_from(env_dir: Option<PathBuf>, exe_dir: Option<&Path>) -> Option<PathBuf> {
+    match env_dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        Some(dir) if dir.is_absolute() => Some(dir),
+        Some(dir) => Some(exe_dir?.join(dir)),
+        None => {
+            let dir = exe_dir?.join(PORTABLE_DIR_NAME);
+            dir.is_dir().then_some(dir)
+        }
+    }
+}
+
+pub fn portable_root() -> Option<&'static Path> {
+    PORTABLE_ROOT
+        .get_or_init(|| {
+            let exe = std::env::current_exe().ok();
+            portable_root_from(
+                std::env::var_os(PORTABLE_DIR_ENV).map(PathBuf::from),
+                exe.as_deref().and_then(Path::parent),
+            )
+        })
+        .as_deref()
+}
+
+pub fn is_portable() -> bool {
+    portable_root().is_some()
+}
+
+fn resolved_in(root: Option<&Path>, path: &str) -> String {
+    match root {
+        Some(root) if Path::new(path).is_relative() && !path.starts_with(['~', '$']) => {
+            root.join(path).to_string_lossy().into_owned()
+        }
+        _ => path.to_string(),
+    }
+}
+
+fn relative_in(root: Option<&Path>, path: &str) -> String {
+    let Some(relative) = root.and_then(|root| Path::new(path).strip_prefix(root).ok()) else {
+        return path.to_string();
+    };
+    if relative.as_os_str().is_empty() {
+        return path.to_string();
+    }
+    relative.to_string_lossy().into_owned()
+}
+
+// Relative store paths are read from the portable folder, wherever the
+// drive is mounted on this machine.
+pub fn resolve_portable_path(path: &str) -> String {
+    resolved_in(portable_root(), path)
+}
+
+// Stores on the portable drive are saved relative to it, so the next
+// machine finds them under its own mount point.
+pub fn portable_relative_path(path: &str) -> String {
+    relative_in(portable_root(), path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{portable_root_from, relative_in, resolved_in};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn the_portable_folder_comes_from_the_environment_or_the_executable() {
+        let exe_dir = Path::new("/media/usb");
+        assert_eq!(
+            portable_root_from(Some(PathBuf::from("/data/keycord")), Some(exe_dir)),
+            Some(PathBuf::from("/data/keycord"))
+        );
+        assert_eq!(
+            portable_root_from(Some(PathBuf::from("data")), Some(exe_dir)),
+            Some(PathBuf::from("/media/usb/data"))
+        );
+        assert_eq!(
+            portable_root_from(None, Some(Path::new("/nonexistent-keycord-test"))),
+            None
+        );
+        assert_eq!(portable_root_from(Some(PathBuf::new()), None), None);
+    }
+
+    #[test]
+    fn store_paths_inside_the_portable_folder_are_kept_relative() {
+        let root = Some(Path::new("/media/usb/keycord-portable"));
+        assert_eq!(
+            relative_in(root, "/media/usb/keycord-portable/store"),
+            "store"
+        );
+        assert_eq!(
+            relative_in(root, "/home/alice/.password-store"),
+            "/home/alice/.password-store"
+        );
+        assert_eq!(
+            resolved_in(root, "store"),
+            "/media/usb/keycord-portable/store"
+        );
+        assert_eq!(resolved_in(root, "/srv/store"), "/srv/store");
+        assert_eq!(resolved_in(root, "~/.password-store"), "~/.password-store");
+        assert_eq!(resolved_in(None, "store"), "store");
+    }
+}
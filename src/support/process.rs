@@ -0,0 +1,119 @@
+use std::io;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const CHILD_POLL_INTERVAL: Duration = Duration::from_millis(50);
+const CHILD_TERMINATE_GRACE: Duration = Duration::from_secs(2);
+
+// A command with a time limit gets its own process group, so stopping it
+// also stops whatever it started, such as gpg and pinentry under `pass`.
+pub fn prepare_command_timeout(cmd: &mut Command, timeout: Option<Duration>) {
+    #[cfg(unix)]
+    if timeout.is_some() {
+        cmd.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = (cmd, timeout);
+}
+
+pub const COMMAND_TIMEOUT_MESSAGE: &str = "The command took too long and was stopped.";
+
+fn command_timeout_error(timeout: Duration) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::TimedOut,
+        format!("{COMMAND_TIMEOUT_MESSAGE} ({}s)", timeout.as_secs()),
+    )
+}
+
+// Waits like `Child::wait`, but stops the command once `timeout` has passed.
+// The child is always reaped before this returns, so nothing lingers as a
+// zombie.
+pub fn wait_for_child(child: &mut Child, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return child.wait();
+    };
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            stop_child(child);
+            return Err(command_timeout_error(timeout));
+        }
+        thread::sleep(CHILD_POLL_INTERVAL);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn signal_child_group(child: &Child, signal: libc::c_int) {
+    let Ok(pid) = libc::pid_t::try_from(child.id()) else {
+        return;
+    };
+    let _ = unsafe { libc::kill(-pid, signal) };
+}
+
+// SIGTERM first, so flatpak-spawn can pass it on to the host, then SIGKILL
+// for anything that ignores it.
+fn stop_child(child: &mut Child) {
+    #[cfg(target_os = "linux")]
+    {
+        signal_child_group(child, libc::SIGTERM);
+        let deadline = Instant::now() + CHILD_TERMINATE_GRACE;
+        while Instant::now() < deadline {
+            if matches!(child.try_wait(), Ok(Some(_))) {
+                signal_child_group(child, libc::SIGKILL);
+                return;
+            }
+            thread::sleep(CHILD_POLL_INTERVAL);
+        }
+        signal_child_group(child, libc::SIGKILL);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    let _ = CHILD_TERMINATE_GRACE;
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{prepare_command_timeout, wait_for_child};
+    use std::io;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn commands_within_the_limit_finish_normally() {
+        let timeout = Some(Duration::from_secs(5));
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "exit 3"]);
+        prepare_command_timeout(&mut cmd, timeout);
+        let mut child = cmd.spawn().expect("command should start");
+
+        let status = wait_for_child(&mut child, timeout).expect("command should finish");
+
+        assert_eq!(status.code(), Some(3));
+    }
+
+    #[test]
+    fn hung_commands_are_stopped_after_the_limit() {
+        let timeout = Some(Duration::from_millis(100));
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", "sleep 30 & wait"]);
+        prepare_command_timeout(&mut cmd, timeout);
+        let mut child = cmd.spawn().expect("command should start");
+        let started = Instant::now();
+
+        let err = wait_for_child(&mut child, timeout).expect_err("command should time out");
+
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(child.try_wait().is_ok());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::logging::log_error;
+use adw::gio::{self, BusType, DBusCallFlags, DBusConnection, DBusSignalFlags, SignalSubscription};
+use adw::glib::variant::ObjectPath;
+use adw::glib::{ToVariant, Variant, VariantDict};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+const PORTAL_BUS_NAME: &str = "org.freedesktop.portal.Desktop";
+const PORTAL_OBJECT_PATH: &str = "/org/freedesktop/portal/desktop";
+const PORTAL_REQUEST_PATH_PREFIX: &str = "/org/freedesktop/portal/desktop/request/";
+const REMOTE_DESKTOP_INTERFACE: &str = "org.freedesktop.portal.RemoteDesktop";
+const REQUEST_INTERFACE: &str = "org.freedesktop.portal.Request";
+const SESSION_INTERFACE: &str = "org.freedesktop.portal.Session";
+const KEYBOARD_DEVICE: u32 = 1;
+const KEY_RELEASED: u32 = 0;
+const KEY_PRESSED: u32 = 1;
+const RESPONSE_SUCCESS: u32 = 0;
+const RESPONSE_CANCELLED: u32 = 1;
+// X keysyms for Unicode characters outside Latin-1 are the code point with
+// this bit set.
+const UNICODE_KEYSYM_OFFSET: u32 = 0x0100_0000;
+const TAB_KEYSYM: u32 = 0xff09;
+const RETURN_KEYSYM: u32 = 0xff0d;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RemoteDesktopError {
+    Cancelled,
+    Failed(String),
+}
+
+thread_local! {
+    static NEXT_PORTAL_TOKEN: Cell<u32> = const { Cell::new(0) };
+}
+
+fn next_portal_token() -> String {
+    let token = NEXT_PORTAL_TOKEN.with(|next| {
+        let token = next.get();
+        next.set(token.wrapping_add(1));
+        token
+    });
+    format!("keycord{}_{token}", std::process::id())
+}
+
+// The portal answers each request with a signal on a path made from our
+// bus name and the token we picked, so the answer is watched for before
+// the request is sent.
+fn portal_request_path(unique_name: &str, token: &str) -> String {
+    let sender = unique_name.trim_start_matches(':').replace('.', "_");
+    format!("{PORTAL_REQUEST_PATH_PREFIX}{sender}/{token}")
+}
+
+fn portal_response(parameters: &Variant) -> Result<VariantDict, RemoteDesktopError> {
+    match parameters.get::<(u32, VariantDict)>() {
+        Some((RESPONSE_SUCCESS, results)) => Ok(results),
+        Some((RESPONSE_CANCELLED, _)) => Err(RemoteDesktopError::Cancelled),
+        _ => Err(RemoteDesktopError::Failed(
+            "The remote desktop portal ended the request.".to_string(),
+        )),
+    }
+}
+
+pub fn keysym_for_char(ch: char) -> i32 {
+    let code = u32::from(ch);
+    let keysym = match ch {
+        '\t' => TAB_KEYSYM,
+        '\n' => RETURN_KEYSYM,
+        ' '..='~' | '\u{a0}'..='\u{ff}' => code,
+        _ => UNICODE_KEYSYM_OFFSET | code,
+    };
+    keysym as i32
+}
+
+type PortalResponseHandler = Box<dyn FnOnce(Result<VariantDict, RemoteDesktopError>)>;
+
+fn portal_request(
+    connection: &DBusConnection,
+    method: &str,
+    arguments: Vec<Variant>,
+    options: &[(&str, Variant)],
+    on_response: impl FnOnce(Result<VariantDict, RemoteDesktopError>) + 'static,
+) {
+    let Some(unique_name) = connection.unique_name() else {
+        on_response(Err(RemoteDesktopError::Failed(
+            "The session bus connection has no name.".to_string(),
+        )));
+        return;
+    };
+    let token = next_portal_token();
+    let handler = Rc::new(RefCell::new(Some(
+        Box::new(on_response) as PortalResponseHandler
+    )));
+    let subscription = Rc::new(RefCell::new(None::<SignalSubscription>));
+
+    let handler_for_signal = handler.clone();
+    let subscription_for_signal = subscription.clone();
+    subscription.replace(Some(connection.subscribe_to_signal(
+        Some(PORTAL_BUS_NAME),
+        Some(REQUEST_INTERFACE),
+        Some("Response"),
+        Some(&portal_request_path(&unique_name, &token)),
+        None,
+        DBusSignalFlags::NONE,
+        move |signal| {
+            subscription_for_signal.borrow_mut().take();
+            if let Some(handler) = handler_for_signal.borrow_mut().take() {
+                handler(portal_response(signal.parameters));
+            }
+        },
+    )));
+
+    let request_options = VariantDict::new(None);
+    request_options.insert("handle_token", token.as_str());
+    for (key, value) in options {
+        request_options.insert_value(key, value);
+    }
+    let mut arguments = arguments;
+    arguments.push(request_options.end());
+
+    connection.call(
+        Some(PORTAL_BUS_NAME),
+        PORTAL_OBJECT_PATH,
+        REMOTE_DESKTOP_INTERFACE,
+        method,
+        Some(&Variant::tuple_from_iter(arguments)),
+        None,
+        DBusCallFlags::NONE,
+        -1,
+        None::<&gio::Cancellable>,
+        move |result| {
+            let Err(err) = result else {
+                return;
+            };
+            subscription.borrow_mut().take();
+            if let Some(handler) = handler.borrow_mut().take() {
+                handler(Err(RemoteDesktopError::Failed(err.to_string())));
+            }
+        },
+    );
+}
+
+// A session that can only press keys. It is never saved for later, so the
+// desktop asks for permission every time one starts, and it closes when
+// dropped.
+pub struct RemoteKeyboard {
+    connection: DBusConnection,
+    session: ObjectPath,
+}
+
+impl RemoteKeyboard {
+    fn notify_keysym(&self, keysym: i32, state: u32) {
+        let arguments = Variant::tuple_from_iter([
+            self.session.to_variant(),
+            VariantDict::new(None).end(),
+            keysym.to_variant(),
+            state.to_variant(),
+        ]);
+        self.connection.call(
+            Some(PORTAL_BUS_NAME),
+            PORTAL_OBJECT_PATH,
+            REMOTE_DESKTOP_INTERFACE,
+            "NotifyKeyboardKeysym",
+            Some(&arguments),
+            None,
+            DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+            |result| {
+                if let Err(err) = result {
+                    log_error(format!("Failed to send a key press: {err}"));
+                }
+            },
+        );
+    }
+
+    pub fn type_keysyms(&self, keysyms: &[i32]) {
+        for keysym in keysyms {
+            self.notify_keysym(*keysym, KEY_PRESSED);
+            self.notify_keysym(*keysym, KEY_RELEASED);
+        }
+    }
+}
+
+impl Drop for RemoteKeyboard {
+    fn drop(&mut self) {
+        self.connection.call(
+            Some(PORTAL_BUS_NAME),
+            &self.session,
+            SESSION_INTERFACE,
+            "Close",
+            None,
+            None,
+            DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+            |result| {
+                if let Err(err) = result {
+                    log_error(format!("Failed to close the remote desktop session: {err}"));
+                }
+            },
+        );
+    }
+}
+
+fn select_keyboard_and_start(
+    keyboard: RemoteKeyboard,
+    on_started: impl FnOnce(Result<RemoteKeyboard, RemoteDesktopError>) + 'static,
+) {
+    let connection = keyboard.connection.clone();
+    let session = keyboard.session.to_variant();
+    portal_request(
+        &connection,
+        "SelectDevices",
+        vec![session.clone()],
+        &[("types", KEYBOARD_DEVICE.to_variant())],
+        move |result| {
+            if let Err(err) = result {
+                on_started(Err(err));
+                return;
+            }
+            let connection = keyboard.connection.clone();
+            portal_request(
+                &connection,
+                "Start",
+                vec![session, "".to_variant()],
+                &[],
+                move |result| {
+                    let devices = result.map(|results| {
+                        results.lookup::<u32>("devices").ok().flatten().unwrap_or(0)
+                    });
+                    on_started(match devices {
+                        Ok(devices) if devices & KEYBOARD_DEVICE != 0 => Ok(keyboard),
+                        Ok(_) => Err(RemoteDesktopError::Cancelled),
+                        Err(err) => Err(err),
+                    });
+                },
+            );
+        },
+    );
+}
+
+pub fn start_remote_keyboard(
+    on_started: impl FnOnce(Result<RemoteKeyboard, RemoteDesktopError>) + 'static,
+) {
+    let connection = match gio::bus_get_sync(BusType::Session, None::<&gio::Cancellable>) {
+        Ok(connection) => connection,
+        Err(err) => {
+            on_started(Err(RemoteDesktopError::Failed(err.to_string())));
+            return;
+        }
+    };
+    let session_token = next_portal_token();
+    let connection_for_session = connection.clone();
+    portal_request(
+        &connection,
+        "CreateSession",
+        Vec::new(),
+        &[("session_handle_token", session_token.to_variant())],
+        move |result| {
+            let session = result.and_then(|results| {
+                results
+                    .lookup::<String>("session_handle")
+                    .ok()
+                    .flatten()
+                    .and_then(|handle| ObjectPath::try_from(handle).ok())
+                    .ok_or_else(|| {
+                        RemoteDesktopError::Failed(
+                            "The remote desktop portal didn't open a session.".to_string(),
+                        )
+                    })
+            });
+            match session {
+                Ok(session) => select_keyboard_and_start(
+                    RemoteKeyboard {
+                        connection: connection_for_session,
+                        session,
+                    },
+                    on_started,
+                ),
+                Err(err) => on_started(Err(err)),
+            }
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keysym_for_char, portal_request_path};
+
+    #[test]
+    fn request_paths_are_built_from_the_bus_name_and_token() {
+        assert_eq!(
+            portal_request_path(":1.42", "keycord7_0"),
+            "/org/freedesktop/portal/desktop/request/1_42/keycord7_0"
+        );
+    }
+
+    #[test]
+    fn characters_become_latin1_or_unicode_keysyms() {
+        assert_eq!(keysym_for_char('a'), 0x61);
+        assert_eq!(keysym_for_char('~'), 0x7e);
+        assert_eq!(keysym_for_char('é'), 0xe9);
+        assert_eq!(keysym_for_char('€'), 0x0100_20ac);
+        assert_eq!(keysym_for_char('\t'), 0xff09);
+    }
+}
@@ -0,0 +1,62 @@
+//! Decrypted item contents stay in `Zeroizing` buffers until they are handed
+//! to GTK or the clipboard, so our own copies are wiped when they are dropped.
+//! GTK keeps its own copy of widget text, which we can't wipe.
+
+use adw::gtk::TextBuffer;
+use adw::prelude::*;
+use zeroize::{Zeroize, Zeroizing};
+
+pub fn string_from_secret_utf8(bytes: Vec<u8>) -> Result<String, String> {
+    String::from_utf8(bytes).map_err(|err| {
+        let message = err.utf8_error().to_string();
+        err.into_bytes().zeroize();
+        message
+    })
+}
+
+// The raw editor text. Reads come back in a `Zeroizing` copy that is wiped
+// when dropped, but the buffer itself is GTK's and isn't wiped, not even by
+// `clear`.
+#[derive(Clone)]
+pub struct SecretTextBuffer {
+    buffer: TextBuffer,
+}
+
+impl SecretTextBuffer {
+    pub const fn new(buffer: TextBuffer) -> Self {
+        Self { buffer }
+    }
+
+    pub fn contents(&self) -> Zeroizing<String> {
+        let (start, end) = self.buffer.bounds();
+        Zeroizing::new(self.buffer.text(&start, &end, false).to_string())
+    }
+
+    pub fn set_contents(&self, contents: &str) {
+        self.buffer.set_text(contents);
+    }
+
+    pub fn clear(&self) {
+        self.buffer.set_text("");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::string_from_secret_utf8;
+
+    #[test]
+    fn decoded_secrets_keep_their_text() {
+        assert_eq!(
+            string_from_secret_utf8(b"hunter2\nusername: alice".to_vec()),
+            Ok("hunter2\nusername: alice".to_string())
+        );
+    }
+
+    #[test]
+    fn invalid_utf8_is_reported_without_the_secret() {
+        let err = string_from_secret_utf8(b"hunter\xff".to_vec()).expect_err("invalid UTF-8");
+        assert!(err.starts_with("invalid utf-8"));
+        assert!(!err.contains("hunter"));
+    }
+}
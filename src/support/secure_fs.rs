@@ -56,6 +56,20 @@ pub fn write_private_file(path: &Path, contents: impl AsRef<[u8]>) -> io::Result
     write_file_atomically(path, contents.as_ref(), AtomicWriteMode::Private)
 }
 
+pub fn append_private_file(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        ensure_private_dir(parent)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+    #[cfg(unix)]
+    options.mode(PRIVATE_FILE_MODE);
+    let mut file = options.open(path)?;
+    set_private_file_permissions(path)?;
+    file.write_all(contents.as_ref())
+}
+
 #[cfg(unix)]
 fn open_temp_file(path: &Path, mode: AtomicWriteMode) -> io::Result<File> {
     let mut options = OpenOptions::new();
@@ -388,7 +402,7 @@ impl<T> Drop for LocalBuffer<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::{write_atomic_file, write_private_file};
+    use super::{append_private_file, write_atomic_file, write_private_file};
     use std::fs;
     #[cfg(unix)]
     use std::os::unix::fs::PermissionsExt;
@@ -443,6 +457,22 @@ mod tests {
         let _ = fs::remove_dir_all(dir);
     }
 
+    #[test]
+    fn private_appends_keep_earlier_lines() {
+        let dir = temp_test_dir();
+        let path = dir.join("state").join("activity.log");
+
+        append_private_file(&path, b"first\n").expect("append first line");
+        append_private_file(&path, b"second\n").expect("append second line");
+
+        assert_eq!(
+            fs::read_to_string(&path).expect("read appended file"),
+            "first\nsecond\n"
+        );
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
     #[cfg(unix)]
     #[test]
     fn private_writes_force_private_permissions() {
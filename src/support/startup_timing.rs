@@ -0,0 +1,79 @@
+use crate::logging::log_info;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug)]
+struct StartupTiming {
+    started: Instant,
+    last_phase: Instant,
+}
+
+static STARTUP_TIMING: Mutex<Option<StartupTiming>> = Mutex::new(None);
+
+fn startup_phase_message(phase: &str, phase_elapsed: Duration, total_elapsed: Duration) -> String {
+    format!(
+        "Startup: {phase} took {} ms ({} ms since launch).",
+        phase_elapsed.as_millis(),
+        total_elapsed.as_millis()
+    )
+}
+
+fn record_startup_phase(phase: &str, finish: bool) {
+    let mut timing = STARTUP_TIMING
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let Some(current) = timing.as_mut() else {
+        return;
+    };
+
+    let now = Instant::now();
+    let message = startup_phase_message(
+        phase,
+        now.duration_since(current.last_phase),
+        now.duration_since(current.started),
+    );
+    current.last_phase = now;
+    if finish {
+        *timing = None;
+    }
+    drop(timing);
+    log_info(message);
+}
+
+pub fn begin_startup_timing() {
+    let now = Instant::now();
+    let mut timing = STARTUP_TIMING
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    *timing = Some(StartupTiming {
+        started: now,
+        last_phase: now,
+    });
+}
+
+pub fn log_startup_phase(phase: &str) {
+    record_startup_phase(phase, false);
+}
+
+// Later window activations don't log phases once the first window is shown.
+pub fn finish_startup_timing(phase: &str) {
+    record_startup_phase(phase, true);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::startup_phase_message;
+    use std::time::Duration;
+
+    #[test]
+    fn startup_phase_messages_include_phase_and_total_time() {
+        assert_eq!(
+            startup_phase_message(
+                "building the main window",
+                Duration::from_millis(42),
+                Duration::from_millis(180),
+            ),
+            "Startup: building the main window took 42 ms (180 ms since launch)."
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::backend::{subscribe_store_events, StoreEvent};
+use crate::logging::log_error;
+use crate::password::entry_files::canonical_store_root;
+use crate::support::paths::app_state_path;
+use crate::support::secure_fs::write_private_file;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const STORE_SYNC_TIMES_FILE_NAME: &str = "store-syncs";
+
+// Sync times are per device, so they live in the state folder instead of
+// inside the stores.
+pub fn store_sync_times_path() -> Option<PathBuf> {
+    app_state_path(STORE_SYNC_TIMES_FILE_NAME)
+}
+
+fn store_sync_key(root: &str) -> String {
+    canonical_store_root(Path::new(root))
+        .to_string_lossy()
+        .into_owned()
+}
+
+// Each line is `<unix seconds>\t<store root>`.
+fn parse_store_sync_times(contents: &str) -> Vec<(i64, &str)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (seconds, root) = line.split_once('\t')?;
+            Some((seconds.trim().parse().ok()?, root))
+        })
+        .filter(|(_, root)| !root.is_empty())
+        .collect()
+}
+
+fn store_sync_time_in(contents: &str, key: &str) -> Option<i64> {
+    parse_store_sync_times(contents)
+        .into_iter()
+        .find_map(|(seconds, root)| (root == key).then_some(seconds))
+}
+
+fn updated_store_sync_times(contents: &str, key: &str, seconds: i64) -> String {
+    let mut updated = format!("{seconds}\t{key}\n");
+    for (seconds, root) in parse_store_sync_times(contents) {
+        if root != key {
+            updated.push_str(&format!("{seconds}\t{root}\n"));
+        }
+    }
+    updated
+}
+
+fn record_store_sync(root: &str) {
+    let Some(path) = store_sync_times_path() else {
+        return;
+    };
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|elapsed| i64::try_from(elapsed.as_secs()).ok())
+        .unwrap_or_default();
+    let contents = fs::read_to_string(&path).unwrap_or_default();
+    let updated = updated_store_sync_times(&contents, &store_sync_key(root), seconds);
+    if let Err(err) = write_private_file(&path, updated) {
+        log_error(format!("Failed to save the sync time of '{root}': {err}"));
+    }
+}
+
+// Every finished sync is announced as a store event, wherever it was
+// started, so the time is kept in one place.
+pub fn track_store_syncs() {
+    subscribe_store_events(|event| {
+        if let StoreEvent::Synced { store } = event {
+            record_store_sync(store);
+        }
+    });
+}
+
+pub fn last_store_sync(root: &str) -> Option<i64> {
+    let contents = fs::read_to_string(store_sync_times_path()?).ok()?;
+    store_sync_time_in(&contents, &store_sync_key(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{store_sync_time_in, updated_store_sync_times};
+
+    #[test]
+    fn recording_a_sync_replaces_the_earlier_time_of_that_store() {
+        let contents = updated_store_sync_times("", "/stores/work", 100);
+        let contents = updated_store_sync_times(&contents, "/stores/home", 200);
+        let contents = updated_store_sync_times(&contents, "/stores/work", 300);
+
+        assert_eq!(contents, "300\t/stores/work\n200\t/stores/home\n");
+        assert_eq!(store_sync_time_in(&contents, "/stores/work"), Some(300));
+        assert_eq!(store_sync_time_in(&contents, "/stores/home"), Some(200));
+        assert_eq!(store_sync_time_in(&contents, "/stores/other"), None);
+    }
+
+    #[test]
+    fn broken_sync_time_lines_are_skipped() {
+        let contents = "soon\t/stores/work\n42\n7\t\n12\t/stores/home\n";
+
+        assert_eq!(store_sync_time_in(contents, "/stores/work"), None);
+        assert_eq!(store_sync_time_in(contents, "/stores/home"), Some(12));
+        assert_eq!(
+            updated_store_sync_times(contents, "/stores/work", 1),
+            "1\t/stores/work\n12\t/stores/home\n"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use crate::i18n::gettext;
 use crate::logging::log_error;
 use crate::preferences::Preferences;
-use adw::glib::{object::IsA, Propagation};
+use adw::glib::{object::IsA, DateTime, Propagation};
 use adw::gtk::{
     gdk, Align, Box as GtkBox, Button, CheckButton, DirectionType, EventControllerKey, Image,
     ListBox, ListBoxRow, Orientation, PolicyType, PropagationPhase, ScrolledWindow, SearchEntry,
@@ -698,6 +698,12 @@ pub fn dialog_content_shell(
     shell
 }
 
+pub fn local_time_text(seconds: i64) -> String {
+    DateTime::from_unix_local(seconds)
+        .and_then(|time| time.format("%Y-%m-%d %H:%M"))
+        .map_or_else(|_| seconds.to_string(), |text| text.to_string())
+}
+
 pub fn flat_icon_button(icon_name: &str) -> Button {
     let button = Button::from_icon_name(icon_name);
     button.add_css_class("flat");
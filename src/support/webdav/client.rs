@@ -0,0 +1,197 @@
+use super::{WebDavCredentials, WebDavDownload};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::{HeaderName, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+use url::Url;
+use zeroize::Zeroizing;
+
+const DESTINATION: HeaderName = HeaderName::from_static("destination");
+const OVERWRITE: HeaderName = HeaderName::from_static("overwrite");
+// Not a synced extension, so a leftover from an interrupted upload is never
+// mistaken for an item.
+const UPLOAD_SUFFIX: &str = ".keycord-upload";
+
+pub(super) struct WebDavClient {
+    client: Client,
+    base: Url,
+    credentials: Option<WebDavCredentials>,
+}
+
+fn http_error(context: &str) -> impl FnOnce(reqwest::Error) -> String + '_ {
+    move |err| format!("Failed to {context}: {err}")
+}
+
+fn status_error(context: &str, response: &Response) -> String {
+    format!(
+        "Failed to {context}: the server answered {}.",
+        response.status()
+    )
+}
+
+impl WebDavClient {
+    pub(super) fn new(base: Url, credentials: Option<WebDavCredentials>) -> Result<Self, String> {
+        let client = Client::builder()
+            .user_agent(concat!(
+                env!("CARGO_PKG_NAME"),
+                "/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .timeout(Duration::from_secs(30))
+            .build()
+            .map_err(http_error("build WebDAV client"))?;
+        Ok(Self {
+            client,
+            base,
+            credentials,
+        })
+    }
+
+    fn resource_url(&self, relative: &str) -> Result<Url, String> {
+        let mut url = self.base.clone();
+        url.path_segments_mut()
+            .map_err(|()| "The WebDAV folder URL can't hold files.".to_string())?
+            .pop_if_empty()
+            .extend(relative.split('/').filter(|segment| !segment.is_empty()));
+        Ok(url)
+    }
+
+    fn request(&self, method: Method, relative: &str) -> Result<RequestBuilder, String> {
+        let request = self.client.request(method, self.resource_url(relative)?);
+        Ok(match &self.credentials {
+            Some(credentials) => {
+                request.basic_auth(&credentials.username, Some(credentials.password.as_str()))
+            }
+            None => request,
+        })
+    }
+
+    pub(super) fn get(&self, relative: &str) -> Result<Option<WebDavDownload>, String> {
+        let context = format!("download {relative}");
+        let response = self
+            .request(Method::GET, relative)?
+            .send()
+            .map_err(http_error(&context))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(status_error(&context, &response));
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let bytes = response.bytes().map_err(http_error(&context))?;
+        Ok(Some(WebDavDownload {
+            bytes: Zeroizing::new(bytes.to_vec()),
+            etag,
+        }))
+    }
+
+    // The file is uploaded under a temporary name and moved over the old one,
+    // so an interrupted upload never leaves a half-written item behind.
+    pub(super) fn put(&self, relative: &str, bytes: Vec<u8>) -> Result<(), String> {
+        self.ensure_parent_collections(relative)?;
+        let upload = format!("{relative}{UPLOAD_SUFFIX}");
+        let context = format!("upload {relative}");
+        let response = self
+            .request(Method::PUT, &upload)?
+            .body(bytes)
+            .send()
+            .map_err(http_error(&context))?;
+        if !response.status().is_success() {
+            return Err(status_error(&context, &response));
+        }
+
+        let moved = self.move_into_place(&upload, relative);
+        if moved.is_err() {
+            let _ = self.delete(&upload);
+        }
+        moved
+    }
+
+    fn move_into_place(&self, from: &str, to: &str) -> Result<(), String> {
+        let context = format!("upload {to}");
+        let method = Method::from_bytes(b"MOVE").map_err(|err| err.to_string())?;
+        let response = self
+            .request(method, from)?
+            .header(DESTINATION, self.resource_url(to)?.as_str())
+            .header(OVERWRITE, "T")
+            .send()
+            .map_err(http_error(&context))?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(status_error(&context, &response))
+        }
+    }
+
+    pub(super) fn put_if_unchanged(
+        &self,
+        relative: &str,
+        bytes: Vec<u8>,
+        etag: Option<&str>,
+    ) -> Result<(), String> {
+        let context = format!("upload {relative}");
+        let request = self.request(Method::PUT, relative)?.body(bytes);
+        let request = match etag {
+            Some(etag) => request.header(IF_MATCH, etag),
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+        let response = request.send().map_err(http_error(&context))?;
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Err(
+                "Another device synced this WebDAV folder at the same time. Sync again."
+                    .to_string(),
+            );
+        }
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(status_error(&context, &response))
+        }
+    }
+
+    pub(super) fn delete(&self, relative: &str) -> Result<(), String> {
+        let context = format!("delete {relative}");
+        let response = self
+            .request(Method::DELETE, relative)?
+            .send()
+            .map_err(http_error(&context))?;
+        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(status_error(&context, &response))
+        }
+    }
+
+    fn ensure_parent_collections(&self, relative: &str) -> Result<(), String> {
+        let Some((parent, _)) = relative.rsplit_once('/') else {
+            return Ok(());
+        };
+
+        let mkcol = Method::from_bytes(b"MKCOL").map_err(|err| err.to_string())?;
+        let mut collection = String::new();
+        for segment in parent.split('/') {
+            if !collection.is_empty() {
+                collection.push('/');
+            }
+            collection.push_str(segment);
+
+            let context = format!("create folder {collection}");
+            let response = self
+                .request(mkcol.clone(), &format!("{collection}/"))?
+                .send()
+                .map_err(http_error(&context))?;
+            if !response.status().is_success()
+                && response.status() != StatusCode::METHOD_NOT_ALLOWED
+            {
+                return Err(status_error(&context, &response));
+            }
+        }
+        Ok(())
+    }
+}
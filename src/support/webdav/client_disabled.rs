@@ -0,0 +1,31 @@
+use super::{WebDavCredentials, WebDavDownload};
+use url::Url;
+
+pub(super) enum WebDavClient {}
+
+impl WebDavClient {
+    pub(super) fn new(_base: Url, _credentials: Option<WebDavCredentials>) -> Result<Self, String> {
+        Err("WebDAV sync is not available in this build.".to_string())
+    }
+
+    pub(super) fn get(&self, _relative: &str) -> Result<Option<WebDavDownload>, String> {
+        match *self {}
+    }
+
+    pub(super) fn put(&self, _relative: &str, _bytes: Vec<u8>) -> Result<(), String> {
+        match *self {}
+    }
+
+    pub(super) fn put_if_unchanged(
+        &self,
+        _relative: &str,
+        _bytes: Vec<u8>,
+        _etag: Option<&str>,
+    ) -> Result<(), String> {
+        match *self {}
+    }
+
+    pub(super) fn delete(&self, _relative: &str) -> Result<(), String> {
+        match *self {}
+    }
+}
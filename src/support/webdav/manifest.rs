@@ -0,0 +1,318 @@
+use crate::support::toml_safety::{parse_toml_with_limits, WEBDAV_MANIFEST_TOML_LIMITS};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub(super) const REMOTE_MANIFEST_NAME: &str = ".keycord-webdav.toml";
+const MANIFEST_VERSION: u32 = 1;
+const RECIPIENTS_FILE_NAME: &str = ".gpg-id";
+const SYNCED_EXTENSIONS: [&str; 2] = ["gpg", "keycord"];
+const CONFLICT_SUFFIX: &str = ".conflict";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct WebDavManifest {
+    #[serde(default)]
+    pub(super) version: u32,
+    #[serde(default)]
+    pub(super) files: BTreeMap<String, String>,
+}
+
+impl WebDavManifest {
+    pub(super) const fn new(files: BTreeMap<String, String>) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            files,
+        }
+    }
+
+    pub(super) fn parse(contents: &str) -> Result<Self, String> {
+        let manifest: Self =
+            parse_toml_with_limits(contents, WEBDAV_MANIFEST_TOML_LIMITS, "WebDAV manifest")?;
+        if manifest.version > MANIFEST_VERSION {
+            return Err(format!(
+                "The WebDAV folder was synced by a newer version (manifest version {}).",
+                manifest.version
+            ));
+        }
+        if let Some(path) = manifest
+            .files
+            .keys()
+            .find(|path| !is_synced_relative_path(path))
+        {
+            return Err(format!(
+                "The WebDAV manifest lists an unexpected file: {path}"
+            ));
+        }
+        Ok(manifest)
+    }
+
+    pub(super) fn render(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum WebDavSyncStep {
+    Upload(String),
+    Download(String),
+    DeleteRemote(String),
+    DeleteLocal(String),
+    KeepConflictCopy(String),
+}
+
+pub(super) fn is_synced_relative_path(path: &str) -> bool {
+    if path.is_empty() || path.starts_with('/') || path.contains('\\') {
+        return false;
+    }
+
+    let mut segments = path.split('/').peekable();
+    while let Some(segment) = segments.next() {
+        if segment.is_empty() || segment == "." || segment == ".." || segment == ".git" {
+            return false;
+        }
+        if segments.peek().is_none() {
+            return segment == RECIPIENTS_FILE_NAME
+                || Path::new(segment)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| SYNCED_EXTENSIONS.contains(&extension));
+        }
+    }
+    false
+}
+
+pub(super) fn file_digest(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in Sha256::digest(bytes) {
+        let _ = write!(&mut hex, "{byte:02x}");
+    }
+    hex
+}
+
+pub(super) fn scan_local_store(root: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut files = BTreeMap::new();
+    let walker = WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || entry.file_name() != ".git");
+    for entry in walker {
+        let entry = entry.map_err(|err| err.to_string())?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !is_synced_relative_path(&relative) {
+            continue;
+        }
+
+        let bytes = fs::read(entry.path()).map_err(|err| err.to_string())?;
+        files.insert(relative, file_digest(&bytes));
+    }
+    Ok(files)
+}
+
+pub(super) fn conflict_copy_path(path: &str) -> String {
+    let (dir, name) = path
+        .rsplit_once('/')
+        .map_or(("", path), |(dir, name)| (dir, name));
+    let (stem, extension) = name
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.is_empty())
+        .map_or((name, None), |(stem, extension)| (stem, Some(extension)));
+    let name = extension.map_or_else(
+        || format!("{stem}{CONFLICT_SUFFIX}"),
+        |extension| format!("{stem}{CONFLICT_SUFFIX}.{extension}"),
+    );
+    if dir.is_empty() {
+        name
+    } else {
+        format!("{dir}/{name}")
+    }
+}
+
+pub(super) fn plan_webdav_sync(
+    local: &BTreeMap<String, String>,
+    remote: &BTreeMap<String, String>,
+    base: &BTreeMap<String, String>,
+) -> Vec<WebDavSyncStep> {
+    let paths = local
+        .keys()
+        .chain(remote.keys())
+        .chain(base.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut steps = Vec::new();
+    for path in paths {
+        let local = local.get(path);
+        let remote = remote.get(path);
+        let base = base.get(path);
+        if local == remote {
+            continue;
+        }
+
+        let step = if remote == base {
+            if local.is_some() {
+                WebDavSyncStep::Upload(path.clone())
+            } else {
+                WebDavSyncStep::DeleteRemote(path.clone())
+            }
+        } else if local == base {
+            if remote.is_some() {
+                WebDavSyncStep::Download(path.clone())
+            } else {
+                WebDavSyncStep::DeleteLocal(path.clone())
+            }
+        } else if local.is_none() {
+            WebDavSyncStep::Download(path.clone())
+        } else if remote.is_none() {
+            WebDavSyncStep::Upload(path.clone())
+        } else {
+            WebDavSyncStep::KeepConflictCopy(path.clone())
+        };
+        steps.push(step);
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        conflict_copy_path, is_synced_relative_path, plan_webdav_sync, scan_local_store,
+        WebDavManifest, WebDavSyncStep,
+    };
+    use std::collections::BTreeMap;
+    use std::fs;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn files(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(path, digest)| ((*path).to_string(), (*digest).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn only_encrypted_entries_and_recipient_files_are_synced() {
+        assert!(is_synced_relative_path("github.gpg"));
+        assert!(is_synced_relative_path("work/.gpg-id"));
+        assert!(is_synced_relative_path(
+            "work/.github.attachments/codes.pdf.gpg"
+        ));
+        assert!(is_synced_relative_path("fido/key.keycord"));
+        assert!(!is_synced_relative_path("notes.txt"));
+        assert!(!is_synced_relative_path(".git/objects/ab.gpg"));
+        assert!(!is_synced_relative_path("../escape.gpg"));
+        assert!(!is_synced_relative_path("/absolute.gpg"));
+        assert!(!is_synced_relative_path(".keycord-webdav.toml"));
+    }
+
+    #[test]
+    fn plan_pushes_local_changes_and_pulls_remote_changes() {
+        let base = files(&[
+            ("a.gpg", "1"),
+            ("b.gpg", "1"),
+            ("c.gpg", "1"),
+            ("d.gpg", "1"),
+        ]);
+        let local = files(&[
+            ("a.gpg", "2"),
+            ("b.gpg", "1"),
+            ("d.gpg", "1"),
+            ("new.gpg", "1"),
+        ]);
+        let remote = files(&[("a.gpg", "1"), ("b.gpg", "3"), ("c.gpg", "1")]);
+
+        assert_eq!(
+            plan_webdav_sync(&local, &remote, &base),
+            vec![
+                WebDavSyncStep::Upload("a.gpg".to_string()),
+                WebDavSyncStep::Download("b.gpg".to_string()),
+                WebDavSyncStep::DeleteRemote("c.gpg".to_string()),
+                WebDavSyncStep::DeleteLocal("d.gpg".to_string()),
+                WebDavSyncStep::Upload("new.gpg".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_keeps_both_sides_of_a_conflict() {
+        let base = files(&[
+            ("edited.gpg", "1"),
+            ("deleted.gpg", "1"),
+            ("removed.gpg", "1"),
+        ]);
+        let local = files(&[
+            ("edited.gpg", "2"),
+            ("removed.gpg", "2"),
+            ("first.gpg", "1"),
+        ]);
+        let remote = files(&[
+            ("edited.gpg", "3"),
+            ("deleted.gpg", "3"),
+            ("first.gpg", "2"),
+        ]);
+
+        assert_eq!(
+            plan_webdav_sync(&local, &remote, &base),
+            vec![
+                WebDavSyncStep::Download("deleted.gpg".to_string()),
+                WebDavSyncStep::KeepConflictCopy("edited.gpg".to_string()),
+                WebDavSyncStep::KeepConflictCopy("first.gpg".to_string()),
+                WebDavSyncStep::Upload("removed.gpg".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn conflict_copies_stay_next_to_the_original_entry() {
+        assert_eq!(
+            conflict_copy_path("work/github.gpg"),
+            "work/github.conflict.gpg"
+        );
+        assert_eq!(conflict_copy_path("work/.gpg-id"), "work/.gpg-id.conflict");
+    }
+
+    #[test]
+    fn manifests_round_trip_and_reject_unexpected_paths() {
+        let manifest = WebDavManifest::new(files(&[("work/github.gpg", "abc")]));
+        let rendered = manifest.render().expect("render manifest");
+
+        assert_eq!(WebDavManifest::parse(&rendered), Ok(manifest));
+        assert!(WebDavManifest::parse("version = 1\n[files]\n\"../x.gpg\" = \"abc\"\n").is_err());
+        assert!(WebDavManifest::parse("version = 99\n").is_err());
+    }
+
+    #[test]
+    fn local_scan_hashes_synced_files_only() {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time")
+            .as_nanos();
+        let store = std::env::temp_dir().join(format!("keycord-webdav-scan-{nanos}"));
+        fs::create_dir_all(store.join("work")).expect("create store");
+        fs::create_dir_all(store.join(".git")).expect("create git folder");
+        fs::write(store.join("work").join("github.gpg"), b"secret").expect("write entry");
+        fs::write(store.join(".gpg-id"), b"ABC\n").expect("write recipients");
+        fs::write(store.join(".git").join("x.gpg"), b"x").expect("write git file");
+        fs::write(store.join("notes.txt"), b"x").expect("write stray file");
+
+        let scanned = scan_local_store(&store).expect("scan store");
+        assert_eq!(
+            scanned.keys().cloned().collect::<Vec<_>>(),
+            vec![".gpg-id".to_string(), "work/github.gpg".to_string()]
+        );
+
+        let _ = fs::remove_dir_all(store);
+    }
+}
@@ -0,0 +1,21 @@
+#[cfg(all(target_os = "linux", feature = "webdav"))]
+mod client;
+#[cfg(not(all(target_os = "linux", feature = "webdav")))]
+#[path = "client_disabled.rs"]
+mod client;
+mod manifest;
+mod sync;
+
+pub use sync::{sync_store_webdav, validate_webdav_folder_url, WebDavSyncSummary};
+use zeroize::Zeroizing;
+
+struct WebDavCredentials {
+    username: String,
+    password: Zeroizing<String>,
+}
+
+#[cfg_attr(not(all(target_os = "linux", feature = "webdav")), allow(dead_code))]
+struct WebDavDownload {
+    bytes: Zeroizing<Vec<u8>>,
+    etag: Option<String>,
+}
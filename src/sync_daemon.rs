@@ -0,0 +1,331 @@
+use crate::i18n::gettext;
+use crate::logging::{log_error, log_info};
+use crate::support::background::spawn_result_task;
+use crate::support::network::{network_is_available, network_monitor};
+use crate::window::{run_sync_operation, GitOperationResult};
+
+use adw::gio::{self, BusNameOwnerFlags, BusType, DBusConnection, DBusInterfaceInfo, DBusNodeInfo};
+use adw::glib::{self, ExitCode, MainLoop, Variant};
+use adw::prelude::*;
+
+use std::cell::{Cell, RefCell};
+use std::ffi::OsString;
+use std::rc::Rc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SYNC_DAEMON_BUS_NAME: &str = env!("SYNC_DAEMON_BUS_NAME");
+const SYNC_DAEMON_OBJECT_PATH: &str = env!("SYNC_DAEMON_OBJECT_PATH");
+const SYNC_DAEMON_INTERFACE: &str = "io.github.noobping.keycord.SyncDaemon";
+const DEFAULT_SYNC_INTERVAL_MINUTES: u64 = 15;
+const SYNC_DAEMON_XML: &str = r#"
+<node>
+  <interface name="io.github.noobping.keycord.SyncDaemon">
+    <method name="SyncNow">
+      <arg type="b" name="started" direction="out" />
+    </method>
+    <method name="GetStatus">
+      <arg type="b" name="syncing" direction="out" />
+      <arg type="x" name="last_sync" direction="out" />
+      <arg type="s" name="result" direction="out" />
+      <arg type="s" name="message" direction="out" />
+    </method>
+    <signal name="SyncFinished">
+      <arg type="s" name="result" />
+      <arg type="s" name="message" />
+    </signal>
+  </interface>
+</node>
+"#;
+
+pub(crate) fn is_sync_daemon_command(args: &[OsString]) -> bool {
+    args.get(1).is_some_and(|arg| arg == "--daemon")
+}
+
+// `--daemon` syncs every 15 minutes; `--daemon --interval 5` picks another
+// number of minutes.
+fn sync_daemon_interval(args: &[OsString]) -> Result<Duration, String> {
+    let mut minutes = DEFAULT_SYNC_INTERVAL_MINUTES;
+    let mut args = args.iter().skip(2);
+    while let Some(arg) = args.next() {
+        if arg != "--interval" {
+            return Err(format!("Unknown daemon option: {}.", arg.to_string_lossy()));
+        }
+        minutes = args
+            .next()
+            .and_then(|value| value.to_str())
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .filter(|minutes| *minutes > 0)
+            .ok_or_else(|| "The sync interval must be a whole number of minutes.".to_string())?;
+    }
+    // The main loop counts the wait in 32-bit milliseconds, a little over
+    // 49 days, and would silently cut off anything longer.
+    minutes
+        .checked_mul(60_000)
+        .filter(|millis| u32::try_from(*millis).is_ok())
+        .map(Duration::from_millis)
+        .ok_or_else(|| "The sync interval is too long.".to_string())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SyncDaemonResult {
+    Never,
+    Synced,
+    Notice,
+    Failed,
+}
+
+impl SyncDaemonResult {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::Synced => "synced",
+            Self::Notice => "notice",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+struct SyncDaemonService {
+    interface_info: DBusInterfaceInfo,
+    connection: RefCell<Option<DBusConnection>>,
+    syncing: Cell<bool>,
+    waiting_for_network: Cell<bool>,
+    last_sync: Cell<i64>,
+    last_result: Cell<SyncDaemonResult>,
+    last_message: RefCell<String>,
+}
+
+pub(crate) fn run(args: &[OsString]) -> ExitCode {
+    crate::i18n::init();
+    let interval = match sync_daemon_interval(args) {
+        Ok(interval) => interval,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let node_info = match DBusNodeInfo::for_xml(SYNC_DAEMON_XML) {
+        Ok(node_info) => node_info,
+        Err(err) => {
+            log_error(format!("Failed to parse sync daemon D-Bus XML: {err}"));
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(interface_info) = node_info.lookup_interface(SYNC_DAEMON_INTERFACE) else {
+        log_error("Sync daemon interface metadata is missing.".to_string());
+        return ExitCode::FAILURE;
+    };
+
+    let main_loop = MainLoop::new(None, false);
+    let service = Rc::new(SyncDaemonService::new(interface_info));
+    let service_for_bus = service.clone();
+    let loop_for_failure = main_loop.clone();
+    let owner_id = gio::bus_own_name(
+        BusType::Session,
+        SYNC_DAEMON_BUS_NAME,
+        BusNameOwnerFlags::NONE,
+        move |connection, _name| {
+            if let Err(err) = service_for_bus.register(&connection) {
+                log_error(format!("Failed to export sync daemon object: {err}"));
+                loop_for_failure.quit();
+            }
+        },
+        |_connection, name| {
+            log_info(format!("Sync daemon bus name acquired: {name}."));
+        },
+        {
+            let main_loop = main_loop.clone();
+            move |_connection, name| {
+                log_info(format!(
+                    "Sync daemon bus name released: {name}. Is another daemon running?"
+                ));
+                main_loop.quit();
+            }
+        },
+    );
+
+    service.sync_now();
+    let network_handler = {
+        let service = service.clone();
+        network_monitor().connect_network_changed(move |_, available| {
+            if available && service.waiting_for_network.replace(false) {
+                service.sync_now();
+            }
+        })
+    };
+    {
+        let service = service.clone();
+        glib::timeout_add_local(interval, move || {
+            service.sync_now();
+            glib::ControlFlow::Continue
+        });
+    }
+
+    log_info(format!(
+        "Sync daemon syncing every {} minutes on {SYNC_DAEMON_BUS_NAME}{SYNC_DAEMON_OBJECT_PATH}.",
+        interval.as_secs() / 60
+    ));
+    main_loop.run();
+    network_monitor().disconnect(network_handler);
+    gio::bus_unown_name(owner_id);
+    ExitCode::SUCCESS
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|elapsed| i64::try_from(elapsed.as_secs()).ok())
+        .unwrap_or_default()
+}
+
+impl SyncDaemonService {
+    fn new(interface_info: DBusInterfaceInfo) -> Self {
+        Self {
+            interface_info,
+            connection: RefCell::new(None),
+            syncing: Cell::new(false),
+            waiting_for_network: Cell::new(false),
+            last_sync: Cell::new(0),
+            last_result: Cell::new(SyncDaemonResult::Never),
+            last_message: RefCell::new(String::new()),
+        }
+    }
+
+    fn register(self: &Rc<Self>, connection: &DBusConnection) -> Result<(), glib::Error> {
+        let service = self.clone();
+        let _registration_id = connection
+            .register_object(SYNC_DAEMON_OBJECT_PATH, &self.interface_info)
+            .method_call(
+                move |_connection,
+                      _sender,
+                      _object_path,
+                      _interface_name,
+                      method_name,
+                      _parameters,
+                      invocation| match method_name {
+                    "SyncNow" => {
+                        let started = service.sync_now();
+                        invocation.return_value(Some(&(started,).to_variant()));
+                    }
+                    "GetStatus" => {
+                        invocation.return_value(Some(&service.status()));
+                    }
+                    _ => {
+                        let message = format!("Unknown sync daemon method: {method_name}.");
+                        log_error(message.clone());
+                        invocation.return_dbus_error(
+                            "org.freedesktop.DBus.Error.UnknownMethod",
+                            &message,
+                        );
+                    }
+                },
+            )
+            .build()?;
+        self.connection.replace(Some(connection.clone()));
+
+        Ok(())
+    }
+
+    fn status(&self) -> Variant {
+        (
+            self.syncing.get(),
+            self.last_sync.get(),
+            self.last_result.get().name(),
+            self.last_message.borrow().as_str(),
+        )
+            .to_variant()
+    }
+
+    // Runs one sync in the background. A sync that is still running is left
+    // alone, so a slow remote never piles up overlapping runs. While offline
+    // the sync waits for the network instead of failing.
+    fn sync_now(self: &Rc<Self>) -> bool {
+        if !network_is_available() {
+            if !self.waiting_for_network.replace(true) {
+                log_info("Offline; the next sync waits for the network.".to_string());
+            }
+            return false;
+        }
+        if self.syncing.replace(true) {
+            return false;
+        }
+
+        let service = self.clone();
+        let service_for_disconnect = self.clone();
+        spawn_result_task(
+            run_sync_operation,
+            move |result| {
+                let (result, message) = match result {
+                    GitOperationResult::Success => (SyncDaemonResult::Synced, String::new()),
+                    GitOperationResult::Notice(message) => {
+                        (SyncDaemonResult::Notice, gettext(message))
+                    }
+                    GitOperationResult::Failed(message) => {
+                        (SyncDaemonResult::Failed, gettext(&message))
+                    }
+                    GitOperationResult::NeedsRecovery(_) => (
+                        SyncDaemonResult::Failed,
+                        gettext("The remote history was rewritten. Open Keycord to sync again."),
+                    ),
+                };
+                service.finish_sync(result, message);
+            },
+            move || {
+                service_for_disconnect.finish_sync(
+                    SyncDaemonResult::Failed,
+                    gettext("Syncing stopped unexpectedly."),
+                );
+            },
+        );
+        true
+    }
+
+    fn finish_sync(&self, result: SyncDaemonResult, message: String) {
+        self.syncing.set(false);
+        self.last_sync.set(unix_now());
+        self.last_result.set(result);
+        self.last_message.replace(message.clone());
+
+        let Some(connection) = self.connection.borrow().clone() else {
+            return;
+        };
+        if let Err(err) = connection.emit_signal(
+            None,
+            SYNC_DAEMON_OBJECT_PATH,
+            SYNC_DAEMON_INTERFACE,
+            "SyncFinished",
+            Some(&(result.name(), message).to_variant()),
+        ) {
+            log_error(format!("Failed to announce the finished sync: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sync_daemon_interval;
+    use std::ffi::OsString;
+    use std::time::Duration;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn daemon_interval_defaults_to_fifteen_minutes() {
+        assert_eq!(
+            sync_daemon_interval(&args(&["keycord", "--daemon"])),
+            Ok(Duration::from_secs(15 * 60))
+        );
+        assert_eq!(
+            sync_daemon_interval(&args(&["keycord", "--daemon", "--interval", "5"])),
+            Ok(Duration::from_secs(5 * 60))
+        );
+        assert!(sync_daemon_interval(&args(&["keycord", "--daemon", "--interval", "0"])).is_err());
+        assert!(
+            sync_daemon_interval(&args(&["keycord", "--daemon", "--interval", "100000"])).is_err()
+        );
+        assert!(sync_daemon_interval(&args(&["keycord", "--daemon", "--verbose"])).is_err());
+    }
+}
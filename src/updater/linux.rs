@@ -3,6 +3,7 @@ use super::logic::{select_update_release_by, ReleaseCandidate, SelectedRelease};
 use crate::i18n::gettext;
 use crate::logging::log_error;
 use crate::setup::{install_locally, is_current_executable_installed_locally};
+use crate::support::paths::app_cache_path;
 use adw::glib;
 use adw::prelude::*;
 use adw::AlertDialog;
@@ -171,9 +172,9 @@ fn create_update_dir() -> Result<PathBuf, String> {
 }
 
 fn update_staging_root() -> UpdateStagingRoot {
-    if let Some(base) = dirs_next::cache_dir().or_else(dirs_next::data_local_dir) {
+    if let Some(path) = app_cache_path("updates") {
         UpdateStagingRoot {
-            path: base.join(env!("CARGO_PKG_NAME")).join("updates"),
+            path,
             needs_creation: true,
         }
     } else {
@@ -2,6 +2,7 @@ use super::common::{sanitize_filename, DownloadedUpdate};
 use super::logic::{
     select_update_release as select_windows_update_release, ReleaseCandidate, SelectedRelease,
 };
+use crate::support::paths::app_cache_path;
 use std::path::PathBuf;
 
 pub fn supports_updater() -> bool {
@@ -56,11 +57,12 @@ pub fn handle_special_command(_args: &[std::ffi::OsString]) -> Option<adw::gtk::
 }
 
 fn cached_download_path(release: &SelectedRelease) -> PathBuf {
-    let base = dirs_next::cache_dir()
-        .or_else(dirs_next::data_local_dir)
-        .unwrap_or_else(std::env::temp_dir);
-    base.join(env!("CARGO_PKG_NAME"))
-        .join("updates")
+    app_cache_path("updates")
+        .unwrap_or_else(|| {
+            std::env::temp_dir()
+                .join(env!("CARGO_PKG_NAME"))
+                .join("updates")
+        })
         .join(format!(
             "{}-{}",
             release.version,
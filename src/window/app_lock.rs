@@ -0,0 +1,274 @@
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::Preferences;
+use crate::private_key::keyring::{
+    forget_app_pin_hash, remember_app_pin_hash, remembered_app_pin_hash,
+};
+use crate::support::app_pin::{
+    app_pin_is_valid, app_pin_matches, app_pin_retry_delay, hash_app_pin,
+};
+use crate::support::background::spawn_result_task;
+use crate::support::object_data::{cloned_data, set_cloned_data};
+use crate::support::runtime::supports_host_command_features;
+use crate::support::ui::dialog_content_shell;
+use adw::gtk::{Align, Box as GtkBox, Label, Orientation};
+use adw::prelude::*;
+use adw::{glib, ApplicationWindow, Dialog, PasswordEntryRow, PreferencesGroup, PreferencesPage};
+use secrecy::ExposeSecret;
+use std::cell::Cell;
+use std::rc::Rc;
+use zeroize::Zeroizing;
+
+const APP_LOCKED_KEY: &str = "app-pin-locked";
+// What the app PIN preference holds when the hash itself is in the keyring.
+const APP_PIN_IN_KEYRING: &str = "keyring";
+
+// Hashing is slow on purpose and the keyring is another process, so this
+// runs off the main thread. It returns what the preference should hold: the
+// keyring marker, or the hash itself where this platform has no keyring.
+fn store_app_pin_hash(pin: &str) -> Result<String, String> {
+    let hash = hash_app_pin(pin)?;
+    match remember_app_pin_hash(&hash) {
+        Ok(()) => Ok(APP_PIN_IN_KEYRING.to_string()),
+        Err(_) if !supports_host_command_features() => Ok(hash),
+        Err(err) => Err(err),
+    }
+}
+
+fn stored_app_pin_hash(preference: &str) -> Result<Zeroizing<String>, String> {
+    if preference.is_empty() {
+        return Err("No app PIN is saved.".to_string());
+    }
+    if preference != APP_PIN_IN_KEYRING {
+        return Ok(Zeroizing::new(preference.to_string()));
+    }
+    remembered_app_pin_hash()?
+        .map(|hash| Zeroizing::new(hash.expose_secret().to_string()))
+        .ok_or_else(|| "The app PIN is missing from the keyring.".to_string())
+}
+
+// A hash that can't be read never unlocks the window.
+fn check_app_pin(preference: &str, pin: &str) -> Result<bool, String> {
+    let stored = stored_app_pin_hash(preference)?;
+    Ok(app_pin_matches(&stored, pin))
+}
+
+pub fn remove_app_pin() -> Result<(), glib::BoolError> {
+    Preferences::new().set_app_pin_hash(None)?;
+    if let Err(err) = forget_app_pin_hash() {
+        log_error(format!(
+            "Failed to remove the app PIN from the keyring: {err}"
+        ));
+    }
+    Ok(())
+}
+
+fn app_locked_flag(window: &ApplicationWindow) -> Rc<Cell<bool>> {
+    cloned_data(window, APP_LOCKED_KEY).unwrap_or_else(|| {
+        let flag = Rc::new(Cell::new(false));
+        set_cloned_data(window, APP_LOCKED_KEY, flag.clone());
+        flag
+    })
+}
+
+fn new_app_pin_error_message(pin: &str, confirm_pin: &str) -> Option<&'static str> {
+    if !app_pin_is_valid(pin) {
+        return Some("Use at least 4 characters and no spaces.");
+    }
+    if pin != confirm_pin {
+        return Some("The PINs do not match.");
+    }
+    None
+}
+
+fn pin_dialog_error_label() -> Label {
+    let error_label = Label::new(None);
+    error_label.set_halign(Align::Start);
+    error_label.set_wrap(true);
+    error_label.add_css_class("error");
+    error_label.add_css_class("caption");
+    error_label.set_margin_top(6);
+    error_label.set_margin_start(18);
+    error_label.set_margin_end(18);
+    error_label.set_margin_bottom(18);
+    error_label.set_visible(false);
+    error_label
+}
+
+fn pin_dialog(title: &str, rows: &[&PasswordEntryRow], error_label: &Label) -> Dialog {
+    let group = PreferencesGroup::new();
+    for row in rows {
+        group.add(*row);
+        let error_label = error_label.clone();
+        row.connect_changed(move |_| error_label.set_visible(false));
+    }
+    let page = PreferencesPage::new();
+    page.add(&group);
+
+    let content = GtkBox::new(Orientation::Vertical, 0);
+    content.append(&page);
+    content.append(error_label);
+
+    Dialog::builder()
+        .title(gettext(title))
+        .content_height(280)
+        .content_width(520)
+        .follows_content_size(true)
+        .child(&dialog_content_shell(title, None, &content))
+        .build()
+}
+
+// The window stays blank and unusable until the PIN is entered. Private keys
+// were already locked, so the PIN only guards what the window itself shows.
+pub fn lock_window_with_app_pin(window: &ApplicationWindow) {
+    let locked = app_locked_flag(window);
+    if locked.get() || !Preferences::new().has_app_pin() {
+        return;
+    }
+    let Some(content) = window.content() else {
+        return;
+    };
+
+    locked.set(true);
+    content.set_opacity(0.0);
+    content.set_sensitive(false);
+
+    let pin_row = PasswordEntryRow::new();
+    pin_row.set_title(&gettext("App PIN"));
+    pin_row.set_show_apply_button(true);
+    let error_label = pin_dialog_error_label();
+    let dialog = pin_dialog("Keycord is locked", &[&pin_row], &error_label);
+    dialog.set_can_close(false);
+
+    let dialog_for_apply = dialog.clone();
+    let failed_attempts = Rc::new(Cell::new(0_u32));
+    pin_row.connect_apply(move |row| {
+        let pin = Zeroizing::new(row.text().to_string());
+        row.set_text("");
+        row.set_sensitive(false);
+        let preference = Preferences::new().app_pin_hash().unwrap_or_default();
+
+        let row = row.clone();
+        let row_for_disconnect = row.clone();
+        let error_label = error_label.clone();
+        let locked = locked.clone();
+        let content = content.clone();
+        let dialog = dialog_for_apply.clone();
+        let failed_attempts = failed_attempts.clone();
+        spawn_result_task(
+            move || check_app_pin(&preference, &pin),
+            move |result| match result {
+                Ok(true) => {
+                    locked.set(false);
+                    content.set_opacity(1.0);
+                    content.set_sensitive(true);
+                    dialog.force_close();
+                }
+                Ok(false) => {
+                    failed_attempts.set(failed_attempts.get().saturating_add(1));
+                    error_label.set_label(&gettext("Wrong PIN. Try again."));
+                    error_label.set_visible(true);
+                    let row = row.clone();
+                    glib::timeout_add_local_once(
+                        app_pin_retry_delay(failed_attempts.get()),
+                        move || {
+                            row.set_sensitive(true);
+                            let _ = row.grab_focus();
+                        },
+                    );
+                }
+                Err(err) => {
+                    log_error(format!("Failed to read the app PIN: {err}"));
+                    error_label.set_label(&gettext(
+                        "Couldn't read the app PIN. Check that the system keyring is unlocked.",
+                    ));
+                    error_label.set_visible(true);
+                    row.set_sensitive(true);
+                }
+            },
+            move || row_for_disconnect.set_sensitive(true),
+        );
+    });
+
+    dialog.present(Some(window));
+    let _ = pin_row.grab_focus();
+}
+
+pub fn present_app_pin_setup_dialog(
+    window: &ApplicationWindow,
+    on_done: impl Fn(Result<bool, adw::glib::BoolError>) + 'static,
+) {
+    let pin_row = PasswordEntryRow::new();
+    pin_row.set_title(&gettext("New app PIN"));
+    let confirm_row = PasswordEntryRow::new();
+    confirm_row.set_title(&gettext("Confirm app PIN"));
+    confirm_row.set_show_apply_button(true);
+    let error_label = pin_dialog_error_label();
+    let dialog = pin_dialog("Set app PIN", &[&pin_row, &confirm_row], &error_label);
+
+    let saved = Rc::new(Cell::new(false));
+    let on_done = Rc::new(on_done);
+    {
+        let saved = saved.clone();
+        let on_done = on_done.clone();
+        let dialog = dialog.clone();
+        let pin_row = pin_row.clone();
+        confirm_row.connect_apply(move |row| {
+            let pin = pin_row.text();
+            if let Some(message) = new_app_pin_error_message(&pin, &row.text()) {
+                error_label.set_label(&gettext(message));
+                error_label.set_visible(true);
+                return;
+            }
+
+            let pin = Zeroizing::new(pin.to_string());
+            pin_row.set_text("");
+            row.set_text("");
+            saved.set(true);
+            dialog.close();
+            let on_done = on_done.clone();
+            let on_done_for_disconnect = on_done.clone();
+            spawn_result_task(
+                move || store_app_pin_hash(&pin),
+                move |result| {
+                    on_done(
+                        result
+                            .map_err(|err| glib::bool_error!("{err}"))
+                            .and_then(|value| Preferences::new().set_app_pin_hash(Some(&value)))
+                            .map(|()| true),
+                    );
+                },
+                move || {
+                    on_done_for_disconnect(Err(glib::bool_error!("Failed to save the app PIN.")))
+                },
+            );
+        });
+    }
+
+    dialog.connect_closed(move |_| {
+        pin_row.set_text("");
+        confirm_row.set_text("");
+        if !saved.get() {
+            on_done(Ok(false));
+        }
+    });
+    dialog.present(Some(window));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::new_app_pin_error_message;
+
+    #[test]
+    fn new_app_pins_must_be_long_enough_and_confirmed() {
+        assert_eq!(new_app_pin_error_message("2468", "2468"), None);
+        assert_eq!(
+            new_app_pin_error_message("246", "246"),
+            Some("Use at least 4 characters and no spaces.")
+        );
+        assert_eq!(
+            new_app_pin_error_message("2468", "2486"),
+            Some("The PINs do not match.")
+        );
+    }
+}
@@ -1,22 +1,27 @@
-use crate::clipboard::connect_copy_button;
+use crate::clipboard::{
+    connect_copy_button, copy_password_entry_otp_to_clipboard, copy_password_entry_to_clipboard,
+};
 use crate::i18n::gettext;
+use crate::password::attachments::attach_file_to_current_entry;
 use crate::password::list::{
-    clear_password_search, password_list_row_action_kind, toggle_password_list_folder_row,
-    PasswordListActionRowKind,
+    clear_password_search, copy_from_selected_pass_file, password_list_row_action_kind,
+    password_list_row_open_requested, toggle_password_list_folder_row, PasswordListActionRowKind,
 };
-use crate::password::model::OpenPassFile;
+use crate::password::model::{OpenPassFile, PassEntry};
 use crate::password::new_item::{
-    clear_new_password_dialog_error, selected_new_password_store, show_new_password_dialog_error,
-    NewPasswordDialogState,
+    clear_new_password_dialog_error, new_password_dialog_path_problem, selected_new_password_store,
+    show_new_password_dialog_error, NewPasswordDialogState,
 };
 use crate::password::page::{
     add_empty_otp_secret, add_pass_field_from_input, apply_pass_file_template,
     begin_new_password_entry, clean_pass_file, copy_current_otp, copy_current_password,
     copy_current_username, focus_add_pass_field_input, generate_password_entry,
-    import_private_key_from_current_pass_file, open_password_entry_page,
+    import_private_key_from_current_pass_file, open_current_website, open_password_entry_page,
     refresh_apply_template_button, refresh_password_analysis_label, save_current_password_entry,
-    show_raw_pass_file_page, toggle_password_options, PasswordPageState,
+    show_current_password_large_type, show_raw_pass_file_page, toggle_password_options,
+    type_current_password, PasswordPageState,
 };
+use crate::preferences::{PasswordListActivation, Preferences};
 use crate::support::actions::{activate_widget_action, register_window_action};
 use crate::support::object_data::non_null_to_string_option;
 use crate::support::ui::connect_entry_row_apply_button_to_nonempty_text;
@@ -35,6 +40,7 @@ pub(super) fn connect_password_list_activation(
     let overlay = overlay.clone();
     let page_state = page_state.clone();
     list.connect_row_activated(move |list, row| {
+        let open_requested = password_list_row_open_requested(row);
         if toggle_password_list_folder_row(list, row) {
             return;
         }
@@ -69,8 +75,30 @@ pub(super) fn connect_password_list_activation(
             overlay.add_toast(Toast::new(&gettext("That item is missing its store.")));
             return;
         };
-        let opened_pass_file = OpenPassFile::from_label(root, &label);
-        open_password_entry_page(&page_state, opened_pass_file, true);
+        let activation = if open_requested {
+            PasswordListActivation::Open
+        } else {
+            Preferences::new().password_list_activation()
+        };
+        match activation {
+            PasswordListActivation::Open => {
+                let opened_pass_file = OpenPassFile::from_label(root, &label);
+                open_password_entry_page(&page_state, opened_pass_file, true);
+            }
+            PasswordListActivation::CopyPassword => {
+                copy_password_entry_to_clipboard(
+                    PassEntry::from_label(root, &label),
+                    overlay.clone(),
+                    None,
+                );
+            }
+            PasswordListActivation::CopyOtp => {
+                copy_password_entry_otp_to_clipboard(
+                    PassEntry::from_label(root, &label),
+                    overlay.clone(),
+                );
+            }
+        }
     });
 }
 
@@ -109,11 +137,16 @@ pub(super) fn connect_new_password_submit(
     let path_entry = dialog_state_for_apply.path_entry.clone();
     path_entry.connect_apply(move |_| {
         clear_new_password_dialog_error(&dialog_state_for_apply);
+        if let Some(message) = new_password_dialog_path_problem(&dialog_state_for_apply) {
+            show_new_password_dialog_error(&dialog_state_for_apply, message);
+            return;
+        }
         if let Err(message) = begin_new_password_entry(
             &page_state_for_apply,
             &dialog_state_for_apply.path_entry.text(),
             selected_new_password_store(&dialog_state_for_apply),
             &dialog_state_for_apply.dialog,
+            dialog_state_for_apply.secure_note.get(),
         ) {
             show_new_password_dialog_error(&dialog_state_for_apply, message);
         }
@@ -123,6 +156,7 @@ pub(super) fn connect_new_password_submit(
 pub(super) fn register_password_page_actions(
     window: &adw::ApplicationWindow,
     page_state: &PasswordPageState,
+    list: &ListBox,
 ) {
     {
         let page_state = page_state.clone();
@@ -133,6 +167,7 @@ pub(super) fn register_password_page_actions(
         let otp_add_button: Widget = page_state.otp_add_button.clone().upcast();
         let import_private_key_button: Widget =
             page_state.import_private_key_button.clone().upcast();
+        let attach_button: Widget = page_state.attach_button.clone().upcast();
         let editor_save_button: Widget = page_state.editor_save_button.clone().upcast();
         let controller = EventControllerKey::new();
         controller.set_propagation_phase(PropagationPhase::Capture);
@@ -158,6 +193,7 @@ pub(super) fn register_password_page_actions(
                     || focus == clean_button
                     || focus == otp_add_button
                     || focus == import_private_key_button
+                    || focus == attach_button
                     || focus == editor_save_button)
             {
                 focus_add_pass_field_input(&page_state);
@@ -256,15 +292,52 @@ pub(super) fn register_password_page_actions(
 
     {
         let page_state = page_state.clone();
+        let action_window = window.clone();
+        register_window_action(window, "attach-file", move || {
+            attach_file_to_current_entry(&page_state, &action_window);
+        });
+    }
+
+    {
+        let page_state = page_state.clone();
+        let list = list.clone();
         register_window_action(window, "copy-password", move || {
-            copy_current_password(&page_state);
+            if !copy_from_selected_pass_file(&list, &page_state.overlay, false) {
+                copy_current_password(&page_state);
+            }
         });
     }
 
     {
         let page_state = page_state.clone();
+        register_window_action(window, "open-website", move || {
+            open_current_website(&page_state);
+        });
+    }
+
+    {
+        let page_state = page_state.clone();
+        let list = list.clone();
         register_window_action(window, "copy-username", move || {
-            copy_current_username(&page_state);
+            if !copy_from_selected_pass_file(&list, &page_state.overlay, true) {
+                copy_current_username(&page_state);
+            }
+        });
+    }
+
+    {
+        let page_state = page_state.clone();
+        let action_window = window.clone();
+        register_window_action(window, "show-large-type", move || {
+            show_current_password_large_type(&page_state, &action_window);
+        });
+    }
+
+    {
+        let page_state = page_state.clone();
+        let action_window = window.clone();
+        register_window_action(window, "type-password", move || {
+            type_current_password(&page_state, &action_window);
         });
     }
 
@@ -6,28 +6,34 @@ use super::deferred::DeferredState;
 use super::widgets::WindowWidgets;
 use crate::logging::log_info;
 use crate::password::list::{
-    connect_selected_pass_file_shortcuts, load_passwords_async, setup_search_filter,
-    PasswordListActions,
+    connect_degraded_mode_banner, connect_selected_pass_file_shortcuts, load_passwords_async,
+    setup_search_filter, PasswordListActions,
 };
+use crate::password::new_folder::register_open_new_folder_action;
 use crate::password::new_item::{register_open_new_password_action, NewPasswordDialogState};
-use crate::password::page::PasswordPageState;
+use crate::password::page::{
+    connect_notes_preview, connect_password_alias_banner, connect_password_character_colors,
+    connect_password_draft_autosave, connect_password_otp, connect_password_page_close_guard,
+    connect_password_page_privacy, connect_password_spell_out, connect_password_store_row,
+    PasswordPageState,
+};
 use crate::preferences::Preferences;
 use crate::store::git_page::{connect_store_git_controls, StoreGitPageState};
 use crate::store::management::{
     connect_store_recipients_controls, initialize_store_import_page, rebuild_store_actions_list,
     register_open_store_picker_action, register_open_store_recipients_shortcut_actions,
-    register_store_recipients_reload_action, register_store_recipients_save_action,
-    StoreImportChrome, StoreImportControls, StoreImportPageState, StoreImportPageWidgets,
-    StoreRecipientsPageState,
+    register_store_folder_recipients_action, register_store_recipients_reload_action,
+    register_store_recipients_save_action, StoreImportChrome, StoreImportControls,
+    StoreImportPageState, StoreImportPageWidgets, StoreRecipientsPageState,
 };
 use crate::support::actions::activate_widget_action;
 use crate::support::runtime::{
     has_host_permission, supports_host_command_features, supports_logging_features,
 };
 use crate::window::controls::{
-    connect_search_visibility, register_back_action, register_context_reload_action,
-    register_context_save_action, register_context_undo_action, register_go_home_action,
-    register_list_visibility_action, register_reload_password_list_action,
+    connect_search_visibility, connect_type_to_search, register_back_action,
+    register_context_reload_action, register_context_save_action, register_context_undo_action,
+    register_go_home_action, register_list_visibility_action, register_reload_password_list_action,
     register_toggle_find_action, BackActionState, ContextUndoActionState,
     ListVisibilityActionState,
 };
@@ -40,13 +46,24 @@ use crate::window::host_access::append_optional_host_access_group_row;
 use crate::window::logs::{register_open_log_action, start_log_poller};
 use crate::window::navigation::{set_save_button_for_password, WindowNavigationState};
 use crate::window::preferences::{
-    connect_audit_history_recipient_row, connect_backend_row,
-    connect_clear_empty_fields_before_save_autosave, connect_new_password_template_autosave,
-    connect_pass_command_row, connect_password_generation_autosave,
-    connect_password_list_sort_autosave, connect_private_key_sync_row,
+    connect_activity_log_autosave, connect_app_pin_row, connect_audit_history_recipient_row,
+    connect_auto_lock_minutes_autosave, connect_backend_row,
+    connect_clear_empty_fields_before_save_autosave, connect_clipboard_clear_seconds_autosave,
+    connect_copy_password_on_open_website_autosave, connect_expiry_reminders_autosave,
+    connect_fill_fields_from_path_autosave, connect_git_gpg_diffs_autosave,
+    connect_large_file_limit_mib_autosave, connect_new_item_website_folder_row,
+    connect_new_password_template_autosave, connect_pass_command_row,
+    connect_password_generation_autosave, connect_password_list_activation_autosave,
+    connect_password_list_density_autosave, connect_password_list_sort_autosave,
+    connect_password_reveal_seconds_autosave, connect_personal_strength_words_autosave,
+    connect_privacy_mode_autosave, connect_private_key_sync_row, connect_protected_folders_row,
+    connect_refuse_large_files_autosave, connect_remember_key_passwords_row,
+    connect_reveal_passwords_briefly_autosave, connect_review_changes_before_save_autosave,
+    connect_search_item_contents_autosave, connect_shortcut_rows, connect_strength_words_row,
     connect_username_fallback_autosave, initialize_backend_row, register_open_preferences_action,
     PreferencesActionState,
 };
+use crate::window::profiles::{register_workspace_profile_actions, WorkspaceProfileState};
 use crate::window::tools::{
     register_open_tools_action, sync_tools_action_availability, ToolsPageState,
 };
@@ -58,6 +75,7 @@ pub(super) fn assemble_password_list_page(widgets: &WindowWidgets) {
     setup_search_filter(
         &widgets.list,
         &widgets.search_entry,
+        &widgets.search_contents_button,
         &primary_menu_button,
         &widgets.password_list_stack,
         &widgets.password_list_status,
@@ -65,6 +83,7 @@ pub(super) fn assemble_password_list_page(widgets: &WindowWidgets) {
         &widgets.password_list_scrolled,
     );
     connect_selected_pass_file_shortcuts(&widgets.list, &widgets.toast_overlay);
+    connect_degraded_mode_banner(&widgets.list, &widgets.degraded_mode_banner);
 
     let list_actions = PasswordListActions::new(
         &widgets.add_button,
@@ -111,7 +130,10 @@ pub(super) fn assemble_password_page(
         &widgets.otp_entry,
         &widgets.copy_otp_button,
     );
+    connect_password_spell_out(&widgets.password_entry, &widgets.password_spell_out_button);
+    connect_password_character_colors(&widgets.password_entry);
     connect_new_password_submit(password_page_state, new_password_dialog_state);
+    connect_password_store_row(password_page_state);
     connect_password_generation_autosave(
         &password_page_state.generator_controls,
         std::slice::from_ref(&preferences_action_state.generator_controls),
@@ -125,8 +147,19 @@ pub(super) fn assemble_password_page(
             revealer.set_reveal_child(button.is_active());
         });
 
-    register_password_page_actions(&widgets.window, password_page_state);
+    connect_notes_preview(password_page_state);
+    connect_password_otp(password_page_state);
+    connect_password_page_privacy(
+        &widgets.window,
+        password_page_state,
+        &widgets.privacy_mode_check,
+    );
+    connect_password_draft_autosave(password_page_state);
+    connect_password_alias_banner(password_page_state);
+    connect_password_page_close_guard(&widgets.window, password_page_state);
+    register_password_page_actions(&widgets.window, password_page_state, &widgets.list);
     register_open_new_password_action(&widgets.window, new_password_dialog_state);
+    register_open_new_folder_action(&widgets.window, &widgets.toast_overlay);
 }
 
 pub(super) fn assemble_preferences_page(
@@ -148,17 +181,128 @@ pub(super) fn assemble_preferences_page(
         &preferences_action_state.clear_empty_fields_before_save_check,
         &widgets.toast_overlay,
     );
+    connect_new_item_website_folder_row(
+        &preferences_action_state.new_item_website_folder_row,
+        &widgets.toast_overlay,
+        preferences,
+    );
+    connect_fill_fields_from_path_autosave(
+        &preferences_action_state.fill_fields_from_path_row,
+        &preferences_action_state.fill_fields_from_path_check,
+        &widgets.toast_overlay,
+    );
+    connect_copy_password_on_open_website_autosave(
+        &preferences_action_state.copy_password_on_open_website_row,
+        &preferences_action_state.copy_password_on_open_website_check,
+        &widgets.toast_overlay,
+    );
+    connect_expiry_reminders_autosave(
+        &preferences_action_state.expiry_reminders_row,
+        &preferences_action_state.expiry_reminders_check,
+        &widgets.toast_overlay,
+    );
+    connect_review_changes_before_save_autosave(
+        &preferences_action_state.review_changes_before_save_row,
+        &preferences_action_state.review_changes_before_save_check,
+        &widgets.toast_overlay,
+    );
+    connect_git_gpg_diffs_autosave(
+        &preferences_action_state.git_gpg_diffs_row,
+        &preferences_action_state.git_gpg_diffs_check,
+        &widgets.toast_overlay,
+    );
+    connect_large_file_limit_mib_autosave(
+        &preferences_action_state.large_file_limit_mib_spin,
+        &widgets.toast_overlay,
+    );
+    connect_refuse_large_files_autosave(
+        &preferences_action_state.refuse_large_files_row,
+        &preferences_action_state.refuse_large_files_check,
+        &widgets.toast_overlay,
+    );
+    connect_clipboard_clear_seconds_autosave(
+        &preferences_action_state.clipboard_clear_seconds_spin,
+        &widgets.toast_overlay,
+    );
+    connect_auto_lock_minutes_autosave(
+        &preferences_action_state.auto_lock_minutes_spin,
+        &widgets.toast_overlay,
+    );
+    connect_privacy_mode_autosave(
+        &preferences_action_state.privacy_mode_row,
+        &preferences_action_state.privacy_mode_check,
+        &widgets.toast_overlay,
+    );
+    connect_reveal_passwords_briefly_autosave(
+        &preferences_action_state.reveal_passwords_briefly_row,
+        &preferences_action_state.reveal_passwords_briefly_check,
+        &widgets.toast_overlay,
+    );
+    connect_password_reveal_seconds_autosave(
+        &preferences_action_state.password_reveal_seconds_spin,
+        &widgets.toast_overlay,
+    );
+    connect_search_item_contents_autosave(
+        &preferences_action_state.search_item_contents_row,
+        &preferences_action_state.search_item_contents_check,
+        &widgets.toast_overlay,
+    );
+    connect_personal_strength_words_autosave(
+        &preferences_action_state.personal_strength_words_row,
+        &preferences_action_state.personal_strength_words_check,
+        &widgets.toast_overlay,
+    );
+    connect_strength_words_row(
+        &preferences_action_state.strength_words_row,
+        &widgets.toast_overlay,
+        preferences,
+    );
+    connect_protected_folders_row(
+        &preferences_action_state.protected_folders_row,
+        &widgets.toast_overlay,
+        preferences,
+    );
+    connect_activity_log_autosave(
+        &preferences_action_state.activity_log_row,
+        &preferences_action_state.activity_log_check,
+        &widgets.toast_overlay,
+    );
+    connect_app_pin_row(
+        &preferences_action_state.app_pin_row,
+        &preferences_action_state.app_pin_check,
+        &widgets.toast_overlay,
+        &widgets.window,
+    );
+    connect_remember_key_passwords_row(
+        &preferences_action_state.remember_key_passwords_row,
+        &preferences_action_state.remember_key_passwords_check,
+        &widgets.toast_overlay,
+    );
+    connect_shortcut_rows(
+        &preferences_action_state.shortcut_rows,
+        &widgets.window,
+        &widgets.toast_overlay,
+    );
     connect_username_fallback_autosave(
         &widgets.preferences_username_folder_check,
         &widgets.preferences_username_filename_check,
         &widgets.toast_overlay,
     );
     connect_password_list_sort_autosave(
-        &widgets.preferences_password_list_sort_filename_check,
-        &widgets.preferences_password_list_sort_store_path_check,
+        &preferences_action_state.password_list_sort_checks,
+        &widgets.toast_overlay,
+        &widgets.window,
+    );
+    connect_password_list_density_autosave(
+        &widgets.preferences_password_list_comfortable_check,
+        &widgets.preferences_password_list_compact_check,
         &widgets.toast_overlay,
         &widgets.window,
     );
+    connect_password_list_activation_autosave(
+        &preferences_action_state.password_list_activation_checks,
+        &widgets.toast_overlay,
+    );
     connect_password_generation_autosave(
         &preferences_action_state.generator_controls,
         std::slice::from_ref(&password_page_state.generator_controls),
@@ -197,6 +341,8 @@ pub(super) fn assemble_store_import_page(
             source_clear_button: &widgets.store_import_source_clear_button,
             source_password_row: &widgets.store_import_password_row,
             target_path_row: &widgets.store_import_target_path_row,
+            organize_row: &widgets.store_import_organize_row,
+            organize_check: &widgets.store_import_organize_check,
             import_button: &widgets.store_import_button,
         },
     });
@@ -216,6 +362,7 @@ pub(super) fn assemble_store_recipients_page(
         store_recipients_page_state,
     );
     register_store_recipients_reload_action(&widgets.window, store_recipients_page_state);
+    register_store_folder_recipients_action(&widgets.window, store_recipients_page_state);
     register_open_store_picker_action(
         &widgets.window,
         &widgets.password_stores,
@@ -281,6 +428,14 @@ pub(super) fn assemble_tools_page(
     });
 }
 
+pub(super) fn assemble_profile_menu(widgets: &WindowWidgets) {
+    register_workspace_profile_actions(&WorkspaceProfileState {
+        window: widgets.window.clone(),
+        overlay: widgets.toast_overlay.clone(),
+        menu: widgets.profile_menu.clone(),
+    });
+}
+
 pub(super) fn register_window_navigation_actions(
     widgets: &WindowWidgets,
     navigation_state: &WindowNavigationState,
@@ -303,6 +458,12 @@ pub(super) fn register_window_navigation_actions(
     register_context_undo_action(&widgets.window, context_undo_state);
 
     connect_search_visibility(&widgets.find_button, &widgets.search_entry, &widgets.list);
+    connect_type_to_search(
+        &widgets.window,
+        navigation_state,
+        &widgets.find_button,
+        &widgets.search_entry,
+    );
     register_toggle_find_action(
         &widgets.window,
         navigation_state,
@@ -14,12 +14,11 @@ use crate::window::navigation::WindowNavigationState;
 use adw::glib::{self, Propagation};
 use adw::gtk::{gdk, DirectionType, EventControllerKey, ListBox, Widget};
 use adw::prelude::*;
-use adw::ApplicationWindow;
 
 pub(super) fn initialize_window_chrome(widgets: &WindowWidgets, preferences: &Preferences) {
     configure_search_entries(widgets);
-    restore_window_size(&widgets.window, preferences);
-    connect_window_size_persistence(&widgets.window);
+    restore_window_state(widgets, preferences);
+    connect_window_state_persistence(widgets);
 }
 
 pub(super) fn connect_window_keyboard_navigation(
@@ -76,6 +75,9 @@ fn preferences_page_detail_widgets(widgets: &WindowWidgets) -> Vec<Widget> {
             .audit_use_commit_history_recipients_check
             .clone()
             .upcast(),
+        widgets.git_gpg_diffs_check.clone().upcast(),
+        widgets.large_file_limit_mib_spin.clone().upcast(),
+        widgets.refuse_large_files_check.clone().upcast(),
         widgets.preferences_username_filename_check.clone().upcast(),
         widgets.preferences_username_folder_check.clone().upcast(),
         widgets
@@ -86,11 +88,52 @@ fn preferences_page_detail_widgets(widgets: &WindowWidgets) -> Vec<Widget> {
             .preferences_password_list_sort_store_path_check
             .clone()
             .upcast(),
+        widgets
+            .preferences_password_list_sort_top_folder_check
+            .clone()
+            .upcast(),
+        widgets
+            .preferences_password_list_comfortable_check
+            .clone()
+            .upcast(),
+        widgets
+            .preferences_password_list_compact_check
+            .clone()
+            .upcast(),
+        widgets
+            .preferences_password_list_activation_open_check
+            .clone()
+            .upcast(),
+        widgets
+            .preferences_password_list_activation_copy_password_check
+            .clone()
+            .upcast(),
+        widgets
+            .preferences_password_list_activation_copy_otp_check
+            .clone()
+            .upcast(),
         widgets.new_pass_file_template_view.clone().upcast(),
+        widgets.new_item_website_folder_row.clone().upcast(),
+        widgets.fill_fields_from_path_check.clone().upcast(),
         widgets
             .clear_empty_fields_before_save_check
             .clone()
             .upcast(),
+        widgets.copy_password_on_open_website_check.clone().upcast(),
+        widgets.expiry_reminders_check.clone().upcast(),
+        widgets.review_changes_before_save_check.clone().upcast(),
+        widgets.clipboard_clear_seconds_spin.clone().upcast(),
+        widgets.auto_lock_minutes_spin.clone().upcast(),
+        widgets.privacy_mode_check.clone().upcast(),
+        widgets.reveal_passwords_briefly_check.clone().upcast(),
+        widgets.password_reveal_seconds_spin.clone().upcast(),
+        widgets.search_item_contents_check.clone().upcast(),
+        widgets.personal_strength_words_check.clone().upcast(),
+        widgets.strength_words_row.clone().upcast(),
+        widgets.protected_folders_row.clone().upcast(),
+        widgets.activity_log_check.clone().upcast(),
+        widgets.app_pin_check.clone().upcast(),
+        widgets.remember_key_passwords_check.clone().upcast(),
         widgets
             .preferences_password_generator_length_spin
             .clone()
@@ -433,9 +476,15 @@ fn connect_page_autofocus(widgets: &WindowWidgets, navigation: &WindowNavigation
     });
 }
 
-fn restore_window_size(window: &ApplicationWindow, preferences: &Preferences) {
+fn restore_window_state(widgets: &WindowWidgets, preferences: &Preferences) {
     let (width, height) = preferences.window_size();
-    window.set_default_size(width, height);
+    widgets.window.set_default_size(width, height);
+    if preferences.window_is_maximized() {
+        widgets.window.maximize();
+    }
+    if preferences.search_visible() {
+        widgets.search_entry.set_visible(true);
+    }
 }
 
 fn configure_search_entries(widgets: &WindowWidgets) {
@@ -455,14 +504,21 @@ fn configure_search_entries(widgets: &WindowWidgets) {
     }
 }
 
-fn connect_window_size_persistence(window: &ApplicationWindow) {
+fn connect_window_state_persistence(widgets: &WindowWidgets) {
     let preferences = Preferences::new();
-    window.connect_close_request(move |window| {
-        let width = window.width();
-        let height = window.height();
-        if width > 0 && height > 0 {
-            let _ = preferences.set_window_size(width, height);
+    let search_entry = widgets.search_entry.clone();
+    widgets.window.connect_close_request(move |window| {
+        let maximized = window.is_maximized();
+        let _ = preferences.set_window_maximized(maximized);
+        // A maximized window would otherwise overwrite the size it restores to.
+        if !maximized {
+            let width = window.width();
+            let height = window.height();
+            if width > 0 && height > 0 {
+                let _ = preferences.set_window_size(width, height);
+            }
         }
+        let _ = preferences.set_search_visible(search_entry.is_visible());
         Propagation::Proceed
     });
 }
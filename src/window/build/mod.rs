@@ -7,8 +7,9 @@ pub(super) mod widgets;
 
 use self::assemble::{
     assemble_docs_page, assemble_git_page, assemble_log_page, assemble_password_list_page,
-    assemble_password_page, assemble_preferences_page, assemble_store_import_page,
-    assemble_store_recipients_page, assemble_tools_page, register_window_navigation_actions,
+    assemble_password_page, assemble_preferences_page, assemble_profile_menu,
+    assemble_store_import_page, assemble_store_recipients_page, assemble_tools_page,
+    register_window_navigation_actions,
 };
 use self::chrome::{
     connect_window_keyboard_navigation, initialize_window_chrome, schedule_initial_focus,
@@ -21,21 +22,25 @@ use self::state::{
     window_navigation_state,
 };
 use self::widgets::WindowWidgets;
+use crate::clipboard::copy_password_entry_with_notification;
 use crate::logging::log_error;
-use crate::password::model::OpenPassFile;
+use crate::password::model::{OpenPassFile, PassEntry};
 use crate::password::otp::PasswordOtpState;
 use crate::password::page::{open_password_entry_page, password_page_has_unsaved_changes};
 use crate::preferences::Preferences;
 use crate::private_key::sync::{sync_private_keys_with_host, PrivateKeySyncDirection};
 use crate::support::actions::activate_widget_action;
+use crate::support::background::spawn_result_task;
 use crate::support::object_data::{cloned_data, set_cloned_data};
 use crate::support::runtime::log_runtime_capabilities_once;
 use crate::window::controls::{
     apply_startup_query, configure_window_shortcuts, ListVisibilityState,
 };
+#[cfg(target_os = "linux")]
+use crate::window::lock_monitor::{connect_auto_lock, connect_session_lock_monitor};
 use crate::window::session::initialize_window_session;
 use adw::gtk::{Builder, ListBox, SearchEntry};
-use adw::{prelude::*, Application, ApplicationWindow};
+use adw::{prelude::*, Application, ApplicationWindow, ToastOverlay};
 use std::rc::Rc;
 
 const UI_SRC: &str = include_str!(concat!(env!("OUT_DIR"), "/window.ui"));
@@ -46,6 +51,7 @@ struct MainWindowCommandState {
     list: ListBox,
     search_entry: SearchEntry,
     password_page: crate::password::page::PasswordPageState,
+    overlay: ToastOverlay,
 }
 
 pub fn create_main_window(
@@ -57,16 +63,12 @@ pub fn create_main_window(
     let widgets = WindowWidgets::load(&builder)?;
     widgets.window.set_application(Some(app));
     initialize_window_session(&widgets.window);
+    #[cfg(target_os = "linux")]
+    connect_session_lock_monitor(&widgets.window);
+    connect_auto_lock(&widgets.window);
     log_runtime_capabilities_once();
 
     let preferences = Preferences::new();
-    if preferences.sync_private_keys_with_host() {
-        if let Err(err) = sync_private_keys_with_host(PrivateKeySyncDirection::HostToApp) {
-            log_error(format!("Failed to sync private keys during startup: {err}"));
-            let _ = preferences.set_sync_private_keys_with_host(false);
-        }
-    }
-
     initialize_window_chrome(&widgets, &preferences);
 
     let new_password_dialog_state = new_password_dialog_state(&widgets);
@@ -79,6 +81,7 @@ pub fn create_main_window(
             list: widgets.list.clone(),
             search_entry: widgets.search_entry.clone(),
             password_page: password_page_state.clone(),
+            overlay: widgets.toast_overlay.clone(),
         },
     );
     let list_visibility = ListVisibilityState::new(false, false);
@@ -142,6 +145,7 @@ pub fn create_main_window(
     assemble_log_page(&widgets, &window_navigation_state);
     assemble_docs_page(&widgets, &docs_page_state);
     assemble_tools_page(&widgets, &tools_page_state);
+    assemble_profile_menu(&widgets);
     register_window_navigation_actions(
         &widgets,
         &window_navigation_state,
@@ -174,10 +178,36 @@ pub fn create_main_window(
     } else {
         schedule_initial_focus(&widgets, &window_navigation_state);
     }
+    start_startup_private_key_sync(&widgets.window, &preferences);
 
     Ok(widgets.window)
 }
 
+// Host keys are synced after the window is built so that startup doesn't
+// wait on the host GPG keyring. The lists reload once the keys are in.
+fn start_startup_private_key_sync(window: &ApplicationWindow, preferences: &Preferences) {
+    if !preferences.sync_private_keys_with_host() {
+        return;
+    }
+
+    let window = window.clone();
+    let preferences = preferences.clone();
+    spawn_result_task(
+        || sync_private_keys_with_host(PrivateKeySyncDirection::HostToApp),
+        move |result| match result {
+            Ok(()) => {
+                activate_widget_action(&window, "win.reload-store-recipients-list");
+                activate_widget_action(&window, "win.reload-password-list");
+            }
+            Err(err) => {
+                log_error(format!("Failed to sync private keys during startup: {err}"));
+                let _ = preferences.set_sync_private_keys_with_host(false);
+            }
+        },
+        || log_error("Private-key sync stopped unexpectedly during startup.".to_string()),
+    );
+}
+
 pub fn dispatch_main_window_command(
     window: &ApplicationWindow,
     startup_query: Option<String>,
@@ -203,4 +233,18 @@ pub fn dispatch_main_window_command(
 
     activate_widget_action(window, "win.go-home");
     apply_startup_query(Some(query), &state.search_entry, &state.list);
+    // A new window focuses the search on its own; a running one has to be
+    // told, so typing after the launch refines the forwarded search.
+    state.search_entry.grab_focus();
+    state.search_entry.set_position(-1);
+}
+
+pub fn copy_main_window_entry(window: &ApplicationWindow, entry: PassEntry) {
+    let Some(state) =
+        cloned_data::<_, MainWindowCommandState>(window, MAIN_WINDOW_COMMAND_STATE_KEY)
+    else {
+        return;
+    };
+
+    copy_password_entry_with_notification(entry, state.overlay);
 }
@@ -16,7 +16,10 @@ use crate::window::controls::{
 use crate::window::docs::{DocumentationPageState, DocumentationPageWidgets};
 use crate::window::git::GitActionState;
 use crate::window::navigation::{WindowNavigationState, WindowPageState};
-use crate::window::preferences::PreferencesActionState;
+use crate::window::preferences::{
+    build_shortcut_rows, PasswordListActivationChecks, PasswordListSortChecks,
+    PreferencesActionState,
+};
 use crate::window::preferences_search::{PreferencesPageSearchState, SearchablePreferencesGroup};
 use crate::window::tools::{
     ToolAuditWidgets, ToolBrowserWidgets, ToolsPageState, ToolsPageWidgets,
@@ -24,17 +27,10 @@ use crate::window::tools::{
 use adw::prelude::*;
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+use zeroize::Zeroizing;
 
 pub(super) fn new_password_dialog_state(_widgets: &WindowWidgets) -> NewPasswordDialogState {
-    let (dialog, store_dropdown, path_entry, error_label) =
-        crate::password::new_item::build_new_password_dialog();
-    NewPasswordDialogState {
-        dialog,
-        path_entry,
-        store_dropdown,
-        error_label,
-        store_roots: Rc::new(RefCell::new(Vec::new())),
-    }
+    crate::password::new_item::build_new_password_dialog()
 }
 
 pub(super) fn password_page_state(
@@ -55,8 +51,15 @@ pub(super) fn password_page_state(
         raw: widgets.open_raw_button.clone(),
         win: widgets.window_title.clone(),
         status: widgets.password_status.clone(),
+        alias_banner: widgets.password_alias_banner.clone(),
+        alias_target: Rc::new(RefCell::new(None)),
+        draft_banner: widgets.password_draft_banner.clone(),
+        store_row: widgets.password_store_row.clone(),
+        store_row_roots: Rc::new(RefCell::new(Vec::new())),
         entry: widgets.password_entry.clone(),
         password_analysis_label: widgets.password_analysis_label.clone(),
+        expiry_label: widgets.password_expiry_label.clone(),
+        dates_label: widgets.password_dates_label.clone(),
         username: widgets.username_entry.clone(),
         otp: otp.clone(),
         field_add_row: widgets.add_field_row.clone(),
@@ -64,6 +67,7 @@ pub(super) fn password_page_state(
         clean_button: widgets.clean_pass_file_button.clone(),
         otp_add_button: widgets.add_otp_button.clone(),
         import_private_key_button: widgets.import_private_key_button.clone(),
+        attach_button: widgets.attach_file_button.clone(),
         editor_save_button: widgets.editor_save_button.clone(),
         generator_settings_button: widgets.password_generator_settings_button.clone(),
         generator_settings_revealer: widgets.password_generator_settings_revealer.clone(),
@@ -75,12 +79,21 @@ pub(super) fn password_page_state(
             &widgets.password_generator_min_symbols_spin,
         ),
         dynamic_box: widgets.dynamic_fields_box.clone(),
+        attachments_box: widgets.attachments_box.clone(),
+        notes_preview_label: widgets.notes_preview_label.clone(),
+        notes_preview_button: widgets.notes_preview_button.clone(),
+        open_website_button: widgets.open_website_button.clone(),
         structured_templates: Rc::new(RefCell::new(Vec::<StructuredPassLine>::new())),
         dynamic_rows: Rc::new(RefCell::new(Vec::<DynamicFieldRow>::new())),
         text: widgets.text_view.clone(),
         overlay: widgets.toast_overlay.clone(),
-        saved_contents: Rc::new(RefCell::new(String::new())),
+        saved_contents: Rc::new(RefCell::new(Zeroizing::default())),
+        drafted_contents: Rc::new(RefCell::new(Zeroizing::default())),
         saved_entry_exists: Rc::new(Cell::new(false)),
+        raw_only: Rc::new(Cell::new(false)),
+        read_only: Rc::new(Cell::new(false)),
+        secure_note: Rc::new(Cell::new(false)),
+        close_after_save: Rc::new(Cell::new(false)),
     }
 }
 
@@ -310,6 +323,7 @@ fn build_store_recipients_page_state(
         saved_recipients,
         recipient_scope_dirs,
         selected_recipient_scope,
+        pending_recipient_scope: Rc::new(RefCell::new(None)),
         private_key_requirement,
         saved_private_key_requirement,
         save_in_flight,
@@ -456,10 +470,39 @@ fn window_page_state(widgets: &WindowWidgets, page: &adw::NavigationPage) -> Win
     }
 }
 
+fn password_list_sort_checks(widgets: &WindowWidgets) -> PasswordListSortChecks {
+    PasswordListSortChecks {
+        filename: widgets
+            .preferences_password_list_sort_filename_check
+            .clone(),
+        store_path: widgets
+            .preferences_password_list_sort_store_path_check
+            .clone(),
+        top_folder: widgets
+            .preferences_password_list_sort_top_folder_check
+            .clone(),
+    }
+}
+
+fn password_list_activation_checks(widgets: &WindowWidgets) -> PasswordListActivationChecks {
+    PasswordListActivationChecks {
+        open: widgets
+            .preferences_password_list_activation_open_check
+            .clone(),
+        copy_password: widgets
+            .preferences_password_list_activation_copy_password_check
+            .clone(),
+        copy_otp: widgets
+            .preferences_password_list_activation_copy_otp_check
+            .clone(),
+    }
+}
+
 pub(super) fn preferences_action_state(
     widgets: &WindowWidgets,
     recipients_page: &StoreRecipientsPageState,
 ) -> PreferencesActionState {
+    let shortcut_rows = build_shortcut_rows(&widgets.settings_shortcuts_group);
     let search = PreferencesPageSearchState::new(
         &widgets.settings_preferences_page,
         &widgets.settings_search_entry,
@@ -483,6 +526,9 @@ pub(super) fn preferences_action_state(
                         .audit_use_commit_history_recipients_row
                         .clone()
                         .upcast(),
+                    widgets.git_gpg_diffs_row.clone().upcast(),
+                    widgets.large_file_limit_mib_row.clone().upcast(),
+                    widgets.refuse_large_files_row.clone().upcast(),
                 ],
             ),
             SearchablePreferencesGroup::with_widgets(
@@ -507,12 +553,72 @@ pub(super) fn preferences_action_state(
                         .preferences_password_list_sort_store_path_row
                         .clone()
                         .upcast(),
+                    widgets
+                        .preferences_password_list_sort_top_folder_row
+                        .clone()
+                        .upcast(),
+                    widgets
+                        .preferences_password_list_comfortable_row
+                        .clone()
+                        .upcast(),
+                    widgets
+                        .preferences_password_list_compact_row
+                        .clone()
+                        .upcast(),
+                    widgets
+                        .preferences_password_list_activation_open_row
+                        .clone()
+                        .upcast(),
+                    widgets
+                        .preferences_password_list_activation_copy_password_row
+                        .clone()
+                        .upcast(),
+                    widgets
+                        .preferences_password_list_activation_copy_otp_row
+                        .clone()
+                        .upcast(),
                 ],
             ),
             SearchablePreferencesGroup::with_widgets(&widgets.settings_template_group, Vec::new()),
             SearchablePreferencesGroup::with_widgets(
                 &widgets.settings_clear_empty_fields_group,
-                vec![widgets.clear_empty_fields_before_save_row.clone().upcast()],
+                vec![
+                    widgets.new_item_website_folder_row.clone().upcast(),
+                    widgets.fill_fields_from_path_row.clone().upcast(),
+                    widgets.clear_empty_fields_before_save_row.clone().upcast(),
+                    widgets.copy_password_on_open_website_row.clone().upcast(),
+                    widgets.expiry_reminders_row.clone().upcast(),
+                    widgets.review_changes_before_save_row.clone().upcast(),
+                ],
+            ),
+            SearchablePreferencesGroup::with_widgets(
+                &widgets.settings_security_group,
+                vec![
+                    widgets.clipboard_clear_seconds_row.clone().upcast(),
+                    widgets.auto_lock_minutes_row.clone().upcast(),
+                    widgets.privacy_mode_row.clone().upcast(),
+                    widgets.reveal_passwords_briefly_row.clone().upcast(),
+                    widgets.password_reveal_seconds_row.clone().upcast(),
+                    widgets.search_item_contents_row.clone().upcast(),
+                    widgets.personal_strength_words_row.clone().upcast(),
+                    widgets.strength_words_row.clone().upcast(),
+                    widgets.protected_folders_row.clone().upcast(),
+                ],
+            ),
+            SearchablePreferencesGroup::with_widgets(
+                &widgets.settings_privacy_group,
+                vec![
+                    widgets.activity_log_row.clone().upcast(),
+                    widgets.app_pin_row.clone().upcast(),
+                    widgets.remember_key_passwords_row.clone().upcast(),
+                ],
+            ),
+            SearchablePreferencesGroup::with_widgets(
+                &widgets.settings_shortcuts_group,
+                shortcut_rows
+                    .iter()
+                    .map(|(_, row)| row.clone().upcast())
+                    .collect(),
             ),
             SearchablePreferencesGroup::with_widgets(
                 &widgets.settings_generator_group,
@@ -547,14 +653,47 @@ pub(super) fn preferences_action_state(
         template_view: widgets.new_pass_file_template_view.clone(),
         clear_empty_fields_before_save_row: widgets.clear_empty_fields_before_save_row.clone(),
         clear_empty_fields_before_save_check: widgets.clear_empty_fields_before_save_check.clone(),
+        new_item_website_folder_row: widgets.new_item_website_folder_row.clone(),
+        fill_fields_from_path_row: widgets.fill_fields_from_path_row.clone(),
+        fill_fields_from_path_check: widgets.fill_fields_from_path_check.clone(),
+        copy_password_on_open_website_row: widgets.copy_password_on_open_website_row.clone(),
+        copy_password_on_open_website_check: widgets.copy_password_on_open_website_check.clone(),
+        expiry_reminders_row: widgets.expiry_reminders_row.clone(),
+        expiry_reminders_check: widgets.expiry_reminders_check.clone(),
+        review_changes_before_save_row: widgets.review_changes_before_save_row.clone(),
+        review_changes_before_save_check: widgets.review_changes_before_save_check.clone(),
+        git_gpg_diffs_row: widgets.git_gpg_diffs_row.clone(),
+        git_gpg_diffs_check: widgets.git_gpg_diffs_check.clone(),
+        large_file_limit_mib_spin: widgets.large_file_limit_mib_spin.clone(),
+        refuse_large_files_row: widgets.refuse_large_files_row.clone(),
+        refuse_large_files_check: widgets.refuse_large_files_check.clone(),
+        clipboard_clear_seconds_spin: widgets.clipboard_clear_seconds_spin.clone(),
+        auto_lock_minutes_spin: widgets.auto_lock_minutes_spin.clone(),
+        privacy_mode_row: widgets.privacy_mode_row.clone(),
+        privacy_mode_check: widgets.privacy_mode_check.clone(),
+        reveal_passwords_briefly_row: widgets.reveal_passwords_briefly_row.clone(),
+        reveal_passwords_briefly_check: widgets.reveal_passwords_briefly_check.clone(),
+        password_reveal_seconds_spin: widgets.password_reveal_seconds_spin.clone(),
+        search_item_contents_row: widgets.search_item_contents_row.clone(),
+        search_item_contents_check: widgets.search_item_contents_check.clone(),
+        personal_strength_words_row: widgets.personal_strength_words_row.clone(),
+        personal_strength_words_check: widgets.personal_strength_words_check.clone(),
+        strength_words_row: widgets.strength_words_row.clone(),
+        protected_folders_row: widgets.protected_folders_row.clone(),
+        activity_log_row: widgets.activity_log_row.clone(),
+        activity_log_check: widgets.activity_log_check.clone(),
+        app_pin_row: widgets.app_pin_row.clone(),
+        app_pin_check: widgets.app_pin_check.clone(),
+        remember_key_passwords_row: widgets.remember_key_passwords_row.clone(),
+        remember_key_passwords_check: widgets.remember_key_passwords_check.clone(),
         username_folder_check: widgets.preferences_username_folder_check.clone(),
         username_filename_check: widgets.preferences_username_filename_check.clone(),
-        password_list_sort_filename_check: widgets
-            .preferences_password_list_sort_filename_check
-            .clone(),
-        password_list_sort_store_path_check: widgets
-            .preferences_password_list_sort_store_path_check
+        password_list_sort_checks: password_list_sort_checks(widgets),
+        password_list_comfortable_check: widgets
+            .preferences_password_list_comfortable_check
             .clone(),
+        password_list_compact_check: widgets.preferences_password_list_compact_check.clone(),
+        password_list_activation_checks: password_list_activation_checks(widgets),
         generator_controls: PasswordGenerationControls::new(
             &widgets.preferences_password_generator_length_spin,
             &widgets.preferences_password_generator_min_lowercase_spin,
@@ -576,6 +715,7 @@ pub(super) fn preferences_action_state(
         audit_use_commit_history_recipients_check: widgets
             .audit_use_commit_history_recipients_check
             .clone(),
+        shortcut_rows,
     }
 }
 
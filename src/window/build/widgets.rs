@@ -1,3 +1,4 @@
+use adw::gio::Menu;
 use adw::glib::{object::IsA, Object};
 use adw::gtk::{
     Box as GtkBox, Builder, Button, CheckButton, Image, Label, ListBox, MenuButton, Popover,
@@ -5,8 +6,8 @@ use adw::gtk::{
 };
 use adw::ActionRow;
 use adw::{
-    ApplicationWindow, ComboRow, EntryRow, NavigationPage, NavigationView, PasswordEntryRow,
-    StatusPage, ToastOverlay, WindowTitle,
+    ApplicationWindow, Banner, ComboRow, EntryRow, NavigationPage, NavigationView,
+    PasswordEntryRow, StatusPage, ToastOverlay, WindowTitle,
 };
 use adw::{PreferencesGroup, PreferencesPage};
 
@@ -20,6 +21,8 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) store_button: Button,
     pub(in crate::window) window_title: WindowTitle,
     pub(in crate::window) primary_menu_button: MenuButton,
+    pub(in crate::window) offline_indicator: Image,
+    pub(in crate::window) profile_menu: Menu,
     pub(in crate::window) tools_audit_filter_button: MenuButton,
     pub(in crate::window) tools_audit_filter_popover: Popover,
     pub(in crate::window) tools_audit_filter_store_box: GtkBox,
@@ -36,6 +39,9 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) settings_password_list_group: PreferencesGroup,
     pub(in crate::window) settings_template_group: PreferencesGroup,
     pub(in crate::window) settings_clear_empty_fields_group: PreferencesGroup,
+    pub(in crate::window) settings_security_group: PreferencesGroup,
+    pub(in crate::window) settings_privacy_group: PreferencesGroup,
+    pub(in crate::window) settings_shortcuts_group: PreferencesGroup,
     pub(in crate::window) settings_generator_group: PreferencesGroup,
     pub(in crate::window) tools_page: NavigationPage,
     pub(in crate::window) tools_search_entry: SearchEntry,
@@ -93,6 +99,8 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) store_import_source_clear_button: Button,
     pub(in crate::window) store_import_password_row: PasswordEntryRow,
     pub(in crate::window) store_import_target_path_row: EntryRow,
+    pub(in crate::window) store_import_organize_row: ActionRow,
+    pub(in crate::window) store_import_organize_check: CheckButton,
     pub(in crate::window) store_import_button: Button,
     pub(in crate::window) store_recipients_page: NavigationPage,
     pub(in crate::window) store_recipients_search_entry: SearchEntry,
@@ -158,14 +166,58 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) new_pass_file_template_view: TextView,
     pub(in crate::window) clear_empty_fields_before_save_row: ActionRow,
     pub(in crate::window) clear_empty_fields_before_save_check: CheckButton,
+    pub(in crate::window) new_item_website_folder_row: EntryRow,
+    pub(in crate::window) fill_fields_from_path_row: ActionRow,
+    pub(in crate::window) fill_fields_from_path_check: CheckButton,
+    pub(in crate::window) copy_password_on_open_website_row: ActionRow,
+    pub(in crate::window) copy_password_on_open_website_check: CheckButton,
+    pub(in crate::window) expiry_reminders_row: ActionRow,
+    pub(in crate::window) expiry_reminders_check: CheckButton,
+    pub(in crate::window) review_changes_before_save_row: ActionRow,
+    pub(in crate::window) review_changes_before_save_check: CheckButton,
+    pub(in crate::window) git_gpg_diffs_row: ActionRow,
+    pub(in crate::window) git_gpg_diffs_check: CheckButton,
+    pub(in crate::window) large_file_limit_mib_row: ActionRow,
+    pub(in crate::window) large_file_limit_mib_spin: SpinButton,
+    pub(in crate::window) refuse_large_files_row: ActionRow,
+    pub(in crate::window) refuse_large_files_check: CheckButton,
+    pub(in crate::window) clipboard_clear_seconds_row: ActionRow,
+    pub(in crate::window) clipboard_clear_seconds_spin: SpinButton,
+    pub(in crate::window) auto_lock_minutes_row: ActionRow,
+    pub(in crate::window) auto_lock_minutes_spin: SpinButton,
+    pub(in crate::window) privacy_mode_row: ActionRow,
+    pub(in crate::window) privacy_mode_check: CheckButton,
+    pub(in crate::window) reveal_passwords_briefly_row: ActionRow,
+    pub(in crate::window) reveal_passwords_briefly_check: CheckButton,
+    pub(in crate::window) password_reveal_seconds_row: ActionRow,
+    pub(in crate::window) password_reveal_seconds_spin: SpinButton,
+    pub(in crate::window) search_item_contents_row: ActionRow,
+    pub(in crate::window) search_item_contents_check: CheckButton,
+    pub(in crate::window) personal_strength_words_row: ActionRow,
+    pub(in crate::window) personal_strength_words_check: CheckButton,
+    pub(in crate::window) strength_words_row: EntryRow,
+    pub(in crate::window) protected_folders_row: EntryRow,
+    pub(in crate::window) activity_log_row: ActionRow,
+    pub(in crate::window) activity_log_check: CheckButton,
+    pub(in crate::window) app_pin_row: ActionRow,
+    pub(in crate::window) app_pin_check: CheckButton,
+    pub(in crate::window) remember_key_passwords_row: ActionRow,
+    pub(in crate::window) remember_key_passwords_check: CheckButton,
     pub(in crate::window) preferences_username_folder_check: CheckButton,
     pub(in crate::window) preferences_username_filename_check: CheckButton,
     pub(in crate::window) preferences_password_list_sort_filename_check: CheckButton,
     pub(in crate::window) preferences_password_list_sort_store_path_check: CheckButton,
+    pub(in crate::window) preferences_password_list_sort_top_folder_check: CheckButton,
+    pub(in crate::window) preferences_password_list_comfortable_check: CheckButton,
+    pub(in crate::window) preferences_password_list_compact_check: CheckButton,
+    pub(in crate::window) preferences_password_list_activation_open_check: CheckButton,
+    pub(in crate::window) preferences_password_list_activation_copy_password_check: CheckButton,
+    pub(in crate::window) preferences_password_list_activation_copy_otp_check: CheckButton,
     pub(in crate::window) password_stores: ListBox,
     pub(in crate::window) password_store_actions: ListBox,
     pub(in crate::window) navigation_view: NavigationView,
     pub(in crate::window) search_entry: SearchEntry,
+    pub(in crate::window) search_contents_button: ToggleButton,
     pub(in crate::window) password_list_stack: Stack,
     pub(in crate::window) password_list_status: StatusPage,
     pub(in crate::window) password_list_spinner: Spinner,
@@ -173,9 +225,15 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) list: ListBox,
     pub(in crate::window) password_page: NavigationPage,
     pub(in crate::window) raw_text_page: NavigationPage,
+    pub(in crate::window) password_alias_banner: Banner,
+    pub(in crate::window) password_draft_banner: Banner,
+    pub(in crate::window) degraded_mode_banner: Banner,
     pub(in crate::window) password_status: StatusPage,
+    pub(in crate::window) password_store_row: ComboRow,
     pub(in crate::window) password_entry: PasswordEntryRow,
     pub(in crate::window) password_analysis_label: Label,
+    pub(in crate::window) password_expiry_label: Label,
+    pub(in crate::window) password_dates_label: Label,
     pub(in crate::window) password_generator_settings_button: ToggleButton,
     pub(in crate::window) password_generator_settings_revealer: Revealer,
     pub(in crate::window) password_generator_length_spin: SpinButton,
@@ -190,12 +248,18 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) clean_pass_file_button: Button,
     pub(in crate::window) add_otp_button: Button,
     pub(in crate::window) import_private_key_button: Button,
+    pub(in crate::window) attach_file_button: Button,
     pub(in crate::window) editor_save_button: Button,
+    pub(in crate::window) password_spell_out_button: MenuButton,
     pub(in crate::window) copy_password_button: Button,
     pub(in crate::window) copy_username_button: Button,
     pub(in crate::window) copy_otp_button: Button,
     pub(in crate::window) text_view: TextView,
     pub(in crate::window) dynamic_fields_box: GtkBox,
+    pub(in crate::window) attachments_box: GtkBox,
+    pub(in crate::window) notes_preview_label: Label,
+    pub(in crate::window) notes_preview_button: ToggleButton,
+    pub(in crate::window) open_website_button: Button,
     pub(in crate::window) open_raw_button: Button,
     pub(in crate::window) preferences_password_generator_length_spin: SpinButton,
     pub(in crate::window) preferences_password_generator_min_lowercase_spin: SpinButton,
@@ -214,6 +278,12 @@ pub(in crate::window) struct WindowWidgets {
     pub(in crate::window) preferences_username_folder_row: ActionRow,
     pub(in crate::window) preferences_password_list_sort_filename_row: ActionRow,
     pub(in crate::window) preferences_password_list_sort_store_path_row: ActionRow,
+    pub(in crate::window) preferences_password_list_sort_top_folder_row: ActionRow,
+    pub(in crate::window) preferences_password_list_comfortable_row: ActionRow,
+    pub(in crate::window) preferences_password_list_compact_row: ActionRow,
+    pub(in crate::window) preferences_password_list_activation_open_row: ActionRow,
+    pub(in crate::window) preferences_password_list_activation_copy_password_row: ActionRow,
+    pub(in crate::window) preferences_password_list_activation_copy_otp_row: ActionRow,
     pub(in crate::window) preferences_password_generator_length_row: ActionRow,
     pub(in crate::window) preferences_password_generator_min_lowercase_row: ActionRow,
     pub(in crate::window) preferences_password_generator_min_uppercase_row: ActionRow,
@@ -246,6 +316,8 @@ impl WindowWidgets {
             store_button: required!("store_button"),
             window_title: required!("window_title"),
             primary_menu_button: required!("primary_menu_button"),
+            offline_indicator: required!("offline_indicator"),
+            profile_menu: required!("profile_menu"),
             tools_audit_filter_button: required!("tools_audit_filter_button"),
             tools_audit_filter_popover: required!("tools_audit_filter_popover"),
             tools_audit_filter_store_box: required!("tools_audit_filter_store_box"),
@@ -262,6 +334,9 @@ impl WindowWidgets {
             settings_password_list_group: required!("settings_password_list_group"),
             settings_template_group: required!("settings_template_group"),
             settings_clear_empty_fields_group: required!("settings_clear_empty_fields_group"),
+            settings_security_group: required!("settings_security_group"),
+            settings_privacy_group: required!("settings_privacy_group"),
+            settings_shortcuts_group: required!("settings_shortcuts_group"),
             settings_generator_group: required!("settings_generator_group"),
             tools_page: required!("tools_page"),
             tools_search_entry: required!("tools_search_entry"),
@@ -319,6 +394,8 @@ impl WindowWidgets {
             store_import_source_clear_button: required!("store_import_source_clear_button"),
             store_import_password_row: required!("store_import_password_row"),
             store_import_target_path_row: required!("store_import_target_path_row"),
+            store_import_organize_row: required!("store_import_organize_row"),
+            store_import_organize_check: required!("store_import_organize_check"),
             store_import_button: required!("store_import_button"),
             store_recipients_page: required!("store_recipients_page"),
             store_recipients_search_entry: required!("store_recipients_search_entry"),
@@ -404,6 +481,43 @@ impl WindowWidgets {
             new_pass_file_template_view: required!("new_pass_file_template_view"),
             clear_empty_fields_before_save_row: required!("clear_empty_fields_before_save_row"),
             clear_empty_fields_before_save_check: required!("clear_empty_fields_before_save_check"),
+            new_item_website_folder_row: required!("new_item_website_folder_row"),
+            fill_fields_from_path_row: required!("fill_fields_from_path_row"),
+            fill_fields_from_path_check: required!("fill_fields_from_path_check"),
+            copy_password_on_open_website_row: required!("copy_password_on_open_website_row"),
+            copy_password_on_open_website_check: required!("copy_password_on_open_website_check"),
+            expiry_reminders_row: required!("expiry_reminders_row"),
+            expiry_reminders_check: required!("expiry_reminders_check"),
+            review_changes_before_save_row: required!("review_changes_before_save_row"),
+            review_changes_before_save_check: required!("review_changes_before_save_check"),
+            git_gpg_diffs_row: required!("git_gpg_diffs_row"),
+            git_gpg_diffs_check: required!("git_gpg_diffs_check"),
+            large_file_limit_mib_row: required!("large_file_limit_mib_row"),
+            large_file_limit_mib_spin: required!("large_file_limit_mib_spin"),
+            refuse_large_files_row: required!("refuse_large_files_row"),
+            refuse_large_files_check: required!("refuse_large_files_check"),
+            clipboard_clear_seconds_row: required!("clipboard_clear_seconds_row"),
+            clipboard_clear_seconds_spin: required!("clipboard_clear_seconds_spin"),
+            auto_lock_minutes_row: required!("auto_lock_minutes_row"),
+            auto_lock_minutes_spin: required!("auto_lock_minutes_spin"),
+            privacy_mode_row: required!("privacy_mode_row"),
+            privacy_mode_check: required!("privacy_mode_check"),
+            reveal_passwords_briefly_row: required!("reveal_passwords_briefly_row"),
+            reveal_passwords_briefly_check: required!("reveal_passwords_briefly_check"),
+            password_reveal_seconds_row: required!("password_reveal_seconds_row"),
+            password_reveal_seconds_spin: required!("password_reveal_seconds_spin"),
+            search_item_contents_row: required!("search_item_contents_row"),
+            search_item_contents_check: required!("search_item_contents_check"),
+            personal_strength_words_row: required!("personal_strength_words_row"),
+            personal_strength_words_check: required!("personal_strength_words_check"),
+            strength_words_row: required!("strength_words_row"),
+            protected_folders_row: required!("protected_folders_row"),
+            activity_log_row: required!("activity_log_row"),
+            activity_log_check: required!("activity_log_check"),
+            app_pin_row: required!("app_pin_row"),
+            app_pin_check: required!("app_pin_check"),
+            remember_key_passwords_row: required!("remember_key_passwords_row"),
+            remember_key_passwords_check: required!("remember_key_passwords_check"),
             preferences_username_folder_check: required!("preferences_username_folder_check"),
             preferences_username_filename_check: required!("preferences_username_filename_check"),
             preferences_password_list_sort_filename_check: required!(
@@ -412,10 +526,29 @@ impl WindowWidgets {
             preferences_password_list_sort_store_path_check: required!(
                 "preferences_password_list_sort_store_path_check"
             ),
+            preferences_password_list_sort_top_folder_check: required!(
+                "preferences_password_list_sort_top_folder_check"
+            ),
+            preferences_password_list_comfortable_check: required!(
+                "preferences_password_list_comfortable_check"
+            ),
+            preferences_password_list_compact_check: required!(
+                "preferences_password_list_compact_check"
+            ),
+            preferences_password_list_activation_open_check: required!(
+                "preferences_password_list_activation_open_check"
+            ),
+            preferences_password_list_activation_copy_password_check: required!(
+                "preferences_password_list_activation_copy_password_check"
+            ),
+            preferences_password_list_activation_copy_otp_check: required!(
+                "preferences_password_list_activation_copy_otp_check"
+            ),
             password_stores: required!("password_stores"),
             password_store_actions: required!("password_store_actions"),
             navigation_view: required!("navigation_view"),
             search_entry: required!("search_entry"),
+            search_contents_button: required!("search_contents_button"),
             password_list_stack: required!("password_list_stack"),
             password_list_status: required!("password_list_status"),
             password_list_spinner: required!("password_list_spinner"),
@@ -423,9 +556,15 @@ impl WindowWidgets {
             list: required!("list"),
             password_page: required!("password_page"),
             raw_text_page: required!("raw_text_page"),
+            password_alias_banner: required!("password_alias_banner"),
+            password_draft_banner: required!("password_draft_banner"),
+            degraded_mode_banner: required!("degraded_mode_banner"),
             password_status: required!("password_status"),
+            password_store_row: required!("password_store_row"),
             password_entry: required!("password_entry"),
             password_analysis_label: required!("password_analysis_label"),
+            password_expiry_label: required!("password_expiry_label"),
+            password_dates_label: required!("password_dates_label"),
             password_generator_settings_button: required!("password_generator_settings_button"),
             password_generator_settings_revealer: required!("password_generator_settings_revealer"),
             password_generator_length_spin: required!("password_generator_length_spin"),
@@ -444,12 +583,18 @@ impl WindowWidgets {
             clean_pass_file_button: required!("clean_pass_file_button"),
             add_otp_button: required!("add_otp_button"),
             import_private_key_button: required!("import_private_key_button"),
+            attach_file_button: required!("attach_file_button"),
             editor_save_button: required!("editor_save_button"),
+            password_spell_out_button: required!("password_spell_out_button"),
             copy_password_button: required!("copy_password_button"),
             copy_username_button: required!("copy_username_button"),
             copy_otp_button: required!("copy_otp_button"),
             text_view: required!("text_view"),
             dynamic_fields_box: required!("dynamic_fields_box"),
+            attachments_box: required!("attachments_box"),
+            notes_preview_label: required!("notes_preview_label"),
+            notes_preview_button: required!("notes_preview_button"),
+            open_website_button: required!("open_website_button"),
             open_raw_button: required!("open_raw_button"),
             preferences_password_generator_length_spin: required!(
                 "preferences_password_generator_length_spin"
@@ -486,6 +631,24 @@ impl WindowWidgets {
             preferences_password_list_sort_store_path_row: required!(
                 "preferences_password_list_sort_store_path_row"
             ),
+            preferences_password_list_sort_top_folder_row: required!(
+                "preferences_password_list_sort_top_folder_row"
+            ),
+            preferences_password_list_comfortable_row: required!(
+                "preferences_password_list_comfortable_row"
+            ),
+            preferences_password_list_compact_row: required!(
+                "preferences_password_list_compact_row"
+            ),
+            preferences_password_list_activation_open_row: required!(
+                "preferences_password_list_activation_open_row"
+            ),
+            preferences_password_list_activation_copy_password_row: required!(
+                "preferences_password_list_activation_copy_password_row"
+            ),
+            preferences_password_list_activation_copy_otp_row: required!(
+                "preferences_password_list_activation_copy_otp_row"
+            ),
             preferences_password_generator_length_row: required!(
                 "preferences_password_generator_length_row"
             ),
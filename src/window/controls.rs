@@ -2,9 +2,9 @@ use crate::i18n::gettext;
 use crate::password::list::{load_passwords_async, PasswordListActions};
 use crate::password::model::OpenPassFile;
 use crate::password::page::{
-    open_password_entry_page, password_page_has_unsaved_changes,
+    confirm_leaving_password_page, open_password_entry_page, password_page_has_unsaved_changes,
     retry_open_password_entry_if_needed, revert_unsaved_password_changes, show_password_list_page,
-    PasswordPageState,
+    PasswordPageExit, PasswordPageState,
 };
 use crate::password::undo::{
     execute_undo_action, pop_undo_action, push_undo_action, unavailable_undo_message,
@@ -16,14 +16,15 @@ use crate::store::recipients_page::handle_store_recipients_subpage_back;
 use crate::support::actions::{activate_widget_action, register_window_action};
 use crate::support::background::spawn_result_task;
 use crate::support::runtime::{
-    has_host_permission, supports_docs_features, supports_host_command_features,
-    supports_logging_features,
+    has_host_permission, supports_host_command_features, supports_logging_features,
 };
 use crate::support::ui::{navigation_stack_is_root, visible_navigation_page_is};
 use crate::window::git::{handle_git_busy_back, GitActionState};
 use crate::window::navigation::{restore_window_for_current_page, WindowNavigationState};
+use crate::window::shortcuts::apply_configurable_shortcuts;
 use crate::window::tools::sync_tools_action_availability;
-use adw::gtk::{Button, ListBox, SearchEntry};
+use adw::glib::Propagation;
+use adw::gtk::{gdk, Button, Editable, EventControllerKey, ListBox, PropagationPhase, SearchEntry};
 use adw::prelude::*;
 use adw::ToastOverlay;
 use adw::{Application, ApplicationWindow, NavigationPage};
@@ -467,6 +468,59 @@ pub fn connect_search_visibility(find_button: &Button, search_entry: &SearchEntr
     });
 }
 
+fn type_to_search_character(key: gdk::Key, modifiers: gdk::ModifierType) -> Option<char> {
+    if modifiers.intersects(
+        gdk::ModifierType::CONTROL_MASK
+            | gdk::ModifierType::ALT_MASK
+            | gdk::ModifierType::SUPER_MASK
+            | gdk::ModifierType::META_MASK,
+    ) {
+        return None;
+    }
+    key.to_unicode()
+        .filter(|character| !character.is_control() && !character.is_whitespace())
+}
+
+fn focus_is_editable(window: &ApplicationWindow) -> bool {
+    adw::gtk::prelude::RootExt::focus(window).is_some_and(|focus| focus.is::<Editable>())
+}
+
+// Works like a GtkSearchBar key capture widget: printable keys typed on the
+// list page open the search and start filtering.
+pub fn connect_type_to_search(
+    window: &ApplicationWindow,
+    navigation: &WindowNavigationState,
+    find_button: &Button,
+    search_entry: &SearchEntry,
+) {
+    let controller = EventControllerKey::new();
+    controller.set_propagation_phase(PropagationPhase::Capture);
+    let window_for_keys = window.clone();
+    let navigation = navigation.clone();
+    let find_button = find_button.clone();
+    let search_entry = search_entry.clone();
+    controller.connect_key_pressed(move |_, key, _, modifiers| {
+        let Some(character) = type_to_search_character(key, modifiers) else {
+            return Propagation::Proceed;
+        };
+        if !navigation_stack_is_root(&navigation.nav)
+            || !find_button.is_visible()
+            || window_for_keys.visible_dialog().is_some()
+            || focus_is_editable(&window_for_keys)
+        {
+            return Propagation::Proceed;
+        }
+
+        let text = format!("{}{character}", search_entry.text());
+        search_entry.set_visible(true);
+        search_entry.grab_focus();
+        search_entry.set_text(&text);
+        search_entry.set_position(-1);
+        Propagation::Stop
+    });
+    window.add_controller(controller);
+}
+
 fn hide_and_clear_search_entry(search_entry: &SearchEntry, list: &ListBox) {
     hide_search_entry(search_entry);
     if !search_entry.text().is_empty() {
@@ -520,6 +574,9 @@ pub fn register_back_action(window: &adw::ApplicationWindow, state: &BackActionS
         if handle_store_recipients_subpage_back(&state.recipients_page) {
             return;
         }
+        if confirm_leaving_password_page(&state.password_page, PasswordPageExit::Back) {
+            return;
+        }
 
         state.navigation.nav.pop();
         if restore_window_for_current_page(
@@ -551,29 +608,7 @@ pub fn register_go_home_action(window: &adw::ApplicationWindow, state: &BackActi
 }
 
 pub fn configure_window_shortcuts(app: &Application) {
-    app.set_accels_for_action("win.back", &["Escape"]);
-    app.set_accels_for_action("win.go-home", &["Home"]);
-    app.set_accels_for_action("win.context-save", &["<primary>s"]);
-    app.set_accels_for_action("win.context-reload", &["F5"]);
-    app.set_accels_for_action("win.synchronize", &["<primary><shift>s"]);
-    app.set_accels_for_action("win.context-undo", &["<primary>z"]);
-    app.set_accels_for_action("win.toggle-find", &["<primary>f"]);
-    app.set_accels_for_action("win.toggle-hidden-and-duplicates", &["<primary>h"]);
-    app.set_accels_for_action("win.open-new-password", &["<primary>n"]);
-    app.set_accels_for_action("win.open-store-picker", &["<primary><shift>n"]);
-    app.set_accels_for_action("win.open-raw-pass-file", &["<primary><shift>r"]);
-    app.set_accels_for_action("win.copy-password", &["<primary><shift>c"]);
-    app.set_accels_for_action("win.copy-username", &["<primary><shift>u"]);
-    app.set_accels_for_action("win.copy-otp", &["<primary><shift>t"]);
-    app.set_accels_for_action("win.apply-pass-template", &["<primary><shift>a"]);
-    app.set_accels_for_action("win.add-pass-field", &["<primary><shift>f"]);
-    app.set_accels_for_action("win.add-otp-secret", &["<primary><shift>o"]);
-    app.set_accels_for_action("win.clean-pass-file", &["<primary><shift>k"]);
-    app.set_accels_for_action("win.generate-password", &["<primary><shift>g"]);
-    app.set_accels_for_action("win.toggle-password-options", &["<primary><shift>p"]);
-    app.set_accels_for_action("win.open-git", &["<primary>g"]);
-    app.set_accels_for_action("win.open-preferences", &["<primary>comma"]);
-    app.set_accels_for_action("win.open-tools", &["<primary>t"]);
+    apply_configurable_shortcuts(app);
 
     for slot in 1..=NUMBERED_STORE_SHORTCUT_COUNT {
         let recipients_action = format!("win.open-store-recipients-{slot}");
@@ -589,10 +624,6 @@ pub fn configure_window_shortcuts(app: &Application) {
         }
     }
 
-    if supports_docs_features() {
-        app.set_accels_for_action("win.open-docs", &["<primary><shift>d"]);
-    }
-    app.set_accels_for_action("app.shortcuts", &["<primary>question"]);
     configure_platform_shortcuts(app);
 }
 
@@ -649,8 +680,37 @@ pub fn apply_startup_query(
 mod tests {
     use super::{
         context_reload_target_from_page, context_save_target_from_page, toggled_list_visibility,
-        ContextReloadTarget, ContextSaveTarget, VisibleContextPage,
+        type_to_search_character, ContextReloadTarget, ContextSaveTarget, VisibleContextPage,
     };
+    use adw::gtk::gdk;
+
+    #[test]
+    fn only_plain_printable_keys_start_a_search() {
+        assert_eq!(
+            type_to_search_character(gdk::Key::g, gdk::ModifierType::empty()),
+            Some('g')
+        );
+        assert_eq!(
+            type_to_search_character(gdk::Key::G, gdk::ModifierType::SHIFT_MASK),
+            Some('G')
+        );
+        assert_eq!(
+            type_to_search_character(gdk::Key::f, gdk::ModifierType::CONTROL_MASK),
+            None
+        );
+        for key in [
+            gdk::Key::space,
+            gdk::Key::Return,
+            gdk::Key::Escape,
+            gdk::Key::BackSpace,
+            gdk::Key::Down,
+        ] {
+            assert_eq!(
+                type_to_search_character(key, gdk::ModifierType::empty()),
+                None
+            );
+        }
+    }
 
     #[test]
     fn context_save_prefers_password_pages() {
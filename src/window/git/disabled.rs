@@ -1,6 +1,7 @@
 use crate::store::git_page::StoreGitPageState;
 use crate::store::management::{StoreRecipientsPageState, NUMBERED_STORE_SHORTCUT_COUNT};
 use crate::support::actions::register_window_action;
+use crate::support::git::StoreCloneOptions;
 use crate::window::build::widgets::WindowWidgets;
 use crate::window::controls::ListVisibilityState;
 use crate::window::navigation::WindowNavigationState;
@@ -26,7 +27,11 @@ impl GitActionState {
     }
 }
 
-pub fn clone_store_repository(_url: &str, _store_root: &str) -> Result<(), String> {
+pub fn clone_store_repository(
+    _url: &str,
+    _store_root: &str,
+    _options: StoreCloneOptions,
+) -> Result<(), String> {
     Err("Host command features are only available on Linux.".to_string())
 }
 
@@ -1,7 +1,8 @@
 #[path = "operations.rs"]
 mod operations;
 
-use self::operations::{run_sync_operation, GitOperationResult};
+use self::operations::run_sync_recovery_operation;
+pub(crate) use self::operations::{run_sync_operation, GitOperationResult};
 use crate::i18n::gettext;
 use crate::logging::log_error;
 use crate::password::list::{load_passwords_async, PasswordListActions};
@@ -13,8 +14,10 @@ use crate::store::management::{
     configured_store_for_shortcut, prompt_store_clone, StoreRecipientsPageState,
     NUMBERED_STORE_SHORTCUT_COUNT,
 };
-use crate::support::actions::register_window_action;
+use crate::support::actions::{activate_widget_action, register_window_action};
 use crate::support::background::spawn_result_task;
+use crate::support::git::{StoreCloneOptions, StoreGitRecovery};
+use crate::support::network::{network_is_available, network_monitor};
 use crate::support::ui::{navigation_stack_is_root, visible_navigation_page_is};
 use crate::window::build::widgets::WindowWidgets;
 use crate::window::controls::ListVisibilityState;
@@ -23,11 +26,18 @@ use crate::window::navigation::{
     WindowNavigationState,
 };
 use crate::window::tools::sync_tools_action_availability;
-use adw::gio::{prelude::*, SimpleAction};
-use adw::gtk::ListBox;
-use adw::{ApplicationWindow, NavigationPage, StatusPage, Toast, ToastOverlay};
+use adw::gio::SimpleAction;
+use adw::gtk::{Image, ListBox};
+use adw::prelude::*;
+use adw::{
+    AlertDialog, ApplicationWindow, NavigationPage, ResponseAppearance, StatusPage, Toast,
+    ToastOverlay,
+};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+const SYNC_RECOVERY_DIALOG_BODY: &str = "The history of {store} was rewritten on the remote, so it can't sync as is. Rebase your local changes onto the new history, or force push to replace the remote with this copy.";
+
 #[derive(Clone)]
 pub struct GitActionState {
     pub window: ApplicationWindow,
@@ -39,6 +49,8 @@ pub struct GitActionState {
     pub busy_page: NavigationPage,
     pub busy_status: StatusPage,
     pub visibility: ListVisibilityState,
+    pub offline_indicator: Image,
+    pub sync_queued: Rc<Cell<bool>>,
 }
 
 impl GitActionState {
@@ -59,14 +71,21 @@ impl GitActionState {
             busy_page: widgets.git_busy_page.clone(),
             busy_status: widgets.git_busy_status.clone(),
             visibility: visibility.clone(),
+            offline_indicator: widgets.offline_indicator.clone(),
+            sync_queued: Rc::new(Cell::new(false)),
         }
     }
 }
 
-pub fn clone_store_repository(url: &str, store_root: &str) -> Result<(), String> {
-    match operations::run_clone_operation_at_root(url, store_root) {
-        GitOperationResult::Success => Ok(()),
+pub fn clone_store_repository(
+    url: &str,
+    store_root: &str,
+    options: StoreCloneOptions,
+) -> Result<(), String> {
+    match operations::run_clone_operation_at_root(url, store_root, options) {
+        GitOperationResult::Success | GitOperationResult::Notice(_) => Ok(()),
         GitOperationResult::Failed(message) => Err(message),
+        GitOperationResult::NeedsRecovery(_) => Err("Couldn't restore the store.".to_string()),
     }
 }
 
@@ -91,6 +110,8 @@ fn set_git_busy_actions_enabled(window: &ApplicationWindow, enabled: bool) {
         "context-save",
         "context-undo",
         "open-new-password",
+        "open-new-secure-note",
+        "open-new-folder",
         "toggle-find",
         "open-git",
         "open-raw-pass-file",
@@ -174,7 +195,23 @@ fn register_cloned_store(
     Ok(true)
 }
 
-fn start_prompted_clone(state: &GitActionState, store: String, url: String) {
+fn show_offline_clone_toast(state: &GitActionState) -> bool {
+    if network_is_available() {
+        return false;
+    }
+
+    state.overlay.add_toast(Toast::new(&gettext(
+        "You're offline. Connect to the internet to restore a store.",
+    )));
+    true
+}
+
+fn start_prompted_clone(
+    state: &GitActionState,
+    store: String,
+    url: String,
+    options: StoreCloneOptions,
+) {
     begin_git_operation(state, "Restoring store");
 
     let state_for_result = state.clone();
@@ -184,7 +221,7 @@ fn start_prompted_clone(state: &GitActionState, store: String, url: String) {
     let store_for_thread = store.clone();
     let store_for_result = store;
     spawn_result_task(
-        move || clone_store_repository(&url, &store_for_thread),
+        move || clone_store_repository(&url, &store_for_thread, options),
         move |result| match result {
             Ok(()) => match register_cloned_store(&settings_for_result, &store_for_result) {
                 Ok(_) => {
@@ -221,18 +258,24 @@ pub fn register_open_git_action(state: &GitActionState) {
     let window = state.window.clone();
     let clone_state = state.clone();
     register_window_action(&window, "git-clone", move || {
+        if show_offline_clone_toast(&clone_state) {
+            return;
+        }
         prompt_store_clone(&clone_state.window, &clone_state.overlay, {
             let state = clone_state.clone();
-            move |store, url| start_prompted_clone(&state, store, url)
+            move |store, url, options| start_prompted_clone(&state, store, url, options)
         });
     });
 
     let window = state.window.clone();
     let open_state = state.clone();
     register_window_action(&window, "open-git", move || {
+        if show_offline_clone_toast(&open_state) {
+            return;
+        }
         prompt_store_clone(&open_state.window, &open_state.overlay, {
             let state = open_state.clone();
-            move |store, url| start_prompted_clone(&state, store, url)
+            move |store, url, options| start_prompted_clone(&state, store, url, options)
         });
     });
 
@@ -263,10 +306,49 @@ pub fn register_open_git_action(state: &GitActionState) {
     }
 }
 
+// Shows the offline icon and runs a sync that was asked for while offline
+// as soon as the network comes back. The handler goes with the window.
+fn connect_network_monitor(state: &GitActionState) {
+    let monitor = network_monitor();
+    state
+        .offline_indicator
+        .set_visible(!monitor.is_network_available());
+
+    let window = state.window.downgrade();
+    let offline_indicator = state.offline_indicator.clone();
+    let sync_queued = state.sync_queued.clone();
+    let handler = monitor.connect_network_changed(move |_, available| {
+        offline_indicator.set_visible(!available);
+        let Some(window) = window.upgrade() else {
+            return;
+        };
+        if available && sync_queued.replace(false) {
+            activate_widget_action(&window, "win.synchronize");
+        }
+    });
+    let handler = RefCell::new(Some(handler));
+    state.window.connect_destroy(move |_| {
+        if let Some(handler) = handler.take() {
+            monitor.disconnect(handler);
+        }
+    });
+}
+
 pub fn register_synchronize_action(state: &GitActionState) {
+    connect_network_monitor(state);
+
     let window = state.window.clone();
     let state = state.clone();
     register_window_action(&window, "synchronize", move || {
+        if !network_is_available() {
+            if !state.sync_queued.replace(true) {
+                state.overlay.add_toast(Toast::new(&gettext(
+                    "You're offline. Keycord will sync when you're back online.",
+                )));
+            }
+            return;
+        }
+        state.sync_queued.set(false);
         begin_git_operation(&state, "Syncing stores");
 
         let state = state.clone();
@@ -277,16 +359,79 @@ pub fn register_synchronize_action(state: &GitActionState) {
                 GitOperationResult::Success => {
                     restore_after_git_operation_and_reload(&state);
                 }
+                GitOperationResult::Notice(message) => {
+                    restore_after_git_operation_and_reload(&state);
+                    state.overlay.add_toast(Toast::new(&gettext(message)));
+                }
                 GitOperationResult::Failed(message) => {
                     restore_after_git_operation_and_reload(&state);
                     state.overlay.add_toast(Toast::new(&gettext(&message)));
                 }
+                GitOperationResult::NeedsRecovery(root) => {
+                    restore_after_git_operation_and_reload(&state);
+                    prompt_sync_recovery(&state, root);
+                }
+            },
+            move || {
+                restore_after_git_operation_and_reload(&state_for_disconnect);
+            },
+        );
+    });
+}
+
+// Asks how to get past a remote whose history was rewritten, instead of
+// failing the same way on every sync.
+fn prompt_sync_recovery(state: &GitActionState, root: String) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Remote history changed"))
+        .body(gettext(SYNC_RECOVERY_DIALOG_BODY).replace("{store}", &root))
+        .build();
+    let cancel = gettext("Cancel");
+    let rebase = gettext("Rebase local changes");
+    let force_push = gettext("Force push");
+    dialog.add_responses(&[
+        ("cancel", cancel.as_str()),
+        ("rebase", rebase.as_str()),
+        ("force-push", force_push.as_str()),
+    ]);
+    dialog.set_response_appearance("rebase", ResponseAppearance::Suggested);
+    dialog.set_response_appearance("force-push", ResponseAppearance::Destructive);
+    dialog.set_close_response("cancel");
+    dialog.set_default_response(Some("rebase"));
+
+    let state = state.clone();
+    dialog.connect_response(None, move |_, response| {
+        let recovery = match response {
+            "rebase" => StoreGitRecovery::RebaseOntoRemote,
+            "force-push" => StoreGitRecovery::ForcePushWithLease,
+            _ => return,
+        };
+        begin_git_operation(&state, "Syncing stores");
+
+        let root = root.clone();
+        let state_for_result = state.clone();
+        let state_for_disconnect = state.clone();
+        spawn_result_task(
+            move || run_sync_recovery_operation(&root, recovery),
+            move |result| {
+                restore_after_git_operation_and_reload(&state_for_result);
+                let message = match result {
+                    GitOperationResult::Notice(message) => message.to_string(),
+                    GitOperationResult::Failed(message) => message,
+                    GitOperationResult::Success | GitOperationResult::NeedsRecovery(_) => {
+                        return;
+                    }
+                };
+                state_for_result
+                    .overlay
+                    .add_toast(Toast::new(&gettext(&message)));
             },
             move || {
                 restore_after_git_operation_and_reload(&state_for_disconnect);
             },
         );
     });
+    dialog.present(Some(&state.window));
 }
 
 pub fn handle_git_busy_back(state: &GitActionState) -> bool {
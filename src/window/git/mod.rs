@@ -8,6 +8,8 @@ use self::disabled as imp;
 #[cfg(target_os = "linux")]
 use self::enabled as imp;
 
+#[cfg(target_os = "linux")]
+pub(crate) use self::enabled::{run_sync_operation, GitOperationResult};
 pub use self::imp::{
     clone_store_repository, handle_git_busy_back, register_open_git_action,
     register_synchronize_action, set_git_action_availability, GitActionState,
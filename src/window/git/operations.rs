@@ -1,18 +1,37 @@
-use crate::logging::{log_error, log_info, run_command_output, CommandLogOptions};
-use crate::preferences::Preferences;
-use crate::support::git::{has_git_repository, sync_store_repository};
+use crate::backend::{lock_store_for_sync, publish_store_event, StoreEvent, STORE_BUSY_TOAST};
+use crate::logging::{log_error, log_info, log_span, run_command_output, CommandLogOptions};
+use crate::preferences::{Preferences, StoreWebDavFolder};
+use crate::support::activity::record_activity;
+use crate::support::git::{
+    apply_store_git_gpg_diff_preference, has_git_repository, recover_store_repository_sync,
+    store_git_sync_needs_recovery, sync_store_repository, StoreCloneOptions, StoreGitRecovery,
+};
+use crate::support::network::is_network_error;
 use crate::support::runtime::require_host_command_features;
+use crate::support::webdav::sync_store_webdav;
 
-pub(super) enum GitOperationResult {
+pub(crate) enum GitOperationResult {
     Success,
+    Notice(&'static str),
     Failed(String),
+    // The store at this root can't sync until its rewritten remote history
+    // is rebased onto or pushed over.
+    NeedsRecovery(String),
 }
 
+const GIT_CONFLICT_COPIES_NOTICE: &str =
+    "Some items were added or changed on two devices. The incoming copies were kept as conflict items.";
+
+const OFFLINE_REMOTE_TOAST: &str = "Can't reach the remote. Check your connection.";
+
 fn git_operation_failed(message: &str) -> GitOperationResult {
     GitOperationResult::Failed(message.to_string())
 }
 
 fn sync_failure_toast(err: &str) -> &'static str {
+    if is_network_error(err) {
+        return OFFLINE_REMOTE_TOAST;
+    }
     if err.contains("Local and remote commits are also waiting to sync") {
         return "Local changes found. Local and remote commits are also waiting to sync.";
     }
@@ -43,19 +62,45 @@ fn syncable_store_roots(stores: &[String]) -> Vec<&str> {
         .collect()
 }
 
-pub(super) fn run_clone_operation_at_root(url: &str, store_root: &str) -> GitOperationResult {
+fn webdav_sync_targets(settings: &Preferences) -> Vec<(String, StoreWebDavFolder)> {
+    settings
+        .stores()
+        .iter()
+        .zip(settings.store_roots())
+        .filter_map(|(store, root)| {
+            settings
+                .store_webdav_folder(store)
+                .map(|folder| (root, folder))
+        })
+        .collect()
+}
+
+pub(super) fn run_clone_operation_at_root(
+    url: &str,
+    store_root: &str,
+    options: StoreCloneOptions,
+) -> GitOperationResult {
     if let Err(message) = require_host_command_features() {
         return git_operation_failed(&message);
     }
 
     let mut cmd = Preferences::remote_git_command();
-    cmd.arg("clone").arg(url).arg(store_root);
+    cmd.arg("clone")
+        .args(options.clone_args())
+        .arg(url)
+        .arg(store_root);
     match run_command_output(
         &mut cmd,
         "Restore password store",
         CommandLogOptions::DEFAULT,
     ) {
-        Ok(output) if output.status.success() => GitOperationResult::Success,
+        Ok(output) if output.status.success() => {
+            apply_store_git_gpg_diff_preference(store_root);
+            GitOperationResult::Success
+        }
+        Ok(output) if is_network_error(&String::from_utf8_lossy(&output.stderr)) => {
+            git_operation_failed(OFFLINE_REMOTE_TOAST)
+        }
         Ok(_) => git_operation_failed("Couldn't restore the store."),
         Err(err) => {
             log_error(format!("Failed to start restore from Git: {err}"));
@@ -64,7 +109,47 @@ pub(super) fn run_clone_operation_at_root(url: &str, store_root: &str) -> GitOpe
     }
 }
 
-pub(super) fn run_sync_operation() -> GitOperationResult {
+pub(super) fn run_sync_recovery_operation(
+    root: &str,
+    recovery: StoreGitRecovery,
+) -> GitOperationResult {
+    let _lock = match lock_store_for_sync(root) {
+        Ok(lock) => lock,
+        Err(err) => {
+            log_error(format!(
+                "Failed to recover sync for password store '{root}': {err}"
+            ));
+            return git_operation_failed(STORE_BUSY_TOAST);
+        }
+    };
+    match recover_store_repository_sync(root, recovery) {
+        Ok(()) => {
+            record_activity(format!("Synced {root} with Git"));
+            publish_store_event(StoreEvent::Synced {
+                store: root.to_string(),
+            });
+            GitOperationResult::Notice("Store synced.")
+        }
+        Err(err) => {
+            log_error(format!(
+                "Failed to recover sync for password store '{root}': {err}"
+            ));
+            if is_network_error(&err) {
+                return git_operation_failed(OFFLINE_REMOTE_TOAST);
+            }
+            git_operation_failed(match recovery {
+                StoreGitRecovery::RebaseOntoRemote => {
+                    "Couldn't rebase local changes. Try a force push instead."
+                }
+                StoreGitRecovery::ForcePushWithLease => {
+                    "Couldn't force push. The remote changed again, so sync first."
+                }
+            })
+        }
+    }
+}
+
+pub(crate) fn run_sync_operation() -> GitOperationResult {
     if let Err(message) = require_host_command_features() {
         return git_operation_failed(&message);
     }
@@ -72,20 +157,85 @@ pub(super) fn run_sync_operation() -> GitOperationResult {
     let settings = Preferences::new();
     let stores = settings.stores();
     let syncable_roots = syncable_store_roots(&stores);
-    if syncable_roots.is_empty() {
-        log_info("Git sync skipped: no Git-backed password stores are configured.".to_string());
+    let webdav_targets = webdav_sync_targets(&settings);
+    if syncable_roots.is_empty() && webdav_targets.is_empty() {
+        log_info(
+            "Sync skipped: no Git-backed or WebDAV password stores are configured.".to_string(),
+        );
         return GitOperationResult::Success;
     }
 
+    let mut git_conflicts = 0;
     for root in syncable_roots {
-        if let Err(err) = sync_store_repository(root) {
-            log_error(format!("Failed to sync password store '{root}': {err}"));
-            return git_operation_failed(sync_failure_toast(&err));
+        let _span = log_span(format!("Git sync for '{root}'"));
+        let _lock = match lock_store_for_sync(root) {
+            Ok(lock) => lock,
+            Err(err) => {
+                log_error(format!("Failed to sync password store '{root}': {err}"));
+                return git_operation_failed(STORE_BUSY_TOAST);
+            }
+        };
+        match sync_store_repository(root) {
+            Ok(summary) => {
+                record_activity(format!("Synced {root} with Git"));
+                publish_store_event(StoreEvent::Synced {
+                    store: root.to_string(),
+                });
+                git_conflicts += summary.conflict_copies.len();
+            }
+            Err(err) => {
+                log_error(format!("Failed to sync password store '{root}': {err}"));
+                if store_git_sync_needs_recovery(&err) {
+                    return GitOperationResult::NeedsRecovery(root.to_string());
+                }
+                return git_operation_failed(sync_failure_toast(&err));
+            }
         }
     }
 
-    log_info("Git sync completed.".to_string());
-    GitOperationResult::Success
+    let mut conflicts = 0;
+    for (root, folder) in &webdav_targets {
+        let _span = log_span(format!("WebDAV sync for '{root}'"));
+        let _lock = match lock_store_for_sync(root) {
+            Ok(lock) => lock,
+            Err(err) => {
+                log_error(format!(
+                    "Failed to sync password store '{root}' with WebDAV: {err}"
+                ));
+                return git_operation_failed(STORE_BUSY_TOAST);
+            }
+        };
+        match sync_store_webdav(root, folder) {
+            Ok(summary) => {
+                log_info(format!(
+                    "WebDAV sync for '{root}': {} uploaded, {} downloaded, {} deleted, {} conflicts.",
+                    summary.uploaded, summary.downloaded, summary.deleted, summary.conflicts
+                ));
+                record_activity(format!("Synced {root} with WebDAV"));
+                publish_store_event(StoreEvent::Synced {
+                    store: root.to_string(),
+                });
+                conflicts += summary.conflicts;
+            }
+            Err(err) => {
+                log_error(format!(
+                    "Failed to sync password store '{root}' with WebDAV: {err}"
+                ));
+                return git_operation_failed("Couldn't sync with the WebDAV folder.");
+            }
+        }
+    }
+
+    log_info("Sync completed.".to_string());
+    if git_conflicts > 0 {
+        GitOperationResult::Notice(GIT_CONFLICT_COPIES_NOTICE)
+    } else if conflicts > 0 {
+        GitOperationResult::Notice(
+            "Some items changed on both sides. Their WebDAV copies were kept as conflict items.",
+        )
+    } else {
+        GitOperationResult::Success
+    }
 }
 
 #[cfg(test)]
@@ -142,6 +292,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_failure_toast_reports_unreachable_remotes_plainly() {
+        assert_eq!(
+            sync_failure_toast(
+                "git pull failed: fatal: unable to access 'https://example.com/store.git/': Could not resolve host: example.com"
+            ),
+            "Can't reach the remote. Check your connection."
+        );
+    }
+
     #[test]
     fn sync_failure_toast_reports_initial_commit_requirement_concisely() {
         assert_eq!(
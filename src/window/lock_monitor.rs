@@ -0,0 +1,164 @@
+use crate::backend::clear_runtime_secret_state;
+use crate::clipboard::clear_copied_secret;
+use crate::logging::{log_error, log_info};
+use crate::preferences::Preferences;
+use crate::support::actions::activate_widget_action;
+use crate::support::object_data::set_cloned_data;
+use crate::window::app_lock::lock_window_with_app_pin;
+use adw::gio::{self, BusType, DBusConnection, DBusSignalFlags, SignalSubscription};
+use adw::glib::{self, SourceId, WeakRef};
+use adw::prelude::*;
+use adw::ApplicationWindow;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const LOCK_MONITOR_KEY: &str = "session-lock-monitor";
+const SCREENSAVER_INTERFACES: &[&str] = &["org.gnome.ScreenSaver", "org.freedesktop.ScreenSaver"];
+const LOGIN1_BUS_NAME: &str = "org.freedesktop.login1";
+const LOGIN1_SESSION_INTERFACE: &str = "org.freedesktop.login1.Session";
+const LOGIN1_SESSION_PATH_PREFIX: &str = "/org/freedesktop/login1/session/";
+
+// Matches sd_bus_path_encode(), which is how logind names session objects.
+fn login1_session_path(session_id: &str) -> String {
+    let mut path = LOGIN1_SESSION_PATH_PREFIX.to_string();
+    if session_id.is_empty() {
+        path.push('_');
+        return path;
+    }
+
+    for (index, byte) in session_id.bytes().enumerate() {
+        if byte.is_ascii_alphabetic() || (byte.is_ascii_digit() && index > 0) {
+            path.push(char::from(byte));
+        } else {
+            path.push_str(&format!("_{byte:02x}"));
+        }
+    }
+    path
+}
+
+fn lock_window(window: &WeakRef<ApplicationWindow>, reason: &str) {
+    clear_runtime_secret_state();
+    clear_copied_secret();
+    if let Some(window) = window.upgrade() {
+        activate_widget_action(&window, "win.go-home");
+        lock_window_with_app_pin(&window);
+    }
+    log_info(format!("Locked private keys {reason}."));
+}
+
+fn bus(bus_type: BusType) -> Option<DBusConnection> {
+    match gio::bus_get_sync(bus_type, None::<&gio::Cancellable>) {
+        Ok(connection) => Some(connection),
+        Err(err) => {
+            log_error(format!("Failed to watch for session locks: {err}"));
+            None
+        }
+    }
+}
+
+fn subscribe_screensaver(
+    connection: &DBusConnection,
+    window: &WeakRef<ApplicationWindow>,
+) -> Vec<SignalSubscription> {
+    SCREENSAVER_INTERFACES
+        .iter()
+        .map(|interface| {
+            let window = window.clone();
+            connection.subscribe_to_signal(
+                None,
+                Some(interface),
+                Some("ActiveChanged"),
+                None,
+                None,
+                DBusSignalFlags::NONE,
+                move |signal| {
+                    if signal.parameters.get::<(bool,)>() == Some((true,)) {
+                        lock_window(&window, "because the screensaver locked the session");
+                    }
+                },
+            )
+        })
+        .collect()
+}
+
+fn subscribe_login1(
+    connection: &DBusConnection,
+    window: &WeakRef<ApplicationWindow>,
+) -> Option<SignalSubscription> {
+    let session_id = std::env::var("XDG_SESSION_ID").ok()?;
+    let path = login1_session_path(&session_id);
+    let window = window.clone();
+    Some(connection.subscribe_to_signal(
+        Some(LOGIN1_BUS_NAME),
+        Some(LOGIN1_SESSION_INTERFACE),
+        Some("Lock"),
+        Some(&path),
+        None,
+        DBusSignalFlags::NONE,
+        move |_| lock_window(&window, "because logind locked the session"),
+    ))
+}
+
+pub fn connect_session_lock_monitor(window: &ApplicationWindow) {
+    let weak = window.downgrade();
+    let mut subscriptions = Vec::new();
+    if let Some(connection) = bus(BusType::Session) {
+        subscriptions.extend(subscribe_screensaver(&connection, &weak));
+    }
+    if let Some(connection) = bus(BusType::System) {
+        subscriptions.extend(subscribe_login1(&connection, &weak));
+    }
+
+    // The subscriptions unsubscribe when the window drops this data.
+    set_cloned_data(window, LOCK_MONITOR_KEY, Rc::new(subscriptions));
+}
+
+// Locks the same way as a session lock once the window has been in the
+// background for the profile's auto-lock time.
+pub fn connect_auto_lock(window: &ApplicationWindow) {
+    let pending = Rc::new(RefCell::new(None::<SourceId>));
+    window.connect_is_active_notify(move |window| {
+        if let Some(source) = pending.borrow_mut().take() {
+            source.remove();
+        }
+        if window.is_active() {
+            return;
+        }
+
+        let minutes = Preferences::new().auto_lock_minutes();
+        if minutes == 0 {
+            return;
+        }
+        let pending_for_timeout = pending.clone();
+        let window = window.downgrade();
+        let source = glib::timeout_add_seconds_local_once(minutes.saturating_mul(60), move || {
+            pending_for_timeout.borrow_mut().take();
+            lock_window(
+                &window,
+                &format!("after {minutes} minutes in the background"),
+            );
+        });
+        *pending.borrow_mut() = Some(source);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::login1_session_path;
+
+    #[test]
+    fn login1_session_paths_escape_like_systemd() {
+        assert_eq!(
+            login1_session_path("3"),
+            "/org/freedesktop/login1/session/_33"
+        );
+        assert_eq!(
+            login1_session_path("c2"),
+            "/org/freedesktop/login1/session/c2"
+        );
+        assert_eq!(
+            login1_session_path("c-1"),
+            "/org/freedesktop/login1/session/c_2d1"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod app_lock;
 mod build;
 mod controls;
 #[cfg(feature = "docs")]
@@ -7,16 +8,23 @@ mod docs;
 mod docs;
 mod git;
 pub(crate) mod host_access;
+#[cfg(target_os = "linux")]
+mod lock_monitor;
 mod logs;
 pub mod navigation;
 mod preferences;
 pub(crate) mod preferences_search;
+mod profiles;
 pub(crate) mod session;
+mod shortcuts;
 mod tools;
 
+pub use self::build::copy_main_window_entry;
 pub use self::build::create_main_window;
 pub use self::build::dispatch_main_window_command;
 pub use self::git::clone_store_repository;
+#[cfg(target_os = "linux")]
+pub(crate) use self::git::{run_sync_operation, GitOperationResult};
 pub(crate) use self::host_access::append_optional_host_access_group_row;
 pub(crate) use self::tools::sync_tools_action_availability;
 
@@ -6,6 +6,7 @@ use super::state::{HasWindowChrome, WindowNavigationState};
 use crate::password::file::sync_username_row;
 use crate::password::opened::get_opened_pass_file;
 use crate::preferences::Preferences;
+use crate::store::appearance::store_entry_subtitle;
 use crate::store::git_page::{sync_store_git_page_header, StoreGitPageState};
 use crate::store::management::{sync_store_recipients_page_header, StoreRecipientsPageState};
 use crate::support::ui::{navigation_stack_is_root, visible_navigation_page_is};
@@ -90,8 +91,8 @@ pub fn restore_window_for_current_page(
     ));
     if page_kind == RestoredPageKind::Password {
         if let Some(pass_file) = get_opened_pass_file(&state.nav) {
-            let label = pass_file.label();
-            show_secondary_page_chrome(&chrome, pass_file.title(), &label, true);
+            let subtitle = store_entry_subtitle(pass_file.store_path(), &pass_file.label());
+            show_secondary_page_chrome(&chrome, pass_file.title(), &subtitle, true);
             state.raw.set_visible(true);
             sync_username_row(&state.username, Some(&pass_file));
         } else {
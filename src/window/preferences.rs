@@ -1,13 +1,20 @@
 use crate::i18n::gettext;
 use crate::logging::log_error;
+use crate::password::entry_files::normalize_password_entry_label;
 use crate::password::generation::{PasswordGenerationControls, PasswordGenerationSettings};
-use crate::preferences::{BackendKind, PasswordListSortMode, Preferences, UsernameFallbackMode};
+use crate::password::strength::sync_strength_dictionaries;
+use crate::preferences::{
+    BackendKind, PasswordListActivation, PasswordListDensity, PasswordListSortMode, Preferences,
+    UsernameFallbackMode,
+};
+use crate::private_key::keyring::forget_all_key_passwords;
 use crate::private_key::sync::{
     preflight_host_to_app_private_key_sync, sync_private_keys_with_host, PrivateKeySyncDirection,
 };
 use crate::store::management::{rebuild_store_list, StoreRecipientsPageState};
 use crate::support::actions::activate_widget_action;
 use crate::support::actions::register_window_action;
+use crate::support::background::spawn_result_task;
 use crate::support::git::git_command_available;
 use crate::support::runtime::{
     has_host_permission, supports_audit_features, supports_host_command_features,
@@ -16,14 +23,19 @@ use crate::support::ui::{
     connect_entry_row_apply_button_to_nonempty_text, focus_first_matching_list_row_in_order,
     list_row_is_keyboard_focusable, reveal_navigation_page,
 };
+use crate::window::app_lock::{present_app_pin_setup_dialog, remove_app_pin};
 use crate::window::navigation::{
     show_secondary_page_chrome, HasWindowChrome, WindowPageState, APP_WINDOW_TITLE,
 };
 use crate::window::preferences_search::PreferencesPageSearchState;
+use crate::window::shortcuts::{
+    apply_configurable_shortcuts, configurable_shortcuts, conflicting_shortcut, normalized_accel,
+    shortcut_accel, ConfigurableShortcut,
+};
 use adw::glib;
-use adw::gtk::{CheckButton, ListBox, TextView};
+use adw::gtk::{CheckButton, ListBox, SpinButton, TextView};
 use adw::prelude::*;
-use adw::{ActionRow, AlertDialog, ComboRow, EntryRow};
+use adw::{ActionRow, AlertDialog, ApplicationWindow, ComboRow, EntryRow, PreferencesGroup};
 use adw::{Toast, ToastOverlay};
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
@@ -402,6 +414,9 @@ pub fn connect_audit_history_recipient_row(state: &PreferencesActionState) {
 
 fn refresh_open_preferences_state(state: &PreferencesActionState, settings: &Preferences) {
     state.pass_row.set_text(&settings.command_value());
+    state
+        .new_item_website_folder_row
+        .set_text(&settings.new_item_website_folder());
     sync_backend_preferences_rows(
         &state.backend_row,
         &state.pass_row,
@@ -411,15 +426,83 @@ fn refresh_open_preferences_state(state: &PreferencesActionState, settings: &Pre
         &state.audit_use_commit_history_recipients_check,
         settings,
     );
-    sync_clear_empty_fields_before_save_check(
+    sync_boolean_preference_check(
         &state.clear_empty_fields_before_save_check,
         settings.clear_empty_fields_before_save(),
     );
+    sync_boolean_preference_check(
+        &state.fill_fields_from_path_check,
+        settings.fill_fields_from_path(),
+    );
+    sync_boolean_preference_check(
+        &state.copy_password_on_open_website_check,
+        settings.copy_password_on_open_website(),
+    );
+    sync_boolean_preference_check(&state.expiry_reminders_check, settings.expiry_reminders());
+    sync_boolean_preference_check(
+        &state.review_changes_before_save_check,
+        settings.review_changes_before_save(),
+    );
+    sync_boolean_preference_check(&state.git_gpg_diffs_check, settings.git_gpg_diffs());
+    sync_number_preference_spin(
+        &state.large_file_limit_mib_spin,
+        settings.large_file_limit_mib(),
+    );
+    sync_boolean_preference_check(
+        &state.refuse_large_files_check,
+        settings.refuse_large_files(),
+    );
+    sync_number_preference_spin(
+        &state.clipboard_clear_seconds_spin,
+        settings.default_clipboard_clear_seconds(),
+    );
+    sync_number_preference_spin(
+        &state.auto_lock_minutes_spin,
+        settings.default_auto_lock_minutes(),
+    );
+    sync_boolean_preference_check(&state.privacy_mode_check, settings.privacy_mode());
+    sync_boolean_preference_check(
+        &state.reveal_passwords_briefly_check,
+        settings.reveal_passwords_briefly(),
+    );
+    sync_number_preference_spin(
+        &state.password_reveal_seconds_spin,
+        settings.password_reveal_seconds(),
+    );
+    sync_boolean_preference_check(
+        &state.search_item_contents_check,
+        settings.search_item_contents(),
+    );
+    sync_boolean_preference_check(
+        &state.personal_strength_words_check,
+        settings.personal_strength_words(),
+    );
+    state
+        .strength_words_row
+        .set_text(&settings.strength_words().join(", "));
+    state
+        .protected_folders_row
+        .set_text(&settings.protected_folders().join(", "));
+    sync_boolean_preference_check(&state.activity_log_check, settings.activity_log_enabled());
+    sync_boolean_preference_check(&state.app_pin_check, settings.has_app_pin());
+    sync_boolean_preference_check(
+        &state.remember_key_passwords_check,
+        settings.remember_key_passwords(),
+    );
+    sync_shortcut_rows(&state.shortcut_rows, settings);
     sync_password_list_sort_checks(
-        &state.password_list_sort_filename_check,
-        &state.password_list_sort_store_path_check,
+        &state.password_list_sort_checks,
         settings.password_list_sort_mode(),
     );
+    sync_password_list_density_checks(
+        &state.password_list_comfortable_check,
+        &state.password_list_compact_check,
+        settings.password_list_density(),
+    );
+    sync_password_list_activation_checks(
+        &state.password_list_activation_checks,
+        settings.password_list_activation(),
+    );
 }
 
 fn refresh_preferences_page(state: &PreferencesActionState) {
@@ -488,10 +571,45 @@ pub struct PreferencesActionState {
     pub template_view: TextView,
     pub clear_empty_fields_before_save_row: ActionRow,
     pub clear_empty_fields_before_save_check: CheckButton,
+    pub new_item_website_folder_row: EntryRow,
+    pub fill_fields_from_path_row: ActionRow,
+    pub fill_fields_from_path_check: CheckButton,
+    pub copy_password_on_open_website_row: ActionRow,
+    pub copy_password_on_open_website_check: CheckButton,
+    pub expiry_reminders_row: ActionRow,
+    pub expiry_reminders_check: CheckButton,
+    pub review_changes_before_save_row: ActionRow,
+    pub review_changes_before_save_check: CheckButton,
+    pub git_gpg_diffs_row: ActionRow,
+    pub git_gpg_diffs_check: CheckButton,
+    pub large_file_limit_mib_spin: SpinButton,
+    pub refuse_large_files_row: ActionRow,
+    pub refuse_large_files_check: CheckButton,
+    pub clipboard_clear_seconds_spin: SpinButton,
+    pub auto_lock_minutes_spin: SpinButton,
+    pub privacy_mode_row: ActionRow,
+    pub privacy_mode_check: CheckButton,
+    pub reveal_passwords_briefly_row: ActionRow,
+    pub reveal_passwords_briefly_check: CheckButton,
+    pub password_reveal_seconds_spin: SpinButton,
+    pub search_item_contents_row: ActionRow,
+    pub search_item_contents_check: CheckButton,
+    pub personal_strength_words_row: ActionRow,
+    pub personal_strength_words_check: CheckButton,
+    pub strength_words_row: EntryRow,
+    pub protected_folders_row: EntryRow,
+    pub activity_log_row: ActionRow,
+    pub activity_log_check: CheckButton,
+    pub app_pin_row: ActionRow,
+    pub app_pin_check: CheckButton,
+    pub remember_key_passwords_row: ActionRow,
+    pub remember_key_passwords_check: CheckButton,
     pub username_folder_check: CheckButton,
     pub username_filename_check: CheckButton,
-    pub password_list_sort_filename_check: CheckButton,
-    pub password_list_sort_store_path_check: CheckButton,
+    pub password_list_sort_checks: PasswordListSortChecks,
+    pub password_list_comfortable_check: CheckButton,
+    pub password_list_compact_check: CheckButton,
+    pub password_list_activation_checks: PasswordListActivationChecks,
     pub generator_controls: PasswordGenerationControls,
     pub stores_list: ListBox,
     pub store_actions_list: ListBox,
@@ -503,18 +621,61 @@ pub struct PreferencesActionState {
     pub sync_private_keys_check: CheckButton,
     pub audit_use_commit_history_recipients_row: ActionRow,
     pub audit_use_commit_history_recipients_check: CheckButton,
+    pub shortcut_rows: Vec<(&'static ConfigurableShortcut, EntryRow)>,
 }
 
-fn sync_clear_empty_fields_before_save_check(check: &CheckButton, enabled: bool) {
+fn sync_boolean_preference_check(check: &CheckButton, enabled: bool) {
     if check.is_active() != enabled {
         check.set_active(enabled);
     }
 }
 
-pub fn connect_clear_empty_fields_before_save_autosave(
+fn sync_number_preference_spin(spin: &SpinButton, value: u32) {
+    if spin.value_as_int().max(0).cast_unsigned() != value {
+        spin.set_value(f64::from(value));
+    }
+}
+
+fn connect_number_preference_autosave(
+    spin: &SpinButton,
+    overlay: &ToastOverlay,
+    context: &'static str,
+    read: fn(&Preferences) -> u32,
+    write: fn(&Preferences, u32) -> Result<(), glib::BoolError>,
+) {
+    let overlay = overlay.clone();
+    let preferences = Preferences::new();
+    sync_number_preference_spin(spin, read(&preferences));
+
+    let syncing = Rc::new(Cell::new(false));
+    let syncing_for_change = syncing.clone();
+    spin.connect_value_changed(move |spin| {
+        if syncing_for_change.get() {
+            return;
+        }
+
+        let desired = spin.value_as_int().max(0).cast_unsigned();
+        let stored = read(&preferences);
+        if desired == stored {
+            return;
+        }
+
+        syncing_for_change.set(true);
+        if let Err(err) = write(&preferences, desired) {
+            toast_preferences_save_error(&overlay, context, &err);
+            spin.set_value(f64::from(stored));
+        }
+        syncing_for_change.set(false);
+    });
+}
+
+fn connect_boolean_preference_autosave(
     row: &ActionRow,
     check: &CheckButton,
     overlay: &ToastOverlay,
+    context: &'static str,
+    read: fn(&Preferences) -> bool,
+    write: fn(&Preferences, bool) -> Result<(), glib::BoolError>,
 ) {
     let check_for_row = check.clone();
     row.connect_activated(move |_| {
@@ -526,7 +687,7 @@ pub fn connect_clear_empty_fields_before_save_autosave(
 
     let overlay = overlay.clone();
     let preferences = Preferences::new();
-    sync_clear_empty_fields_before_save_check(check, preferences.clear_empty_fields_before_save());
+    sync_boolean_preference_check(check, read(&preferences));
 
     let syncing = Rc::new(Cell::new(false));
     let syncing_for_toggle = syncing.clone();
@@ -536,20 +697,468 @@ pub fn connect_clear_empty_fields_before_save_autosave(
         }
 
         let desired = button.is_active();
-        let stored = preferences.clear_empty_fields_before_save();
+        let stored = read(&preferences);
         if desired == stored {
             return;
         }
 
         syncing_for_toggle.set(true);
-        if let Err(err) = preferences.set_clear_empty_fields_before_save(desired) {
-            toast_preferences_save_error(&overlay, "clear empty fields before save", &err);
+        if let Err(err) = write(&preferences, desired) {
+            toast_preferences_save_error(&overlay, context, &err);
             button.set_active(stored);
         }
         syncing_for_toggle.set(false);
     });
 }
 
+pub fn connect_clear_empty_fields_before_save_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "clear empty fields before save",
+        Preferences::clear_empty_fields_before_save,
+        Preferences::set_clear_empty_fields_before_save,
+    );
+}
+
+pub fn connect_new_item_website_folder_row(
+    row: &EntryRow,
+    overlay: &ToastOverlay,
+    preferences: &Preferences,
+) {
+    row.set_text(&preferences.new_item_website_folder());
+    let overlay = overlay.clone();
+    let preferences = preferences.clone();
+    row.connect_apply(move |row| {
+        let folder = normalize_password_entry_label(&row.text());
+        row.set_text(&folder);
+        if let Err(err) = preferences.set_new_item_website_folder(&folder) {
+            toast_preferences_save_error(&overlay, "website folder", &err);
+        }
+    });
+}
+
+pub fn connect_fill_fields_from_path_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "fill in fields from the name",
+        Preferences::fill_fields_from_path,
+        Preferences::set_fill_fields_from_path,
+    );
+}
+
+pub fn connect_copy_password_on_open_website_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "copy password when opening a website",
+        Preferences::copy_password_on_open_website,
+        Preferences::set_copy_password_on_open_website,
+    );
+}
+
+pub fn connect_expiry_reminders_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "expiry reminders",
+        Preferences::expiry_reminders,
+        Preferences::set_expiry_reminders,
+    );
+}
+
+pub fn connect_review_changes_before_save_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "change review",
+        Preferences::review_changes_before_save,
+        Preferences::set_review_changes_before_save,
+    );
+}
+
+pub fn connect_git_gpg_diffs_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "decrypted Git diffs",
+        Preferences::git_gpg_diffs,
+        Preferences::set_git_gpg_diffs,
+    );
+}
+
+pub fn connect_large_file_limit_mib_autosave(spin: &SpinButton, overlay: &ToastOverlay) {
+    connect_number_preference_autosave(
+        spin,
+        overlay,
+        "large file limit",
+        Preferences::large_file_limit_mib,
+        Preferences::set_large_file_limit_mib,
+    );
+}
+
+pub fn connect_refuse_large_files_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "large file handling",
+        Preferences::refuse_large_files,
+        Preferences::set_refuse_large_files,
+    );
+}
+
+pub fn connect_privacy_mode_autosave(row: &ActionRow, check: &CheckButton, overlay: &ToastOverlay) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "privacy mode",
+        Preferences::privacy_mode,
+        Preferences::set_privacy_mode,
+    );
+}
+
+pub fn connect_reveal_passwords_briefly_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "brief password reveal",
+        Preferences::reveal_passwords_briefly,
+        Preferences::set_reveal_passwords_briefly,
+    );
+}
+
+pub fn connect_clipboard_clear_seconds_autosave(spin: &SpinButton, overlay: &ToastOverlay) {
+    connect_number_preference_autosave(
+        spin,
+        overlay,
+        "clipboard clear timeout",
+        Preferences::default_clipboard_clear_seconds,
+        Preferences::set_default_clipboard_clear_seconds,
+    );
+}
+
+pub fn connect_auto_lock_minutes_autosave(spin: &SpinButton, overlay: &ToastOverlay) {
+    connect_number_preference_autosave(
+        spin,
+        overlay,
+        "auto-lock timeout",
+        Preferences::default_auto_lock_minutes,
+        Preferences::set_default_auto_lock_minutes,
+    );
+}
+
+pub fn connect_password_reveal_seconds_autosave(spin: &SpinButton, overlay: &ToastOverlay) {
+    connect_number_preference_autosave(
+        spin,
+        overlay,
+        "password reveal duration",
+        Preferences::password_reveal_seconds,
+        Preferences::set_password_reveal_seconds,
+    );
+}
+
+pub fn connect_search_item_contents_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "content search",
+        Preferences::search_item_contents,
+        Preferences::set_search_item_contents,
+    );
+}
+
+// Words from item names are gathered while the list loads, so turning the
+// check on reloads the list to pick them up.
+pub fn connect_personal_strength_words_autosave(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "personal strength words",
+        Preferences::personal_strength_words,
+        Preferences::set_personal_strength_words,
+    );
+    check.connect_toggled(|check| {
+        sync_strength_dictionaries(&Preferences::new());
+        if check.is_active() {
+            activate_widget_action(check, "win.reload-password-list");
+        }
+    });
+}
+
+fn comma_separated_values(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn connect_strength_words_row(
+    row: &EntryRow,
+    overlay: &ToastOverlay,
+    preferences: &Preferences,
+) {
+    row.set_text(&preferences.strength_words().join(", "));
+    let overlay = overlay.clone();
+    let preferences = preferences.clone();
+    row.connect_apply(move |row| {
+        let words = comma_separated_values(&row.text());
+        if let Err(err) = preferences.set_strength_words(&words) {
+            toast_preferences_save_error(&overlay, "strength check words", &err);
+            return;
+        }
+        row.set_text(&preferences.strength_words().join(", "));
+        sync_strength_dictionaries(&preferences);
+    });
+}
+
+pub fn connect_protected_folders_row(
+    row: &EntryRow,
+    overlay: &ToastOverlay,
+    preferences: &Preferences,
+) {
+    row.set_text(&preferences.protected_folders().join(", "));
+    let overlay = overlay.clone();
+    let preferences = preferences.clone();
+    row.connect_apply(move |row| {
+        let folders = comma_separated_values(&row.text());
+        if let Err(err) = preferences.set_protected_folders(&folders) {
+            toast_preferences_save_error(&overlay, "protected folders", &err);
+            return;
+        }
+        row.set_text(&preferences.protected_folders().join(", "));
+    });
+}
+
+pub fn connect_activity_log_autosave(row: &ActionRow, check: &CheckButton, overlay: &ToastOverlay) {
+    connect_boolean_preference_autosave(
+        row,
+        check,
+        overlay,
+        "activity log",
+        Preferences::activity_log_enabled,
+        Preferences::set_activity_log_enabled,
+    );
+}
+
+pub fn connect_app_pin_row(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+    window: &ApplicationWindow,
+) {
+    let check_for_row = check.clone();
+    row.connect_activated(move |_| check_for_row.set_active(!check_for_row.is_active()));
+    sync_boolean_preference_check(check, Preferences::new().has_app_pin());
+
+    let overlay = overlay.clone();
+    let window = window.clone();
+    check.connect_toggled(move |button| {
+        let preferences = Preferences::new();
+        let enabled = button.is_active();
+        if enabled == preferences.has_app_pin() {
+            return;
+        }
+
+        if !enabled {
+            match remove_app_pin() {
+                Ok(()) => overlay.add_toast(Toast::new(&gettext("App PIN removed."))),
+                Err(err) => {
+                    toast_preferences_save_error(&overlay, "app PIN", &err);
+                    button.set_active(true);
+                }
+            }
+            return;
+        }
+
+        let button = button.clone();
+        let overlay = overlay.clone();
+        present_app_pin_setup_dialog(&window, move |result| match result {
+            Ok(true) => overlay.add_toast(Toast::new(&gettext("App PIN set."))),
+            Ok(false) => button.set_active(false),
+            Err(err) => {
+                toast_preferences_save_error(&overlay, "app PIN", &err);
+                button.set_active(false);
+            }
+        });
+    });
+}
+
+// Key passwords are only stored through the host keyring, so the row stays
+// hidden where host commands are unavailable.
+pub fn connect_remember_key_passwords_row(
+    row: &ActionRow,
+    check: &CheckButton,
+    overlay: &ToastOverlay,
+) {
+    row.set_visible(supports_host_command_features());
+    let check_for_row = check.clone();
+    row.connect_activated(move |_| check_for_row.set_active(!check_for_row.is_active()));
+    sync_boolean_preference_check(check, Preferences::new().remember_key_passwords());
+
+    let overlay = overlay.clone();
+    check.connect_toggled(move |button| {
+        let preferences = Preferences::new();
+        let enabled = button.is_active();
+        if enabled == preferences.remember_key_passwords() {
+            return;
+        }
+        if let Err(err) = preferences.set_remember_key_passwords(enabled) {
+            toast_preferences_save_error(&overlay, "remembered key passwords", &err);
+            button.set_active(!enabled);
+            return;
+        }
+        if enabled {
+            return;
+        }
+
+        let overlay_for_disconnect = overlay.clone();
+        let overlay = overlay.clone();
+        spawn_result_task(
+            forget_all_key_passwords,
+            move |result| match result {
+                Ok(()) => overlay.add_toast(Toast::new(&gettext("Saved key passwords forgotten."))),
+                Err(err) => {
+                    log_error(format!("Failed to forget saved key passwords: {err}"));
+                    overlay.add_toast(Toast::new(&gettext(
+                        "Couldn't forget the saved key passwords.",
+                    )));
+                }
+            },
+            move || {
+                overlay_for_disconnect.add_toast(Toast::new(&gettext(
+                    "Couldn't forget the saved key passwords.",
+                )));
+            },
+        );
+    });
+}
+
+pub fn build_shortcut_rows(
+    group: &PreferencesGroup,
+) -> Vec<(&'static ConfigurableShortcut, EntryRow)> {
+    configurable_shortcuts()
+        .map(|shortcut| {
+            let row = EntryRow::new();
+            row.set_title(&gettext(shortcut.title));
+            row.set_show_apply_button(true);
+            group.add(&row);
+            (shortcut, row)
+        })
+        .collect()
+}
+
+fn sync_shortcut_rows(rows: &[(&'static ConfigurableShortcut, EntryRow)], settings: &Preferences) {
+    let overrides = settings.shortcut_overrides();
+    for (shortcut, row) in rows {
+        row.set_text(&shortcut_accel(shortcut, &overrides));
+    }
+}
+
+fn save_shortcut_row(
+    shortcut: &ConfigurableShortcut,
+    row: &EntryRow,
+    window: &adw::ApplicationWindow,
+    overlay: &ToastOverlay,
+) {
+    let preferences = Preferences::new();
+    let text = row.text();
+    let accel = if text.trim().is_empty() {
+        None
+    } else {
+        let Some(accel) = normalized_accel(&text) else {
+            overlay.add_toast(Toast::new(&gettext("That isn't a valid shortcut.")));
+            return;
+        };
+        if let Some(conflict) =
+            conflicting_shortcut(shortcut.action, &accel, &preferences.shortcut_overrides())
+        {
+            overlay.add_toast(Toast::new(
+                &gettext("That shortcut is already used for {action}.")
+                    .replace("{action}", &gettext(conflict.title)),
+            ));
+            return;
+        }
+        (normalized_accel(shortcut.default_accel).as_ref() != Some(&accel)).then_some(accel)
+    };
+
+    if let Err(err) = preferences.set_shortcut_override(shortcut.action, accel.as_deref()) {
+        toast_preferences_save_error(overlay, "keyboard shortcut", &err);
+        return;
+    }
+    if let Some(app) = window.application() {
+        apply_configurable_shortcuts(&app);
+    }
+    row.set_text(&shortcut_accel(shortcut, &preferences.shortcut_overrides()));
+}
+
+// Clearing a row and applying it puts the default shortcut back.
+pub fn connect_shortcut_rows(
+    rows: &[(&'static ConfigurableShortcut, EntryRow)],
+    window: &adw::ApplicationWindow,
+    overlay: &ToastOverlay,
+) {
+    sync_shortcut_rows(rows, &Preferences::new());
+    for (shortcut, row) in rows {
+        let shortcut = *shortcut;
+        let window = window.clone();
+        let overlay = overlay.clone();
+        row.connect_apply(move |row| save_shortcut_row(shortcut, row, &window, &overlay));
+    }
+}
+
 pub fn connect_new_password_template_autosave(template_view: &TextView, overlay: &ToastOverlay) {
     let overlay = overlay.clone();
     let preferences = Preferences::new();
@@ -627,43 +1236,44 @@ pub fn connect_username_fallback_autosave(
     }
 }
 
-fn sync_password_list_sort_checks(
-    filename_check: &CheckButton,
-    store_path_check: &CheckButton,
-    mode: PasswordListSortMode,
-) {
-    let (filename_active, store_path_active) = password_list_sort_check_state(mode);
-    filename_check.set_active(filename_active);
-    store_path_check.set_active(store_path_active);
+#[derive(Clone)]
+pub struct PasswordListSortChecks {
+    pub filename: CheckButton,
+    pub store_path: CheckButton,
+    pub top_folder: CheckButton,
 }
 
-const fn password_list_sort_check_state(mode: PasswordListSortMode) -> (bool, bool) {
+fn sync_password_list_sort_checks(checks: &PasswordListSortChecks, mode: PasswordListSortMode) {
+    let (filename_active, store_path_active, top_folder_active) =
+        password_list_sort_check_state(mode);
+    checks.filename.set_active(filename_active);
+    checks.store_path.set_active(store_path_active);
+    checks.top_folder.set_active(top_folder_active);
+}
+
+const fn password_list_sort_check_state(mode: PasswordListSortMode) -> (bool, bool, bool) {
     match mode {
-        PasswordListSortMode::Filename => (true, false),
-        PasswordListSortMode::StorePath => (false, true),
+        PasswordListSortMode::Filename => (true, false, false),
+        PasswordListSortMode::StorePath => (false, true, false),
+        PasswordListSortMode::TopFolder => (false, false, true),
     }
 }
 
 pub fn connect_password_list_sort_autosave(
-    filename_check: &CheckButton,
-    store_path_check: &CheckButton,
+    checks: &PasswordListSortChecks,
     overlay: &ToastOverlay,
     window: &adw::ApplicationWindow,
 ) {
     let preferences = Preferences::new();
-    sync_password_list_sort_checks(
-        filename_check,
-        store_path_check,
-        preferences.password_list_sort_mode(),
-    );
+    sync_password_list_sort_checks(checks, preferences.password_list_sort_mode());
 
     let syncing = Rc::new(Cell::new(false));
     for (button, mode) in [
-        (filename_check.clone(), PasswordListSortMode::Filename),
-        (store_path_check.clone(), PasswordListSortMode::StorePath),
+        (checks.filename.clone(), PasswordListSortMode::Filename),
+        (checks.store_path.clone(), PasswordListSortMode::StorePath),
+        (checks.top_folder.clone(), PasswordListSortMode::TopFolder),
     ] {
-        let filename_check = filename_check.clone();
-        let store_path_check = store_path_check.clone();
+        let checks = checks.clone();
         let overlay = overlay.clone();
         let preferences = preferences.clone();
         let syncing = syncing.clone();
@@ -681,9 +1291,9 @@ pub fn connect_password_list_sort_autosave(
             syncing.set(true);
             if let Err(err) = preferences.set_password_list_sort_mode(mode) {
                 toast_preferences_save_error(&overlay, "password list sort", &err);
-                sync_password_list_sort_checks(&filename_check, &store_path_check, stored);
+                sync_password_list_sort_checks(&checks, stored);
             } else {
-                sync_password_list_sort_checks(&filename_check, &store_path_check, mode);
+                sync_password_list_sort_checks(&checks, mode);
                 activate_widget_action(&window, "win.reload-password-list");
             }
             syncing.set(false);
@@ -691,6 +1301,142 @@ pub fn connect_password_list_sort_autosave(
     }
 }
 
+fn sync_password_list_density_checks(
+    comfortable_check: &CheckButton,
+    compact_check: &CheckButton,
+    density: PasswordListDensity,
+) {
+    let (comfortable_active, compact_active) = password_list_density_check_state(density);
+    comfortable_check.set_active(comfortable_active);
+    compact_check.set_active(compact_active);
+}
+
+const fn password_list_density_check_state(density: PasswordListDensity) -> (bool, bool) {
+    match density {
+        PasswordListDensity::Comfortable => (true, false),
+        PasswordListDensity::Compact => (false, true),
+    }
+}
+
+pub fn connect_password_list_density_autosave(
+    comfortable_check: &CheckButton,
+    compact_check: &CheckButton,
+    overlay: &ToastOverlay,
+    window: &adw::ApplicationWindow,
+) {
+    let preferences = Preferences::new();
+    sync_password_list_density_checks(
+        comfortable_check,
+        compact_check,
+        preferences.password_list_density(),
+    );
+
+    let syncing = Rc::new(Cell::new(false));
+    for (button, density) in [
+        (comfortable_check.clone(), PasswordListDensity::Comfortable),
+        (compact_check.clone(), PasswordListDensity::Compact),
+    ] {
+        let comfortable_check = comfortable_check.clone();
+        let compact_check = compact_check.clone();
+        let overlay = overlay.clone();
+        let preferences = preferences.clone();
+        let syncing = syncing.clone();
+        let window = window.clone();
+        button.connect_toggled(move |button| {
+            if syncing.get() || !button.is_active() {
+                return;
+            }
+
+            let stored = preferences.password_list_density();
+            if stored == density {
+                return;
+            }
+
+            syncing.set(true);
+            if let Err(err) = preferences.set_password_list_density(density) {
+                toast_preferences_save_error(&overlay, "password list density", &err);
+                sync_password_list_density_checks(&comfortable_check, &compact_check, stored);
+            } else {
+                sync_password_list_density_checks(&comfortable_check, &compact_check, density);
+                activate_widget_action(&window, "win.reload-password-list");
+            }
+            syncing.set(false);
+        });
+    }
+}
+
+#[derive(Clone)]
+pub struct PasswordListActivationChecks {
+    pub open: CheckButton,
+    pub copy_password: CheckButton,
+    pub copy_otp: CheckButton,
+}
+
+fn sync_password_list_activation_checks(
+    checks: &PasswordListActivationChecks,
+    activation: PasswordListActivation,
+) {
+    let (open_active, copy_password_active, copy_otp_active) =
+        password_list_activation_check_state(activation);
+    checks.open.set_active(open_active);
+    checks.copy_password.set_active(copy_password_active);
+    checks.copy_otp.set_active(copy_otp_active);
+}
+
+const fn password_list_activation_check_state(
+    activation: PasswordListActivation,
+) -> (bool, bool, bool) {
+    match activation {
+        PasswordListActivation::Open => (true, false, false),
+        PasswordListActivation::CopyPassword => (false, true, false),
+        PasswordListActivation::CopyOtp => (false, false, true),
+    }
+}
+
+// The list reads this setting when a row is activated, so a change needs
+// no reload.
+pub fn connect_password_list_activation_autosave(
+    checks: &PasswordListActivationChecks,
+    overlay: &ToastOverlay,
+) {
+    let preferences = Preferences::new();
+    sync_password_list_activation_checks(checks, preferences.password_list_activation());
+
+    let syncing = Rc::new(Cell::new(false));
+    for (button, activation) in [
+        (checks.open.clone(), PasswordListActivation::Open),
+        (
+            checks.copy_password.clone(),
+            PasswordListActivation::CopyPassword,
+        ),
+        (checks.copy_otp.clone(), PasswordListActivation::CopyOtp),
+    ] {
+        let checks = checks.clone();
+        let overlay = overlay.clone();
+        let preferences = preferences.clone();
+        let syncing = syncing.clone();
+        button.connect_toggled(move |button| {
+            if syncing.get() || !button.is_active() {
+                return;
+            }
+
+            let stored = preferences.password_list_activation();
+            if stored == activation {
+                return;
+            }
+
+            syncing.set(true);
+            if let Err(err) = preferences.set_password_list_activation(activation) {
+                toast_preferences_save_error(&overlay, "password list activation", &err);
+                sync_password_list_activation_checks(&checks, stored);
+            } else {
+                sync_password_list_activation_checks(&checks, activation);
+            }
+            syncing.set(false);
+        });
+    }
+}
+
 pub fn connect_password_generation_autosave(
     controls: &PasswordGenerationControls,
     mirrors: &[PasswordGenerationControls],
@@ -760,9 +1506,13 @@ pub fn register_open_preferences_action(
 mod tests {
     use super::{
         available_backend_kinds, backend_kind_for_combo_position, combo_position_for_backend_kind,
+        password_list_activation_check_state, password_list_density_check_state,
         password_list_sort_check_state, username_fallback_check_state,
     };
-    use crate::preferences::{BackendKind, PasswordListSortMode, UsernameFallbackMode};
+    use crate::preferences::{
+        BackendKind, PasswordListActivation, PasswordListDensity, PasswordListSortMode,
+        UsernameFallbackMode,
+    };
 
     #[test]
     fn username_fallback_sync_marks_only_the_selected_mode() {
@@ -780,14 +1530,46 @@ mod tests {
     fn password_list_sort_sync_marks_only_the_selected_mode() {
         assert_eq!(
             password_list_sort_check_state(PasswordListSortMode::Filename),
-            (true, false)
+            (true, false, false)
         );
         assert_eq!(
             password_list_sort_check_state(PasswordListSortMode::StorePath),
+            (false, true, false)
+        );
+        assert_eq!(
+            password_list_sort_check_state(PasswordListSortMode::TopFolder),
+            (false, false, true)
+        );
+    }
+
+    #[test]
+    fn password_list_density_sync_marks_only_the_selected_density() {
+        assert_eq!(
+            password_list_density_check_state(PasswordListDensity::Comfortable),
+            (true, false)
+        );
+        assert_eq!(
+            password_list_density_check_state(PasswordListDensity::Compact),
             (false, true)
         );
     }
 
+    #[test]
+    fn password_list_activation_sync_marks_only_the_selected_behavior() {
+        assert_eq!(
+            password_list_activation_check_state(PasswordListActivation::Open),
+            (true, false, false)
+        );
+        assert_eq!(
+            password_list_activation_check_state(PasswordListActivation::CopyPassword),
+            (false, true, false)
+        );
+        assert_eq!(
+            password_list_activation_check_state(PasswordListActivation::CopyOtp),
+            (false, false, true)
+        );
+    }
+
     #[test]
     fn backend_combo_round_trips_available_backends() {
         for backend in available_backend_kinds() {
@@ -0,0 +1,335 @@
+use crate::backend::clear_runtime_secret_state;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::{
+    Preferences, WorkspaceProfile, MAX_AUTO_LOCK_MINUTES, MAX_CLIPBOARD_CLEAR_SECONDS,
+};
+use crate::support::actions::{activate_widget_action, register_window_action};
+use crate::support::ui::{append_action_group_row_with_button, dialog_content_shell};
+use adw::gio::{Menu, MenuItem, SimpleAction};
+use adw::glib::VariantTy;
+use adw::gtk::{Align, Box as GtkBox, Button, Label, Orientation};
+use adw::prelude::*;
+use adw::{
+    ApplicationWindow, Dialog, EntryRow, PreferencesGroup, PreferencesPage, SpinRow, Toast,
+    ToastOverlay,
+};
+
+const SWITCH_PROFILE_ACTION: &str = "switch-profile";
+
+#[derive(Clone)]
+pub struct WorkspaceProfileState {
+    pub window: ApplicationWindow,
+    pub overlay: ToastOverlay,
+    pub menu: Menu,
+}
+
+fn profile_from_input(
+    base: WorkspaceProfile,
+    name: &str,
+    git_name: &str,
+    git_email: &str,
+    clipboard_clear_seconds: f64,
+    auto_lock_minutes: f64,
+) -> Result<WorkspaceProfile, &'static str> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Enter a profile name.");
+    }
+    let git_name = git_name.trim();
+    let git_email = git_email.trim();
+    if git_name.is_empty() != git_email.is_empty() {
+        return Err("Enter both a Git name and email, or neither.");
+    }
+    if !git_email.is_empty() && !git_email.contains('@') {
+        return Err("Enter a valid Git email.");
+    }
+
+    Ok(WorkspaceProfile {
+        name: name.to_string(),
+        git_name: git_name.to_string(),
+        git_email: git_email.to_string(),
+        clipboard_clear_seconds: clipboard_clear_seconds
+            .round()
+            .clamp(0.0, f64::from(MAX_CLIPBOARD_CLEAR_SECONDS))
+            as u32,
+        auto_lock_minutes: auto_lock_minutes
+            .round()
+            .clamp(0.0, f64::from(MAX_AUTO_LOCK_MINUTES)) as u32,
+        ..base
+    })
+}
+
+pub fn rebuild_workspace_profile_menu(state: &WorkspaceProfileState) {
+    let settings = Preferences::new();
+    let active = settings
+        .active_workspace_profile()
+        .map(|profile| profile.name)
+        .unwrap_or_default();
+    if let Some(action) = state
+        .window
+        .lookup_action(SWITCH_PROFILE_ACTION)
+        .and_then(|action| action.downcast::<SimpleAction>().ok())
+    {
+        action.set_state(&active.to_variant());
+    }
+
+    state.menu.remove_all();
+    let profiles = Menu::new();
+    for profile in settings.workspace_profiles() {
+        let item = MenuItem::new(Some(&profile.name), None);
+        item.set_action_and_target_value(
+            Some(&format!("win.{SWITCH_PROFILE_ACTION}")),
+            Some(&profile.name.to_variant()),
+        );
+        profiles.append_item(&item);
+    }
+    state.menu.append_section(None, &profiles);
+
+    let actions = Menu::new();
+    actions.append(
+        Some(&gettext("_New Profile from Current Setup")),
+        Some("win.new-profile"),
+    );
+    if !active.is_empty() {
+        actions.append(Some(&gettext("_Edit Profile")), Some("win.edit-profile"));
+    }
+    state.menu.append_section(None, &actions);
+}
+
+fn switch_workspace_profile(state: &WorkspaceProfileState, name: &str) {
+    match Preferences::new().activate_workspace_profile(name) {
+        Ok(true) => {
+            clear_runtime_secret_state();
+            rebuild_workspace_profile_menu(state);
+            activate_widget_action(&state.window, "win.go-home");
+            state
+                .overlay
+                .add_toast(Toast::new(&gettext("Switched profile.")));
+        }
+        Ok(false) => rebuild_workspace_profile_menu(state),
+        Err(err) => {
+            log_error(format!("Failed to switch to profile '{name}': {err}"));
+            state
+                .overlay
+                .add_toast(Toast::new(&gettext("Couldn't switch profile.")));
+        }
+    }
+}
+
+fn spin_row(title: &str, subtitle: &str, max: f64, value: u32) -> SpinRow {
+    let row = SpinRow::with_range(0.0, max, 1.0);
+    row.set_title(&gettext(title));
+    row.set_subtitle(&gettext(subtitle));
+    row.set_value(f64::from(value));
+    row
+}
+
+fn present_workspace_profile_dialog(state: &WorkspaceProfileState, existing: Option<String>) {
+    let settings = Preferences::new();
+    let profile = settings.current_setup_as_workspace_profile(existing.as_deref().unwrap_or(""));
+
+    let name_row = EntryRow::new();
+    name_row.set_title(&gettext("Name"));
+    name_row.set_text(&profile.name);
+    let git_name_row = EntryRow::new();
+    git_name_row.set_title(&gettext("Git author name"));
+    git_name_row.set_text(&profile.git_name);
+    let git_email_row = EntryRow::new();
+    git_email_row.set_title(&gettext("Git author email"));
+    git_email_row.set_text(&profile.git_email);
+    let clipboard_row = spin_row(
+        "Clear clipboard after",
+        "Seconds. Use 0 to keep copied values.",
+        f64::from(MAX_CLIPBOARD_CLEAR_SECONDS),
+        profile.clipboard_clear_seconds,
+    );
+    let auto_lock_row = spin_row(
+        "Lock keys after",
+        "Minutes in the background. Use 0 to stay unlocked.",
+        f64::from(MAX_AUTO_LOCK_MINUTES),
+        profile.auto_lock_minutes,
+    );
+
+    let group = PreferencesGroup::builder()
+        .description(gettext(
+            "The profile keeps the current stores and private key. Switching profiles later restores them.",
+        ))
+        .build();
+    group.add(&name_row);
+    group.add(&git_name_row);
+    group.add(&git_email_row);
+    group.add(&clipboard_row);
+    group.add(&auto_lock_row);
+
+    let page = PreferencesPage::new();
+    page.add(&group);
+
+    let error_label = Label::new(None);
+    error_label.set_halign(Align::Start);
+    error_label.set_wrap(true);
+    error_label.add_css_class("error");
+    error_label.add_css_class("caption");
+    error_label.set_margin_start(18);
+    error_label.set_margin_end(18);
+    error_label.set_visible(false);
+
+    let save_button = Button::with_label(&gettext("Save"));
+    save_button.add_css_class("suggested-action");
+    save_button.add_css_class("pill");
+    save_button.set_halign(Align::Center);
+    save_button.set_margin_top(12);
+    save_button.set_margin_bottom(18);
+
+    let content = GtkBox::new(Orientation::Vertical, 0);
+    content.append(&page);
+    content.append(&error_label);
+    content.append(&save_button);
+
+    let title = if existing.is_some() {
+        "Edit profile"
+    } else {
+        "New profile"
+    };
+    let dialog = Dialog::builder()
+        .title(gettext(title))
+        .content_width(640)
+        .follows_content_size(true)
+        .child(&dialog_content_shell(title, existing.as_deref(), &content))
+        .build();
+
+    if let Some(name) = existing.clone() {
+        let remove_group = PreferencesGroup::new();
+        let state = state.clone();
+        let dialog = dialog.clone();
+        append_action_group_row_with_button(
+            &remove_group,
+            "Remove profile",
+            "Forget this profile. Stores and keys stay as they are.",
+            "user-trash-symbolic",
+            move || match Preferences::new().remove_workspace_profile(&name) {
+                Ok(()) => {
+                    rebuild_workspace_profile_menu(&state);
+                    dialog.close();
+                }
+                Err(err) => {
+                    log_error(format!("Failed to remove profile '{name}': {err}"));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't remove the profile.")));
+                }
+            },
+        );
+        page.add(&remove_group);
+    }
+
+    {
+        let state = state.clone();
+        let dialog = dialog.clone();
+        let error_label = error_label.clone();
+        save_button.connect_clicked(move |_| {
+            let result = profile_from_input(
+                profile.clone(),
+                &name_row.text(),
+                &git_name_row.text(),
+                &git_email_row.text(),
+                clipboard_row.value(),
+                auto_lock_row.value(),
+            );
+            let profile = match result {
+                Ok(profile) => profile,
+                Err(message) => {
+                    error_label.set_label(&gettext(message));
+                    error_label.set_visible(true);
+                    return;
+                }
+            };
+
+            match Preferences::new().save_workspace_profile(existing.as_deref(), profile) {
+                Ok(()) => {
+                    rebuild_workspace_profile_menu(&state);
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Profile saved.")));
+                    dialog.close();
+                }
+                Err(err) => {
+                    log_error(format!("Failed to save profile: {err}"));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't save the profile.")));
+                }
+            }
+        });
+    }
+
+    dialog.present(Some(&state.window));
+}
+
+pub fn register_workspace_profile_actions(state: &WorkspaceProfileState) {
+    let active = Preferences::new()
+        .active_workspace_profile()
+        .map(|profile| profile.name)
+        .unwrap_or_default();
+    let action = SimpleAction::new_stateful(
+        SWITCH_PROFILE_ACTION,
+        Some(VariantTy::STRING),
+        &active.to_variant(),
+    );
+    {
+        let state = state.clone();
+        action.connect_activate(move |_, parameter| {
+            if let Some(name) = parameter.and_then(|parameter| parameter.get::<String>()) {
+                switch_workspace_profile(&state, &name);
+            }
+        });
+    }
+    state.window.add_action(&action);
+
+    {
+        let state = state.clone();
+        register_window_action(&state.window.clone(), "new-profile", move || {
+            present_workspace_profile_dialog(&state, None);
+        });
+    }
+    {
+        let state = state.clone();
+        register_window_action(&state.window.clone(), "edit-profile", move || {
+            let active = Preferences::new()
+                .active_workspace_profile()
+                .map(|profile| profile.name);
+            if active.is_some() {
+                present_workspace_profile_dialog(&state, active);
+            }
+        });
+    }
+
+    rebuild_workspace_profile_menu(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::profile_from_input;
+    use crate::preferences::WorkspaceProfile;
+
+    #[test]
+    fn profile_input_requires_a_name_and_a_complete_git_identity() {
+        let base = WorkspaceProfile {
+            stores: vec!["~/work-store".to_string()],
+            ..WorkspaceProfile::default()
+        };
+
+        assert!(profile_from_input(base.clone(), " ", "", "", 0.0, 0.0).is_err());
+        assert!(profile_from_input(base.clone(), "Work", "Alice", "", 0.0, 0.0).is_err());
+        assert!(profile_from_input(base.clone(), "Work", "Alice", "alice", 0.0, 0.0).is_err());
+
+        let profile =
+            profile_from_input(base, " Work ", " Alice ", "alice@example.com", 45.0, 9999.0)
+                .expect("valid profile");
+        assert_eq!(profile.name, "Work");
+        assert_eq!(profile.git_name, "Alice");
+        assert_eq!(profile.stores, vec!["~/work-store".to_string()]);
+        assert_eq!(profile.clipboard_clear_seconds, 45);
+        assert_eq!(profile.auto_lock_minutes, 240);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::preferences::Preferences;
+use crate::support::runtime::supports_docs_features;
+use adw::gtk;
+use adw::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfigurableShortcut {
+    pub action: &'static str,
+    pub title: &'static str,
+    pub default_accel: &'static str,
+}
+
+const fn shortcut(
+    action: &'static str,
+    title: &'static str,
+    default_accel: &'static str,
+) -> ConfigurableShortcut {
+    ConfigurableShortcut {
+        action,
+        title,
+        default_accel,
+    }
+}
+
+// Titles match the shortcuts window so both places share one translation.
+const CONFIGURABLE_SHORTCUTS: &[ConfigurableShortcut] = &[
+    shortcut("win.open-new-password", "Open a new item", "<primary>n"),
+    shortcut(
+        "win.context-save",
+        "Save current page or sync from home",
+        "<primary>s",
+    ),
+    shortcut(
+        "win.open-raw-pass-file",
+        "Open raw text",
+        "<primary><shift>r",
+    ),
+    shortcut("win.copy-password", "Copy password", "<primary><shift>c"),
+    shortcut("win.copy-username", "Copy username", "<primary><shift>u"),
+    shortcut("win.copy-otp", "Copy OTP", "<primary><shift>t"),
+    shortcut("win.open-website", "Open website", "<primary><shift>w"),
+    shortcut(
+        "win.show-large-type",
+        "Show in large type",
+        "<primary><shift>l",
+    ),
+    shortcut(
+        "win.type-password",
+        "Type password into another window",
+        "<primary><shift>y",
+    ),
+    shortcut(
+        "win.apply-pass-template",
+        "Apply template",
+        "<primary><shift>a",
+    ),
+    shortcut("win.add-pass-field", "Add field", "<primary><shift>f"),
+    shortcut("win.add-otp-secret", "Add OTP field", "<primary><shift>o"),
+    shortcut(
+        "win.toggle-password-options",
+        "Password options",
+        "<primary><shift>p",
+    ),
+    shortcut(
+        "win.clean-pass-file",
+        "Clean pass file",
+        "<primary><shift>k",
+    ),
+    shortcut(
+        "win.generate-password",
+        "Generate password",
+        "<primary><shift>g",
+    ),
+    shortcut("win.context-undo", "Undo or revert changes", "<primary>z"),
+    shortcut("win.toggle-find", "Find item", "<primary>f"),
+    shortcut(
+        "win.toggle-hidden-and-duplicates",
+        "Show hidden and duplicate entries",
+        "<primary>h",
+    ),
+    shortcut("win.synchronize", "Sync stores", "<primary><shift>s"),
+    shortcut("win.context-reload", "Refresh current list", "F5"),
+    shortcut("win.back", "Go back", "Escape"),
+    shortcut("win.go-home", "Go home", "Home"),
+    shortcut(
+        "win.open-store-picker",
+        "Add or create store",
+        "<primary><shift>n",
+    ),
+    shortcut("win.open-git", "Open Git tools", "<primary>g"),
+    shortcut("win.open-tools", "Open tools", "<primary>t"),
+    shortcut("win.open-docs", "Open docs", "<primary><shift>d"),
+    shortcut("win.open-preferences", "Open preferences", "<primary>comma"),
+    shortcut("app.shortcuts", "Show shortcuts", "<primary>question"),
+];
+
+fn shortcut_is_available(shortcut: &ConfigurableShortcut) -> bool {
+    shortcut.action != "win.open-docs" || supports_docs_features()
+}
+
+pub fn configurable_shortcuts() -> impl Iterator<Item = &'static ConfigurableShortcut> {
+    CONFIGURABLE_SHORTCUTS
+        .iter()
+        .filter(|shortcut| shortcut_is_available(shortcut))
+}
+
+// Accelerators are compared in GTK's canonical spelling, so `<Ctrl>S` and
+// `<primary>s` count as the same shortcut.
+pub fn normalized_accel(accel: &str) -> Option<String> {
+    let (key, modifiers) = gtk::accelerator_parse(accel.trim())?;
+    Some(gtk::accelerator_name(key, modifiers).to_string())
+}
+
+// A stored override that no longer parses falls back to the default, so a
+// hand-edited setting can't leave an action without a shortcut.
+fn resolved_shortcut_accel(
+    shortcut: &ConfigurableShortcut,
+    overrides: &[(String, String)],
+    normalize: impl Fn(&str) -> Option<String>,
+) -> String {
+    overrides
+        .iter()
+        .find(|(action, _)| action == shortcut.action)
+        .and_then(|(_, accel)| normalize(accel))
+        .unwrap_or_else(|| shortcut.default_accel.to_string())
+}
+
+pub fn shortcut_accel(shortcut: &ConfigurableShortcut, overrides: &[(String, String)]) -> String {
+    resolved_shortcut_accel(shortcut, overrides, normalized_accel)
+}
+
+fn conflicting_shortcut_with(
+    action: &str,
+    accel: &str,
+    overrides: &[(String, String)],
+    normalize: impl Fn(&str) -> Option<String> + Copy,
+) -> Option<&'static ConfigurableShortcut> {
+    let accel = normalize(accel)?;
+    configurable_shortcuts().find(|shortcut| {
+        shortcut.action != action
+            && normalize(&resolved_shortcut_accel(shortcut, overrides, normalize)).as_ref()
+                == Some(&accel)
+    })
+}
+
+pub fn conflicting_shortcut(
+    action: &str,
+    accel: &str,
+    overrides: &[(String, String)],
+) -> Option<&'static ConfigurableShortcut> {
+    conflicting_shortcut_with(action, accel, overrides, normalized_accel)
+}
+
+pub fn apply_configurable_shortcuts(app: &impl IsA<gtk::Application>) {
+    let overrides = Preferences::new().shortcut_overrides();
+    for shortcut in configurable_shortcuts() {
+        let accel = shortcut_accel(shortcut, &overrides);
+        app.set_accels_for_action(shortcut.action, &[accel.as_str()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{conflicting_shortcut_with, resolved_shortcut_accel, CONFIGURABLE_SHORTCUTS};
+
+    fn lowercase(accel: &str) -> Option<String> {
+        let accel = accel.trim().to_ascii_lowercase();
+        (!accel.is_empty() && !accel.contains(' ')).then_some(accel)
+    }
+
+    fn overrides(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(action, accel)| ((*action).to_string(), (*accel).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn configurable_shortcuts_have_unique_actions_and_defaults() {
+        for (index, shortcut) in CONFIGURABLE_SHORTCUTS.iter().enumerate() {
+            for other in &CONFIGURABLE_SHORTCUTS[index + 1..] {
+                assert_ne!(shortcut.action, other.action);
+                assert_ne!(shortcut.default_accel, other.default_accel);
+            }
+        }
+    }
+
+    #[test]
+    fn overrides_replace_defaults_and_invalid_overrides_are_ignored() {
+        let copy_password = CONFIGURABLE_SHORTCUTS
+            .iter()
+            .find(|shortcut| shortcut.action == "win.copy-password")
+            .expect("copy password shortcut");
+
+        assert_eq!(
+            resolved_shortcut_accel(copy_password, &[], lowercase),
+            "<primary><shift>c"
+        );
+        assert_eq!(
+            resolved_shortcut_accel(
+                copy_password,
+                &overrides(&[("win.copy-password", "<Primary>J")]),
+                lowercase
+            ),
+            "<primary>j"
+        );
+        assert_eq!(
+            resolved_shortcut_accel(
+                copy_password,
+                &overrides(&[("win.copy-password", "not valid")]),
+                lowercase
+            ),
+            "<primary><shift>c"
+        );
+    }
+
+    #[test]
+    fn conflicts_consider_other_actions_and_their_overrides() {
+        let conflict = conflicting_shortcut_with("win.copy-password", "<Primary>F", &[], lowercase)
+            .expect("find shortcut conflicts");
+        assert_eq!(conflict.action, "win.toggle-find");
+
+        assert!(conflicting_shortcut_with(
+            "win.copy-password",
+            "<primary>f",
+            &overrides(&[("win.toggle-find", "<primary>j")]),
+            lowercase,
+        )
+        .is_none());
+        assert!(conflicting_shortcut_with(
+            "win.copy-password",
+            "<primary><shift>c",
+            &[],
+            lowercase
+        )
+        .is_none());
+    }
+}
@@ -1,6 +1,11 @@
+mod activity;
 mod audit;
+mod cleanup;
+mod field_replace;
 mod field_values;
 mod menu;
+mod remote_store;
+mod settings_transfer;
 #[cfg(test)]
 mod tests;
 mod unlock;
@@ -35,12 +40,16 @@ use adw::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use self::activity::append_activity_log_row;
 use self::audit::AuditToolState;
+use self::cleanup::append_local_data_row;
 use self::field_values::FieldValueBrowserState;
 use self::menu::{
     append_optional_pass_import_row, append_optional_setup_row, configure_optional_doc_row,
     configure_optional_log_rows, sync_optional_setup_row,
 };
+use self::remote_store::append_remote_store_row;
+use self::settings_transfer::append_settings_transfer_rows;
 use self::weak_passwords::WeakPasswordToolState;
 
 const TOOLS_PAGE_TITLE: &str = "Tools";
@@ -329,6 +338,10 @@ impl ToolsPageState {
 
         configure_optional_doc_row(self);
         configure_optional_log_rows(self);
+        append_activity_log_row(self);
+        append_local_data_row(self);
+        append_remote_store_row(self);
+        append_settings_transfer_rows(self);
         *self.select_page.setup_row.borrow_mut() = append_optional_setup_row(self);
         *self.select_page.pass_import_row.borrow_mut() = append_optional_pass_import_row(self);
         self.sync_action_availability();
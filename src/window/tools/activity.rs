@@ -0,0 +1,146 @@
+use super::ToolsPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::support::activity::{purge_activity_log, read_activity_log};
+use crate::support::ui::{append_action_row_with_button, dialog_content_shell};
+use adw::gtk::{Align, Box as GtkBox, Button, Orientation, TextView, WrapMode};
+use adw::prelude::*;
+use adw::{AlertDialog, Dialog, ResponseAppearance, StatusPage, Toast};
+
+pub(super) fn append_activity_log_row(state: &ToolsPageState) {
+    let state_for_open = state.clone();
+    append_action_row_with_button(
+        &state.select_page.list,
+        "Activity log",
+        "See what was copied, saved, deleted, and synced on this device.",
+        "document-open-recent-symbolic",
+        move || {
+            state_for_open.close_select_dialog();
+            state_for_open.present_activity_log_dialog();
+        },
+    );
+}
+
+// The file is written oldest first; the viewer shows the latest entries on top.
+fn newest_first(contents: &str) -> String {
+    contents
+        .lines()
+        .rev()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl ToolsPageState {
+    fn present_activity_log_dialog(&self) {
+        let contents = match read_activity_log() {
+            Ok(contents) => newest_first(&contents),
+            Err(err) => {
+                log_error(format!("Failed to read the activity log: {err}"));
+                self.overlay
+                    .add_toast(Toast::new(&gettext("Couldn't read the activity log.")));
+                return;
+            }
+        };
+
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(18);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+        if contents.is_empty() {
+            let status = StatusPage::builder()
+                .icon_name("document-open-recent-symbolic")
+                .title(gettext("No activity yet"))
+                .description(gettext(
+                    "Copied, saved, deleted, and synced items show up here.",
+                ))
+                .build();
+            content.append(&status);
+        } else {
+            let view = TextView::builder()
+                .editable(false)
+                .cursor_visible(false)
+                .monospace(true)
+                .wrap_mode(WrapMode::WordChar)
+                .build();
+            view.add_css_class("card");
+            view.set_top_margin(12);
+            view.set_bottom_margin(12);
+            view.set_left_margin(12);
+            view.set_right_margin(12);
+            view.buffer().set_text(&contents);
+            content.append(&view);
+        }
+
+        let clear_button = Button::with_label(&gettext("Clear Log"));
+        clear_button.add_css_class("destructive-action");
+        clear_button.add_css_class("pill");
+        clear_button.set_halign(Align::Center);
+        clear_button.set_sensitive(!contents.is_empty());
+        content.append(&clear_button);
+
+        let dialog = Dialog::builder()
+            .title(gettext("Activity log"))
+            .content_height(520)
+            .content_width(720)
+            .follows_content_size(true)
+            .child(&dialog_content_shell("Activity log", None, &content))
+            .build();
+
+        let state = self.clone();
+        let dialog_for_clear = dialog.clone();
+        clear_button.connect_clicked(move |_| {
+            state.confirm_activity_log_purge(&dialog_for_clear);
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn confirm_activity_log_purge(&self, log_dialog: &Dialog) {
+        let dialog = AlertDialog::builder()
+            .heading(gettext("Clear the activity log?"))
+            .body(gettext(
+                "All recorded activity on this device is removed. This can't be undone.",
+            ))
+            .build();
+        let cancel = gettext("Cancel");
+        let clear = gettext("Clear");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("clear", clear.as_str())]);
+        dialog.set_response_appearance("clear", ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("cancel"));
+
+        let overlay = self.overlay.clone();
+        let log_dialog = log_dialog.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "clear" {
+                return;
+            }
+            match purge_activity_log() {
+                Ok(()) => {
+                    log_dialog.close();
+                    overlay.add_toast(Toast::new(&gettext("Activity log cleared.")));
+                }
+                Err(err) => {
+                    log_error(format!("Failed to clear the activity log: {err}"));
+                    overlay.add_toast(Toast::new(&gettext("Couldn't clear the activity log.")));
+                }
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::newest_first;
+
+    #[test]
+    fn activity_log_viewer_shows_the_latest_entry_first() {
+        assert_eq!(
+            newest_first("2025-12-01 09:00:00 Copied the password of mail\n2025-12-01 09:05:00 Edited mail\n"),
+            "2025-12-01 09:05:00 Edited mail\n2025-12-01 09:00:00 Copied the password of mail"
+        );
+        assert_eq!(newest_first(""), "");
+    }
+}
@@ -8,7 +8,7 @@ use super::{
     AUDIT_ROW_GIT_UNAVAILABLE_SUBTITLE, AUDIT_ROW_SUBTITLE, AUDIT_SEARCH_EMPTY_SUBTITLE,
     AUDIT_SEARCH_EMPTY_TITLE, AUDIT_SUBTITLE, AUDIT_TITLE,
 };
-use crate::i18n::gettext;
+use crate::i18n::{gettext, ngettext, pgettext};
 use crate::preferences::Preferences;
 use crate::store::labels::{shortened_store_label_for_path, shortened_store_label_map};
 use crate::support::background::spawn_result_task;
@@ -865,7 +865,8 @@ fn branch_row_subtitle(branch: &StoreGitAuditBranchRef, runtime: &AuditBranchSta
     }
     if runtime.loaded_once.get() {
         let count = runtime.commits.borrow().len();
-        let loaded = gettext("{count} commits loaded").replace("{count}", &count.to_string());
+        let loaded = ngettext("{count} commit loaded", "{count} commits loaded", count)
+            .replace("{count}", &count.to_string());
         return format!("{scope} · {loaded}");
     }
 
@@ -948,7 +949,7 @@ fn audit_commit_matches_query(commit: &StoreGitAuditCommit, query: &str) -> bool
 fn trimmed_multiline_text(text: &str) -> String {
     let trimmed = text.trim_end();
     if trimmed.is_empty() {
-        gettext("Empty")
+        pgettext("commit message", "Empty")
     } else {
         trimmed.to_string()
     }
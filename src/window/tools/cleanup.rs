@@ -0,0 +1,286 @@
+use super::ToolsPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::Preferences;
+use crate::private_key::keyring::forget_all_key_passwords;
+use crate::support::actions::activate_widget_action;
+use crate::support::background::spawn_result_task;
+use crate::support::local_data::{
+    clear_local_data, local_data_size, removable_store_clones, remove_store_clone, LocalData,
+    RemovableStoreClone,
+};
+use crate::support::runtime::supports_host_command_features;
+use crate::support::ui::{append_action_row_with_button, dialog_content_shell};
+use adw::gtk::{Align, Button, CheckButton, Label};
+use adw::prelude::*;
+use adw::{
+    glib, ActionRow, AlertDialog, Dialog, PreferencesGroup, PreferencesPage, ResponseAppearance,
+    Toast,
+};
+
+const LOCAL_DATA_TITLE: &str = "Clean up local data";
+
+pub(super) fn append_local_data_row(state: &ToolsPageState) {
+    let state_for_open = state.clone();
+    append_action_row_with_button(
+        &state.select_page.list,
+        LOCAL_DATA_TITLE,
+        "Remove caches, drafts, history, and saved key passwords from this device.",
+        "user-trash-symbolic",
+        move || {
+            state_for_open.close_select_dialog();
+            state_for_open.present_local_data_dialog();
+        },
+    );
+}
+
+#[derive(Clone, Debug, Default)]
+struct LocalDataCleanup {
+    data: Vec<LocalData>,
+    forget_key_passwords: bool,
+    store_clones: Vec<RemovableStoreClone>,
+}
+
+impl LocalDataCleanup {
+    const fn is_empty(&self) -> bool {
+        self.data.is_empty() && !self.forget_key_passwords && self.store_clones.is_empty()
+    }
+}
+
+// Returns the stores whose folders were removed and what went wrong.
+fn run_local_data_cleanup(cleanup: LocalDataCleanup) -> (Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    for data in cleanup.data {
+        if let Err(err) = clear_local_data(data) {
+            errors.push(format!("Failed to clear {}: {err}", data.title()));
+        }
+    }
+    if cleanup.forget_key_passwords {
+        if let Err(err) = forget_all_key_passwords() {
+            errors.push(format!("Failed to forget saved key passwords: {err}"));
+        }
+    }
+    let mut removed_stores = Vec::new();
+    for clone in cleanup.store_clones {
+        match remove_store_clone(&clone.root) {
+            Ok(()) => removed_stores.push(clone.store),
+            Err(err) => errors.push(format!("Failed to remove the store copy: {err}")),
+        }
+    }
+    (removed_stores, errors)
+}
+
+fn local_data_check_row(
+    group: &PreferencesGroup,
+    title: &str,
+    subtitle: &str,
+    size: Option<u64>,
+    active: bool,
+) -> CheckButton {
+    let row = ActionRow::builder().title(title).subtitle(subtitle).build();
+    let check = CheckButton::builder()
+        .active(active)
+        .valign(Align::Center)
+        .build();
+    row.add_prefix(&check);
+    row.set_activatable_widget(Some(&check));
+    if let Some(size) = size {
+        let label = Label::new(Some(&glib::format_size(size)));
+        label.add_css_class("dim-label");
+        row.add_suffix(&label);
+    }
+    group.add(&row);
+    check
+}
+
+impl ToolsPageState {
+    pub(super) fn present_local_data_dialog(&self) {
+        // Stores opened with `--store` or `PASSWORD_STORE_DIR` come after the
+        // configured ones and aren't Keycord's to remove, so the zip leaves
+        // them out.
+        let settings = Preferences::new();
+        let stores = settings
+            .configured_stores()
+            .into_iter()
+            .zip(settings.store_roots())
+            .collect::<Vec<_>>();
+        let state = self.clone();
+        let overlay = self.overlay.clone();
+        spawn_result_task(
+            move || {
+                let sizes = LocalData::ALL
+                    .into_iter()
+                    .map(|data| (data, local_data_size(data)))
+                    .collect::<Vec<_>>();
+                (sizes, removable_store_clones(&stores))
+            },
+            move |(sizes, store_clones)| state.show_local_data_dialog(&sizes, store_clones),
+            move || {
+                overlay.add_toast(Toast::new(&gettext("Couldn't read the local data.")));
+            },
+        );
+    }
+
+    fn show_local_data_dialog(
+        &self,
+        sizes: &[(LocalData, u64)],
+        store_clones: Vec<RemovableStoreClone>,
+    ) {
+        let page = PreferencesPage::new();
+        let device_group = PreferencesGroup::builder()
+            .title(gettext("On this device"))
+            .build();
+        let data_checks = sizes
+            .iter()
+            .map(|(data, size)| {
+                let check = local_data_check_row(
+                    &device_group,
+                    &gettext(data.title()),
+                    &gettext(data.description()),
+                    Some(*size),
+                    true,
+                );
+                (*data, check)
+            })
+            .collect::<Vec<_>>();
+        // Key passwords live in the desktop's keyring, which has no size to show.
+        let key_passwords_check = supports_host_command_features().then(|| {
+            local_data_check_row(
+                &device_group,
+                &gettext("Saved key passwords"),
+                &gettext("Key passwords remembered in the system keyring."),
+                None,
+                true,
+            )
+        });
+        page.add(&device_group);
+
+        let store_checks = if store_clones.is_empty() {
+            Vec::new()
+        } else {
+            let store_group = PreferencesGroup::builder()
+                .title(gettext("Store copies"))
+                .description(gettext(
+                    "Only stores with every change on a remote are listed. The remote keeps the items, and the store is removed from Keycord on this device.",
+                ))
+                .build();
+            let checks = store_clones
+                .into_iter()
+                .map(|clone| {
+                    let check = local_data_check_row(
+                        &store_group,
+                        &clone.store,
+                        &gettext("Local copy of a synced store"),
+                        Some(clone.bytes),
+                        false,
+                    );
+                    (clone, check)
+                })
+                .collect::<Vec<_>>();
+            page.add(&store_group);
+            checks
+        };
+
+        let clean_button = Button::with_label(&gettext("Clean Up"));
+        clean_button.add_css_class("destructive-action");
+        clean_button.add_css_class("pill");
+        clean_button.set_halign(Align::Center);
+        let button_group = PreferencesGroup::new();
+        button_group.add(&clean_button);
+        page.add(&button_group);
+
+        let dialog = Dialog::builder()
+            .title(gettext(LOCAL_DATA_TITLE))
+            .content_width(560)
+            .content_height(560)
+            .child(&dialog_content_shell(LOCAL_DATA_TITLE, None, &page))
+            .build();
+
+        let selected = move || LocalDataCleanup {
+            data: data_checks
+                .iter()
+                .filter(|(_, check)| check.is_active())
+                .map(|(data, _)| *data)
+                .collect(),
+            forget_key_passwords: key_passwords_check
+                .as_ref()
+                .is_some_and(CheckButton::is_active),
+            store_clones: store_checks
+                .iter()
+                .filter(|(_, check)| check.is_active())
+                .map(|(clone, _)| clone.clone())
+                .collect(),
+        };
+        let state = self.clone();
+        let dialog_for_clean = dialog.clone();
+        clean_button.connect_clicked(move |_| {
+            let cleanup = selected();
+            if !cleanup.is_empty() {
+                state.confirm_local_data_cleanup(&dialog_for_clean, cleanup);
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn confirm_local_data_cleanup(&self, data_dialog: &Dialog, cleanup: LocalDataCleanup) {
+        let body = if cleanup.store_clones.is_empty() {
+            "The selected data is removed from this device. This can't be undone."
+        } else {
+            "The selected data and store folders are removed from this device. This can't be undone."
+        };
+        let dialog = AlertDialog::builder()
+            .heading(gettext("Clean up local data?"))
+            .body(gettext(body))
+            .build();
+        let cancel = gettext("Cancel");
+        let clean = gettext("Clean Up");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("clean", clean.as_str())]);
+        dialog.set_response_appearance("clean", ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("cancel"));
+
+        let window = self.window.clone();
+        let overlay = self.overlay.clone();
+        let data_dialog = data_dialog.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "clean" {
+                return;
+            }
+            data_dialog.close();
+            let cleanup = cleanup.clone();
+            let window = window.clone();
+            let overlay = overlay.clone();
+            let overlay_for_disconnect = overlay.clone();
+            spawn_result_task(
+                move || run_local_data_cleanup(cleanup),
+                move |(removed_stores, errors)| {
+                    if !removed_stores.is_empty() {
+                        let settings = Preferences::new();
+                        let stores = settings
+                            .stores()
+                            .into_iter()
+                            .filter(|store| !removed_stores.contains(store))
+                            .collect();
+                        if let Err(err) = settings.set_stores(stores) {
+                            log_error(format!("Failed to save stores: {err}"));
+                        }
+                        activate_widget_action(&window, "win.go-home");
+                    }
+                    if errors.is_empty() {
+                        overlay.add_toast(Toast::new(&gettext("Local data cleaned up.")));
+                        return;
+                    }
+                    for err in errors {
+                        log_error(err);
+                    }
+                    overlay.add_toast(Toast::new(&gettext("Couldn't clean up all local data.")));
+                },
+                move || {
+                    overlay_for_disconnect
+                        .add_toast(Toast::new(&gettext("Couldn't clean up all local data.")));
+                },
+            );
+        });
+        dialog.present(Some(&self.window));
+    }
+}
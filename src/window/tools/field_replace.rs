@@ -0,0 +1,428 @@
+use super::{collect_loaded_entry_requests, FieldValueRequest, ToolsPageState};
+use crate::backend::{
+    read_password_entries_in_parallel, read_password_entry, save_password_entries,
+};
+use crate::i18n::{gettext, ngettext};
+use crate::logging::log_error;
+use crate::password::file::replace_field_value_contents;
+use crate::preferences::Preferences;
+use crate::support::background::spawn_result_task;
+use crate::support::ui::flat_icon_button_with_tooltip;
+use adw::gtk::{Image, ListBox};
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog, EntryRow, PreferencesGroup, ResponseAppearance, Toast};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use zeroize::Zeroizing;
+
+const MAX_PREVIEW_LABELS: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldReplacement {
+    root: String,
+    label: String,
+}
+
+#[derive(Clone, Debug)]
+struct FieldValueChange {
+    field: String,
+    normalized_value: String,
+    new_value: String,
+}
+
+impl FieldValueChange {
+    fn apply(&self, contents: &str) -> Option<Zeroizing<String>> {
+        replace_field_value_contents(
+            contents,
+            &self.field,
+            &self.normalized_value,
+            &self.new_value,
+        )
+        .map(Zeroizing::new)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct FieldReplacePreview {
+    replacements: Vec<FieldReplacement>,
+    unreadable: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldReplaceFailure {
+    label: String,
+    message: String,
+}
+
+// The value rows keep their main action of searching the list; replacing is
+// an extra suffix button so it can't be started by accident.
+pub(super) fn append_field_value_row(
+    list: &ListBox,
+    title: &str,
+    subtitle: &str,
+    open: impl Fn() + 'static,
+    replace: impl Fn() + 'static,
+) {
+    let row = ActionRow::builder()
+        .title(title)
+        .subtitle(subtitle)
+        .activatable(true)
+        .build();
+    let replace_button =
+        flat_icon_button_with_tooltip("edit-find-replace-symbolic", "Replace value");
+    replace_button.set_valign(adw::gtk::Align::Center);
+    replace_button.connect_clicked(move |_| replace());
+    row.add_suffix(&replace_button);
+    row.add_suffix(&Image::from_icon_name("go-next-symbolic"));
+    row.connect_activated(move |_| open());
+    list.append(&row);
+}
+
+impl ToolsPageState {
+    pub(super) fn present_field_replace_dialog(
+        &self,
+        field: &str,
+        display_value: &str,
+        normalized_value: &str,
+    ) {
+        let dialog = AlertDialog::builder()
+            .heading(gettext("Replace value"))
+            .body(fill_value_and_field(
+                &gettext(
+                    "Replace “{value}” in the {field} field of every item in the current list.",
+                ),
+                display_value,
+                field,
+            ))
+            .build();
+        let new_value_row = EntryRow::new();
+        new_value_row.set_title(&gettext("New value"));
+        new_value_row.set_text(display_value);
+        let group = PreferencesGroup::new();
+        group.add(&new_value_row);
+        dialog.set_extra_child(Some(&group));
+
+        let cancel = gettext("Cancel");
+        let preview = gettext("Find Items");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("preview", preview.as_str())]);
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("preview"));
+        dialog.set_response_enabled("preview", false);
+        {
+            let dialog = dialog.clone();
+            let display_value = display_value.to_string();
+            new_value_row.connect_changed(move |row| {
+                dialog.set_response_enabled("preview", row.text() != display_value);
+            });
+        }
+
+        let state = self.clone();
+        let field = field.to_string();
+        let normalized_value = normalized_value.to_string();
+        dialog.connect_response(None, move |_, response| {
+            if response != "preview" {
+                return;
+            }
+            state.find_field_replacements(FieldValueChange {
+                field: field.clone(),
+                normalized_value: normalized_value.clone(),
+                new_value: new_value_row.text().to_string(),
+            });
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn find_field_replacements(&self, change: FieldValueChange) {
+        let settings = Preferences::new();
+        let requests = collect_loaded_entry_requests(&self.root_list)
+            .into_iter()
+            .filter(|request| !settings.store_is_read_only(&request.root))
+            .collect::<Vec<_>>();
+
+        let state = self.clone();
+        self.unlock_tool_keys_if_needed(
+            requests,
+            Rc::new(move |requests| {
+                let state = state.clone();
+                let change = change.clone();
+                let change_for_task = change.clone();
+                spawn_result_task(
+                    move || build_field_replace_preview(requests, &change_for_task),
+                    move |preview| state.present_field_replace_preview(change, preview),
+                    || {},
+                );
+            }),
+            Rc::new(|| {}),
+        );
+    }
+
+    fn present_field_replace_preview(
+        &self,
+        change: FieldValueChange,
+        preview: FieldReplacePreview,
+    ) {
+        let count = preview.replacements.len();
+        if count == 0 {
+            self.overlay.add_toast(Toast::new(&gettext(
+                "No items in the current list use that value.",
+            )));
+            return;
+        }
+
+        let mut body = preview_labels(&preview.replacements);
+        if preview.unreadable > 0 {
+            body.push_str("\n\n");
+            body.push_str(
+                &ngettext(
+                    "{count} item couldn't be read and will be skipped.",
+                    "{count} items couldn't be read and will be skipped.",
+                    preview.unreadable,
+                )
+                .replace("{count}", &preview.unreadable.to_string()),
+            );
+        }
+        let dialog = AlertDialog::builder()
+            .heading(
+                ngettext(
+                    "Replace the value in {count} item?",
+                    "Replace the value in {count} items?",
+                    count,
+                )
+                .replace("{count}", &count.to_string()),
+            )
+            .body(body)
+            .build();
+        let cancel = gettext("Cancel");
+        let replace = gettext("Replace");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("replace", replace.as_str())]);
+        dialog.set_response_appearance("replace", ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("cancel"));
+
+        let state = self.clone();
+        let replacements = Rc::new(preview.replacements);
+        dialog.connect_response(None, move |_, response| {
+            if response == "replace" {
+                state.apply_field_replacements(change.clone(), replacements.as_ref().clone());
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn apply_field_replacements(
+        &self,
+        change: FieldValueChange,
+        replacements: Vec<FieldReplacement>,
+    ) {
+        let total = replacements.len();
+        let message = format!("Replace {} in {total} passwords", change.field);
+        let state = self.clone();
+        spawn_result_task(
+            move || save_field_replacements(replacements, &change, &message),
+            move |failures| state.finish_field_replacements(total, &failures),
+            || {},
+        );
+    }
+
+    fn finish_field_replacements(&self, total: usize, failures: &[FieldReplaceFailure]) {
+        for failure in failures {
+            log_error(format!(
+                "Failed to replace a field value in {}: {}",
+                failure.label, failure.message
+            ));
+        }
+        self.clear_field_values_cache();
+        self.prepare_field_values_browser();
+
+        let updated = total - failures.len();
+        if failures.is_empty() {
+            self.overlay.add_toast(Toast::new(
+                &ngettext("Updated {count} item.", "Updated {count} items.", updated)
+                    .replace("{count}", &updated.to_string()),
+            ));
+            return;
+        }
+
+        let dialog = AlertDialog::builder()
+            .heading(
+                ngettext(
+                    "Couldn't update {count} item",
+                    "Couldn't update {count} items",
+                    failures.len(),
+                )
+                .replace("{count}", &failures.len().to_string()),
+            )
+            .body(failure_summary(updated, failures))
+            .build();
+        let close = gettext("Close");
+        dialog.add_responses(&[("close", close.as_str())]);
+        dialog.set_close_response("close");
+        dialog.present(Some(&self.window));
+    }
+}
+
+// The preview only decides which items are listed. The new contents are
+// made again from what each item holds when the user confirms.
+fn build_field_replace_preview(
+    requests: Vec<FieldValueRequest>,
+    change: &FieldValueChange,
+) -> FieldReplacePreview {
+    let change = change.clone();
+    let results = read_password_entries_in_parallel(requests, move |request| {
+        let contents = Zeroizing::new(read_password_entry(&request.root, &request.label).ok()?);
+        Some(change.apply(&contents).map(|_| FieldReplacement {
+            root: request.root,
+            label: request.label,
+        }))
+    });
+
+    let mut preview = FieldReplacePreview::default();
+    for result in results {
+        match result.flatten() {
+            Some(Some(replacement)) => preview.replacements.push(replacement),
+            Some(None) => {}
+            None => preview.unreadable += 1,
+        }
+    }
+    preview
+}
+
+// Each item is read again right before saving, so a change made since the
+// preview is kept, and an item whose field no longer holds the old value is
+// left alone. Entries are saved store by store so each store gets a single
+// commit.
+fn save_field_replacements(
+    replacements: Vec<FieldReplacement>,
+    change: &FieldValueChange,
+    message: &str,
+) -> Vec<FieldReplaceFailure> {
+    let mut failures = Vec::new();
+    let mut by_store: BTreeMap<String, Vec<(String, Zeroizing<String>)>> = BTreeMap::new();
+    for replacement in replacements {
+        let contents = match read_password_entry(&replacement.root, &replacement.label) {
+            Ok(contents) => Zeroizing::new(contents),
+            Err(err) => {
+                failures.push(FieldReplaceFailure {
+                    label: replacement.label,
+                    message: err.to_string(),
+                });
+                continue;
+            }
+        };
+        let Some(contents) = change.apply(&contents) else {
+            failures.push(FieldReplaceFailure {
+                label: replacement.label,
+                message: gettext("The value changed since the preview."),
+            });
+            continue;
+        };
+        by_store
+            .entry(replacement.root)
+            .or_default()
+            .push((replacement.label, contents));
+    }
+
+    for (root, entries) in by_store {
+        let results = save_password_entries(&root, &entries, message);
+        for ((label, _), result) in entries.into_iter().zip(results) {
+            if let Err(err) = result {
+                failures.push(FieldReplaceFailure {
+                    label,
+                    message: err.to_string(),
+                });
+            }
+        }
+    }
+    failures
+}
+
+fn preview_labels(replacements: &[FieldReplacement]) -> String {
+    let mut labels = replacements
+        .iter()
+        .take(MAX_PREVIEW_LABELS)
+        .map(|replacement| replacement.label.clone())
+        .collect::<Vec<_>>();
+    let hidden = replacements.len().saturating_sub(MAX_PREVIEW_LABELS);
+    if hidden > 0 {
+        labels.push(
+            ngettext("and {count} more", "and {count} more", hidden)
+                .replace("{count}", &hidden.to_string()),
+        );
+    }
+    labels.join("\n")
+}
+
+// Both placeholders are filled in one pass, so a value that contains
+// `{field}` is shown as typed.
+fn fill_value_and_field(template: &str, value: &str, field: &str) -> String {
+    template
+        .split("{value}")
+        .map(|part| part.replace("{field}", field))
+        .collect::<Vec<_>>()
+        .join(value)
+}
+
+fn failure_summary(updated: usize, failures: &[FieldReplaceFailure]) -> String {
+    let mut lines = vec![ngettext(
+        "{count} item was updated. These items were left unchanged:",
+        "{count} items were updated. These items were left unchanged:",
+        updated,
+    )
+    .replace("{count}", &updated.to_string())];
+    lines.extend(
+        failures
+            .iter()
+            .map(|failure| format!("{}: {}", failure.label, failure.message)),
+    );
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        failure_summary, fill_value_and_field, preview_labels, FieldReplaceFailure,
+        FieldReplacement,
+    };
+
+    fn replacement(label: &str) -> FieldReplacement {
+        FieldReplacement {
+            root: "/tmp/store".to_string(),
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn placeholders_in_the_value_are_not_filled_in() {
+        assert_eq!(
+            fill_value_and_field("Replace “{value}” in the {field} field.", "{field}", "url"),
+            "Replace “{field}” in the url field."
+        );
+    }
+
+    #[test]
+    fn preview_lists_the_first_labels_and_counts_the_rest() {
+        let replacements = (0..12)
+            .map(|index| replacement(&format!("mail/{index}")))
+            .collect::<Vec<_>>();
+
+        let preview = preview_labels(&replacements);
+
+        assert!(preview.starts_with("mail/0\nmail/1\n"));
+        assert!(preview.contains("mail/9\n"));
+        assert!(!preview.contains("mail/10"));
+        assert!(preview.ends_with("and 2 more"));
+    }
+
+    #[test]
+    fn failure_summary_lists_each_failed_item() {
+        let failures = vec![FieldReplaceFailure {
+            label: "team/smtp".to_string(),
+            message: "The store is read-only.".to_string(),
+        }];
+
+        assert_eq!(
+            failure_summary(3, &failures),
+            "3 items were updated. These items were left unchanged:\nteam/smtp: The store is read-only."
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::field_replace::append_field_value_row;
 use super::{
     append_loading_rows, collect_loaded_entry_requests, next_generation, FieldValueRequest,
     ToolsPageState, FIELD_VALUES_EMPTY_SUBTITLE, FIELD_VALUES_EMPTY_TITLE,
@@ -6,8 +7,8 @@ use super::{
     FIELD_VALUES_TITLE, FIELD_VALUES_VALUES_SUBTITLE, VALUE_VALUES_EMPTY_SUBTITLE,
     VALUE_VALUES_EMPTY_TITLE, VALUE_VALUES_FILTER_EMPTY_SUBTITLE, VALUE_VALUES_FILTER_EMPTY_TITLE,
 };
-use crate::backend::read_password_entry;
-use crate::i18n::gettext;
+use crate::backend::{read_password_entries_in_parallel, read_password_entry};
+use crate::i18n::ngettext;
 use crate::password::file::{searchable_pass_fields, SearchablePassField};
 use crate::password::opened::clear_opened_pass_file;
 use crate::preferences::Preferences;
@@ -315,12 +316,21 @@ impl ToolsPageState {
             let state = self.clone();
             let field = selected_field.clone();
             let display_value = value.display_value.clone();
-            append_action_row_with_button(
+            let state_for_replace = self.clone();
+            let field_for_replace = selected_field.clone();
+            let value_for_replace = value.clone();
+            append_field_value_row(
                 &self.field_browser.value_list,
                 &value.display_value,
                 &subtitle,
-                "go-next-symbolic",
                 move || state.apply_root_search(&format_exact_field_query(&field, &display_value)),
+                move || {
+                    state_for_replace.present_field_replace_dialog(
+                        &field_for_replace,
+                        &value_for_replace.display_value,
+                        &value_for_replace.normalized_value,
+                    );
+                },
             );
         }
     }
@@ -370,14 +380,15 @@ fn build_field_value_catalog_batch(
     generation: u64,
     requests: Vec<FieldValueRequest>,
 ) -> FieldValueCatalogBatch {
-    let indexed_entries = requests
-        .into_iter()
-        .filter_map(|request| {
-            read_password_entry(&request.root, &request.label)
-                .ok()
-                .map(|contents| searchable_pass_fields(&contents))
-        })
-        .collect::<Vec<_>>();
+    let indexed_entries = read_password_entries_in_parallel(requests, |request| {
+        read_password_entry(&request.root, &request.label)
+            .ok()
+            .map(|contents| searchable_pass_fields(&contents))
+    })
+    .into_iter()
+    .flatten()
+    .flatten()
+    .collect::<Vec<_>>();
 
     FieldValueCatalogBatch {
         generation,
@@ -463,19 +474,11 @@ fn escape_quoted_search_component(value: &str) -> String {
 }
 
 pub(super) fn unique_values_subtitle(count: usize) -> String {
-    let template = if count == 1 {
-        gettext("{count} unique value")
-    } else {
-        gettext("{count} unique values")
-    };
-    template.replace("{count}", &count.to_string())
+    ngettext("{count} unique value", "{count} unique values", count)
+        .replace("{count}", &count.to_string())
 }
 
 pub(super) fn matching_items_subtitle(count: usize) -> String {
-    let template = if count == 1 {
-        gettext("{count} matching item")
-    } else {
-        gettext("{count} matching items")
-    };
-    template.replace("{count}", &count.to_string())
+    ngettext("{count} matching item", "{count} matching items", count)
+        .replace("{count}", &count.to_string())
 }
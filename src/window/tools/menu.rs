@@ -3,7 +3,7 @@ use crate::clipboard::set_clipboard_text;
 use crate::i18n::gettext;
 #[cfg(all(target_os = "linux", feature = "setup"))]
 use crate::logging::log_error;
-use crate::logging::log_snapshot;
+use crate::logging::{log_snapshot, set_verbose_logging, verbose_logging};
 use crate::preferences::Preferences;
 #[cfg(all(target_os = "linux", feature = "setup"))]
 use crate::setup::{
@@ -19,7 +19,7 @@ use crate::support::runtime::{
 use crate::support::ui::append_action_row_with_button;
 use crate::window::navigation::show_log_page;
 use adw::prelude::*;
-use adw::{ActionRow, Toast};
+use adw::{ActionRow, SwitchRow, Toast};
 use std::rc::Rc;
 
 const fn information_group_visible(docs_supported: bool, logging_supported: bool) -> bool {
@@ -85,6 +85,17 @@ pub(super) fn configure_optional_log_rows(state: &ToolsPageState) {
         .select_page
         .copy_logs_button
         .connect_clicked(move |_| copy_action());
+
+    let verbose_row = SwitchRow::builder()
+        .title(gettext("Detailed logs"))
+        .subtitle(gettext(
+            "Also log timings and debug steps until Keycord closes.",
+        ))
+        .active(verbose_logging())
+        .visible(logging_supported)
+        .build();
+    verbose_row.connect_active_notify(|row| set_verbose_logging(row.is_active()));
+    state.select_page.logs_list.append(&verbose_row);
 }
 
 #[cfg(test)]
@@ -122,6 +133,10 @@ pub(super) fn append_optional_setup_row(state: &ToolsPageState) -> Option<Action
             };
 
             match result {
+                Ok(()) if installed => {
+                    refresh_state.refresh_select_page();
+                    offer_local_data_cleanup(&refresh_state);
+                }
                 Ok(()) => refresh_state.refresh_select_page(),
                 Err(err) => {
                     log_error(format!("Failed to update local app menu entry: {err}"));
@@ -133,6 +148,19 @@ pub(super) fn append_optional_setup_row(state: &ToolsPageState) -> Option<Action
     Some(row)
 }
 
+// Someone removing Keycord from the app menu may be leaving the machine, so
+// the data it keeps here is one click away.
+#[cfg(all(target_os = "linux", feature = "setup"))]
+fn offer_local_data_cleanup(state: &ToolsPageState) {
+    let toast = Toast::builder()
+        .title(gettext("Removed from the app menu."))
+        .button_label(gettext("Clean Up Data…"))
+        .build();
+    let state_for_cleanup = state.clone();
+    toast.connect_button_clicked(move |_| state_for_cleanup.present_local_data_dialog());
+    state.overlay.add_toast(toast);
+}
+
 #[cfg(not(all(target_os = "linux", feature = "setup")))]
 pub(super) const fn append_optional_setup_row(_state: &ToolsPageState) -> Option<ActionRow> {
     None
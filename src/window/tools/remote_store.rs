@@ -0,0 +1,249 @@
+use super::{FieldValueRequest, ToolsPageState};
+use crate::backend::read_password_line;
+use crate::clipboard::set_clipboard_text;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::support::activity::record_activity;
+use crate::support::background::spawn_result_task;
+use crate::support::git::{fetch_remote_store, RemoteStoreItem, RemoteStoreSnapshot};
+use crate::support::ui::{append_action_row_with_button, dialog_content_shell};
+use adw::gtk::{
+    Box as GtkBox, Image, ListBox, Orientation, PolicyType, ScrolledWindow, SearchEntry,
+    SelectionMode,
+};
+use adw::prelude::*;
+use adw::{ActionRow, AlertDialog, Dialog, EntryRow, PreferencesGroup, Toast};
+use std::rc::Rc;
+use zeroize::Zeroizing;
+
+pub(super) fn append_remote_store_row(state: &ToolsPageState) {
+    let state_for_open = state.clone();
+    append_action_row_with_button(
+        &state.select_page.list,
+        "Browse a remote store",
+        "Copy a password from a Git remote without cloning the store.",
+        "folder-remote-symbolic",
+        move || {
+            state_for_open.close_select_dialog();
+            state_for_open.present_remote_store_address_dialog();
+        },
+    );
+}
+
+fn remote_label_matches(label: &str, query: &str) -> bool {
+    let query = query.trim().to_lowercase();
+    query.is_empty() || label.to_lowercase().contains(&query)
+}
+
+impl ToolsPageState {
+    fn present_remote_store_address_dialog(&self) {
+        let dialog = AlertDialog::builder()
+            .heading(gettext("Browse a remote store"))
+            .body(gettext(
+                "Keycord fetches the latest commit and lists its items. Nothing is checked out.",
+            ))
+            .build();
+        let url_row = EntryRow::new();
+        url_row.set_title(&gettext("Git address"));
+        let group = PreferencesGroup::new();
+        group.add(&url_row);
+        dialog.set_extra_child(Some(&group));
+
+        let cancel = gettext("Cancel");
+        let browse = gettext("Browse");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("browse", browse.as_str())]);
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("browse"));
+        dialog.set_response_enabled("browse", false);
+        {
+            let dialog = dialog.clone();
+            url_row.connect_changed(move |row| {
+                dialog.set_response_enabled("browse", !row.text().trim().is_empty());
+            });
+        }
+
+        let state = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "browse" {
+                state.fetch_remote_store(url_row.text().trim().to_string());
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn fetch_remote_store(&self, url: String) {
+        self.overlay
+            .add_toast(Toast::new(&gettext("Fetching the remote store…")));
+        let state = self.clone();
+        let overlay = self.overlay.clone();
+        spawn_result_task(
+            move || fetch_remote_store(&url),
+            move |result| match result {
+                Ok(snapshot) => state.present_remote_store_dialog(Rc::new(snapshot)),
+                Err(err) => {
+                    log_error(format!("Failed to fetch the remote store: {err}"));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't fetch the remote store.")));
+                }
+            },
+            move || {
+                overlay.add_toast(Toast::new(&gettext("Couldn't fetch the remote store.")));
+            },
+        );
+    }
+
+    fn present_remote_store_dialog(&self, snapshot: Rc<RemoteStoreSnapshot>) {
+        let labels = snapshot.labels();
+        if labels.is_empty() {
+            self.overlay
+                .add_toast(Toast::new(&gettext("The remote store has no items.")));
+            return;
+        }
+
+        let search_entry = SearchEntry::new();
+        search_entry.set_placeholder_text(Some(&gettext("Search items")));
+
+        let list = ListBox::new();
+        list.add_css_class("boxed-list");
+        list.set_selection_mode(SelectionMode::None);
+        for label in labels {
+            let row = ActionRow::builder()
+                .title(&label)
+                .use_markup(false)
+                .activatable(true)
+                .build();
+            row.add_suffix(&Image::from_icon_name("edit-copy-symbolic"));
+            let state = self.clone();
+            let snapshot = snapshot.clone();
+            row.connect_activated(move |_| state.copy_remote_store_password(&snapshot, &label));
+            list.append(&row);
+        }
+        {
+            let search_entry = search_entry.clone();
+            list.set_filter_func(move |row| {
+                row.downcast_ref::<ActionRow>()
+                    .is_none_or(|row| remote_label_matches(&row.title(), &search_entry.text()))
+            });
+        }
+        {
+            let list = list.clone();
+            search_entry.connect_search_changed(move |_| list.invalidate_filter());
+        }
+
+        let scrolled = ScrolledWindow::builder()
+            .hscrollbar_policy(PolicyType::Never)
+            .vexpand(true)
+            .child(&list)
+            .build();
+        let content = GtkBox::new(Orientation::Vertical, 12);
+        content.set_margin_top(12);
+        content.set_margin_bottom(18);
+        content.set_margin_start(12);
+        content.set_margin_end(12);
+        content.append(&search_entry);
+        content.append(&scrolled);
+
+        let dialog = Dialog::builder()
+            .title(gettext("Remote store"))
+            .content_height(520)
+            .content_width(640)
+            .child(&dialog_content_shell(
+                "Remote store",
+                Some(snapshot.url()),
+                &content,
+            ))
+            .build();
+        dialog.present(Some(&self.window));
+        let _ = search_entry.grab_focus();
+    }
+
+    // Only the item and its recipient files leave the Git cache, and they are
+    // removed again once the password is on the clipboard.
+    fn copy_remote_store_password(&self, snapshot: &Rc<RemoteStoreSnapshot>, label: &str) {
+        let state = self.clone();
+        let overlay = self.overlay.clone();
+        let task_snapshot = snapshot.as_ref().clone();
+        let task_label = label.to_string();
+        let label = label.to_string();
+        let url = snapshot.url().to_string();
+        spawn_result_task(
+            move || task_snapshot.extract_item(&task_label),
+            move |result| match result {
+                Ok(item) => state.read_remote_store_password(Rc::new(item), label, url),
+                Err(err) => {
+                    log_error(format!(
+                        "Failed to read '{label}' from the remote store: {err}"
+                    ));
+                    state
+                        .overlay
+                        .add_toast(Toast::new(&gettext("Couldn't copy the password.")));
+                }
+            },
+            move || {
+                overlay.add_toast(Toast::new(&gettext("Couldn't copy the password.")));
+            },
+        );
+    }
+
+    fn read_remote_store_password(&self, item: Rc<RemoteStoreItem>, label: String, url: String) {
+        let state = self.clone();
+        let request = FieldValueRequest {
+            root: item.root(),
+            label: label.clone(),
+        };
+        self.unlock_tool_keys_if_needed(
+            vec![request],
+            Rc::new(move |requests| {
+                let Some(request) = requests.into_iter().next() else {
+                    return;
+                };
+                let item = item.clone();
+                let overlay = state.overlay.clone();
+                let overlay_for_disconnect = state.overlay.clone();
+                let label = label.clone();
+                let url = url.clone();
+                spawn_result_task(
+                    move || read_password_line(&request.root, &request.label).map(Zeroizing::new),
+                    move |result| {
+                        drop(item);
+                        match result {
+                            Ok(password) => {
+                                if set_clipboard_text(&password, &overlay, None) {
+                                    overlay.add_toast(Toast::new(&gettext("Copied.")));
+                                    record_activity(format!(
+                                        "Copied the password of {label} from {url}"
+                                    ));
+                                }
+                            }
+                            Err(err) => {
+                                log_error(format!(
+                                    "Failed to decrypt '{label}' from the remote store: {err}"
+                                ));
+                                overlay
+                                    .add_toast(Toast::new(&gettext("Couldn't copy the password.")));
+                            }
+                        }
+                    },
+                    move || {
+                        overlay_for_disconnect
+                            .add_toast(Toast::new(&gettext("Couldn't copy the password.")));
+                    },
+                );
+            }),
+            Rc::new(|| {}),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remote_label_matches;
+
+    #[test]
+    fn remote_store_search_ignores_case() {
+        assert!(remote_label_matches("Team/Mail", "mail"));
+        assert!(remote_label_matches("team/mail", "  "));
+        assert!(!remote_label_matches("team/mail", "bank"));
+    }
+}
@@ -0,0 +1,162 @@
+use super::ToolsPageState;
+use crate::i18n::gettext;
+use crate::logging::log_error;
+use crate::preferences::{
+    parse_settings_export, settings_export_toml, Preferences, SettingsExport,
+    SettingsImportSummary, SETTINGS_EXPORT_FILE_NAME,
+};
+use crate::support::actions::activate_widget_action;
+use crate::support::file_picker::{choose_file_bytes, choose_local_folder_path};
+use crate::support::secure_fs::write_private_file;
+use crate::support::ui::append_action_row_with_button;
+use crate::window::shortcuts::apply_configurable_shortcuts;
+use adw::prelude::*;
+use adw::{AlertDialog, ResponseAppearance, Toast};
+use std::cell::RefCell;
+use std::path::Path;
+
+pub(super) fn append_settings_transfer_rows(state: &ToolsPageState) {
+    let state_for_export = state.clone();
+    append_action_row_with_button(
+        &state.select_page.list,
+        "Export settings",
+        "Save stores, profiles, shortcuts, and templates to a file.",
+        "document-save-symbolic",
+        move || {
+            state_for_export.close_select_dialog();
+            state_for_export.export_settings();
+        },
+    );
+
+    let state_for_import = state.clone();
+    append_action_row_with_button(
+        &state.select_page.list,
+        "Import settings",
+        "Add settings from an exported file to this setup.",
+        "document-open-symbolic",
+        move || {
+            state_for_import.close_select_dialog();
+            state_for_import.import_settings();
+        },
+    );
+}
+
+impl ToolsPageState {
+    fn export_settings(&self) {
+        let overlay = self.overlay.clone();
+        choose_local_folder_path(
+            &self.window,
+            "Export settings",
+            "Export",
+            true,
+            &self.overlay,
+            move |folder| {
+                let path = Path::new(&folder).join(SETTINGS_EXPORT_FILE_NAME);
+                let result = settings_export_toml(&Preferences::new().settings_export()).and_then(
+                    |contents| write_private_file(&path, contents).map_err(|err| err.to_string()),
+                );
+                match result {
+                    Ok(()) => overlay.add_toast(Toast::new(
+                        &gettext("Saved settings to {file}.")
+                            .replace("{file}", SETTINGS_EXPORT_FILE_NAME),
+                    )),
+                    Err(err) => {
+                        log_error(format!(
+                            "Failed to export settings to '{}': {err}",
+                            path.display()
+                        ));
+                        overlay.add_toast(Toast::new(&gettext("Couldn't export settings.")));
+                    }
+                }
+            },
+        );
+    }
+
+    fn import_settings(&self) {
+        let state = self.clone();
+        choose_file_bytes(
+            &self.window,
+            "Import settings",
+            "Import",
+            &self.overlay,
+            "Failed to read the selected settings file",
+            "Couldn't read that file.",
+            move |bytes| match parse_settings_export(&String::from_utf8_lossy(&bytes)) {
+                Ok(imported) => state.confirm_settings_import(imported),
+                Err(err) => {
+                    log_error(format!("Failed to import settings: {err}"));
+                    state.overlay.add_toast(Toast::new(&gettext(err.message())));
+                }
+            },
+        );
+    }
+
+    // A settings file can add stores and change the command every read runs
+    // through, so nothing is saved before the user has seen that.
+    fn confirm_settings_import(&self, imported: SettingsExport) {
+        let summary = Preferences::new().settings_import_summary(&imported);
+        let dialog = AlertDialog::builder()
+            .heading(gettext("Import these settings?"))
+            .body(settings_import_summary_text(&summary))
+            .build();
+        let cancel = gettext("Cancel");
+        let import = gettext("Import");
+        dialog.add_responses(&[("cancel", cancel.as_str()), ("import", import.as_str())]);
+        if summary.pass_command.is_some() {
+            dialog.set_response_appearance("import", ResponseAppearance::Destructive);
+        }
+        dialog.set_close_response("cancel");
+        dialog.set_default_response(Some("cancel"));
+
+        let state = self.clone();
+        let imported = RefCell::new(Some(imported));
+        dialog.connect_response(None, move |_, response| {
+            if response != "import" {
+                return;
+            }
+            if let Some(imported) = imported.borrow_mut().take() {
+                state.apply_settings_import(imported);
+            }
+        });
+        dialog.present(Some(&self.window));
+    }
+
+    fn apply_settings_import(&self, imported: SettingsExport) {
+        if let Err(err) = Preferences::new().import_settings(imported) {
+            log_error(format!("Failed to save imported settings: {err}"));
+            self.overlay
+                .add_toast(Toast::new(&gettext("Couldn't import settings.")));
+            return;
+        }
+
+        if let Some(app) = self.window.application() {
+            apply_configurable_shortcuts(&app);
+        }
+        activate_widget_action(&self.window, "win.go-home");
+        self.overlay
+            .add_toast(Toast::new(&gettext("Imported settings.")));
+    }
+}
+
+fn settings_import_summary_text(summary: &SettingsImportSummary) -> String {
+    let mut parts = Vec::new();
+    if !summary.new_stores.is_empty() {
+        parts.push(format!(
+            "{}\n{}",
+            gettext("Adds these stores:"),
+            summary.new_stores.join("\n")
+        ));
+    }
+    if let Some(command) = summary.pass_command.as_deref() {
+        parts.push(
+            gettext(
+                "Changes the pass command to {command}. Keycord runs it to read and save items.",
+            )
+            .replace("{command}", command),
+        );
+    }
+    parts.push(gettext(
+        "Shortcuts, profiles, templates, and store settings from the file replace the ones with the same name.",
+    ));
+    parts.join("\n\n")
+}
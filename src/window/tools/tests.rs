@@ -7,7 +7,7 @@ use super::{
     password_read_tools_available_for_store_roots_with, tool_browser_flow_is_visible,
     tool_row_matches_query, FieldValueRequest,
 };
-use crate::i18n::gettext;
+use crate::i18n::ngettext;
 use crate::password::file::SearchablePassField;
 use std::collections::BTreeMap;
 
@@ -106,19 +106,19 @@ fn exact_field_queries_escape_quotes_and_backslashes() {
 fn count_subtitles_pluralize() {
     assert_eq!(
         unique_values_subtitle(1),
-        gettext("{count} unique value").replace("{count}", "1")
+        ngettext("{count} unique value", "{count} unique values", 1).replace("{count}", "1")
     );
     assert_eq!(
         unique_values_subtitle(2),
-        gettext("{count} unique values").replace("{count}", "2")
+        ngettext("{count} unique value", "{count} unique values", 2).replace("{count}", "2")
     );
     assert_eq!(
         matching_items_subtitle(1),
-        gettext("{count} matching item").replace("{count}", "1")
+        ngettext("{count} matching item", "{count} matching items", 1).replace("{count}", "1")
     );
     assert_eq!(
         matching_items_subtitle(3),
-        gettext("{count} matching items").replace("{count}", "3")
+        ngettext("{count} matching item", "{count} matching items", 3).replace("{count}", "3")
     );
 }
 
@@ -5,7 +5,7 @@ use super::{
     WEAK_PASSWORDS_LOADING_SUBTITLE, WEAK_PASSWORDS_LOADING_TITLE, WEAK_PASSWORDS_SUBTITLE,
     WEAK_PASSWORDS_TITLE,
 };
-use crate::backend::read_password_line;
+use crate::backend::{read_password_entries_in_parallel, read_password_line};
 use crate::password::model::OpenPassFile;
 use crate::password::page::open_password_entry_page;
 use crate::password::strength::weak_password_reason;
@@ -265,20 +265,21 @@ fn build_weak_password_batch(
     generation: u64,
     requests: Vec<FieldValueRequest>,
 ) -> WeakPasswordBatch {
-    let results = requests
-        .into_iter()
-        .filter_map(|request| {
-            let password = read_password_line(&request.root, &request.label).ok()?;
-            let reason = weak_password_reason(&password)?;
-            Some(WeakPasswordFinding {
-                root: request.root,
-                label: request.label.to_string(),
-                normalized_label: request.label.to_lowercase(),
-                normalized_reason: reason.to_lowercase(),
-                reason,
-            })
+    let results = read_password_entries_in_parallel(requests, |request| {
+        let password = read_password_line(&request.root, &request.label).ok()?;
+        let reason = weak_password_reason(&password)?;
+        Some(WeakPasswordFinding {
+            root: request.root,
+            label: request.label.to_string(),
+            normalized_label: request.label.to_lowercase(),
+            normalized_reason: reason.to_lowercase(),
+            reason,
         })
-        .collect();
+    })
+    .into_iter()
+    .flatten()
+    .flatten()
+    .collect();
 
     WeakPasswordBatch {
         generation,